@@ -1,3 +1,11 @@
 //! Helpful utilities for [`eyeball-im`][eyeball_im].
+//!
+//! The [`prelude`] module re-exports the commonly used items of this crate,
+//! `eyeball` and `eyeball-im`.
 
+pub mod prelude;
+#[cfg(feature = "proptest")]
+pub mod proptest;
+#[cfg(doctest)]
+mod send_sync;
 pub mod vector;
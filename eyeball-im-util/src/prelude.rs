@@ -0,0 +1,28 @@
+//! Re-exports of the most commonly used types and traits of this crate,
+//! [`eyeball`] and [`eyeball_im`], for glob-importing.
+//!
+//! This saves having to import the same handful of items from all three
+//! crates.
+//!
+//! ```
+//! use eyeball_im_util::prelude::*;
+//! use imbl::vector;
+//! use stream_assert::assert_next_eq;
+//!
+//! let mut ob = ObservableVector::from(vector![3, 1]);
+//! let (values, mut sub) = ob.subscribe().sort();
+//! assert_eq!(values, vector![1, 3]);
+//!
+//! ob.push_back(2);
+//! assert_next_eq!(sub, VectorDiff::Insert { index: 1, value: 2 });
+//!
+//! let _subscriber: Subscriber<u32> = Observable::subscribe(&Observable::new(0));
+//! ```
+
+#[doc(no_inline)]
+pub use eyeball::prelude::*;
+#[doc(no_inline)]
+pub use eyeball_im::prelude::*;
+
+#[doc(no_inline)]
+pub use crate::vector::{VectorObserverExt, VectorSubscriberExt};
@@ -0,0 +1,159 @@
+//! [`proptest`](https://docs.rs/proptest) strategies for sequences of
+//! [`VectorDiff`]s, for model-based testing of adapters.
+//!
+//! The generated diffs are always valid for the generated initial values, that
+//! is, applying them one after the other to the initial values with
+//! [`VectorDiff::apply`] never panics. This makes it possible to compare the
+//! output of an adapter with the result of applying the same transformation to
+//! the fully updated values:
+//!
+//! ```
+//! use eyeball_im::VectorDiff;
+//! use eyeball_im_util::{proptest::vector_diffs, vector::VectorObserverExt};
+//! use futures_util::{stream, FutureExt, StreamExt};
+//! use imbl::Vector;
+//! use proptest::prelude::*;
+//!
+//! proptest! {
+//!     fn sort_matches_model((initial, diffs) in vector_diffs(any::<u8>(), 8, 32)) {
+//!         let mut expected = initial.clone();
+//!         for diff in diffs.clone() {
+//!             diff.apply(&mut expected);
+//!         }
+//!         expected.sort();
+//!
+//!         let (mut values, mut sub) = (initial, stream::iter(diffs)).sort();
+//!         while let Some(Some(diff)) = sub.next().now_or_never() {
+//!             diff.apply(&mut values);
+//!         }
+//!         prop_assert_eq!(values, expected);
+//!     }
+//! }
+//!
+//! sort_matches_model();
+//! ```
+
+use std::fmt;
+
+use eyeball_im::{ResetReason, VectorDiff};
+use imbl::Vector;
+use proptest::{collection::vec, prelude::*};
+
+/// The number of different kinds of diffs that [`diff_from_op`] generates.
+const DIFF_KINDS: u8 = 12;
+
+/// A strategy for initial values along with a sequence of diffs that is valid
+/// for them.
+///
+/// The initial values contain at most `max_len` elements and at most
+/// `max_diffs` diffs are generated. All the values are generated by `value`.
+/// Every kind of [`VectorDiff`] is generated, including `Move` and `Reset`.
+pub fn vector_diffs<T, S>(
+    value: S,
+    max_len: usize,
+    max_diffs: usize,
+) -> impl Strategy<Value = (Vector<T>, Vec<VectorDiff<T>>)>
+where
+    T: Clone + fmt::Debug,
+    S: Strategy<Value = T> + Clone,
+{
+    initial_and_ops(value, max_len, max_diffs).prop_map(|(initial, ops)| {
+        let mut shadow = initial.clone();
+        let diffs = ops
+            .into_iter()
+            .filter_map(|(kind, a, b, values, _)| {
+                let diff = diff_from_op(kind, a, b, values, shadow.len())?;
+                diff.clone().apply(&mut shadow);
+                Some(diff)
+            })
+            .collect();
+
+        (initial, diffs)
+    })
+}
+
+/// A strategy for initial values along with a sequence of batches of diffs
+/// that is valid for them.
+///
+/// This works like [`vector_diffs`], but splits the diffs into non-empty
+/// batches at random positions, like a batched subscriber stream of an
+/// `ObservableVector` would after transactions or `append`s.
+pub fn vector_diff_batches<T, S>(
+    value: S,
+    max_len: usize,
+    max_diffs: usize,
+) -> impl Strategy<Value = (Vector<T>, Vec<Vec<VectorDiff<T>>>)>
+where
+    T: Clone + fmt::Debug,
+    S: Strategy<Value = T> + Clone,
+{
+    initial_and_ops(value, max_len, max_diffs).prop_map(|(initial, ops)| {
+        let mut shadow = initial.clone();
+        let mut batches: Vec<Vec<_>> = Vec::new();
+        for (kind, a, b, values, new_batch) in ops {
+            let Some(diff) = diff_from_op(kind, a, b, values, shadow.len()) else {
+                continue;
+            };
+            diff.clone().apply(&mut shadow);
+
+            match batches.last_mut() {
+                Some(batch) if !new_batch => batch.push(diff),
+                _ => batches.push(vec![diff]),
+            }
+        }
+
+        (initial, batches)
+    })
+}
+
+/// An abstract operation: the kind of diff, two random numbers to derive
+/// indices from, the values for the diff and whether it starts a new batch.
+type Op<T> = (u8, usize, usize, Vec<T>, bool);
+
+fn initial_and_ops<T, S>(
+    value: S,
+    max_len: usize,
+    max_diffs: usize,
+) -> impl Strategy<Value = (Vector<T>, Vec<Op<T>>)>
+where
+    T: Clone + fmt::Debug,
+    S: Strategy<Value = T> + Clone,
+{
+    let initial = vec(value.clone(), 0..=max_len).prop_map(Vector::from);
+    let op = (0..DIFF_KINDS, any::<usize>(), any::<usize>(), vec(value, 1..4), any::<bool>());
+    (initial, vec(op, 0..=max_diffs))
+}
+
+/// Turn an abstract operation into a diff that is valid for values of the
+/// given length.
+///
+/// Returns `None` if the kind of diff requires an element, but `len` is `0`.
+/// `values` must not be empty.
+fn diff_from_op<T: Clone>(
+    kind: u8,
+    a: usize,
+    b: usize,
+    mut values: Vec<T>,
+    len: usize,
+) -> Option<VectorDiff<T>> {
+    let diff = match kind {
+        0 => VectorDiff::Append { values: values.into_iter().collect() },
+        1 => VectorDiff::Clear,
+        2 => VectorDiff::PushFront { value: values.swap_remove(0) },
+        3 => VectorDiff::PushBack { value: values.swap_remove(0) },
+        4 => VectorDiff::Insert { index: a % (len + 1), value: values.swap_remove(0) },
+        5 => VectorDiff::Truncate { length: a % (len + 1) },
+        6 => {
+            let reason = if b % 2 == 0 { ResetReason::Update } else { ResetReason::Lagged };
+            VectorDiff::Reset { values: values.into_iter().collect(), reason }
+        }
+        _ if len == 0 => return None,
+        7 => VectorDiff::PopFront,
+        8 => VectorDiff::PopBack,
+        9 => VectorDiff::Set { index: a % len, value: values.swap_remove(0) },
+        10 => VectorDiff::Remove { index: a % len },
+        _ => VectorDiff::Move { from: a % len, to: b % len },
+    };
+
+    Some(diff)
+}
@@ -0,0 +1,29 @@
+//! Compile-fail tests checking that the adapters can't be sent to other
+//! threads if the functions they hold can't. The opposite is checked in
+//! `tests/it/send_sync.rs`.
+//!
+//! ```compile_fail,E0277
+//! use std::rc::Rc;
+//!
+//! use eyeball_im::ObservableVector;
+//! use eyeball_im_util::vector::VectorObserverExt;
+//!
+//! fn assert_send<T: Send>(_: &T) {}
+//!
+//! let ob = ObservableVector::<u32>::new();
+//! let max = Rc::new(10);
+//! assert_send(&ob.subscribe().filter(move |v| v < &max).1);
+//! ```
+//!
+//! ```compile_fail,E0277
+//! use std::rc::Rc;
+//!
+//! use eyeball_im::ObservableVector;
+//! use eyeball_im_util::vector::VectorObserverExt;
+//!
+//! fn assert_send<T: Send>(_: &T) {}
+//!
+//! let ob = ObservableVector::<u32>::new();
+//! let offset = Rc::new(1);
+//! assert_send(&ob.subscribe().sort_by_key(move |v| v + *offset).1);
+//! ```
@@ -1,26 +1,115 @@
 //! Utilities around [`ObservableVector`][eyeball_im::ObservableVector].
+//!
+//! Adapters are usually created through the methods of [`VectorObserverExt`],
+//! which take a [`VectorSubscriber`][eyeball_im::VectorSubscriber] or the
+//! output of another adapter, and thus always get initial values that match
+//! the stream of diffs. The `new` constructors of the adapter types take the
+//! initial values and the stream separately instead, and trust that the
+//! values are the state of the observed vector right before the first diff
+//! of the stream. Adapters fed inconsistent inputs produce wrong results or
+//! panic, so these constructors should only be used for streams that can't
+//! be observed through a [`VectorObserver`], e.g. ones received over the
+//! network; putting a [`Validate`] adapter first guards against malformed
+//! diffs in that case.
 
+mod aggregate;
+mod anchored_window;
+#[cfg(feature = "tokio")]
+mod batch_timeout;
+mod chain;
+mod chunk_timeout;
+mod conditional;
+mod debug;
+mod dedup;
+mod dynamic_filter;
+mod empty;
+mod extremum;
 mod filter;
+mod filter_map_cached;
+mod filter_with_index;
+mod fold;
+mod group_by;
 mod head;
+mod join_map;
+mod latest;
+mod len;
+mod map_into;
+mod map_with_index;
+#[cfg(feature = "tokio")]
+mod materialize;
+mod merge;
 mod ops;
+mod padded_head;
+mod record;
+mod reorder;
+#[cfg(feature = "futures-signals")]
+mod signal_vec;
 mod sort;
+mod sort_limit;
+mod switch;
 mod tail;
+mod take_until_closed;
+#[cfg(feature = "tokio")]
+mod throttle;
+mod tombstones;
 mod traits;
+mod try_map;
+mod unbatch;
+mod unique;
+mod validate;
+mod watch_index;
+mod window;
 
 use eyeball_im::VectorDiff;
 use futures_core::Stream;
 
 use self::ops::{VectorDiffContainerFamilyMember, VectorDiffContainerOps};
+#[cfg(feature = "futures-signals")]
+pub use self::signal_vec::{ingest_signal_vec, VectorSignalVec};
 pub use self::{
+    aggregate::{AnyAll, Mean, Sum},
+    anchored_window::{AnchorSpec, AnchoredWindow},
+    chain::Chain,
+    chunk_timeout::ChunkTimeout,
+    conditional::{FilterIf, SortIf},
+    debug::{AdapterDebug, AdapterState},
+    dedup::{Dedup, DedupByKey},
+    dynamic_filter::DynamicFilter,
+    empty::EmptyTransitions,
+    extremum::ExtremumByKey,
     filter::{Filter, FilterMap},
-    head::{EmptyLimitStream, Head},
-    sort::{Sort, SortBy, SortByKey},
+    filter_map_cached::FilterMapCached,
+    filter_with_index::FilterWithIndex,
+    fold::{Fold, FoldItems},
+    group_by::{GroupBy, Grouped},
+    head::{EmptyLimitStream, Head, Saturation},
+    join_map::JoinMap,
+    latest::{latest_of_each, LatestOfEach},
+    len::ObservedLen,
+    map_into::MapInto,
+    map_with_index::MapWithIndex,
+    merge::{merge_diff_streams, MergeDiffStreams},
+    padded_head::PaddedHead,
+    record::{DiffLog, DiffLogEntry, DiffLogHandle, Record, Replay},
+    sort::{DynamicSortBy, ResortHandle, Sort, SortBy, SortByKey},
+    sort_limit::SortLimit,
+    switch::{switch_vectors, SwitchVectors},
     tail::Tail,
+    take_until_closed::TakeUntilClosed,
+    tombstones::{Tombstoned, Tombstones},
     traits::{
         BatchedVectorSubscriber, VectorDiffContainer, VectorObserver, VectorObserverExt,
         VectorSubscriberExt,
     },
+    try_map::TryMap,
+    unbatch::Unbatch,
+    unique::UniqueByKey,
+    validate::Validate,
+    watch_index::WatchIndex,
+    window::{Window, WindowSpec, WindowSpecStream},
 };
+#[cfg(feature = "tokio")]
+pub use self::{batch_timeout::BatchTimeout, materialize::MaterializedVector, throttle::Throttle};
 
 /// Type alias for extracting the element type from a stream of
 /// [`VectorDiffContainer`]s.
@@ -56,3 +145,18 @@ type VectorDiffContainerStreamTailBuf<S> =
 /// [`VectorDiffContainer`]s' `SortBuf`.
 type VectorDiffContainerStreamSortBuf<S> =
     <<S as Stream>::Item as VectorDiffContainerOps<VectorDiffContainerStreamElement<S>>>::SortBuf;
+
+/// Get the index that the item at `index` has after a [`VectorDiff::Move`]
+/// from `from` to `to`.
+fn index_after_move(index: usize, from: usize, to: usize) -> usize {
+    if index == from {
+        return to;
+    }
+
+    let index = if index > from { index - 1 } else { index };
+    if index >= to {
+        index + 1
+    } else {
+        index
+    }
+}
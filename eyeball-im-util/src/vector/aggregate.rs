@@ -0,0 +1,488 @@
+use std::{
+    fmt,
+    future::Future,
+    ops::{Add, Sub},
+    pin::Pin,
+    task::{self, ready, Poll},
+};
+
+use eyeball::{SharedObservable, Subscriber};
+use eyeball_im::{Vector, VectorDiff};
+use futures_core::Stream;
+use pin_project_lite::pin_project;
+
+use super::{
+    fold::fold_diff_values, AdapterDebug, AdapterState, VectorDiffContainer,
+    VectorDiffContainerOps, VectorDiffContainerStreamElement,
+};
+
+pin_project! {
+    /// A future that maintains the sum of a numeric projection of the
+    /// underlying observed vector's values, which can be observed while the
+    /// future runs.
+    ///
+    /// The sum is adjusted incrementally: values that are added are added to
+    /// it and values that are removed, including the previous value of a
+    /// `VectorDiff::Set`, are subtracted from it. Subscribers are only
+    /// notified if a batch of diffs changed the sum.
+    ///
+    /// The future completes once the underlying stream has ended.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use eyeball_im::ObservableVector;
+    /// use eyeball_im_util::vector::VectorObserverExt;
+    /// use futures_util::FutureExt;
+    /// use imbl::vector;
+    ///
+    /// let mut ob = ObservableVector::<&str>::from(vector!["hello", "world"]);
+    /// let mut total_len = ob.subscribe().sum(|s| s.len());
+    /// assert_eq!(total_len.get(), 10);
+    ///
+    /// ob.set(1, "there");
+    /// ob.push_back("!");
+    /// assert_eq!((&mut total_len).now_or_never(), None);
+    /// assert_eq!(total_len.get(), 11);
+    /// ```
+    pub struct Sum<S, F, N>
+    where
+        S: Stream,
+        S::Item: VectorDiffContainer,
+    {
+        // The main stream to poll items from.
+        #[pin]
+        inner_stream: S,
+
+        // The observable holding the sum.
+        observable: SharedObservable<N>,
+
+        // The buffered vector that is updated with the main stream's items.
+        buffered_vector: Vector<VectorDiffContainerStreamElement<S>>,
+
+        // The function to project a value to the number to sum up.
+        f: F,
+    }
+}
+
+impl<S, F, N> Sum<S, F, N>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    F: Fn(&VectorDiffContainerStreamElement<S>) -> N,
+    N: Add<Output = N> + Sub<Output = N> + Default + Clone + PartialEq,
+{
+    /// Create a new `Sum` with the given initial values, stream of
+    /// `VectorDiff` updates for those values, and projection function.
+    pub fn new(
+        initial_values: Vector<VectorDiffContainerStreamElement<S>>,
+        inner_stream: S,
+        f: F,
+    ) -> Self {
+        let sum = initial_values.iter().fold(N::default(), |sum, value| sum + f(value));
+        let observable = SharedObservable::new(sum);
+        Self { inner_stream, observable, buffered_vector: initial_values, f }
+    }
+
+    /// Get the current sum.
+    pub fn get(&self) -> N {
+        self.observable.get()
+    }
+
+    /// Obtain a new subscriber to the sum.
+    pub fn subscribe(&self) -> Subscriber<N> {
+        self.observable.subscribe()
+    }
+}
+
+impl<S, F, N> Future for Sum<S, F, N>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    F: Fn(&VectorDiffContainerStreamElement<S>) -> N,
+    N: Add<Output = N> + Sub<Output = N> + Default + Clone + PartialEq,
+{
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+        loop {
+            let Some(diffs) = ready!(this.inner_stream.as_mut().poll_next(cx)) else {
+                return Poll::Ready(());
+            };
+
+            let f = &*this.f;
+            let mut sum = this.observable.get();
+            for diff in diffs.into_vec() {
+                sum = fold_diff_values(
+                    this.buffered_vector,
+                    sum,
+                    diff,
+                    |sum, value| sum + f(value),
+                    |sum, value| sum - f(value),
+                );
+            }
+            this.observable.set_if_not_eq(sum);
+        }
+    }
+}
+
+impl<S, F, N> AdapterDebug for Sum<S, F, N>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn adapter_state(&self) -> AdapterState {
+        AdapterState { buffered_len: Some(self.buffered_vector.len()), ..AdapterState::new("Sum") }
+    }
+}
+
+impl<S, F, N> fmt::Debug for Sum<S, F, N>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.adapter_state(), f)
+    }
+}
+
+pin_project! {
+    /// A future that maintains the arithmetic mean of a numeric projection of
+    /// the underlying observed vector's values, which can be observed while
+    /// the future runs.
+    ///
+    /// The mean is `None` while there are no values. Like [`Sum`], the sum the
+    /// mean is computed from is adjusted incrementally rather than computed
+    /// from scratch for every diff. To keep floating-point errors from
+    /// accumulating indefinitely, it is computed from scratch whenever all
+    /// values are replaced at once, and reset to zero whenever there are no
+    /// values left. Subscribers are only notified if a batch of diffs changed
+    /// the mean.
+    ///
+    /// The future completes once the underlying stream has ended.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use eyeball_im::ObservableVector;
+    /// use eyeball_im_util::vector::VectorObserverExt;
+    /// use futures_util::FutureExt;
+    /// use imbl::vector;
+    ///
+    /// let mut ob = ObservableVector::<u8>::from(vector![1, 2]);
+    /// let mut mean = ob.subscribe().mean(|&v| v.into());
+    /// assert_eq!(mean.get(), Some(1.5));
+    ///
+    /// ob.push_back(6);
+    /// assert_eq!((&mut mean).now_or_never(), None);
+    /// assert_eq!(mean.get(), Some(3.0));
+    ///
+    /// ob.clear();
+    /// assert_eq!((&mut mean).now_or_never(), None);
+    /// assert_eq!(mean.get(), None);
+    /// ```
+    pub struct Mean<S, F>
+    where
+        S: Stream,
+        S::Item: VectorDiffContainer,
+    {
+        // The main stream to poll items from.
+        #[pin]
+        inner_stream: S,
+
+        // The observable holding the mean.
+        observable: SharedObservable<Option<f64>>,
+
+        // The sum of the projected values.
+        sum: f64,
+
+        // The buffered vector that is updated with the main stream's items.
+        buffered_vector: Vector<VectorDiffContainerStreamElement<S>>,
+
+        // The function to project a value to the number to average.
+        f: F,
+    }
+}
+
+impl<S, F> Mean<S, F>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    F: Fn(&VectorDiffContainerStreamElement<S>) -> f64,
+{
+    /// Create a new `Mean` with the given initial values, stream of
+    /// `VectorDiff` updates for those values, and projection function.
+    pub fn new(
+        initial_values: Vector<VectorDiffContainerStreamElement<S>>,
+        inner_stream: S,
+        f: F,
+    ) -> Self {
+        let sum = initial_values.iter().map(&f).sum();
+        let observable = SharedObservable::new(mean(sum, initial_values.len()));
+        Self { inner_stream, observable, sum, buffered_vector: initial_values, f }
+    }
+
+    /// Get the current mean.
+    pub fn get(&self) -> Option<f64> {
+        self.observable.get()
+    }
+
+    /// Obtain a new subscriber to the mean.
+    pub fn subscribe(&self) -> Subscriber<Option<f64>> {
+        self.observable.subscribe()
+    }
+}
+
+impl<S, F> Future for Mean<S, F>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    F: Fn(&VectorDiffContainerStreamElement<S>) -> f64,
+{
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+        loop {
+            let Some(diffs) = ready!(this.inner_stream.as_mut().poll_next(cx)) else {
+                return Poll::Ready(());
+            };
+
+            let f = &*this.f;
+            for diff in diffs.into_vec() {
+                // Start over rather than subtracting all of the old values.
+                if matches!(diff, VectorDiff::Reset { .. }) {
+                    this.buffered_vector.clear();
+                    *this.sum = 0.0;
+                }
+
+                *this.sum = fold_diff_values(
+                    this.buffered_vector,
+                    *this.sum,
+                    diff,
+                    |sum, value| sum + f(value),
+                    |sum, value| sum - f(value),
+                );
+
+                if this.buffered_vector.is_empty() {
+                    *this.sum = 0.0;
+                }
+            }
+
+            this.observable.set_if_not_eq(mean(*this.sum, this.buffered_vector.len()));
+        }
+    }
+}
+
+impl<S, F> AdapterDebug for Mean<S, F>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn adapter_state(&self) -> AdapterState {
+        AdapterState { buffered_len: Some(self.buffered_vector.len()), ..AdapterState::new("Mean") }
+    }
+}
+
+impl<S, F> fmt::Debug for Mean<S, F>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.adapter_state(), f)
+    }
+}
+
+fn mean(sum: f64, len: usize) -> Option<f64> {
+    (len != 0).then(|| sum / len as f64)
+}
+
+pin_project! {
+    /// A future that keeps track of whether any or all of the underlying
+    /// observed vector's values match a predicate, which can be observed while
+    /// the future runs.
+    ///
+    /// Created by [`VectorObserverExt::any`] and [`VectorObserverExt::all`].
+    /// Like for [`Iterator::any`] and [`Iterator::all`], no value matches when
+    /// there are no values, while all of them do.
+    ///
+    /// Rather than applying the predicate to all values for every diff, the
+    /// number of matching values is adjusted incrementally. Subscribers are
+    /// only notified if a batch of diffs changed the result.
+    ///
+    /// The future completes once the underlying stream has ended.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use eyeball_im::ObservableVector;
+    /// use eyeball_im_util::vector::VectorObserverExt;
+    /// use futures_util::FutureExt;
+    /// use imbl::vector;
+    ///
+    /// let mut ob = ObservableVector::<bool>::from(vector![true, false]);
+    /// let mut all_selected = ob.subscribe().all(|&selected| selected);
+    /// assert!(!all_selected.get());
+    ///
+    /// ob.set(1, true);
+    /// assert_eq!((&mut all_selected).now_or_never(), None);
+    /// assert!(all_selected.get());
+    /// assert_eq!(all_selected.matching(), 2);
+    /// ```
+    ///
+    /// [`VectorObserverExt::any`]: super::VectorObserverExt::any
+    /// [`VectorObserverExt::all`]: super::VectorObserverExt::all
+    pub struct AnyAll<S, F>
+    where
+        S: Stream,
+        S::Item: VectorDiffContainer,
+    {
+        // The main stream to poll items from.
+        #[pin]
+        inner_stream: S,
+
+        // The observable holding the result.
+        observable: SharedObservable<bool>,
+
+        // The number of values matching the predicate.
+        matching: usize,
+
+        // The buffered vector that is updated with the main stream's items.
+        buffered_vector: Vector<VectorDiffContainerStreamElement<S>>,
+
+        // The predicate.
+        pred: F,
+
+        // Whether all values have to match, rather than any of them.
+        all: bool,
+    }
+}
+
+impl<S, F> AnyAll<S, F>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    F: Fn(&VectorDiffContainerStreamElement<S>) -> bool,
+{
+    /// Create a new `AnyAll` that tracks whether any value matches `pred`, with
+    /// the given initial values, stream of `VectorDiff` updates for those
+    /// values, and predicate.
+    pub fn any(
+        initial_values: Vector<VectorDiffContainerStreamElement<S>>,
+        inner_stream: S,
+        pred: F,
+    ) -> Self {
+        Self::new(initial_values, inner_stream, pred, false)
+    }
+
+    /// Create a new `AnyAll` that tracks whether all values match `pred`, with
+    /// the given initial values, stream of `VectorDiff` updates for those
+    /// values, and predicate.
+    pub fn all(
+        initial_values: Vector<VectorDiffContainerStreamElement<S>>,
+        inner_stream: S,
+        pred: F,
+    ) -> Self {
+        Self::new(initial_values, inner_stream, pred, true)
+    }
+
+    fn new(
+        initial_values: Vector<VectorDiffContainerStreamElement<S>>,
+        inner_stream: S,
+        pred: F,
+        all: bool,
+    ) -> Self {
+        let matching = initial_values.iter().filter(|value| pred(value)).count();
+        let result = any_all(matching, initial_values.len(), all);
+
+        Self {
+            inner_stream,
+            observable: SharedObservable::new(result),
+            matching,
+            buffered_vector: initial_values,
+            pred,
+            all,
+        }
+    }
+
+    /// Get whether any / all values currently match the predicate.
+    pub fn get(&self) -> bool {
+        self.observable.get()
+    }
+
+    /// Obtain a new subscriber to whether any / all values match the
+    /// predicate.
+    pub fn subscribe(&self) -> Subscriber<bool> {
+        self.observable.subscribe()
+    }
+
+    /// Get the number of values currently matching the predicate.
+    pub fn matching(&self) -> usize {
+        self.matching
+    }
+}
+
+impl<S, F> Future for AnyAll<S, F>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    F: Fn(&VectorDiffContainerStreamElement<S>) -> bool,
+{
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+        loop {
+            let Some(diffs) = ready!(this.inner_stream.as_mut().poll_next(cx)) else {
+                return Poll::Ready(());
+            };
+
+            let pred = &*this.pred;
+            for diff in diffs.into_vec() {
+                *this.matching = fold_diff_values(
+                    this.buffered_vector,
+                    *this.matching,
+                    diff,
+                    |matching, value| matching + usize::from(pred(value)),
+                    |matching, value| matching - usize::from(pred(value)),
+                );
+            }
+
+            let result = any_all(*this.matching, this.buffered_vector.len(), *this.all);
+            this.observable.set_if_not_eq(result);
+        }
+    }
+}
+
+impl<S, F> AdapterDebug for AnyAll<S, F>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn adapter_state(&self) -> AdapterState {
+        AdapterState {
+            buffered_len: Some(self.buffered_vector.len()),
+            ..AdapterState::new("AnyAll")
+        }
+    }
+}
+
+impl<S, F> fmt::Debug for AnyAll<S, F>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.adapter_state(), f)
+    }
+}
+
+fn any_all(matching: usize, len: usize, all: bool) -> bool {
+    if all {
+        matching == len
+    } else {
+        matching != 0
+    }
+}
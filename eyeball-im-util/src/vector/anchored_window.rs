@@ -0,0 +1,387 @@
+use std::{
+    cmp::Ordering,
+    fmt, mem,
+    pin::Pin,
+    task::{self, ready, Poll},
+};
+
+use eyeball_im::VectorDiff;
+use futures_core::Stream;
+use imbl::Vector;
+use pin_project_lite::pin_project;
+use smallvec::SmallVec;
+
+use super::{
+    index_after_move,
+    window::{handle_diff, update_spec, window_values},
+    AdapterDebug, AdapterState, VectorDiffContainer, VectorDiffContainerOps,
+    VectorDiffContainerStreamElement, VectorDiffContainerStreamTailBuf, WindowSpec,
+};
+
+/// The placement of an [`AnchoredWindow`] around its anchor.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct AnchorSpec<K> {
+    /// The key of the anchor item.
+    pub key: K,
+    /// The index of the anchor within the window, i.e. the number of items
+    /// before it in the window.
+    pub position: usize,
+    /// The maximum number of items in the window.
+    pub len: usize,
+}
+
+impl<K> AnchorSpec<K> {
+    /// Create a new `AnchorSpec`.
+    pub fn new(key: K, position: usize, len: usize) -> Self {
+        Self { key, position, len }
+    }
+
+    /// Create a new `AnchorSpec` for a window of `len` items with the anchor
+    /// in the middle.
+    pub fn centered(key: K, len: usize) -> Self {
+        Self { key, position: len / 2, len }
+    }
+}
+
+pin_project! {
+    /// A [`VectorDiff`] stream adapter that presents a window of the underlying
+    /// [`ObservableVector`]'s items around an anchor item, which is identified
+    /// by its key.
+    ///
+    /// This is meant for virtual scrolling: the window starts `position`
+    /// items before the anchor, as given by an [`AnchorSpec`], and moves
+    /// along with the anchor as items are inserted or removed before it, so
+    /// the anchor keeps its index within the window. Items that are inserted
+    /// or removed before the window thus don't result in any diffs. Unlike
+    /// with a [`Window`][super::Window] controlled from the outside, the
+    /// offset is adjusted in the same step as the diff that shifts the
+    /// anchor.
+    ///
+    /// If there are less than `position` items before the anchor, the window
+    /// starts at the first item. If the anchor isn't among the observed
+    /// values, or stops being so because it's removed or replaced by an item
+    /// with a different key, the window stays at its current offset until a
+    /// new `AnchorSpec` is received from the anchor stream or the observed
+    /// values are reset.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use eyeball_im::{ObservableVector, VectorDiff};
+    /// use eyeball_im_util::vector::{AnchorSpec, VectorObserverExt};
+    /// use futures_util::stream;
+    /// use imbl::vector;
+    /// use stream_assert::{assert_next_eq, assert_pending};
+    ///
+    /// let mut ob = ObservableVector::<char>::from(vector!['a', 'b', 'c', 'd', 'e']);
+    /// let (values, mut sub) = ob.subscribe().anchored_window(
+    ///     |c| *c,
+    ///     AnchorSpec::centered('c', 3),
+    ///     stream::pending(),
+    /// );
+    /// assert_eq!(values, vector!['b', 'c', 'd']);
+    ///
+    /// // Items before the window don't change it.
+    /// ob.push_front('z');
+    /// assert_pending!(sub);
+    ///
+    /// // Items after the anchor are added to the window as usual.
+    /// ob.insert(4, 'x');
+    /// assert_next_eq!(sub, VectorDiff::PopBack);
+    /// assert_next_eq!(sub, VectorDiff::Insert { index: 2, value: 'x' });
+    /// ```
+    ///
+    /// [`ObservableVector`]: eyeball_im::ObservableVector
+    #[project = AnchoredWindowProj]
+    pub struct AnchoredWindow<S, K, F, A>
+    where
+        S: Stream,
+        S::Item: VectorDiffContainer,
+    {
+        // The main stream to poll items from.
+        #[pin]
+        inner_stream: S,
+
+        // The stream to poll new anchor specs from.
+        #[pin]
+        anchor_stream: A,
+
+        // The buffered vector that is updated with the main stream's items.
+        // It's used to find the anchor and to provide items that enter the
+        // window.
+        buffered_vector: Vector<VectorDiffContainerStreamElement<S>>,
+
+        // The function to get the key of an item.
+        key_fn: F,
+
+        // The current anchor spec.
+        anchor: AnchorSpec<K>,
+
+        // The index of the anchor in `buffered_vector`, if it's in there.
+        anchor_index: Option<usize>,
+
+        // The current window.
+        spec: WindowSpec,
+
+        // Diffs that are ready to be emitted, used if the item type is just
+        // `VectorDiff<_>` (non-batched) since one update can result in
+        // multiple diffs.
+        ready_values: VectorDiffContainerStreamTailBuf<S>,
+    }
+}
+
+impl<S, K, F, A> AnchoredWindow<S, K, F, A>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    K: PartialEq,
+    F: Fn(&VectorDiffContainerStreamElement<S>) -> K,
+    A: Stream<Item = AnchorSpec<K>>,
+{
+    /// Create a new [`AnchoredWindow`] with the given (unlimited) initial
+    /// values, stream of `VectorDiff` updates for those values, key function,
+    /// and an initial anchor spec as well as a stream of new anchor specs.
+    ///
+    /// If the initial anchor isn't among the initial values, the window
+    /// starts at the first item.
+    pub fn new(
+        initial_values: Vector<VectorDiffContainerStreamElement<S>>,
+        inner_stream: S,
+        key_fn: F,
+        initial_anchor: AnchorSpec<K>,
+        anchor_stream: A,
+    ) -> (Vector<VectorDiffContainerStreamElement<S>>, Self) {
+        let anchor_index = find_anchor(&initial_values, &key_fn, &initial_anchor.key);
+        let spec = window_spec(anchor_index, &initial_anchor, WindowSpec::default());
+        let window_values = window_values(&initial_values, spec);
+        let stream = Self {
+            inner_stream,
+            anchor_stream,
+            buffered_vector: initial_values,
+            key_fn,
+            anchor: initial_anchor,
+            anchor_index,
+            spec,
+            ready_values: Default::default(),
+        };
+
+        (window_values, stream)
+    }
+
+    /// Get the index of the anchor in the observed values, or `None` if it
+    /// isn't among them.
+    pub fn anchor_index(&self) -> Option<usize> {
+        self.anchor_index
+    }
+
+    /// Get the current [`WindowSpec`].
+    pub fn spec(&self) -> WindowSpec {
+        self.spec
+    }
+}
+
+impl<S, K, F, A> Stream for AnchoredWindow<S, K, F, A>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    K: PartialEq,
+    F: Fn(&VectorDiffContainerStreamElement<S>) -> K,
+    A: Stream<Item = AnchorSpec<K>>,
+{
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        self.project().poll_next(cx)
+    }
+}
+
+impl<S, K, F, A> AdapterDebug for AnchoredWindow<S, K, F, A>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn adapter_state(&self) -> AdapterState {
+        AdapterState {
+            buffered_len: Some(self.buffered_vector.len()),
+            len: Some(self.spec.count(self.buffered_vector.len())),
+            limit: Some(self.spec.len),
+            offset: Some(self.spec.offset),
+            ready_values: S::Item::tail_buf_len(&self.ready_values),
+            ..AdapterState::new("AnchoredWindow")
+        }
+    }
+}
+
+impl<S, K, F, A> fmt::Debug for AnchoredWindow<S, K, F, A>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.adapter_state(), f)
+    }
+}
+
+impl<S, K, F, A> AnchoredWindowProj<'_, S, K, F, A>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    K: PartialEq,
+    F: Fn(&VectorDiffContainerStreamElement<S>) -> K,
+    A: Stream<Item = AnchorSpec<K>>,
+{
+    fn poll_next(&mut self, cx: &mut task::Context<'_>) -> Poll<Option<S::Item>> {
+        loop {
+            // First off, if any values are ready, return them.
+            if let Some(value) = S::Item::pop_from_tail_buf(self.ready_values) {
+                return Poll::Ready(Some(value));
+            }
+
+            // Poll a new anchor spec from `anchor_stream` before polling
+            // `inner_stream`.
+            while let Poll::Ready(Some(anchor)) = self.anchor_stream.as_mut().poll_next(cx) {
+                *self.anchor_index = find_anchor(self.buffered_vector, &*self.key_fn, &anchor.key);
+                *self.anchor = anchor;
+
+                let next_spec = window_spec(*self.anchor_index, self.anchor, *self.spec);
+                let old_spec = mem::replace(self.spec, next_spec);
+                let diffs = update_spec(self.buffered_vector, old_spec, next_spec);
+                if !diffs.is_empty() {
+                    return Poll::Ready(S::Item::extend_tail_buf(diffs, self.ready_values));
+                }
+            }
+
+            // Poll `VectorDiff`s from the `inner_stream`.
+            let Some(diffs) = ready!(self.inner_stream.as_mut().poll_next(cx)) else {
+                return Poll::Ready(None);
+            };
+
+            // Consume and apply the diffs if possible.
+            let ready = diffs.push_into_tail_buf(self.ready_values, |diff| {
+                handle_anchored_diff(
+                    diff,
+                    self.buffered_vector,
+                    &*self.key_fn,
+                    self.anchor,
+                    self.anchor_index,
+                    self.spec,
+                )
+            });
+            if let Some(diff) = ready {
+                return Poll::Ready(Some(diff));
+            }
+
+            // Else loop and poll the streams again.
+        }
+    }
+}
+
+/// Handle `diff`, updating `buffered_vector`, the index of the anchor and the
+/// window, and return the diffs of the window.
+fn handle_anchored_diff<T, K, F>(
+    diff: VectorDiff<T>,
+    buffered_vector: &mut Vector<T>,
+    key_fn: &F,
+    anchor: &AnchorSpec<K>,
+    anchor_index: &mut Option<usize>,
+    spec: &mut WindowSpec,
+) -> SmallVec<[VectorDiff<T>; 2]>
+where
+    T: Clone,
+    K: PartialEq,
+    F: Fn(&T) -> K,
+{
+    let prev_len = buffered_vector.len();
+    let shifted_offset = shifted_offset(&diff, spec.offset);
+
+    *anchor_index = anchor_index.and_then(|index| index_after(&diff, index, prev_len));
+    diff.clone().apply(buffered_vector);
+    match &diff {
+        VectorDiff::Set { index, .. }
+            if *anchor_index == Some(*index) && key_fn(&buffered_vector[*index]) != anchor.key =>
+        {
+            *anchor_index = None;
+        }
+        VectorDiff::Reset { .. } => {
+            *anchor_index = find_anchor(buffered_vector, key_fn, &anchor.key);
+        }
+        _ => {}
+    }
+
+    let next_spec = window_spec(*anchor_index, anchor, *spec);
+    let old_spec = mem::replace(spec, next_spec);
+
+    // If the change happened before the window and the window moved along
+    // with the anchor, it still contains the same items.
+    if shifted_offset == Some(next_spec.offset) {
+        return SmallVec::new();
+    }
+
+    if let VectorDiff::Reset { .. } = diff {
+        return handle_diff(diff, next_spec, prev_len, buffered_vector);
+    }
+
+    let mut res = handle_diff(diff, old_spec, prev_len, buffered_vector);
+    res.extend(update_spec(buffered_vector, old_spec, next_spec));
+    res
+}
+
+/// Find the index of the first value with the given key.
+fn find_anchor<T, K, F>(values: &Vector<T>, key_fn: &F, key: &K) -> Option<usize>
+where
+    T: Clone,
+    K: PartialEq,
+    F: Fn(&T) -> K,
+{
+    values.iter().position(|value| key_fn(value) == *key)
+}
+
+/// Get the window for the anchor at `anchor_index`, or the window at the
+/// offset of `current` if the anchor is unknown.
+fn window_spec<K>(
+    anchor_index: Option<usize>,
+    anchor: &AnchorSpec<K>,
+    current: WindowSpec,
+) -> WindowSpec {
+    let offset = anchor_index.map_or(current.offset, |index| index.saturating_sub(anchor.position));
+    WindowSpec::new(offset, anchor.len)
+}
+
+/// Get the index that the item at `offset` has after `diff`, if `diff` only
+/// inserts, removes or moves items before it.
+fn shifted_offset<T>(diff: &VectorDiff<T>, offset: usize) -> Option<usize> {
+    match diff {
+        VectorDiff::PushFront { .. } => Some(offset + 1),
+        VectorDiff::Insert { index, .. } if *index <= offset => Some(offset + 1),
+        VectorDiff::PopFront if offset > 0 => Some(offset - 1),
+        VectorDiff::Remove { index } if *index < offset => Some(offset - 1),
+        VectorDiff::Move { from, to } if *from < offset && *to < offset => Some(offset),
+        _ => None,
+    }
+}
+
+/// Get the index that the item at `index` of a vector with `len` items has
+/// after `diff`, or `None` if `diff` removes it.
+///
+/// `VectorDiff::Set` and `VectorDiff::Reset` have to be checked separately.
+fn index_after<T>(diff: &VectorDiff<T>, index: usize, len: usize) -> Option<usize> {
+    match diff {
+        VectorDiff::Append { .. } | VectorDiff::PushBack { .. } | VectorDiff::Set { .. } => {
+            Some(index)
+        }
+        VectorDiff::Clear | VectorDiff::Reset { .. } => None,
+        VectorDiff::PushFront { .. } => Some(index + 1),
+        VectorDiff::PopFront => index.checked_sub(1),
+        VectorDiff::PopBack => (index + 1 < len).then_some(index),
+        VectorDiff::Insert { index: inserted, .. } => {
+            Some(if *inserted <= index { index + 1 } else { index })
+        }
+        VectorDiff::Remove { index: removed } => match removed.cmp(&index) {
+            Ordering::Less => Some(index - 1),
+            Ordering::Equal => None,
+            Ordering::Greater => Some(index),
+        },
+        VectorDiff::Truncate { length } => (index < *length).then_some(index),
+        VectorDiff::Move { from, to } => Some(index_after_move(index, *from, *to)),
+    }
+}
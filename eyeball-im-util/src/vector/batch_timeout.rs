@@ -0,0 +1,167 @@
+use std::{
+    fmt,
+    future::Future,
+    mem,
+    pin::Pin,
+    task::{self, Poll},
+    time::Duration,
+};
+
+use eyeball_im::{Vector, VectorDiff};
+use futures_core::Stream;
+use pin_project_lite::pin_project;
+use tokio::time::{sleep, Sleep};
+
+use super::{
+    AdapterDebug, AdapterState, VectorDiffContainer, VectorDiffContainerOps,
+    VectorDiffContainerStreamElement,
+};
+
+pin_project! {
+    /// A [`VectorDiff`] stream adapter that collects the diffs of its inner
+    /// stream into batches, emitting a batch once it holds a maximum number of
+    /// diffs or once a timeout has passed since its first diff arrived.
+    ///
+    /// This lets a render loop consume at most one batch per frame, by using
+    /// the frame duration as the timeout, while still bounding the size of
+    /// the batches. Batches never contain more than `max_len` diffs; a larger
+    /// batch from the inner stream is split up. Once the inner stream has
+    /// ended, the remaining diffs are emitted right away.
+    ///
+    /// Unlike [`Throttle`][super::Throttle], this adapter doesn't emit the
+    /// first diff after a quiet period right away, and doesn't merge diffs.
+    ///
+    /// Since this adapter uses tokio's timer, it must be polled from within a
+    /// tokio runtime that has the time driver enabled.
+    pub struct BatchTimeout<S>
+    where
+        S: Stream,
+        S::Item: VectorDiffContainer,
+    {
+        // The main stream to poll items from.
+        #[pin]
+        inner_stream: S,
+
+        // The maximum number of diffs in an emitted batch.
+        max_len: usize,
+
+        // The time after which a batch is emitted even if it isn't full.
+        timeout: Duration,
+
+        // The diffs that have not been emitted yet.
+        buffer: Vec<VectorDiff<VectorDiffContainerStreamElement<S>>>,
+
+        // The timer for the oldest buffered diff, if there are any.
+        sleep: Option<Pin<Box<Sleep>>>,
+
+        // Whether `inner_stream` has ended.
+        inner_done: bool,
+    }
+}
+
+impl<S> BatchTimeout<S>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    /// Create a new `BatchTimeout` with the given initial values, stream of
+    /// `VectorDiff` updates for those values, maximum batch length and
+    /// timeout.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_len` is `0`.
+    pub fn new(
+        initial_values: Vector<VectorDiffContainerStreamElement<S>>,
+        inner_stream: S,
+        max_len: usize,
+        timeout: Duration,
+    ) -> (Vector<VectorDiffContainerStreamElement<S>>, Self) {
+        assert!(max_len > 0, "max_len must not be 0");
+
+        let stream = Self {
+            inner_stream,
+            max_len,
+            timeout,
+            buffer: Vec::new(),
+            sleep: None,
+            inner_done: false,
+        };
+        (initial_values, stream)
+    }
+}
+
+impl<S> Stream for BatchTimeout<S>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    type Item = Vec<VectorDiff<VectorDiffContainerStreamElement<S>>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        // Collect all the diffs that are ready, so the timer of a batch
+        // starts with the oldest diff in it.
+        while !*this.inner_done {
+            match this.inner_stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(diffs)) => {
+                    if this.buffer.is_empty() {
+                        *this.sleep = Some(Box::pin(sleep(*this.timeout)));
+                    }
+                    this.buffer.extend(diffs.into_vec());
+                }
+                Poll::Ready(None) => *this.inner_done = true,
+                Poll::Pending => break,
+            }
+        }
+
+        // If a full batch is ready, return it. The timer keeps running for
+        // the diffs that are left, if any.
+        if this.buffer.len() >= *this.max_len {
+            let rest = this.buffer.split_off(*this.max_len);
+            if rest.is_empty() {
+                *this.sleep = None;
+            }
+            return Poll::Ready(Some(mem::replace(this.buffer, rest)));
+        }
+
+        if this.buffer.is_empty() {
+            *this.sleep = None;
+            return if *this.inner_done { Poll::Ready(None) } else { Poll::Pending };
+        }
+
+        let timed_out =
+            this.sleep.as_mut().map_or(true, |sleep| sleep.as_mut().poll(cx).is_ready());
+        if *this.inner_done || timed_out {
+            *this.sleep = None;
+            return Poll::Ready(Some(mem::take(this.buffer)));
+        }
+
+        Poll::Pending
+    }
+}
+
+impl<S> AdapterDebug for BatchTimeout<S>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn adapter_state(&self) -> AdapterState {
+        AdapterState {
+            limit: Some(self.max_len),
+            ready_values: self.buffer.len(),
+            ..AdapterState::new("BatchTimeout")
+        }
+    }
+}
+
+impl<S> fmt::Debug for BatchTimeout<S>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.adapter_state(), f)
+    }
+}
@@ -0,0 +1,238 @@
+use std::{
+    fmt,
+    pin::Pin,
+    task::{self, Poll},
+};
+
+use eyeball_im::Vector;
+use futures_core::Stream;
+use pin_project_lite::pin_project;
+
+use super::{
+    merge::translate_diff, AdapterDebug, AdapterState, VectorDiffContainer, VectorDiffContainerOps,
+    VectorDiffContainerStreamElement, VectorDiffContainerStreamTailBuf,
+};
+
+pin_project! {
+    /// A [`VectorDiff`] stream adapter that presents two observed vectors as
+    /// a single one, the first one followed by the second one.
+    ///
+    /// Indices of the second vector's diffs are offset by the current length
+    /// of the first vector. Diffs that can't be expressed as-is in the chained
+    /// vector (e.g. a `VectorDiff::Append` to the first vector while the second
+    /// one isn't empty) are turned into equivalent `VectorDiff::Insert`s and
+    /// `VectorDiff::Remove`s.
+    ///
+    /// Unlike [`merge_diff_streams`], the two streams don't need to be of the
+    /// same type, so the vectors can be observed through different adapters.
+    /// The stream ends once both underlying streams have ended.
+    ///
+    /// # Panics
+    ///
+    /// Polling panics if one of the streams produces a diff that targets
+    /// indices outside of its own vector, like for [`MergeDiffStreams`].
+    ///
+    /// ```rust
+    /// use eyeball_im::{ObservableVector, VectorDiff};
+    /// use eyeball_im_util::vector::VectorObserverExt;
+    /// use imbl::vector;
+    /// use stream_assert::{assert_next_eq, assert_pending};
+    ///
+    /// let mut pinned = ObservableVector::<u32>::from(vector![7]);
+    /// let mut regular = ObservableVector::<u32>::from(vector![3, 1, 2]);
+    ///
+    /// let (values, mut sub) = pinned.subscribe().chain(regular.subscribe().sort());
+    /// assert_eq!(values, vector![7, 1, 2, 3]);
+    ///
+    /// regular.push_back(0);
+    /// assert_next_eq!(sub, VectorDiff::Insert { index: 1, value: 0 });
+    ///
+    /// pinned.push_back(8);
+    /// assert_next_eq!(sub, VectorDiff::Insert { index: 1, value: 8 });
+    ///
+    /// assert_pending!(sub);
+    /// ```
+    ///
+    /// [`VectorDiff`]: eyeball_im::VectorDiff
+    /// [`merge_diff_streams`]: super::merge_diff_streams
+    /// [`MergeDiffStreams`]: super::MergeDiffStreams
+    #[project = ChainProj]
+    pub struct Chain<A, B>
+    where
+        A: Stream,
+        A::Item: VectorDiffContainer,
+    {
+        // The stream of the first vector, `None` once it is exhausted.
+        #[pin]
+        first: Option<A>,
+
+        // The stream of the second vector, `None` once it is exhausted.
+        #[pin]
+        second: Option<B>,
+
+        // The current length of the first vector.
+        first_len: usize,
+
+        // The current length of the second vector.
+        second_len: usize,
+
+        // Whether to poll the second stream first, such that both streams get
+        // polled fairly.
+        second_first: bool,
+
+        // Translating a single diff can produce many diffs.
+        ready_values: VectorDiffContainerStreamTailBuf<A>,
+    }
+}
+
+impl<A, B> Chain<A, B>
+where
+    A: Stream,
+    A::Item: VectorDiffContainer,
+    B: Stream<Item = A::Item>,
+{
+    /// Create a new `Chain` with the given initial values and stream of
+    /// `VectorDiff` updates of both vectors.
+    ///
+    /// Returns the concatenated initial values as well as a stream of updates
+    /// to them.
+    pub fn new(
+        first_values: Vector<VectorDiffContainerStreamElement<A>>,
+        first: A,
+        second_values: Vector<VectorDiffContainerStreamElement<A>>,
+        second: B,
+    ) -> (Vector<VectorDiffContainerStreamElement<A>>, Self) {
+        let first_len = first_values.len();
+        let second_len = second_values.len();
+
+        let mut values = first_values;
+        values.append(second_values);
+
+        let stream = Self {
+            first: Some(first),
+            second: Some(second),
+            first_len,
+            second_len,
+            second_first: false,
+            ready_values: Default::default(),
+        };
+
+        (values, stream)
+    }
+}
+
+impl<A, B> Stream for Chain<A, B>
+where
+    A: Stream,
+    A::Item: VectorDiffContainer,
+    B: Stream<Item = A::Item>,
+{
+    type Item = A::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        self.project().poll_next(cx)
+    }
+}
+
+impl<A, B> AdapterDebug for Chain<A, B>
+where
+    A: Stream,
+    A::Item: VectorDiffContainer,
+{
+    fn adapter_state(&self) -> AdapterState {
+        AdapterState {
+            len: Some(self.first_len + self.second_len),
+            offset: Some(self.first_len),
+            ready_values: A::Item::tail_buf_len(&self.ready_values),
+            ..AdapterState::new("Chain")
+        }
+    }
+}
+
+impl<A, B> fmt::Debug for Chain<A, B>
+where
+    A: Stream,
+    A::Item: VectorDiffContainer,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.adapter_state(), f)
+    }
+}
+
+impl<A, B> ChainProj<'_, A, B>
+where
+    A: Stream,
+    A::Item: VectorDiffContainer,
+    B: Stream<Item = A::Item>,
+{
+    fn poll_next(&mut self, cx: &mut task::Context<'_>) -> Poll<Option<A::Item>> {
+        loop {
+            // First off, if any values are ready, return them.
+            if let Some(value) = A::Item::pop_from_tail_buf(self.ready_values) {
+                return Poll::Ready(Some(value));
+            }
+
+            let second_first = *self.second_first;
+            let polled = if second_first {
+                self.poll_second(cx).or_else(|| self.poll_first(cx))
+            } else {
+                self.poll_first(cx).or_else(|| self.poll_second(cx))
+            };
+
+            let Some((from_second, diffs)) = polled else {
+                return if self.first.is_none() && self.second.is_none() {
+                    Poll::Ready(None)
+                } else {
+                    Poll::Pending
+                };
+            };
+            *self.second_first = !from_second;
+
+            let ready = if from_second {
+                let offset = *self.first_len;
+                let len = &mut *self.second_len;
+                diffs.push_into_tail_buf(self.ready_values, |diff| {
+                    translate_diff(1, offset, true, len, diff).into_iter().collect()
+                })
+            } else {
+                let at_end = *self.second_len == 0;
+                let len = &mut *self.first_len;
+                diffs.push_into_tail_buf(self.ready_values, |diff| {
+                    translate_diff(0, 0, at_end, len, diff).into_iter().collect()
+                })
+            };
+
+            if let Some(diffs) = ready {
+                return Poll::Ready(Some(diffs));
+            }
+
+            // Else loop and poll the streams again.
+        }
+    }
+
+    /// Poll the first stream, if it's not exhausted yet.
+    fn poll_first(&mut self, cx: &mut task::Context<'_>) -> Option<(bool, A::Item)> {
+        let stream = self.first.as_mut().as_pin_mut()?;
+        match stream.poll_next(cx) {
+            Poll::Ready(Some(diffs)) => Some((false, diffs)),
+            Poll::Ready(None) => {
+                self.first.set(None);
+                None
+            }
+            Poll::Pending => None,
+        }
+    }
+
+    /// Poll the second stream, if it's not exhausted yet.
+    fn poll_second(&mut self, cx: &mut task::Context<'_>) -> Option<(bool, A::Item)> {
+        let stream = self.second.as_mut().as_pin_mut()?;
+        match stream.poll_next(cx) {
+            Poll::Ready(Some(diffs)) => Some((true, diffs)),
+            Poll::Ready(None) => {
+                self.second.set(None);
+                None
+            }
+            Poll::Pending => None,
+        }
+    }
+}
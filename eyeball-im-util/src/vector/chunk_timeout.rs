@@ -0,0 +1,199 @@
+use std::{
+    fmt, mem,
+    pin::Pin,
+    task::{self, Poll},
+};
+
+use eyeball_im::{Vector, VectorDiff};
+use futures_core::Stream;
+use pin_project_lite::pin_project;
+
+use super::{
+    AdapterDebug, AdapterState, VectorDiffContainer, VectorDiffContainerOps,
+    VectorDiffContainerStreamElement,
+};
+
+pin_project! {
+    /// A [`VectorDiff`] stream adapter that re-batches the diffs of its inner
+    /// stream, emitting a batch once it holds a maximum number of diffs or
+    /// once the inner stream has gone quiet.
+    ///
+    /// Quiescence is measured with a separate tick stream, for example an
+    /// interval timer: buffered diffs are emitted on a tick if no new diffs
+    /// arrived since the previous tick. Under sustained load, this results in
+    /// batches of `max_len` diffs, which amortizes fixed costs that downstream
+    /// consumers have per batch, like a layout pass. When updates are rare,
+    /// they are emitted within two ticks.
+    ///
+    /// Batches never contain more than `max_len` diffs; a larger batch from
+    /// the inner stream is split up. If the tick stream ends, buffered diffs
+    /// are emitted whenever the inner stream has no more diffs ready. Once the
+    /// inner stream has ended, the remaining diffs are emitted right away.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use eyeball::Observable;
+    /// use eyeball_im::{ObservableVector, VectorDiff};
+    /// use eyeball_im_util::vector::{VectorObserverExt, VectorSubscriberExt};
+    /// use imbl::vector;
+    /// use stream_assert::{assert_next_eq, assert_pending};
+    ///
+    /// let mut ob = ObservableVector::<u32>::new();
+    /// let mut tick = Observable::new(());
+    /// let (_, mut sub) =
+    ///     ob.subscribe().batched().chunk_timeout(3, Observable::subscribe(&tick));
+    ///
+    /// // A full batch is emitted right away.
+    /// for i in 0..4 {
+    ///     ob.push_back(i);
+    /// }
+    /// assert_next_eq!(sub, (0..3).map(|value| VectorDiff::PushBack { value }).collect::<Vec<_>>());
+    /// assert_pending!(sub);
+    ///
+    /// // The rest is emitted after a tick without new diffs.
+    /// Observable::set(&mut tick, ());
+    /// assert_pending!(sub);
+    /// Observable::set(&mut tick, ());
+    /// assert_next_eq!(sub, vec![VectorDiff::PushBack { value: 3 }]);
+    /// ```
+    pub struct ChunkTimeout<S, G>
+    where
+        S: Stream,
+        S::Item: VectorDiffContainer,
+    {
+        // The main stream to poll items from.
+        #[pin]
+        inner_stream: S,
+
+        // The stream of ticks used to detect quiescence.
+        #[pin]
+        tick_stream: G,
+
+        // The maximum number of diffs in an emitted batch.
+        max_len: usize,
+
+        // The diffs that have not been emitted yet.
+        buffer: Vec<VectorDiff<VectorDiffContainerStreamElement<S>>>,
+
+        // Whether new diffs arrived since the last tick.
+        active: bool,
+
+        // Whether `inner_stream` has ended.
+        inner_done: bool,
+
+        // Whether `tick_stream` has ended.
+        ticks_done: bool,
+    }
+}
+
+impl<S, G> ChunkTimeout<S, G>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    G: Stream,
+{
+    /// Create a new `ChunkTimeout` with the given initial values, stream of
+    /// `VectorDiff` updates for those values, maximum batch length and tick
+    /// stream.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_len` is `0`.
+    pub fn new(
+        initial_values: Vector<VectorDiffContainerStreamElement<S>>,
+        inner_stream: S,
+        max_len: usize,
+        tick_stream: G,
+    ) -> (Vector<VectorDiffContainerStreamElement<S>>, Self) {
+        assert!(max_len > 0, "max_len must not be 0");
+
+        let stream = Self {
+            inner_stream,
+            tick_stream,
+            max_len,
+            buffer: Vec::new(),
+            active: false,
+            inner_done: false,
+            ticks_done: false,
+        };
+        (initial_values, stream)
+    }
+}
+
+impl<S, G> Stream for ChunkTimeout<S, G>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    G: Stream,
+{
+    type Item = Vec<VectorDiff<VectorDiffContainerStreamElement<S>>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        loop {
+            // First off, if a full batch is ready, return it.
+            if this.buffer.len() >= *this.max_len {
+                let rest = this.buffer.split_off(*this.max_len);
+                return Poll::Ready(Some(mem::replace(this.buffer, rest)));
+            }
+
+            if !*this.inner_done {
+                match this.inner_stream.as_mut().poll_next(cx) {
+                    Poll::Ready(Some(diffs)) => {
+                        this.buffer.extend(diffs.into_vec());
+                        *this.active = true;
+                        continue;
+                    }
+                    Poll::Ready(None) => *this.inner_done = true,
+                    Poll::Pending => {}
+                }
+            }
+
+            if this.buffer.is_empty() {
+                return if *this.inner_done { Poll::Ready(None) } else { Poll::Pending };
+            }
+
+            // Once the inner stream has ended, or there are no more ticks to
+            // wait for, there is no point in waiting for more diffs.
+            if *this.inner_done || *this.ticks_done {
+                return Poll::Ready(Some(mem::take(this.buffer)));
+            }
+
+            match this.tick_stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(_)) => {
+                    if !mem::take(this.active) {
+                        return Poll::Ready(Some(mem::take(this.buffer)));
+                    }
+                }
+                Poll::Ready(None) => *this.ticks_done = true,
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+impl<S, G> AdapterDebug for ChunkTimeout<S, G>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn adapter_state(&self) -> AdapterState {
+        AdapterState {
+            limit: Some(self.max_len),
+            ready_values: self.buffer.len(),
+            ..AdapterState::new("ChunkTimeout")
+        }
+    }
+}
+
+impl<S, G> fmt::Debug for ChunkTimeout<S, G>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.adapter_state(), f)
+    }
+}
@@ -0,0 +1,461 @@
+use std::{
+    cmp::Ordering,
+    fmt, mem,
+    pin::Pin,
+    task::{self, ready, Poll},
+};
+
+use eyeball_im::{ResetReason, Vector, VectorDiff};
+use futures_core::Stream;
+use pin_project_lite::pin_project;
+
+use super::{
+    filter::FilterIndices, reorder::reorder_moves, sort::handle_diff_and_update_buffered_vector,
+    AdapterDebug, AdapterState, VectorDiffContainer, VectorDiffContainerDiff,
+    VectorDiffContainerOps, VectorDiffContainerStreamElement, VectorDiffContainerStreamTailBuf,
+};
+
+pin_project! {
+    /// A [`VectorDiff`] stream adapter that presents a filtered view of the
+    /// underlying [`ObservableVector`]s items, but only while a condition is
+    /// enabled.
+    ///
+    /// The condition is driven by a stream of `bool`s, for example a
+    /// [`Subscriber<bool>`](eyeball::Subscriber). While it is `false`, diffs
+    /// are passed through unchanged. When it switches, the minimal set of
+    /// `VectorDiff::Remove`s (when enabling) or `VectorDiff::Insert`s (when
+    /// disabling) is emitted to go from one view to the other.
+    ///
+    /// ```rust
+    /// use eyeball_im::{ObservableVector, VectorDiff};
+    /// use eyeball_im_util::vector::VectorObserverExt;
+    /// use futures_util::stream;
+    /// use imbl::vector;
+    /// use stream_assert::{assert_next_eq, assert_pending};
+    ///
+    /// let ob = ObservableVector::<u32>::from(vector![1, 2, 3, 4]);
+    /// let (values, mut sub) =
+    ///     ob.subscribe().filter_if(|v| v % 2 == 0, false, stream::iter([true]));
+    ///
+    /// // Initially disabled, so all values are there.
+    /// assert_eq!(values, vector![1, 2, 3, 4]);
+    ///
+    /// // The condition got enabled: odd values are removed.
+    /// assert_next_eq!(sub, VectorDiff::Remove { index: 2 });
+    /// assert_next_eq!(sub, VectorDiff::Remove { index: 0 });
+    /// assert_pending!(sub);
+    /// ```
+    ///
+    /// [`ObservableVector`]: eyeball_im::ObservableVector
+    #[project = FilterIfProj]
+    pub struct FilterIf<S, F, C>
+    where
+        S: Stream,
+        S::Item: VectorDiffContainer,
+    {
+        // The main stream to poll items from.
+        #[pin]
+        inner_stream: S,
+
+        // The condition stream to poll new states from.
+        #[pin]
+        condition_stream: C,
+
+        filter: F,
+
+        // The buffered vector that is updated with the main stream's items.
+        // It's used to provide the values that re-appear when the condition is
+        // disabled.
+        buffered_vector: Vector<VectorDiffContainerStreamElement<S>>,
+
+        // Which items of `buffered_vector` match the filter.
+        indices: FilterIndices,
+
+        // Whether the filter is currently applied.
+        enabled: bool,
+
+        // Toggling the condition can produce multiple items at once.
+        ready_values: VectorDiffContainerStreamTailBuf<S>,
+    }
+}
+
+impl<S, F, C> FilterIf<S, F, C>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    F: Fn(&VectorDiffContainerStreamElement<S>) -> bool,
+    C: Stream<Item = bool>,
+{
+    /// Create a new `FilterIf` with the given (unfiltered) initial values,
+    /// stream of `VectorDiff` updates for those values, filter, initial
+    /// condition state and stream of condition states.
+    pub fn new(
+        initial_values: Vector<VectorDiffContainerStreamElement<S>>,
+        inner_stream: S,
+        filter: F,
+        initial_enabled: bool,
+        condition_stream: C,
+    ) -> (Vector<VectorDiffContainerStreamElement<S>>, Self) {
+        let mut indices = FilterIndices::default();
+        let filtered = indices.append_filter(initial_values.clone(), &filter).unwrap_or_default();
+        let values = if initial_enabled { filtered } else { initial_values.clone() };
+
+        let stream = Self {
+            inner_stream,
+            condition_stream,
+            filter,
+            buffered_vector: initial_values,
+            indices,
+            enabled: initial_enabled,
+            ready_values: Default::default(),
+        };
+
+        (values, stream)
+    }
+}
+
+impl<S, F, C> Stream for FilterIf<S, F, C>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    F: Fn(&VectorDiffContainerStreamElement<S>) -> bool,
+    C: Stream<Item = bool>,
+{
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        self.project().poll_next(cx)
+    }
+}
+
+impl<S, F, C> AdapterDebug for FilterIf<S, F, C>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn adapter_state(&self) -> AdapterState {
+        let buffered_len = self.buffered_vector.len();
+        let len = if self.enabled { self.indices.filtered_indices().len() } else { buffered_len };
+        AdapterState {
+            buffered_len: Some(buffered_len),
+            len: Some(len),
+            enabled: Some(self.enabled),
+            ready_values: S::Item::tail_buf_len(&self.ready_values),
+            ..AdapterState::new("FilterIf")
+        }
+    }
+}
+
+impl<S, F, C> fmt::Debug for FilterIf<S, F, C>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.adapter_state(), f)
+    }
+}
+
+impl<S, F, C> FilterIfProj<'_, S, F, C>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    F: Fn(&VectorDiffContainerStreamElement<S>) -> bool,
+    C: Stream<Item = bool>,
+{
+    fn poll_next(&mut self, cx: &mut task::Context<'_>) -> Poll<Option<S::Item>> {
+        loop {
+            // First off, if any values are ready, return them.
+            if let Some(value) = S::Item::pop_from_tail_buf(self.ready_values) {
+                return Poll::Ready(Some(value));
+            }
+
+            // Poll the condition stream before polling `inner_stream`.
+            while let Poll::Ready(Some(enabled)) = self.condition_stream.as_mut().poll_next(cx) {
+                if let Some(diffs) = self.set_enabled(enabled) {
+                    return Poll::Ready(S::Item::extend_tail_buf(diffs, self.ready_values));
+                }
+            }
+
+            let Some(diffs) = ready!(self.inner_stream.as_mut().poll_next(cx)) else {
+                return Poll::Ready(None);
+            };
+
+            let f = &*self.filter;
+            let f2 = |value| f(&value).then_some(value);
+            let result = diffs.filter_map(|diff| {
+                diff.clone().apply(self.buffered_vector);
+
+                let indices = &mut *self.indices;
+                let filtered = match diff.clone() {
+                    VectorDiff::Append { values } => indices.handle_append_filter(values, f),
+                    VectorDiff::Clear => indices.handle_clear(),
+                    VectorDiff::PushFront { value } => indices.handle_push_front(value, &f2),
+                    VectorDiff::PushBack { value } => indices.handle_push_back(value, &f2),
+                    VectorDiff::PopFront => indices.handle_pop_front(),
+                    VectorDiff::PopBack => indices.handle_pop_back(),
+                    VectorDiff::Insert { index, value } => indices.handle_insert(index, value, &f2),
+                    VectorDiff::Set { index, value } => indices.handle_set(index, value, &f2),
+                    VectorDiff::Remove { index } => indices.handle_remove(index),
+                    VectorDiff::Truncate { length } => indices.handle_truncate(length),
+                    VectorDiff::Move { from, to } => indices.handle_move(from, to),
+                    VectorDiff::Reset { values, reason } => {
+                        indices.handle_reset_filter(values, reason, f)
+                    }
+                };
+
+                if *self.enabled {
+                    filtered
+                } else {
+                    Some(diff)
+                }
+            });
+
+            if let Some(diffs) = result {
+                return Poll::Ready(Some(diffs));
+            }
+        }
+    }
+
+    /// Switch the condition, returning the diffs that transform the current
+    /// view into the new one, if any.
+    fn set_enabled(&mut self, enabled: bool) -> Option<Vec<VectorDiffContainerDiff<S>>> {
+        if mem::replace(self.enabled, enabled) == enabled || self.buffered_vector.is_empty() {
+            return None;
+        }
+
+        let filtered_indices = self.indices.filtered_indices();
+        let non_matching = (0..self.buffered_vector.len())
+            .filter(|idx| filtered_indices.binary_search(idx).is_err());
+
+        let diffs: Vec<_> = if enabled {
+            if filtered_indices.is_empty() {
+                vec![VectorDiff::Clear]
+            } else {
+                // Remove from the back so earlier indices stay valid.
+                let mut non_matching: Vec<_> = non_matching.collect();
+                non_matching.reverse();
+                non_matching.into_iter().map(|index| VectorDiff::Remove { index }).collect()
+            }
+        } else if filtered_indices.is_empty() {
+            vec![VectorDiff::Append { values: self.buffered_vector.clone() }]
+        } else {
+            // Insert from the front, every item before `index` is present by
+            // the time it is inserted.
+            non_matching
+                .map(|index| VectorDiff::Insert {
+                    index,
+                    value: self.buffered_vector[index].clone(),
+                })
+                .collect()
+        };
+
+        (!diffs.is_empty()).then_some(diffs)
+    }
+}
+
+pin_project! {
+    /// A [`VectorDiff`] stream adapter that presents a sorted view of the
+    /// underlying [`ObservableVector`]s items, but only while a condition is
+    /// enabled.
+    ///
+    /// The condition is driven by a stream of `bool`s, for example a
+    /// [`Subscriber<bool>`](eyeball::Subscriber). While it is `false`, diffs
+    /// are passed through unchanged. When it switches, the items that are out
+    /// of place are moved to their new position; items that are already in
+    /// the right relative order are left alone. If more than half of the items
+    /// would have to be moved, a `VectorDiff::Reset` is emitted instead.
+    ///
+    /// [`ObservableVector`]: eyeball_im::ObservableVector
+    #[project = SortIfProj]
+    pub struct SortIf<S, F, C>
+    where
+        S: Stream,
+        S::Item: VectorDiffContainer,
+    {
+        // The main stream to poll items from.
+        #[pin]
+        inner_stream: S,
+
+        // The condition stream to poll new states from.
+        #[pin]
+        condition_stream: C,
+
+        compare: F,
+
+        // A replica of the observed vector, in its original order.
+        unsorted_vector: Vector<VectorDiffContainerStreamElement<S>>,
+
+        // The **sorted** buffered vector, alongside the unsorted indices.
+        sorted_vector: Vector<(usize, VectorDiffContainerStreamElement<S>)>,
+
+        // Whether the sort is currently applied.
+        enabled: bool,
+
+        // This adapter can produce many items per item of the underlying stream.
+        ready_values: VectorDiffContainerStreamTailBuf<S>,
+    }
+}
+
+impl<S, F, C> SortIf<S, F, C>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    F: Fn(&VectorDiffContainerStreamElement<S>, &VectorDiffContainerStreamElement<S>) -> Ordering,
+    C: Stream<Item = bool>,
+{
+    /// Create a new `SortIf` with the given (unsorted) initial values, stream
+    /// of `VectorDiff` updates for those values, comparison function, initial
+    /// condition state and stream of condition states.
+    pub fn new(
+        initial_values: Vector<VectorDiffContainerStreamElement<S>>,
+        inner_stream: S,
+        compare: F,
+        initial_enabled: bool,
+        condition_stream: C,
+    ) -> (Vector<VectorDiffContainerStreamElement<S>>, Self) {
+        let mut sorted_vector: Vector<_> = initial_values.iter().cloned().enumerate().collect();
+        sorted_vector.sort_by(|(_, left), (_, right)| compare(left, right));
+
+        let values = if initial_enabled {
+            sorted_vector.iter().map(|(_, value)| value.clone()).collect()
+        } else {
+            initial_values.clone()
+        };
+
+        let stream = Self {
+            inner_stream,
+            condition_stream,
+            compare,
+            unsorted_vector: initial_values,
+            sorted_vector,
+            enabled: initial_enabled,
+            ready_values: Default::default(),
+        };
+
+        (values, stream)
+    }
+}
+
+impl<S, F, C> Stream for SortIf<S, F, C>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    F: Fn(&VectorDiffContainerStreamElement<S>, &VectorDiffContainerStreamElement<S>) -> Ordering,
+    C: Stream<Item = bool>,
+{
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        self.project().poll_next(cx)
+    }
+}
+
+impl<S, F, C> AdapterDebug for SortIf<S, F, C>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn adapter_state(&self) -> AdapterState {
+        AdapterState {
+            buffered_len: Some(self.unsorted_vector.len()),
+            len: Some(self.unsorted_vector.len()),
+            enabled: Some(self.enabled),
+            ready_values: S::Item::tail_buf_len(&self.ready_values),
+            ..AdapterState::new("SortIf")
+        }
+    }
+}
+
+impl<S, F, C> fmt::Debug for SortIf<S, F, C>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.adapter_state(), f)
+    }
+}
+
+impl<S, F, C> SortIfProj<'_, S, F, C>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    F: Fn(&VectorDiffContainerStreamElement<S>, &VectorDiffContainerStreamElement<S>) -> Ordering,
+    C: Stream<Item = bool>,
+{
+    fn poll_next(&mut self, cx: &mut task::Context<'_>) -> Poll<Option<S::Item>> {
+        loop {
+            // First off, if any values are ready, return them.
+            if let Some(value) = S::Item::pop_from_tail_buf(self.ready_values) {
+                return Poll::Ready(Some(value));
+            }
+
+            // Poll the condition stream before polling `inner_stream`.
+            while let Poll::Ready(Some(enabled)) = self.condition_stream.as_mut().poll_next(cx) {
+                if let Some(diffs) = self.set_enabled(enabled) {
+                    return Poll::Ready(S::Item::extend_tail_buf(diffs, self.ready_values));
+                }
+            }
+
+            let Some(diffs) = ready!(self.inner_stream.as_mut().poll_next(cx)) else {
+                return Poll::Ready(None);
+            };
+
+            let compare = &*self.compare;
+            let ready = diffs.push_into_tail_buf(self.ready_values, |diff| {
+                diff.clone().apply(self.unsorted_vector);
+                let sorted = handle_diff_and_update_buffered_vector(
+                    diff.clone(),
+                    compare,
+                    self.sorted_vector,
+                );
+
+                if *self.enabled {
+                    sorted
+                } else {
+                    [diff].into_iter().collect()
+                }
+            });
+
+            if let Some(diff) = ready {
+                return Poll::Ready(Some(diff));
+            }
+        }
+    }
+
+    /// Switch the condition, returning the diffs that transform the current
+    /// view into the new one, if any.
+    fn set_enabled(&mut self, enabled: bool) -> Option<Vec<VectorDiffContainerDiff<S>>> {
+        if mem::replace(self.enabled, enabled) == enabled {
+            return None;
+        }
+
+        // The index of every item of the current view in the new one.
+        let new_indices = if enabled {
+            let mut new_indices = vec![0; self.sorted_vector.len()];
+            for (sorted_index, (unsorted_index, _)) in self.sorted_vector.iter().enumerate() {
+                new_indices[*unsorted_index] = sorted_index;
+            }
+            new_indices
+        } else {
+            self.sorted_vector.iter().map(|(unsorted_index, _)| *unsorted_index).collect()
+        };
+
+        let diffs: Vec<_> = match reorder_moves(&new_indices) {
+            Some(moves) => {
+                moves.into_iter().map(|(from, to)| VectorDiff::Move { from, to }).collect()
+            }
+            None => {
+                let values = if enabled {
+                    self.sorted_vector.iter().map(|(_, value)| value.clone()).collect()
+                } else {
+                    self.unsorted_vector.clone()
+                };
+                vec![VectorDiff::Reset { values, reason: ResetReason::Update }]
+            }
+        };
+
+        (!diffs.is_empty()).then_some(diffs)
+    }
+}
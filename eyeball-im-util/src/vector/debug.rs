@@ -0,0 +1,102 @@
+use std::fmt;
+
+/// Access to the internal state of a [`VectorDiff`] stream adapter, for
+/// debugging purposes.
+///
+/// This is implemented by all adapters in this crate, and their [`Debug`]
+/// implementations print the [`AdapterState`] returned by
+/// [`adapter_state`][Self::adapter_state].
+///
+/// ```rust
+/// use eyeball_im::ObservableVector;
+/// use eyeball_im_util::vector::{AdapterDebug, VectorObserverExt};
+///
+/// let mut ob = ObservableVector::<u32>::new();
+/// ob.append([1, 2, 3].into_iter().collect());
+/// let (_values, sub) = ob.subscribe().head(2);
+///
+/// let state = sub.adapter_state();
+/// assert_eq!(state.name, "Head");
+/// assert_eq!(state.buffered_len, Some(3));
+/// assert_eq!(state.len, Some(2));
+/// assert_eq!(state.limit, Some(2));
+/// assert_eq!(state.ready_values, 0);
+///
+/// assert_eq!(format!("{sub:?}"), "Head { buffered_len: 3, len: 2, limit: 2, ready_values: 0 }");
+/// ```
+///
+/// [`VectorDiff`]: eyeball_im::VectorDiff
+/// [`Debug`]: std::fmt::Debug
+pub trait AdapterDebug {
+    /// Get a snapshot of the adapter's internal state.
+    fn adapter_state(&self) -> AdapterState;
+}
+
+/// A snapshot of the internal state of a [`VectorDiff`] stream adapter.
+///
+/// Returned by [`AdapterDebug::adapter_state`]. Fields that don't apply to
+/// an adapter are `None`.
+///
+/// [`VectorDiff`]: eyeball_im::VectorDiff
+#[derive(Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct AdapterState {
+    /// The name of the adapter type, for example `"Head"`.
+    pub name: &'static str,
+
+    /// The length of the observed vector, for adapters that keep track of it.
+    pub buffered_len: Option<usize>,
+
+    /// The length of the vector presented by the adapter, for adapters that
+    /// keep track of it.
+    pub len: Option<usize>,
+
+    /// The current limit, for adapters that have one.
+    pub limit: Option<usize>,
+
+    /// The current offset, for adapters that have one.
+    pub offset: Option<usize>,
+
+    /// Whether the adapter's transformation is currently applied, for
+    /// adapters that are controlled by a condition.
+    pub enabled: Option<bool>,
+
+    /// The number of diffs that were computed, but not emitted yet.
+    pub ready_values: usize,
+}
+
+impl AdapterState {
+    pub(super) fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            buffered_len: None,
+            len: None,
+            limit: None,
+            offset: None,
+            enabled: None,
+            ready_values: 0,
+        }
+    }
+}
+
+impl fmt::Debug for AdapterState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut s = f.debug_struct(self.name);
+        if let Some(buffered_len) = &self.buffered_len {
+            s.field("buffered_len", buffered_len);
+        }
+        if let Some(len) = &self.len {
+            s.field("len", len);
+        }
+        if let Some(limit) = &self.limit {
+            s.field("limit", limit);
+        }
+        if let Some(offset) = &self.offset {
+            s.field("offset", offset);
+        }
+        if let Some(enabled) = &self.enabled {
+            s.field("enabled", enabled);
+        }
+        s.field("ready_values", &self.ready_values).finish()
+    }
+}
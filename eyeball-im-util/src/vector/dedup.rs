@@ -0,0 +1,512 @@
+use std::{
+    fmt,
+    pin::Pin,
+    task::{self, ready, Poll},
+};
+
+use eyeball_im::{Vector, VectorDiff};
+use futures_core::Stream;
+use pin_project_lite::pin_project;
+
+use super::{
+    AdapterDebug, AdapterState, VectorDiffContainer, VectorDiffContainerOps,
+    VectorDiffContainerStreamElement, VectorDiffContainerStreamTailBuf,
+};
+
+pin_project! {
+    /// A [`VectorDiff`] stream adapter that presents a view of the underlying
+    /// [`ObservableVector`]'s items where consecutive equal items are collapsed
+    /// into one, like [`Vec::dedup`].
+    ///
+    /// The first item of every run of equal items is the one that is kept.
+    /// Upstream diffs are translated into diffs of the deduplicated view,
+    /// which includes showing or hiding the item after the changed one if it
+    /// starts or stops being equal to its predecessor.
+    ///
+    /// Finding the position of an item in the deduplicated view takes time
+    /// linear in the number of items before it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use eyeball_im::{ObservableVector, VectorDiff};
+    /// use eyeball_im_util::vector::VectorObserverExt;
+    /// use imbl::vector;
+    /// use stream_assert::{assert_closed, assert_next_eq, assert_pending};
+    ///
+    /// let mut ob = ObservableVector::<char>::from(vector!['a', 'a', 'b', 'c']);
+    /// let (values, mut sub) = ob.subscribe().dedup();
+    /// assert_eq!(values, vector!['a', 'b', 'c']);
+    ///
+    /// // Inserting a duplicate doesn't change the view.
+    /// ob.insert(3, 'b');
+    /// assert_pending!(sub);
+    ///
+    /// // Replacing the item between two equal ones merges them.
+    /// ob.push_back('b');
+    /// ob.set(4, 'b');
+    /// assert_next_eq!(sub, VectorDiff::Insert { index: 3, value: 'b' });
+    /// assert_next_eq!(sub, VectorDiff::Remove { index: 2 });
+    /// assert_next_eq!(sub, VectorDiff::Remove { index: 2 });
+    ///
+    /// drop(ob);
+    /// assert_closed!(sub);
+    /// ```
+    ///
+    /// [`ObservableVector`]: eyeball_im::ObservableVector
+    pub struct Dedup<S>
+    where
+        S: Stream,
+        S::Item: VectorDiffContainer,
+    {
+        #[pin]
+        inner: DedupImpl<S>,
+    }
+}
+
+impl<S> Dedup<S>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    VectorDiffContainerStreamElement<S>: PartialEq,
+{
+    /// Create a new `Dedup` with the given initial values and stream of
+    /// `VectorDiff` updates for those values.
+    pub fn new(
+        initial_values: Vector<VectorDiffContainerStreamElement<S>>,
+        inner_stream: S,
+    ) -> (Vector<VectorDiffContainerStreamElement<S>>, Self) {
+        let (initial_deduped, inner) = DedupImpl::new(initial_values, inner_stream, &PartialEq::eq);
+        (initial_deduped, Self { inner })
+    }
+}
+
+impl<S> Stream for Dedup<S>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    VectorDiffContainerStreamElement<S>: PartialEq,
+{
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        self.project().inner.poll_next(cx, &PartialEq::eq)
+    }
+}
+
+impl<S> AdapterDebug for Dedup<S>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn adapter_state(&self) -> AdapterState {
+        self.inner.adapter_state("Dedup")
+    }
+}
+
+impl<S> fmt::Debug for Dedup<S>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.adapter_state(), f)
+    }
+}
+
+pin_project! {
+    /// A [`VectorDiff`] stream adapter that presents a view of the underlying
+    /// [`ObservableVector`]'s items where consecutive items with equal keys
+    /// are collapsed into one, like [`Vec::dedup_by_key`].
+    ///
+    /// See [`Dedup`] for more details.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use eyeball_im::{ObservableVector, VectorDiff};
+    /// use eyeball_im_util::vector::VectorObserverExt;
+    /// use imbl::vector;
+    /// use stream_assert::{assert_next_eq, assert_pending};
+    ///
+    /// let mut ob = ObservableVector::<u32>::from(vector![10, 11, 20]);
+    /// let (values, mut sub) = ob.subscribe().dedup_by_key(|v| v / 10);
+    /// assert_eq!(values, vector![10, 20]);
+    ///
+    /// // Changing the first item of a run changes the view.
+    /// ob.set(0, 12);
+    /// assert_next_eq!(sub, VectorDiff::Set { index: 0, value: 12 });
+    ///
+    /// // Changing the other ones doesn't.
+    /// ob.set(1, 13);
+    /// assert_pending!(sub);
+    /// ```
+    ///
+    /// [`ObservableVector`]: eyeball_im::ObservableVector
+    pub struct DedupByKey<S, F>
+    where
+        S: Stream,
+        S::Item: VectorDiffContainer,
+    {
+        #[pin]
+        inner: DedupImpl<S>,
+
+        // The function that computes the key of an item.
+        key_fn: F,
+    }
+}
+
+impl<S, F, K> DedupByKey<S, F>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    F: Fn(&VectorDiffContainerStreamElement<S>) -> K,
+    K: PartialEq,
+{
+    /// Create a new `DedupByKey` with the given initial values, stream of
+    /// `VectorDiff` updates for those values, and key function.
+    pub fn new(
+        initial_values: Vector<VectorDiffContainerStreamElement<S>>,
+        inner_stream: S,
+        key_fn: F,
+    ) -> (Vector<VectorDiffContainerStreamElement<S>>, Self) {
+        let (initial_deduped, inner) =
+            DedupImpl::new(initial_values, inner_stream, &|a, b| key_fn(a) == key_fn(b));
+        (initial_deduped, Self { inner, key_fn })
+    }
+}
+
+impl<S, F, K> Stream for DedupByKey<S, F>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    F: Fn(&VectorDiffContainerStreamElement<S>) -> K,
+    K: PartialEq,
+{
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        let key_fn = &*this.key_fn;
+        this.inner.poll_next(cx, &|a, b| key_fn(a) == key_fn(b))
+    }
+}
+
+impl<S, F> AdapterDebug for DedupByKey<S, F>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn adapter_state(&self) -> AdapterState {
+        self.inner.adapter_state("DedupByKey")
+    }
+}
+
+impl<S, F> fmt::Debug for DedupByKey<S, F>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.adapter_state(), f)
+    }
+}
+
+pin_project! {
+    struct DedupImpl<S>
+    where
+        S: Stream,
+        S::Item: VectorDiffContainer,
+    {
+        // The main stream to poll items from.
+        #[pin]
+        inner_stream: S,
+
+        // The buffered vector that is updated with the main stream's items,
+        // including the duplicates.
+        buffered_vector: Vector<VectorDiffContainerStreamElement<S>>,
+
+        // Diffs that are ready to be emitted, used if the item type is just
+        // `VectorDiff<_>` (non-batched) since one update can result in
+        // multiple diffs.
+        ready_values: VectorDiffContainerStreamTailBuf<S>,
+    }
+}
+
+impl<S> DedupImpl<S>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn new(
+        initial_values: Vector<VectorDiffContainerStreamElement<S>>,
+        inner_stream: S,
+        eq: &impl Fn(&VectorDiffContainerStreamElement<S>, &VectorDiffContainerStreamElement<S>) -> bool,
+    ) -> (Vector<VectorDiffContainerStreamElement<S>>, Self) {
+        let initial_deduped = deduped(&initial_values, None, eq);
+        let this = Self {
+            inner_stream,
+            buffered_vector: initial_values,
+            ready_values: Default::default(),
+        };
+
+        (initial_deduped, this)
+    }
+
+    fn adapter_state(&self, name: &'static str) -> AdapterState {
+        AdapterState {
+            buffered_len: Some(self.buffered_vector.len()),
+            ready_values: S::Item::tail_buf_len(&self.ready_values),
+            ..AdapterState::new(name)
+        }
+    }
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+        eq: &impl Fn(&VectorDiffContainerStreamElement<S>, &VectorDiffContainerStreamElement<S>) -> bool,
+    ) -> Poll<Option<S::Item>> {
+        let mut this = self.project();
+
+        loop {
+            // First off, if any values are ready, return them.
+            if let Some(value) = S::Item::pop_from_tail_buf(this.ready_values) {
+                return Poll::Ready(Some(value));
+            }
+
+            // Poll `VectorDiff`s from the `inner_stream`.
+            let Some(diffs) = ready!(this.inner_stream.as_mut().poll_next(cx)) else {
+                return Poll::Ready(None);
+            };
+
+            let mut res = Vec::new();
+            for diff in diffs.into_vec() {
+                handle_diff(diff, this.buffered_vector, eq, &mut res);
+            }
+
+            if let Some(diffs) = S::Item::extend_tail_buf(res, this.ready_values) {
+                return Poll::Ready(Some(diffs));
+            }
+
+            // Else loop and poll the streams again.
+        }
+    }
+}
+
+/// Translate `diff` to diffs of the deduplicated view of `buffered_vector`,
+/// and apply it to `buffered_vector`.
+fn handle_diff<T: Clone>(
+    diff: VectorDiff<T>,
+    buffered_vector: &mut Vector<T>,
+    eq: &impl Fn(&T, &T) -> bool,
+    res: &mut Vec<VectorDiff<T>>,
+) {
+    match diff {
+        VectorDiff::Append { values } => {
+            let deduped = deduped(&values, buffered_vector.last(), eq);
+            buffered_vector.append(values);
+            if !deduped.is_empty() {
+                res.push(VectorDiff::Append { values: deduped });
+            }
+        }
+        VectorDiff::Clear => {
+            buffered_vector.clear();
+            res.push(VectorDiff::Clear);
+        }
+        VectorDiff::PushFront { value } => {
+            insert(buffered_vector, 0, value, eq, res);
+        }
+        VectorDiff::PushBack { value } => {
+            insert(buffered_vector, buffered_vector.len(), value, eq, res);
+        }
+        VectorDiff::PopFront => {
+            remove(buffered_vector, 0, eq, res);
+        }
+        VectorDiff::PopBack => {
+            remove(buffered_vector, buffered_vector.len() - 1, eq, res);
+        }
+        VectorDiff::Insert { index, value } => {
+            insert(buffered_vector, index, value, eq, res);
+        }
+        VectorDiff::Set { index, value } => {
+            set(buffered_vector, index, value, eq, res);
+        }
+        VectorDiff::Remove { index } => {
+            remove(buffered_vector, index, eq, res);
+        }
+        VectorDiff::Truncate { length } => {
+            let old_length = row_index(buffered_vector, buffered_vector.len(), eq);
+            let new_length = row_index(buffered_vector, length, eq);
+            buffered_vector.truncate(length);
+            if new_length != old_length {
+                res.push(VectorDiff::Truncate { length: new_length });
+            }
+        }
+        VectorDiff::Move { from, to } => {
+            let value = remove(buffered_vector, from, eq, res);
+            insert(buffered_vector, to, value, eq, res);
+        }
+        VectorDiff::Reset { values, reason } => {
+            res.push(VectorDiff::Reset { values: deduped(&values, None, eq), reason });
+            *buffered_vector = values;
+        }
+    }
+}
+
+/// Insert `value` at `index` of `buffered_vector`.
+fn insert<T: Clone>(
+    buffered_vector: &mut Vector<T>,
+    index: usize,
+    value: T,
+    eq: &impl Fn(&T, &T) -> bool,
+    res: &mut Vec<VectorDiff<T>>,
+) {
+    let prev = index.checked_sub(1).map(|i| &buffered_vector[i]);
+    let next = buffered_vector.get(index);
+
+    // If the value equals the previous one, it's hidden and doesn't change
+    // whether the next one is.
+    if starts_run(prev, &value, eq) {
+        let row = row_index(buffered_vector, index, eq);
+        match next {
+            // The value takes the place of the next one as the first of its
+            // run.
+            Some(next) if starts_run(prev, next, eq) && eq(&value, next) => {
+                res.push(VectorDiff::Set { index: row, value: value.clone() });
+            }
+            // The value splits the run of the previous one.
+            Some(next) if !starts_run(prev, next, eq) && !eq(&value, next) => {
+                res.push(VectorDiff::Insert { index: row, value: value.clone() });
+                res.push(VectorDiff::Insert { index: row + 1, value: next.clone() });
+            }
+            _ => {
+                res.push(VectorDiff::Insert { index: row, value: value.clone() });
+            }
+        }
+    }
+
+    buffered_vector.insert(index, value);
+}
+
+/// Remove the value at `index` of `buffered_vector`.
+fn remove<T: Clone>(
+    buffered_vector: &mut Vector<T>,
+    index: usize,
+    eq: &impl Fn(&T, &T) -> bool,
+    res: &mut Vec<VectorDiff<T>>,
+) -> T {
+    let prev = index.checked_sub(1).map(|i| &buffered_vector[i]);
+    let value = &buffered_vector[index];
+    let next = buffered_vector.get(index + 1);
+
+    // If the value equals the previous one, it's hidden and doesn't change
+    // whether the next one is.
+    if starts_run(prev, value, eq) {
+        let row = row_index(buffered_vector, index, eq);
+        match next {
+            // The next value takes the place of this one as the first of its
+            // run.
+            Some(next) if eq(value, next) && starts_run(prev, next, eq) => {
+                res.push(VectorDiff::Set { index: row, value: next.clone() });
+            }
+            // The runs of the previous and next values are merged.
+            Some(next) if !eq(value, next) && !starts_run(prev, next, eq) => {
+                res.push(VectorDiff::Remove { index: row });
+                res.push(VectorDiff::Remove { index: row });
+            }
+            _ => {
+                res.push(VectorDiff::Remove { index: row });
+            }
+        }
+    }
+
+    buffered_vector.remove(index)
+}
+
+/// Replace the value at `index` of `buffered_vector` with `value`.
+fn set<T: Clone>(
+    buffered_vector: &mut Vector<T>,
+    index: usize,
+    value: T,
+    eq: &impl Fn(&T, &T) -> bool,
+    res: &mut Vec<VectorDiff<T>>,
+) {
+    let prev = index.checked_sub(1).map(|i| &buffered_vector[i]);
+    let old_value = &buffered_vector[index];
+    let next = buffered_vector.get(index + 1);
+
+    let was_shown = starts_run(prev, old_value, eq);
+    let is_shown = starts_run(prev, &value, eq);
+
+    // If the old and new value are both hidden, they are equal and whether the
+    // next one is shown doesn't change either.
+    if was_shown || is_shown {
+        let row = row_index(buffered_vector, index, eq);
+        let value_diff = match (was_shown, is_shown) {
+            (true, true) => Some(VectorDiff::Set { index: row, value: value.clone() }),
+            (true, false) => Some(VectorDiff::Remove { index: row }),
+            (false, true) => Some(VectorDiff::Insert { index: row, value: value.clone() }),
+            (false, false) => None,
+        };
+
+        let next_row = row + usize::from(is_shown);
+        let next_diff = next.and_then(|next| match (!eq(old_value, next), !eq(&value, next)) {
+            (true, false) => Some(VectorDiff::Remove { index: next_row }),
+            (false, true) => Some(VectorDiff::Insert { index: next_row, value: next.clone() }),
+            _ => None,
+        });
+
+        match (value_diff, next_diff) {
+            // One of the values takes the place of the other one as the first
+            // of their run.
+            (Some(VectorDiff::Remove { index }), Some(VectorDiff::Insert { value, .. }))
+            | (Some(VectorDiff::Insert { index, value }), Some(VectorDiff::Remove { .. })) => {
+                res.push(VectorDiff::Set { index, value });
+            }
+            (value_diff, next_diff) => res.extend(value_diff.into_iter().chain(next_diff)),
+        }
+    }
+
+    buffered_vector.set(index, value);
+}
+
+/// Whether `value` is shown in the deduplicated view if it comes after `prev`.
+fn starts_run<T>(prev: Option<&T>, value: &T, eq: &impl Fn(&T, &T) -> bool) -> bool {
+    match prev {
+        Some(prev) => !eq(prev, value),
+        None => true,
+    }
+}
+
+/// Get the number of values shown in the deduplicated view among the first
+/// `index` values of `values`, which is the row of the value at `index`.
+fn row_index<T>(values: &Vector<T>, index: usize, eq: &impl Fn(&T, &T) -> bool) -> usize {
+    let mut prev = None;
+    let mut count = 0;
+    for value in values.iter().take(index) {
+        if starts_run(prev, value, eq) {
+            count += 1;
+        }
+        prev = Some(value);
+    }
+
+    count
+}
+
+/// Get the values of `values` that are shown in the deduplicated view, if they
+/// come after `prev`.
+fn deduped<'a, T: Clone>(
+    values: &'a Vector<T>,
+    mut prev: Option<&'a T>,
+    eq: &impl Fn(&T, &T) -> bool,
+) -> Vector<T> {
+    let mut res = Vector::new();
+    for value in values {
+        if starts_run(prev, value, eq) {
+            res.push_back(value.clone());
+        }
+        prev = Some(value);
+    }
+
+    res
+}
@@ -0,0 +1,250 @@
+use std::{
+    fmt,
+    pin::Pin,
+    task::{self, ready, Poll},
+};
+
+use eyeball_im::{Vector, VectorDiff};
+use futures_core::Stream;
+use pin_project_lite::pin_project;
+
+use super::{
+    filter::FilterIndices, AdapterDebug, AdapterState, VectorDiffContainer, VectorDiffContainerOps,
+    VectorDiffContainerStreamElement, VectorDiffContainerStreamTailBuf,
+};
+
+pin_project! {
+    /// A [`VectorDiff`] stream adapter that presents a filtered view of the
+    /// underlying [`ObservableVector`]s items, with a filter that can be
+    /// replaced while the adapter is running.
+    ///
+    /// The filters are taken from a stream, for example a
+    /// [`Subscriber`](eyeball::Subscriber) mapped to a filter closure. Each
+    /// time it yields a new filter, all values are filtered again and the
+    /// minimal set of `VectorDiff::Remove`s and `VectorDiff::Insert`s is
+    /// emitted to go from the previous view to the new one; values that match
+    /// both filters stay where they are. If several filters are ready at once,
+    /// only the latest one is applied. Yielding the same filter again can be
+    /// used to re-evaluate it, e.g. if it depends on state outside of the
+    /// values.
+    ///
+    /// Until the filter stream yields a first filter, no values match, unless
+    /// an initial filter was given to [`dynamic_filter_with_initial_value`].
+    ///
+    /// ```rust
+    /// use eyeball::Observable;
+    /// use eyeball_im::{ObservableVector, VectorDiff};
+    /// use eyeball_im_util::vector::VectorObserverExt;
+    /// use imbl::vector;
+    /// use stream_assert::{assert_next_eq, assert_pending};
+    ///
+    /// fn contains(query: String) -> impl Fn(&&str) -> bool {
+    ///     move |value| value.contains(&query)
+    /// }
+    ///
+    /// let ob = ObservableVector::<&str>::from(vector!["apple", "banana", "cherry"]);
+    /// let mut query = Observable::new("an".to_owned());
+    /// let filters = Observable::subscribe(&query).map(|query| contains(query.clone()));
+    /// let (values, mut sub) =
+    ///     ob.subscribe().dynamic_filter_with_initial_value(contains("an".to_owned()), filters);
+    /// assert_eq!(values, vector!["banana"]);
+    ///
+    /// Observable::set(&mut query, "a".to_owned());
+    /// assert_next_eq!(sub, VectorDiff::Insert { index: 0, value: "apple" });
+    /// assert_pending!(sub);
+    ///
+    /// Observable::set(&mut query, "rr".to_owned());
+    /// assert_next_eq!(sub, VectorDiff::Clear);
+    /// assert_next_eq!(sub, VectorDiff::Append { values: vector!["cherry"] });
+    /// ```
+    ///
+    /// [`ObservableVector`]: eyeball_im::ObservableVector
+    /// [`dynamic_filter_with_initial_value`]: super::VectorObserverExt::dynamic_filter_with_initial_value
+    #[project = DynamicFilterProj]
+    pub struct DynamicFilter<S, F, P>
+    where
+        S: Stream,
+        S::Item: VectorDiffContainer,
+    {
+        // The main stream to poll items from.
+        #[pin]
+        inner_stream: S,
+
+        // The filter stream to poll new filters from.
+        #[pin]
+        filter_stream: P,
+
+        // The current filter, `None` until the filter stream yielded one.
+        filter: Option<F>,
+
+        // The buffered vector that is updated with the main stream's items.
+        // It's used to filter all values again when the filter changes.
+        buffered_vector: Vector<VectorDiffContainerStreamElement<S>>,
+
+        // Which items of `buffered_vector` match the filter.
+        indices: FilterIndices,
+
+        // Changing the filter can produce multiple items at once.
+        ready_values: VectorDiffContainerStreamTailBuf<S>,
+    }
+}
+
+impl<S, F, P> DynamicFilter<S, F, P>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    F: Fn(&VectorDiffContainerStreamElement<S>) -> bool,
+    P: Stream<Item = F>,
+{
+    /// Create a new `DynamicFilter` with the given (unfiltered) initial values,
+    /// stream of `VectorDiff` updates for those values, and stream of filters.
+    ///
+    /// No values match until the filter stream yields a first filter, so the
+    /// initial filtered values would always be empty and aren't returned.
+    pub fn new(
+        initial_values: Vector<VectorDiffContainerStreamElement<S>>,
+        inner_stream: S,
+        filter_stream: P,
+    ) -> Self {
+        let mut indices = FilterIndices::default();
+        indices.refilter(&initial_values, &|_: &_| false);
+
+        Self {
+            inner_stream,
+            filter_stream,
+            filter: None,
+            buffered_vector: initial_values,
+            indices,
+            ready_values: Default::default(),
+        }
+    }
+
+    /// Create a new `DynamicFilter` with the given (unfiltered) initial values,
+    /// stream of `VectorDiff` updates for those values, initial filter and
+    /// stream of filters.
+    pub fn with_initial_filter(
+        initial_values: Vector<VectorDiffContainerStreamElement<S>>,
+        inner_stream: S,
+        initial_filter: F,
+        filter_stream: P,
+    ) -> (Vector<VectorDiffContainerStreamElement<S>>, Self) {
+        let mut indices = FilterIndices::default();
+        let values =
+            indices.append_filter(initial_values.clone(), &initial_filter).unwrap_or_default();
+
+        let stream = Self {
+            inner_stream,
+            filter_stream,
+            filter: Some(initial_filter),
+            buffered_vector: initial_values,
+            indices,
+            ready_values: Default::default(),
+        };
+
+        (values, stream)
+    }
+}
+
+impl<S, F, P> Stream for DynamicFilter<S, F, P>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    F: Fn(&VectorDiffContainerStreamElement<S>) -> bool,
+    P: Stream<Item = F>,
+{
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        self.project().poll_next(cx)
+    }
+}
+
+impl<S, F, P> AdapterDebug for DynamicFilter<S, F, P>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn adapter_state(&self) -> AdapterState {
+        AdapterState {
+            buffered_len: Some(self.buffered_vector.len()),
+            len: Some(self.indices.filtered_indices().len()),
+            ready_values: S::Item::tail_buf_len(&self.ready_values),
+            ..AdapterState::new("DynamicFilter")
+        }
+    }
+}
+
+impl<S, F, P> fmt::Debug for DynamicFilter<S, F, P>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.adapter_state(), f)
+    }
+}
+
+impl<S, F, P> DynamicFilterProj<'_, S, F, P>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    F: Fn(&VectorDiffContainerStreamElement<S>) -> bool,
+    P: Stream<Item = F>,
+{
+    fn poll_next(&mut self, cx: &mut task::Context<'_>) -> Poll<Option<S::Item>> {
+        loop {
+            // First off, if any values are ready, return them.
+            if let Some(value) = S::Item::pop_from_tail_buf(self.ready_values) {
+                return Poll::Ready(Some(value));
+            }
+
+            // Poll the filter stream before polling `inner_stream`, only
+            // keeping the latest filter.
+            let mut new_filter = None;
+            while let Poll::Ready(Some(filter)) = self.filter_stream.as_mut().poll_next(cx) {
+                new_filter = Some(filter);
+            }
+
+            if let Some(filter) = new_filter {
+                let diffs = self.indices.refilter(self.buffered_vector, &filter);
+                *self.filter = Some(filter);
+                if !diffs.is_empty() {
+                    return Poll::Ready(S::Item::extend_tail_buf(diffs, self.ready_values));
+                }
+            }
+
+            let Some(diffs) = ready!(self.inner_stream.as_mut().poll_next(cx)) else {
+                return Poll::Ready(None);
+            };
+
+            let filter = self.filter.as_ref();
+            let f = |value: &_| filter.map_or(false, |f| f(value));
+            let f2 = |value| f(&value).then_some(value);
+            let result = diffs.filter_map(|diff| {
+                diff.clone().apply(self.buffered_vector);
+
+                let indices = &mut *self.indices;
+                match diff {
+                    VectorDiff::Append { values } => indices.handle_append_filter(values, &f),
+                    VectorDiff::Clear => indices.handle_clear(),
+                    VectorDiff::PushFront { value } => indices.handle_push_front(value, &f2),
+                    VectorDiff::PushBack { value } => indices.handle_push_back(value, &f2),
+                    VectorDiff::PopFront => indices.handle_pop_front(),
+                    VectorDiff::PopBack => indices.handle_pop_back(),
+                    VectorDiff::Insert { index, value } => indices.handle_insert(index, value, &f2),
+                    VectorDiff::Set { index, value } => indices.handle_set(index, value, &f2),
+                    VectorDiff::Remove { index } => indices.handle_remove(index),
+                    VectorDiff::Truncate { length } => indices.handle_truncate(length),
+                    VectorDiff::Move { from, to } => indices.handle_move(from, to),
+                    VectorDiff::Reset { values, reason } => {
+                        indices.handle_reset_filter(values, reason, &f)
+                    }
+                }
+            });
+
+            if let Some(diffs) = result {
+                return Poll::Ready(Some(diffs));
+            }
+        }
+    }
+}
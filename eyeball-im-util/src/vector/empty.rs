@@ -0,0 +1,133 @@
+use std::{
+    fmt,
+    pin::Pin,
+    task::{self, ready, Poll},
+};
+
+use eyeball::{SharedObservable, Subscriber};
+use eyeball_im::{Vector, VectorDiff};
+use futures_core::Stream;
+use pin_project_lite::pin_project;
+
+use super::{
+    AdapterDebug, AdapterState, VectorDiffContainer, VectorDiffContainerOps,
+    VectorDiffContainerStreamElement,
+};
+
+pin_project! {
+    /// A [`VectorDiff`] stream adapter that passes all diffs through
+    /// unchanged, while keeping track of whether the presented view is empty.
+    ///
+    /// Use [`subscribe_is_empty`](Self::subscribe_is_empty) to get notified
+    /// only when the view transitions between empty and non-empty. Since this
+    /// adapter sits inside the adapter chain, it reflects the view presented
+    /// by the adapters before it, e.g. a filtered view.
+    ///
+    /// ```rust
+    /// use eyeball_im::{ObservableVector, VectorDiff};
+    /// use eyeball_im_util::vector::VectorObserverExt;
+    /// use stream_assert::{assert_next_eq, assert_pending};
+    ///
+    /// let mut ob = ObservableVector::<u32>::new();
+    /// let (_, mut sub) = ob.subscribe().filter(|v| v % 2 == 0).empty_transitions();
+    /// let mut is_empty = sub.subscribe_is_empty();
+    /// assert!(sub.is_empty());
+    ///
+    /// // Filtered out, the view stays empty.
+    /// ob.push_back(1);
+    /// assert_pending!(sub);
+    /// assert_pending!(is_empty);
+    ///
+    /// ob.push_back(2);
+    /// assert_next_eq!(sub, VectorDiff::PushBack { value: 2 });
+    /// assert_next_eq!(is_empty, false);
+    ///
+    /// // No transition, no update.
+    /// ob.push_back(4);
+    /// assert_next_eq!(sub, VectorDiff::PushBack { value: 4 });
+    /// assert_pending!(is_empty);
+    /// ```
+    pub struct EmptyTransitions<S> {
+        #[pin]
+        inner: S,
+        len: usize,
+        is_empty: SharedObservable<bool>,
+    }
+}
+
+impl<S> EmptyTransitions<S>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    /// Create a new `EmptyTransitions` with the given initial values and stream
+    /// of `VectorDiff` updates for those values.
+    pub fn new(
+        initial_values: Vector<VectorDiffContainerStreamElement<S>>,
+        inner: S,
+    ) -> (Vector<VectorDiffContainerStreamElement<S>>, Self) {
+        let len = initial_values.len();
+        let is_empty = SharedObservable::new(len == 0);
+        (initial_values, Self { inner, len, is_empty })
+    }
+
+    /// Whether the view is currently empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Get a [`Subscriber`] that yields a new value every time the view
+    /// transitions between empty (`true`) and non-empty (`false`).
+    ///
+    /// The state is updated whenever this stream is polled.
+    pub fn subscribe_is_empty(&self) -> Subscriber<bool> {
+        self.is_empty.subscribe()
+    }
+}
+
+impl<S> Stream for EmptyTransitions<S>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        let Some(diffs) = ready!(this.inner.poll_next(cx)) else {
+            return Poll::Ready(None);
+        };
+
+        diffs.for_each_diff(|diff| *this.len = len_after(diff, *this.len));
+        this.is_empty.set_if_not_eq(*this.len == 0);
+
+        Poll::Ready(Some(diffs))
+    }
+}
+
+impl<S> AdapterDebug for EmptyTransitions<S> {
+    fn adapter_state(&self) -> AdapterState {
+        AdapterState { len: Some(self.len), ..AdapterState::new("EmptyTransitions") }
+    }
+}
+
+impl<S> fmt::Debug for EmptyTransitions<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.adapter_state(), f)
+    }
+}
+
+/// Compute the length of a vector of length `len` after applying `diff`.
+pub(super) fn len_after<T>(diff: &VectorDiff<T>, len: usize) -> usize {
+    match diff {
+        VectorDiff::Append { values } => len + values.len(),
+        VectorDiff::Clear => 0,
+        VectorDiff::PushFront { .. } | VectorDiff::PushBack { .. } | VectorDiff::Insert { .. } => {
+            len + 1
+        }
+        VectorDiff::PopFront | VectorDiff::PopBack | VectorDiff::Remove { .. } => len - 1,
+        VectorDiff::Set { .. } | VectorDiff::Move { .. } => len,
+        VectorDiff::Truncate { length } => *length,
+        VectorDiff::Reset { values, .. } => values.len(),
+    }
+}
@@ -0,0 +1,377 @@
+use std::{
+    cmp::Ordering,
+    fmt,
+    future::Future,
+    pin::Pin,
+    task::{self, ready, Poll},
+};
+
+use eyeball::{SharedObservable, Subscriber};
+use eyeball_im::{Vector, VectorDiff};
+use futures_core::Stream;
+use pin_project_lite::pin_project;
+
+use super::{
+    index_after_move, AdapterDebug, AdapterState, VectorDiffContainer, VectorDiffContainerOps,
+    VectorDiffContainerStreamElement,
+};
+
+pin_project! {
+    /// A future that keeps track of the value of the underlying observed
+    /// vector with the smallest or largest key, which can be observed while
+    /// the future runs.
+    ///
+    /// Created by [`VectorObserverExt::min_by_key`] and
+    /// [`VectorObserverExt::max_by_key`]. The extremum is `None` while there
+    /// are no values. If several values have the same smallest / largest key,
+    /// the first one is the extremum.
+    ///
+    /// New values are only compared against the current extremum. All values
+    /// are only looked at again if the current extremum is removed or replaced
+    /// by a value with a worse key, once per batch of diffs. Subscribers are
+    /// only notified if a batch of diffs changed the extremum.
+    ///
+    /// The future completes once the underlying stream has ended.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use eyeball_im::ObservableVector;
+    /// use eyeball_im_util::vector::VectorObserverExt;
+    /// use futures_util::FutureExt;
+    /// use imbl::vector;
+    ///
+    /// let mut ob = ObservableVector::<&str>::from(vector!["hi", "hello"]);
+    /// let mut longest = ob.subscribe().max_by_key(|s| s.len());
+    /// assert_eq!(longest.get(), Some("hello"));
+    ///
+    /// ob.push_back("hey");
+    /// ob.remove(1);
+    /// assert_eq!((&mut longest).now_or_never(), None);
+    /// assert_eq!(longest.get(), Some("hey"));
+    /// ```
+    ///
+    /// [`VectorObserverExt::min_by_key`]: super::VectorObserverExt::min_by_key
+    /// [`VectorObserverExt::max_by_key`]: super::VectorObserverExt::max_by_key
+    pub struct ExtremumByKey<S, F>
+    where
+        S: Stream,
+        S::Item: VectorDiffContainer,
+    {
+        // The main stream to poll items from.
+        #[pin]
+        inner_stream: S,
+
+        // The observable holding the extremum.
+        observable: SharedObservable<Option<VectorDiffContainerStreamElement<S>>>,
+
+        // The buffered vector that is updated with the main stream's items.
+        buffered_vector: Vector<VectorDiffContainerStreamElement<S>>,
+
+        // The function to convert an item to a key used for comparison.
+        key_fn: F,
+
+        // `Ordering::Less` to track the minimum, `Ordering::Greater` to track
+        // the maximum.
+        wanted: Ordering,
+
+        // The index of the extremum in `buffered_vector`.
+        extremum_index: Option<usize>,
+    }
+}
+
+impl<S, F, K> ExtremumByKey<S, F>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    F: Fn(&VectorDiffContainerStreamElement<S>) -> K,
+    K: Ord,
+{
+    /// Create a new `ExtremumByKey` that tracks the value with the smallest
+    /// key, with the given initial values, stream of `VectorDiff` updates for
+    /// those values, and key function.
+    pub fn min(
+        initial_values: Vector<VectorDiffContainerStreamElement<S>>,
+        inner_stream: S,
+        key_fn: F,
+    ) -> Self {
+        Self::new(initial_values, inner_stream, key_fn, Ordering::Less)
+    }
+
+    /// Create a new `ExtremumByKey` that tracks the value with the largest
+    /// key, with the given initial values, stream of `VectorDiff` updates for
+    /// those values, and key function.
+    pub fn max(
+        initial_values: Vector<VectorDiffContainerStreamElement<S>>,
+        inner_stream: S,
+        key_fn: F,
+    ) -> Self {
+        Self::new(initial_values, inner_stream, key_fn, Ordering::Greater)
+    }
+
+    fn new(
+        initial_values: Vector<VectorDiffContainerStreamElement<S>>,
+        inner_stream: S,
+        key_fn: F,
+        wanted: Ordering,
+    ) -> Self {
+        let extremum_index = find_extremum(&initial_values, &key_fn, wanted);
+        let extremum = extremum_index.map(|index| initial_values[index].clone());
+
+        Self {
+            inner_stream,
+            observable: SharedObservable::new(extremum),
+            buffered_vector: initial_values,
+            key_fn,
+            wanted,
+            extremum_index,
+        }
+    }
+
+    /// Get the current extremum.
+    pub fn get(&self) -> Option<VectorDiffContainerStreamElement<S>> {
+        self.observable.get()
+    }
+
+    /// Obtain a new subscriber to the extremum.
+    pub fn subscribe(&self) -> Subscriber<Option<VectorDiffContainerStreamElement<S>>> {
+        self.observable.subscribe()
+    }
+}
+
+impl<S, F, K> Future for ExtremumByKey<S, F>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    F: Fn(&VectorDiffContainerStreamElement<S>) -> K,
+    K: Ord,
+{
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+        loop {
+            let Some(diffs) = ready!(this.inner_stream.as_mut().poll_next(cx)) else {
+                return Poll::Ready(());
+            };
+
+            let mut tracker = Tracker {
+                buffered_vector: this.buffered_vector,
+                key_fn: &*this.key_fn,
+                wanted: *this.wanted,
+                extremum_index: *this.extremum_index,
+                changed: false,
+                needs_search: false,
+            };
+            for diff in diffs.into_vec() {
+                tracker.handle_diff(diff);
+            }
+
+            if tracker.needs_search {
+                let index = find_extremum(tracker.buffered_vector, tracker.key_fn, tracker.wanted);
+                tracker.changed |= index != tracker.extremum_index;
+                tracker.extremum_index = index;
+            }
+
+            *this.extremum_index = tracker.extremum_index;
+            if tracker.changed {
+                let extremum =
+                    tracker.extremum_index.map(|index| this.buffered_vector[index].clone());
+                this.observable.set(extremum);
+            }
+        }
+    }
+}
+
+impl<S, F> AdapterDebug for ExtremumByKey<S, F>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn adapter_state(&self) -> AdapterState {
+        AdapterState {
+            buffered_len: Some(self.buffered_vector.len()),
+            ..AdapterState::new("ExtremumByKey")
+        }
+    }
+}
+
+impl<S, F> fmt::Debug for ExtremumByKey<S, F>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.adapter_state(), f)
+    }
+}
+
+/// The state of an [`ExtremumByKey`] while it handles a batch of diffs.
+struct Tracker<'a, T: Clone, F> {
+    buffered_vector: &'a mut Vector<T>,
+    key_fn: &'a F,
+    wanted: Ordering,
+
+    // The index of the extremum, or if `needs_search` is set, that of the
+    // previous extremum (`None` if it was removed).
+    extremum_index: Option<usize>,
+
+    // Whether the extremum is a different value than before the batch.
+    changed: bool,
+
+    // Whether the extremum has to be searched for among all values, because
+    // the previous one was removed, replaced by a worse value or moved.
+    needs_search: bool,
+}
+
+impl<T, F, K> Tracker<'_, T, F>
+where
+    T: Clone,
+    F: Fn(&T) -> K,
+    K: Ord,
+{
+    fn handle_diff(&mut self, diff: VectorDiff<T>) {
+        let len = self.buffered_vector.len();
+
+        // Look at what is removed or moved before applying the diff.
+        match &diff {
+            VectorDiff::Clear => {
+                self.remove_all();
+            }
+            VectorDiff::PushFront { .. } => {
+                self.shift_after_insert(0);
+            }
+            VectorDiff::Insert { index, .. } => {
+                self.shift_after_insert(*index);
+            }
+            VectorDiff::PopFront => {
+                self.remove(0);
+            }
+            VectorDiff::PopBack => {
+                self.remove(len - 1);
+            }
+            VectorDiff::Remove { index } => {
+                self.remove(*index);
+            }
+            VectorDiff::Set { index, value } if self.extremum_index == Some(*index) => {
+                // If the new value's key is worse, another value may be the
+                // extremum now.
+                let ordering = self.compare_to_extremum(value);
+                if ordering != self.wanted && ordering != Ordering::Equal {
+                    self.needs_search = true;
+                }
+                self.changed = true;
+            }
+            VectorDiff::Truncate { length } => {
+                if self.extremum_index.map_or(false, |index| index >= *length) {
+                    self.extremum_index = None;
+                    self.needs_search = true;
+                    self.changed = true;
+                }
+            }
+            VectorDiff::Move { from, to } => {
+                if let Some(index) = self.extremum_index {
+                    // The order of the extremum and values with the same key
+                    // may have changed.
+                    let ordering = self.compare_to_extremum(&self.buffered_vector[*from]);
+                    if *from == index || ordering == Ordering::Equal {
+                        self.needs_search = true;
+                    }
+                    self.extremum_index = Some(index_after_move(index, *from, *to));
+                }
+            }
+            VectorDiff::Reset { .. } => {
+                self.remove_all();
+                self.needs_search = true;
+            }
+            VectorDiff::Append { .. } | VectorDiff::PushBack { .. } | VectorDiff::Set { .. } => {}
+        }
+
+        let added = match &diff {
+            VectorDiff::Append { values } => len..len + values.len(),
+            VectorDiff::PushFront { .. } => 0..1,
+            VectorDiff::PushBack { .. } => len..len + 1,
+            VectorDiff::Insert { index, .. } => *index..*index + 1,
+            VectorDiff::Set { index, .. } if self.extremum_index != Some(*index) => {
+                *index..*index + 1
+            }
+            _ => 0..0,
+        };
+
+        diff.apply(self.buffered_vector);
+
+        // Then compare what was added against the extremum.
+        for index in added {
+            self.consider(index);
+        }
+    }
+
+    /// Compare `value`'s key to the current extremum's key.
+    fn compare_to_extremum(&self, value: &T) -> Ordering {
+        match self.extremum_index {
+            Some(index) => (self.key_fn)(value).cmp(&(self.key_fn)(&self.buffered_vector[index])),
+            None => self.wanted,
+        }
+    }
+
+    /// Make the value at `index` the extremum if it's better than the current
+    /// one, or as good and in front of it.
+    fn consider(&mut self, index: usize) {
+        if self.needs_search {
+            return;
+        }
+
+        let ordering = self.compare_to_extremum(&self.buffered_vector[index]);
+        let is_before = self.extremum_index.map_or(false, |extremum| index < extremum);
+        if ordering == self.wanted || (ordering == Ordering::Equal && is_before) {
+            self.extremum_index = Some(index);
+            self.changed = true;
+        }
+    }
+
+    fn shift_after_insert(&mut self, inserted_index: usize) {
+        if let Some(index) = &mut self.extremum_index {
+            if *index >= inserted_index {
+                *index += 1;
+            }
+        }
+    }
+
+    fn remove(&mut self, removed_index: usize) {
+        match &mut self.extremum_index {
+            Some(index) if *index == removed_index => {
+                self.extremum_index = None;
+                self.needs_search = true;
+                self.changed = true;
+            }
+            Some(index) if *index > removed_index => *index -= 1,
+            _ => {}
+        }
+    }
+
+    fn remove_all(&mut self) {
+        if self.extremum_index.take().is_some() {
+            self.changed = true;
+        }
+        self.needs_search = false;
+    }
+}
+
+/// Find the index of the first value with the smallest / largest key.
+fn find_extremum<T, F, K>(values: &Vector<T>, key_fn: &F, wanted: Ordering) -> Option<usize>
+where
+    T: Clone,
+    F: Fn(&T) -> K,
+    K: Ord,
+{
+    let mut keys = values.iter().map(key_fn).enumerate();
+    let (mut extremum_index, mut extremum_key) = keys.next()?;
+    for (index, key) in keys {
+        if key.cmp(&extremum_key) == wanted {
+            extremum_index = index;
+            extremum_key = key;
+        }
+    }
+
+    Some(extremum_index)
+}
@@ -1,16 +1,17 @@
 use std::{
     collections::VecDeque,
+    fmt, mem,
     ops::Not,
     pin::Pin,
     task::{self, ready, Poll},
 };
 
-use eyeball_im::{Vector, VectorDiff};
+use eyeball_im::{ResetReason, Vector, VectorDiff};
 use futures_core::Stream;
 use pin_project_lite::pin_project;
 
 use super::{
-    VectorDiffContainer, VectorDiffContainerDiff, VectorDiffContainerOps,
+    index_after_move, AdapterDebug, AdapterState, VectorDiffContainer, VectorDiffContainerOps,
     VectorDiffContainerStreamElement, VectorDiffContainerStreamMappedItem,
 };
 
@@ -52,7 +53,7 @@ where
             keep
         });
 
-        let inner = FilterImpl { inner, filtered_indices, original_len };
+        let inner = FilterImpl { inner, indices: FilterIndices { filtered_indices, original_len } };
         (values, Self { inner, filter })
     }
 }
@@ -71,6 +72,18 @@ where
     }
 }
 
+impl<S, F> AdapterDebug for Filter<S, F> {
+    fn adapter_state(&self) -> AdapterState {
+        self.inner.adapter_state("Filter")
+    }
+}
+
+impl<S, F> fmt::Debug for Filter<S, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.adapter_state(), f)
+    }
+}
+
 pin_project! {
     /// A [`VectorDiff`] stream adapter that presents a filter+mapped view of
     /// the underlying [`ObservableVector`]s items.
@@ -106,7 +119,7 @@ where
             })
             .unzip();
 
-        let inner = FilterImpl { inner, filtered_indices, original_len };
+        let inner = FilterImpl { inner, indices: FilterIndices { filtered_indices, original_len } };
         (values, Self { inner, filter })
     }
 }
@@ -126,38 +139,109 @@ where
     }
 }
 
+impl<S, F> AdapterDebug for FilterMap<S, F> {
+    fn adapter_state(&self) -> AdapterState {
+        self.inner.adapter_state("FilterMap")
+    }
+}
+
+impl<S, F> fmt::Debug for FilterMap<S, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.adapter_state(), f)
+    }
+}
+
 pin_project! {
     #[project = FilterImplProj]
     pub(super) struct FilterImpl<S> {
         #[pin]
         inner: S,
-        // Original indices of the elements the filter was applied to.
-        //
-        // For example, if the first element of this list is 1, that means the
-        // first original element got filtered out so a set for index = 1 should
-        // translate to a set for index = 0 on the filtered elements (if the
-        // filter still matches after the set operation).
-        filtered_indices: VecDeque<usize>,
-        // Length of the original vector (before filter).
-        original_len: usize,
+        indices: FilterIndices,
     }
 }
 
-impl<S> FilterImplProj<'_, S>
-where
-    S: Stream,
-    S::Item: VectorDiffContainer,
-{
-    fn append_filter<F>(
-        &mut self,
-        mut values: Vector<VectorDiffContainerStreamElement<S>>,
-        f: &F,
-    ) -> Option<Vector<VectorDiffContainerStreamElement<S>>>
+/// Bookkeeping of which elements of the original vector pass a filter.
+///
+/// This is independent of the stream the diffs come from, such that adapters
+/// other than [`Filter`] and [`FilterMap`] can reuse it.
+#[derive(Debug, Default)]
+pub(super) struct FilterIndices {
+    // Original indices of the elements the filter was applied to.
+    //
+    // For example, if the first element of this list is 1, that means the
+    // first original element got filtered out so a set for index = 1 should
+    // translate to a set for index = 0 on the filtered elements (if the
+    // filter still matches after the set operation).
+    filtered_indices: VecDeque<usize>,
+    // Length of the original vector (before filter).
+    original_len: usize,
+}
+
+impl FilterIndices {
+    pub(super) fn filtered_indices(&self) -> &VecDeque<usize> {
+        &self.filtered_indices
+    }
+
+    pub(super) fn original_len(&self) -> usize {
+        self.original_len
+    }
+
+    /// Re-apply a (possibly changed) filter to all of `values`, returning the
+    /// diffs that transform the previous filtered view into the new one.
+    ///
+    /// Values that match both the previous and the new filter are left alone,
+    /// so the diffs consist of `VectorDiff::Remove`s followed by
+    /// `VectorDiff::Insert`s, unless no value matches both filters, in which
+    /// case a `VectorDiff::Clear` and / or `VectorDiff::Append` is used
+    /// instead.
+    pub(super) fn refilter<T, F>(&mut self, values: &Vector<T>, f: &F) -> Vec<VectorDiff<T>>
     where
-        F: Fn(&VectorDiffContainerStreamElement<S>) -> bool,
+        T: Clone,
+        F: Fn(&T) -> bool,
     {
-        let mut original_idx = *self.original_len;
-        *self.original_len += values.len();
+        let new_indices: VecDeque<_> =
+            values.iter().enumerate().filter(|(_, value)| f(value)).map(|(idx, _)| idx).collect();
+        let old_indices = mem::replace(&mut self.filtered_indices, new_indices);
+        self.original_len = values.len();
+        let new_indices = &self.filtered_indices;
+
+        if !old_indices.iter().any(|idx| new_indices.binary_search(idx).is_ok()) {
+            let mut diffs = Vec::new();
+            if !old_indices.is_empty() {
+                diffs.push(VectorDiff::Clear);
+            }
+            if !new_indices.is_empty() {
+                let values = new_indices.iter().map(|&idx| values[idx].clone()).collect();
+                diffs.push(VectorDiff::Append { values });
+            }
+            return diffs;
+        }
+
+        // Remove from the back so earlier indices stay valid.
+        let removals = old_indices
+            .iter()
+            .enumerate()
+            .rev()
+            .filter(|(_, idx)| new_indices.binary_search(idx).is_err())
+            .map(|(index, _)| VectorDiff::Remove { index });
+        // Insert from the front, every value before `index` is present by the
+        // time it is inserted.
+        let insertions = new_indices
+            .iter()
+            .enumerate()
+            .filter(|(_, idx)| old_indices.binary_search(idx).is_err())
+            .map(|(index, &idx)| VectorDiff::Insert { index, value: values[idx].clone() });
+
+        removals.chain(insertions).collect()
+    }
+
+    pub(super) fn append_filter<T, F>(&mut self, mut values: Vector<T>, f: &F) -> Option<Vector<T>>
+    where
+        T: Clone,
+        F: Fn(&T) -> bool,
+    {
+        let mut original_idx = self.original_len;
+        self.original_len += values.len();
         values.retain(|value| {
             let keep = f(value);
             if keep {
@@ -170,17 +254,18 @@ where
         values.is_empty().not().then_some(values)
     }
 
-    fn append_filter_map<U, F>(
+    pub(super) fn append_filter_map<T, U, F>(
         &mut self,
-        values: Vector<VectorDiffContainerStreamElement<S>>,
+        values: Vector<T>,
         f: &F,
     ) -> Option<Vector<U>>
     where
+        T: Clone,
         U: Clone,
-        F: Fn(VectorDiffContainerStreamElement<S>) -> Option<U>,
+        F: Fn(T) -> Option<U>,
     {
-        let mut original_idx = *self.original_len;
-        *self.original_len += values.len();
+        let mut original_idx = self.original_len;
+        self.original_len += values.len();
         let mapped_values: Vector<_> = values
             .into_iter()
             .filter_map(|val| {
@@ -196,46 +281,44 @@ where
         mapped_values.is_empty().not().then_some(mapped_values)
     }
 
-    fn handle_append_filter<F>(
+    pub(super) fn handle_append_filter<T, F>(
         &mut self,
-        values: Vector<VectorDiffContainerStreamElement<S>>,
+        values: Vector<T>,
         f: &F,
-    ) -> Option<VectorDiffContainerDiff<S>>
+    ) -> Option<VectorDiff<T>>
     where
-        F: Fn(&VectorDiffContainerStreamElement<S>) -> bool,
+        T: Clone,
+        F: Fn(&T) -> bool,
     {
         self.append_filter(values, f).map(|values| VectorDiff::Append { values })
     }
 
-    fn handle_append_filter_map<U, F>(
+    pub(super) fn handle_append_filter_map<T, U, F>(
         &mut self,
-        values: Vector<VectorDiffContainerStreamElement<S>>,
+        values: Vector<T>,
         f: &F,
     ) -> Option<VectorDiff<U>>
     where
+        T: Clone,
         U: Clone,
-        F: Fn(VectorDiffContainerStreamElement<S>) -> Option<U>,
+        F: Fn(T) -> Option<U>,
     {
         self.append_filter_map(values, f).map(|values| VectorDiff::Append { values })
     }
 
-    fn handle_clear<U>(&mut self) -> Option<VectorDiff<U>> {
+    pub(super) fn handle_clear<U>(&mut self) -> Option<VectorDiff<U>> {
         self.filtered_indices.clear();
-        *self.original_len = 0;
+        self.original_len = 0;
         Some(VectorDiff::Clear)
     }
 
-    fn handle_push_front<U, F>(
-        &mut self,
-        value: VectorDiffContainerStreamElement<S>,
-        f: &F,
-    ) -> Option<VectorDiff<U>>
+    pub(super) fn handle_push_front<T, U, F>(&mut self, value: T, f: &F) -> Option<VectorDiff<U>>
     where
         U: Clone,
-        F: Fn(VectorDiffContainerStreamElement<S>) -> Option<U>,
+        F: Fn(T) -> Option<U>,
     {
-        *self.original_len += 1;
-        for idx in &mut *self.filtered_indices {
+        self.original_len += 1;
+        for idx in &mut self.filtered_indices {
             *idx += 1;
         }
 
@@ -245,55 +328,51 @@ where
         })
     }
 
-    fn handle_push_back<U, F>(
-        &mut self,
-        value: VectorDiffContainerStreamElement<S>,
-        f: &F,
-    ) -> Option<VectorDiff<U>>
+    pub(super) fn handle_push_back<T, U, F>(&mut self, value: T, f: &F) -> Option<VectorDiff<U>>
     where
         U: Clone,
-        F: Fn(VectorDiffContainerStreamElement<S>) -> Option<U>,
+        F: Fn(T) -> Option<U>,
     {
-        let original_idx = *self.original_len;
-        *self.original_len += 1;
+        let original_idx = self.original_len;
+        self.original_len += 1;
         f(value).map(|value| {
             self.filtered_indices.push_back(original_idx);
             VectorDiff::PushBack { value }
         })
     }
 
-    fn handle_pop_front<U>(&mut self) -> Option<VectorDiff<U>> {
-        *self.original_len -= 1;
+    pub(super) fn handle_pop_front<U>(&mut self) -> Option<VectorDiff<U>> {
+        self.original_len -= 1;
         let result = self.filtered_indices.front().map_or(false, |&idx| idx == 0).then(|| {
             assert!(self.filtered_indices.pop_front().is_some());
             VectorDiff::PopFront
         });
-        for idx in &mut *self.filtered_indices {
+        for idx in &mut self.filtered_indices {
             *idx -= 1;
         }
 
         result
     }
 
-    fn handle_pop_back<U>(&mut self) -> Option<VectorDiff<U>> {
-        *self.original_len -= 1;
-        self.filtered_indices.back().map_or(false, |&idx| idx == *self.original_len).then(|| {
+    pub(super) fn handle_pop_back<U>(&mut self) -> Option<VectorDiff<U>> {
+        self.original_len -= 1;
+        self.filtered_indices.back().map_or(false, |&idx| idx == self.original_len).then(|| {
             assert!(self.filtered_indices.pop_back().is_some());
             VectorDiff::PopBack
         })
     }
 
-    fn handle_insert<U, F>(
+    pub(super) fn handle_insert<T, U, F>(
         &mut self,
         index: usize,
-        value: VectorDiffContainerStreamElement<S>,
+        value: T,
         f: &F,
     ) -> Option<VectorDiff<U>>
     where
         U: Clone,
-        F: Fn(VectorDiffContainerStreamElement<S>) -> Option<U>,
+        F: Fn(T) -> Option<U>,
     {
-        *self.original_len += 1;
+        self.original_len += 1;
         let original_idx = index;
         let index = self.filtered_indices.partition_point(|&i| i < original_idx);
         for idx in self.filtered_indices.iter_mut().skip(index) {
@@ -306,15 +385,15 @@ where
         })
     }
 
-    fn handle_set<U, F>(
+    pub(super) fn handle_set<T, U, F>(
         &mut self,
         index: usize,
-        value: VectorDiffContainerStreamElement<S>,
+        value: T,
         f: &F,
     ) -> Option<VectorDiff<U>>
     where
         U: Clone,
-        F: Fn(VectorDiffContainerStreamElement<S>) -> Option<U>,
+        F: Fn(T) -> Option<U>,
     {
         let original_idx = index;
         let new_value = f(value);
@@ -337,9 +416,9 @@ where
         }
     }
 
-    fn handle_remove<U>(&mut self, index: usize) -> Option<VectorDiff<U>> {
+    pub(super) fn handle_remove<U>(&mut self, index: usize) -> Option<VectorDiff<U>> {
         let original_idx = index;
-        *self.original_len -= 1;
+        self.original_len -= 1;
 
         let index = self.filtered_indices.partition_point(|&i| i < original_idx);
         let result =
@@ -356,8 +435,30 @@ where
         result
     }
 
-    fn handle_truncate<U>(&mut self, len: usize) -> Option<VectorDiff<U>> {
-        *self.original_len = len;
+    pub(super) fn handle_move<U>(&mut self, from: usize, to: usize) -> Option<VectorDiff<U>> {
+        let filtered_from = self.filtered_indices.partition_point(|&i| i < from);
+        let matched = self.filtered_indices.get(filtered_from).map_or(false, |&i| i == from);
+        if matched {
+            self.filtered_indices.remove(filtered_from);
+        }
+
+        for idx in self.filtered_indices.iter_mut() {
+            *idx = index_after_move(*idx, from, to);
+        }
+
+        if !matched {
+            // The moved value didn't match the filter
+            return None;
+        }
+
+        let filtered_to = self.filtered_indices.partition_point(|&i| i < to);
+        self.filtered_indices.insert(filtered_to, to);
+        (filtered_from != filtered_to)
+            .then_some(VectorDiff::Move { from: filtered_from, to: filtered_to })
+    }
+
+    pub(super) fn handle_truncate<U>(&mut self, len: usize) -> Option<VectorDiff<U>> {
+        self.original_len = len;
         let new_filtered_len = self.filtered_indices.iter().take_while(|&&idx| idx < len).count();
         (new_filtered_len < self.filtered_indices.len()).then(|| {
             self.filtered_indices.truncate(new_filtered_len);
@@ -365,33 +466,57 @@ where
         })
     }
 
-    fn handle_reset_filter<F>(
+    pub(super) fn handle_reset_filter<T, F>(
         &mut self,
-        values: Vector<VectorDiffContainerStreamElement<S>>,
+        values: Vector<T>,
+        reason: ResetReason,
         f: &F,
-    ) -> Option<VectorDiffContainerDiff<S>>
+    ) -> Option<VectorDiff<T>>
     where
-        F: Fn(&VectorDiffContainerStreamElement<S>) -> bool,
+        T: Clone,
+        F: Fn(&T) -> bool,
     {
         self.filtered_indices.clear();
-        *self.original_len = 0;
-        self.append_filter(values, f).map(|values| VectorDiff::Reset { values })
+        self.original_len = 0;
+        // Always emit the reset, even if no value matches, so the values from
+        // before it are removed.
+        let values = self.append_filter(values, f).unwrap_or_default();
+        Some(VectorDiff::Reset { values, reason })
     }
 
-    fn handle_reset_filter_map<U, F>(
+    pub(super) fn handle_reset_filter_map<T, U, F>(
         &mut self,
-        values: Vector<VectorDiffContainerStreamElement<S>>,
+        values: Vector<T>,
+        reason: ResetReason,
         f: &F,
     ) -> Option<VectorDiff<U>>
     where
+        T: Clone,
         U: Clone,
-        F: Fn(VectorDiffContainerStreamElement<S>) -> Option<U>,
+        F: Fn(T) -> Option<U>,
     {
         self.filtered_indices.clear();
-        *self.original_len = 0;
-        self.append_filter_map(values, f).map(|values| VectorDiff::Reset { values })
+        self.original_len = 0;
+        let values = self.append_filter_map(values, f).unwrap_or_default();
+        Some(VectorDiff::Reset { values, reason })
     }
+}
 
+impl<S> FilterImpl<S> {
+    fn adapter_state(&self, name: &'static str) -> AdapterState {
+        AdapterState {
+            buffered_len: Some(self.indices.original_len),
+            len: Some(self.indices.filtered_indices.len()),
+            ..AdapterState::new(name)
+        }
+    }
+}
+
+impl<S> FilterImplProj<'_, S>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
     fn handle_diff_filter<F>(&mut self, f: &F, cx: &mut task::Context<'_>) -> Poll<Option<S::Item>>
     where
         F: Fn(&VectorDiffContainerStreamElement<S>) -> bool,
@@ -404,17 +529,22 @@ where
             };
 
             let result = diffs.filter_map(|diff| match diff {
-                VectorDiff::Append { values } => self.handle_append_filter(values, f),
-                VectorDiff::Clear => self.handle_clear(),
-                VectorDiff::PushFront { value } => self.handle_push_front(value, &f2),
-                VectorDiff::PushBack { value } => self.handle_push_back(value, &f2),
-                VectorDiff::PopFront => self.handle_pop_front(),
-                VectorDiff::PopBack => self.handle_pop_back(),
-                VectorDiff::Insert { index, value } => self.handle_insert(index, value, &f2),
-                VectorDiff::Set { index, value } => self.handle_set(index, value, &f2),
-                VectorDiff::Remove { index } => self.handle_remove(index),
-                VectorDiff::Truncate { length } => self.handle_truncate(length),
-                VectorDiff::Reset { values } => self.handle_reset_filter(values, f),
+                VectorDiff::Append { values } => self.indices.handle_append_filter(values, f),
+                VectorDiff::Clear => self.indices.handle_clear(),
+                VectorDiff::PushFront { value } => self.indices.handle_push_front(value, &f2),
+                VectorDiff::PushBack { value } => self.indices.handle_push_back(value, &f2),
+                VectorDiff::PopFront => self.indices.handle_pop_front(),
+                VectorDiff::PopBack => self.indices.handle_pop_back(),
+                VectorDiff::Insert { index, value } => {
+                    self.indices.handle_insert(index, value, &f2)
+                }
+                VectorDiff::Set { index, value } => self.indices.handle_set(index, value, &f2),
+                VectorDiff::Remove { index } => self.indices.handle_remove(index),
+                VectorDiff::Truncate { length } => self.indices.handle_truncate(length),
+                VectorDiff::Move { from, to } => self.indices.handle_move(from, to),
+                VectorDiff::Reset { values, reason } => {
+                    self.indices.handle_reset_filter(values, reason, f)
+                }
             });
 
             if let Some(diffs) = result {
@@ -438,17 +568,20 @@ where
             };
 
             let result = diffs.filter_map(|diff| match diff {
-                VectorDiff::Append { values } => self.handle_append_filter_map(values, f),
-                VectorDiff::Clear => self.handle_clear(),
-                VectorDiff::PushFront { value } => self.handle_push_front(value, f),
-                VectorDiff::PushBack { value } => self.handle_push_back(value, f),
-                VectorDiff::PopFront => self.handle_pop_front(),
-                VectorDiff::PopBack => self.handle_pop_back(),
-                VectorDiff::Insert { index, value } => self.handle_insert(index, value, f),
-                VectorDiff::Set { index, value } => self.handle_set(index, value, f),
-                VectorDiff::Remove { index } => self.handle_remove(index),
-                VectorDiff::Truncate { length } => self.handle_truncate(length),
-                VectorDiff::Reset { values } => self.handle_reset_filter_map(values, f),
+                VectorDiff::Append { values } => self.indices.handle_append_filter_map(values, f),
+                VectorDiff::Clear => self.indices.handle_clear(),
+                VectorDiff::PushFront { value } => self.indices.handle_push_front(value, f),
+                VectorDiff::PushBack { value } => self.indices.handle_push_back(value, f),
+                VectorDiff::PopFront => self.indices.handle_pop_front(),
+                VectorDiff::PopBack => self.indices.handle_pop_back(),
+                VectorDiff::Insert { index, value } => self.indices.handle_insert(index, value, f),
+                VectorDiff::Set { index, value } => self.indices.handle_set(index, value, f),
+                VectorDiff::Remove { index } => self.indices.handle_remove(index),
+                VectorDiff::Truncate { length } => self.indices.handle_truncate(length),
+                VectorDiff::Move { from, to } => self.indices.handle_move(from, to),
+                VectorDiff::Reset { values, reason } => {
+                    self.indices.handle_reset_filter_map(values, reason, f)
+                }
             });
 
             if let Some(diffs) = result {
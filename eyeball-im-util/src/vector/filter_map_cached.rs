@@ -0,0 +1,218 @@
+use std::{
+    collections::VecDeque,
+    convert::identity,
+    fmt,
+    pin::Pin,
+    task::{self, ready, Poll},
+};
+
+use eyeball_im::{Vector, VectorDiff};
+use futures_core::Stream;
+use pin_project_lite::pin_project;
+
+use super::{
+    filter::FilterIndices, AdapterDebug, AdapterState, VectorDiffContainer, VectorDiffContainerOps,
+    VectorDiffContainerStreamElement, VectorDiffContainerStreamMappedItem,
+};
+
+pin_project! {
+    /// A [`VectorDiff`] stream adapter that presents a filter+mapped view of
+    /// the underlying [`ObservableVector`]s items, where the filter+map
+    /// function gets mutable access to a state that is kept for each item.
+    ///
+    /// The state of an item is created with [`Default::default`] when the
+    /// item is added, follows it when it changes position and is dropped when
+    /// it is removed. The filter+map function is called with an item and its
+    /// state when the item is added, and again when it is replaced through a
+    /// `VectorDiff::Set`, in which case the state is kept. That makes it
+    /// possible to cache expensive derived values and to only compute them
+    /// again when the parts of the item they are derived from change.
+    ///
+    /// A `VectorDiff::Reset` drops the state of all items.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use eyeball_im::{ObservableVector, VectorDiff};
+    /// use eyeball_im_util::vector::VectorObserverExt;
+    /// use imbl::vector;
+    /// use stream_assert::{assert_next_eq, assert_pending};
+    ///
+    /// #[derive(Clone)]
+    /// struct Message {
+    ///     body: &'static str,
+    ///     read: bool,
+    /// }
+    ///
+    /// // The body is only "parsed" again when it changes.
+    /// let parse = |msg: Message, cache: &mut Option<(&'static str, usize)>| {
+    ///     let (_, parsed) = cache.get_or_insert_with(|| (msg.body, msg.body.len()));
+    ///     Some((*parsed, msg.read))
+    /// };
+    ///
+    /// let mut ob = ObservableVector::from(vector![Message { body: "hi", read: false }]);
+    /// let (values, mut sub) = ob.subscribe().filter_map_cached(parse);
+    /// assert_eq!(values, vector![(2, false)]);
+    ///
+    /// ob.set(0, Message { body: "hi", read: true });
+    /// assert_next_eq!(sub, VectorDiff::Set { index: 0, value: (2, true) });
+    /// assert_pending!(sub);
+    /// ```
+    ///
+    /// [`ObservableVector`]: eyeball_im::ObservableVector
+    pub struct FilterMapCached<S, F, C> {
+        // The main stream to poll items from.
+        #[pin]
+        inner: S,
+
+        // The function that filter+maps an item along with its state.
+        f: F,
+
+        // Which of the items match.
+        indices: FilterIndices,
+
+        // The state of each item of the underlying vector.
+        states: VecDeque<C>,
+    }
+}
+
+impl<S, U, F, C> FilterMapCached<S, F, C>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    U: Clone,
+    F: Fn(VectorDiffContainerStreamElement<S>, &mut C) -> Option<U>,
+    C: Default,
+{
+    /// Create a new `FilterMapCached` with the given (un-filter+mapped)
+    /// initial values, stream of `VectorDiff` updates for those values, and
+    /// filter+map function.
+    pub fn new(
+        values: Vector<VectorDiffContainerStreamElement<S>>,
+        inner: S,
+        f: F,
+    ) -> (Vector<U>, Self) {
+        let mut states = VecDeque::new();
+        let mapped = map_with_new_states(values, &mut states, &f);
+        let mut indices = FilterIndices::default();
+        let values = indices.append_filter_map(mapped, &identity).unwrap_or_default();
+
+        (values, Self { inner, f, indices, states })
+    }
+}
+
+impl<S, U, F, C> Stream for FilterMapCached<S, F, C>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    U: Clone,
+    F: Fn(VectorDiffContainerStreamElement<S>, &mut C) -> Option<U>,
+    C: Default,
+{
+    type Item = VectorDiffContainerStreamMappedItem<S, U>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        let (f, indices, states) = (&*this.f, this.indices, this.states);
+
+        loop {
+            let Some(diffs) = ready!(this.inner.as_mut().poll_next(cx)) else {
+                return Poll::Ready(None);
+            };
+
+            let result = diffs.filter_map(|diff| match diff {
+                VectorDiff::Append { values } => {
+                    let mapped = map_with_new_states(values, states, f);
+                    indices.handle_append_filter_map(mapped, &identity)
+                }
+                VectorDiff::Clear => {
+                    states.clear();
+                    indices.handle_clear()
+                }
+                VectorDiff::PushFront { value } => {
+                    states.push_front(C::default());
+                    indices.handle_push_front(f(value, &mut states[0]), &identity)
+                }
+                VectorDiff::PushBack { value } => {
+                    states.push_back(C::default());
+                    let mapped = f(value, states.back_mut().unwrap());
+                    indices.handle_push_back(mapped, &identity)
+                }
+                VectorDiff::PopFront => {
+                    states.pop_front();
+                    indices.handle_pop_front()
+                }
+                VectorDiff::PopBack => {
+                    states.pop_back();
+                    indices.handle_pop_back()
+                }
+                VectorDiff::Insert { index, value } => {
+                    states.insert(index, C::default());
+                    indices.handle_insert(index, f(value, &mut states[index]), &identity)
+                }
+                VectorDiff::Set { index, value } => {
+                    indices.handle_set(index, f(value, &mut states[index]), &identity)
+                }
+                VectorDiff::Remove { index } => {
+                    states.remove(index);
+                    indices.handle_remove(index)
+                }
+                VectorDiff::Truncate { length } => {
+                    states.truncate(length);
+                    indices.handle_truncate(length)
+                }
+                VectorDiff::Move { from, to } => {
+                    let state = states.remove(from).unwrap();
+                    states.insert(to, state);
+                    indices.handle_move(from, to)
+                }
+                VectorDiff::Reset { values, reason } => {
+                    states.clear();
+                    let mapped = map_with_new_states(values, states, f);
+                    indices.handle_reset_filter_map(mapped, reason, &identity)
+                }
+            });
+
+            if let Some(diffs) = result {
+                return Poll::Ready(Some(diffs));
+            }
+        }
+    }
+}
+
+impl<S, F, C> AdapterDebug for FilterMapCached<S, F, C> {
+    fn adapter_state(&self) -> AdapterState {
+        AdapterState {
+            buffered_len: Some(self.states.len()),
+            len: Some(self.indices.filtered_indices().len()),
+            ..AdapterState::new("FilterMapCached")
+        }
+    }
+}
+
+impl<S, F, C> fmt::Debug for FilterMapCached<S, F, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.adapter_state(), f)
+    }
+}
+
+/// Filter+map `values` with new states, which are appended to `states`.
+fn map_with_new_states<T, U, F, C>(
+    values: Vector<T>,
+    states: &mut VecDeque<C>,
+    f: &F,
+) -> Vector<Option<U>>
+where
+    T: Clone,
+    U: Clone,
+    F: Fn(T, &mut C) -> Option<U>,
+    C: Default,
+{
+    values
+        .into_iter()
+        .map(|value| {
+            states.push_back(C::default());
+            f(value, states.back_mut().unwrap())
+        })
+        .collect()
+}
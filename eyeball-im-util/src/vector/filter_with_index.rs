@@ -0,0 +1,338 @@
+use std::{
+    fmt, mem,
+    pin::Pin,
+    task::{self, ready, Poll},
+};
+
+use eyeball_im::{Vector, VectorDiff};
+use futures_core::Stream;
+use pin_project_lite::pin_project;
+
+use super::{
+    index_after_move, AdapterDebug, AdapterState, VectorDiffContainer, VectorDiffContainerOps,
+    VectorDiffContainerStreamElement, VectorDiffContainerStreamTailBuf,
+};
+
+pin_project! {
+    /// A [`VectorDiff`] stream adapter that presents a filtered view of the
+    /// underlying [`ObservableVector`]'s items, where the filter receives the
+    /// index of each item along with the item itself.
+    ///
+    /// Whenever items change position, e.g. because an item was inserted or
+    /// removed before them, the filter is applied to them again with their new
+    /// index. Items that match the filter both before and after are left
+    /// alone, the others are removed from or inserted into the view. This
+    /// makes it possible to filter items by their position, e.g. to only keep
+    /// every n-th item, at the cost of filtering all of the items after a
+    /// changed position again.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use eyeball_im::{ObservableVector, VectorDiff};
+    /// use eyeball_im_util::vector::VectorObserverExt;
+    /// use imbl::vector;
+    /// use stream_assert::{assert_next_eq, assert_pending};
+    ///
+    /// let mut ob = ObservableVector::<char>::from(vector!['a', 'b', 'c', 'd']);
+    /// let (values, mut sub) = ob.subscribe().filter_with_index(|index, _| index % 2 == 0);
+    /// assert_eq!(values, vector!['a', 'c']);
+    ///
+    /// ob.push_back('e');
+    /// assert_next_eq!(sub, VectorDiff::PushBack { value: 'e' });
+    ///
+    /// // The items after the removed one move to the other parity.
+    /// ob.remove(0);
+    /// assert_next_eq!(sub, VectorDiff::Truncate { length: 0 });
+    /// assert_next_eq!(sub, VectorDiff::Append { values: vector!['b', 'd'] });
+    /// assert_pending!(sub);
+    /// ```
+    ///
+    /// [`ObservableVector`]: eyeball_im::ObservableVector
+    #[project = FilterWithIndexProj]
+    pub struct FilterWithIndex<S, F>
+    where
+        S: Stream,
+        S::Item: VectorDiffContainer,
+    {
+        // The main stream to poll items from.
+        #[pin]
+        inner_stream: S,
+
+        // The filter function, which receives an item and its index.
+        f: F,
+
+        // The buffered vector that is updated with the main stream's items.
+        buffered_vector: Vector<VectorDiffContainerStreamElement<S>>,
+
+        // The indices of the items in `buffered_vector` that match the
+        // filter, in ascending order.
+        filtered_indices: Vec<usize>,
+
+        // Diffs that are ready to be emitted, used if the item type is just
+        // `VectorDiff<_>` (non-batched) since one update can result in
+        // multiple diffs.
+        ready_values: VectorDiffContainerStreamTailBuf<S>,
+    }
+}
+
+impl<S, F> FilterWithIndex<S, F>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    F: Fn(usize, &VectorDiffContainerStreamElement<S>) -> bool,
+{
+    /// Create a new `FilterWithIndex` with the given (unfiltered) initial
+    /// values, stream of `VectorDiff` updates for those values, and filter.
+    ///
+    /// Returns the filtered initial values as well as a stream of updates to
+    /// them.
+    pub fn new(
+        initial_values: Vector<VectorDiffContainerStreamElement<S>>,
+        inner_stream: S,
+        f: F,
+    ) -> (Vector<VectorDiffContainerStreamElement<S>>, Self) {
+        let filtered_indices = filter_from(&initial_values, 0, &f);
+        let values = filtered_indices.iter().map(|&idx| initial_values[idx].clone()).collect();
+        let stream = Self {
+            inner_stream,
+            f,
+            buffered_vector: initial_values,
+            filtered_indices,
+            ready_values: Default::default(),
+        };
+
+        (values, stream)
+    }
+}
+
+impl<S, F> Stream for FilterWithIndex<S, F>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    F: Fn(usize, &VectorDiffContainerStreamElement<S>) -> bool,
+{
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        self.project().poll_next(cx)
+    }
+}
+
+impl<S, F> AdapterDebug for FilterWithIndex<S, F>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn adapter_state(&self) -> AdapterState {
+        AdapterState {
+            buffered_len: Some(self.buffered_vector.len()),
+            len: Some(self.filtered_indices.len()),
+            ready_values: S::Item::tail_buf_len(&self.ready_values),
+            ..AdapterState::new("FilterWithIndex")
+        }
+    }
+}
+
+impl<S, F> fmt::Debug for FilterWithIndex<S, F>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.adapter_state(), f)
+    }
+}
+
+impl<S, F> FilterWithIndexProj<'_, S, F>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    F: Fn(usize, &VectorDiffContainerStreamElement<S>) -> bool,
+{
+    fn poll_next(&mut self, cx: &mut task::Context<'_>) -> Poll<Option<S::Item>> {
+        loop {
+            // First off, if any values are ready, return them.
+            if let Some(value) = S::Item::pop_from_tail_buf(self.ready_values) {
+                return Poll::Ready(Some(value));
+            }
+
+            // Poll `VectorDiff`s from the `inner_stream`.
+            let Some(diffs) = ready!(self.inner_stream.as_mut().poll_next(cx)) else {
+                return Poll::Ready(None);
+            };
+
+            let mut res = Vec::new();
+            for diff in diffs.into_vec() {
+                self.handle_diff(diff, &mut res);
+            }
+
+            if let Some(diffs) = S::Item::extend_tail_buf(res, self.ready_values) {
+                return Poll::Ready(Some(diffs));
+            }
+
+            // Else loop and poll the streams again.
+        }
+    }
+
+    fn handle_diff(
+        &mut self,
+        diff: VectorDiff<VectorDiffContainerStreamElement<S>>,
+        res: &mut Vec<VectorDiff<VectorDiffContainerStreamElement<S>>>,
+    ) {
+        let len = self.buffered_vector.len();
+        diff.clone().apply(self.buffered_vector);
+
+        match diff {
+            VectorDiff::Append { values } => {
+                let appended = filter_from(&values, len, &*self.f);
+                if !appended.is_empty() {
+                    let values = appended.iter().map(|&idx| values[idx - len].clone()).collect();
+                    self.filtered_indices.extend(appended);
+                    res.push(VectorDiff::Append { values });
+                }
+            }
+            VectorDiff::Clear => {
+                if !self.filtered_indices.is_empty() {
+                    self.filtered_indices.clear();
+                    res.push(VectorDiff::Clear);
+                }
+            }
+            VectorDiff::PushBack { value } => {
+                if (self.f)(len, &value) {
+                    self.filtered_indices.push(len);
+                    res.push(VectorDiff::PushBack { value });
+                }
+            }
+            VectorDiff::PopBack => {
+                if self.filtered_indices.last() == Some(&(len - 1)) {
+                    self.filtered_indices.pop();
+                    res.push(VectorDiff::PopBack);
+                }
+            }
+            VectorDiff::Set { index, value } => {
+                let matches = (self.f)(index, &value);
+                match (self.filtered_indices.binary_search(&index), matches) {
+                    (Ok(pos), true) => res.push(VectorDiff::Set { index: pos, value }),
+                    (Ok(pos), false) => {
+                        self.filtered_indices.remove(pos);
+                        res.push(VectorDiff::Remove { index: pos });
+                    }
+                    (Err(pos), true) => {
+                        self.filtered_indices.insert(pos, index);
+                        res.push(VectorDiff::Insert { index: pos, value });
+                    }
+                    (Err(_), false) => {}
+                }
+            }
+            VectorDiff::Truncate { length } => {
+                let new_len = self.filtered_indices.partition_point(|&idx| idx < length);
+                if new_len < self.filtered_indices.len() {
+                    self.filtered_indices.truncate(new_len);
+                    res.push(VectorDiff::Truncate { length: new_len });
+                }
+            }
+            VectorDiff::PushFront { .. } => {
+                self.refilter(0, |idx| Some(idx + 1), res);
+            }
+            VectorDiff::PopFront => {
+                self.refilter(0, |idx| idx.checked_sub(1), res);
+            }
+            VectorDiff::Insert { index, .. } => {
+                self.refilter(index, |idx| Some(if idx >= index { idx + 1 } else { idx }), res);
+            }
+            VectorDiff::Remove { index } => {
+                self.refilter(
+                    index,
+                    |idx| (idx != index).then(|| idx - usize::from(idx > index)),
+                    res,
+                );
+            }
+            VectorDiff::Move { from, to } => {
+                // The moved item is handled as removed and inserted again, so
+                // the other items keep their order.
+                let start = from.min(to);
+                self.refilter(
+                    start,
+                    |idx| (idx != from).then(|| index_after_move(idx, from, to)),
+                    res,
+                );
+            }
+            VectorDiff::Reset { values, reason } => {
+                *self.filtered_indices = filter_from(&values, 0, &*self.f);
+                let values = self.filtered_indices.iter().map(|&idx| values[idx].clone()).collect();
+                res.push(VectorDiff::Reset { values, reason });
+            }
+        }
+    }
+
+    /// Apply the filter again to the items of the buffered vector from
+    /// `start` on, after their indices were changed according to `new_index`,
+    /// which returns `None` for removed items.
+    ///
+    /// Items that matched the filter before and still match it with their new
+    /// index stay in place, the others are removed or inserted.
+    fn refilter(
+        &mut self,
+        start: usize,
+        new_index: impl Fn(usize) -> Option<usize>,
+        res: &mut Vec<VectorDiff<VectorDiffContainerStreamElement<S>>>,
+    ) {
+        let num_unchanged = self.filtered_indices.partition_point(|&idx| idx < start);
+        let mut new_indices = self.filtered_indices[..num_unchanged].to_vec();
+        new_indices.extend(filter_from(&self.buffered_vector.skip(start), start, &*self.f));
+
+        // The items that still match, with their new index.
+        let kept: Vec<usize> = self.filtered_indices[num_unchanged..]
+            .iter()
+            .filter_map(|&idx| new_index(idx))
+            .filter(|idx| new_indices[num_unchanged..].binary_search(idx).is_ok())
+            .collect();
+        let old_indices = mem::replace(self.filtered_indices, new_indices);
+
+        if kept.is_empty() {
+            if old_indices.len() > num_unchanged {
+                res.push(VectorDiff::Truncate { length: num_unchanged });
+            }
+            if self.filtered_indices.len() > num_unchanged {
+                let values = self.filtered_indices[num_unchanged..]
+                    .iter()
+                    .map(|&idx| self.buffered_vector[idx].clone())
+                    .collect();
+                res.push(VectorDiff::Append { values });
+            }
+            return;
+        }
+
+        // Remove from the back so earlier positions stay valid.
+        for (pos, &idx) in old_indices.iter().enumerate().skip(num_unchanged).rev() {
+            if new_index(idx).map_or(true, |idx| kept.binary_search(&idx).is_err()) {
+                res.push(VectorDiff::Remove { index: pos });
+            }
+        }
+        // Insert from the front, every item before `pos` is present by the
+        // time it is inserted.
+        for (pos, &idx) in self.filtered_indices.iter().enumerate().skip(num_unchanged) {
+            if kept.binary_search(&idx).is_err() {
+                let value = self.buffered_vector[idx].clone();
+                res.push(VectorDiff::Insert { index: pos, value });
+            }
+        }
+    }
+}
+
+/// Get the indices of the items of `values` that match `f`, where the first
+/// item is at index `offset`.
+fn filter_from<T: Clone>(
+    values: &Vector<T>,
+    offset: usize,
+    f: &impl Fn(usize, &T) -> bool,
+) -> Vec<usize> {
+    values
+        .iter()
+        .enumerate()
+        .map(|(i, value)| (offset + i, value))
+        .filter(|(idx, value)| f(*idx, value))
+        .map(|(idx, _)| idx)
+        .collect()
+}
@@ -0,0 +1,340 @@
+use std::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    task::{self, ready, Poll},
+};
+
+use eyeball::{SharedObservable, Subscriber};
+use eyeball_im::{Vector, VectorDiff};
+use futures_core::Stream;
+use pin_project_lite::pin_project;
+
+use super::{
+    AdapterDebug, AdapterState, VectorDiffContainer, VectorDiffContainerOps,
+    VectorDiffContainerStreamElement,
+};
+
+pin_project! {
+    /// A future that folds the [`VectorDiff`]s of the underlying stream into
+    /// an accumulator, which can be observed while the future runs.
+    ///
+    /// The accumulator starts out as the given initial value, folded with a
+    /// `VectorDiff::Append` of the initial values if there are any. After
+    /// that, every batch of diffs polled from the underlying stream is folded
+    /// into it and the result is published through a [`SharedObservable`], so
+    /// there is a single update per batch. Use [`subscribe`][Self::subscribe]
+    /// to get notified of these updates.
+    ///
+    /// The future completes once the underlying stream has ended.
+    ///
+    /// Since diffs that remove values don't contain them, the fold function
+    /// can't undo the contribution of removed values by itself. For running
+    /// totals like a sum, [`FoldItems`] is usually the better fit.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use eyeball_im::{ObservableVector, VectorDiff};
+    /// use eyeball_im_util::vector::VectorObserverExt;
+    /// use futures_util::FutureExt;
+    /// use imbl::vector;
+    ///
+    /// let mut ob = ObservableVector::<&str>::from(vector!["a", "b"]);
+    /// let mut num_inserted = ob.subscribe().fold(0, |acc, diff| match diff {
+    ///     VectorDiff::Append { values } => acc + values.len(),
+    ///     VectorDiff::PushFront { .. }
+    ///     | VectorDiff::PushBack { .. }
+    ///     | VectorDiff::Insert { .. } => acc + 1,
+    ///     _ => acc,
+    /// });
+    /// assert_eq!(num_inserted.get(), 2);
+    ///
+    /// ob.push_back("c");
+    /// ob.remove(0);
+    /// assert_eq!((&mut num_inserted).now_or_never(), None);
+    /// assert_eq!(num_inserted.get(), 3);
+    /// ```
+    pub struct Fold<S, A, F> {
+        // The main stream to poll items from.
+        #[pin]
+        inner_stream: S,
+
+        // The observable holding the accumulator.
+        observable: SharedObservable<A>,
+
+        // The fold function.
+        f: F,
+    }
+}
+
+impl<S, A, F> Fold<S, A, F>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    A: Clone,
+    F: FnMut(A, &VectorDiff<VectorDiffContainerStreamElement<S>>) -> A,
+{
+    /// Create a new `Fold` with the given initial values, stream of
+    /// `VectorDiff` updates for those values, initial accumulator and fold
+    /// function.
+    pub fn new(
+        initial_values: Vector<VectorDiffContainerStreamElement<S>>,
+        inner_stream: S,
+        init: A,
+        mut f: F,
+    ) -> Self {
+        let acc = if initial_values.is_empty() {
+            init
+        } else {
+            f(init, &VectorDiff::Append { values: initial_values })
+        };
+
+        Self { inner_stream, observable: SharedObservable::new(acc), f }
+    }
+
+    /// Get a clone of the current accumulator.
+    pub fn get(&self) -> A {
+        self.observable.get()
+    }
+
+    /// Obtain a new subscriber to the accumulator.
+    pub fn subscribe(&self) -> Subscriber<A> {
+        self.observable.subscribe()
+    }
+}
+
+impl<S, A, F> Future for Fold<S, A, F>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    A: Clone,
+    F: FnMut(A, &VectorDiff<VectorDiffContainerStreamElement<S>>) -> A,
+{
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+        loop {
+            let Some(diffs) = ready!(this.inner_stream.as_mut().poll_next(cx)) else {
+                return Poll::Ready(());
+            };
+
+            let acc = diffs.into_vec().iter().fold(this.observable.get(), &mut *this.f);
+            this.observable.set(acc);
+        }
+    }
+}
+
+impl<S, A, F> AdapterDebug for Fold<S, A, F> {
+    fn adapter_state(&self) -> AdapterState {
+        AdapterState::new("Fold")
+    }
+}
+
+impl<S, A, F> fmt::Debug for Fold<S, A, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.adapter_state(), f)
+    }
+}
+
+pin_project! {
+    /// A future that folds the values of the underlying observed vector into
+    /// an accumulator, which can be observed while the future runs.
+    ///
+    /// Unlike [`Fold`], this is not based on diffs but on the values
+    /// themselves: `add` is called for every value that is added, and
+    /// `remove` for every value that is removed, with a `VectorDiff::Set`
+    /// counting as both. This makes it straightforward to maintain running
+    /// totals, like the number of unread items or the sum of their sizes.
+    ///
+    /// Every batch of diffs polled from the underlying stream results in a
+    /// single update of the accumulator. Use [`subscribe`][Self::subscribe] to
+    /// get notified of these updates.
+    ///
+    /// The future completes once the underlying stream has ended.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use eyeball_im::ObservableVector;
+    /// use eyeball_im_util::vector::VectorObserverExt;
+    /// use futures_util::FutureExt;
+    /// use imbl::vector;
+    ///
+    /// let mut ob = ObservableVector::<u32>::from(vector![1, 2]);
+    /// let mut sum = ob.subscribe().fold_items(0, |acc, v| acc + v, |acc, v| acc - v);
+    /// assert_eq!(sum.get(), 3);
+    ///
+    /// ob.push_back(3);
+    /// ob.set(0, 10);
+    /// assert_eq!((&mut sum).now_or_never(), None);
+    /// assert_eq!(sum.get(), 15);
+    ///
+    /// ob.clear();
+    /// drop(ob);
+    /// assert_eq!((&mut sum).now_or_never(), Some(()));
+    /// assert_eq!(sum.get(), 0);
+    /// ```
+    pub struct FoldItems<S, A, F, G>
+    where
+        S: Stream,
+        S::Item: VectorDiffContainer,
+    {
+        // The main stream to poll items from.
+        #[pin]
+        inner_stream: S,
+
+        // The observable holding the accumulator.
+        observable: SharedObservable<A>,
+
+        // The buffered vector that is updated with the main stream's items.
+        buffered_vector: Vector<VectorDiffContainerStreamElement<S>>,
+
+        // The function called for added values.
+        add: F,
+
+        // The function called for removed values.
+        remove: G,
+    }
+}
+
+impl<S, A, F, G> FoldItems<S, A, F, G>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    A: Clone,
+    F: FnMut(A, &VectorDiffContainerStreamElement<S>) -> A,
+    G: FnMut(A, &VectorDiffContainerStreamElement<S>) -> A,
+{
+    /// Create a new `FoldItems` with the given initial values, stream of
+    /// `VectorDiff` updates for those values, initial accumulator and
+    /// functions for added and removed values.
+    pub fn new(
+        initial_values: Vector<VectorDiffContainerStreamElement<S>>,
+        inner_stream: S,
+        init: A,
+        mut add: F,
+        remove: G,
+    ) -> Self {
+        let acc = initial_values.iter().fold(init, &mut add);
+        let observable = SharedObservable::new(acc);
+        Self { inner_stream, observable, buffered_vector: initial_values, add, remove }
+    }
+
+    /// Get a clone of the current accumulator.
+    pub fn get(&self) -> A {
+        self.observable.get()
+    }
+
+    /// Obtain a new subscriber to the accumulator.
+    pub fn subscribe(&self) -> Subscriber<A> {
+        self.observable.subscribe()
+    }
+}
+
+impl<S, A, F, G> Future for FoldItems<S, A, F, G>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    A: Clone,
+    F: FnMut(A, &VectorDiffContainerStreamElement<S>) -> A,
+    G: FnMut(A, &VectorDiffContainerStreamElement<S>) -> A,
+{
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+        loop {
+            let Some(diffs) = ready!(this.inner_stream.as_mut().poll_next(cx)) else {
+                return Poll::Ready(());
+            };
+
+            let mut acc = this.observable.get();
+            for diff in diffs.into_vec() {
+                acc = fold_diff_values(
+                    this.buffered_vector,
+                    acc,
+                    diff,
+                    &mut *this.add,
+                    &mut *this.remove,
+                );
+            }
+            this.observable.set(acc);
+        }
+    }
+}
+
+impl<S, A, F, G> AdapterDebug for FoldItems<S, A, F, G>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn adapter_state(&self) -> AdapterState {
+        AdapterState {
+            buffered_len: Some(self.buffered_vector.len()),
+            ..AdapterState::new("FoldItems")
+        }
+    }
+}
+
+impl<S, A, F, G> fmt::Debug for FoldItems<S, A, F, G>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.adapter_state(), f)
+    }
+}
+
+/// Fold `diff` into `acc`, calling `add` for every value it adds and `remove`
+/// for every value of `buffered_vector` it removes, then apply it to
+/// `buffered_vector`.
+pub(super) fn fold_diff_values<T, A>(
+    buffered_vector: &mut Vector<T>,
+    acc: A,
+    diff: VectorDiff<T>,
+    mut add: impl FnMut(A, &T) -> A,
+    remove: impl FnMut(A, &T) -> A,
+) -> A
+where
+    T: Clone,
+{
+    let len = buffered_vector.len();
+
+    // Values that are removed by the diff, as a range of the buffered vector
+    // before it is applied.
+    let removed = match &diff {
+        VectorDiff::Clear | VectorDiff::Reset { .. } => 0..len,
+        VectorDiff::PopFront => 0..1,
+        VectorDiff::PopBack => len - 1..len,
+        VectorDiff::Set { index, .. } | VectorDiff::Remove { index } => *index..*index + 1,
+        VectorDiff::Truncate { length } => *length..len,
+        VectorDiff::Append { .. }
+        | VectorDiff::PushFront { .. }
+        | VectorDiff::PushBack { .. }
+        | VectorDiff::Insert { .. }
+        | VectorDiff::Move { .. } => 0..0,
+    };
+    let acc = buffered_vector.focus().narrow(removed).into_iter().fold(acc, remove);
+
+    let acc = match &diff {
+        VectorDiff::Append { values } | VectorDiff::Reset { values, .. } => {
+            values.iter().fold(acc, add)
+        }
+        VectorDiff::PushFront { value }
+        | VectorDiff::PushBack { value }
+        | VectorDiff::Insert { value, .. }
+        | VectorDiff::Set { value, .. } => add(acc, value),
+        VectorDiff::Clear
+        | VectorDiff::PopFront
+        | VectorDiff::PopBack
+        | VectorDiff::Remove { .. }
+        | VectorDiff::Truncate { .. }
+        | VectorDiff::Move { .. } => acc,
+    };
+
+    diff.apply(buffered_vector);
+    acc
+}
@@ -0,0 +1,392 @@
+use std::{
+    fmt,
+    pin::Pin,
+    task::{self, ready, Poll},
+};
+
+use eyeball_im::{Vector, VectorDiff};
+use futures_core::Stream;
+use pin_project_lite::pin_project;
+
+use super::{
+    AdapterDebug, AdapterState, VectorDiffContainer, VectorDiffContainerOps,
+    VectorDiffContainerStreamElement, VectorDiffContainerStreamMappedItem,
+};
+
+/// An item of the sectioned view presented by [`GroupBy`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Grouped<K, T> {
+    /// The header of a group, placed before the items of that group.
+    Header(K),
+
+    /// An item of the observed vector.
+    Item(T),
+}
+
+/// Type alias for the stream item type of [`GroupBy`].
+type GroupedItem<S, K> =
+    VectorDiffContainerStreamMappedItem<S, Grouped<K, VectorDiffContainerStreamElement<S>>>;
+
+/// Type alias for the buffer of diffs that are ready to be emitted by
+/// [`GroupBy`].
+type GroupedTailBuf<S, K> = <GroupedItem<S, K> as VectorDiffContainerOps<
+    Grouped<K, VectorDiffContainerStreamElement<S>>,
+>>::TailBuf;
+
+pin_project! {
+    /// A [`VectorDiff`] stream adapter that presents a sectioned view of the
+    /// underlying [`ObservableVector`]'s items.
+    ///
+    /// Consecutive items for which the key function returns equal keys form a
+    /// group, and a [`Grouped::Header`] with that key is placed before the
+    /// items of each group. Items that should end up in the same group thus
+    /// have to be next to each other, e.g. by being sorted by their key
+    /// beforehand. Upstream diffs are translated into diffs of the sectioned
+    /// view, adding and removing headers as groups are created, split, merged
+    /// or emptied.
+    ///
+    /// Finding the position of an item in the sectioned view takes time linear
+    /// in the number of items before it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use eyeball_im::{ObservableVector, VectorDiff};
+    /// use eyeball_im_util::vector::{Grouped, VectorObserverExt};
+    /// use imbl::vector;
+    /// use stream_assert::{assert_next_eq, assert_pending};
+    ///
+    /// let mut ob = ObservableVector::<&str>::from(vector!["anna", "bob", "bert"]);
+    /// let (values, mut sub) = ob.subscribe().group_by(|name| name.chars().next());
+    /// assert_eq!(
+    ///     values,
+    ///     vector![
+    ///         Grouped::Header(Some('a')),
+    ///         Grouped::Item("anna"),
+    ///         Grouped::Header(Some('b')),
+    ///         Grouped::Item("bob"),
+    ///         Grouped::Item("bert"),
+    ///     ]
+    /// );
+    ///
+    /// // Adding to an existing group
+    /// ob.insert(1, "alex");
+    /// assert_next_eq!(sub, VectorDiff::Insert { index: 2, value: Grouped::Item("alex") });
+    ///
+    /// // Emptying a group removes its header too
+    /// ob.remove(0);
+    /// ob.remove(0);
+    /// assert_next_eq!(sub, VectorDiff::Remove { index: 1 });
+    /// assert_next_eq!(sub, VectorDiff::Remove { index: 1 });
+    /// assert_next_eq!(sub, VectorDiff::Remove { index: 0 });
+    /// assert_pending!(sub);
+    /// ```
+    ///
+    /// [`ObservableVector`]: eyeball_im::ObservableVector
+    #[project = GroupByProj]
+    pub struct GroupBy<S, F, K>
+    where
+        S: Stream,
+        S::Item: VectorDiffContainer,
+    {
+        // The main stream to poll items from.
+        #[pin]
+        inner_stream: S,
+
+        // The function that computes the key of an item.
+        key_fn: F,
+
+        // The buffered vector that is updated with the main stream's items.
+        buffered_vector: Vector<VectorDiffContainerStreamElement<S>>,
+
+        // The keys of the items in `buffered_vector`.
+        keys: Vec<K>,
+
+        // Diffs that are ready to be emitted, used if the item type is just
+        // `VectorDiff<_>` (non-batched) since one update can result in
+        // multiple diffs.
+        ready_values: GroupedTailBuf<S, K>,
+    }
+}
+
+impl<S, F, K> GroupBy<S, F, K>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    F: Fn(&VectorDiffContainerStreamElement<S>) -> K,
+    K: Clone + PartialEq,
+{
+    /// Create a new `GroupBy` with the given initial values, stream of
+    /// `VectorDiff` updates for those values, and key function.
+    ///
+    /// Returns the sectioned initial values as well as a stream of updates to
+    /// them.
+    pub fn new(
+        initial_values: Vector<VectorDiffContainerStreamElement<S>>,
+        inner_stream: S,
+        key_fn: F,
+    ) -> (Vector<Grouped<K, VectorDiffContainerStreamElement<S>>>, Self) {
+        let mut keys = Vec::with_capacity(initial_values.len());
+        let values = grouped_rows(initial_values.iter().cloned(), &key_fn, &mut keys);
+        let stream = Self {
+            inner_stream,
+            key_fn,
+            buffered_vector: initial_values,
+            keys,
+            ready_values: Default::default(),
+        };
+
+        (values, stream)
+    }
+}
+
+impl<S, F, K> Stream for GroupBy<S, F, K>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    F: Fn(&VectorDiffContainerStreamElement<S>) -> K,
+    K: Clone + PartialEq,
+{
+    type Item = GroupedItem<S, K>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        self.project().poll_next(cx)
+    }
+}
+
+impl<S, F, K> AdapterDebug for GroupBy<S, F, K>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    K: PartialEq,
+{
+    fn adapter_state(&self) -> AdapterState {
+        let num_groups = (0..self.keys.len()).filter(|&i| is_group_start(&self.keys, i)).count();
+        AdapterState {
+            buffered_len: Some(self.buffered_vector.len()),
+            len: Some(self.keys.len() + num_groups),
+            ready_values: GroupedItem::<S, K>::tail_buf_len(&self.ready_values),
+            ..AdapterState::new("GroupBy")
+        }
+    }
+}
+
+impl<S, F, K> fmt::Debug for GroupBy<S, F, K>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    K: PartialEq,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.adapter_state(), f)
+    }
+}
+
+impl<S, F, K> GroupByProj<'_, S, F, K>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    F: Fn(&VectorDiffContainerStreamElement<S>) -> K,
+    K: Clone + PartialEq,
+{
+    fn poll_next(&mut self, cx: &mut task::Context<'_>) -> Poll<Option<GroupedItem<S, K>>> {
+        loop {
+            // First off, if any values are ready, return them.
+            if let Some(value) = GroupedItem::<S, K>::pop_from_tail_buf(self.ready_values) {
+                return Poll::Ready(Some(value));
+            }
+
+            // Poll `VectorDiff`s from the `inner_stream`.
+            let Some(diffs) = ready!(self.inner_stream.as_mut().poll_next(cx)) else {
+                return Poll::Ready(None);
+            };
+
+            let mut res = Vec::new();
+            for diff in diffs.into_vec() {
+                self.handle_diff(diff, &mut res);
+            }
+
+            if let Some(diffs) = GroupedItem::<S, K>::extend_tail_buf(res, self.ready_values) {
+                return Poll::Ready(Some(diffs));
+            }
+
+            // Else loop and poll the streams again.
+        }
+    }
+
+    fn handle_diff(
+        &mut self,
+        diff: VectorDiff<VectorDiffContainerStreamElement<S>>,
+        res: &mut Vec<VectorDiff<Grouped<K, VectorDiffContainerStreamElement<S>>>>,
+    ) {
+        // Keep the `buffered_vector` in sync with the observed `Vector`, it's
+        // needed to re-insert moved items.
+        diff.clone().apply(self.buffered_vector);
+
+        match diff {
+            VectorDiff::Append { values } => {
+                let first = self.keys.len();
+                let mut rows = grouped_rows(values.into_iter(), &*self.key_fn, self.keys);
+                // Continue the last group instead of starting a new one if the
+                // first key matches it.
+                if first > 0 && first < self.keys.len() && !is_group_start(self.keys, first) {
+                    rows.pop_front();
+                }
+                if !rows.is_empty() {
+                    res.push(VectorDiff::Append { values: rows });
+                }
+            }
+            VectorDiff::Clear => {
+                self.keys.clear();
+                res.push(VectorDiff::Clear);
+            }
+            VectorDiff::PushFront { value } => {
+                self.insert(0, value, res);
+            }
+            VectorDiff::PushBack { value } => {
+                self.insert(self.keys.len(), value, res);
+            }
+            VectorDiff::PopFront => {
+                self.remove(0, res);
+            }
+            VectorDiff::PopBack => {
+                self.remove(self.keys.len() - 1, res);
+            }
+            VectorDiff::Insert { index, value } => {
+                self.insert(index, value, res);
+            }
+            VectorDiff::Set { index, value } => {
+                let key = (self.key_fn)(&value);
+                if key == self.keys[index] {
+                    let index = row_index(self.keys, index);
+                    res.push(VectorDiff::Set { index, value: Grouped::Item(value) });
+                } else {
+                    self.remove(index, res);
+                    self.insert(index, value, res);
+                }
+            }
+            VectorDiff::Remove { index } => {
+                self.remove(index, res);
+            }
+            VectorDiff::Truncate { length } => {
+                if length == 0 {
+                    res.push(VectorDiff::Clear);
+                } else {
+                    res.push(VectorDiff::Truncate { length: row_index(self.keys, length - 1) + 1 });
+                }
+                self.keys.truncate(length);
+            }
+            VectorDiff::Move { from, to } => {
+                self.remove(from, res);
+                self.insert(to, self.buffered_vector[to].clone(), res);
+            }
+            VectorDiff::Reset { values, reason } => {
+                self.keys.clear();
+                let values = grouped_rows(values.into_iter(), &*self.key_fn, self.keys);
+                res.push(VectorDiff::Reset { values, reason });
+            }
+        }
+    }
+
+    /// Insert `value` at `index` of the observed vector.
+    fn insert(
+        &mut self,
+        index: usize,
+        value: VectorDiffContainerStreamElement<S>,
+        res: &mut Vec<VectorDiff<Grouped<K, VectorDiffContainerStreamElement<S>>>>,
+    ) {
+        let key = (self.key_fn)(&value);
+        let prev = index.checked_sub(1).map(|i| &self.keys[i]);
+        let next = self.keys.get(index);
+
+        // The row right after the previous item.
+        let row = match index {
+            0 => 0,
+            _ => row_index(self.keys, index - 1) + 1,
+        };
+
+        if prev == Some(&key) {
+            // Append to the group of the previous item.
+            res.push(VectorDiff::Insert { index: row, value: Grouped::Item(value) });
+        } else if next == Some(&key) {
+            // Prepend to the group of the next item, after its header.
+            res.push(VectorDiff::Insert { index: row + 1, value: Grouped::Item(value) });
+        } else {
+            // Start a new group, which splits the group of the previous and
+            // next items if they are the same.
+            let split_key = prev.filter(|&prev| Some(prev) == next).cloned();
+            res.push(VectorDiff::Insert { index: row, value: Grouped::Header(key.clone()) });
+            res.push(VectorDiff::Insert { index: row + 1, value: Grouped::Item(value) });
+            if let Some(split_key) = split_key {
+                res.push(VectorDiff::Insert { index: row + 2, value: Grouped::Header(split_key) });
+            }
+        }
+
+        self.keys.insert(index, key);
+    }
+
+    /// Remove the item at `index` of the observed vector.
+    fn remove(
+        &mut self,
+        index: usize,
+        res: &mut Vec<VectorDiff<Grouped<K, VectorDiffContainerStreamElement<S>>>>,
+    ) {
+        let row = row_index(self.keys, index);
+        let key = &self.keys[index];
+        let prev = index.checked_sub(1).map(|i| &self.keys[i]);
+        let next = self.keys.get(index + 1);
+
+        res.push(VectorDiff::Remove { index: row });
+        if prev != Some(key) && next != Some(key) {
+            // The group is empty now, remove its header.
+            res.push(VectorDiff::Remove { index: row - 1 });
+
+            // The groups of the previous and next items are merged if they
+            // are the same, remove the header of the latter.
+            if prev.is_some() && prev == next {
+                res.push(VectorDiff::Remove { index: row - 1 });
+            }
+        }
+
+        self.keys.remove(index);
+    }
+}
+
+/// Whether the item at `index` is the first item of its group.
+fn is_group_start<K: PartialEq>(keys: &[K], index: usize) -> bool {
+    index == 0 || keys[index - 1] != keys[index]
+}
+
+/// Get the row of the item at `index` in the sectioned view.
+fn row_index<K: PartialEq>(keys: &[K], index: usize) -> usize {
+    index + (0..=index).filter(|&i| is_group_start(keys, i)).count()
+}
+
+/// Compute the rows of the sectioned view for `values`, which come after the
+/// items whose keys are in `keys`.
+///
+/// The keys of `values` are pushed to `keys`. A header is emitted for the first
+/// value even if its key equals the last one in `keys`.
+fn grouped_rows<T, K>(
+    values: impl Iterator<Item = T>,
+    key_fn: &impl Fn(&T) -> K,
+    keys: &mut Vec<K>,
+) -> Vector<Grouped<K, T>>
+where
+    T: Clone,
+    K: Clone + PartialEq,
+{
+    let mut rows = Vector::new();
+    let mut prev_key: Option<K> = None;
+    for value in values {
+        let key = key_fn(&value);
+        if prev_key.as_ref() != Some(&key) {
+            rows.push_back(Grouped::Header(key.clone()));
+        }
+        keys.push(key.clone());
+        prev_key = Some(key);
+        rows.push_back(Grouped::Item(value));
+    }
+
+    rows
+}
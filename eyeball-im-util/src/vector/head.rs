@@ -1,15 +1,16 @@
 use arrayvec::ArrayVec;
 use std::{
     cmp::{min, Ordering},
-    mem,
+    fmt, mem,
     pin::Pin,
     task::{self, ready, Poll},
 };
 
 use super::{
-    VectorDiffContainer, VectorDiffContainerOps, VectorDiffContainerStreamElement,
-    VectorDiffContainerStreamHeadBuf, VectorObserver,
+    AdapterDebug, AdapterState, VectorDiffContainer, VectorDiffContainerOps,
+    VectorDiffContainerStreamElement, VectorDiffContainerStreamHeadBuf, VectorObserver,
 };
+use eyeball::{SharedObservable, Subscriber};
 use eyeball_im::VectorDiff;
 use futures_core::Stream;
 use imbl::Vector;
@@ -32,7 +33,9 @@ pin_project! {
     /// items of the `Vector` have a non-negligible size.
     ///
     /// It's okay to have a limit larger than the length of the observed
-    /// `Vector`.
+    /// `Vector`. If the view should always contain exactly `limit` items
+    /// instead, e.g. to show skeleton rows while loading, use
+    /// [`PaddedHead`](super::PaddedHead), which pads it with placeholders.
     ///
     /// # Examples
     ///
@@ -110,6 +113,9 @@ pin_project! {
         // is removed, but 12 has to be pushed back as it "enters" the "view".
         // That second `PushBack` diff is buffered here.
         ready_values: VectorDiffContainerStreamHeadBuf<S>,
+
+        // Whether the view is full and whether items exist beyond it.
+        saturation: SharedObservable<Saturation>,
     }
 }
 
@@ -152,12 +158,15 @@ where
         inner_stream: S,
         limit_stream: L,
     ) -> Self {
+        let saturation = SharedObservable::new(Saturation::new(initial_values.len(), 0));
+
         Self {
             inner_stream,
             limit_stream,
             buffered_vector: initial_values,
             limit: 0,
             ready_values: Default::default(),
+            saturation,
         }
     }
 
@@ -171,6 +180,7 @@ where
         limit_stream: L,
     ) -> (Vector<VectorDiffContainerStreamElement<S>>, Self) {
         let buffered_vector = initial_values.clone();
+        let buffered_len = buffered_vector.len();
         if initial_limit < initial_values.len() {
             initial_values.truncate(initial_limit);
         }
@@ -181,10 +191,25 @@ where
             buffered_vector,
             limit: initial_limit,
             ready_values: Default::default(),
+            saturation: SharedObservable::new(Saturation::new(buffered_len, initial_limit)),
         };
 
         (initial_values, stream)
     }
+
+    /// Get the current [`Saturation`] of the view.
+    pub fn saturation(&self) -> Saturation {
+        self.saturation.get()
+    }
+
+    /// Get a [`Subscriber`] that yields a new [`Saturation`] every time the
+    /// view becomes (or stops being) full, or items beyond the limit appear
+    /// or disappear.
+    ///
+    /// The saturation is updated whenever this stream is polled.
+    pub fn subscribe_saturation(&self) -> Subscriber<Saturation> {
+        self.saturation.subscribe()
+    }
 }
 
 impl<S, L> Stream for Head<S, L>
@@ -196,7 +221,10 @@ where
     type Item = S::Item;
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
-        self.project().poll_next(cx)
+        let mut this = self.project();
+        let result = this.poll_next(cx);
+        this.update_saturation();
+        result
     }
 }
 
@@ -213,12 +241,42 @@ where
     }
 }
 
+impl<S, L> AdapterDebug for Head<S, L>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn adapter_state(&self) -> AdapterState {
+        AdapterState {
+            buffered_len: Some(self.buffered_vector.len()),
+            len: Some(min(self.buffered_vector.len(), self.limit)),
+            limit: Some(self.limit),
+            ready_values: S::Item::head_buf_len(&self.ready_values),
+            ..AdapterState::new("Head")
+        }
+    }
+}
+
+impl<S, L> fmt::Debug for Head<S, L>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.adapter_state(), f)
+    }
+}
+
 impl<S, L> HeadProj<'_, S, L>
 where
     S: Stream,
     S::Item: VectorDiffContainer,
     L: Stream<Item = usize>,
 {
+    fn update_saturation(&self) {
+        self.saturation.set_if_not_eq(Saturation::new(self.buffered_vector.len(), *self.limit));
+    }
+
     fn poll_next(&mut self, cx: &mut task::Context<'_>) -> Poll<Option<S::Item>> {
         loop {
             // First off, if any values are ready, return them.
@@ -433,16 +491,53 @@ fn handle_diff<T: Clone>(
                 res.push(VectorDiff::Truncate { length: new_length });
             }
         }
-        VectorDiff::Reset { values: mut new_values } => {
+        VectorDiff::Move { from, to } => match (from < limit, to < limit) {
+            (true, true) => {
+                res.push(VectorDiff::Move { from, to });
+            }
+            (true, false) => {
+                // The item leaves the view, the next one enters it at the back.
+                res.push(VectorDiff::Remove { index: from });
+                res.push(VectorDiff::PushBack { value: buffered_vector[limit - 1].clone() });
+            }
+            (false, true) => {
+                // The item enters the view, the last one leaves it.
+                res.push(VectorDiff::PopBack);
+                res.push(VectorDiff::Insert { index: to, value: buffered_vector[to].clone() });
+            }
+            (false, false) => {
+                // Move after `limit`, ignore the diff.
+            }
+        },
+        VectorDiff::Reset { values: mut new_values, reason } => {
             if new_values.len() > limit {
                 // There are too many values, truncate.
                 new_values.truncate(limit);
             }
 
             // There is space for these new items.
-            res.push(VectorDiff::Reset { values: new_values });
+            res.push(VectorDiff::Reset { values: new_values, reason });
         }
     }
 
     res
 }
+
+/// Whether the view of a [`Head`] or [`Tail`](super::Tail) adapter is
+/// saturated, see [`Head::saturation`] and [`Tail::saturation`].
+///
+/// [`Tail::saturation`]: super::Tail::saturation
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Saturation {
+    /// Whether the view holds as many items as the limit allows.
+    pub is_full: bool,
+    /// Whether the underlying vector holds items that are beyond the limit,
+    /// i.e. that are not part of the view.
+    pub has_more: bool,
+}
+
+impl Saturation {
+    pub(super) fn new(len: usize, limit: usize) -> Self {
+        Self { is_full: len >= limit, has_more: len > limit }
+    }
+}
@@ -0,0 +1,333 @@
+use std::{
+    fmt,
+    hash::Hash,
+    mem,
+    pin::Pin,
+    task::{self, ready, Poll},
+};
+
+use eyeball_im::{MapDiff, Vector, VectorDiff};
+use futures_core::Stream;
+use imbl::HashMap;
+use pin_project_lite::pin_project;
+
+use super::{
+    AdapterDebug, AdapterState, VectorDiffContainer, VectorDiffContainerOps,
+    VectorDiffContainerStreamElement, VectorDiffContainerStreamMappedItem,
+};
+
+/// Type alias for the buffer of diffs that are ready to be emitted by
+/// [`JoinMap`].
+type MappedTailBuf<S, U> =
+    <VectorDiffContainerStreamMappedItem<S, U> as VectorDiffContainerOps<U>>::TailBuf;
+
+pin_project! {
+    /// A [`VectorDiff`] stream adapter that joins the underlying
+    /// [`ObservableVector`]'s items with the entries of an [`ObservableMap`].
+    ///
+    /// Each item is mapped along with the map entry under its key, as returned
+    /// by a key function, or `None` if there is no such entry. Whenever the
+    /// map entry under a key is inserted, updated or removed, the items with
+    /// that key are mapped again and a `VectorDiff::Set` is emitted for each
+    /// of them.
+    ///
+    /// A typical use case is a list of user IDs that is joined with a cache of
+    /// user profiles, which is filled in as the profiles are loaded.
+    ///
+    /// Changes of the map are applied before the changes of the vector that
+    /// are ready at the same time. If the stream of map changes ends, the
+    /// last state of the map is kept.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use eyeball_im::{ObservableMap, ObservableVector, VectorDiff};
+    /// use eyeball_im_util::vector::VectorObserverExt;
+    /// use imbl::vector;
+    /// use stream_assert::{assert_next_eq, assert_pending};
+    ///
+    /// let mut user_ids = ObservableVector::from(vector!["@alice", "@bob"]);
+    /// let mut profiles = ObservableMap::new();
+    /// profiles.insert("@alice", "Alice");
+    ///
+    /// let (values, mut sub) = user_ids.subscribe().join_map(
+    ///     profiles.subscribe(),
+    ///     |user_id| *user_id,
+    ///     |user_id, name| name.copied().unwrap_or(user_id),
+    /// );
+    /// assert_eq!(values, vector!["Alice", "@bob"]);
+    ///
+    /// profiles.insert("@bob", "Bob");
+    /// assert_next_eq!(sub, VectorDiff::Set { index: 1, value: "Bob" });
+    ///
+    /// user_ids.push_back("@alice");
+    /// assert_next_eq!(sub, VectorDiff::PushBack { value: "Alice" });
+    ///
+    /// profiles.remove("@alice");
+    /// assert_next_eq!(sub, VectorDiff::Set { index: 0, value: "@alice" });
+    /// assert_next_eq!(sub, VectorDiff::Set { index: 2, value: "@alice" });
+    /// assert_pending!(sub);
+    /// ```
+    ///
+    /// [`ObservableVector`]: eyeball_im::ObservableVector
+    /// [`ObservableMap`]: eyeball_im::ObservableMap
+    #[project = JoinMapProj]
+    pub struct JoinMap<S, M, K, V, KF, F, U>
+    where
+        S: Stream,
+        S::Item: VectorDiffContainer,
+        U: Clone,
+        U: 'static,
+    {
+        // The main stream to poll items from.
+        #[pin]
+        inner_stream: S,
+
+        // The stream of changes to the map.
+        #[pin]
+        map_stream: M,
+
+        // Whether `map_stream` has ended.
+        map_stream_done: bool,
+
+        // The function that returns the key of an item.
+        key_fn: KF,
+
+        // The function that maps an item and its map entry.
+        f: F,
+
+        // The buffered vector that is updated with the main stream's items.
+        buffered_vector: Vector<VectorDiffContainerStreamElement<S>>,
+
+        // The map that is updated with the map stream's changes.
+        map: HashMap<K, V>,
+
+        // Diffs that are ready to be emitted, used if the item type is just
+        // `VectorDiff<_>` (non-batched) since one update can result in
+        // multiple diffs.
+        ready_values: MappedTailBuf<S, U>,
+    }
+}
+
+impl<S, M, K, V, KF, F, U> JoinMap<S, M, K, V, KF, F, U>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    M: Stream<Item = MapDiff<K, V>>,
+    K: Hash + Eq + Clone,
+    V: Clone,
+    KF: Fn(&VectorDiffContainerStreamElement<S>) -> K,
+    F: Fn(VectorDiffContainerStreamElement<S>, Option<&V>) -> U,
+    U: Clone + 'static,
+{
+    /// Create a new `JoinMap` with the given initial values, stream of
+    /// `VectorDiff` updates for those values, initial map entries, stream of
+    /// `MapDiff` updates for those entries, key function and mapping function.
+    ///
+    /// Returns the mapped initial values as well as a stream of updates to
+    /// them.
+    pub fn new(
+        initial_values: Vector<VectorDiffContainerStreamElement<S>>,
+        inner_stream: S,
+        map: HashMap<K, V>,
+        map_stream: M,
+        key_fn: KF,
+        f: F,
+    ) -> (Vector<U>, Self) {
+        let values = join(&initial_values, &map, &key_fn, &f);
+        let stream = Self {
+            inner_stream,
+            map_stream,
+            map_stream_done: false,
+            key_fn,
+            f,
+            buffered_vector: initial_values,
+            map,
+            ready_values: Default::default(),
+        };
+
+        (values, stream)
+    }
+}
+
+impl<S, M, K, V, KF, F, U> Stream for JoinMap<S, M, K, V, KF, F, U>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    M: Stream<Item = MapDiff<K, V>>,
+    K: Hash + Eq + Clone,
+    V: Clone,
+    KF: Fn(&VectorDiffContainerStreamElement<S>) -> K,
+    F: Fn(VectorDiffContainerStreamElement<S>, Option<&V>) -> U,
+    U: Clone + 'static,
+{
+    type Item = VectorDiffContainerStreamMappedItem<S, U>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        self.project().poll_next(cx)
+    }
+}
+
+impl<S, M, K, V, KF, F, U> AdapterDebug for JoinMap<S, M, K, V, KF, F, U>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    U: Clone + 'static,
+{
+    fn adapter_state(&self) -> AdapterState {
+        AdapterState {
+            buffered_len: Some(self.buffered_vector.len()),
+            ready_values: VectorDiffContainerStreamMappedItem::<S, U>::tail_buf_len(
+                &self.ready_values,
+            ),
+            ..AdapterState::new("JoinMap")
+        }
+    }
+}
+
+impl<S, M, K, V, KF, F, U> fmt::Debug for JoinMap<S, M, K, V, KF, F, U>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    U: Clone + 'static,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.adapter_state(), f)
+    }
+}
+
+impl<S, M, K, V, KF, F, U> JoinMapProj<'_, S, M, K, V, KF, F, U>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    M: Stream<Item = MapDiff<K, V>>,
+    K: Hash + Eq + Clone,
+    V: Clone,
+    KF: Fn(&VectorDiffContainerStreamElement<S>) -> K,
+    F: Fn(VectorDiffContainerStreamElement<S>, Option<&V>) -> U,
+    U: Clone + 'static,
+{
+    fn poll_next(
+        &mut self,
+        cx: &mut task::Context<'_>,
+    ) -> Poll<Option<VectorDiffContainerStreamMappedItem<S, U>>> {
+        loop {
+            // First off, if any values are ready, return them.
+            if let Some(value) =
+                VectorDiffContainerStreamMappedItem::<S, U>::pop_from_tail_buf(self.ready_values)
+            {
+                return Poll::Ready(Some(value));
+            }
+
+            // Poll changes of the map before polling `inner_stream`.
+            while !*self.map_stream_done {
+                let Poll::Ready(map_diff) = self.map_stream.as_mut().poll_next(cx) else {
+                    break;
+                };
+                let Some(map_diff) = map_diff else {
+                    *self.map_stream_done = true;
+                    break;
+                };
+
+                let res = self.handle_map_diff(map_diff);
+                if let Some(diffs) = VectorDiffContainerStreamMappedItem::<S, U>::extend_tail_buf(
+                    res,
+                    self.ready_values,
+                ) {
+                    return Poll::Ready(Some(diffs));
+                }
+            }
+
+            // Poll `VectorDiff`s from the `inner_stream`.
+            let Some(diffs) = ready!(self.inner_stream.as_mut().poll_next(cx)) else {
+                return Poll::Ready(None);
+            };
+
+            let res = diffs.into_vec().into_iter().map(|diff| self.handle_diff(diff)).collect();
+            if let Some(diffs) =
+                VectorDiffContainerStreamMappedItem::<S, U>::extend_tail_buf(res, self.ready_values)
+            {
+                return Poll::Ready(Some(diffs));
+            }
+
+            // Else loop and poll the streams again.
+        }
+    }
+
+    fn handle_diff(
+        &mut self,
+        diff: VectorDiff<VectorDiffContainerStreamElement<S>>,
+    ) -> VectorDiff<U> {
+        diff.clone().apply(self.buffered_vector);
+
+        let (map, key_fn, f) = (&*self.map, &*self.key_fn, &*self.f);
+        diff.map(|value| join_one(value, map, key_fn, f))
+    }
+
+    fn handle_map_diff(&mut self, map_diff: MapDiff<K, V>) -> Vec<VectorDiff<U>> {
+        // Update the map, keeping the previous entries if they are all replaced.
+        let old_map = match map_diff {
+            MapDiff::Insert { key, value } | MapDiff::Update { key, value } => {
+                let changed_key = key.clone();
+                self.map.insert(key, value);
+                return self.remap(|key| *key == changed_key);
+            }
+            MapDiff::Remove { key } => {
+                if self.map.remove(&key).is_none() {
+                    return Vec::new();
+                }
+                return self.remap(|k| *k == key);
+            }
+            MapDiff::Clear => mem::take(self.map),
+            MapDiff::Reset { values } => mem::replace(self.map, values),
+        };
+
+        let map = &*self.map;
+        self.remap(|key| old_map.contains_key(key) || map.contains_key(key))
+    }
+
+    /// Map the items of the buffered vector whose key matches `affected`
+    /// again, returning a `VectorDiff::Set` for each of them.
+    fn remap(&self, affected: impl Fn(&K) -> bool) -> Vec<VectorDiff<U>> {
+        self.buffered_vector
+            .iter()
+            .enumerate()
+            .filter(|(_, value)| affected(&(*self.key_fn)(value)))
+            .map(|(index, value)| {
+                let value = join_one(value.clone(), &*self.map, &*self.key_fn, &*self.f);
+                VectorDiff::Set { index, value }
+            })
+            .collect()
+    }
+}
+
+/// Map `value` along with its entry in `map`.
+fn join_one<T, K, V, U>(
+    value: T,
+    map: &HashMap<K, V>,
+    key_fn: &impl Fn(&T) -> K,
+    f: &impl Fn(T, Option<&V>) -> U,
+) -> U
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+{
+    let key = key_fn(&value);
+    f(value, map.get(&key))
+}
+
+/// Map all of `values` along with their entries in `map`.
+fn join<T, K, V, U>(
+    values: &Vector<T>,
+    map: &HashMap<K, V>,
+    key_fn: &impl Fn(&T) -> K,
+    f: &impl Fn(T, Option<&V>) -> U,
+) -> Vector<U>
+where
+    T: Clone,
+    K: Hash + Eq + Clone,
+    V: Clone,
+    U: Clone,
+{
+    values.iter().map(|value| join_one(value.clone(), map, key_fn, f)).collect()
+}
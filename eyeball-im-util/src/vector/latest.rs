@@ -0,0 +1,248 @@
+use std::{
+    fmt,
+    pin::Pin,
+    task::{self, Poll},
+};
+
+use eyeball::Subscriber;
+use eyeball_im::{Vector, VectorDiff};
+use futures_core::Stream;
+use pin_project_lite::pin_project;
+
+use super::{
+    AdapterDebug, AdapterState, VectorDiffContainer, VectorDiffContainerOps,
+    VectorDiffContainerStreamMappedItem, VectorObserver,
+};
+
+/// Turn an observed vector of [`Subscriber`]s into an observed vector of their
+/// latest values.
+///
+/// See [`LatestOfEach`] for more details.
+pub fn latest_of_each<T, O>(sources: O) -> (Vector<T>, LatestOfEach<O::Stream, T>)
+where
+    T: Clone + 'static,
+    O: VectorObserver<Subscriber<T>>,
+    <O::Stream as Stream>::Item: VectorDiffContainer<Element = Subscriber<T>>,
+{
+    let (subscribers, inner_stream) = sources.into_parts();
+    let mut sources = Vec::with_capacity(subscribers.len());
+    let values = subscribers.into_iter().map(|sub| push_source(&mut sources, sub)).collect();
+
+    let stream = LatestOfEach { inner_stream, sources, next_source: 0, inner_stream_done: false };
+    (values, stream)
+}
+
+pin_project! {
+    /// A [`VectorDiff`] stream that maintains a vector of the latest values
+    /// of a changing set of [`Subscriber`]s.
+    ///
+    /// The set of subscribers is itself an observed vector, usually an
+    /// [`ObservableVector`] of subscribers: adding, removing or moving a
+    /// subscriber in it results in the corresponding change to the vector of
+    /// values, and every update of one of the subscribed observables results
+    /// in a [`VectorDiff::Set`] at that subscriber's position. This makes the
+    /// vector adapters (sorting, filtering, …) usable for the latest values of
+    /// any number of scalar observables.
+    ///
+    /// Subscribers are polled fairly. If an observable is dropped, the last
+    /// value observed from it is kept until its subscriber is removed. The stream ends once
+    /// the stream of subscribers has ended and all observables have been
+    /// dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use eyeball::Observable;
+    /// use eyeball_im::{ObservableVector, VectorDiff};
+    /// use eyeball_im_util::vector::latest_of_each;
+    /// use imbl::vector;
+    /// use stream_assert::{assert_next_eq, assert_pending};
+    ///
+    /// let mut alice = Observable::new(3);
+    /// let mut bob = Observable::new(5);
+    ///
+    /// let mut sources = ObservableVector::new();
+    /// sources.push_back(Observable::subscribe(&alice));
+    ///
+    /// let (values, mut sub) = latest_of_each(sources.subscribe());
+    /// assert_eq!(values, vector![3]);
+    ///
+    /// sources.push_back(Observable::subscribe(&bob));
+    /// assert_next_eq!(sub, VectorDiff::PushBack { value: 5 });
+    ///
+    /// Observable::set(&mut alice, 4);
+    /// assert_next_eq!(sub, VectorDiff::Set { index: 0, value: 4 });
+    ///
+    /// sources.remove(0);
+    /// assert_next_eq!(sub, VectorDiff::Remove { index: 0 });
+    ///
+    /// Observable::set(&mut alice, 10);
+    /// Observable::set(&mut bob, 6);
+    /// assert_next_eq!(sub, VectorDiff::Set { index: 0, value: 6 });
+    /// assert_pending!(sub);
+    /// ```
+    ///
+    /// [`ObservableVector`]: eyeball_im::ObservableVector
+    pub struct LatestOfEach<S, T> {
+        // The stream of changes to the set of subscribers.
+        #[pin]
+        inner_stream: S,
+
+        // The subscribers, in the order of their values.
+        sources: Vec<Source<T>>,
+
+        // The source to poll first, such that all sources get polled fairly.
+        next_source: usize,
+
+        // Whether `inner_stream` has ended.
+        inner_stream_done: bool,
+    }
+}
+
+struct Source<T> {
+    subscriber: Subscriber<T>,
+    // Whether the observable was dropped.
+    closed: bool,
+}
+
+impl<S, T> Stream for LatestOfEach<S, T>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer<Element = Subscriber<T>>,
+    T: Clone + 'static,
+{
+    type Item = VectorDiffContainerStreamMappedItem<S, T>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        // Changes to the set of subscribers come first, so that the indices of
+        // value updates are always in line with what was emitted before.
+        while !*this.inner_stream_done {
+            match this.inner_stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(diffs)) => {
+                    let sources = &mut *this.sources;
+                    if let Some(diffs) = diffs.filter_map(|diff| Some(handle_diff(sources, diff))) {
+                        return Poll::Ready(Some(diffs));
+                    }
+                }
+                Poll::Ready(None) => *this.inner_stream_done = true,
+                Poll::Pending => break,
+            }
+        }
+
+        let source_count = this.sources.len();
+        for i in (0..source_count).map(|i| (*this.next_source + i) % source_count) {
+            let source = &mut this.sources[i];
+            if source.closed {
+                continue;
+            }
+
+            match Pin::new(&mut source.subscriber).poll_next(cx) {
+                Poll::Ready(Some(value)) => {
+                    *this.next_source = (i + 1) % source_count;
+                    let diff = VectorDiff::Set { index: i, value };
+                    return Poll::Ready(Some(
+                        VectorDiffContainerStreamMappedItem::<S, T>::from_item(diff),
+                    ));
+                }
+                Poll::Ready(None) => source.closed = true,
+                Poll::Pending => {}
+            }
+        }
+
+        if *this.inner_stream_done && this.sources.iter().all(|source| source.closed) {
+            Poll::Ready(None)
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+impl<S, T> AdapterDebug for LatestOfEach<S, T> {
+    fn adapter_state(&self) -> AdapterState {
+        AdapterState { len: Some(self.sources.len()), ..AdapterState::new("LatestOfEach") }
+    }
+}
+
+impl<S, T> fmt::Debug for LatestOfEach<S, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.adapter_state(), f)
+    }
+}
+
+/// Apply a change of the set of subscribers to `sources`, and translate it to
+/// the corresponding change of the set of values.
+fn handle_diff<T: Clone>(
+    sources: &mut Vec<Source<T>>,
+    diff: VectorDiff<Subscriber<T>>,
+) -> VectorDiff<T> {
+    match diff {
+        VectorDiff::Append { values } => {
+            let values = values.into_iter().map(|sub| push_source(sources, sub)).collect();
+            VectorDiff::Append { values }
+        }
+        VectorDiff::Clear => {
+            sources.clear();
+            VectorDiff::Clear
+        }
+        VectorDiff::PushFront { value } => {
+            let (source, value) = make_source(value);
+            sources.insert(0, source);
+            VectorDiff::PushFront { value }
+        }
+        VectorDiff::PushBack { value } => {
+            let value = push_source(sources, value);
+            VectorDiff::PushBack { value }
+        }
+        VectorDiff::PopFront => {
+            sources.remove(0);
+            VectorDiff::PopFront
+        }
+        VectorDiff::PopBack => {
+            sources.pop();
+            VectorDiff::PopBack
+        }
+        VectorDiff::Insert { index, value } => {
+            let (source, value) = make_source(value);
+            sources.insert(index, source);
+            VectorDiff::Insert { index, value }
+        }
+        VectorDiff::Set { index, value } => {
+            let (source, value) = make_source(value);
+            sources[index] = source;
+            VectorDiff::Set { index, value }
+        }
+        VectorDiff::Remove { index } => {
+            sources.remove(index);
+            VectorDiff::Remove { index }
+        }
+        VectorDiff::Truncate { length } => {
+            sources.truncate(length);
+            VectorDiff::Truncate { length }
+        }
+        VectorDiff::Move { from, to } => {
+            let source = sources.remove(from);
+            sources.insert(to, source);
+            VectorDiff::Move { from, to }
+        }
+        VectorDiff::Reset { values, reason } => {
+            sources.clear();
+            let values = values.into_iter().map(|sub| push_source(sources, sub)).collect();
+            VectorDiff::Reset { values, reason }
+        }
+    }
+}
+
+fn make_source<T: Clone>(mut subscriber: Subscriber<T>) -> (Source<T>, T) {
+    // Mark the current value as observed, it is part of the diff that adds
+    // the subscriber already.
+    let value = subscriber.next_now();
+    (Source { subscriber, closed: false }, value)
+}
+
+fn push_source<T: Clone>(sources: &mut Vec<Source<T>>, subscriber: Subscriber<T>) -> T {
+    let (source, value) = make_source(subscriber);
+    sources.push(source);
+    value
+}
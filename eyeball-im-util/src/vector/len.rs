@@ -0,0 +1,106 @@
+use std::{
+    fmt,
+    pin::Pin,
+    task::{self, ready, Poll},
+};
+
+use futures_core::Stream;
+use pin_project_lite::pin_project;
+
+use super::{
+    empty::len_after, AdapterDebug, AdapterState, VectorDiffContainer, VectorDiffContainerOps,
+};
+
+pin_project! {
+    /// A stream that yields the length of the observed vector whenever it
+    /// changes.
+    ///
+    /// Created with [`len_observable`]. Unlike
+    /// [`VectorLenSubscriber`], this works at any point of an adapter chain,
+    /// e.g. to count the items of a filtered view. The diffs of the underlying
+    /// stream are consumed. If the length changed multiple times for a single
+    /// item of the underlying stream (a batch of diffs), only the final length
+    /// is yielded.
+    ///
+    /// ```rust
+    /// use eyeball_im::ObservableVector;
+    /// use eyeball_im_util::vector::VectorObserverExt;
+    /// use imbl::vector;
+    /// use stream_assert::{assert_next_eq, assert_pending};
+    ///
+    /// let mut ob = ObservableVector::<u32>::from(vector![1, 2, 3]);
+    /// let mut len = ob.subscribe().filter(|v| v % 2 == 1).len_observable();
+    /// assert_eq!(len.get(), 2);
+    ///
+    /// ob.push_back(5);
+    /// assert_next_eq!(len, 3);
+    ///
+    /// // Filtered out, the length stays the same.
+    /// ob.push_back(6);
+    /// assert_pending!(len);
+    /// ```
+    ///
+    /// [`len_observable`]: super::VectorObserverExt::len_observable
+    /// [`VectorLenSubscriber`]: eyeball_im::VectorLenSubscriber
+    pub struct ObservedLen<S> {
+        #[pin]
+        inner: S,
+        len: usize,
+    }
+}
+
+impl<S> ObservedLen<S>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    /// Create a new `ObservedLen` with the given initial length and stream of
+    /// `VectorDiff` updates.
+    pub fn new(len: usize, inner: S) -> Self {
+        Self { inner, len }
+    }
+
+    /// Get the latest length this stream has seen.
+    ///
+    /// Initially, this is the length of the observed values at the time of
+    /// creating the stream.
+    pub fn get(&self) -> usize {
+        self.len
+    }
+}
+
+impl<S> Stream for ObservedLen<S>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    type Item = usize;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        loop {
+            let Some(diffs) = ready!(this.inner.as_mut().poll_next(cx)) else {
+                return Poll::Ready(None);
+            };
+
+            let mut len = *this.len;
+            diffs.for_each_diff(|diff| len = len_after(diff, len));
+            if len != *this.len {
+                *this.len = len;
+                return Poll::Ready(Some(len));
+            }
+        }
+    }
+}
+
+impl<S> AdapterDebug for ObservedLen<S> {
+    fn adapter_state(&self) -> AdapterState {
+        AdapterState { len: Some(self.len), ..AdapterState::new("ObservedLen") }
+    }
+}
+
+impl<S> fmt::Debug for ObservedLen<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.adapter_state(), f)
+    }
+}
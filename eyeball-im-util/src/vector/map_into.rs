@@ -0,0 +1,93 @@
+use std::{
+    fmt,
+    marker::PhantomData,
+    pin::Pin,
+    task::{self, ready, Poll},
+};
+
+use eyeball_im::Vector;
+use futures_core::Stream;
+use pin_project_lite::pin_project;
+
+use super::{
+    AdapterDebug, AdapterState, VectorDiffContainer, VectorDiffContainerOps,
+    VectorDiffContainerStreamElement, VectorDiffContainerStreamMappedItem,
+};
+
+pin_project! {
+    /// A [`VectorDiff`] stream adapter that converts the underlying
+    /// [`ObservableVector`]'s items to another type using [`Into`].
+    ///
+    /// ```rust
+    /// use eyeball_im::{ObservableVector, VectorDiff};
+    /// use eyeball_im_util::vector::VectorObserverExt;
+    /// use imbl::vector;
+    /// use stream_assert::assert_next_eq;
+    ///
+    /// let mut ob = ObservableVector::<u8>::from(vector![1, 2]);
+    /// let (values, mut sub) = ob.subscribe().map_values_into::<u32>();
+    /// assert_eq!(values, vector![1_u32, 2]);
+    ///
+    /// ob.push_back(3);
+    /// assert_next_eq!(sub, VectorDiff::PushBack { value: 3_u32 });
+    /// ```
+    ///
+    /// [`VectorDiff`]: eyeball_im::VectorDiff
+    /// [`ObservableVector`]: eyeball_im::ObservableVector
+    pub struct MapInto<S, U> {
+        #[pin]
+        inner: S,
+        _marker: PhantomData<fn() -> U>,
+    }
+}
+
+impl<S, U> MapInto<S, U>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    VectorDiffContainerStreamElement<S>: Into<U>,
+    U: Clone,
+{
+    /// Create a new `MapInto` with the given (unconverted) initial values and
+    /// stream of `VectorDiff` updates for those values.
+    pub fn new(values: Vector<VectorDiffContainerStreamElement<S>>, inner: S) -> (Vector<U>, Self) {
+        let values = values.into_iter().map(Into::into).collect();
+        (values, Self { inner, _marker: PhantomData })
+    }
+}
+
+impl<S, U> Stream for MapInto<S, U>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    VectorDiffContainerStreamElement<S>: Into<U>,
+    U: Clone,
+{
+    type Item = VectorDiffContainerStreamMappedItem<S, U>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        loop {
+            let Some(diffs) = ready!(this.inner.as_mut().poll_next(cx)) else {
+                return Poll::Ready(None);
+            };
+
+            // Only `None` for empty batches, which are skipped.
+            if let Some(diffs) = diffs.filter_map(|diff| Some(diff.map(Into::into))) {
+                return Poll::Ready(Some(diffs));
+            }
+        }
+    }
+}
+
+impl<S, U> AdapterDebug for MapInto<S, U> {
+    fn adapter_state(&self) -> AdapterState {
+        AdapterState::new("MapInto")
+    }
+}
+
+impl<S, U> fmt::Debug for MapInto<S, U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.adapter_state(), f)
+    }
+}
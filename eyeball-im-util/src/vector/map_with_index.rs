@@ -0,0 +1,261 @@
+use std::{
+    fmt,
+    pin::Pin,
+    task::{self, ready, Poll},
+};
+
+use eyeball_im::{Vector, VectorDiff};
+use futures_core::Stream;
+use pin_project_lite::pin_project;
+
+use super::{
+    AdapterDebug, AdapterState, VectorDiffContainer, VectorDiffContainerOps,
+    VectorDiffContainerStreamElement, VectorDiffContainerStreamMappedItem,
+};
+
+/// Type alias for the buffer of diffs that are ready to be emitted by
+/// [`MapWithIndex`].
+type MappedTailBuf<S, U> =
+    <VectorDiffContainerStreamMappedItem<S, U> as VectorDiffContainerOps<U>>::TailBuf;
+
+pin_project! {
+    /// A [`VectorDiff`] stream adapter that presents a mapped view of the
+    /// underlying [`ObservableVector`]'s items, where the mapping function
+    /// receives the index of each item along with the item itself.
+    ///
+    /// Whenever items change position, e.g. because an item was inserted or
+    /// removed before them, they are mapped again with their new index and a
+    /// `VectorDiff::Set` is emitted for each of them. This makes it possible
+    /// to present items differently depending on their position, at the cost
+    /// of re-mapping all of the items after a changed position.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use eyeball_im::{ObservableVector, VectorDiff};
+    /// use eyeball_im_util::vector::VectorObserverExt;
+    /// use imbl::vector;
+    /// use stream_assert::{assert_next_eq, assert_pending};
+    ///
+    /// let mut ob = ObservableVector::<&str>::from(vector!["a", "b"]);
+    /// let (values, mut sub) = ob.subscribe().map_with_index(|index, value| (index % 2, value));
+    /// assert_eq!(values, vector![(0, "a"), (1, "b")]);
+    ///
+    /// ob.push_back("c");
+    /// assert_next_eq!(sub, VectorDiff::PushBack { value: (0, "c") });
+    ///
+    /// // The items after the removed one are moved up.
+    /// ob.remove(0);
+    /// assert_next_eq!(sub, VectorDiff::Remove { index: 0 });
+    /// assert_next_eq!(sub, VectorDiff::Set { index: 0, value: (0, "b") });
+    /// assert_next_eq!(sub, VectorDiff::Set { index: 1, value: (1, "c") });
+    /// assert_pending!(sub);
+    /// ```
+    ///
+    /// [`ObservableVector`]: eyeball_im::ObservableVector
+    #[project = MapWithIndexProj]
+    pub struct MapWithIndex<S, F, U>
+    where
+        S: Stream,
+        S::Item: VectorDiffContainer,
+        U: Clone,
+        U: 'static,
+    {
+        // The main stream to poll items from.
+        #[pin]
+        inner_stream: S,
+
+        // The function that maps an item and its index.
+        f: F,
+
+        // The buffered vector that is updated with the main stream's items.
+        buffered_vector: Vector<VectorDiffContainerStreamElement<S>>,
+
+        // Diffs that are ready to be emitted, used if the item type is just
+        // `VectorDiff<_>` (non-batched) since one update can result in
+        // multiple diffs.
+        ready_values: MappedTailBuf<S, U>,
+    }
+}
+
+impl<S, F, U> MapWithIndex<S, F, U>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    F: Fn(usize, VectorDiffContainerStreamElement<S>) -> U,
+    U: Clone + 'static,
+{
+    /// Create a new `MapWithIndex` with the given initial values, stream of
+    /// `VectorDiff` updates for those values, and mapping function.
+    ///
+    /// Returns the mapped initial values as well as a stream of updates to
+    /// them.
+    pub fn new(
+        initial_values: Vector<VectorDiffContainerStreamElement<S>>,
+        inner_stream: S,
+        f: F,
+    ) -> (Vector<U>, Self) {
+        let values = map_from(&initial_values, 0, &f);
+        let stream = Self {
+            inner_stream,
+            f,
+            buffered_vector: initial_values,
+            ready_values: Default::default(),
+        };
+
+        (values, stream)
+    }
+}
+
+impl<S, F, U> Stream for MapWithIndex<S, F, U>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    F: Fn(usize, VectorDiffContainerStreamElement<S>) -> U,
+    U: Clone + 'static,
+{
+    type Item = VectorDiffContainerStreamMappedItem<S, U>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        self.project().poll_next(cx)
+    }
+}
+
+impl<S, F, U> AdapterDebug for MapWithIndex<S, F, U>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    U: Clone + 'static,
+{
+    fn adapter_state(&self) -> AdapterState {
+        AdapterState {
+            buffered_len: Some(self.buffered_vector.len()),
+            ready_values: VectorDiffContainerStreamMappedItem::<S, U>::tail_buf_len(
+                &self.ready_values,
+            ),
+            ..AdapterState::new("MapWithIndex")
+        }
+    }
+}
+
+impl<S, F, U> fmt::Debug for MapWithIndex<S, F, U>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    U: Clone + 'static,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.adapter_state(), f)
+    }
+}
+
+impl<S, F, U> MapWithIndexProj<'_, S, F, U>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    F: Fn(usize, VectorDiffContainerStreamElement<S>) -> U,
+    U: Clone + 'static,
+{
+    fn poll_next(
+        &mut self,
+        cx: &mut task::Context<'_>,
+    ) -> Poll<Option<VectorDiffContainerStreamMappedItem<S, U>>> {
+        loop {
+            // First off, if any values are ready, return them.
+            if let Some(value) =
+                VectorDiffContainerStreamMappedItem::<S, U>::pop_from_tail_buf(self.ready_values)
+            {
+                return Poll::Ready(Some(value));
+            }
+
+            // Poll `VectorDiff`s from the `inner_stream`.
+            let Some(diffs) = ready!(self.inner_stream.as_mut().poll_next(cx)) else {
+                return Poll::Ready(None);
+            };
+
+            let mut res = Vec::new();
+            for diff in diffs.into_vec() {
+                self.handle_diff(diff, &mut res);
+            }
+
+            if let Some(diffs) =
+                VectorDiffContainerStreamMappedItem::<S, U>::extend_tail_buf(res, self.ready_values)
+            {
+                return Poll::Ready(Some(diffs));
+            }
+
+            // Else loop and poll the streams again.
+        }
+    }
+
+    fn handle_diff(
+        &mut self,
+        diff: VectorDiff<VectorDiffContainerStreamElement<S>>,
+        res: &mut Vec<VectorDiff<U>>,
+    ) {
+        let len = self.buffered_vector.len();
+        diff.clone().apply(self.buffered_vector);
+
+        match diff {
+            VectorDiff::Append { values } => {
+                res.push(VectorDiff::Append { values: map_from(&values, len, &*self.f) });
+            }
+            VectorDiff::Clear => {
+                res.push(VectorDiff::Clear);
+            }
+            VectorDiff::PushFront { value } => {
+                res.push(VectorDiff::PushFront { value: (self.f)(0, value) });
+                self.remap(1..len + 1, res);
+            }
+            VectorDiff::PushBack { value } => {
+                res.push(VectorDiff::PushBack { value: (self.f)(len, value) });
+            }
+            VectorDiff::PopFront => {
+                res.push(VectorDiff::PopFront);
+                self.remap(0..len - 1, res);
+            }
+            VectorDiff::PopBack => {
+                res.push(VectorDiff::PopBack);
+            }
+            VectorDiff::Insert { index, value } => {
+                res.push(VectorDiff::Insert { index, value: (self.f)(index, value) });
+                self.remap(index + 1..len + 1, res);
+            }
+            VectorDiff::Set { index, value } => {
+                res.push(VectorDiff::Set { index, value: (self.f)(index, value) });
+            }
+            VectorDiff::Remove { index } => {
+                res.push(VectorDiff::Remove { index });
+                self.remap(index..len - 1, res);
+            }
+            VectorDiff::Truncate { length } => {
+                res.push(VectorDiff::Truncate { length });
+            }
+            VectorDiff::Move { from, to } => {
+                // All of the items between the two positions have a new index.
+                self.remap(from.min(to)..from.max(to) + 1, res);
+            }
+            VectorDiff::Reset { values, reason } => {
+                res.push(VectorDiff::Reset { values: map_from(&values, 0, &*self.f), reason });
+            }
+        }
+    }
+
+    /// Map the items in `range` of the buffered vector again, emitting a
+    /// `VectorDiff::Set` for each of them.
+    fn remap(&self, range: std::ops::Range<usize>, res: &mut Vec<VectorDiff<U>>) {
+        for index in range {
+            let value = (self.f)(index, self.buffered_vector[index].clone());
+            res.push(VectorDiff::Set { index, value });
+        }
+    }
+}
+
+/// Map `values`, the first of which is at index `offset`.
+fn map_from<T: Clone, U: Clone>(
+    values: &Vector<T>,
+    offset: usize,
+    f: &impl Fn(usize, T) -> U,
+) -> Vector<U> {
+    values.iter().enumerate().map(|(i, value)| f(offset + i, value.clone())).collect()
+}
@@ -0,0 +1,119 @@
+use std::{
+    future::poll_fn,
+    pin::Pin,
+    sync::{Arc, Mutex},
+};
+
+use eyeball_im::{ObservableVector, ObservableVectorTransaction, VectorDiff, VectorSubscriber};
+use futures_core::Stream;
+use imbl::Vector;
+use tokio::{runtime::Handle, task::JoinHandle};
+
+use super::{VectorDiffContainer, VectorDiffContainerOps};
+
+/// The output of an adapter chain, materialized into an [`ObservableVector`]
+/// by a background task.
+///
+/// Created by [`VectorObserverExt::spawn_materialized`]. This allows computing
+/// an expensive derived view (e.g. a sorted and filtered one) only once, and
+/// sharing it with any number of subscribers across an application.
+///
+/// `MaterializedVector` can be cloned cheaply. The background task runs until
+/// the last clone is dropped, at which point it is aborted and all subscribers
+/// are closed.
+///
+/// [`VectorObserverExt::spawn_materialized`]: super::VectorObserverExt::spawn_materialized
+#[derive(Debug)]
+pub struct MaterializedVector<T: Clone + 'static> {
+    inner: Arc<MaterializedVectorInner<T>>,
+}
+
+impl<T> MaterializedVector<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    pub(super) fn spawn<S>(initial_values: Vector<T>, stream: S, handle: &Handle) -> Self
+    where
+        S: Stream + Send + 'static,
+        S::Item: VectorDiffContainer<Element = T>,
+    {
+        let vector = Arc::new(Mutex::new(ObservableVector::from(initial_values)));
+        let task = handle.spawn(drive(Box::pin(stream), vector.clone()));
+        Self { inner: Arc::new(MaterializedVectorInner { vector, task }) }
+    }
+
+    /// Get a clone of the current values.
+    pub fn get(&self) -> Vector<T> {
+        (**self.inner.vector.lock().unwrap()).clone()
+    }
+
+    /// Obtain a new subscriber to the materialized values.
+    pub fn subscribe(&self) -> VectorSubscriber<T> {
+        self.inner.vector.lock().unwrap().subscribe()
+    }
+}
+
+impl<T: Clone + 'static> Clone for MaterializedVector<T> {
+    fn clone(&self) -> Self {
+        Self { inner: self.inner.clone() }
+    }
+}
+
+#[derive(Debug)]
+struct MaterializedVectorInner<T: Clone + 'static> {
+    vector: Arc<Mutex<ObservableVector<T>>>,
+    task: JoinHandle<()>,
+}
+
+impl<T: Clone + 'static> Drop for MaterializedVectorInner<T> {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn drive<S, T>(mut stream: Pin<Box<S>>, vector: Arc<Mutex<ObservableVector<T>>>)
+where
+    S: Stream,
+    S::Item: VectorDiffContainer<Element = T>,
+    T: Clone + 'static,
+{
+    while let Some(diffs) = poll_fn(|cx| stream.as_mut().poll_next(cx)).await {
+        let mut guard = vector.lock().unwrap();
+        let mut txn = guard.transaction();
+        for diff in diffs.into_vec() {
+            apply_diff(&mut txn, diff);
+        }
+        txn.commit();
+    }
+}
+
+fn apply_diff<T: Clone + 'static>(
+    txn: &mut ObservableVectorTransaction<'_, T>,
+    diff: VectorDiff<T>,
+) {
+    match diff {
+        VectorDiff::Append { values } => txn.append(values),
+        VectorDiff::Clear => txn.clear(),
+        VectorDiff::PushFront { value } => txn.push_front(value),
+        VectorDiff::PushBack { value } => txn.push_back(value),
+        VectorDiff::PopFront => {
+            txn.pop_front();
+        }
+        VectorDiff::PopBack => {
+            txn.pop_back();
+        }
+        VectorDiff::Insert { index, value } => txn.insert(index, value),
+        VectorDiff::Set { index, value } => {
+            txn.set(index, value);
+        }
+        VectorDiff::Remove { index } => {
+            txn.remove(index);
+        }
+        VectorDiff::Truncate { length } => txn.truncate(length),
+        VectorDiff::Move { from, to } => txn.move_item(from, to),
+        VectorDiff::Reset { values, .. } => {
+            txn.clear();
+            txn.append(values);
+        }
+    }
+}
@@ -0,0 +1,303 @@
+use std::{
+    fmt,
+    pin::Pin,
+    task::{self, Poll},
+};
+
+use eyeball_im::{Vector, VectorDiff};
+use futures_core::Stream;
+use pin_project_lite::pin_project;
+
+use super::{
+    AdapterDebug, AdapterState, VectorDiffContainer, VectorDiffContainerOps,
+    VectorDiffContainerStreamTailBuf, VectorObserver,
+};
+
+/// Merge several [`VectorObserver`]s into a single one whose vector is the
+/// concatenation of all of theirs, in iteration order.
+///
+/// See [`MergeDiffStreams`] for more details.
+pub fn merge_diff_streams<T, O>(
+    segments: impl IntoIterator<Item = O>,
+) -> (Vector<T>, MergeDiffStreams<O::Stream>)
+where
+    T: Clone + 'static,
+    O: VectorObserver<T>,
+    <O::Stream as Stream>::Item: VectorDiffContainer<Element = T>,
+{
+    let mut values = Vector::new();
+    let segments = segments
+        .into_iter()
+        .map(|observer| {
+            let (segment_values, stream) = observer.into_parts();
+            let len = segment_values.len();
+            values.append(segment_values);
+            Segment { stream: Some(Box::pin(stream)), len }
+        })
+        .collect();
+
+    (values, MergeDiffStreams { segments, next_segment: 0, ready_values: Default::default() })
+}
+
+pin_project! {
+    /// A [`VectorDiff`] stream that merges the streams of several
+    /// independently observed vectors (segments) into the stream of a single
+    /// virtual vector.
+    ///
+    /// The segments are laid out one after the other. The offset of each
+    /// segment is the sum of the lengths of the segments before it, and the
+    /// indices of every diff are translated accordingly. Diffs that can't be
+    /// expressed as-is in the merged vector (e.g. a `VectorDiff::Append` to a
+    /// segment that isn't the last non-empty one) are turned into equivalent
+    /// `VectorDiff::Insert`s and `VectorDiff::Remove`s.
+    ///
+    /// Every diff is checked against the length of the segment it comes from.
+    ///
+    /// # Panics
+    ///
+    /// Polling panics if a segment produces a diff that targets indices
+    /// outside of its own range, since this would modify another segment of
+    /// the merged vector.
+    ///
+    /// ```rust
+    /// use eyeball_im::{ObservableVector, VectorDiff};
+    /// use eyeball_im_util::vector::merge_diff_streams;
+    /// use imbl::vector;
+    /// use stream_assert::{assert_next_eq, assert_pending};
+    ///
+    /// let mut monday = ObservableVector::<&str>::from(vector!["a", "b"]);
+    /// let mut tuesday = ObservableVector::<&str>::from(vector!["c"]);
+    ///
+    /// let (values, mut sub) = merge_diff_streams([monday.subscribe(), tuesday.subscribe()]);
+    /// assert_eq!(values, vector!["a", "b", "c"]);
+    ///
+    /// monday.push_back("b2");
+    /// assert_next_eq!(sub, VectorDiff::Insert { index: 2, value: "b2" });
+    ///
+    /// tuesday.push_front("c0");
+    /// assert_next_eq!(sub, VectorDiff::Insert { index: 3, value: "c0" });
+    ///
+    /// assert_pending!(sub);
+    /// ```
+    pub struct MergeDiffStreams<S>
+    where
+        S: Stream,
+        S::Item: VectorDiffContainer,
+    {
+        segments: Vec<Segment<S>>,
+
+        // The segment to poll first, such that all segments get polled fairly.
+        next_segment: usize,
+
+        // Translating a single diff can produce many diffs.
+        ready_values: VectorDiffContainerStreamTailBuf<S>,
+    }
+}
+
+struct Segment<S> {
+    // `None` once the stream is exhausted.
+    stream: Option<Pin<Box<S>>>,
+    len: usize,
+}
+
+impl<S> Stream for MergeDiffStreams<S>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+
+        'outer: loop {
+            // First off, if any values are ready, return them.
+            if let Some(value) = S::Item::pop_from_tail_buf(this.ready_values) {
+                return Poll::Ready(Some(value));
+            }
+
+            let segment_count = this.segments.len();
+            for i in (0..segment_count).map(|i| (*this.next_segment + i) % segment_count) {
+                let Some(stream) = &mut this.segments[i].stream else {
+                    continue;
+                };
+
+                match stream.as_mut().poll_next(cx) {
+                    Poll::Ready(Some(diffs)) => {
+                        *this.next_segment = (i + 1) % segment_count;
+
+                        let segments = &mut *this.segments;
+                        let offset = segments[..i].iter().map(|segment| segment.len).sum();
+                        let at_end = segments[i + 1..].iter().all(|segment| segment.len == 0);
+                        let len = &mut segments[i].len;
+                        let ready = diffs.push_into_tail_buf(this.ready_values, |diff| {
+                            translate_diff(i, offset, at_end, len, diff).into_iter().collect()
+                        });
+
+                        if let Some(diff) = ready {
+                            return Poll::Ready(Some(diff));
+                        }
+
+                        continue 'outer;
+                    }
+                    Poll::Ready(None) => {
+                        this.segments[i].stream = None;
+                    }
+                    Poll::Pending => {}
+                }
+            }
+
+            return if this.segments.iter().all(|segment| segment.stream.is_none()) {
+                Poll::Ready(None)
+            } else {
+                Poll::Pending
+            };
+        }
+    }
+}
+
+impl<S> AdapterDebug for MergeDiffStreams<S>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn adapter_state(&self) -> AdapterState {
+        AdapterState {
+            len: Some(self.segments.iter().map(|segment| segment.len).sum()),
+            ready_values: S::Item::tail_buf_len(&self.ready_values),
+            ..AdapterState::new("MergeDiffStreams")
+        }
+    }
+}
+
+impl<S> fmt::Debug for MergeDiffStreams<S>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.adapter_state(), f)
+    }
+}
+
+/// Translate a `diff` of the segment at `segment_idx` into diffs of the merged
+/// vector, and update the segment's length `len`.
+///
+/// `offset` is the sum of the lengths of the segments before it, and `at_end`
+/// whether all segments after it are empty.
+pub(super) fn translate_diff<T: Clone>(
+    segment_idx: usize,
+    offset: usize,
+    at_end: bool,
+    len: &mut usize,
+    diff: VectorDiff<T>,
+) -> Vec<VectorDiff<T>> {
+    let check_index = |index: usize, upper_bound: usize| {
+        assert!(
+            index < upper_bound,
+            "segment {segment_idx} produced a diff for index {index}, \
+             but its range only spans {upper_bound} items"
+        );
+    };
+
+    match diff {
+        VectorDiff::Append { values } => {
+            let start = offset + *len;
+            *len += values.len();
+            if at_end {
+                vec![VectorDiff::Append { values }]
+            } else {
+                values
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, value)| VectorDiff::Insert { index: start + i, value })
+                    .collect()
+            }
+        }
+        VectorDiff::Clear => {
+            let removed = std::mem::take(len);
+            if at_end && offset == 0 {
+                vec![VectorDiff::Clear]
+            } else if at_end {
+                vec![VectorDiff::Truncate { length: offset }]
+            } else {
+                vec![VectorDiff::Remove { index: offset }; removed]
+            }
+        }
+        VectorDiff::PushFront { value } => {
+            *len += 1;
+            if offset == 0 {
+                vec![VectorDiff::PushFront { value }]
+            } else {
+                vec![VectorDiff::Insert { index: offset, value }]
+            }
+        }
+        VectorDiff::PushBack { value } => {
+            let index = offset + *len;
+            *len += 1;
+            if at_end {
+                vec![VectorDiff::PushBack { value }]
+            } else {
+                vec![VectorDiff::Insert { index, value }]
+            }
+        }
+        VectorDiff::PopFront => {
+            check_index(0, *len);
+            *len -= 1;
+            if offset == 0 {
+                vec![VectorDiff::PopFront]
+            } else {
+                vec![VectorDiff::Remove { index: offset }]
+            }
+        }
+        VectorDiff::PopBack => {
+            check_index(0, *len);
+            *len -= 1;
+            if at_end {
+                vec![VectorDiff::PopBack]
+            } else {
+                vec![VectorDiff::Remove { index: offset + *len }]
+            }
+        }
+        VectorDiff::Insert { index, value } => {
+            check_index(index, *len + 1);
+            *len += 1;
+            vec![VectorDiff::Insert { index: offset + index, value }]
+        }
+        VectorDiff::Set { index, value } => {
+            check_index(index, *len);
+            vec![VectorDiff::Set { index: offset + index, value }]
+        }
+        VectorDiff::Remove { index } => {
+            check_index(index, *len);
+            *len -= 1;
+            vec![VectorDiff::Remove { index: offset + index }]
+        }
+        VectorDiff::Truncate { length } => {
+            check_index(length, *len + 1);
+            let removed = *len - length;
+            *len = length;
+            if at_end {
+                vec![VectorDiff::Truncate { length: offset + length }]
+            } else {
+                vec![VectorDiff::Remove { index: offset + length }; removed]
+            }
+        }
+        VectorDiff::Move { from, to } => {
+            check_index(from, *len);
+            check_index(to, *len);
+            vec![VectorDiff::Move { from: offset + from, to: offset + to }]
+        }
+        VectorDiff::Reset { values, reason } => {
+            if offset == 0 && at_end {
+                *len = values.len();
+                vec![VectorDiff::Reset { values, reason }]
+            } else {
+                let mut diffs = translate_diff(segment_idx, offset, at_end, len, VectorDiff::Clear);
+                let append = VectorDiff::Append { values };
+                diffs.extend(translate_diff(segment_idx, offset, at_end, len, append));
+                diffs
+            }
+        }
+    }
+}
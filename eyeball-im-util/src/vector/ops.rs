@@ -10,6 +10,10 @@ pub trait VectorDiffContainerOps<T>: Sized {
 
     fn from_item(vector_diff: VectorDiff<T>) -> Self;
 
+    fn for_each_diff(&self, f: impl FnMut(&VectorDiff<T>));
+
+    fn into_vec(self) -> Vec<VectorDiff<T>>;
+
     fn filter_map<U>(
         self,
         f: impl FnMut(VectorDiff<T>) -> Option<VectorDiff<U>>,
@@ -39,7 +43,15 @@ pub trait VectorDiffContainerOps<T>: Sized {
         map_diffs: impl FnMut(VectorDiff<T>) -> SmallVec<[VectorDiff<T>; 2]>,
     ) -> Option<Self>;
 
+    fn extend_sort_buf(diffs: Vec<VectorDiff<T>>, buffer: &mut Self::SortBuf) -> Option<Self>;
+
     fn pop_from_sort_buf(buffer: &mut Self::SortBuf) -> Option<Self>;
+
+    fn head_buf_len(buffer: &Self::HeadBuf) -> usize;
+
+    fn tail_buf_len(buffer: &Self::TailBuf) -> usize;
+
+    fn sort_buf_len(buffer: &Self::SortBuf) -> usize;
 }
 
 #[allow(unreachable_pub)]
@@ -55,6 +67,14 @@ impl<T> VectorDiffContainerOps<T> for VectorDiff<T> {
         vector_diff
     }
 
+    fn for_each_diff(&self, mut f: impl FnMut(&VectorDiff<T>)) {
+        f(self);
+    }
+
+    fn into_vec(self) -> Vec<VectorDiff<T>> {
+        vec![self]
+    }
+
     fn filter_map<U>(
         self,
         mut f: impl FnMut(VectorDiff<T>) -> Option<VectorDiff<U>>,
@@ -129,9 +149,30 @@ impl<T> VectorDiffContainerOps<T> for VectorDiff<T> {
         }
     }
 
+    fn extend_sort_buf(diffs: Vec<VectorDiff<T>>, buffer: &mut Self::SortBuf) -> Option<Self> {
+        assert!(buffer.is_empty(), "buffer must be empty when calling `extend_sort_buf`");
+
+        // Store the `diffs` in reverse order to pop from it, like above.
+        buffer.extend(diffs.into_iter().rev());
+
+        buffer.pop()
+    }
+
     fn pop_from_sort_buf(buffer: &mut Self::SortBuf) -> Option<Self> {
         buffer.pop()
     }
+
+    fn head_buf_len(buffer: &Self::HeadBuf) -> usize {
+        buffer.is_some().into()
+    }
+
+    fn tail_buf_len(buffer: &Self::TailBuf) -> usize {
+        buffer.len()
+    }
+
+    fn sort_buf_len(buffer: &Self::SortBuf) -> usize {
+        buffer.len()
+    }
 }
 
 impl<T> VectorDiffContainerOps<T> for Vec<VectorDiff<T>> {
@@ -144,6 +185,14 @@ impl<T> VectorDiffContainerOps<T> for Vec<VectorDiff<T>> {
         vec![vector_diff]
     }
 
+    fn for_each_diff(&self, f: impl FnMut(&VectorDiff<T>)) {
+        self.iter().for_each(f);
+    }
+
+    fn into_vec(self) -> Vec<VectorDiff<T>> {
+        self
+    }
+
     fn filter_map<U>(
         self,
         f: impl FnMut(VectorDiff<T>) -> Option<VectorDiff<U>>,
@@ -215,9 +264,29 @@ impl<T> VectorDiffContainerOps<T> for Vec<VectorDiff<T>> {
         }
     }
 
+    fn extend_sort_buf(diffs: Vec<VectorDiff<T>>, _buffer: &mut Self::SortBuf) -> Option<Self> {
+        if diffs.is_empty() {
+            None
+        } else {
+            Some(diffs)
+        }
+    }
+
     fn pop_from_sort_buf(_: &mut Self::HeadBuf) -> Option<Self> {
         None
     }
+
+    fn head_buf_len(_: &Self::HeadBuf) -> usize {
+        0
+    }
+
+    fn tail_buf_len(_: &Self::TailBuf) -> usize {
+        0
+    }
+
+    fn sort_buf_len(_: &Self::SortBuf) -> usize {
+        0
+    }
 }
 
 #[allow(unreachable_pub)]
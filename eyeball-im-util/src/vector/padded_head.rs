@@ -0,0 +1,264 @@
+use std::{
+    fmt,
+    pin::Pin,
+    task::{self, ready, Poll},
+};
+
+use eyeball::Subscriber;
+use eyeball_im::{ResetReason, VectorDiff};
+use futures_core::Stream;
+use imbl::Vector;
+use pin_project_lite::pin_project;
+use smallvec::{smallvec, SmallVec};
+
+use super::{
+    AdapterDebug, AdapterState, EmptyLimitStream, Head, Saturation, VectorDiffContainer,
+    VectorDiffContainerOps, VectorDiffContainerStreamElement, VectorDiffContainerStreamTailBuf,
+};
+
+pin_project! {
+    /// A [`VectorDiff`] stream adapter that presents the first `limit` items
+    /// of the underlying [`ObservableVector`], padded with placeholders so
+    /// that the view always contains exactly `limit` items.
+    ///
+    /// This is meant for fixed-size viewports, like a table widget with a
+    /// fixed number of rows. Items entering the view at its end replace
+    /// placeholders through [`VectorDiff::Set`] instead of growing the view,
+    /// and items leaving it are replaced by new placeholders.
+    ///
+    /// Placeholders are created by calling the `placeholder` function. See
+    /// [`Head`] for details on how the limited view is computed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use eyeball_im::{ObservableVector, VectorDiff};
+    /// use eyeball_im_util::vector::VectorObserverExt;
+    /// use imbl::vector;
+    /// use stream_assert::{assert_next_eq, assert_pending};
+    ///
+    /// let mut ob = ObservableVector::<char>::new();
+    /// let (values, mut sub) = ob.subscribe().padded_head(3, || '-');
+    /// assert_eq!(values, vector!['-', '-', '-']);
+    ///
+    /// ob.push_back('a');
+    /// assert_next_eq!(sub, VectorDiff::Set { index: 0, value: 'a' });
+    ///
+    /// ob.push_front('b');
+    /// assert_next_eq!(sub, VectorDiff::PushFront { value: 'b' });
+    /// assert_next_eq!(sub, VectorDiff::PopBack);
+    ///
+    /// ob.pop_back();
+    /// assert_next_eq!(sub, VectorDiff::Set { index: 1, value: '-' });
+    /// assert_pending!(sub);
+    /// ```
+    ///
+    /// [`ObservableVector`]: eyeball_im::ObservableVector
+    #[project = PaddedHeadProj]
+    pub struct PaddedHead<S, F>
+    where
+        S: Stream,
+        S::Item: VectorDiffContainer,
+    {
+        // The limited view without padding.
+        #[pin]
+        inner_stream: Head<S, EmptyLimitStream>,
+
+        // The function creating placeholders.
+        placeholder: F,
+
+        // The fixed length of the view.
+        limit: usize,
+
+        // The number of actual (non-placeholder) items in the view.
+        len: usize,
+
+        // Buffered diffs, since a single diff of the unpadded view can result
+        // in multiple diffs of the padded view.
+        ready_values: VectorDiffContainerStreamTailBuf<S>,
+    }
+}
+
+impl<S, F> PaddedHead<S, F>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    F: Fn() -> VectorDiffContainerStreamElement<S>,
+{
+    /// Create a new [`PaddedHead`] with the given (unlimited) initial values,
+    /// stream of `VectorDiff` updates for those values, fixed limit and
+    /// placeholder function.
+    ///
+    /// Returns the truncated or padded initial values, which always contain
+    /// exactly `limit` items, as well as a stream of updates that keeps it that
+    /// way.
+    pub fn new(
+        initial_values: Vector<VectorDiffContainerStreamElement<S>>,
+        inner_stream: S,
+        limit: usize,
+        placeholder: F,
+    ) -> (Vector<VectorDiffContainerStreamElement<S>>, Self) {
+        let (mut values, inner_stream) = Head::new(initial_values, inner_stream, limit);
+        let len = values.len();
+        values.extend((len..limit).map(|_| placeholder()));
+
+        let stream =
+            Self { inner_stream, placeholder, limit, len, ready_values: Default::default() };
+        (values, stream)
+    }
+
+    /// Get the current [`Saturation`] of the view, ignoring placeholders.
+    pub fn saturation(&self) -> Saturation {
+        self.inner_stream.saturation()
+    }
+
+    /// Get a [`Subscriber`] for the [`Saturation`] of the view, ignoring
+    /// placeholders.
+    ///
+    /// See [`Head::subscribe_saturation`] for details.
+    pub fn subscribe_saturation(&self) -> Subscriber<Saturation> {
+        self.inner_stream.subscribe_saturation()
+    }
+}
+
+impl<S, F> Stream for PaddedHead<S, F>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    F: Fn() -> VectorDiffContainerStreamElement<S>,
+{
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        loop {
+            // First off, if any values are ready, return them.
+            if let Some(value) = S::Item::pop_from_tail_buf(this.ready_values) {
+                return Poll::Ready(Some(value));
+            }
+
+            let Some(diffs) = ready!(this.inner_stream.as_mut().poll_next(cx)) else {
+                return Poll::Ready(None);
+            };
+
+            let ready = diffs.push_into_tail_buf(this.ready_values, |diff| {
+                pad_diff(diff, *this.limit, this.len, &*this.placeholder)
+            });
+
+            if let Some(diff) = ready {
+                return Poll::Ready(Some(diff));
+            }
+        }
+    }
+}
+
+impl<S, F> AdapterDebug for PaddedHead<S, F>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn adapter_state(&self) -> AdapterState {
+        AdapterState {
+            buffered_len: self.inner_stream.adapter_state().buffered_len,
+            len: Some(self.limit),
+            limit: Some(self.limit),
+            ready_values: S::Item::tail_buf_len(&self.ready_values),
+            ..AdapterState::new("PaddedHead")
+        }
+    }
+}
+
+impl<S, F> fmt::Debug for PaddedHead<S, F>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.adapter_state(), f)
+    }
+}
+
+/// Translate a diff of the unpadded view, which currently contains `len`
+/// items, into diffs of the padded view.
+fn pad_diff<T: Clone>(
+    diff: VectorDiff<T>,
+    limit: usize,
+    len: &mut usize,
+    placeholder: impl Fn() -> T,
+) -> SmallVec<[VectorDiff<T>; 2]> {
+    let placeholders = |count: usize| (0..count).map(|_| placeholder()).collect();
+
+    match diff {
+        VectorDiff::Append { values } => {
+            let start = *len;
+            *len += values.len();
+            values
+                .into_iter()
+                .enumerate()
+                .map(|(i, value)| VectorDiff::Set { index: start + i, value })
+                .collect()
+        }
+        VectorDiff::Clear => {
+            if *len == 0 {
+                return SmallVec::new();
+            }
+
+            *len = 0;
+            smallvec![VectorDiff::Reset {
+                values: placeholders(limit),
+                reason: ResetReason::Update
+            }]
+        }
+        VectorDiff::PushFront { value } => {
+            *len += 1;
+            smallvec![VectorDiff::PushFront { value }, VectorDiff::PopBack]
+        }
+        VectorDiff::PushBack { value } => {
+            let index = *len;
+            *len += 1;
+            smallvec![VectorDiff::Set { index, value }]
+        }
+        VectorDiff::PopFront => {
+            *len -= 1;
+            smallvec![VectorDiff::PopFront, VectorDiff::PushBack { value: placeholder() }]
+        }
+        VectorDiff::PopBack => {
+            *len -= 1;
+            smallvec![VectorDiff::Set { index: *len, value: placeholder() }]
+        }
+        VectorDiff::Insert { index, value } => {
+            *len += 1;
+            if index == *len - 1 {
+                smallvec![VectorDiff::Set { index, value }]
+            } else {
+                smallvec![VectorDiff::Insert { index, value }, VectorDiff::PopBack]
+            }
+        }
+        VectorDiff::Set { index, value } => smallvec![VectorDiff::Set { index, value }],
+        VectorDiff::Move { from, to } => smallvec![VectorDiff::Move { from, to }],
+        VectorDiff::Remove { index } => {
+            *len -= 1;
+            if index == *len {
+                smallvec![VectorDiff::Set { index, value: placeholder() }]
+            } else {
+                smallvec![
+                    VectorDiff::Remove { index },
+                    VectorDiff::PushBack { value: placeholder() }
+                ]
+            }
+        }
+        VectorDiff::Truncate { length } => {
+            let diffs = (length..*len)
+                .map(|index| VectorDiff::Set { index, value: placeholder() })
+                .collect();
+            *len = length;
+            diffs
+        }
+        VectorDiff::Reset { mut values, reason } => {
+            *len = values.len();
+            values.append(placeholders(limit - *len));
+            smallvec![VectorDiff::Reset { values, reason }]
+        }
+    }
+}
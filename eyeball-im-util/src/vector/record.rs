@@ -0,0 +1,291 @@
+use std::{
+    fmt,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{self, ready, Poll},
+    time::{Duration, Instant},
+    vec,
+};
+
+use eyeball_im::{Vector, VectorDiff};
+use futures_core::Stream;
+use pin_project_lite::pin_project;
+
+use super::{
+    AdapterDebug, AdapterState, VectorDiffContainer, VectorDiffContainerOps,
+    VectorDiffContainerStreamElement,
+};
+
+/// A recording of an observed vector: its initial values and all of the
+/// diffs that followed them.
+///
+/// Recorded with the [`Record`] adapter, and fed back into adapters with
+/// [`DiffLog::replay`]. If the `serde` Cargo feature is enabled, a log can be
+/// serialized and deserialized, which makes it possible to reproduce issues
+/// with adapters from a log recorded elsewhere. It is (de)serialized as a
+/// pair of the initial values and the list of entries, and each entry as a
+/// pair of its timestamp and its diffs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DiffLog<T: Clone> {
+    /// The values the diffs apply to.
+    pub initial_values: Vector<T>,
+    /// The recorded batches of diffs, in order.
+    pub entries: Vec<DiffLogEntry<T>>,
+}
+
+impl<T: Clone> DiffLog<T> {
+    /// Create a new `DiffLog` with the given initial values and no entries.
+    pub fn new(initial_values: Vector<T>) -> Self {
+        Self { initial_values, entries: Vec::new() }
+    }
+
+    /// Get the values after applying all of the recorded diffs to the initial
+    /// values.
+    pub fn final_values(&self) -> Vector<T> {
+        let mut values = self.initial_values.clone();
+        for diff in self.entries.iter().flat_map(|entry| &entry.diffs) {
+            diff.clone().apply(&mut values);
+        }
+        values
+    }
+
+    /// Replay the log.
+    ///
+    /// Returns the initial values as well as a stream that yields the diffs of
+    /// each entry as a batch, right away. Like any pair of values and stream
+    /// of diffs, the result can be used with the methods of
+    /// [`VectorObserverExt`][super::VectorObserverExt], to feed the log into
+    /// adapters. Use [`VectorObserverExt::unbatched`] in front of them to
+    /// get single diffs instead.
+    ///
+    /// [`VectorObserverExt::unbatched`]: super::VectorObserverExt::unbatched
+    pub fn replay(self) -> (Vector<T>, Replay<T>) {
+        (self.initial_values, Replay { entries: self.entries.into_iter() })
+    }
+}
+
+/// A batch of diffs in a [`DiffLog`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DiffLogEntry<T: Clone> {
+    /// The time between the start of the recording and this entry, if the log
+    /// was recorded with timestamps.
+    pub elapsed: Option<Duration>,
+    /// The diffs, as they were yielded by the recorded stream in one item.
+    pub diffs: Vec<VectorDiff<T>>,
+}
+
+/// A handle to the [`DiffLog`] written by a [`Record`] adapter.
+///
+/// It can be cloned cheaply, and stays valid after the adapter is dropped.
+#[derive(Debug)]
+pub struct DiffLogHandle<T: Clone> {
+    log: Arc<Mutex<DiffLog<T>>>,
+}
+
+impl<T: Clone> DiffLogHandle<T> {
+    /// Get a clone of the log recorded so far.
+    pub fn get(&self) -> DiffLog<T> {
+        self.log.lock().unwrap().clone()
+    }
+}
+
+impl<T: Clone> Clone for DiffLogHandle<T> {
+    fn clone(&self) -> Self {
+        Self { log: self.log.clone() }
+    }
+}
+
+pin_project! {
+    /// A [`VectorDiff`] stream adapter that forwards all items of the
+    /// underlying stream unchanged, and records them in a [`DiffLog`].
+    ///
+    /// The log can be accessed through a [`DiffLogHandle`], obtained from
+    /// [`Record::log`] before the adapter is passed on. Each item of the
+    /// underlying stream becomes one [`DiffLogEntry`], optionally along with
+    /// the time that has passed since the adapter was created.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use eyeball_im::{ObservableVector, VectorDiff};
+    /// use eyeball_im_util::vector::VectorObserverExt;
+    /// use imbl::vector;
+    /// use stream_assert::{assert_closed, assert_next_eq};
+    ///
+    /// let mut ob = ObservableVector::<u32>::from(vector![3, 1]);
+    /// let (values, recording) = ob.subscribe().record();
+    /// let log = recording.log();
+    /// let (_, mut sub) = (values, recording).sort();
+    ///
+    /// ob.push_back(2);
+    /// assert_next_eq!(sub, VectorDiff::Insert { index: 1, value: 2 });
+    ///
+    /// // Feed the recorded diffs into a new adapter.
+    /// let (values, diffs) = log.get().replay().unbatched();
+    /// let (values, mut replayed) = (values, diffs).sort();
+    /// assert_eq!(values, vector![1, 3]);
+    /// assert_next_eq!(replayed, VectorDiff::Insert { index: 1, value: 2 });
+    /// assert_closed!(replayed);
+    /// ```
+    pub struct Record<S>
+    where
+        S: Stream,
+        S::Item: VectorDiffContainer,
+    {
+        // The main stream to poll items from.
+        #[pin]
+        inner_stream: S,
+
+        // The log that the items are recorded in.
+        log: Arc<Mutex<DiffLog<VectorDiffContainerStreamElement<S>>>>,
+
+        // The time the recording started, if timestamps are recorded.
+        start: Option<Instant>,
+    }
+}
+
+impl<S> Record<S>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    /// Create a new `Record` with the given initial values and stream of
+    /// `VectorDiff` updates for those values, which records timestamps if
+    /// `with_timestamps` is `true`.
+    pub fn new(
+        initial_values: Vector<VectorDiffContainerStreamElement<S>>,
+        inner_stream: S,
+        with_timestamps: bool,
+    ) -> (Vector<VectorDiffContainerStreamElement<S>>, Self) {
+        let log = Arc::new(Mutex::new(DiffLog::new(initial_values.clone())));
+        let start = with_timestamps.then(Instant::now);
+        (initial_values, Self { inner_stream, log, start })
+    }
+
+    /// Get a handle to the recorded log.
+    pub fn log(&self) -> DiffLogHandle<VectorDiffContainerStreamElement<S>> {
+        DiffLogHandle { log: self.log.clone() }
+    }
+}
+
+impl<S> Stream for Record<S>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        let Some(item) = ready!(this.inner_stream.poll_next(cx)) else {
+            return Poll::Ready(None);
+        };
+
+        let mut diffs = Vec::new();
+        item.for_each_diff(|diff| diffs.push(diff.clone()));
+        let elapsed = this.start.map(|start| start.elapsed());
+        this.log.lock().unwrap().entries.push(DiffLogEntry { elapsed, diffs });
+
+        Poll::Ready(Some(item))
+    }
+}
+
+impl<S> AdapterDebug for Record<S>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn adapter_state(&self) -> AdapterState {
+        AdapterState::new("Record")
+    }
+}
+
+impl<S> fmt::Debug for Record<S>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.adapter_state(), f)
+    }
+}
+
+/// A stream that yields the entries of a [`DiffLog`] as batches of diffs.
+///
+/// Created by [`DiffLog::replay`].
+pub struct Replay<T: Clone> {
+    entries: vec::IntoIter<DiffLogEntry<T>>,
+}
+
+// The entries are never pinned.
+impl<T: Clone> Unpin for Replay<T> {}
+
+impl<T: Clone> Stream for Replay<T> {
+    type Item = Vec<VectorDiff<T>>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        _cx: &mut task::Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        Poll::Ready(self.entries.next().map(|entry| entry.diffs))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+}
+
+impl<T: Clone> fmt::Debug for Replay<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Replay").field("remaining_entries", &self.entries.len()).finish()
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use std::time::Duration;
+
+    use eyeball_im::{Vector, VectorDiff};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::{DiffLog, DiffLogEntry};
+
+    impl<T> Serialize for DiffLog<T>
+    where
+        T: Clone + Serialize,
+    {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            (&self.initial_values, &self.entries).serialize(serializer)
+        }
+    }
+
+    impl<'de, T> Deserialize<'de> for DiffLog<T>
+    where
+        T: Clone + Deserialize<'de>,
+    {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let (initial_values, entries): (Vector<T>, _) = Deserialize::deserialize(deserializer)?;
+            Ok(Self { initial_values, entries })
+        }
+    }
+
+    impl<T> Serialize for DiffLogEntry<T>
+    where
+        T: Clone + Serialize,
+    {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            (&self.elapsed, &self.diffs).serialize(serializer)
+        }
+    }
+
+    impl<'de, T> Deserialize<'de> for DiffLogEntry<T>
+    where
+        T: Clone + Deserialize<'de>,
+    {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let (elapsed, diffs): (Option<Duration>, Vec<VectorDiff<T>>) =
+                Deserialize::deserialize(deserializer)?;
+            Ok(Self { elapsed, diffs })
+        }
+    }
+}
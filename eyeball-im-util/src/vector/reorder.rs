@@ -0,0 +1,141 @@
+//! Helpers for adapters that reorder all of their values at once.
+
+/// The `(from, to)` positions of the moves that reorder a vector where the
+/// value at index `i` ends up at index `new_indices[i]`, or `None` if more than
+/// half of the values would have to be moved, in which case a `Reset` is
+/// cheaper for subscribers.
+///
+/// The values that form a longest subsequence which is already in order stay
+/// where they are, the others are moved one after another, in their new
+/// order, to right before the first unmoved value with a greater new index.
+/// Moves that would leave a value where it is are skipped.
+///
+/// Every value has a fixed slot for where it is before being moved and one
+/// for where it ends up, so the positions are the number of occupied slots
+/// before these, which a Fenwick tree over the slots counts in `O(log n)`.
+pub(super) fn reorder_moves(new_indices: &[usize]) -> Option<Vec<(usize, usize)>> {
+    let settled = longest_increasing_subsequence(new_indices);
+    let num_moves = settled.iter().filter(|&&settled| !settled).count();
+    if num_moves * 2 > new_indices.len() {
+        return None;
+    }
+
+    let len = new_indices.len();
+    let mut old_indices = vec![0; len];
+    for (old_index, &new_index) in new_indices.iter().enumerate() {
+        old_indices[new_index] = old_index;
+    }
+
+    // The old index of each value to move, and that of the settled value
+    // it is moved in front of (`len` for the end), by new index. The latter
+    // never decreases, since settled values are in order already.
+    let mut moves = Vec::new();
+    let mut before = len;
+    for &old_index in old_indices.iter().rev() {
+        if settled[old_index] {
+            before = old_index;
+        } else {
+            moves.push((old_index, before));
+        }
+    }
+    moves.reverse();
+
+    // Element `i` is moved to slot `before + i`, after the ones moved in front
+    // of the same value before it. The slot of an value that isn't moved
+    // yet is after all the ones moved in front of it or an earlier value.
+    let mut moved_before = vec![0; len + 1];
+    for &(_, before) in &moves {
+        moved_before[before] += 1;
+    }
+    let mut slots = Vec::with_capacity(len);
+    let mut num_moved_before = 0;
+    for (old_index, num_moved) in moved_before.into_iter().take(len).enumerate() {
+        num_moved_before += num_moved;
+        slots.push(old_index + num_moved_before);
+    }
+
+    let mut occupied = SlotCounter::new(len + moves.len());
+    for &slot in &slots {
+        occupied.insert(slot);
+    }
+
+    let moves = moves
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, (old_index, before))| {
+            let from = occupied.count_before(slots[old_index]);
+            occupied.remove(slots[old_index]);
+            let to = occupied.count_before(before + i);
+            occupied.insert(before + i);
+            (from != to).then_some((from, to))
+        })
+        .collect();
+
+    Some(moves)
+}
+
+/// A Fenwick tree counting the occupied slots before a given one.
+struct SlotCounter {
+    tree: Vec<usize>,
+}
+
+impl SlotCounter {
+    fn new(num_slots: usize) -> Self {
+        Self { tree: vec![0; num_slots + 1] }
+    }
+
+    fn insert(&mut self, slot: usize) {
+        let mut i = slot + 1;
+        while i < self.tree.len() {
+            self.tree[i] += 1;
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    fn remove(&mut self, slot: usize) {
+        let mut i = slot + 1;
+        while i < self.tree.len() {
+            self.tree[i] -= 1;
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    fn count_before(&self, slot: usize) -> usize {
+        let mut count = 0;
+        let mut i = slot;
+        while i > 0 {
+            count += self.tree[i];
+            i &= i - 1;
+        }
+        count
+    }
+}
+
+/// For every element of `seq`, whether it is part of a longest strictly
+/// increasing subsequence.
+fn longest_increasing_subsequence(seq: &[usize]) -> Vec<bool> {
+    // `tails[k]` is the index of the smallest last element of an increasing
+    // subsequence of length `k + 1`, `prev[i]` the index of the element before
+    // `seq[i]` in the subsequence that ends with it.
+    let mut tails: Vec<usize> = Vec::new();
+    let mut prev = vec![None; seq.len()];
+    for (i, &value) in seq.iter().enumerate() {
+        let k = tails.partition_point(|&j| seq[j] < value);
+        if k > 0 {
+            prev[i] = Some(tails[k - 1]);
+        }
+        if k == tails.len() {
+            tails.push(i);
+        } else {
+            tails[k] = i;
+        }
+    }
+
+    let mut result = vec![false; seq.len()];
+    let mut next = tails.last().copied();
+    while let Some(i) = next {
+        result[i] = true;
+        next = prev[i];
+    }
+    result
+}
@@ -0,0 +1,268 @@
+use std::{
+    collections::VecDeque,
+    fmt,
+    future::poll_fn,
+    pin::Pin,
+    sync::Mutex,
+    task::{self, ready, Poll},
+};
+
+use eyeball_im::{ObservableVector, ObservableVectorTransaction, Vector, VectorDiff};
+use futures_core::Stream;
+use futures_signals::signal_vec::{SignalVec, VecDiff};
+use pin_project_lite::pin_project;
+
+use super::{
+    AdapterDebug, AdapterState, VectorDiffContainer, VectorDiffContainerOps,
+    VectorDiffContainerStreamElement,
+};
+
+pin_project! {
+    /// A [`VectorDiff`] stream adapter that implements [`SignalVec`], for
+    /// consuming observable vectors in libraries built on `futures-signals`.
+    ///
+    /// The first change yielded is a [`VecDiff::Replace`] with the initial
+    /// values, as `SignalVec` consumers expect. After that, every `VectorDiff`
+    /// of the inner stream is translated into one or more equivalent
+    /// `VecDiff`s.
+    ///
+    /// ```rust
+    /// use eyeball_im::ObservableVector;
+    /// use eyeball_im_util::vector::VectorObserverExt;
+    /// use futures_signals::signal_vec::{SignalVecExt, VecDiff};
+    /// use stream_assert::{assert_next_eq, assert_pending};
+    ///
+    /// let mut ob = ObservableVector::<u32>::new();
+    /// ob.push_back(1);
+    /// let mut st = ob.subscribe().into_signal_vec().to_stream();
+    /// assert_next_eq!(st, VecDiff::Replace { values: vec![1] });
+    /// assert_pending!(st);
+    ///
+    /// ob.push_front(0);
+    /// assert_next_eq!(st, VecDiff::InsertAt { index: 0, value: 0 });
+    /// ```
+    pub struct VectorSignalVec<S>
+    where
+        S: Stream,
+        S::Item: VectorDiffContainer,
+    {
+        #[pin]
+        inner: S,
+
+        // The length of the vector, needed to translate truncations.
+        len: usize,
+
+        // Translated changes that have not been yielded yet.
+        ready_values: VecDeque<VecDiff<VectorDiffContainerStreamElement<S>>>,
+    }
+}
+
+impl<S> VectorSignalVec<S>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    /// Create a new `VectorSignalVec` with the given initial values and stream
+    /// of `VectorDiff` updates for those values.
+    pub fn new(initial_values: Vector<VectorDiffContainerStreamElement<S>>, inner: S) -> Self {
+        let len = initial_values.len();
+        let replace = VecDiff::Replace { values: initial_values.into_iter().collect() };
+        Self { inner, len, ready_values: VecDeque::from([replace]) }
+    }
+}
+
+impl<S> SignalVec for VectorSignalVec<S>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    type Item = VectorDiffContainerStreamElement<S>;
+
+    fn poll_vec_change(
+        self: Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> Poll<Option<VecDiff<Self::Item>>> {
+        let mut this = self.project();
+
+        loop {
+            if let Some(diff) = this.ready_values.pop_front() {
+                return Poll::Ready(Some(diff));
+            }
+
+            let Some(diffs) = ready!(this.inner.as_mut().poll_next(cx)) else {
+                return Poll::Ready(None);
+            };
+            for diff in diffs.into_vec() {
+                push_translated(this.ready_values, this.len, diff);
+            }
+        }
+    }
+}
+
+impl<S> AdapterDebug for VectorSignalVec<S>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn adapter_state(&self) -> AdapterState {
+        AdapterState {
+            len: Some(self.len),
+            ready_values: self.ready_values.len(),
+            ..AdapterState::new("VectorSignalVec")
+        }
+    }
+}
+
+impl<S> fmt::Debug for VectorSignalVec<S>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.adapter_state(), f)
+    }
+}
+
+/// Translate `diff` into `VecDiff`s, add them to `ready_values` and keep `len`
+/// up to date.
+fn push_translated<T: Clone>(
+    ready_values: &mut VecDeque<VecDiff<T>>,
+    len: &mut usize,
+    diff: VectorDiff<T>,
+) {
+    match diff {
+        VectorDiff::Append { values } => {
+            *len += values.len();
+            ready_values.extend(values.into_iter().map(|value| VecDiff::Push { value }));
+        }
+        VectorDiff::Clear => {
+            *len = 0;
+            ready_values.push_back(VecDiff::Clear {});
+        }
+        VectorDiff::PushFront { value } => {
+            *len += 1;
+            ready_values.push_back(VecDiff::InsertAt { index: 0, value });
+        }
+        VectorDiff::PushBack { value } => {
+            *len += 1;
+            ready_values.push_back(VecDiff::Push { value });
+        }
+        VectorDiff::PopFront => {
+            *len -= 1;
+            ready_values.push_back(VecDiff::RemoveAt { index: 0 });
+        }
+        VectorDiff::PopBack => {
+            *len -= 1;
+            ready_values.push_back(VecDiff::Pop {});
+        }
+        VectorDiff::Insert { index, value } => {
+            *len += 1;
+            ready_values.push_back(VecDiff::InsertAt { index, value });
+        }
+        VectorDiff::Set { index, value } => {
+            ready_values.push_back(VecDiff::UpdateAt { index, value });
+        }
+        VectorDiff::Remove { index } => {
+            *len -= 1;
+            ready_values.push_back(VecDiff::RemoveAt { index });
+        }
+        VectorDiff::Truncate { length } => {
+            // `SignalVec` has no truncation, so pop the removed elements one
+            // by one.
+            ready_values.extend((length..*len).map(|_| VecDiff::Pop {}));
+            *len = length;
+        }
+        VectorDiff::Reset { values, .. } => {
+            *len = values.len();
+            ready_values.push_back(VecDiff::Replace { values: values.into_iter().collect() });
+        }
+        VectorDiff::Move { from, to } => {
+            ready_values.push_back(VecDiff::Move { old_index: from, new_index: to });
+        }
+    }
+}
+
+/// Apply the changes of a [`SignalVec`] to an [`ObservableVector`], until the
+/// `SignalVec` ends.
+///
+/// This is the reverse of [`VectorSignalVec`]: it allows using the adapters of
+/// this crate on data that originates from `futures-signals`, by subscribing
+/// to `vector` while the returned future is running. All changes that are
+/// ready at the same time are applied as a single transaction.
+///
+/// The lock is only held while applying changes, never across an `.await`.
+///
+/// # Panics
+///
+/// Panics if the lock is poisoned, or if the `SignalVec` yields a change that
+/// doesn't fit the current contents of `vector`, e.g. removing an element
+/// past the end.
+pub async fn ingest_signal_vec<S>(signal_vec: S, vector: &Mutex<ObservableVector<S::Item>>)
+where
+    S: SignalVec,
+    S::Item: Clone + 'static,
+{
+    let mut signal_vec = Box::pin(signal_vec);
+    loop {
+        let (diffs, done) = poll_fn(|cx| poll_ready_changes(signal_vec.as_mut(), cx)).await;
+        if !diffs.is_empty() {
+            let mut guard = vector.lock().unwrap();
+            let mut txn = guard.transaction();
+            for diff in diffs {
+                apply_vec_diff(&mut txn, diff);
+            }
+            txn.commit();
+        }
+
+        if done {
+            break;
+        }
+    }
+}
+
+/// Poll `signal_vec` until it is pending or has ended, collecting all changes
+/// it yields on the way.
+///
+/// Returns `Pending` if there are no changes, and whether the `SignalVec` has
+/// ended otherwise.
+fn poll_ready_changes<S: SignalVec>(
+    mut signal_vec: Pin<&mut S>,
+    cx: &mut task::Context<'_>,
+) -> Poll<(Vec<VecDiff<S::Item>>, bool)> {
+    let mut diffs = Vec::new();
+    loop {
+        match signal_vec.as_mut().poll_vec_change(cx) {
+            Poll::Ready(Some(diff)) => diffs.push(diff),
+            Poll::Ready(None) => return Poll::Ready((diffs, true)),
+            Poll::Pending if diffs.is_empty() => return Poll::Pending,
+            Poll::Pending => return Poll::Ready((diffs, false)),
+        }
+    }
+}
+
+fn apply_vec_diff<T: Clone + 'static>(
+    txn: &mut ObservableVectorTransaction<'_, T>,
+    diff: VecDiff<T>,
+) {
+    match diff {
+        VecDiff::Replace { values } => {
+            if !txn.is_empty() {
+                txn.clear();
+            }
+            txn.append(values.into_iter().collect());
+        }
+        VecDiff::InsertAt { index, value } => txn.insert(index, value),
+        VecDiff::UpdateAt { index, value } => {
+            txn.set(index, value);
+        }
+        VecDiff::RemoveAt { index } => {
+            txn.remove(index);
+        }
+        VecDiff::Move { old_index, new_index } => txn.move_item(old_index, new_index),
+        VecDiff::Push { value } => txn.push_back(value),
+        VecDiff::Pop {} => {
+            txn.pop_back();
+        }
+        VecDiff::Clear {} => txn.clear(),
+    }
+}
@@ -1,18 +1,20 @@
 use std::{
     cmp::Ordering,
+    fmt,
     ops::Not,
     pin::Pin,
     task::{self, ready, Poll},
 };
 
-use eyeball_im::{Vector, VectorDiff};
+use eyeball::{SharedObservable, Subscriber};
+use eyeball_im::{ResetReason, Vector, VectorDiff};
 use futures_core::Stream;
 use pin_project_lite::pin_project;
 use smallvec::SmallVec;
 
 use super::{
-    VectorDiffContainer, VectorDiffContainerOps, VectorDiffContainerStreamElement,
-    VectorDiffContainerStreamSortBuf,
+    index_after_move, reorder::reorder_moves, AdapterDebug, AdapterState, VectorDiffContainer,
+    VectorDiffContainerOps, VectorDiffContainerStreamElement, VectorDiffContainerStreamSortBuf,
 };
 
 type UnsortedIndex = usize;
@@ -107,6 +109,26 @@ where
     }
 }
 
+impl<S> AdapterDebug for Sort<S>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn adapter_state(&self) -> AdapterState {
+        self.inner.adapter_state("Sort")
+    }
+}
+
+impl<S> fmt::Debug for Sort<S>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.adapter_state(), f)
+    }
+}
+
 pin_project! {
     /// A [`VectorDiff`] stream adapter that presents a sorted view of the
     /// underlying [`ObservableVector`] items.
@@ -161,6 +183,159 @@ where
     }
 }
 
+impl<S, F> AdapterDebug for SortBy<S, F>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn adapter_state(&self) -> AdapterState {
+        self.inner.adapter_state("SortBy")
+    }
+}
+
+impl<S, F> fmt::Debug for SortBy<S, F>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.adapter_state(), f)
+    }
+}
+
+pin_project! {
+    /// A [`VectorDiff`] stream adapter that presents a sorted view of the
+    /// underlying [`ObservableVector`] items, with a comparison function that
+    /// can be replaced while the adapter is running.
+    ///
+    /// The comparison functions are taken from a stream, for example a
+    /// [`Subscriber`] mapped to a comparison closure, e.g. when the user
+    /// toggles the column a list is sorted by. Each time it yields a new
+    /// comparison function, the view is sorted again with a minimal number of
+    /// `VectorDiff::Move`s: values that are already in the right order
+    /// relative to each other stay where they are, and values that compare
+    /// equal keep their current order. If more than half of the values would
+    /// have to be moved, a `VectorDiff::Reset` is emitted instead. If several
+    /// comparison functions are ready at once, only the latest one is
+    /// applied. Otherwise this adapter
+    /// works exactly like [`Sort`], see that type's documentation for details
+    /// on how this adapter operates.
+    ///
+    /// ```rust
+    /// use std::cmp::Ordering;
+    ///
+    /// use eyeball::Observable;
+    /// use eyeball_im::{ObservableVector, VectorDiff};
+    /// use eyeball_im_util::vector::VectorObserverExt;
+    /// use imbl::vector;
+    /// use stream_assert::{assert_next_eq, assert_pending};
+    ///
+    /// fn compare(by_name: bool) -> impl Fn(&(u32, char), &(u32, char)) -> Ordering {
+    ///     move |a, b| if by_name { a.1.cmp(&b.1) } else { a.0.cmp(&b.0) }
+    /// }
+    ///
+    /// let ob = ObservableVector::from(vector![(2, 'a'), (1, 'b'), (3, 'c'), (4, 'd')]);
+    /// let mut by_name = Observable::new(false);
+    /// let compare_stream = Observable::subscribe(&by_name).map(|&b| compare(b));
+    /// let (values, mut sub) = ob.subscribe().dynamic_sort_by(compare(false), compare_stream);
+    /// assert_eq!(values, vector![(1, 'b'), (2, 'a'), (3, 'c'), (4, 'd')]);
+    ///
+    /// Observable::set(&mut by_name, true);
+    /// assert_next_eq!(sub, VectorDiff::Move { from: 0, to: 1 });
+    /// assert_pending!(sub);
+    /// ```
+    ///
+    /// [`ObservableVector`]: eyeball_im::ObservableVector
+    pub struct DynamicSortBy<S, F, P>
+    where
+        S: Stream,
+        S::Item: VectorDiffContainer,
+    {
+        #[pin]
+        inner: SortImpl<S>,
+
+        // The stream to poll new comparison functions from.
+        #[pin]
+        compare_stream: P,
+
+        // The current comparison function.
+        compare: F,
+    }
+}
+
+impl<S, F, P> DynamicSortBy<S, F, P>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    F: Fn(&VectorDiffContainerStreamElement<S>, &VectorDiffContainerStreamElement<S>) -> Ordering,
+    P: Stream<Item = F>,
+{
+    /// Create a new `DynamicSortBy` with the given (unsorted) initial values,
+    /// stream of `VectorDiff` updates for those values, initial comparison
+    /// function and stream of comparison functions.
+    pub fn new(
+        initial_values: Vector<VectorDiffContainerStreamElement<S>>,
+        inner_stream: S,
+        initial_compare: F,
+        compare_stream: P,
+    ) -> (Vector<VectorDiffContainerStreamElement<S>>, Self) {
+        let (initial_sorted, inner) = SortImpl::new(initial_values, inner_stream, &initial_compare);
+        (initial_sorted, Self { inner, compare_stream, compare: initial_compare })
+    }
+}
+
+impl<S, F, P> Stream for DynamicSortBy<S, F, P>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    F: Fn(&VectorDiffContainerStreamElement<S>, &VectorDiffContainerStreamElement<S>) -> Ordering,
+    P: Stream<Item = F>,
+{
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        // Ready values were computed with the current comparison function, so
+        // they have to be emitted before a new one can be applied.
+        if S::Item::sort_buf_len(&this.inner.ready_values) == 0 {
+            let mut new_compare = None;
+            while let Poll::Ready(Some(compare)) = this.compare_stream.as_mut().poll_next(cx) {
+                new_compare = Some(compare);
+            }
+
+            if let Some(compare) = new_compare {
+                *this.compare = compare;
+                if let Some(diffs) = this.inner.as_mut().resort_all(&*this.compare) {
+                    return Poll::Ready(Some(diffs));
+                }
+            }
+        }
+
+        this.inner.poll_next(cx, &*this.compare)
+    }
+}
+
+impl<S, F, P> AdapterDebug for DynamicSortBy<S, F, P>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn adapter_state(&self) -> AdapterState {
+        self.inner.adapter_state("DynamicSortBy")
+    }
+}
+
+impl<S, F, P> fmt::Debug for DynamicSortBy<S, F, P>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.adapter_state(), f)
+    }
+}
+
 pin_project! {
     /// A [`VectorDiff`] stream adapter that presents a sorted view of the
     /// underlying [`ObservableVector`] items.
@@ -169,6 +344,40 @@ pin_project! {
     /// and comparing those. Otherwise this adapter works exactly like [`Sort`],
     /// see that type's documentation for details on how this adapter operates.
     ///
+    /// If the keys depend on something other than the items themselves, e.g.
+    /// the current time, they can change without the underlying vector
+    /// changing. A [`ResortHandle`] obtained from
+    /// [`resort_handle`][Self::resort_handle] can be used to tell the adapter
+    /// about such changes, after which it moves the affected items to their
+    /// new position.
+    ///
+    /// ```rust
+    /// use std::{cell::Cell, rc::Rc};
+    ///
+    /// use eyeball_im::{ObservableVector, VectorDiff};
+    /// use eyeball_im_util::vector::VectorObserverExt;
+    /// use imbl::vector;
+    /// use stream_assert::{assert_next_eq, assert_pending};
+    ///
+    /// // Items are sorted by their value, except for a pinned one which comes
+    /// // first.
+    /// let pinned = Rc::new(Cell::new(None));
+    /// let key_fn = {
+    ///     let pinned = pinned.clone();
+    ///     move |v: &u32| (pinned.get() != Some(*v), *v)
+    /// };
+    ///
+    /// let ob = ObservableVector::<u32>::from(vector![3, 1, 2]);
+    /// let (values, mut sub) = ob.subscribe().sort_by_key(key_fn);
+    /// assert_eq!(values, vector![1, 2, 3]);
+    ///
+    /// // Pin the item at index 0 of `ob`.
+    /// pinned.set(Some(3));
+    /// sub.resort_handle().resort_indices([0]);
+    /// assert_next_eq!(sub, VectorDiff::Move { from: 2, to: 0 });
+    /// assert_pending!(sub);
+    /// ```
+    ///
     /// [`ObservableVector`]: eyeball_im::ObservableVector
     pub struct SortByKey<S, F>
     where
@@ -180,6 +389,14 @@ pin_project! {
 
         // The function to convert an item to a key used for comparison.
         key_fn: F,
+
+        // The unsorted indices of items whose key has changed, filled by
+        // `ResortHandle`s.
+        resort_requests: SharedObservable<Vec<UnsortedIndex>>,
+
+        // A subscriber to `resort_requests`, to get woken up when there are
+        // new requests.
+        resort_subscriber: Subscriber<Vec<UnsortedIndex>>,
     }
 }
 
@@ -199,7 +416,15 @@ where
     ) -> (Vector<VectorDiffContainerStreamElement<S>>, Self) {
         let (initial_sorted, inner) =
             SortImpl::new(initial_values, inner_stream, |a, b| key_fn(a).cmp(&key_fn(b)));
-        (initial_sorted, Self { inner, key_fn })
+        let resort_requests = SharedObservable::new(Vec::new());
+        let resort_subscriber = resort_requests.subscribe();
+        (initial_sorted, Self { inner, key_fn, resort_requests, resort_subscriber })
+    }
+
+    /// Get a handle for notifying this adapter about items whose key has
+    /// changed.
+    pub fn resort_handle(&self) -> ResortHandle {
+        ResortHandle { requests: self.resort_requests.clone() }
     }
 }
 
@@ -213,9 +438,66 @@ where
     type Item = S::Item;
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
-        let this = self.project();
+        let mut this = self.project();
         let key_fn = &*this.key_fn;
-        this.inner.poll_next(cx, |a, b| key_fn(a).cmp(&key_fn(b)))
+        let compare = |a: &_, b: &_| key_fn(a).cmp(&key_fn(b));
+
+        // Register interest in new requests. The requests themselves are taken
+        // out below, since they may have to wait for ready values to be
+        // emitted first.
+        _ = Pin::new(&mut *this.resort_subscriber).poll_next(cx);
+
+        if let Some(diffs) = this.inner.as_mut().resort(this.resort_requests, compare) {
+            return Poll::Ready(Some(diffs));
+        }
+
+        this.inner.poll_next(cx, compare)
+    }
+}
+
+impl<S, F> AdapterDebug for SortByKey<S, F>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn adapter_state(&self) -> AdapterState {
+        self.inner.adapter_state("SortByKey")
+    }
+}
+
+impl<S, F> fmt::Debug for SortByKey<S, F>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.adapter_state(), f)
+    }
+}
+
+/// A handle for notifying a [`SortByKey`] adapter that the keys of some items
+/// have changed without the items themselves changing.
+///
+/// Created by [`SortByKey::resort_handle`]. Handles can be cloned cheaply and
+/// are independent of the lifetime of the adapter; requests made after the
+/// adapter was dropped are ignored.
+#[derive(Clone, Debug)]
+pub struct ResortHandle {
+    requests: SharedObservable<Vec<UnsortedIndex>>,
+}
+
+impl ResortHandle {
+    /// Notify the adapter that the keys of the items at the given indices of
+    /// the underlying vector have changed.
+    ///
+    /// The indices are resolved the next time the adapter is polled, before
+    /// any updates of the underlying vector that it hasn't observed yet, so
+    /// they should be given after the adapter has caught up with those. The
+    /// affected items are moved to their new position with a
+    /// `VectorDiff::Move` each, items that are still at the right position
+    /// are left untouched and indices past the end are ignored.
+    pub fn resort_indices(&self, indices: impl IntoIterator<Item = usize>) {
+        self.requests.update(|requests| requests.extend(indices));
     }
 }
 
@@ -270,6 +552,62 @@ where
         )
     }
 
+    fn adapter_state(&self, name: &'static str) -> AdapterState {
+        AdapterState {
+            buffered_len: Some(self.buffered_vector.len()),
+            len: Some(self.buffered_vector.len()),
+            ready_values: S::Item::sort_buf_len(&self.ready_values),
+            ..AdapterState::new(name)
+        }
+    }
+
+    /// Take the pending requests out of `resort_requests` and move the
+    /// affected items, unless there are still ready values to be emitted.
+    fn resort<F>(
+        self: Pin<&mut Self>,
+        resort_requests: &SharedObservable<Vec<UnsortedIndex>>,
+        compare: F,
+    ) -> Option<S::Item>
+    where
+        F: Fn(
+            &VectorDiffContainerStreamElement<S>,
+            &VectorDiffContainerStreamElement<S>,
+        ) -> Ordering,
+    {
+        let this = self.project();
+        if S::Item::sort_buf_len(this.ready_values) != 0 {
+            return None;
+        }
+
+        // Don't notify our own subscriber about taking the requests out.
+        let mut indices = Vec::new();
+        resort_requests.update_if(|requests| {
+            indices = std::mem::take(requests);
+            false
+        });
+        if indices.is_empty() {
+            return None;
+        }
+
+        let diffs = resort_and_update_buffered_vector(indices, compare, this.buffered_vector);
+        S::Item::extend_sort_buf(diffs, this.ready_values)
+    }
+
+    /// Sort all values again with a new comparison function.
+    ///
+    /// Must only be called when there are no ready values to be emitted.
+    fn resort_all<F>(self: Pin<&mut Self>, compare: F) -> Option<S::Item>
+    where
+        F: Fn(
+            &VectorDiffContainerStreamElement<S>,
+            &VectorDiffContainerStreamElement<S>,
+        ) -> Ordering,
+    {
+        let this = self.project();
+        let diffs = resort_all_and_update_buffered_vector(compare, this.buffered_vector);
+        S::Item::extend_sort_buf(diffs, this.ready_values)
+    }
+
     fn poll_next<F>(
         self: Pin<&mut Self>,
         cx: &mut task::Context<'_>,
@@ -316,7 +654,7 @@ where
 /// value?), `Vector::binary_search_by` is used — it is possible because the
 /// `Vector` is sorted. When looking for the _unsorted index_ of a value,
 /// `Iterator::position` is used.
-fn handle_diff_and_update_buffered_vector<T, F>(
+pub(super) fn handle_diff_and_update_buffered_vector<T, F>(
     diff: VectorDiff<T>,
     compare: F,
     buffered_vector: &mut Vector<(usize, T)>,
@@ -641,57 +979,61 @@ where
                     Err(index) => index,
                 };
 
-            match old_index.cmp(&new_index) {
-                // `old_index` is before `new_index`.
-                // Remove value at `old_index`, and insert the new value at `new_index - 1`: we need
-                // to subtract 1 because `old_index` has been removed before `new_insert`, which
-                // has shifted the indices.
-                //
-                // SAFETY: `new_index - 1` won't underflow because `new_index` is necessarily
-                // greater than `old_index` here. `old_index` cannot be lower than 0, so
-                // `new_index` cannot be lower than 1, hence `new_index - 1` cannot be lower
-                // than 0.
-                Ordering::Less => {
-                    let new_index = new_index - 1;
-                    let new_unsorted_index_with_value = (new_unsorted_index, new_value.clone());
-
-                    // If `old_index == new_index`, we are clearly updating the same index.
-                    // Then, let's emit a `VectorDiff::Set`.
-                    if old_index == new_index {
-                        buffered_vector.set(old_index, new_unsorted_index_with_value);
-
-                        result.push(VectorDiff::Set { index: old_index, value: new_value });
-                    } else {
-                        buffered_vector.remove(old_index);
-                        buffered_vector.insert(new_index, new_unsorted_index_with_value);
-
-                        result.push(VectorDiff::Remove { index: old_index });
-                        result.push(VectorDiff::Insert { index: new_index, value: new_value });
-                    }
-                }
-                // `old_index` is the same as `new_index`.
-                Ordering::Equal => {
-                    buffered_vector.set(new_index, (new_unsorted_index, new_value.clone()));
-                    result.push(VectorDiff::Set { index: new_index, value: new_value });
-                }
-                // `old_index` is after `new_index`.
-                // Remove value at `old_index`, and insert the new value at `new_index`. No shifting
-                // here.
-                Ordering::Greater => {
-                    buffered_vector.remove(old_index);
-                    buffered_vector.insert(new_index, (new_unsorted_index, new_value.clone()));
-
-                    result.push(VectorDiff::Remove { index: old_index });
-                    result.push(VectorDiff::Insert { index: new_index, value: new_value });
-                }
+            // If `old_index` is before `new_index`, we need to subtract 1 from `new_index`
+            // because removing the value at `old_index` shifts the indices after it.
+            //
+            // SAFETY: `new_index - 1` won't underflow because `new_index` is necessarily
+            // greater than `old_index` here. `old_index` cannot be lower than 0, so
+            // `new_index` cannot be lower than 1, hence `new_index - 1` cannot be lower
+            // than 0.
+            let new_index = if old_index < new_index { new_index - 1 } else { new_index };
+
+            // Update the value in place with a `VectorDiff::Set`, then move it with a
+            // `VectorDiff::Move` if its sorted position has changed. Compared to a
+            // `VectorDiff::Remove` + `VectorDiff::Insert`, this preserves the identity of
+            // the value, e.g. for animating its relocation.
+            buffered_vector.set(old_index, (new_unsorted_index, new_value.clone()));
+            result.push(VectorDiff::Set { index: old_index, value: new_value });
+
+            if old_index != new_index {
+                let item = buffered_vector.remove(old_index);
+                buffered_vector.insert(new_index, item);
+                result.push(VectorDiff::Move { from: old_index, to: new_index });
             }
         }
         VectorDiff::Truncate { length: new_length } => {
-            // Keep values where their `unsorted_index` is lower than the `new_length`.
-            buffered_vector.retain(|(unsorted_index, _)| *unsorted_index < new_length);
-            result.push(VectorDiff::Truncate { length: new_length });
+            // Find the sorted indices of the values to remove, i.e. the values where their
+            // `unsorted_index` isn't lower than the `new_length`.
+            let removed_indices: Vec<_> = buffered_vector
+                .iter()
+                .enumerate()
+                .filter(|(_, (unsorted_index, _))| *unsorted_index >= new_length)
+                .map(|(sorted_index, _)| sorted_index)
+                .collect();
+            let remaining_length = buffered_vector.len() - removed_indices.len();
+
+            // All at the end? Let's emit a `VectorDiff::Truncate`.
+            if removed_indices.first().map_or(true, |&index| index >= remaining_length) {
+                buffered_vector.truncate(remaining_length);
+                result.push(VectorDiff::Truncate { length: remaining_length });
+            }
+            // Otherwise, let's emit a `VectorDiff::Remove` for each of them, from the
+            // back so that the indices stay valid.
+            else {
+                for index in removed_indices.into_iter().rev() {
+                    buffered_vector.remove(index);
+                    result.push(VectorDiff::Remove { index });
+                }
+            }
+        }
+        VectorDiff::Move { from, to } => {
+            // Moving a value doesn't change the sorted order. Only the unsorted indices
+            // need to be updated.
+            buffered_vector.iter_mut().for_each(|(unsorted_index, _)| {
+                *unsorted_index = index_after_move(*unsorted_index, from, to);
+            });
         }
-        VectorDiff::Reset { values: new_values } => {
+        VectorDiff::Reset { values: new_values, reason } => {
             // Calculate the `new_values` with their `unsorted_index`.
             let mut new_values = new_values.into_iter().enumerate().collect::<Vector<_>>();
 
@@ -702,9 +1044,113 @@ where
             *buffered_vector = new_values.clone();
             result.push(VectorDiff::Reset {
                 values: new_values.into_iter().map(|(_, value)| value).collect(),
+                reason,
             });
         }
     }
 
     result
 }
+
+/// Move the values at the given unsorted indices of `buffered_vector`, whose
+/// keys have changed, to their new sorted position.
+///
+/// All other values are assumed to still be sorted relative to each other.
+/// Values are moved one after another, to right before the first value
+/// greater than them that is not going to be moved later, which keeps those
+/// values sorted relative to each other as well.
+fn resort_and_update_buffered_vector<T, F>(
+    indices: Vec<UnsortedIndex>,
+    compare: F,
+    buffered_vector: &mut Vector<(UnsortedIndex, T)>,
+) -> Vec<VectorDiff<T>>
+where
+    T: Clone,
+    F: Fn(&T, &T) -> Ordering,
+{
+    let len = buffered_vector.len();
+    let mut pending = vec![false; len];
+    for index in indices {
+        if let Some(pending) = pending.get_mut(index) {
+            *pending = true;
+        }
+    }
+
+    let mut result = Vec::new();
+    for unsorted_index in 0..len {
+        if !pending[unsorted_index] {
+            continue;
+        }
+        pending[unsorted_index] = false;
+
+        let from = buffered_vector
+            .iter()
+            .position(|(index, _)| *index == unsorted_index)
+            .expect("`buffered_vector` must contain an item for every unsorted index");
+        let value = &buffered_vector[from].1;
+
+        // If the value is still in order with the values around it that are
+        // not going to be moved, there is nothing to do.
+        let is_settled = |(index, _): &&(UnsortedIndex, T)| !pending[*index];
+        let prev = buffered_vector.iter().take(from).rev().find(is_settled);
+        let next = buffered_vector.iter().skip(from + 1).find(is_settled);
+        if prev.map_or(true, |(_, prev)| compare(prev, value).is_le())
+            && next.map_or(true, |(_, next)| compare(value, next).is_le())
+        {
+            continue;
+        }
+
+        let item = buffered_vector.remove(from);
+        let to = buffered_vector
+            .iter()
+            .position(|(index, other)| !pending[*index] && compare(other, &item.1).is_gt())
+            .unwrap_or(len - 1);
+        buffered_vector.insert(to, item);
+        result.push(VectorDiff::Move { from, to });
+    }
+
+    result
+}
+
+/// Sort all values of `buffered_vector` again with a new comparison function,
+/// moving as few values as possible.
+///
+/// See [`reorder_moves`] for which values are moved. If too many would have to
+/// be moved, the values are sorted and a `Reset` is emitted instead. Values
+/// that compare equal keep their current order.
+fn resort_all_and_update_buffered_vector<T, F>(
+    compare: F,
+    buffered_vector: &mut Vector<(UnsortedIndex, T)>,
+) -> Vec<VectorDiff<T>>
+where
+    T: Clone,
+    F: Fn(&T, &T) -> Ordering,
+{
+    // The current index of every value, in sorted order.
+    let order = {
+        let values: Vec<_> = buffered_vector.iter().map(|(_, value)| value).collect();
+        let mut order: Vec<_> = (0..values.len()).collect();
+        order.sort_by(|&a, &b| compare(values[a], values[b]));
+        order
+    };
+
+    let mut new_indices = vec![0; order.len()];
+    for (new_index, &old_index) in order.iter().enumerate() {
+        new_indices[old_index] = new_index;
+    }
+
+    let Some(moves) = reorder_moves(&new_indices) else {
+        *buffered_vector = order.into_iter().map(|index| buffered_vector[index].clone()).collect();
+        let values = buffered_vector.iter().map(|(_, value)| value.clone()).collect();
+        return vec![VectorDiff::Reset { values, reason: ResetReason::Update }];
+    };
+
+    moves
+        .into_iter()
+        .map(|(from, to)| {
+            let item = buffered_vector.remove(from);
+            buffered_vector.insert(to, item);
+            VectorDiff::Move { from, to }
+        })
+        .collect()
+}
@@ -0,0 +1,513 @@
+use std::{
+    cmp::min,
+    fmt,
+    pin::Pin,
+    task::{self, ready, Poll},
+};
+
+use eyeball_im::{Vector, VectorDiff};
+use futures_core::Stream;
+use pin_project_lite::pin_project;
+
+use super::{
+    index_after_move, AdapterDebug, AdapterState, EmptyLimitStream, VectorDiffContainer,
+    VectorDiffContainerOps, VectorDiffContainerStreamElement, VectorDiffContainerStreamTailBuf,
+};
+
+type UnsortedIndex = usize;
+
+pin_project! {
+    /// A [`VectorDiff`] stream adapter that presents a sorted view of the
+    /// smallest items of the underlying [`ObservableVector`], limited to a
+    /// certain number of items.
+    ///
+    /// This produces the same view as [`Sort`](super::Sort) followed by
+    /// [`Head`](super::Head), but only the items inside of the view are kept
+    /// sorted: The initial values are selected without sorting all of them,
+    /// and updates of items that are not part of the view and don't enter it
+    /// cost next to nothing and produce no diffs. When an item leaves the
+    /// view, e.g. because it is removed, the remaining items are scanned for
+    /// the one that takes its place. Like with `Head`, the limit can be
+    /// changed while the adapter is running by passing a stream of limits.
+    ///
+    /// The order of items that compare equal is unspecified.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use eyeball_im::{ObservableVector, VectorDiff};
+    /// use eyeball_im_util::vector::VectorObserverExt;
+    /// use imbl::vector;
+    /// use stream_assert::{assert_closed, assert_next_eq, assert_pending};
+    ///
+    /// let mut ob = ObservableVector::<u32>::from(vector![50, 10, 40, 30]);
+    /// let (values, mut sub) = ob.subscribe().sort_limit(2);
+    /// assert_eq!(values, vector![10, 30]);
+    ///
+    /// // Items that don't enter the view produce no diffs.
+    /// ob.push_back(60);
+    /// assert_pending!(sub);
+    ///
+    /// // An item that enters the view pushes the largest one out of it.
+    /// ob.push_back(20);
+    /// assert_next_eq!(sub, VectorDiff::PopBack);
+    /// assert_next_eq!(sub, VectorDiff::PushBack { value: 20 });
+    ///
+    /// // An item that leaves the view is replaced by the next smallest one.
+    /// ob.remove(1);
+    /// assert_next_eq!(sub, VectorDiff::PopFront);
+    /// assert_next_eq!(sub, VectorDiff::PushBack { value: 30 });
+    /// assert_pending!(sub);
+    ///
+    /// drop(ob);
+    /// assert_closed!(sub);
+    /// ```
+    ///
+    /// [`ObservableVector`]: eyeball_im::ObservableVector
+    #[project = SortLimitProj]
+    pub struct SortLimit<S, L>
+    where
+        S: Stream,
+        S::Item: VectorDiffContainer,
+    {
+        // The main stream to poll items from.
+        #[pin]
+        inner_stream: S,
+
+        // The limit stream to poll new limits from.
+        #[pin]
+        limit_stream: L,
+
+        // The buffered vector that is updated with the main stream's items, in
+        // their original order.
+        buffered_vector: Vector<VectorDiffContainerStreamElement<S>>,
+
+        // The **sorted** items of the view, along with their index in
+        // `buffered_vector`. Always contains `min(limit, buffered_vector.len())`
+        // items, none of which are greater than any item outside of the view.
+        view: Vector<(UnsortedIndex, VectorDiffContainerStreamElement<S>)>,
+
+        // The current limit.
+        limit: usize,
+
+        // This adapter can produce many items per item of the underlying stream.
+        //
+        // Thus, if the item type is just `VectorDiff<_>` (non-bached, can't
+        // just add diffs to a `poll_next` result), we need a buffer to store the
+        // possible extra items in.
+        ready_values: VectorDiffContainerStreamTailBuf<S>,
+    }
+}
+
+impl<S> SortLimit<S, EmptyLimitStream>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    VectorDiffContainerStreamElement<S>: Ord,
+{
+    /// Create a new `SortLimit` with the given (unsorted) initial values,
+    /// stream of `VectorDiff` updates for those values, and a fixed limit.
+    ///
+    /// Returns the `limit` smallest initial values in sorted order as well as
+    /// a stream of updates to them.
+    pub fn new(
+        initial_values: Vector<VectorDiffContainerStreamElement<S>>,
+        inner_stream: S,
+        limit: usize,
+    ) -> (Vector<VectorDiffContainerStreamElement<S>>, Self) {
+        Self::dynamic_with_initial_limit(initial_values, inner_stream, limit, EmptyLimitStream)
+    }
+}
+
+impl<S, L> SortLimit<S, L>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    VectorDiffContainerStreamElement<S>: Ord,
+    L: Stream<Item = usize>,
+{
+    /// Create a new `SortLimit` with the given (unsorted) initial values,
+    /// stream of `VectorDiff` updates for those values, and a stream of
+    /// limits.
+    ///
+    /// This is equivalent to `dynamic_with_initial_limit` where the
+    /// `initial_limit` is 0, except that it doesn't return the limited
+    /// vector as it would be empty anyways.
+    ///
+    /// Note that the returned `SortLimit` won't produce anything until the
+    /// first limit is produced by the limit stream.
+    pub fn dynamic(
+        initial_values: Vector<VectorDiffContainerStreamElement<S>>,
+        inner_stream: S,
+        limit_stream: L,
+    ) -> Self {
+        Self::dynamic_with_initial_limit(initial_values, inner_stream, 0, limit_stream).1
+    }
+
+    /// Create a new `SortLimit` with the given (unsorted) initial values,
+    /// stream of `VectorDiff` updates for those values, and an initial limit
+    /// as well as a stream of new limits.
+    pub fn dynamic_with_initial_limit(
+        initial_values: Vector<VectorDiffContainerStreamElement<S>>,
+        inner_stream: S,
+        initial_limit: usize,
+        limit_stream: L,
+    ) -> (Vector<VectorDiffContainerStreamElement<S>>, Self) {
+        let view = smallest(initial_values.iter().cloned().enumerate(), initial_limit);
+        let stream = Self {
+            inner_stream,
+            limit_stream,
+            buffered_vector: initial_values,
+            view: view.clone(),
+            limit: initial_limit,
+            ready_values: Default::default(),
+        };
+
+        (view.into_iter().map(|(_, value)| value).collect(), stream)
+    }
+}
+
+impl<S, L> Stream for SortLimit<S, L>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    VectorDiffContainerStreamElement<S>: Ord,
+    L: Stream<Item = usize>,
+{
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        self.project().poll_next(cx)
+    }
+}
+
+impl<S, L> AdapterDebug for SortLimit<S, L>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn adapter_state(&self) -> AdapterState {
+        AdapterState {
+            buffered_len: Some(self.buffered_vector.len()),
+            len: Some(self.view.len()),
+            limit: Some(self.limit),
+            ready_values: S::Item::tail_buf_len(&self.ready_values),
+            ..AdapterState::new("SortLimit")
+        }
+    }
+}
+
+impl<S, L> fmt::Debug for SortLimit<S, L>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.adapter_state(), f)
+    }
+}
+
+impl<S, L> SortLimitProj<'_, S, L>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    VectorDiffContainerStreamElement<S>: Ord,
+    L: Stream<Item = usize>,
+{
+    fn poll_next(&mut self, cx: &mut task::Context<'_>) -> Poll<Option<S::Item>> {
+        loop {
+            // First off, if any values are ready, return them.
+            if let Some(value) = S::Item::pop_from_tail_buf(self.ready_values) {
+                return Poll::Ready(Some(value));
+            }
+
+            // Poll a new limit from `limit_stream` before polling `inner_stream`.
+            while let Poll::Ready(Some(next_limit)) = self.limit_stream.as_mut().poll_next(cx) {
+                let mut view = View { items: self.view, diffs: Vec::new() };
+                *self.limit = next_limit;
+                view.truncate(next_limit);
+                view.fill(self.buffered_vector, next_limit);
+
+                if let Some(diffs) = S::Item::extend_tail_buf(view.diffs, self.ready_values) {
+                    return Poll::Ready(Some(diffs));
+                }
+            }
+
+            // Poll `VectorDiff`s from the `inner_stream`.
+            let Some(diffs) = ready!(self.inner_stream.as_mut().poll_next(cx)) else {
+                return Poll::Ready(None);
+            };
+
+            let mut view = View { items: self.view, diffs: Vec::new() };
+            for diff in diffs.into_vec() {
+                view.handle_diff(diff, self.buffered_vector, *self.limit);
+            }
+
+            if let Some(diffs) = S::Item::extend_tail_buf(view.diffs, self.ready_values) {
+                return Poll::Ready(Some(diffs));
+            }
+
+            // Else loop and poll the streams again.
+        }
+    }
+}
+
+/// The sorted view of a [`SortLimit`], along with the diffs produced by
+/// updating it.
+struct View<'a, T: Clone> {
+    items: &'a mut Vector<(UnsortedIndex, T)>,
+    diffs: Vec<VectorDiff<T>>,
+}
+
+impl<T: Clone + Ord> View<'_, T> {
+    fn handle_diff(&mut self, diff: VectorDiff<T>, buffered_vector: &mut Vector<T>, limit: usize) {
+        match diff {
+            VectorDiff::Append { values } => {
+                for value in values {
+                    self.insert(buffered_vector, limit, buffered_vector.len(), value);
+                }
+            }
+            VectorDiff::Clear => {
+                buffered_vector.clear();
+                self.items.clear();
+                self.diffs.push(VectorDiff::Clear);
+            }
+            VectorDiff::PushFront { value } => self.insert(buffered_vector, limit, 0, value),
+            VectorDiff::PushBack { value } => {
+                self.insert(buffered_vector, limit, buffered_vector.len(), value);
+            }
+            VectorDiff::PopFront => self.remove(buffered_vector, limit, 0),
+            VectorDiff::PopBack => {
+                self.remove(buffered_vector, limit, buffered_vector.len() - 1);
+            }
+            VectorDiff::Insert { index, value } => {
+                self.insert(buffered_vector, limit, index, value);
+            }
+            VectorDiff::Set { index, value } => self.set(buffered_vector, limit, index, value),
+            VectorDiff::Remove { index } => self.remove(buffered_vector, limit, index),
+            VectorDiff::Truncate { length } => {
+                buffered_vector.truncate(length);
+
+                let positions = self.positions(|index| index >= length);
+                let tail = self.items.len() - positions.len();
+                if positions.iter().all(|&position| position >= tail) {
+                    // The removed items are exactly the last ones of the view.
+                    self.truncate(tail);
+                } else {
+                    // Remove them from the back of the view, so the positions
+                    // of the items before them stay valid.
+                    for position in positions {
+                        self.remove_at(position);
+                    }
+                }
+
+                self.fill(buffered_vector, limit);
+            }
+            VectorDiff::Move { from, to } => {
+                let value = buffered_vector.remove(from);
+                buffered_vector.insert(to, value);
+
+                // The items don't change, so neither does their order.
+                for (index, _) in self.items.iter_mut() {
+                    *index = index_after_move(*index, from, to);
+                }
+            }
+            VectorDiff::Reset { values, reason } => {
+                *self.items = smallest(values.iter().cloned().enumerate(), limit);
+                *buffered_vector = values;
+                let values = self.items.iter().map(|(_, value)| value.clone()).collect();
+                self.diffs.push(VectorDiff::Reset { values, reason });
+            }
+        }
+    }
+
+    /// Insert `value` at `index` of `buffered_vector`, and into the view if it
+    /// is among the `limit` smallest items.
+    fn insert(&mut self, buffered_vector: &mut Vector<T>, limit: usize, index: usize, value: T) {
+        buffered_vector.insert(index, value.clone());
+        for (unsorted_index, _) in self.items.iter_mut() {
+            if *unsorted_index >= index {
+                *unsorted_index += 1;
+            }
+        }
+
+        self.offer(limit, index, value);
+    }
+
+    /// Remove the item at `index` of `buffered_vector`, and from the view if
+    /// it was part of it.
+    fn remove(&mut self, buffered_vector: &mut Vector<T>, limit: usize, index: usize) {
+        buffered_vector.remove(index);
+        if let Some(&position) = self.positions(|i| i == index).first() {
+            self.remove_at(position);
+        }
+
+        for (unsorted_index, _) in self.items.iter_mut() {
+            if *unsorted_index > index {
+                *unsorted_index -= 1;
+            }
+        }
+
+        self.fill(buffered_vector, limit);
+    }
+
+    /// Replace the item at `index` of `buffered_vector` with `value`, and
+    /// update the view accordingly.
+    fn set(&mut self, buffered_vector: &mut Vector<T>, limit: usize, index: usize, value: T) {
+        buffered_vector.set(index, value.clone());
+
+        let Some(&position) = self.positions(|i| i == index).first() else {
+            // The item was not part of the view, its replacement may be.
+            self.offer(limit, index, value);
+            return;
+        };
+
+        // The replacement leaves the view if an item outside of it is smaller.
+        let next = self.smallest_outside(buffered_vector, 1).pop_front();
+        if let Some(next) = next.filter(|(_, next)| *next < value) {
+            self.remove_at(position);
+            self.push_back(next);
+            return;
+        }
+
+        self.items.remove(position);
+        let new_position =
+            self.items.binary_search_by(|(_, v)| v.cmp(&value)).unwrap_or_else(|p| p);
+        if new_position == position {
+            self.diffs.push(VectorDiff::Set { index: position, value: value.clone() });
+        } else {
+            self.diffs.push(remove_diff(position, self.items.len() + 1));
+            self.diffs.push(insert_diff(new_position, self.items.len(), value.clone()));
+        }
+        self.items.insert(new_position, (index, value));
+    }
+
+    /// Insert the item with the given unsorted index into the view if it is
+    /// among the `limit` smallest items, pushing the largest item out of the
+    /// view if it's full.
+    fn offer(&mut self, limit: usize, index: UnsortedIndex, value: T) {
+        if self.items.len() >= limit {
+            match self.items.last() {
+                Some((_, last)) if value < *last => {
+                    self.items.pop_back();
+                    self.diffs.push(VectorDiff::PopBack);
+                }
+                _ => return,
+            }
+        }
+
+        let position = self.items.binary_search_by(|(_, v)| v.cmp(&value)).unwrap_or_else(|p| p);
+        self.diffs.push(insert_diff(position, self.items.len(), value.clone()));
+        self.items.insert(position, (index, value));
+    }
+
+    /// Shrink the view to at most `limit` items.
+    fn truncate(&mut self, limit: usize) {
+        if limit < self.items.len() {
+            self.items.truncate(limit);
+            self.diffs.push(VectorDiff::Truncate { length: limit });
+        }
+    }
+
+    /// Add the smallest items outside of the view to it until it contains
+    /// `limit` items or all of `buffered_vector`.
+    fn fill(&mut self, buffered_vector: &Vector<T>, limit: usize) {
+        let missing = min(limit, buffered_vector.len()).saturating_sub(self.items.len());
+        let mut items = self.smallest_outside(buffered_vector, missing);
+
+        match items.len() {
+            0 => {}
+            1 => self.push_back(items.pop_front().unwrap()),
+            _ => {
+                self.items.append(items.clone());
+                let values = items.into_iter().map(|(_, value)| value).collect();
+                self.diffs.push(VectorDiff::Append { values });
+            }
+        }
+    }
+
+    /// Get the `n` smallest items of `buffered_vector` that are not part of
+    /// the view, in sorted order.
+    fn smallest_outside(
+        &self,
+        buffered_vector: &Vector<T>,
+        n: usize,
+    ) -> Vector<(UnsortedIndex, T)> {
+        if n == 0 {
+            return Vector::new();
+        }
+
+        let mut in_view: Vec<_> = self.items.iter().map(|(index, _)| *index).collect();
+        in_view.sort_unstable();
+
+        let outside = buffered_vector
+            .iter()
+            .cloned()
+            .enumerate()
+            .filter(|(index, _)| in_view.binary_search(index).is_err());
+        smallest(outside, n)
+    }
+
+    /// Get the positions in the view of the items whose unsorted index
+    /// matches `f`, in descending order.
+    fn positions(&self, f: impl Fn(UnsortedIndex) -> bool) -> Vec<usize> {
+        let mut positions: Vec<_> = self
+            .items
+            .iter()
+            .enumerate()
+            .filter(|(_, (index, _))| f(*index))
+            .map(|(position, _)| position)
+            .collect();
+        positions.reverse();
+        positions
+    }
+
+    fn remove_at(&mut self, position: usize) {
+        self.diffs.push(remove_diff(position, self.items.len()));
+        self.items.remove(position);
+    }
+
+    fn push_back(&mut self, (index, value): (UnsortedIndex, T)) {
+        self.items.push_back((index, value.clone()));
+        self.diffs.push(VectorDiff::PushBack { value });
+    }
+}
+
+/// Get the `n` smallest of the given items, in sorted order.
+///
+/// Only those items are sorted, the others are just partitioned off.
+fn smallest<T: Ord + Clone>(
+    items: impl Iterator<Item = (UnsortedIndex, T)>,
+    n: usize,
+) -> Vector<(UnsortedIndex, T)> {
+    let compare = |(_, a): &(_, T), (_, b): &(_, T)| a.cmp(b);
+
+    let mut items: Vec<_> = items.collect();
+    if n < items.len() {
+        if n == 0 {
+            return Vector::new();
+        }
+
+        items.select_nth_unstable_by(n - 1, compare);
+        items.truncate(n);
+    }
+
+    items.sort_by(compare);
+    items.into_iter().collect()
+}
+
+fn insert_diff<T: Clone>(position: usize, len: usize, value: T) -> VectorDiff<T> {
+    match position {
+        0 => VectorDiff::PushFront { value },
+        p if p == len => VectorDiff::PushBack { value },
+        index => VectorDiff::Insert { index, value },
+    }
+}
+
+fn remove_diff<T: Clone>(position: usize, len: usize) -> VectorDiff<T> {
+    match position {
+        0 => VectorDiff::PopFront,
+        p if p + 1 == len => VectorDiff::PopBack,
+        index => VectorDiff::Remove { index },
+    }
+}
@@ -0,0 +1,152 @@
+use std::{
+    fmt,
+    pin::Pin,
+    task::{self, Poll},
+};
+
+use eyeball_im::{ResetReason, Vector, VectorDiff};
+use futures_core::Stream;
+use pin_project_lite::pin_project;
+
+use super::{
+    AdapterDebug, AdapterState, VectorDiffContainer, VectorDiffContainerOps,
+    VectorDiffContainerStreamElement, VectorObserver,
+};
+
+/// Observe the vector of `initial`, switching over to the vector of each
+/// observer yielded by `switches` as it arrives.
+///
+/// See [`SwitchVectors`] for more details.
+pub fn switch_vectors<T, O, S>(initial: O, switches: S) -> (Vector<T>, SwitchVectors<S, O::Stream>)
+where
+    T: Clone + 'static,
+    O: VectorObserver<T>,
+    <O::Stream as Stream>::Item: VectorDiffContainer<Element = T>,
+    S: Stream<Item = O>,
+{
+    let (values, current) = initial.into_parts();
+    let stream = SwitchVectors { switches, switches_done: false, current: Some(current) };
+    (values, stream)
+}
+
+pin_project! {
+    /// A [`VectorDiff`] stream that forwards the diffs of one observed vector
+    /// at a time, switching to another one whenever a stream of
+    /// [`VectorObserver`]s yields a new one.
+    ///
+    /// On every switch, the subscription to the previous vector is dropped and
+    /// a `VectorDiff::Reset` with the values of the new vector is emitted,
+    /// followed by the diffs of the new vector. This is useful when the vector
+    /// to present depends on some other state, like the currently selected
+    /// room in a chat application. If several observers were yielded since
+    /// the stream was last polled, only the latest one is switched to.
+    ///
+    /// The stream ends once the stream of observers has ended and the stream
+    /// of the current vector has ended too.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::cell::RefCell;
+    ///
+    /// use eyeball::Observable;
+    /// use eyeball_im::{ObservableVector, ResetReason, VectorDiff};
+    /// use eyeball_im_util::vector::switch_vectors;
+    /// use imbl::vector;
+    /// use stream_assert::{assert_next_eq, assert_pending};
+    ///
+    /// let rooms = [
+    ///     RefCell::new(ObservableVector::from(vector!["hi"])),
+    ///     RefCell::new(ObservableVector::from(vector!["hello", "there"])),
+    /// ];
+    /// let mut selected_room = Observable::new(0);
+    ///
+    /// let switches =
+    ///     Observable::subscribe(&selected_room).map(|&idx| rooms[idx].borrow().subscribe());
+    /// let (values, mut sub) = switch_vectors(rooms[0].borrow().subscribe(), switches);
+    /// assert_eq!(values, vector!["hi"]);
+    ///
+    /// rooms[0].borrow_mut().push_back("bye");
+    /// assert_next_eq!(sub, VectorDiff::PushBack { value: "bye" });
+    ///
+    /// Observable::set(&mut selected_room, 1);
+    /// assert_next_eq!(
+    ///     sub,
+    ///     VectorDiff::Reset { values: vector!["hello", "there"], reason: ResetReason::Update }
+    /// );
+    ///
+    /// // Updates of the previous room are not forwarded anymore.
+    /// rooms[0].borrow_mut().clear();
+    /// assert_pending!(sub);
+    /// ```
+    pub struct SwitchVectors<S, St> {
+        // The stream of observers to switch to.
+        #[pin]
+        switches: S,
+
+        // Whether `switches` has ended.
+        switches_done: bool,
+
+        // The stream of the current vector, `None` once it has ended.
+        #[pin]
+        current: Option<St>,
+    }
+}
+
+impl<S, St> Stream for SwitchVectors<S, St>
+where
+    St: Stream,
+    St::Item: VectorDiffContainer,
+    S: Stream,
+    S::Item: VectorObserver<VectorDiffContainerStreamElement<St>, Stream = St>,
+{
+    type Item = St::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        // Switches come first, so no diffs of a vector that was already
+        // switched away from are emitted.
+        let mut latest = None;
+        while !*this.switches_done {
+            match this.switches.as_mut().poll_next(cx) {
+                Poll::Ready(Some(observer)) => latest = Some(observer),
+                Poll::Ready(None) => *this.switches_done = true,
+                Poll::Pending => break,
+            }
+        }
+
+        if let Some(observer) = latest {
+            let (values, stream) = observer.into_parts();
+            this.current.set(Some(stream));
+            let reset = VectorDiff::Reset { values, reason: ResetReason::Update };
+            return Poll::Ready(Some(St::Item::from_item(reset)));
+        }
+
+        if let Some(current) = this.current.as_mut().as_pin_mut() {
+            match current.poll_next(cx) {
+                Poll::Ready(Some(diffs)) => return Poll::Ready(Some(diffs)),
+                Poll::Ready(None) => this.current.set(None),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        if *this.switches_done {
+            Poll::Ready(None)
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+impl<S, St> AdapterDebug for SwitchVectors<S, St> {
+    fn adapter_state(&self) -> AdapterState {
+        AdapterState::new("SwitchVectors")
+    }
+}
+
+impl<S, St> fmt::Debug for SwitchVectors<S, St> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.adapter_state(), f)
+    }
+}
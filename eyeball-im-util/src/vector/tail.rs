@@ -1,6 +1,7 @@
 use smallvec::SmallVec;
 use std::{
     cmp::{min, Ordering},
+    fmt,
     iter::repeat,
     mem,
     pin::Pin,
@@ -8,9 +9,11 @@ use std::{
 };
 
 use super::{
-    EmptyLimitStream, VectorDiffContainer, VectorDiffContainerOps,
-    VectorDiffContainerStreamElement, VectorDiffContainerStreamTailBuf, VectorObserver,
+    AdapterDebug, AdapterState, EmptyLimitStream, Saturation, VectorDiffContainer,
+    VectorDiffContainerOps, VectorDiffContainerStreamElement, VectorDiffContainerStreamTailBuf,
+    VectorObserver,
 };
+use eyeball::{SharedObservable, Subscriber};
 use eyeball_im::VectorDiff;
 use futures_core::Stream;
 use imbl::Vector;
@@ -113,6 +116,9 @@ pin_project! {
         // is removed, but 10 has to be pushed front as it "enters" the "view".
         // That second `PushFront` diff is buffered here.
         ready_values: VectorDiffContainerStreamTailBuf<S>,
+
+        // Whether the view is full and whether items exist beyond it.
+        saturation: SharedObservable<Saturation>,
     }
 }
 
@@ -156,12 +162,15 @@ where
         inner_stream: S,
         limit_stream: L,
     ) -> Self {
+        let saturation = SharedObservable::new(Saturation::new(initial_values.len(), 0));
+
         Self {
             inner_stream,
             limit_stream,
             buffered_vector: initial_values,
             limit: 0,
             ready_values: Default::default(),
+            saturation,
         }
     }
 
@@ -175,6 +184,7 @@ where
         limit_stream: L,
     ) -> (Vector<VectorDiffContainerStreamElement<S>>, Self) {
         let buffered_vector = initial_values.clone();
+        let buffered_len = buffered_vector.len();
 
         let initial_values = if initial_limit < initial_values.len() {
             initial_values.truncate_from_end(initial_limit)
@@ -188,10 +198,25 @@ where
             buffered_vector,
             limit: initial_limit,
             ready_values: Default::default(),
+            saturation: SharedObservable::new(Saturation::new(buffered_len, initial_limit)),
         };
 
         (initial_values, stream)
     }
+
+    /// Get the current [`Saturation`] of the view.
+    pub fn saturation(&self) -> Saturation {
+        self.saturation.get()
+    }
+
+    /// Get a [`Subscriber`] that yields a new [`Saturation`] every time the
+    /// view becomes (or stops being) full, or items beyond the limit appear
+    /// or disappear.
+    ///
+    /// The saturation is updated whenever this stream is polled.
+    pub fn subscribe_saturation(&self) -> Subscriber<Saturation> {
+        self.saturation.subscribe()
+    }
 }
 
 impl<S, L> Stream for Tail<S, L>
@@ -203,7 +228,10 @@ where
     type Item = S::Item;
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
-        self.project().poll_next(cx)
+        let mut this = self.project();
+        let result = this.poll_next(cx);
+        this.update_saturation();
+        result
     }
 }
 
@@ -220,12 +248,42 @@ where
     }
 }
 
+impl<S, L> AdapterDebug for Tail<S, L>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn adapter_state(&self) -> AdapterState {
+        AdapterState {
+            buffered_len: Some(self.buffered_vector.len()),
+            len: Some(min(self.buffered_vector.len(), self.limit)),
+            limit: Some(self.limit),
+            ready_values: S::Item::tail_buf_len(&self.ready_values),
+            ..AdapterState::new("Tail")
+        }
+    }
+}
+
+impl<S, L> fmt::Debug for Tail<S, L>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.adapter_state(), f)
+    }
+}
+
 impl<S, L> TailProj<'_, S, L>
 where
     S: Stream,
     S::Item: VectorDiffContainer,
     L: Stream<Item = usize>,
 {
+    fn update_saturation(&self) {
+        self.saturation.set_if_not_eq(Saturation::new(self.buffered_vector.len(), *self.limit));
+    }
+
     fn poll_next(&mut self, cx: &mut task::Context<'_>) -> Poll<Option<S::Item>> {
         loop {
             // First off, if any values are ready, return them.
@@ -481,11 +539,40 @@ fn handle_diff<T: Clone>(
             );
         }
 
-        VectorDiff::Reset { values: new_values } => {
+        VectorDiff::Move { from, to } => {
+            match (from >= index_of_limit, to >= index_of_limit) {
+                (true, true) => {
+                    res.push(VectorDiff::Move {
+                        from: from - index_of_limit,
+                        to: to - index_of_limit,
+                    });
+                }
+                (true, false) => {
+                    // The item leaves the view, the previous one enters it at the front.
+                    res.push(VectorDiff::Remove { index: from - index_of_limit });
+                    res.push(VectorDiff::PushFront {
+                        value: buffered_vector[index_of_limit].clone(),
+                    });
+                }
+                (false, true) => {
+                    // The item enters the view, the first one leaves it.
+                    res.push(VectorDiff::PopFront);
+                    res.push(VectorDiff::Insert {
+                        index: to - index_of_limit,
+                        value: buffered_vector[to].clone(),
+                    });
+                }
+                (false, false) => {
+                    // Move before `limit`, ignore the diff.
+                }
+            }
+        }
+
+        VectorDiff::Reset { values: new_values, reason } => {
             let new_values = new_values.truncate_from_end(limit);
 
             // There is space for these new items.
-            res.push(VectorDiff::Reset { values: new_values });
+            res.push(VectorDiff::Reset { values: new_values, reason });
         }
     }
 
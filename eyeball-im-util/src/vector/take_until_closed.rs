@@ -0,0 +1,126 @@
+use std::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    task::{self, Poll},
+};
+
+use eyeball_im::{Vector, VectorDiff};
+use futures_core::Stream;
+use pin_project_lite::pin_project;
+
+use super::{
+    AdapterDebug, AdapterState, VectorDiffContainer, VectorDiffContainerOps,
+    VectorDiffContainerStreamElement,
+};
+
+pin_project! {
+    /// A [`VectorDiff`] stream adapter that forwards all diffs unchanged until
+    /// a future completes, then emits a final [`VectorDiff::Clear`] and ends.
+    ///
+    /// This ties the lifetime of the view to something else, for example the
+    /// room a list of messages belongs to: once the room is gone, the view is
+    /// emptied and the stream ends, without having to `select!` over both and
+    /// inject the `Clear` manually. To wait for a stream to end, pass a future
+    /// that drains it.
+    ///
+    /// If the underlying stream ends first, this stream ends too, without a
+    /// `Clear`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use eyeball::SharedObservable;
+    /// use eyeball_im::{ObservableVector, VectorDiff};
+    /// use eyeball_im_util::vector::VectorObserverExt;
+    /// use imbl::vector;
+    /// use stream_assert::{assert_closed, assert_next_eq, assert_pending};
+    ///
+    /// let mut ob = ObservableVector::<u32>::from(vector![1, 2]);
+    /// let room = SharedObservable::new("Room name");
+    /// let mut room_sub = room.subscribe();
+    /// let (values, mut sub) = ob.subscribe().take_until_closed(Box::pin(async move {
+    ///     while room_sub.next().await.is_some() {}
+    /// }));
+    /// assert_eq!(values, vector![1, 2]);
+    ///
+    /// ob.push_back(3);
+    /// assert_next_eq!(sub, VectorDiff::PushBack { value: 3 });
+    /// room.set("New room name");
+    /// assert_pending!(sub);
+    ///
+    /// // The room is gone
+    /// drop(room);
+    /// assert_next_eq!(sub, VectorDiff::Clear);
+    /// assert_closed!(sub);
+    /// ```
+    pub struct TakeUntilClosed<S, F> {
+        // The main stream to poll items from.
+        #[pin]
+        inner_stream: S,
+
+        // The future that ends this stream once it completes.
+        #[pin]
+        close_future: F,
+
+        // Whether this stream has ended.
+        done: bool,
+    }
+}
+
+impl<S, F> TakeUntilClosed<S, F>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    F: Future,
+{
+    /// Create a new `TakeUntilClosed` with the given initial values, stream of
+    /// `VectorDiff` updates for those values, and future that ends the stream.
+    pub fn new(
+        initial_values: Vector<VectorDiffContainerStreamElement<S>>,
+        inner_stream: S,
+        close_future: F,
+    ) -> (Vector<VectorDiffContainerStreamElement<S>>, Self) {
+        (initial_values, Self { inner_stream, close_future, done: false })
+    }
+}
+
+impl<S, F> Stream for TakeUntilClosed<S, F>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    F: Future,
+{
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        if *this.done {
+            return Poll::Ready(None);
+        }
+
+        if this.close_future.poll(cx).is_ready() {
+            *this.done = true;
+            return Poll::Ready(Some(S::Item::from_item(VectorDiff::Clear)));
+        }
+
+        let poll = this.inner_stream.poll_next(cx);
+        if let Poll::Ready(None) = poll {
+            *this.done = true;
+        }
+
+        poll
+    }
+}
+
+impl<S, F> AdapterDebug for TakeUntilClosed<S, F> {
+    fn adapter_state(&self) -> AdapterState {
+        AdapterState::new("TakeUntilClosed")
+    }
+}
+
+impl<S, F> fmt::Debug for TakeUntilClosed<S, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.adapter_state(), f)
+    }
+}
@@ -0,0 +1,182 @@
+use std::{
+    fmt,
+    future::Future,
+    mem,
+    pin::Pin,
+    task::{self, Poll},
+    time::Duration,
+};
+
+use eyeball_im::{ResetReason, Vector, VectorDiff};
+use futures_core::Stream;
+use pin_project_lite::pin_project;
+use tokio::time::{sleep, Instant, Sleep};
+
+use super::{
+    AdapterDebug, AdapterState, VectorDiffContainer, VectorDiffContainerOps,
+    VectorDiffContainerStreamElement,
+};
+
+pin_project! {
+    /// A [`VectorDiff`] stream adapter that emits the diffs of its inner stream
+    /// as batches, at most once per interval.
+    ///
+    /// The first update is emitted right away. Updates that arrive within the
+    /// interval after an emission are buffered and emitted together once the
+    /// interval has passed. While buffering, diffs are merged where that is
+    /// possible without changing the result, e.g. a [`VectorDiff::Reset`]
+    /// discards all diffs before it and consecutive [`VectorDiff::PushBack`]s
+    /// are merged into one [`VectorDiff::Append`].
+    ///
+    /// Since this adapter uses tokio's timer, it must be polled from within a
+    /// tokio runtime that has the time driver enabled.
+    pub struct Throttle<S>
+    where
+        S: Stream,
+        S::Item: VectorDiffContainer,
+    {
+        #[pin]
+        inner: S,
+
+        // The interval between two emitted batches.
+        interval: Duration,
+
+        // The diffs that have not been emitted yet.
+        buffer: Vec<VectorDiff<VectorDiffContainerStreamElement<S>>>,
+
+        // The timer for the current interval, if a batch was emitted recently.
+        sleep: Option<Pin<Box<Sleep>>>,
+
+        // Whether `inner` has ended.
+        inner_done: bool,
+    }
+}
+
+impl<S> Throttle<S>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    /// Create a new `Throttle` with the given initial values, stream of
+    /// `VectorDiff` updates for those values, and interval.
+    pub fn new(
+        initial_values: Vector<VectorDiffContainerStreamElement<S>>,
+        inner: S,
+        interval: Duration,
+    ) -> (Vector<VectorDiffContainerStreamElement<S>>, Self) {
+        let stream = Self { inner, interval, buffer: Vec::new(), sleep: None, inner_done: false };
+        (initial_values, stream)
+    }
+}
+
+impl<S> Stream for Throttle<S>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    type Item = Vec<VectorDiff<VectorDiffContainerStreamElement<S>>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        while !*this.inner_done {
+            match this.inner.as_mut().poll_next(cx) {
+                Poll::Ready(Some(diffs)) => {
+                    for diff in diffs.into_vec() {
+                        push_merged(this.buffer, diff);
+                    }
+                }
+                Poll::Ready(None) => *this.inner_done = true,
+                Poll::Pending => break,
+            }
+        }
+
+        if let Some(sleep) = this.sleep {
+            if sleep.as_mut().poll(cx).is_ready() {
+                *this.sleep = None;
+            }
+        }
+
+        if this.buffer.is_empty() {
+            return if *this.inner_done { Poll::Ready(None) } else { Poll::Pending };
+        }
+
+        // Once the inner stream has ended, there is no point in waiting for
+        // more diffs to batch up.
+        if this.sleep.is_some() && !*this.inner_done {
+            return Poll::Pending;
+        }
+
+        let deadline = Instant::now() + *this.interval;
+        match this.sleep {
+            Some(sleep) => sleep.as_mut().reset(deadline),
+            None => *this.sleep = Some(Box::pin(sleep(*this.interval))),
+        }
+
+        Poll::Ready(Some(mem::take(this.buffer)))
+    }
+}
+
+impl<S> AdapterDebug for Throttle<S>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn adapter_state(&self) -> AdapterState {
+        AdapterState { ready_values: self.buffer.len(), ..AdapterState::new("Throttle") }
+    }
+}
+
+impl<S> fmt::Debug for Throttle<S>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.adapter_state(), f)
+    }
+}
+
+/// Add `diff` to the buffered diffs, merging it with the last one if possible.
+fn push_merged<T: Clone>(buffer: &mut Vec<VectorDiff<T>>, diff: VectorDiff<T>) {
+    match (buffer.last_mut(), diff) {
+        // Everything before these is irrelevant for the result.
+        (_, diff @ (VectorDiff::Clear | VectorDiff::Reset { .. })) => {
+            buffer.clear();
+            buffer.push(diff);
+        }
+        (Some(VectorDiff::Clear), VectorDiff::Append { values }) => {
+            *buffer.last_mut().unwrap() = VectorDiff::Reset { values, reason: ResetReason::Update };
+        }
+        (Some(VectorDiff::Clear), VectorDiff::PushBack { value }) => {
+            *buffer.last_mut().unwrap() =
+                VectorDiff::Reset { values: Vector::unit(value), reason: ResetReason::Update };
+        }
+        (
+            Some(VectorDiff::Append { values } | VectorDiff::Reset { values, .. }),
+            VectorDiff::Append { values: new_values },
+        ) => {
+            values.append(new_values);
+        }
+        (
+            Some(VectorDiff::Append { values } | VectorDiff::Reset { values, .. }),
+            VectorDiff::PushBack { value },
+        ) => {
+            values.push_back(value);
+        }
+        (Some(last @ VectorDiff::PushBack { .. }), VectorDiff::PushBack { value }) => {
+            let values = match mem::replace(last, VectorDiff::Clear) {
+                VectorDiff::PushBack { value: prev } => [prev, value].into_iter().collect(),
+                _ => unreachable!(),
+            };
+            *last = VectorDiff::Append { values };
+        }
+        (
+            Some(VectorDiff::Set { index, value }),
+            VectorDiff::Set { index: new_index, value: new_value },
+        ) if *index == new_index => {
+            *value = new_value;
+        }
+        (_, diff) => buffer.push(diff),
+    }
+}
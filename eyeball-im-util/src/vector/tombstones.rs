@@ -0,0 +1,400 @@
+use std::{
+    fmt,
+    pin::Pin,
+    task::{self, Poll},
+};
+
+use eyeball_im::{Vector, VectorDiff};
+use futures_core::Stream;
+use pin_project_lite::pin_project;
+
+use super::{
+    AdapterDebug, AdapterState, VectorDiffContainer, VectorDiffContainerOps,
+    VectorDiffContainerStreamElement, VectorDiffContainerStreamMappedItem,
+};
+
+/// An item of the view presented by [`Tombstones`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Tombstoned<T> {
+    /// An item of the observed vector.
+    Item(T),
+
+    /// An item that was removed from the observed vector, and is kept in the
+    /// view until its grace period is over.
+    Tombstone(T),
+}
+
+/// Type alias for the stream item type of [`Tombstones`].
+type TombstonedItem<S> =
+    VectorDiffContainerStreamMappedItem<S, Tombstoned<VectorDiffContainerStreamElement<S>>>;
+
+/// Type alias for the buffer of diffs that are ready to be emitted by
+/// [`Tombstones`].
+type TombstonedTailBuf<S> = <TombstonedItem<S> as VectorDiffContainerOps<
+    Tombstoned<VectorDiffContainerStreamElement<S>>,
+>>::TailBuf;
+
+pin_project! {
+    /// A [`VectorDiff`] stream adapter that keeps removed items in the view as
+    /// [`Tombstoned::Tombstone`]s for a grace period before removing them.
+    ///
+    /// The grace period is a number of ticks of a separate stream, for example
+    /// an interval timer. When an item is removed from the underlying
+    /// [`ObservableVector`], a `VectorDiff::Set` replaces it with a tombstone
+    /// in the view, and only once the tick stream has yielded `grace_ticks`
+    /// more items, a `VectorDiff::Remove` for the tombstone is emitted. This
+    /// allows showing something like a "Message deleted – Undo" placeholder
+    /// in place of the removed item.
+    ///
+    /// If an item that is equal to a tombstone is inserted back where the
+    /// tombstone is, the tombstone is turned back into a regular item instead
+    /// of a new item being added to the view, which makes undoing a removal
+    /// seamless. `VectorDiff::Clear` and `VectorDiff::Reset` remove all
+    /// tombstones right away, and if the tick stream ends, the remaining
+    /// tombstones are kept until then.
+    ///
+    /// Finding the position of an item in the view takes time linear in the
+    /// length of the view.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use eyeball::Observable;
+    /// use eyeball_im::{ObservableVector, VectorDiff};
+    /// use eyeball_im_util::vector::{Tombstoned, VectorObserverExt};
+    /// use imbl::vector;
+    /// use stream_assert::{assert_next_eq, assert_pending};
+    ///
+    /// let mut ob = ObservableVector::<&str>::from(vector!["hi", "there"]);
+    /// let mut tick = Observable::new(());
+    /// let (values, mut sub) = ob.subscribe().tombstones(2, Observable::subscribe(&tick));
+    /// assert_eq!(values, vector![Tombstoned::Item("hi"), Tombstoned::Item("there")]);
+    ///
+    /// ob.remove(0);
+    /// assert_next_eq!(sub, VectorDiff::Set { index: 0, value: Tombstoned::Tombstone("hi") });
+    ///
+    /// Observable::set(&mut tick, ());
+    /// assert_pending!(sub);
+    /// Observable::set(&mut tick, ());
+    /// assert_next_eq!(sub, VectorDiff::Remove { index: 0 });
+    ///
+    /// // Undoing a removal within the grace period
+    /// ob.remove(0);
+    /// assert_next_eq!(sub, VectorDiff::Set { index: 0, value: Tombstoned::Tombstone("there") });
+    /// ob.insert(0, "there");
+    /// assert_next_eq!(sub, VectorDiff::Set { index: 0, value: Tombstoned::Item("there") });
+    /// assert_pending!(sub);
+    /// ```
+    ///
+    /// [`ObservableVector`]: eyeball_im::ObservableVector
+    #[project = TombstonesProj]
+    pub struct Tombstones<S, G>
+    where
+        S: Stream,
+        S::Item: VectorDiffContainer,
+    {
+        // The main stream to poll items from.
+        #[pin]
+        inner_stream: S,
+
+        // The stream of ticks that make tombstones expire, `None` once it has
+        // ended.
+        #[pin]
+        tick_stream: Option<G>,
+
+        // The number of ticks tombstones are kept for.
+        grace_ticks: usize,
+
+        // The values of the view, both items and tombstones.
+        view: Vector<VectorDiffContainerStreamElement<S>>,
+
+        // For every value of `view`, `None` if it is an item, or the number of
+        // ticks left until it is removed if it is a tombstone.
+        ticks_left: Vec<Option<usize>>,
+
+        // Diffs that are ready to be emitted, used if the item type is just
+        // `VectorDiff<_>` (non-batched) since one update can result in
+        // multiple diffs.
+        ready_values: TombstonedTailBuf<S>,
+    }
+}
+
+impl<S, G> Tombstones<S, G>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    VectorDiffContainerStreamElement<S>: PartialEq,
+    G: Stream,
+{
+    /// Create a new `Tombstones` with the given initial values, stream of
+    /// `VectorDiff` updates for those values, grace period and tick stream.
+    ///
+    /// Returns the initial values, wrapped in [`Tombstoned::Item`], as well as
+    /// a stream of updates to them.
+    pub fn new(
+        initial_values: Vector<VectorDiffContainerStreamElement<S>>,
+        inner_stream: S,
+        grace_ticks: usize,
+        tick_stream: G,
+    ) -> (Vector<Tombstoned<VectorDiffContainerStreamElement<S>>>, Self) {
+        let values = initial_values.iter().cloned().map(Tombstoned::Item).collect();
+        let stream = Self {
+            inner_stream,
+            tick_stream: Some(tick_stream),
+            grace_ticks,
+            ticks_left: vec![None; initial_values.len()],
+            view: initial_values,
+            ready_values: Default::default(),
+        };
+
+        (values, stream)
+    }
+}
+
+impl<S, G> Stream for Tombstones<S, G>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    VectorDiffContainerStreamElement<S>: PartialEq,
+    G: Stream,
+{
+    type Item = TombstonedItem<S>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        self.project().poll_next(cx)
+    }
+}
+
+impl<S, G> AdapterDebug for Tombstones<S, G>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn adapter_state(&self) -> AdapterState {
+        AdapterState {
+            buffered_len: Some(self.ticks_left.iter().filter(|t| t.is_none()).count()),
+            len: Some(self.view.len()),
+            limit: Some(self.grace_ticks),
+            ready_values: TombstonedItem::<S>::tail_buf_len(&self.ready_values),
+            ..AdapterState::new("Tombstones")
+        }
+    }
+}
+
+impl<S, G> fmt::Debug for Tombstones<S, G>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.adapter_state(), f)
+    }
+}
+
+impl<S, G> TombstonesProj<'_, S, G>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    VectorDiffContainerStreamElement<S>: PartialEq,
+    G: Stream,
+{
+    fn poll_next(&mut self, cx: &mut task::Context<'_>) -> Poll<Option<TombstonedItem<S>>> {
+        loop {
+            // First off, if any values are ready, return them.
+            if let Some(value) = TombstonedItem::<S>::pop_from_tail_buf(self.ready_values) {
+                return Poll::Ready(Some(value));
+            }
+
+            let mut res = Vec::new();
+            match self.inner_stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(diffs)) => {
+                    for diff in diffs.into_vec() {
+                        self.handle_diff(diff, &mut res);
+                    }
+                }
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => {
+                    if !self.poll_tick(cx) {
+                        return Poll::Pending;
+                    }
+                    self.expire(&mut res);
+                }
+            }
+
+            if let Some(diffs) = TombstonedItem::<S>::extend_tail_buf(res, self.ready_values) {
+                return Poll::Ready(Some(diffs));
+            }
+
+            // Else loop and poll the streams again.
+        }
+    }
+
+    /// Poll the tick stream, returning whether it ticked.
+    fn poll_tick(&mut self, cx: &mut task::Context<'_>) -> bool {
+        let Some(tick_stream) = self.tick_stream.as_mut().as_pin_mut() else {
+            return false;
+        };
+
+        match tick_stream.poll_next(cx) {
+            Poll::Ready(Some(_)) => true,
+            Poll::Ready(None) => {
+                self.tick_stream.set(None);
+                false
+            }
+            Poll::Pending => false,
+        }
+    }
+
+    /// Count down the ticks of all tombstones, removing the expired ones.
+    fn expire(
+        &mut self,
+        res: &mut Vec<VectorDiff<Tombstoned<VectorDiffContainerStreamElement<S>>>>,
+    ) {
+        // Iterate backwards so the indices of the removals stay valid.
+        for index in (0..self.ticks_left.len()).rev() {
+            if let Some(ticks_left) = &mut self.ticks_left[index] {
+                *ticks_left = ticks_left.saturating_sub(1);
+                if *ticks_left == 0 {
+                    self.view.remove(index);
+                    self.ticks_left.remove(index);
+                    res.push(VectorDiff::Remove { index });
+                }
+            }
+        }
+    }
+
+    fn handle_diff(
+        &mut self,
+        diff: VectorDiff<VectorDiffContainerStreamElement<S>>,
+        res: &mut Vec<VectorDiff<Tombstoned<VectorDiffContainerStreamElement<S>>>>,
+    ) {
+        match diff {
+            VectorDiff::Append { values } => {
+                self.ticks_left.extend(values.iter().map(|_| None));
+                self.view.append(values.clone());
+                res.push(VectorDiff::Append {
+                    values: values.into_iter().map(Tombstoned::Item).collect(),
+                });
+            }
+            VectorDiff::Clear => {
+                self.view.clear();
+                self.ticks_left.clear();
+                res.push(VectorDiff::Clear);
+            }
+            VectorDiff::PushFront { value } => {
+                self.insert(0, value, res);
+            }
+            VectorDiff::PushBack { value } => {
+                let index = self.item_count();
+                self.insert(index, value, res);
+            }
+            VectorDiff::PopFront => {
+                self.remove(0, res);
+            }
+            VectorDiff::PopBack => {
+                let index = self.item_count() - 1;
+                self.remove(index, res);
+            }
+            VectorDiff::Insert { index, value } => {
+                self.insert(index, value, res);
+            }
+            VectorDiff::Set { index, value } => {
+                let row = self.row_index(index);
+                self.view.set(row, value.clone());
+                res.push(VectorDiff::Set { index: row, value: Tombstoned::Item(value) });
+            }
+            VectorDiff::Remove { index } => {
+                self.remove(index, res);
+            }
+            VectorDiff::Truncate { length } => {
+                for index in (length..self.item_count()).rev() {
+                    self.remove(index, res);
+                }
+            }
+            VectorDiff::Move { from, to } => {
+                let from_row = self.row_index(from);
+                let value = self.view.remove(from_row);
+                self.ticks_left.remove(from_row);
+                let to_row = self.row_index(to);
+                self.view.insert(to_row, value);
+                self.ticks_left.insert(to_row, None);
+                if from_row != to_row {
+                    res.push(VectorDiff::Move { from: from_row, to: to_row });
+                }
+            }
+            VectorDiff::Reset { values, reason } => {
+                *self.ticks_left = vec![None; values.len()];
+                *self.view = values.clone();
+                let values = values.into_iter().map(Tombstoned::Item).collect();
+                res.push(VectorDiff::Reset { values, reason });
+            }
+        }
+    }
+
+    /// Get the number of items in the view, i.e. the length of the observed
+    /// vector.
+    fn item_count(&self) -> usize {
+        self.ticks_left.iter().filter(|t| t.is_none()).count()
+    }
+
+    /// Get the index in the view of the item at `index` of the observed
+    /// vector, or the length of the view if `index` is the length of the
+    /// observed vector.
+    fn row_index(&self, index: usize) -> usize {
+        self.ticks_left
+            .iter()
+            .enumerate()
+            .filter(|(_, t)| t.is_none())
+            .nth(index)
+            .map_or(self.ticks_left.len(), |(row, _)| row)
+    }
+
+    /// Insert `value` at `index` of the observed vector.
+    fn insert(
+        &mut self,
+        index: usize,
+        value: VectorDiffContainerStreamElement<S>,
+        res: &mut Vec<VectorDiff<Tombstoned<VectorDiffContainerStreamElement<S>>>>,
+    ) {
+        let row = self.row_index(index);
+
+        // Revive a matching tombstone in between the previous item and the
+        // position of the insertion, if there is one.
+        let gap_start =
+            self.ticks_left[..row].iter().rposition(Option::is_none).map_or(0, |i| i + 1);
+        if let Some(tombstone) = (gap_start..row).find(|&i| self.view[i] == value) {
+            self.ticks_left[tombstone] = None;
+            res.push(VectorDiff::Set { index: tombstone, value: Tombstoned::Item(value) });
+            return;
+        }
+
+        self.view.insert(row, value.clone());
+        self.ticks_left.insert(row, None);
+        let value = Tombstoned::Item(value);
+        res.push(if row == 0 {
+            VectorDiff::PushFront { value }
+        } else if row == self.view.len() - 1 {
+            VectorDiff::PushBack { value }
+        } else {
+            VectorDiff::Insert { index: row, value }
+        });
+    }
+
+    /// Remove the item at `index` of the observed vector.
+    fn remove(
+        &mut self,
+        index: usize,
+        res: &mut Vec<VectorDiff<Tombstoned<VectorDiffContainerStreamElement<S>>>>,
+    ) {
+        let row = self.row_index(index);
+        if *self.grace_ticks == 0 {
+            self.view.remove(row);
+            self.ticks_left.remove(row);
+            res.push(VectorDiff::Remove { index: row });
+        } else {
+            self.ticks_left[row] = Some(*self.grace_ticks);
+            let value = Tombstoned::Tombstone(self.view[row].clone());
+            res.push(VectorDiff::Set { index: row, value });
+        }
+    }
+}
@@ -1,19 +1,34 @@
 //! Public traits.
 
-use std::cmp::Ordering;
+use std::{
+    cmp::Ordering,
+    future::Future,
+    hash::Hash,
+    ops::{Add, Sub},
+};
 
 use eyeball_im::{
-    VectorDiff, VectorSubscriber, VectorSubscriberBatchedStream, VectorSubscriberStream,
+    MapSubscriber, MapSubscriberStream, VectorDiff, VectorSubscriber,
+    VectorSubscriberBatchedStream, VectorSubscriberStream,
 };
 use futures_core::Stream;
 use imbl::Vector;
 
+#[cfg(feature = "futures-signals")]
+use super::VectorSignalVec;
 use super::{
     ops::{
         VecVectorDiffFamily, VectorDiffContainerFamily, VectorDiffContainerOps, VectorDiffFamily,
     },
-    EmptyLimitStream, Filter, FilterMap, Head, Sort, SortBy, SortByKey, Tail,
+    AnchorSpec, AnchoredWindow, AnyAll, Chain, ChunkTimeout, Dedup, DedupByKey, DynamicFilter,
+    DynamicSortBy, EmptyLimitStream, EmptyTransitions, ExtremumByKey, Filter, FilterIf, FilterMap,
+    FilterMapCached, FilterWithIndex, Fold, FoldItems, GroupBy, Grouped, Head, JoinMap, MapInto,
+    MapWithIndex, Mean, ObservedLen, PaddedHead, Record, Sort, SortBy, SortByKey, SortIf,
+    SortLimit, Sum, Tail, TakeUntilClosed, Tombstoned, Tombstones, TryMap, Unbatch, UniqueByKey,
+    Validate, WatchIndex, Window, WindowSpec,
 };
+#[cfg(feature = "tokio")]
+use super::{BatchTimeout, MaterializedVector, Throttle};
 
 /// Abstraction over stream items that the adapters in this module can deal
 /// with.
@@ -105,6 +120,43 @@ where
     T: Clone + 'static,
     <Self::Stream as Stream>::Item: VectorDiffContainer<Element = T>,
 {
+    /// Present the observed values followed by the values observed by
+    /// `other` as a single vector.
+    ///
+    /// See [`Chain`] for more details.
+    fn chain<O>(self, other: O) -> (Vector<T>, Chain<Self::Stream, O::Stream>)
+    where
+        O: VectorObserver<T>,
+        O::Stream: Stream<Item = <Self::Stream as Stream>::Item>,
+    {
+        let (items, stream) = self.into_parts();
+        let (other_items, other_stream) = other.into_parts();
+        Chain::new(items, stream, other_items, other_stream)
+    }
+
+    /// Collapse runs of consecutive equal values into one.
+    ///
+    /// See [`Dedup`] for more details.
+    fn dedup(self) -> (Vector<T>, Dedup<Self::Stream>)
+    where
+        T: PartialEq,
+    {
+        let (items, stream) = self.into_parts();
+        Dedup::new(items, stream)
+    }
+
+    /// Collapse runs of consecutive values with equal keys into one.
+    ///
+    /// See [`DedupByKey`] for more details.
+    fn dedup_by_key<F, K>(self, key_fn: F) -> (Vector<T>, DedupByKey<Self::Stream, F>)
+    where
+        F: Fn(&T) -> K,
+        K: PartialEq,
+    {
+        let (items, stream) = self.into_parts();
+        DedupByKey::new(items, stream, key_fn)
+    }
+
     /// Filter the vector's values with the given function.
     fn filter<F>(self, f: F) -> (Vector<T>, Filter<Self::Stream, F>)
     where
@@ -124,6 +176,276 @@ where
         FilterMap::new(items, stream, f)
     }
 
+    /// Filter and map the values of the vector, giving the filter+map function
+    /// mutable access to a state that is kept for each value.
+    ///
+    /// See [`FilterMapCached`] for more details.
+    fn filter_map_cached<U, F, C>(self, f: F) -> (Vector<U>, FilterMapCached<Self::Stream, F, C>)
+    where
+        U: Clone,
+        F: Fn(T, &mut C) -> Option<U>,
+        C: Default,
+    {
+        let (items, stream) = self.into_parts();
+        FilterMapCached::new(items, stream, f)
+    }
+
+    /// Filter the values of the vector along with their index, filtering them
+    /// again whenever their index changes.
+    ///
+    /// See [`FilterWithIndex`] for more details.
+    fn filter_with_index<F>(self, f: F) -> (Vector<T>, FilterWithIndex<Self::Stream, F>)
+    where
+        F: Fn(usize, &T) -> bool,
+    {
+        let (items, stream) = self.into_parts();
+        FilterWithIndex::new(items, stream, f)
+    }
+
+    /// Map the values of the vector along with their index, mapping them again
+    /// whenever their index changes.
+    ///
+    /// See [`MapWithIndex`] for more details.
+    fn map_with_index<U, F>(self, f: F) -> (Vector<U>, MapWithIndex<Self::Stream, F, U>)
+    where
+        U: Clone + 'static,
+        F: Fn(usize, T) -> U,
+    {
+        let (items, stream) = self.into_parts();
+        MapWithIndex::new(items, stream, f)
+    }
+
+    /// Convert the vector's values to another type using [`Into`].
+    ///
+    /// See [`MapInto`] for more details.
+    fn map_values_into<U>(self) -> (Vector<U>, MapInto<Self::Stream, U>)
+    where
+        T: Into<U>,
+        U: Clone,
+    {
+        let (items, stream) = self.into_parts();
+        MapInto::new(items, stream)
+    }
+
+    /// Convert the vector's values to another type using [`TryInto`], leaving
+    /// out the values that fail to convert.
+    ///
+    /// This is a [`FilterMap`] that discards the conversion errors.
+    #[allow(clippy::type_complexity)]
+    fn map_values_try_into<U>(self) -> (Vector<U>, FilterMap<Self::Stream, fn(T) -> Option<U>>)
+    where
+        T: TryInto<U>,
+        U: Clone,
+    {
+        let (items, stream) = self.into_parts();
+        FilterMap::new(items, stream, |value| value.try_into().ok())
+    }
+
+    /// Map the vector's values with the given fallible function, leaving out
+    /// the values that fail to map.
+    ///
+    /// The errors are discarded, use
+    /// [`try_map_with_error_handler`][Self::try_map_with_error_handler] to
+    /// handle them. See [`TryMap`] for more details.
+    #[allow(clippy::type_complexity)]
+    fn try_map<U, E, F>(self, f: F) -> (Vector<U>, TryMap<Self::Stream, F, fn(E)>)
+    where
+        U: Clone,
+        F: Fn(T) -> Result<U, E>,
+    {
+        self.try_map_with_error_handler(f, drop as fn(E))
+    }
+
+    /// Map the vector's values with the given fallible function, leaving out
+    /// the values that fail to map and passing their errors to `on_error`.
+    ///
+    /// See [`TryMap`] for more details.
+    fn try_map_with_error_handler<U, E, F, H>(
+        self,
+        f: F,
+        on_error: H,
+    ) -> (Vector<U>, TryMap<Self::Stream, F, H>)
+    where
+        U: Clone,
+        F: Fn(T) -> Result<U, E>,
+        H: Fn(E),
+    {
+        let (items, stream) = self.into_parts();
+        TryMap::new(items, stream, f, on_error)
+    }
+
+    /// Map the vector's values along with the entries of an [`ObservableMap`]
+    /// under their keys, mapping them again whenever those entries change.
+    ///
+    /// See [`JoinMap`] for more details.
+    ///
+    /// [`ObservableMap`]: eyeball_im::ObservableMap
+    #[allow(clippy::type_complexity)]
+    fn join_map<K, V, KF, F, U>(
+        self,
+        map: MapSubscriber<K, V>,
+        key_fn: KF,
+        f: F,
+    ) -> (Vector<U>, JoinMap<Self::Stream, MapSubscriberStream<K, V>, K, V, KF, F, U>)
+    where
+        K: Hash + Eq + Clone + 'static,
+        V: Clone + 'static,
+        KF: Fn(&T) -> K,
+        F: Fn(T, Option<&V>) -> U,
+        U: Clone + 'static,
+    {
+        let (items, stream) = self.into_parts();
+        let (map, map_stream) = map.into_values_and_stream();
+        JoinMap::new(items, stream, map, map_stream, key_fn, f)
+    }
+
+    /// Filter the vector's values with the given function, but only while the
+    /// latest value of `condition_stream` (or `initial_enabled`, before it
+    /// produced anything) is `true`.
+    ///
+    /// See [`FilterIf`] for more details.
+    fn filter_if<F, C>(
+        self,
+        f: F,
+        initial_enabled: bool,
+        condition_stream: C,
+    ) -> (Vector<T>, FilterIf<Self::Stream, F, C>)
+    where
+        F: Fn(&T) -> bool,
+        C: Stream<Item = bool>,
+    {
+        let (items, stream) = self.into_parts();
+        FilterIf::new(items, stream, f, initial_enabled, condition_stream)
+    }
+
+    /// Filter the vector's values with the latest filter from the given
+    /// stream.
+    ///
+    /// No values match until the stream yields a first filter.
+    ///
+    /// See [`DynamicFilter`] for more details.
+    fn dynamic_filter<F, P>(self, filter_stream: P) -> DynamicFilter<Self::Stream, F, P>
+    where
+        F: Fn(&T) -> bool,
+        P: Stream<Item = F>,
+    {
+        let (items, stream) = self.into_parts();
+        DynamicFilter::new(items, stream, filter_stream)
+    }
+
+    /// Filter the vector's values with `initial_filter` initially, and with
+    /// the latest filter from the given stream once it yields one.
+    ///
+    /// See [`DynamicFilter`] for more details.
+    fn dynamic_filter_with_initial_value<F, P>(
+        self,
+        initial_filter: F,
+        filter_stream: P,
+    ) -> (Vector<T>, DynamicFilter<Self::Stream, F, P>)
+    where
+        F: Fn(&T) -> bool,
+        P: Stream<Item = F>,
+    {
+        let (items, stream) = self.into_parts();
+        DynamicFilter::with_initial_filter(items, stream, initial_filter, filter_stream)
+    }
+
+    /// Present the observed values in groups of consecutive values with equal
+    /// keys, each preceded by a header with the key.
+    ///
+    /// See [`GroupBy`] for more details.
+    #[allow(clippy::type_complexity)]
+    fn group_by<F, K>(self, key_fn: F) -> (Vector<Grouped<K, T>>, GroupBy<Self::Stream, F, K>)
+    where
+        F: Fn(&T) -> K,
+        K: Clone + PartialEq,
+    {
+        let (items, stream) = self.into_parts();
+        GroupBy::new(items, stream, key_fn)
+    }
+
+    /// Only keep the first value for each key, across all observed values.
+    ///
+    /// See [`UniqueByKey`] for more details.
+    #[allow(clippy::type_complexity)]
+    fn unique_by_key<F, K>(self, key_fn: F) -> (Vector<T>, UniqueByKey<Self::Stream, F, K>)
+    where
+        F: Fn(&T) -> K,
+        K: PartialEq,
+    {
+        let (items, stream) = self.into_parts();
+        UniqueByKey::new(items, stream, key_fn)
+    }
+
+    /// Re-batch the observed updates into batches of at most `max_len` diffs,
+    /// which are emitted once they are full or on a tick of `tick_stream` if
+    /// no new diffs arrived since the previous tick.
+    ///
+    /// See [`ChunkTimeout`] for more details.
+    fn chunk_timeout<G>(
+        self,
+        max_len: usize,
+        tick_stream: G,
+    ) -> (Vector<T>, ChunkTimeout<Self::Stream, G>)
+    where
+        G: Stream,
+    {
+        let (items, stream) = self.into_parts();
+        ChunkTimeout::new(items, stream, max_len, tick_stream)
+    }
+
+    /// Keep removed values in the view as tombstones until `tick_stream` has
+    /// ticked `grace_ticks` times.
+    ///
+    /// See [`Tombstones`] for more details.
+    fn tombstones<G>(
+        self,
+        grace_ticks: usize,
+        tick_stream: G,
+    ) -> (Vector<Tombstoned<T>>, Tombstones<Self::Stream, G>)
+    where
+        T: PartialEq,
+        G: Stream,
+    {
+        let (items, stream) = self.into_parts();
+        Tombstones::new(items, stream, grace_ticks, tick_stream)
+    }
+
+    /// Flatten the batches of the underlying stream into single diffs.
+    ///
+    /// See [`Unbatch`] for more details.
+    fn unbatched(self) -> (Vector<T>, Unbatch<Self::Stream>) {
+        let (items, stream) = self.into_parts();
+        Unbatch::new(items, stream)
+    }
+
+    /// Record the observed updates in a [`DiffLog`][super::DiffLog], e.g. to
+    /// replay them later.
+    ///
+    /// See [`Record`] for more details.
+    fn record(self) -> (Vector<T>, Record<Self::Stream>) {
+        let (items, stream) = self.into_parts();
+        Record::new(items, stream, false)
+    }
+
+    /// Record the observed updates in a [`DiffLog`][super::DiffLog] along with
+    /// the time that has passed since the recording started.
+    ///
+    /// See [`Record`] for more details.
+    fn record_with_timestamps(self) -> (Vector<T>, Record<Self::Stream>) {
+        let (items, stream) = self.into_parts();
+        Record::new(items, stream, true)
+    }
+
+    /// Turn malformed diffs from the underlying stream into resets, instead of
+    /// letting the adapters after this one panic on them.
+    ///
+    /// See [`Validate`] for more details.
+    fn validate(self) -> (Vector<T>, Validate<Self::Stream>) {
+        let (items, stream) = self.into_parts();
+        Validate::new(items, stream)
+    }
+
     /// Limit the observed values to the first `limit` values.
     ///
     /// See [`Head`] for more details.
@@ -132,6 +454,23 @@ where
         Head::new(items, stream, limit)
     }
 
+    /// Limit the observed values to the first `limit` values, padding them
+    /// with values created by `placeholder` so there are always exactly
+    /// `limit` of them.
+    ///
+    /// See [`PaddedHead`] for more details.
+    fn padded_head<F>(
+        self,
+        limit: usize,
+        placeholder: F,
+    ) -> (Vector<T>, PaddedHead<Self::Stream, F>)
+    where
+        F: Fn() -> T,
+    {
+        let (items, stream) = self.into_parts();
+        PaddedHead::new(items, stream, limit, placeholder)
+    }
+
     /// Limit the first observed values to a number of values determined by the
     /// given stream.
     ///
@@ -160,6 +499,66 @@ where
         Head::dynamic_with_initial_limit(items, stream, initial_limit, limit_stream)
     }
 
+    /// Present a window of the observed values, determined by the given
+    /// stream of window specs.
+    ///
+    /// See [`Window`] for more details.
+    fn dynamic_window<W>(self, spec_stream: W) -> Window<Self::Stream, W>
+    where
+        W: Stream<Item = WindowSpec>,
+    {
+        let (items, stream) = self.into_parts();
+        Window::dynamic(items, stream, spec_stream)
+    }
+
+    /// Present the window `initial_spec` of the observed values initially,
+    /// and update the window with the value from the given stream.
+    ///
+    /// See [`Window`] for more details.
+    fn dynamic_window_with_initial_value<W>(
+        self,
+        initial_spec: WindowSpec,
+        spec_stream: W,
+    ) -> (Vector<T>, Window<Self::Stream, W>)
+    where
+        W: Stream<Item = WindowSpec>,
+    {
+        let (items, stream) = self.into_parts();
+        Window::dynamic_with_initial_spec(items, stream, initial_spec, spec_stream)
+    }
+
+    /// Present a window of the observed values around the anchor item with
+    /// the key given by `initial_anchor`, and update the anchor with the
+    /// values from the given stream.
+    ///
+    /// See [`AnchoredWindow`] for more details.
+    fn anchored_window<K, F, A>(
+        self,
+        key_fn: F,
+        initial_anchor: AnchorSpec<K>,
+        anchor_stream: A,
+    ) -> (Vector<T>, AnchoredWindow<Self::Stream, K, F, A>)
+    where
+        K: PartialEq,
+        F: Fn(&T) -> K,
+        A: Stream<Item = AnchorSpec<K>>,
+    {
+        let (items, stream) = self.into_parts();
+        AnchoredWindow::new(items, stream, key_fn, initial_anchor, anchor_stream)
+    }
+
+    /// Watch the element at the index given by the latest value of the given
+    /// stream.
+    ///
+    /// See [`WatchIndex`] for more details.
+    fn watch_index_stream<I>(self, index_stream: I) -> WatchIndex<Self::Stream, I>
+    where
+        I: Stream<Item = usize>,
+    {
+        let (items, stream) = self.into_parts();
+        WatchIndex::new(items, stream, index_stream)
+    }
+
     /// Limit the observed values to the last `limit` values.
     ///
     /// See [`Tail`] for more details.
@@ -196,6 +595,18 @@ where
         Tail::dynamic_with_initial_limit(items, stream, initial_limit, limit_stream)
     }
 
+    /// Forward all updates until `close_future` completes, then clear the
+    /// observed values and end the stream.
+    ///
+    /// See [`TakeUntilClosed`] for more details.
+    fn take_until_closed<F>(self, close_future: F) -> (Vector<T>, TakeUntilClosed<Self::Stream, F>)
+    where
+        F: Future,
+    {
+        let (items, stream) = self.into_parts();
+        TakeUntilClosed::new(items, stream, close_future)
+    }
+
     /// Sort the observed values.
     ///
     /// See [`Sort`] for more details.
@@ -218,6 +629,23 @@ where
         SortBy::new(items, stream, compare)
     }
 
+    /// Sort the observed values with `initial_compare` initially, and with the
+    /// latest comparison function from the given stream once it yields one.
+    ///
+    /// See [`DynamicSortBy`] for more details.
+    fn dynamic_sort_by<F, P>(
+        self,
+        initial_compare: F,
+        compare_stream: P,
+    ) -> (Vector<T>, DynamicSortBy<Self::Stream, F, P>)
+    where
+        F: Fn(&T, &T) -> Ordering,
+        P: Stream<Item = F>,
+    {
+        let (items, stream) = self.into_parts();
+        DynamicSortBy::new(items, stream, initial_compare, compare_stream)
+    }
+
     /// Sort the observed values with the given key function.
     ///
     /// See [`SortBy`] for more details.
@@ -229,6 +657,254 @@ where
         let (items, stream) = self.into_parts();
         SortByKey::new(items, stream, key_fn)
     }
+
+    /// Sort the observed values and limit them to the first `limit` values,
+    /// only keeping those sorted.
+    ///
+    /// See [`SortLimit`] for more details.
+    fn sort_limit(self, limit: usize) -> (Vector<T>, SortLimit<Self::Stream, EmptyLimitStream>)
+    where
+        T: Ord,
+    {
+        let (items, stream) = self.into_parts();
+        SortLimit::new(items, stream, limit)
+    }
+
+    /// Sort the observed values and limit them to a number of values
+    /// determined by the given stream, only keeping those sorted.
+    ///
+    /// See [`SortLimit`] for more details.
+    fn dynamic_sort_limit<L>(self, limit_stream: L) -> SortLimit<Self::Stream, L>
+    where
+        T: Ord,
+        L: Stream<Item = usize>,
+    {
+        let (items, stream) = self.into_parts();
+        SortLimit::dynamic(items, stream, limit_stream)
+    }
+
+    /// Sort the observed values and limit them to `initial_limit` values
+    /// initially, and update the limit with the value from the given stream.
+    ///
+    /// See [`SortLimit`] for more details.
+    fn dynamic_sort_limit_with_initial_value<L>(
+        self,
+        initial_limit: usize,
+        limit_stream: L,
+    ) -> (Vector<T>, SortLimit<Self::Stream, L>)
+    where
+        T: Ord,
+        L: Stream<Item = usize>,
+    {
+        let (items, stream) = self.into_parts();
+        SortLimit::dynamic_with_initial_limit(items, stream, initial_limit, limit_stream)
+    }
+
+    /// Sort the observed values, but only while the latest value of
+    /// `condition_stream` (or `initial_enabled`, before it produced anything)
+    /// is `true`.
+    ///
+    /// See [`SortIf`] for more details.
+    #[allow(clippy::type_complexity)]
+    fn sort_if<C>(
+        self,
+        initial_enabled: bool,
+        condition_stream: C,
+    ) -> (Vector<T>, SortIf<Self::Stream, fn(&T, &T) -> Ordering, C>)
+    where
+        T: Ord,
+        C: Stream<Item = bool>,
+    {
+        let (items, stream) = self.into_parts();
+        SortIf::new(items, stream, T::cmp, initial_enabled, condition_stream)
+    }
+
+    /// Sort the observed values with the given comparison function, but only
+    /// while the latest value of `condition_stream` (or `initial_enabled`,
+    /// before it produced anything) is `true`.
+    ///
+    /// See [`SortIf`] for more details.
+    fn sort_by_if<F, C>(
+        self,
+        compare: F,
+        initial_enabled: bool,
+        condition_stream: C,
+    ) -> (Vector<T>, SortIf<Self::Stream, F, C>)
+    where
+        F: Fn(&T, &T) -> Ordering,
+        C: Stream<Item = bool>,
+    {
+        let (items, stream) = self.into_parts();
+        SortIf::new(items, stream, compare, initial_enabled, condition_stream)
+    }
+
+    /// Keep track of whether the observed values are empty, to get notified
+    /// when they transition between empty and non-empty.
+    ///
+    /// See [`EmptyTransitions`] for more details.
+    fn empty_transitions(self) -> (Vector<T>, EmptyTransitions<Self::Stream>) {
+        let (items, stream) = self.into_parts();
+        EmptyTransitions::new(items, stream)
+    }
+
+    /// Get a stream of the length of the observed values, which yields a new
+    /// length whenever it changes.
+    ///
+    /// See [`ObservedLen`] for more details.
+    fn len_observable(self) -> ObservedLen<Self::Stream> {
+        let (items, stream) = self.into_parts();
+        ObservedLen::new(items.len(), stream)
+    }
+
+    /// Fold the observed updates into an accumulator that can be observed
+    /// while the returned future runs.
+    ///
+    /// See [`Fold`] for more details.
+    fn fold<A, F>(self, init: A, f: F) -> Fold<Self::Stream, A, F>
+    where
+        A: Clone,
+        F: FnMut(A, &VectorDiff<T>) -> A,
+    {
+        let (items, stream) = self.into_parts();
+        Fold::new(items, stream, init, f)
+    }
+
+    /// Fold the observed values into an accumulator that can be observed
+    /// while the returned future runs, calling `add` for every added value and
+    /// `remove` for every removed value.
+    ///
+    /// See [`FoldItems`] for more details.
+    fn fold_items<A, F, G>(self, init: A, add: F, remove: G) -> FoldItems<Self::Stream, A, F, G>
+    where
+        A: Clone,
+        F: FnMut(A, &T) -> A,
+        G: FnMut(A, &T) -> A,
+    {
+        let (items, stream) = self.into_parts();
+        FoldItems::new(items, stream, init, add, remove)
+    }
+
+    /// Keep track of the sum of `f` applied to all observed values.
+    ///
+    /// See [`Sum`] for more details.
+    fn sum<F, N>(self, f: F) -> Sum<Self::Stream, F, N>
+    where
+        F: Fn(&T) -> N,
+        N: Add<Output = N> + Sub<Output = N> + Default + Clone + PartialEq,
+    {
+        let (items, stream) = self.into_parts();
+        Sum::new(items, stream, f)
+    }
+
+    /// Keep track of the arithmetic mean of `f` applied to all observed
+    /// values.
+    ///
+    /// See [`Mean`] for more details.
+    fn mean<F>(self, f: F) -> Mean<Self::Stream, F>
+    where
+        F: Fn(&T) -> f64,
+    {
+        let (items, stream) = self.into_parts();
+        Mean::new(items, stream, f)
+    }
+
+    /// Keep track of whether any of the observed values match `pred`.
+    ///
+    /// See [`AnyAll`] for more details.
+    fn any<F>(self, pred: F) -> AnyAll<Self::Stream, F>
+    where
+        F: Fn(&T) -> bool,
+    {
+        let (items, stream) = self.into_parts();
+        AnyAll::any(items, stream, pred)
+    }
+
+    /// Keep track of whether all of the observed values match `pred`.
+    ///
+    /// See [`AnyAll`] for more details.
+    fn all<F>(self, pred: F) -> AnyAll<Self::Stream, F>
+    where
+        F: Fn(&T) -> bool,
+    {
+        let (items, stream) = self.into_parts();
+        AnyAll::all(items, stream, pred)
+    }
+
+    /// Keep track of the first observed value with the smallest key.
+    ///
+    /// See [`ExtremumByKey`] for more details.
+    fn min_by_key<F, K>(self, key_fn: F) -> ExtremumByKey<Self::Stream, F>
+    where
+        F: Fn(&T) -> K,
+        K: Ord,
+    {
+        let (items, stream) = self.into_parts();
+        ExtremumByKey::min(items, stream, key_fn)
+    }
+
+    /// Keep track of the first observed value with the largest key.
+    ///
+    /// See [`ExtremumByKey`] for more details.
+    fn max_by_key<F, K>(self, key_fn: F) -> ExtremumByKey<Self::Stream, F>
+    where
+        F: Fn(&T) -> K,
+        K: Ord,
+    {
+        let (items, stream) = self.into_parts();
+        ExtremumByKey::max(items, stream, key_fn)
+    }
+
+    /// Turn this observer into a [`SignalVec`], for use with libraries built
+    /// on `futures-signals`.
+    ///
+    /// See [`VectorSignalVec`] for more details.
+    ///
+    /// [`SignalVec`]: futures_signals::signal_vec::SignalVec
+    #[cfg(feature = "futures-signals")]
+    fn into_signal_vec(self) -> VectorSignalVec<Self::Stream> {
+        let (items, stream) = self.into_parts();
+        VectorSignalVec::new(items, stream)
+    }
+
+    /// Spawn a task on the given runtime that drives this adapter chain and
+    /// materializes its output into an [`ObservableVector`].
+    ///
+    /// See [`MaterializedVector`] for more details.
+    ///
+    /// [`ObservableVector`]: eyeball_im::ObservableVector
+    #[cfg(feature = "tokio")]
+    fn spawn_materialized(self, handle: &tokio::runtime::Handle) -> MaterializedVector<T>
+    where
+        T: Send + Sync,
+        Self::Stream: Send + 'static,
+    {
+        let (items, stream) = self.into_parts();
+        MaterializedVector::spawn(items, stream, handle)
+    }
+
+    /// Collect the observed updates into batches of at most `max_len` diffs,
+    /// which are emitted once they are full or `timeout` after their first
+    /// diff arrived.
+    ///
+    /// See [`BatchTimeout`] for more details.
+    #[cfg(feature = "tokio")]
+    fn batch_timeout(
+        self,
+        max_len: usize,
+        timeout: std::time::Duration,
+    ) -> (Vector<T>, BatchTimeout<Self::Stream>) {
+        let (items, stream) = self.into_parts();
+        BatchTimeout::new(items, stream, max_len, timeout)
+    }
+
+    /// Emit the observed updates as batches, at most once per `interval`.
+    ///
+    /// See [`Throttle`] for more details.
+    #[cfg(feature = "tokio")]
+    fn throttle(self, interval: std::time::Duration) -> (Vector<T>, Throttle<Self::Stream>) {
+        let (items, stream) = self.into_parts();
+        Throttle::new(items, stream, interval)
+    }
 }
 
 impl<T, O> VectorObserverExt<T> for O
@@ -0,0 +1,171 @@
+use std::{
+    fmt,
+    pin::Pin,
+    task::{self, ready, Poll},
+};
+
+use eyeball_im::{Vector, VectorDiff};
+use futures_core::Stream;
+use pin_project_lite::pin_project;
+
+use super::{
+    filter::FilterIndices, AdapterDebug, AdapterState, VectorDiffContainer, VectorDiffContainerOps,
+    VectorDiffContainerStreamElement, VectorDiffContainerStreamMappedItem,
+};
+
+pin_project! {
+    /// A [`VectorDiff`] stream adapter that presents a view of the underlying
+    /// [`ObservableVector`]s items mapped with a fallible function.
+    ///
+    /// Items that fail to map are left out of the view, like with
+    /// [`FilterMap`](super::FilterMap), and the error is passed to an error
+    /// handler. Created through [`VectorObserverExt::try_map`], which discards
+    /// the errors, or [`VectorObserverExt::try_map_with_error_handler`], which
+    /// allows forwarding them to a channel, logging them, etc.
+    ///
+    /// If an item that failed to map is replaced through a `VectorDiff::Set`
+    /// with one that maps successfully, it enters the view, and the other way
+    /// around.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// use eyeball_im::{ObservableVector, VectorDiff};
+    /// use eyeball_im_util::vector::VectorObserverExt;
+    /// use imbl::vector;
+    /// use stream_assert::{assert_next_eq, assert_pending};
+    ///
+    /// let errors = Arc::new(Mutex::new(Vec::new()));
+    /// let on_error = {
+    ///     let errors = errors.clone();
+    ///     move |e| errors.lock().unwrap().push(e)
+    /// };
+    ///
+    /// let mut ob = ObservableVector::from(vector!["1", "x", "3"]);
+    /// let (values, mut sub) =
+    ///     ob.subscribe().try_map_with_error_handler(|s| s.parse::<u32>(), on_error);
+    /// assert_eq!(values, vector![1, 3]);
+    /// assert_eq!(errors.lock().unwrap().len(), 1);
+    ///
+    /// // The item that failed to map enters the view once it maps successfully.
+    /// ob.set(1, "2");
+    /// assert_next_eq!(sub, VectorDiff::Insert { index: 1, value: 2 });
+    ///
+    /// ob.push_back("y");
+    /// assert_pending!(sub);
+    /// assert_eq!(errors.lock().unwrap().len(), 2);
+    /// ```
+    ///
+    /// [`ObservableVector`]: eyeball_im::ObservableVector
+    /// [`VectorObserverExt::try_map`]: super::VectorObserverExt::try_map
+    /// [`VectorObserverExt::try_map_with_error_handler`]: super::VectorObserverExt::try_map_with_error_handler
+    pub struct TryMap<S, F, H> {
+        // The main stream to poll items from.
+        #[pin]
+        inner: S,
+
+        // The fallible mapping function.
+        f: F,
+
+        // The function that is called with the errors of `f`.
+        on_error: H,
+
+        // Which of the items were mapped successfully.
+        indices: FilterIndices,
+    }
+}
+
+impl<S, U, E, F, H> TryMap<S, F, H>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    U: Clone,
+    F: Fn(VectorDiffContainerStreamElement<S>) -> Result<U, E>,
+    H: Fn(E),
+{
+    /// Create a new `TryMap` with the given (unmapped) initial values, stream
+    /// of `VectorDiff` updates for those values, mapping function and error
+    /// handler.
+    pub fn new(
+        values: Vector<VectorDiffContainerStreamElement<S>>,
+        inner: S,
+        f: F,
+        on_error: H,
+    ) -> (Vector<U>, Self) {
+        let mut indices = FilterIndices::default();
+        let values = indices.append_filter_map(values, &|value| ok_or_handle(&f, &on_error, value));
+
+        (values.unwrap_or_default(), Self { inner, f, on_error, indices })
+    }
+}
+
+impl<S, U, E, F, H> Stream for TryMap<S, F, H>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    U: Clone,
+    F: Fn(VectorDiffContainerStreamElement<S>) -> Result<U, E>,
+    H: Fn(E),
+{
+    type Item = VectorDiffContainerStreamMappedItem<S, U>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        let (f, on_error, indices) = (&*this.f, &*this.on_error, this.indices);
+        let f = &|value| ok_or_handle(f, on_error, value);
+
+        loop {
+            let Some(diffs) = ready!(this.inner.as_mut().poll_next(cx)) else {
+                return Poll::Ready(None);
+            };
+
+            let result = diffs.filter_map(|diff| match diff {
+                VectorDiff::Append { values } => indices.handle_append_filter_map(values, f),
+                VectorDiff::Clear => indices.handle_clear(),
+                VectorDiff::PushFront { value } => indices.handle_push_front(value, f),
+                VectorDiff::PushBack { value } => indices.handle_push_back(value, f),
+                VectorDiff::PopFront => indices.handle_pop_front(),
+                VectorDiff::PopBack => indices.handle_pop_back(),
+                VectorDiff::Insert { index, value } => indices.handle_insert(index, value, f),
+                VectorDiff::Set { index, value } => indices.handle_set(index, value, f),
+                VectorDiff::Remove { index } => indices.handle_remove(index),
+                VectorDiff::Truncate { length } => indices.handle_truncate(length),
+                VectorDiff::Move { from, to } => indices.handle_move(from, to),
+                VectorDiff::Reset { values, reason } => {
+                    indices.handle_reset_filter_map(values, reason, f)
+                }
+            });
+
+            if let Some(diffs) = result {
+                return Poll::Ready(Some(diffs));
+            }
+        }
+    }
+}
+
+impl<S, F, H> AdapterDebug for TryMap<S, F, H> {
+    fn adapter_state(&self) -> AdapterState {
+        AdapterState {
+            buffered_len: Some(self.indices.original_len()),
+            len: Some(self.indices.filtered_indices().len()),
+            ..AdapterState::new("TryMap")
+        }
+    }
+}
+
+impl<S, F, H> fmt::Debug for TryMap<S, F, H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.adapter_state(), f)
+    }
+}
+
+/// Map `value` with `f`, passing the error to `on_error` if it fails.
+fn ok_or_handle<T, U, E>(
+    f: impl Fn(T) -> Result<U, E>,
+    on_error: impl Fn(E),
+    value: T,
+) -> Option<U> {
+    f(value).map_err(on_error).ok()
+}
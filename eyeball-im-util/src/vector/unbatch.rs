@@ -0,0 +1,122 @@
+use std::{
+    fmt,
+    pin::Pin,
+    task::{self, ready, Poll},
+};
+
+use eyeball_im::{Vector, VectorDiff};
+use futures_core::Stream;
+use pin_project_lite::pin_project;
+
+use super::{
+    AdapterDebug, AdapterState, VectorDiffContainer, VectorDiffContainerOps,
+    VectorDiffContainerStreamElement,
+};
+
+pin_project! {
+    /// A [`VectorDiff`] stream adapter that flattens the batches of its inner
+    /// stream, yielding their diffs one by one in the same order.
+    ///
+    /// This is the inverse of [`VectorSubscriberExt::batched`], for adapter
+    /// chains that start out with a batched stream but end in a consumer that
+    /// expects single diffs. Empty batches are skipped. If the inner stream
+    /// already yields single diffs, they are passed through as-is.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use eyeball_im::{ObservableVector, VectorDiff};
+    /// use eyeball_im_util::vector::{VectorObserverExt, VectorSubscriberExt};
+    /// use imbl::vector;
+    /// use stream_assert::{assert_next_eq, assert_pending};
+    ///
+    /// let mut ob = ObservableVector::<u32>::new();
+    /// let filtered = ob.subscribe().batched().filter(|v| v % 2 == 0);
+    /// let (values, mut sub) = filtered.unbatched();
+    /// assert_eq!(values, vector![]);
+    ///
+    /// let mut txn = ob.transaction();
+    /// txn.push_back(1);
+    /// txn.push_back(2);
+    /// txn.push_back(4);
+    /// txn.commit();
+    /// assert_next_eq!(sub, VectorDiff::PushBack { value: 2 });
+    /// assert_next_eq!(sub, VectorDiff::PushBack { value: 4 });
+    /// assert_pending!(sub);
+    /// ```
+    ///
+    /// [`VectorSubscriberExt::batched`]: super::VectorSubscriberExt::batched
+    pub struct Unbatch<S>
+    where
+        S: Stream,
+        S::Item: VectorDiffContainer,
+    {
+        // The main stream to poll items from.
+        #[pin]
+        inner_stream: S,
+
+        // The remaining diffs of the last batch, in reverse order to pop from
+        // the end.
+        ready_values: Vec<VectorDiff<VectorDiffContainerStreamElement<S>>>,
+    }
+}
+
+impl<S> Unbatch<S>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    /// Create a new `Unbatch` with the given initial values and stream of
+    /// `VectorDiff` updates for those values.
+    pub fn new(
+        initial_values: Vector<VectorDiffContainerStreamElement<S>>,
+        inner_stream: S,
+    ) -> (Vector<VectorDiffContainerStreamElement<S>>, Self) {
+        (initial_values, Self { inner_stream, ready_values: Vec::new() })
+    }
+}
+
+impl<S> Stream for Unbatch<S>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    type Item = VectorDiff<VectorDiffContainerStreamElement<S>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        loop {
+            // First off, if any values are ready, return them.
+            if let Some(diff) = this.ready_values.pop() {
+                return Poll::Ready(Some(diff));
+            }
+
+            let Some(diffs) = ready!(this.inner_stream.as_mut().poll_next(cx)) else {
+                return Poll::Ready(None);
+            };
+
+            this.ready_values.extend(diffs.into_vec().into_iter().rev());
+        }
+    }
+}
+
+impl<S> AdapterDebug for Unbatch<S>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn adapter_state(&self) -> AdapterState {
+        AdapterState { ready_values: self.ready_values.len(), ..AdapterState::new("Unbatch") }
+    }
+}
+
+impl<S> fmt::Debug for Unbatch<S>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.adapter_state(), f)
+    }
+}
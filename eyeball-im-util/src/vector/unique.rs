@@ -0,0 +1,380 @@
+use std::{
+    fmt,
+    pin::Pin,
+    task::{self, ready, Poll},
+};
+
+use eyeball_im::{Vector, VectorDiff};
+use futures_core::Stream;
+use pin_project_lite::pin_project;
+
+use super::{
+    AdapterDebug, AdapterState, VectorDiffContainer, VectorDiffContainerOps,
+    VectorDiffContainerStreamElement, VectorDiffContainerStreamTailBuf,
+};
+
+pin_project! {
+    /// A [`VectorDiff`] stream adapter that presents a view of the underlying
+    /// [`ObservableVector`]'s items where only the first item for each key is
+    /// kept.
+    ///
+    /// Unlike [`DedupByKey`], this considers all items of the vector, not just
+    /// consecutive ones. When an item is added before the retained occurrence
+    /// of its key, it takes that occurrence's place in the view. When the
+    /// retained occurrence is removed, the next item with the same key, if
+    /// any, is shown in its place.
+    ///
+    /// Finding the position of an item in the view takes time linear in the
+    /// length of the vector.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use eyeball_im::{ObservableVector, VectorDiff};
+    /// use eyeball_im_util::vector::VectorObserverExt;
+    /// use imbl::vector;
+    /// use stream_assert::{assert_next_eq, assert_pending};
+    ///
+    /// let mut ob = ObservableVector::<(u32, &str)>::from(vector![(1, "a"), (2, "b")]);
+    /// let (values, mut sub) = ob.subscribe().unique_by_key(|(id, _)| *id);
+    /// assert_eq!(values, vector![(1, "a"), (2, "b")]);
+    ///
+    /// // A later duplicate is not shown.
+    /// ob.push_back((1, "c"));
+    /// assert_pending!(sub);
+    ///
+    /// // Until the first occurrence is removed.
+    /// ob.remove(0);
+    /// assert_next_eq!(sub, VectorDiff::Remove { index: 0 });
+    /// assert_next_eq!(sub, VectorDiff::Insert { index: 1, value: (1, "c") });
+    /// ```
+    ///
+    /// [`ObservableVector`]: eyeball_im::ObservableVector
+    /// [`DedupByKey`]: super::DedupByKey
+    #[project = UniqueByKeyProj]
+    pub struct UniqueByKey<S, F, K>
+    where
+        S: Stream,
+        S::Item: VectorDiffContainer,
+    {
+        // The main stream to poll items from.
+        #[pin]
+        inner_stream: S,
+
+        // The function that computes the key of an item.
+        key_fn: F,
+
+        // The buffered vector that is updated with the main stream's items.
+        buffered_vector: Vector<VectorDiffContainerStreamElement<S>>,
+
+        // The keys of the items in `buffered_vector`.
+        keys: Vec<K>,
+
+        // Whether the items in `buffered_vector` are shown, i.e. whether they
+        // are the first occurrence of their key.
+        shown: Vec<bool>,
+
+        // Diffs that are ready to be emitted, used if the item type is just
+        // `VectorDiff<_>` (non-batched) since one update can result in
+        // multiple diffs.
+        ready_values: VectorDiffContainerStreamTailBuf<S>,
+    }
+}
+
+impl<S, F, K> UniqueByKey<S, F, K>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    F: Fn(&VectorDiffContainerStreamElement<S>) -> K,
+    K: PartialEq,
+{
+    /// Create a new `UniqueByKey` with the given initial values, stream of
+    /// `VectorDiff` updates for those values, and key function.
+    pub fn new(
+        initial_values: Vector<VectorDiffContainerStreamElement<S>>,
+        inner_stream: S,
+        key_fn: F,
+    ) -> (Vector<VectorDiffContainerStreamElement<S>>, Self) {
+        let mut keys = Vec::with_capacity(initial_values.len());
+        let mut shown = Vec::with_capacity(initial_values.len());
+        let values = shown_values(&initial_values, &key_fn, &mut keys, &mut shown);
+        let stream = Self {
+            inner_stream,
+            key_fn,
+            buffered_vector: initial_values,
+            keys,
+            shown,
+            ready_values: Default::default(),
+        };
+
+        (values, stream)
+    }
+}
+
+impl<S, F, K> Stream for UniqueByKey<S, F, K>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    F: Fn(&VectorDiffContainerStreamElement<S>) -> K,
+    K: PartialEq,
+{
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        self.project().poll_next(cx)
+    }
+}
+
+impl<S, F, K> AdapterDebug for UniqueByKey<S, F, K>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn adapter_state(&self) -> AdapterState {
+        AdapterState {
+            buffered_len: Some(self.buffered_vector.len()),
+            len: Some(row_index(&self.shown, self.shown.len())),
+            ready_values: S::Item::tail_buf_len(&self.ready_values),
+            ..AdapterState::new("UniqueByKey")
+        }
+    }
+}
+
+impl<S, F, K> fmt::Debug for UniqueByKey<S, F, K>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.adapter_state(), f)
+    }
+}
+
+impl<S, F, K> UniqueByKeyProj<'_, S, F, K>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    F: Fn(&VectorDiffContainerStreamElement<S>) -> K,
+    K: PartialEq,
+{
+    fn poll_next(&mut self, cx: &mut task::Context<'_>) -> Poll<Option<S::Item>> {
+        loop {
+            // First off, if any values are ready, return them.
+            if let Some(value) = S::Item::pop_from_tail_buf(self.ready_values) {
+                return Poll::Ready(Some(value));
+            }
+
+            // Poll `VectorDiff`s from the `inner_stream`.
+            let Some(diffs) = ready!(self.inner_stream.as_mut().poll_next(cx)) else {
+                return Poll::Ready(None);
+            };
+
+            let mut res = Vec::new();
+            for diff in diffs.into_vec() {
+                self.handle_diff(diff, &mut res);
+            }
+
+            if let Some(diffs) = S::Item::extend_tail_buf(res, self.ready_values) {
+                return Poll::Ready(Some(diffs));
+            }
+
+            // Else loop and poll the streams again.
+        }
+    }
+
+    fn handle_diff(
+        &mut self,
+        diff: VectorDiff<VectorDiffContainerStreamElement<S>>,
+        res: &mut Vec<VectorDiff<VectorDiffContainerStreamElement<S>>>,
+    ) {
+        match diff {
+            VectorDiff::Append { values } => {
+                let values = self.append(values);
+                if !values.is_empty() {
+                    res.push(VectorDiff::Append { values });
+                }
+            }
+            VectorDiff::Clear => {
+                self.clear();
+                res.push(VectorDiff::Clear);
+            }
+            VectorDiff::PushFront { value } => {
+                self.insert(0, value, res);
+            }
+            VectorDiff::PushBack { value } => {
+                self.insert(self.keys.len(), value, res);
+            }
+            VectorDiff::PopFront => {
+                self.remove(0, res);
+            }
+            VectorDiff::PopBack => {
+                self.remove(self.keys.len() - 1, res);
+            }
+            VectorDiff::Insert { index, value } => {
+                self.insert(index, value, res);
+            }
+            VectorDiff::Set { index, value } => {
+                let key = (self.key_fn)(&value);
+                if key == self.keys[index] {
+                    self.buffered_vector.set(index, value.clone());
+                    if self.shown[index] {
+                        res.push(VectorDiff::Set { index: row_index(self.shown, index), value });
+                    }
+                } else {
+                    self.remove(index, res);
+                    self.insert(index, value, res);
+                }
+            }
+            VectorDiff::Remove { index } => {
+                self.remove(index, res);
+            }
+            VectorDiff::Truncate { length } => {
+                // The retained occurrences of the keys of the remaining items
+                // are among the remaining items, so nothing new is shown.
+                let old_length = row_index(self.shown, self.shown.len());
+                let new_length = row_index(self.shown, length);
+                self.buffered_vector.truncate(length);
+                self.keys.truncate(length);
+                self.shown.truncate(length);
+                if new_length != old_length {
+                    res.push(VectorDiff::Truncate { length: new_length });
+                }
+            }
+            VectorDiff::Move { from, to } => {
+                let value = self.remove(from, res);
+                self.insert(to, value, res);
+            }
+            VectorDiff::Reset { values, reason } => {
+                self.clear();
+                let values = self.append(values);
+                res.push(VectorDiff::Reset { values, reason });
+            }
+        }
+    }
+
+    /// Append `values` to the observed vector, returning the ones that are
+    /// shown.
+    fn append(
+        &mut self,
+        values: Vector<VectorDiffContainerStreamElement<S>>,
+    ) -> Vector<VectorDiffContainerStreamElement<S>> {
+        let shown_values = shown_values(&values, &*self.key_fn, self.keys, self.shown);
+        self.buffered_vector.append(values);
+        shown_values
+    }
+
+    fn clear(&mut self) {
+        self.buffered_vector.clear();
+        self.keys.clear();
+        self.shown.clear();
+    }
+
+    /// Insert `value` at `index` of the observed vector.
+    fn insert(
+        &mut self,
+        index: usize,
+        value: VectorDiffContainerStreamElement<S>,
+        res: &mut Vec<VectorDiff<VectorDiffContainerStreamElement<S>>>,
+    ) {
+        let key = (self.key_fn)(&value);
+        let first = self.keys.iter().position(|k| *k == key);
+        let shown = first.map_or(true, |first| first >= index);
+
+        if shown {
+            let row = row_index(self.shown, index);
+            match first {
+                // The value takes the place of the retained occurrence of its
+                // key.
+                Some(first) => {
+                    let first_row = row_index(self.shown, first);
+                    self.shown[first] = false;
+                    if first_row == row {
+                        res.push(VectorDiff::Set { index: row, value: value.clone() });
+                    } else {
+                        res.push(VectorDiff::Insert { index: row, value: value.clone() });
+                        res.push(VectorDiff::Remove { index: first_row + 1 });
+                    }
+                }
+                None => {
+                    res.push(VectorDiff::Insert { index: row, value: value.clone() });
+                }
+            }
+        }
+
+        self.buffered_vector.insert(index, value);
+        self.keys.insert(index, key);
+        self.shown.insert(index, shown);
+    }
+
+    /// Remove the value at `index` of the observed vector.
+    fn remove(
+        &mut self,
+        index: usize,
+        res: &mut Vec<VectorDiff<VectorDiffContainerStreamElement<S>>>,
+    ) -> VectorDiffContainerStreamElement<S> {
+        let value = self.buffered_vector.remove(index);
+        let key = self.keys.remove(index);
+        let shown = self.shown.remove(index);
+
+        if shown {
+            let row = row_index(self.shown, index);
+            // The next occurrence of the key takes the place of the removed
+            // one, if there is any.
+            match self.keys[index..].iter().position(|k| *k == key) {
+                Some(offset) => {
+                    let next = index + offset;
+                    let next_row = row_index(self.shown, next);
+                    let next_value = self.buffered_vector[next].clone();
+                    self.shown[next] = true;
+                    if next_row == row {
+                        res.push(VectorDiff::Set { index: row, value: next_value });
+                    } else {
+                        res.push(VectorDiff::Remove { index: row });
+                        res.push(VectorDiff::Insert { index: next_row, value: next_value });
+                    }
+                }
+                None => {
+                    res.push(VectorDiff::Remove { index: row });
+                }
+            }
+        }
+
+        value
+    }
+}
+
+/// Get the number of shown items before `index`, which is the row of the item
+/// at `index` in the view.
+fn row_index(shown: &[bool], index: usize) -> usize {
+    shown[..index].iter().filter(|&&shown| shown).count()
+}
+
+/// Get the values of `values` that are shown if they come after the items whose
+/// keys are in `keys`.
+///
+/// The keys of `values` and whether they are shown are pushed to `keys` and
+/// `shown`.
+fn shown_values<T, K>(
+    values: &Vector<T>,
+    key_fn: &impl Fn(&T) -> K,
+    keys: &mut Vec<K>,
+    shown: &mut Vec<bool>,
+) -> Vector<T>
+where
+    T: Clone,
+    K: PartialEq,
+{
+    let mut res = Vector::new();
+    for value in values {
+        let key = key_fn(value);
+        let is_shown = !keys.contains(&key);
+        if is_shown {
+            res.push_back(value.clone());
+        }
+
+        keys.push(key);
+        shown.push(is_shown);
+    }
+
+    res
+}
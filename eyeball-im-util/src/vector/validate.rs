@@ -0,0 +1,172 @@
+use std::{
+    fmt,
+    pin::Pin,
+    task::{self, ready, Poll},
+};
+
+use eyeball_im::{ResetReason, Vector, VectorDiff};
+use futures_core::Stream;
+use pin_project_lite::pin_project;
+
+use super::{
+    AdapterDebug, AdapterState, VectorDiffContainer, VectorDiffContainerOps,
+    VectorDiffContainerStreamElement,
+};
+
+pin_project! {
+    /// A [`VectorDiff`] stream adapter that checks the diffs of the underlying
+    /// stream against the vector they are applied to, turning malformed ones
+    /// into a `VectorDiff::Reset`.
+    ///
+    /// Adapters assume that the diffs they receive are well-formed, and panic
+    /// otherwise, e.g. when a `VectorDiff::Remove` targets an index past the
+    /// end of the vector. That holds for diffs coming from an
+    /// [`ObservableVector`], but not necessarily for diffs from other sources,
+    /// like ones deserialized from the network. Putting this adapter at the
+    /// start of an adapter chain makes the rest of the chain lenient towards
+    /// such input.
+    ///
+    /// A malformed diff is applied as far as possible (e.g. an insertion past
+    /// the end becomes an insertion at the end, a removal past the end is
+    /// ignored) and replaced by a `VectorDiff::Reset` with the resulting
+    /// values and [`ResetReason::Lagged`], since it's most likely the result
+    /// of updates that were missed.
+    ///
+    /// ```rust
+    /// use eyeball_im::{ResetReason, VectorDiff};
+    /// use eyeball_im_util::vector::VectorObserverExt;
+    /// use futures_util::stream;
+    /// use imbl::vector;
+    /// use stream_assert::{assert_closed, assert_next_eq};
+    ///
+    /// let diffs = stream::iter([
+    ///     VectorDiff::PushBack { value: 3 },
+    ///     VectorDiff::Remove { index: 5 },
+    ///     VectorDiff::Insert { index: 7, value: 4 },
+    /// ]);
+    /// let (values, mut sub) = (vector![1, 2], diffs).validate();
+    /// assert_eq!(values, vector![1, 2]);
+    ///
+    /// assert_next_eq!(sub, VectorDiff::PushBack { value: 3 });
+    /// assert_next_eq!(
+    ///     sub,
+    ///     VectorDiff::Reset { values: vector![1, 2, 3], reason: ResetReason::Lagged }
+    /// );
+    /// assert_next_eq!(
+    ///     sub,
+    ///     VectorDiff::Reset { values: vector![1, 2, 3, 4], reason: ResetReason::Lagged }
+    /// );
+    /// assert_closed!(sub);
+    /// ```
+    ///
+    /// [`ObservableVector`]: eyeball_im::ObservableVector
+    #[project = ValidateProj]
+    pub struct Validate<S>
+    where
+        S: Stream,
+        S::Item: VectorDiffContainer,
+    {
+        // The main stream to poll items from.
+        #[pin]
+        inner_stream: S,
+
+        // The buffered vector that is updated with the main stream's items.
+        buffered_vector: Vector<VectorDiffContainerStreamElement<S>>,
+    }
+}
+
+impl<S> Validate<S>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    /// Create a new `Validate` with the given initial values and stream of
+    /// `VectorDiff` updates for those values.
+    pub fn new(
+        initial_values: Vector<VectorDiffContainerStreamElement<S>>,
+        inner_stream: S,
+    ) -> (Vector<VectorDiffContainerStreamElement<S>>, Self) {
+        let stream = Self { inner_stream, buffered_vector: initial_values.clone() };
+        (initial_values, stream)
+    }
+}
+
+impl<S> Stream for Validate<S>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        loop {
+            let Some(diffs) = ready!(this.inner_stream.as_mut().poll_next(cx)) else {
+                return Poll::Ready(None);
+            };
+
+            let validated = diffs.filter_map(|diff| Some(validate(this.buffered_vector, diff)));
+            if let Some(diffs) = validated {
+                return Poll::Ready(Some(diffs));
+            }
+        }
+    }
+}
+
+impl<S> AdapterDebug for Validate<S>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn adapter_state(&self) -> AdapterState {
+        AdapterState {
+            buffered_len: Some(self.buffered_vector.len()),
+            ..AdapterState::new("Validate")
+        }
+    }
+}
+
+impl<S> fmt::Debug for Validate<S>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.adapter_state(), f)
+    }
+}
+
+/// Apply `diff` to `values`, returning it if it's well-formed or a reset to the
+/// resulting values otherwise.
+fn validate<T: Clone>(values: &mut Vector<T>, diff: VectorDiff<T>) -> VectorDiff<T> {
+    let len = values.len();
+    let well_formed = match &diff {
+        VectorDiff::Append { .. }
+        | VectorDiff::Clear
+        | VectorDiff::PushFront { .. }
+        | VectorDiff::PushBack { .. }
+        | VectorDiff::Reset { .. } => true,
+        VectorDiff::PopFront | VectorDiff::PopBack => len > 0,
+        VectorDiff::Insert { index, .. } => *index <= len,
+        VectorDiff::Set { index, .. } | VectorDiff::Remove { index } => *index < len,
+        VectorDiff::Truncate { length } => *length <= len,
+        VectorDiff::Move { from, to } => *from < len && *to < len,
+    };
+
+    if well_formed {
+        diff.clone().apply(values);
+        return diff;
+    }
+
+    // Apply the parts of the diff that make sense.
+    match diff {
+        VectorDiff::Insert { value, .. } => values.push_back(value),
+        VectorDiff::Move { from, .. } if from < len => {
+            let value = values.remove(from);
+            values.push_back(value);
+        }
+        _ => {}
+    }
+
+    VectorDiff::Reset { values: values.clone(), reason: ResetReason::Lagged }
+}
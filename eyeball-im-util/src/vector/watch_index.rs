@@ -0,0 +1,222 @@
+use std::{
+    cmp::{max, min},
+    fmt,
+    pin::Pin,
+    task::{self, ready, Poll},
+};
+
+use eyeball_im::VectorDiff;
+use futures_core::Stream;
+use imbl::Vector;
+use pin_project_lite::pin_project;
+
+use super::{
+    AdapterDebug, AdapterState, VectorDiffContainer, VectorDiffContainerOps,
+    VectorDiffContainerStreamElement,
+};
+
+pin_project! {
+    /// A stream adapter that yields the element at a dynamic index of the
+    /// underlying [`ObservableVector`].
+    ///
+    /// The index is controlled by a stream of indices, for example the
+    /// currently selected row of a list. Whenever the index changes, or the
+    /// element at the current index changes, the element at that index is
+    /// yielded, or `None` if the index is out of bounds. Inserting or removing
+    /// elements before the index counts as a change, since it shifts a
+    /// different element to the index. The same element can be yielded again
+    /// if it is moved away and back within a single batch of updates.
+    ///
+    /// Nothing is yielded until the first index is produced by the index
+    /// stream. If the index stream ends, the last index is kept. The stream
+    /// ends once the underlying stream of `VectorDiff`s ends.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use eyeball::SharedObservable;
+    /// use eyeball_im::ObservableVector;
+    /// use eyeball_im_util::vector::VectorObserverExt;
+    /// use imbl::vector;
+    /// use stream_assert::{assert_next_eq, assert_pending};
+    ///
+    /// let mut ob = ObservableVector::<char>::from(vector!['a', 'b', 'c']);
+    /// let selected = SharedObservable::new(1);
+    /// let mut sub = ob.subscribe().watch_index_stream(selected.subscribe_reset());
+    /// assert_next_eq!(sub, Some('b'));
+    ///
+    /// // Changes to other elements are not forwarded.
+    /// ob.set(2, 'z');
+    /// assert_pending!(sub);
+    ///
+    /// // Inserting an element before the index shifts another one to it.
+    /// ob.push_front('x');
+    /// assert_next_eq!(sub, Some('a'));
+    ///
+    /// selected.set(5);
+    /// assert_next_eq!(sub, None);
+    /// ```
+    ///
+    /// [`ObservableVector`]: eyeball_im::ObservableVector
+    #[project = WatchIndexProj]
+    pub struct WatchIndex<S, I>
+    where
+        S: Stream,
+        S::Item: VectorDiffContainer,
+    {
+        // The main stream to poll items from.
+        #[pin]
+        inner_stream: S,
+
+        // The stream to poll new indices from.
+        #[pin]
+        index_stream: I,
+
+        // The buffered vector that is updated with the main stream's items.
+        buffered_vector: Vector<VectorDiffContainerStreamElement<S>>,
+
+        // The current index, `None` until the first one is received.
+        index: Option<usize>,
+
+        // Whether `index_stream` has ended.
+        index_stream_done: bool,
+    }
+}
+
+impl<S, I> WatchIndex<S, I>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    I: Stream<Item = usize>,
+{
+    /// Create a new [`WatchIndex`] with the given (unlimited) initial values,
+    /// stream of `VectorDiff` updates for those values, and a stream of
+    /// indices.
+    pub fn new(
+        initial_values: Vector<VectorDiffContainerStreamElement<S>>,
+        inner_stream: S,
+        index_stream: I,
+    ) -> Self {
+        Self {
+            inner_stream,
+            index_stream,
+            buffered_vector: initial_values,
+            index: None,
+            index_stream_done: false,
+        }
+    }
+
+    /// Get the current index, if one was received already.
+    pub fn index(&self) -> Option<usize> {
+        self.index
+    }
+}
+
+impl<S, I> Stream for WatchIndex<S, I>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    I: Stream<Item = usize>,
+{
+    type Item = Option<VectorDiffContainerStreamElement<S>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        self.project().poll_next(cx)
+    }
+}
+
+impl<S, I> AdapterDebug for WatchIndex<S, I>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn adapter_state(&self) -> AdapterState {
+        AdapterState {
+            buffered_len: Some(self.buffered_vector.len()),
+            offset: self.index,
+            ..AdapterState::new("WatchIndex")
+        }
+    }
+}
+
+impl<S, I> fmt::Debug for WatchIndex<S, I>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.adapter_state(), f)
+    }
+}
+
+impl<S, I> WatchIndexProj<'_, S, I>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    I: Stream<Item = usize>,
+{
+    fn poll_next(
+        &mut self,
+        cx: &mut task::Context<'_>,
+    ) -> Poll<Option<Option<VectorDiffContainerStreamElement<S>>>> {
+        // Poll new indices from `index_stream` before polling `inner_stream`,
+        // only keeping the latest one.
+        let mut index_changed = false;
+        while !*self.index_stream_done {
+            match self.index_stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(index)) => {
+                    if *self.index != Some(index) {
+                        *self.index = Some(index);
+                        index_changed = true;
+                    }
+                }
+                Poll::Ready(None) => *self.index_stream_done = true,
+                Poll::Pending => break,
+            }
+        }
+
+        if index_changed {
+            return Poll::Ready(Some(self.current()));
+        }
+
+        loop {
+            let Some(diffs) = ready!(self.inner_stream.as_mut().poll_next(cx)) else {
+                return Poll::Ready(None);
+            };
+
+            let mut changed = false;
+            diffs.for_each_diff(|diff| {
+                if let Some(index) = *self.index {
+                    changed |= affects_index(diff, index, self.buffered_vector.len());
+                }
+                diff.clone().apply(self.buffered_vector);
+            });
+
+            if changed {
+                return Poll::Ready(Some(self.current()));
+            }
+        }
+    }
+
+    fn current(&self) -> Option<VectorDiffContainerStreamElement<S>> {
+        self.buffered_vector.get((*self.index)?).cloned()
+    }
+}
+
+/// Whether applying `diff` to a vector of length `len` changes which element
+/// is at `index`, or the element itself.
+fn affects_index<T>(diff: &VectorDiff<T>, index: usize, len: usize) -> bool {
+    match diff {
+        VectorDiff::Append { values } => index >= len && index < len + values.len(),
+        VectorDiff::Clear | VectorDiff::PopFront => index < len,
+        VectorDiff::PushFront { .. } => index <= len,
+        VectorDiff::PushBack { .. } => index == len,
+        VectorDiff::PopBack => index + 1 == len,
+        VectorDiff::Insert { index: i, .. } => *i <= index && index <= len,
+        VectorDiff::Set { index: i, .. } => *i == index,
+        VectorDiff::Remove { index: i } => *i <= index && index < len,
+        VectorDiff::Truncate { length } => *length <= index && index < len,
+        VectorDiff::Move { from, to } => min(*from, *to) <= index && index <= max(*from, *to),
+        VectorDiff::Reset { .. } => true,
+    }
+}
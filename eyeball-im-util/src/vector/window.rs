@@ -0,0 +1,588 @@
+use std::{
+    cmp::{max, min},
+    fmt, mem,
+    pin::Pin,
+    task::{self, ready, Poll},
+};
+
+use eyeball_im::VectorDiff;
+use futures_core::Stream;
+use imbl::Vector;
+use pin_project_lite::pin_project;
+use smallvec::SmallVec;
+
+use super::{
+    AdapterDebug, AdapterState, VectorDiffContainer, VectorDiffContainerOps,
+    VectorDiffContainerStreamElement, VectorDiffContainerStreamTailBuf, VectorObserver,
+};
+
+/// The range of items presented by a [`Window`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct WindowSpec {
+    /// The index of the first item in the window.
+    pub offset: usize,
+    /// The maximum number of items in the window.
+    pub len: usize,
+}
+
+impl WindowSpec {
+    /// Create a new `WindowSpec`.
+    pub fn new(offset: usize, len: usize) -> Self {
+        Self { offset, len }
+    }
+
+    /// The index after the last item in the window.
+    fn end(self) -> usize {
+        self.offset.saturating_add(self.len)
+    }
+
+    /// The number of items in the window, for a vector of length `len`.
+    pub(super) fn count(self, len: usize) -> usize {
+        min(len.saturating_sub(self.offset), self.len)
+    }
+}
+
+pin_project! {
+    /// A stream of [`WindowSpec`]s built from separate streams of offsets and
+    /// lengths, for use with [`Window`].
+    ///
+    /// Whenever either stream yields a new value, a `WindowSpec` with the
+    /// latest offset and length is yielded. Offsets and lengths that are ready
+    /// at the same time result in a single `WindowSpec`. The stream ends once
+    /// both inner streams have ended.
+    ///
+    /// ```rust
+    /// use eyeball::SharedObservable;
+    /// use eyeball_im::{ObservableVector, VectorDiff};
+    /// use eyeball_im_util::vector::{VectorObserverExt, WindowSpec, WindowSpecStream};
+    /// use imbl::vector;
+    /// use stream_assert::{assert_next_eq, assert_pending};
+    ///
+    /// let ob = ObservableVector::<char>::from(vector!['a', 'b', 'c', 'd', 'e']);
+    /// let offset = SharedObservable::new(1);
+    /// let limit = SharedObservable::new(2);
+    /// let spec_stream =
+    ///     WindowSpecStream::new(WindowSpec::new(1, 2), offset.subscribe(), limit.subscribe());
+    /// let (values, mut sub) =
+    ///     ob.subscribe().dynamic_window_with_initial_value(spec_stream.spec(), spec_stream);
+    /// assert_eq!(values, vector!['b', 'c']);
+    ///
+    /// limit.set(3);
+    /// assert_next_eq!(sub, VectorDiff::Append { values: vector!['d'] });
+    ///
+    /// offset.set(2);
+    /// assert_next_eq!(sub, VectorDiff::PopFront);
+    /// assert_next_eq!(sub, VectorDiff::Append { values: vector!['e'] });
+    /// assert_pending!(sub);
+    /// ```
+    pub struct WindowSpecStream<O, L> {
+        #[pin]
+        offset_stream: O,
+        #[pin]
+        len_stream: L,
+
+        // The window made of the latest offset and length.
+        spec: WindowSpec,
+
+        offset_stream_done: bool,
+        len_stream_done: bool,
+    }
+}
+
+impl<O, L> WindowSpecStream<O, L>
+where
+    O: Stream<Item = usize>,
+    L: Stream<Item = usize>,
+{
+    /// Create a new `WindowSpecStream` with the given initial window, which is
+    /// updated with the values of the given streams of offsets and lengths.
+    ///
+    /// The initial window is not yielded by the stream.
+    pub fn new(initial_spec: WindowSpec, offset_stream: O, len_stream: L) -> Self {
+        Self {
+            offset_stream,
+            len_stream,
+            spec: initial_spec,
+            offset_stream_done: false,
+            len_stream_done: false,
+        }
+    }
+
+    /// Get the window made of the latest offset and length.
+    pub fn spec(&self) -> WindowSpec {
+        self.spec
+    }
+}
+
+impl<O, L> Stream for WindowSpecStream<O, L>
+where
+    O: Stream<Item = usize>,
+    L: Stream<Item = usize>,
+{
+    type Item = WindowSpec;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        let mut changed = false;
+
+        while !*this.offset_stream_done {
+            match this.offset_stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(offset)) => {
+                    changed |= this.spec.offset != offset;
+                    this.spec.offset = offset;
+                }
+                Poll::Ready(None) => *this.offset_stream_done = true,
+                Poll::Pending => break,
+            }
+        }
+
+        while !*this.len_stream_done {
+            match this.len_stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(len)) => {
+                    changed |= this.spec.len != len;
+                    this.spec.len = len;
+                }
+                Poll::Ready(None) => *this.len_stream_done = true,
+                Poll::Pending => break,
+            }
+        }
+
+        if changed {
+            Poll::Ready(Some(*this.spec))
+        } else if *this.offset_stream_done && *this.len_stream_done {
+            Poll::Ready(None)
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+impl<O, L> fmt::Debug for WindowSpecStream<O, L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WindowSpecStream")
+            .field("spec", &self.spec)
+            .field("offset_stream_done", &self.offset_stream_done)
+            .field("len_stream_done", &self.len_stream_done)
+            .finish_non_exhaustive()
+    }
+}
+
+pin_project! {
+    /// A [`VectorDiff`] stream adapter that presents a window of the underlying
+    /// [`ObservableVector`]'s items, skipping the first `offset` items and
+    /// limiting the view to `len` items.
+    ///
+    /// The window is controlled by a single stream of [`WindowSpec`]s. When the
+    /// window changes, the minimal diffs between the old and the new window
+    /// are emitted in one step, which avoids the intermediate states that
+    /// come up when controlling the offset and the length separately. If the
+    /// offset and the length come from separate streams, combine them with
+    /// [`WindowSpecStream`].
+    ///
+    /// It's okay for the window to extend beyond the end of the observed
+    /// `Vector`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use eyeball::SharedObservable;
+    /// use eyeball_im::{ObservableVector, VectorDiff};
+    /// use eyeball_im_util::vector::{VectorObserverExt, WindowSpec};
+    /// use imbl::vector;
+    /// use stream_assert::{assert_next_eq, assert_pending};
+    ///
+    /// let mut ob = ObservableVector::<char>::from(vector!['a', 'b', 'c', 'd', 'e']);
+    /// let spec = SharedObservable::new(WindowSpec::new(1, 2));
+    /// let (values, mut sub) =
+    ///     ob.subscribe().dynamic_window_with_initial_value(spec.get(), spec.subscribe());
+    /// assert_eq!(values, vector!['b', 'c']);
+    ///
+    /// // Move the window by one item.
+    /// spec.set(WindowSpec::new(2, 2));
+    /// assert_next_eq!(sub, VectorDiff::PopFront);
+    /// assert_next_eq!(sub, VectorDiff::Append { values: vector!['d'] });
+    ///
+    /// // Only changes within the window are forwarded.
+    /// ob.set(0, 'z');
+    /// assert_pending!(sub);
+    /// ob.set(3, 'y');
+    /// assert_next_eq!(sub, VectorDiff::Set { index: 1, value: 'y' });
+    /// ```
+    ///
+    /// [`ObservableVector`]: eyeball_im::ObservableVector
+    #[project = WindowProj]
+    pub struct Window<S, W>
+    where
+        S: Stream,
+        S::Item: VectorDiffContainer,
+    {
+        // The main stream to poll items from.
+        #[pin]
+        inner_stream: S,
+
+        // The stream to poll new window specs from.
+        #[pin]
+        spec_stream: W,
+
+        // The buffered vector that is updated with the main stream's items.
+        // It's used to provide items that enter the window.
+        buffered_vector: Vector<VectorDiffContainerStreamElement<S>>,
+
+        // The current window.
+        spec: WindowSpec,
+
+        // Diffs that are ready to be emitted, used if the item type is just
+        // `VectorDiff<_>` (non-batched) since one update can result in
+        // multiple diffs.
+        ready_values: VectorDiffContainerStreamTailBuf<S>,
+    }
+}
+
+impl<S, W> Window<S, W>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    W: Stream<Item = WindowSpec>,
+{
+    /// Create a new [`Window`] with the given (unlimited) initial values,
+    /// stream of `VectorDiff` updates for those values, and a stream of
+    /// window specs.
+    ///
+    /// This is equivalent to `dynamic_with_initial_spec` where the
+    /// `initial_spec` is the empty window at offset 0, except that it doesn't
+    /// return the initial window as it would be empty anyways.
+    ///
+    /// Note that the returned `Window` won't produce anything until the first
+    /// window spec is produced by the spec stream.
+    pub fn dynamic(
+        initial_values: Vector<VectorDiffContainerStreamElement<S>>,
+        inner_stream: S,
+        spec_stream: W,
+    ) -> Self {
+        Self {
+            inner_stream,
+            spec_stream,
+            buffered_vector: initial_values,
+            spec: WindowSpec::default(),
+            ready_values: Default::default(),
+        }
+    }
+
+    /// Create a new [`Window`] with the given (unlimited) initial values,
+    /// stream of `VectorDiff` updates for those values, and an initial window
+    /// spec as well as a stream of new window specs.
+    pub fn dynamic_with_initial_spec(
+        initial_values: Vector<VectorDiffContainerStreamElement<S>>,
+        inner_stream: S,
+        initial_spec: WindowSpec,
+        spec_stream: W,
+    ) -> (Vector<VectorDiffContainerStreamElement<S>>, Self) {
+        let window_values = window_values(&initial_values, initial_spec);
+        let stream = Self {
+            inner_stream,
+            spec_stream,
+            buffered_vector: initial_values,
+            spec: initial_spec,
+            ready_values: Default::default(),
+        };
+
+        (window_values, stream)
+    }
+
+    /// Get the current [`WindowSpec`].
+    pub fn spec(&self) -> WindowSpec {
+        self.spec
+    }
+}
+
+impl<S, W> Stream for Window<S, W>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    W: Stream<Item = WindowSpec>,
+{
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        self.project().poll_next(cx)
+    }
+}
+
+impl<S, W> VectorObserver<VectorDiffContainerStreamElement<S>> for Window<S, W>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    W: Stream<Item = WindowSpec>,
+{
+    type Stream = Self;
+
+    fn into_parts(self) -> (Vector<VectorDiffContainerStreamElement<S>>, Self::Stream) {
+        (window_values(&self.buffered_vector, self.spec), self)
+    }
+}
+
+impl<S, W> AdapterDebug for Window<S, W>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn adapter_state(&self) -> AdapterState {
+        AdapterState {
+            buffered_len: Some(self.buffered_vector.len()),
+            len: Some(self.spec.count(self.buffered_vector.len())),
+            limit: Some(self.spec.len),
+            offset: Some(self.spec.offset),
+            ready_values: S::Item::tail_buf_len(&self.ready_values),
+            ..AdapterState::new("Window")
+        }
+    }
+}
+
+impl<S, W> fmt::Debug for Window<S, W>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.adapter_state(), f)
+    }
+}
+
+impl<S, W> WindowProj<'_, S, W>
+where
+    S: Stream,
+    S::Item: VectorDiffContainer,
+    W: Stream<Item = WindowSpec>,
+{
+    fn poll_next(&mut self, cx: &mut task::Context<'_>) -> Poll<Option<S::Item>> {
+        loop {
+            // First off, if any values are ready, return them.
+            if let Some(value) = S::Item::pop_from_tail_buf(self.ready_values) {
+                return Poll::Ready(Some(value));
+            }
+
+            // Poll a new window spec from `spec_stream` before polling
+            // `inner_stream`.
+            while let Poll::Ready(Some(next_spec)) = self.spec_stream.as_mut().poll_next(cx) {
+                let old_spec = mem::replace(self.spec, next_spec);
+                let diffs = update_spec(self.buffered_vector, old_spec, next_spec);
+                if !diffs.is_empty() {
+                    return Poll::Ready(S::Item::extend_tail_buf(diffs, self.ready_values));
+                }
+            }
+
+            // Poll `VectorDiff`s from the `inner_stream`.
+            let Some(diffs) = ready!(self.inner_stream.as_mut().poll_next(cx)) else {
+                return Poll::Ready(None);
+            };
+
+            // Consume and apply the diffs if possible.
+            let ready = diffs.push_into_tail_buf(self.ready_values, |diff| {
+                let prev_len = self.buffered_vector.len();
+
+                // Update the `buffered_vector`. It's a replica of the original observed
+                // `Vector`. We need to maintain it in order to be able to produce valid
+                // `VectorDiff`s when items enter the window.
+                diff.clone().apply(self.buffered_vector);
+
+                handle_diff(diff, *self.spec, prev_len, self.buffered_vector)
+            });
+
+            if let Some(diff) = ready {
+                return Poll::Ready(Some(diff));
+            }
+
+            // Else loop and poll the streams again.
+        }
+    }
+}
+
+/// Get the values in the window `spec` of `values`.
+pub(super) fn window_values<T: Clone>(values: &Vector<T>, spec: WindowSpec) -> Vector<T> {
+    values.skip(min(spec.offset, values.len())).take(spec.count(values.len()))
+}
+
+/// Compute the diffs that turn the window `old` of `values` into the window
+/// `new`.
+pub(super) fn update_spec<T: Clone>(
+    values: &Vector<T>,
+    old: WindowSpec,
+    new: WindowSpec,
+) -> Vec<VectorDiff<T>> {
+    let len = values.len();
+    let (old_start, old_count) = (old.offset, old.count(len));
+    let (new_start, new_count) = (new.offset, new.count(len));
+
+    if new_count == 0 {
+        return if old_count == 0 { Vec::new() } else { vec![VectorDiff::Clear] };
+    }
+    if old_count == 0 {
+        return vec![VectorDiff::Append { values: window_values(values, new) }];
+    }
+
+    // The part of the vector that is in both windows.
+    let overlap_start = max(old_start, new_start);
+    let overlap_end = min(old_start + old_count, new_start + new_count);
+
+    let num_popped_front = overlap_start.saturating_sub(old_start);
+    let num_pushed_front = overlap_start.saturating_sub(new_start);
+    if overlap_start >= overlap_end
+        || num_popped_front + num_pushed_front > overlap_end - overlap_start
+    {
+        // Replacing the whole window is cheaper.
+        return vec![VectorDiff::Clear, VectorDiff::Append { values: window_values(values, new) }];
+    }
+
+    let mut diffs = Vec::new();
+    if overlap_end < old_start + old_count {
+        diffs.push(VectorDiff::Truncate { length: overlap_end - old_start });
+    }
+    diffs.extend((0..num_popped_front).map(|_| VectorDiff::PopFront));
+    diffs.extend(
+        (new_start..overlap_start)
+            .rev()
+            .map(|i| VectorDiff::PushFront { value: values[i].clone() }),
+    );
+    if overlap_end < new_start + new_count {
+        let values = values.skip(overlap_end).take(new_start + new_count - overlap_end);
+        diffs.push(VectorDiff::Append { values });
+    }
+
+    diffs
+}
+
+pub(super) fn handle_diff<T: Clone>(
+    diff: VectorDiff<T>,
+    spec: WindowSpec,
+    prev_len: usize,
+    buffered_vector: &Vector<T>,
+) -> SmallVec<[VectorDiff<T>; 2]> {
+    let mut res = SmallVec::new();
+
+    // If the window is empty, we have nothing to do.
+    if spec.len == 0 {
+        return res;
+    }
+
+    match diff {
+        VectorDiff::Append { .. } => {
+            let old_count = spec.count(prev_len);
+            let new_count = spec.count(buffered_vector.len());
+            if new_count > old_count {
+                let values =
+                    buffered_vector.skip(spec.offset + old_count).take(new_count - old_count);
+                res.push(VectorDiff::Append { values });
+            }
+        }
+        VectorDiff::Clear => {
+            if spec.count(prev_len) > 0 {
+                res.push(VectorDiff::Clear);
+            }
+        }
+        VectorDiff::PushFront { value } => {
+            handle_insert(&mut res, spec, prev_len, buffered_vector, 0, value);
+        }
+        VectorDiff::PushBack { value } => {
+            handle_insert(&mut res, spec, prev_len, buffered_vector, prev_len, value);
+        }
+        VectorDiff::PopFront => {
+            handle_remove(&mut res, spec, prev_len, buffered_vector, 0);
+        }
+        VectorDiff::PopBack => {
+            handle_remove(&mut res, spec, prev_len, buffered_vector, prev_len - 1);
+        }
+        VectorDiff::Insert { index, value } => {
+            handle_insert(&mut res, spec, prev_len, buffered_vector, index, value);
+        }
+        VectorDiff::Set { index, value } => {
+            if (spec.offset..spec.end()).contains(&index) {
+                res.push(VectorDiff::Set { index: index - spec.offset, value });
+            }
+        }
+        VectorDiff::Remove { index } => {
+            handle_remove(&mut res, spec, prev_len, buffered_vector, index);
+        }
+        VectorDiff::Truncate { length } => {
+            let new_count = spec.count(length);
+            if new_count < spec.count(prev_len) {
+                res.push(VectorDiff::Truncate { length: new_count });
+            }
+        }
+        VectorDiff::Move { from, to } => {
+            let window = spec.offset..spec.end();
+            if window.contains(&from) && window.contains(&to) {
+                res.push(VectorDiff::Move { from: from - spec.offset, to: to - spec.offset });
+            } else if (from < spec.offset) != (to < spec.offset)
+                || window.contains(&from)
+                || window.contains(&to)
+            {
+                // The item moves into, out of or across the window. Handle it
+                // as a removal followed by an insertion.
+                let mut intermediate = buffered_vector.clone();
+                let value = intermediate.remove(to);
+                handle_remove(&mut res, spec, prev_len, &intermediate, from);
+                handle_insert(&mut res, spec, prev_len - 1, buffered_vector, to, value);
+            }
+            // Otherwise, the item moves entirely before or after the window,
+            // which doesn't affect it.
+        }
+        VectorDiff::Reset { reason, .. } => {
+            res.push(VectorDiff::Reset { values: window_values(buffered_vector, spec), reason });
+        }
+    }
+
+    res
+}
+
+/// Handle the insertion of `value` at `index` into a vector that had
+/// `prev_len` items before, and has the items of `buffered_vector` after.
+fn handle_insert<T: Clone>(
+    res: &mut SmallVec<[VectorDiff<T>; 2]>,
+    spec: WindowSpec,
+    prev_len: usize,
+    buffered_vector: &Vector<T>,
+    index: usize,
+    value: T,
+) {
+    if index >= spec.end() || buffered_vector.len() <= spec.offset {
+        return;
+    }
+
+    // The last item of a full window is pushed out.
+    if spec.count(prev_len) == spec.len {
+        res.push(VectorDiff::PopBack);
+    }
+
+    if index < spec.offset {
+        // The item before the window is shifted into it.
+        res.push(VectorDiff::PushFront { value: buffered_vector[spec.offset].clone() });
+    } else {
+        res.push(VectorDiff::Insert { index: index - spec.offset, value });
+    }
+}
+
+/// Handle the removal of the item at `index` from a vector that had `prev_len`
+/// items before, and has the items of `buffered_vector` after.
+fn handle_remove<T: Clone>(
+    res: &mut SmallVec<[VectorDiff<T>; 2]>,
+    spec: WindowSpec,
+    prev_len: usize,
+    buffered_vector: &Vector<T>,
+    index: usize,
+) {
+    if index >= spec.end() || spec.count(prev_len) == 0 {
+        return;
+    }
+
+    if index < spec.offset {
+        // The first item of the window is shifted out of it.
+        res.push(VectorDiff::PopFront);
+    } else {
+        res.push(VectorDiff::Remove { index: index - spec.offset });
+    }
+
+    // The item after the window is shifted into it.
+    if prev_len > spec.end() {
+        res.push(VectorDiff::PushBack { value: buffered_vector[spec.end() - 1].clone() });
+    }
+}
@@ -0,0 +1,69 @@
+use eyeball::Observable;
+use eyeball_im::{ObservableVector, VectorDiff};
+use eyeball_im_util::vector::{AdapterDebug, VectorObserverExt, VectorSubscriberExt, WindowSpec};
+use imbl::vector;
+use stream_assert::{assert_next_eq, assert_pending};
+
+#[test]
+fn filter() {
+    let mut ob = ObservableVector::<u32>::from(vector![1, 2, 3, 4, 5, 6]);
+    let (_, mut sub) = ob.subscribe().filter(|v| v % 2 == 0);
+    assert_eq!(format!("{sub:?}"), "Filter { buffered_len: 6, len: 3, ready_values: 0 }");
+
+    ob.push_back(8);
+    assert_next_eq!(sub, VectorDiff::PushBack { value: 8 });
+    let state = sub.adapter_state();
+    assert_eq!(state.buffered_len, Some(7));
+    assert_eq!(state.len, Some(4));
+}
+
+#[test]
+fn head_ready_values() {
+    let mut ob = ObservableVector::<u32>::from(vector![10, 11, 12]);
+    let (_, mut sub) = ob.subscribe().head(2);
+    assert_eq!(sub.adapter_state().ready_values, 0);
+
+    // Popping at the front removes 10 and makes 12 enter the view, but only
+    // one diff can be emitted at a time.
+    ob.pop_front();
+    assert_next_eq!(sub, VectorDiff::PopFront);
+    assert_eq!(sub.adapter_state().ready_values, 1);
+    assert_next_eq!(sub, VectorDiff::PushBack { value: 12 });
+    assert_eq!(sub.adapter_state().ready_values, 0);
+    assert_pending!(sub);
+
+    // Batched streams never need to buffer anything.
+    let (_, mut sub) = ob.subscribe().batched().head(1);
+    ob.pop_front();
+    assert_next_eq!(sub, vec![VectorDiff::PopFront, VectorDiff::PushBack { value: 12 }]);
+    assert_eq!(format!("{sub:?}"), "Head { buffered_len: 1, len: 1, limit: 1, ready_values: 0 }");
+}
+
+#[test]
+fn sort_if_enabled() {
+    let ob = ObservableVector::<u32>::from(vector![4, 0, 1, 3, 2]);
+    let mut enabled = Observable::new(false);
+    let (_, mut sub) = ob.subscribe().sort_if(false, Observable::subscribe(&enabled));
+    assert_eq!(
+        format!("{sub:?}"),
+        "SortIf { buffered_len: 5, len: 5, enabled: false, ready_values: 0 }"
+    );
+
+    Observable::set(&mut enabled, true);
+    assert_next_eq!(sub, VectorDiff::Move { from: 3, to: 4 });
+    let state = sub.adapter_state();
+    assert_eq!(state.enabled, Some(true));
+    assert_eq!(state.ready_values, 1);
+}
+
+#[test]
+fn window() {
+    let ob = ObservableVector::<u32>::from(vector![0, 1, 2, 3, 4]);
+    let spec = Observable::new(WindowSpec::new(3, 4));
+    let (_, sub) =
+        ob.subscribe().dynamic_window_with_initial_value(*spec, Observable::subscribe(&spec));
+    assert_eq!(
+        format!("{sub:?}"),
+        "Window { buffered_len: 5, len: 2, limit: 4, offset: 3, ready_values: 0 }"
+    );
+}
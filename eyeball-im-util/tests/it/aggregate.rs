@@ -0,0 +1,153 @@
+use eyeball_im::ObservableVector;
+use eyeball_im_util::vector::{VectorObserverExt, VectorSubscriberExt};
+use futures_util::FutureExt;
+use imbl::vector;
+use stream_assert::{assert_next_eq, assert_pending};
+
+#[test]
+fn sum() {
+    let mut ob = ObservableVector::<i32>::from(vector![1, 2, 3]);
+    let mut sum = ob.subscribe().sum(|&v| v);
+    let mut sub = sum.subscribe();
+    assert_eq!(sum.get(), 6);
+
+    ob.set(0, 4);
+    ob.remove(1);
+    assert_eq!((&mut sum).now_or_never(), None);
+    assert_eq!(sum.get(), 7);
+
+    ob.truncate(1);
+    ob.append(vector![-1, 5]);
+    ob.pop_back();
+    ob.move_item(0, 1);
+    assert_eq!((&mut sum).now_or_never(), None);
+    assert_eq!(sum.get(), 3);
+    assert_next_eq!(sub, 3);
+
+    // A batch that doesn't change the sum doesn't notify subscribers.
+    ob.set(0, -1);
+    ob.push_front(0);
+    assert_eq!((&mut sum).now_or_never(), None);
+    assert_pending!(sub);
+
+    ob.splice(.., vector![10, 20]);
+    assert_eq!((&mut sum).now_or_never(), None);
+    assert_next_eq!(sub, 30);
+
+    drop(ob);
+    assert_eq!((&mut sum).now_or_never(), Some(()));
+}
+
+#[test]
+fn sum_batched() {
+    let mut ob = ObservableVector::<u32>::from(vector![1, 2, 3]);
+    let mut sum = ob.subscribe().batched().head(2).sum(|&v| u64::from(v));
+    let mut sub = sum.subscribe();
+    assert_eq!(sum.get(), 3);
+
+    let mut txn = ob.transaction();
+    txn.push_front(10);
+    txn.pop_back();
+    txn.commit();
+    assert_eq!((&mut sum).now_or_never(), None);
+    assert_next_eq!(sub, 11);
+    assert_pending!(sub);
+}
+
+#[test]
+fn mean() {
+    let mut ob = ObservableVector::<f64>::new();
+    let mut mean = ob.subscribe().mean(|&v| v);
+    let mut sub = mean.subscribe();
+    assert_eq!(mean.get(), None);
+
+    ob.append(vector![1.0, 2.0, 6.0]);
+    assert_eq!((&mut mean).now_or_never(), None);
+    assert_next_eq!(sub, Some(3.0));
+
+    ob.set(2, 3.0);
+    ob.pop_front();
+    assert_eq!((&mut mean).now_or_never(), None);
+    assert_next_eq!(sub, Some(2.5));
+
+    ob.truncate(0);
+    assert_eq!((&mut mean).now_or_never(), None);
+    assert_next_eq!(sub, None);
+
+    // Values that don't sum up exactly don't leave an error behind once they
+    // are gone.
+    ob.append(vector![0.1, 0.2]);
+    ob.clear();
+    ob.push_back(0.3);
+    assert_eq!((&mut mean).now_or_never(), None);
+    assert_next_eq!(sub, Some(0.3));
+
+    ob.splice(.., vector![4.0, 8.0]);
+    assert_eq!((&mut mean).now_or_never(), None);
+    assert_next_eq!(sub, Some(6.0));
+    assert_pending!(sub);
+}
+
+#[test]
+fn any() {
+    let mut ob = ObservableVector::<i32>::from(vector![1, 3]);
+    let mut any_even = ob.subscribe().any(|v| v % 2 == 0);
+    let mut sub = any_even.subscribe();
+    assert!(!any_even.get());
+
+    ob.push_back(4);
+    assert_eq!((&mut any_even).now_or_never(), None);
+    assert_next_eq!(sub, true);
+
+    // A batch that doesn't change the result doesn't notify subscribers.
+    ob.insert(0, 6);
+    ob.remove(3);
+    assert_eq!((&mut any_even).now_or_never(), None);
+    assert_pending!(sub);
+    assert_eq!(any_even.matching(), 1);
+
+    ob.set(0, 5);
+    assert_eq!((&mut any_even).now_or_never(), None);
+    assert_next_eq!(sub, false);
+
+    ob.splice(.., vector![1, 3]);
+    ob.clear();
+    assert_eq!((&mut any_even).now_or_never(), None);
+    assert_pending!(sub);
+    assert_eq!(any_even.matching(), 0);
+}
+
+#[test]
+fn all() {
+    let mut ob = ObservableVector::<bool>::new();
+    let mut all_selected = ob.subscribe().batched().all(|&selected| selected);
+    let mut sub = all_selected.subscribe();
+    assert!(all_selected.get());
+
+    ob.append(vector![true, false, true]);
+    assert_eq!((&mut all_selected).now_or_never(), None);
+    assert_next_eq!(sub, false);
+
+    let mut txn = ob.transaction();
+    txn.set(1, true);
+    txn.move_item(1, 0);
+    txn.commit();
+    assert_eq!((&mut all_selected).now_or_never(), None);
+    assert_next_eq!(sub, true);
+    assert_eq!(all_selected.matching(), 3);
+
+    ob.push_front(false);
+    ob.pop_front();
+    assert_eq!((&mut all_selected).now_or_never(), None);
+    assert_pending!(sub);
+
+    ob.truncate(1);
+    ob.set(0, false);
+    assert_eq!((&mut all_selected).now_or_never(), None);
+    assert_next_eq!(sub, false);
+
+    ob.pop_back();
+    drop(ob);
+    assert_eq!((&mut all_selected).now_or_never(), Some(()));
+    assert!(all_selected.get());
+}
@@ -0,0 +1,185 @@
+use eyeball::SharedObservable;
+use eyeball_im::{ObservableVector, ResetReason, VectorDiff};
+use eyeball_im_util::vector::{
+    AnchorSpec, AnchoredWindow, VectorObserverExt, VectorSubscriberExt, WindowSpec,
+};
+use futures_util::stream;
+use imbl::{vector, Vector};
+use stream_assert::{assert_next_eq, assert_pending};
+
+#[test]
+fn changes_before_anchor() {
+    let mut ob = ObservableVector::<char>::from(vector!['a', 'b', 'c', 'd', 'e']);
+    let (values, mut sub) =
+        ob.subscribe().anchored_window(|c| *c, AnchorSpec::new('d', 1, 3), stream::pending());
+    assert_eq!(values, vector!['c', 'd', 'e']);
+    assert_eq!(sub.spec(), WindowSpec::new(2, 3));
+
+    // Changes before the window only move it.
+    ob.push_front('y');
+    ob.insert(1, 'z');
+    assert_pending!(sub);
+    assert_eq!(sub.spec(), WindowSpec::new(4, 3));
+    ob.remove(0);
+    ob.pop_front();
+    ob.move_item(0, 1);
+    assert_pending!(sub);
+    assert_eq!(sub.spec(), WindowSpec::new(2, 3));
+    assert_eq!(sub.anchor_index(), Some(3));
+
+    // An item inserted before the anchor within the window pushes the first
+    // item out of the window.
+    ob.insert(3, 'x');
+    assert_next_eq!(sub, VectorDiff::PopBack);
+    assert_next_eq!(sub, VectorDiff::Insert { index: 1, value: 'x' });
+    assert_next_eq!(sub, VectorDiff::PopFront);
+    assert_next_eq!(sub, VectorDiff::Append { values: vector!['e'] });
+    assert_pending!(sub);
+    assert_eq!(sub.spec(), WindowSpec::new(3, 3));
+
+    // Changes after the anchor are forwarded as usual.
+    ob.set(5, 'w');
+    assert_next_eq!(sub, VectorDiff::Set { index: 2, value: 'w' });
+    assert_pending!(sub);
+}
+
+#[test]
+fn anchor_near_start() {
+    let mut ob = ObservableVector::<char>::from(vector!['a', 'b', 'c', 'd']);
+    let (values, mut sub) =
+        ob.subscribe().anchored_window(|c| *c, AnchorSpec::centered('a', 3), stream::pending());
+    assert_eq!(values, vector!['a', 'b', 'c']);
+
+    // Once there are enough items before the anchor, the window keeps it at
+    // its position.
+    ob.push_front('z');
+    assert_next_eq!(sub, VectorDiff::PopBack);
+    assert_next_eq!(sub, VectorDiff::Insert { index: 0, value: 'z' });
+    assert_pending!(sub);
+    ob.push_front('y');
+    assert_pending!(sub);
+    assert_eq!(sub.spec(), WindowSpec::new(1, 3));
+}
+
+#[test]
+fn anchor_removed() {
+    let mut ob = ObservableVector::<char>::from(vector!['a', 'b', 'c', 'd', 'e']);
+    let (values, mut sub) =
+        ob.subscribe().anchored_window(|c| *c, AnchorSpec::centered('c', 3), stream::pending());
+    assert_eq!(values, vector!['b', 'c', 'd']);
+
+    // Without an anchor, the window stays at its offset.
+    ob.remove(2);
+    assert_next_eq!(sub, VectorDiff::Remove { index: 1 });
+    assert_next_eq!(sub, VectorDiff::PushBack { value: 'e' });
+    assert_eq!(sub.anchor_index(), None);
+    ob.push_front('z');
+    assert_next_eq!(sub, VectorDiff::PopBack);
+    assert_next_eq!(sub, VectorDiff::PushFront { value: 'a' });
+    assert_pending!(sub);
+
+    ob.clear();
+    ob.append(vector!['a', 'b', 'c']);
+    assert_next_eq!(sub, VectorDiff::Clear);
+    assert_next_eq!(sub, VectorDiff::Append { values: vector!['b', 'c'] });
+    assert_pending!(sub);
+}
+
+#[test]
+fn reset() {
+    let diffs = stream::iter([VectorDiff::Reset {
+        values: vector!['x', 'y', 'z', 'c'],
+        reason: ResetReason::Update,
+    }]);
+    let (values, mut sub) = AnchoredWindow::new(
+        vector!['a', 'b'],
+        diffs,
+        |c| *c,
+        AnchorSpec::new('c', 1, 2),
+        stream::pending(),
+    );
+    assert_eq!(values, vector!['a', 'b']);
+    assert_eq!(sub.anchor_index(), None);
+
+    // A reset looks for the anchor again.
+    assert_next_eq!(
+        sub,
+        VectorDiff::Reset { values: vector!['z', 'c'], reason: ResetReason::Update }
+    );
+    assert_eq!(sub.anchor_index(), Some(3));
+}
+
+#[test]
+fn anchor_changes() {
+    let ob = ObservableVector::<char>::from(vector!['a', 'b', 'c', 'd', 'e', 'f']);
+    let anchor = SharedObservable::new(AnchorSpec::new('b', 0, 2));
+    let (values, mut sub) =
+        ob.subscribe().anchored_window(|c| *c, anchor.get(), anchor.subscribe());
+    assert_eq!(values, vector!['b', 'c']);
+
+    anchor.set(AnchorSpec::new('c', 0, 2));
+    assert_next_eq!(sub, VectorDiff::PopFront);
+    assert_next_eq!(sub, VectorDiff::Append { values: vector!['d'] });
+    assert_pending!(sub);
+
+    anchor.set(AnchorSpec::new('f', 1, 3));
+    assert_next_eq!(sub, VectorDiff::Clear);
+    assert_next_eq!(sub, VectorDiff::Append { values: vector!['e', 'f'] });
+    assert_pending!(sub);
+
+    // An unknown anchor only changes the length of the window.
+    anchor.set(AnchorSpec::new('q', 0, 1));
+    assert_next_eq!(sub, VectorDiff::Truncate { length: 1 });
+    assert_pending!(sub);
+    assert_eq!(sub.spec(), WindowSpec::new(4, 1));
+}
+
+#[test]
+fn batched() {
+    let mut ob = ObservableVector::<char>::from(vector!['a', 'b', 'c', 'd']);
+    let (values, mut sub) = ob.subscribe().batched().anchored_window(
+        |c| *c,
+        AnchorSpec::new('c', 1, 2),
+        stream::pending(),
+    );
+    assert_eq!(values, vector!['b', 'c']);
+
+    let mut txn = ob.transaction();
+    txn.push_front('z');
+    txn.push_back('e');
+    txn.set(2, 'x');
+    txn.commit();
+    assert_next_eq!(sub, vec![VectorDiff::Set { index: 0, value: 'x' }]);
+    assert_pending!(sub);
+}
+
+#[test]
+fn consistency() {
+    let mut rng = crate::Rng::new(0x2545_f491);
+
+    let mut ob = ObservableVector::from((0..8).collect::<Vector<u32>>());
+    let anchor = SharedObservable::new(AnchorSpec::new(4, 2, 4));
+    let (mut values, mut sub) =
+        ob.subscribe().anchored_window(|v| *v, anchor.get(), anchor.subscribe());
+
+    for step in 0..2000 {
+        let len = ob.len();
+        if len > 0 && rng.below(12) == 0 {
+            anchor.set(AnchorSpec::new(ob[rng.below(len)], rng.below(6), rng.below(8)));
+        } else {
+            crate::random_update(&mut ob, &mut rng, |rng| rng.below(1000) as u32);
+        }
+        crate::apply_ready(&mut sub, &mut values);
+
+        let spec = sub.spec();
+        let anchor = anchor.get();
+        if let Some(index) = sub.anchor_index() {
+            assert_eq!(ob[index], anchor.key, "after step {step}");
+            assert_eq!(spec.offset, index.saturating_sub(anchor.position), "after step {step}");
+        }
+        assert_eq!(spec.len, anchor.len);
+
+        let expected: Vector<u32> = ob.iter().skip(spec.offset).take(spec.len).copied().collect();
+        assert_eq!(values, expected, "after step {step}");
+    }
+}
@@ -0,0 +1,107 @@
+use std::time::Duration;
+
+use eyeball_im::{ObservableVector, VectorDiff};
+use eyeball_im_util::vector::VectorObserverExt;
+use futures_util::{FutureExt, StreamExt};
+use imbl::vector;
+use tokio::time::advance;
+
+#[tokio::test(start_paused = true)]
+async fn timeout() {
+    let mut ob = ObservableVector::<u32>::from(vector![1]);
+    let (values, mut sub) = ob.subscribe().batch_timeout(10, Duration::from_millis(100));
+    assert_eq!(values, vector![1]);
+    assert!(sub.next().now_or_never().is_none());
+
+    // Diffs are buffered until the timeout has passed since the first one
+    ob.push_back(2);
+    assert!(sub.next().now_or_never().is_none());
+    advance(Duration::from_millis(50)).await;
+    ob.set(0, 10);
+    assert!(sub.next().now_or_never().is_none());
+
+    advance(Duration::from_millis(50)).await;
+    assert_eq!(
+        sub.next().now_or_never(),
+        Some(Some(vec![
+            VectorDiff::PushBack { value: 2 },
+            VectorDiff::Set { index: 0, value: 10 },
+        ]))
+    );
+
+    // The timer only starts again with the next diff
+    advance(Duration::from_millis(200)).await;
+    assert!(sub.next().now_or_never().is_none());
+    ob.remove(1);
+    assert!(sub.next().now_or_never().is_none());
+    advance(Duration::from_millis(100)).await;
+    assert_eq!(sub.next().now_or_never(), Some(Some(vec![VectorDiff::Remove { index: 1 }])));
+}
+
+#[tokio::test(start_paused = true)]
+async fn max_len() {
+    let mut ob = ObservableVector::<u32>::new();
+    let (_, mut sub) = ob.subscribe().batch_timeout(2, Duration::from_millis(100));
+
+    // Full batches are emitted right away
+    ob.push_back(1);
+    ob.push_back(2);
+    ob.push_back(3);
+    assert_eq!(
+        sub.next().now_or_never(),
+        Some(Some(vec![VectorDiff::PushBack { value: 1 }, VectorDiff::PushBack { value: 2 }]))
+    );
+    assert!(sub.next().now_or_never().is_none());
+
+    // The rest keeps the deadline of the diffs before it
+    advance(Duration::from_millis(60)).await;
+    ob.push_back(4);
+    ob.push_back(5);
+    assert_eq!(
+        sub.next().now_or_never(),
+        Some(Some(vec![VectorDiff::PushBack { value: 3 }, VectorDiff::PushBack { value: 4 }]))
+    );
+    assert!(sub.next().now_or_never().is_none());
+    advance(Duration::from_millis(40)).await;
+    assert_eq!(sub.next().now_or_never(), Some(Some(vec![VectorDiff::PushBack { value: 5 }])));
+}
+
+#[tokio::test(start_paused = true)]
+async fn batched() {
+    let mut ob = ObservableVector::<u32>::new();
+    let (_, mut sub) = ob.subscribe().batch_timeout(2, Duration::from_millis(100));
+
+    // Larger batches from the underlying stream are split up
+    let mut txn = ob.transaction();
+    txn.push_back(1);
+    txn.push_back(2);
+    txn.push_back(3);
+    txn.commit();
+    assert_eq!(
+        sub.next().now_or_never(),
+        Some(Some(vec![VectorDiff::PushBack { value: 1 }, VectorDiff::PushBack { value: 2 }]))
+    );
+    assert!(sub.next().now_or_never().is_none());
+    advance(Duration::from_millis(100)).await;
+    assert_eq!(sub.next().now_or_never(), Some(Some(vec![VectorDiff::PushBack { value: 3 }])));
+}
+
+#[tokio::test(start_paused = true)]
+async fn flush_on_end() {
+    let mut ob = ObservableVector::<u32>::new();
+    let (_, mut sub) = ob.subscribe().batch_timeout(10, Duration::from_millis(100));
+
+    ob.push_back(1);
+    assert!(sub.next().now_or_never().is_none());
+
+    drop(ob);
+    assert_eq!(sub.next().now_or_never(), Some(Some(vec![VectorDiff::PushBack { value: 1 }])));
+    assert_eq!(sub.next().now_or_never(), Some(None));
+}
+
+#[test]
+#[should_panic = "max_len must not be 0"]
+fn zero_max_len() {
+    let ob = ObservableVector::<u32>::new();
+    let _ = ob.subscribe().batch_timeout(0, Duration::from_millis(100));
+}
@@ -0,0 +1,82 @@
+use eyeball_im::{ObservableVector, VectorDiff};
+use eyeball_im_util::vector::{VectorObserverExt, VectorSubscriberExt};
+use imbl::vector;
+use stream_assert::{assert_closed, assert_next_eq, assert_pending};
+
+#[test]
+fn offsets() {
+    let mut first = ObservableVector::<char>::from(vector!['a', 'b']);
+    let mut second = ObservableVector::<char>::from(vector!['x']);
+    let (values, mut sub) = first.subscribe().chain(second.subscribe());
+    assert_eq!(values, vector!['a', 'b', 'x']);
+
+    second.push_front('w');
+    assert_next_eq!(sub, VectorDiff::Insert { index: 2, value: 'w' });
+    second.push_back('y');
+    assert_next_eq!(sub, VectorDiff::PushBack { value: 'y' });
+
+    // Changes of the first vector shift the second one
+    first.remove(0);
+    assert_next_eq!(sub, VectorDiff::Remove { index: 0 });
+    second.set(0, 'v');
+    assert_next_eq!(sub, VectorDiff::Set { index: 1, value: 'v' });
+
+    first.append(vector!['c', 'd']);
+    assert_next_eq!(sub, VectorDiff::Insert { index: 1, value: 'c' });
+    assert_next_eq!(sub, VectorDiff::Insert { index: 2, value: 'd' });
+    second.remove(2);
+    assert_next_eq!(sub, VectorDiff::Remove { index: 5 });
+    assert_pending!(sub);
+}
+
+#[test]
+fn clear_and_reset() {
+    let mut first = ObservableVector::<u32>::from(vector![1, 2]);
+    let mut second = ObservableVector::<u32>::from(vector![3]);
+    let (_, mut sub) = first.subscribe().chain(second.subscribe());
+
+    second.clear();
+    assert_next_eq!(sub, VectorDiff::Truncate { length: 2 });
+
+    // The first vector is at the end now
+    first.clear();
+    assert_next_eq!(sub, VectorDiff::Clear);
+    first.append(vector![4, 5]);
+    assert_next_eq!(sub, VectorDiff::Append { values: vector![4, 5] });
+    assert_pending!(sub);
+}
+
+#[test]
+fn batched() {
+    let mut first = ObservableVector::<u32>::new();
+    let mut second = ObservableVector::<u32>::from(vector![10]);
+    let (_, mut sub) = first.subscribe().batched().chain(second.subscribe().batched());
+
+    let mut txn = first.transaction();
+    txn.push_back(1);
+    txn.push_back(2);
+    txn.commit();
+    assert_next_eq!(
+        sub,
+        vec![VectorDiff::Insert { index: 0, value: 1 }, VectorDiff::Insert { index: 1, value: 2 }]
+    );
+
+    second.push_back(11);
+    assert_next_eq!(sub, vec![VectorDiff::PushBack { value: 11 }]);
+    assert_pending!(sub);
+}
+
+#[test]
+fn ends_after_both() {
+    let first = ObservableVector::<u32>::new();
+    let mut second = ObservableVector::<u32>::new();
+    let (_, mut sub) = first.subscribe().chain(second.subscribe());
+
+    drop(first);
+    assert_pending!(sub);
+    second.push_back(1);
+    assert_next_eq!(sub, VectorDiff::PushBack { value: 1 });
+
+    drop(second);
+    assert_closed!(sub);
+}
@@ -0,0 +1,90 @@
+use eyeball::Observable;
+use eyeball_im::{ObservableVector, VectorDiff};
+use eyeball_im_util::vector::{VectorObserverExt, VectorSubscriberExt};
+use futures_util::stream;
+use imbl::vector;
+use stream_assert::{assert_closed, assert_next_eq, assert_pending};
+
+fn push_backs(values: impl IntoIterator<Item = u32>) -> Vec<VectorDiff<u32>> {
+    values.into_iter().map(|value| VectorDiff::PushBack { value }).collect()
+}
+
+#[test]
+fn full_batches() {
+    let mut ob = ObservableVector::<u32>::new();
+    let tick = Observable::new(());
+    let (values, mut sub) = ob.subscribe().batched().chunk_timeout(2, Observable::subscribe(&tick));
+    assert!(values.is_empty());
+    assert_pending!(sub);
+
+    // A large batch from the inner stream is split up.
+    let mut txn = ob.transaction();
+    for i in 0..5 {
+        txn.push_back(i);
+    }
+    txn.commit();
+    assert_next_eq!(sub, push_backs([0, 1]));
+    assert_next_eq!(sub, push_backs([2, 3]));
+    assert_pending!(sub);
+
+    ob.push_back(5);
+    assert_next_eq!(sub, push_backs([4, 5]));
+    assert_pending!(sub);
+}
+
+#[test]
+fn quiescence() {
+    let mut ob = ObservableVector::<u32>::from(vector![1]);
+    let mut tick = Observable::new(());
+    let (values, mut sub) = ob.subscribe().chunk_timeout(10, Observable::subscribe(&tick));
+    assert_eq!(values, vector![1]);
+
+    // Ticks without buffered diffs don't do anything.
+    Observable::set(&mut tick, ());
+    assert_pending!(sub);
+
+    ob.push_back(2);
+    Observable::set(&mut tick, ());
+    assert_pending!(sub);
+
+    // Diffs keep arriving, so the batch keeps growing.
+    ob.set(0, 0);
+    Observable::set(&mut tick, ());
+    assert_pending!(sub);
+
+    Observable::set(&mut tick, ());
+    assert_next_eq!(
+        sub,
+        vec![VectorDiff::PushBack { value: 2 }, VectorDiff::Set { index: 0, value: 0 }]
+    );
+    assert_pending!(sub);
+
+    Observable::set(&mut tick, ());
+    assert_pending!(sub);
+}
+
+#[test]
+fn inner_end() {
+    let mut ob = ObservableVector::<u32>::new();
+    let tick = Observable::new(());
+    let (_, mut sub) = ob.subscribe().chunk_timeout(10, Observable::subscribe(&tick));
+
+    ob.push_back(1);
+    ob.pop_back();
+    assert_pending!(sub);
+
+    drop(ob);
+    assert_next_eq!(sub, vec![VectorDiff::PushBack { value: 1 }, VectorDiff::PopBack]);
+    assert_closed!(sub);
+}
+
+#[test]
+fn tick_end() {
+    let mut ob = ObservableVector::<u32>::new();
+    let (_, mut sub) = ob.subscribe().chunk_timeout(10, stream::empty::<()>());
+
+    ob.push_back(1);
+    ob.push_back(2);
+    assert_next_eq!(sub, push_backs([1, 2]));
+    assert_pending!(sub);
+}
@@ -0,0 +1,113 @@
+use eyeball_im::{ObservableVector, VectorDiff};
+use eyeball_im_util::vector::{VectorObserverExt, VectorSubscriberExt};
+use imbl::{vector, Vector};
+use stream_assert::{assert_closed, assert_next_eq, assert_pending};
+
+#[test]
+fn insert_and_remove() {
+    let mut ob = ObservableVector::<char>::from(vector!['a', 'b', 'b', 'c']);
+    let (values, mut sub) = ob.subscribe().dedup();
+    assert_eq!(values, vector!['a', 'b', 'c']);
+
+    // Inserting a different value into a run splits it
+    ob.insert(2, 'x');
+    assert_next_eq!(sub, VectorDiff::Insert { index: 2, value: 'x' });
+    assert_next_eq!(sub, VectorDiff::Insert { index: 3, value: 'b' });
+    assert_pending!(sub);
+
+    // Removing it again merges the runs
+    ob.remove(2);
+    assert_next_eq!(sub, VectorDiff::Remove { index: 2 });
+    assert_next_eq!(sub, VectorDiff::Remove { index: 2 });
+    assert_pending!(sub);
+
+    // Inserting an equal value before the first of a run replaces it
+    ob.insert(1, 'b');
+    assert_next_eq!(sub, VectorDiff::Set { index: 1, value: 'b' });
+
+    // Removing a duplicate doesn't change anything
+    ob.remove(2);
+    assert_pending!(sub);
+
+    // Removing the first of a run shows the next one instead
+    ob.remove(1);
+    assert_next_eq!(sub, VectorDiff::Set { index: 1, value: 'b' });
+    ob.remove(1);
+    assert_next_eq!(sub, VectorDiff::Remove { index: 1 });
+    assert_pending!(sub);
+
+    drop(ob);
+    assert_closed!(sub);
+}
+
+#[test]
+fn set() {
+    let mut ob = ObservableVector::<u32>::from(vector![10, 20, 30]);
+    let (_, mut sub) = ob.subscribe().dedup_by_key(|v| v / 10);
+
+    // Becoming equal to the previous value hides the value
+    ob.set(1, 11);
+    assert_next_eq!(sub, VectorDiff::Remove { index: 1 });
+
+    // Becoming equal to the next value hides that one instead
+    ob.set(1, 31);
+    assert_next_eq!(sub, VectorDiff::Set { index: 1, value: 31 });
+    assert_pending!(sub);
+}
+
+#[test]
+fn append_and_truncate() {
+    let mut ob = ObservableVector::<u32>::from(vector![1]);
+    let (_, mut sub) = ob.subscribe().dedup();
+
+    ob.append(vector![1, 2, 2, 3]);
+    assert_next_eq!(sub, VectorDiff::Append { values: vector![2, 3] });
+
+    ob.append(vector![3]);
+    assert_pending!(sub);
+
+    ob.truncate(4);
+    assert_next_eq!(sub, VectorDiff::Truncate { length: 2 });
+    ob.truncate(3);
+    assert_pending!(sub);
+}
+
+#[test]
+fn batched() {
+    let mut ob = ObservableVector::<u32>::from(vector![1, 1]);
+    let (_, mut sub) = ob.subscribe().batched().dedup();
+
+    let mut txn = ob.transaction();
+    txn.insert(1, 2);
+    txn.push_back(1);
+    txn.commit();
+    assert_next_eq!(
+        sub,
+        vec![VectorDiff::Insert { index: 1, value: 2 }, VectorDiff::Insert { index: 2, value: 1 },]
+    );
+    assert_pending!(sub);
+}
+
+/// Apply random updates to the vector, and check that the emitted diffs always
+/// result in the expected deduplicated view.
+#[test]
+fn consistency() {
+    let mut rng = crate::Rng::new(0x2545_f491);
+
+    let key = |v: &u32| v % 3;
+    let mut ob = ObservableVector::from((0..8).collect::<Vector<u32>>());
+    let (mut values, mut sub) = ob.subscribe().dedup_by_key(key);
+
+    for step in 0..2000 {
+        crate::random_update(&mut ob, &mut rng, |rng| rng.below(1000) as u32);
+        crate::apply_ready(&mut sub, &mut values);
+
+        let mut expected = Vector::new();
+        for (idx, v) in ob.iter().enumerate() {
+            if idx == 0 || key(&ob[idx - 1]) != key(v) {
+                expected.push_back(*v);
+            }
+        }
+        assert_eq!(values, expected, "after step {step}");
+    }
+}
@@ -0,0 +1,131 @@
+use eyeball::Observable;
+use eyeball_im::{ObservableVector, VectorDiff};
+use eyeball_im_util::vector::{VectorObserverExt, VectorSubscriberExt};
+use futures_util::{stream, StreamExt};
+use imbl::{vector, Vector};
+use stream_assert::{assert_closed, assert_next_eq, assert_pending};
+
+fn multiple_of(modulus: u32) -> impl Fn(&u32) -> bool {
+    move |value| value % modulus == 0
+}
+
+#[test]
+fn no_initial_filter() {
+    let mut ob = ObservableVector::<u32>::from(vector![1, 2, 3, 4]);
+    let mut modulus = Observable::new(2);
+    let mut sub = ob
+        .subscribe()
+        .dynamic_filter(Observable::subscribe_reset(&modulus).map(|&modulus| multiple_of(modulus)));
+
+    assert_next_eq!(sub, VectorDiff::Append { values: vector![2, 4] });
+    assert_pending!(sub);
+
+    ob.push_back(6);
+    ob.push_back(7);
+    assert_next_eq!(sub, VectorDiff::PushBack { value: 6 });
+    assert_pending!(sub);
+
+    Observable::set(&mut modulus, 3);
+    assert_next_eq!(sub, VectorDiff::Remove { index: 1 });
+    assert_next_eq!(sub, VectorDiff::Remove { index: 0 });
+    assert_next_eq!(sub, VectorDiff::Insert { index: 0, value: 3 });
+    assert_pending!(sub);
+
+    drop(ob);
+    assert_closed!(sub);
+}
+
+#[test]
+fn latest_filter_only() {
+    let ob = ObservableVector::<u32>::from(vector![1, 2, 3, 4, 6]);
+    let (values, mut sub) = ob.subscribe().dynamic_filter_with_initial_value(
+        multiple_of(2),
+        stream::iter([3, 1, 2]).map(multiple_of),
+    );
+
+    assert_eq!(values, vector![2, 4, 6]);
+
+    // The filters are all ready at once and the last one is the same as the
+    // initial one, so nothing changes.
+    assert_pending!(sub);
+}
+
+#[test]
+fn clear_and_append() {
+    let ob = ObservableVector::<u32>::from(vector![2, 3, 4]);
+    let mut modulus = Observable::new(2);
+    let (values, mut sub) = ob.subscribe().dynamic_filter_with_initial_value(
+        multiple_of(2),
+        Observable::subscribe(&modulus).map(|&modulus| multiple_of(modulus)),
+    );
+    assert_eq!(values, vector![2, 4]);
+
+    Observable::set(&mut modulus, 3);
+    assert_next_eq!(sub, VectorDiff::Clear);
+    assert_next_eq!(sub, VectorDiff::Append { values: vector![3] });
+
+    Observable::set(&mut modulus, 5);
+    assert_next_eq!(sub, VectorDiff::Clear);
+    assert_pending!(sub);
+
+    Observable::set(&mut modulus, 1);
+    assert_next_eq!(sub, VectorDiff::Append { values: vector![2, 3, 4] });
+    assert_pending!(sub);
+}
+
+#[test]
+fn batched() {
+    let mut ob = ObservableVector::<u32>::from(vector![1, 2, 3, 4]);
+    let mut modulus = Observable::new(2);
+    let (values, mut sub) = ob.subscribe().batched().dynamic_filter_with_initial_value(
+        multiple_of(2),
+        Observable::subscribe(&modulus).map(|&modulus| multiple_of(modulus)),
+    );
+    assert_eq!(values, vector![2, 4]);
+
+    Observable::set(&mut modulus, 4);
+    assert_next_eq!(sub, vec![VectorDiff::Remove { index: 0 }]);
+
+    let mut txn = ob.transaction();
+    txn.push_front(8);
+    txn.set(3, 5);
+    txn.commit();
+    assert_next_eq!(sub, vec![VectorDiff::PushFront { value: 8 }]);
+    assert_pending!(sub);
+
+    Observable::set(&mut modulus, 1);
+    assert_next_eq!(
+        sub,
+        vec![
+            VectorDiff::Insert { index: 1, value: 1 },
+            VectorDiff::Insert { index: 2, value: 2 },
+            VectorDiff::Insert { index: 3, value: 5 },
+        ]
+    );
+    assert_pending!(sub);
+}
+
+#[test]
+fn consistency() {
+    let mut rng = crate::Rng::new(0x2545_f491_4f6c_dd1d);
+
+    let mut ob = ObservableVector::<u32>::new();
+    let mut modulus = Observable::new(1);
+    let (mut view, mut sub) = ob.subscribe().dynamic_filter_with_initial_value(
+        multiple_of(1),
+        Observable::subscribe(&modulus).map(|&modulus| multiple_of(modulus)),
+    );
+
+    for step in 0..2000 {
+        if rng.below(4) == 0 {
+            Observable::set(&mut modulus, rng.below(4) as u32 + 1);
+        } else {
+            crate::random_update(&mut ob, &mut rng, |rng| rng.below(20) as u32);
+        }
+        crate::apply_ready(&mut sub, &mut view);
+
+        let modulus = *modulus;
+        let expected: Vector<_> = ob.iter().copied().filter(|v| v % modulus == 0).collect();
+        assert_eq!(view, expected, "after step {step}");
+    }
+}
@@ -0,0 +1,153 @@
+use std::cmp::Ordering;
+
+use eyeball::Observable;
+use eyeball_im::{ObservableVector, ResetReason, VectorDiff};
+use eyeball_im_util::vector::{VectorObserverExt, VectorSubscriberExt};
+use futures_util::{stream, StreamExt};
+use imbl::{vector, Vector};
+use stream_assert::{assert_closed, assert_next_eq, assert_pending};
+
+/// Sort by `value % modulus` first, then by value.
+fn by_remainder(modulus: u32) -> impl Fn(&u32, &u32) -> Ordering {
+    move |a, b| (a % modulus, a).cmp(&(b % modulus, b))
+}
+
+/// Sort in ascending or descending order.
+fn in_order(descending: bool) -> impl Fn(&u32, &u32) -> Ordering {
+    move |a, b| if descending { b.cmp(a) } else { a.cmp(b) }
+}
+
+#[test]
+fn change_compare() {
+    let mut ob = ObservableVector::<u32>::from(vector![1, 2, 3, 4, 5, 6]);
+    let mut modulus = Observable::new(1);
+    let (values, mut sub) = ob.subscribe().dynamic_sort_by(
+        by_remainder(1),
+        Observable::subscribe(&modulus).map(|&modulus| by_remainder(modulus)),
+    );
+    assert_eq!(values, vector![1, 2, 3, 4, 5, 6]);
+
+    // [2, 4, 6, 1, 3, 5]: the even values stay, the odd ones are moved
+    // behind them.
+    Observable::set(&mut modulus, 2);
+    assert_next_eq!(sub, VectorDiff::Move { from: 0, to: 5 });
+    assert_next_eq!(sub, VectorDiff::Move { from: 1, to: 5 });
+    assert_next_eq!(sub, VectorDiff::Move { from: 2, to: 5 });
+    assert_pending!(sub);
+
+    // Updates are sorted with the new comparison function.
+    ob.push_back(7);
+    ob.push_front(8);
+    assert_next_eq!(sub, VectorDiff::PushBack { value: 7 });
+    assert_next_eq!(sub, VectorDiff::Insert { index: 3, value: 8 });
+    assert_pending!(sub);
+
+    drop(ob);
+    assert_closed!(sub);
+}
+
+#[test]
+fn already_sorted() {
+    let ob = ObservableVector::<u32>::from(vector![4, 2, 6]);
+    let mut modulus = Observable::new(1);
+    let (values, mut sub) = ob.subscribe().dynamic_sort_by(
+        by_remainder(1),
+        Observable::subscribe(&modulus).map(|&modulus| by_remainder(modulus)),
+    );
+    assert_eq!(values, vector![2, 4, 6]);
+
+    // All values have the same remainder, nothing moves.
+    Observable::set(&mut modulus, 2);
+    assert_pending!(sub);
+}
+
+#[test]
+fn reset_when_most_values_move() {
+    let mut ob = ObservableVector::<u32>::from(vector![1, 2, 3, 4]);
+    let mut descending = Observable::new(false);
+    let (values, mut sub) = ob.subscribe().dynamic_sort_by(
+        in_order(false),
+        Observable::subscribe(&descending).map(|&descending| in_order(descending)),
+    );
+    assert_eq!(values, vector![1, 2, 3, 4]);
+
+    // Reversing the order would take a move for all but one value.
+    Observable::set(&mut descending, true);
+    assert_next_eq!(
+        sub,
+        VectorDiff::Reset { values: vector![4, 3, 2, 1], reason: ResetReason::Update }
+    );
+    assert_pending!(sub);
+
+    // Updates are sorted into the reset values.
+    ob.push_back(0);
+    ob.push_back(5);
+    assert_next_eq!(sub, VectorDiff::PushBack { value: 0 });
+    assert_next_eq!(sub, VectorDiff::PushFront { value: 5 });
+    assert_pending!(sub);
+}
+
+#[test]
+fn latest_compare_only() {
+    let ob = ObservableVector::<u32>::from(vector![3, 1, 2]);
+    let (values, mut sub) =
+        ob.subscribe().dynamic_sort_by(by_remainder(1), stream::iter([2, 3, 1]).map(by_remainder));
+    assert_eq!(values, vector![1, 2, 3]);
+
+    // The comparison functions are all ready at once and the last one is the
+    // same as the initial one, so nothing changes.
+    assert_pending!(sub);
+}
+
+#[test]
+fn batched() {
+    let mut ob = ObservableVector::<u32>::from(vector![1, 2, 3, 4]);
+    let mut modulus = Observable::new(1);
+    let (values, mut sub) = ob.subscribe().batched().dynamic_sort_by(
+        by_remainder(1),
+        Observable::subscribe(&modulus).map(|&modulus| by_remainder(modulus)),
+    );
+    assert_eq!(values, vector![1, 2, 3, 4]);
+
+    Observable::set(&mut modulus, 2);
+    assert_next_eq!(
+        sub,
+        vec![VectorDiff::Move { from: 0, to: 3 }, VectorDiff::Move { from: 1, to: 3 }]
+    );
+    assert_pending!(sub);
+
+    let mut txn = ob.transaction();
+    txn.push_back(6);
+    txn.push_back(5);
+    txn.commit();
+    assert_next_eq!(
+        sub,
+        vec![VectorDiff::Insert { index: 2, value: 6 }, VectorDiff::PushBack { value: 5 }]
+    );
+    assert_pending!(sub);
+}
+
+#[test]
+fn consistency() {
+    let mut rng = crate::Rng::new(0x2545_f491_4f6c_dd1d);
+
+    let mut ob = ObservableVector::<u32>::new();
+    let mut modulus = Observable::new(1);
+    let (mut view, mut sub) = ob.subscribe().dynamic_sort_by(
+        by_remainder(1),
+        Observable::subscribe(&modulus).map(|&modulus| by_remainder(modulus)),
+    );
+
+    for step in 0..2000 {
+        if rng.below(4) == 0 {
+            Observable::set(&mut modulus, rng.below(4) as u32 + 1);
+        } else {
+            crate::random_update(&mut ob, &mut rng, |rng| rng.below(20) as u32);
+        }
+        crate::apply_ready(&mut sub, &mut view);
+
+        let mut expected: Vector<_> = ob.clone();
+        expected.sort_by(by_remainder(*modulus));
+        assert_eq!(view, expected, "after step {step}");
+    }
+}
@@ -0,0 +1,52 @@
+use eyeball_im::{ObservableVector, VectorDiff};
+use eyeball_im_util::vector::{VectorObserverExt, VectorSubscriberExt};
+use imbl::vector;
+use stream_assert::{assert_closed, assert_next_eq, assert_pending};
+
+#[test]
+fn transitions() {
+    let mut ob = ObservableVector::<u32>::from(vector![1]);
+    let (values, mut sub) = ob.subscribe().empty_transitions();
+    assert_eq!(values, vector![1]);
+    assert!(!sub.is_empty());
+
+    let mut is_empty = sub.subscribe_is_empty();
+    assert_pending!(is_empty);
+
+    ob.pop_back();
+    assert_next_eq!(sub, VectorDiff::PopBack);
+    assert_next_eq!(is_empty, true);
+
+    ob.append(vector![1, 2, 3]);
+    assert_next_eq!(sub, VectorDiff::Append { values: vector![1, 2, 3] });
+    assert_next_eq!(is_empty, false);
+
+    ob.truncate(1);
+    assert_next_eq!(sub, VectorDiff::Truncate { length: 1 });
+    assert_pending!(is_empty);
+
+    ob.clear();
+    assert_next_eq!(sub, VectorDiff::Clear);
+    assert_next_eq!(is_empty, true);
+
+    drop(ob);
+    assert_closed!(sub);
+    drop(sub);
+    assert_closed!(is_empty);
+}
+
+#[test]
+fn batched() {
+    let mut ob = ObservableVector::<u32>::new();
+    let (_, mut sub) = ob.subscribe().batched().empty_transitions();
+    let mut is_empty = sub.subscribe_is_empty();
+
+    // Intermediate states within a batch don't count.
+    let mut txn = ob.transaction();
+    txn.push_back(1);
+    txn.pop_back();
+    txn.commit();
+    assert_next_eq!(sub, vec![VectorDiff::PushBack { value: 1 }, VectorDiff::PopBack]);
+    assert_pending!(is_empty);
+    assert!(sub.is_empty());
+}
@@ -0,0 +1,112 @@
+use eyeball_im::ObservableVector;
+use eyeball_im_util::vector::{VectorObserverExt, VectorSubscriberExt};
+use futures_util::FutureExt;
+use imbl::{vector, Vector};
+use stream_assert::{assert_next_eq, assert_pending};
+
+#[test]
+fn min() {
+    let mut ob = ObservableVector::<u32>::from(vector![3, 1, 2]);
+    let mut min = ob.subscribe().min_by_key(|&v| v);
+    let mut sub = min.subscribe();
+    assert_eq!(min.get(), Some(1));
+
+    // Diffs that don't affect the minimum don't notify subscribers.
+    ob.push_back(4);
+    ob.remove(0);
+    ob.set(1, 5);
+    assert_eq!((&mut min).now_or_never(), None);
+    assert_pending!(sub);
+
+    ob.insert(1, 0);
+    assert_eq!((&mut min).now_or_never(), None);
+    assert_next_eq!(sub, Some(0));
+
+    // [1, 0, 5, 4]
+    ob.remove(1);
+    assert_eq!((&mut min).now_or_never(), None);
+    assert_next_eq!(sub, Some(1));
+
+    ob.clear();
+    assert_eq!((&mut min).now_or_never(), None);
+    assert_next_eq!(sub, None);
+
+    ob.push_front(7);
+    assert_eq!((&mut min).now_or_never(), None);
+    assert_next_eq!(sub, Some(7));
+
+    drop(ob);
+    assert_eq!((&mut min).now_or_never(), Some(()));
+}
+
+#[test]
+fn max_first_of_equal() {
+    let mut ob = ObservableVector::<(u32, char)>::from(vector![(1, 'a'), (2, 'b'), (2, 'c')]);
+    let mut max = ob.subscribe().max_by_key(|&(k, _)| k);
+    let mut sub = max.subscribe();
+    assert_eq!(max.get(), Some((2, 'b')));
+
+    ob.insert(1, (2, 'd'));
+    assert_eq!((&mut max).now_or_never(), None);
+    assert_next_eq!(sub, Some((2, 'd')));
+
+    // [(1, 'a'), (2, 'd'), (2, 'b'), (2, 'c')]
+    ob.move_item(3, 0);
+    assert_eq!((&mut max).now_or_never(), None);
+    assert_next_eq!(sub, Some((2, 'c')));
+
+    // [(2, 'c'), (1, 'a'), (2, 'd'), (2, 'b')]
+    ob.set(0, (0, 'c'));
+    assert_eq!((&mut max).now_or_never(), None);
+    assert_next_eq!(sub, Some((2, 'd')));
+
+    // Replacing the maximum notifies subscribers even if the key is the same.
+    ob.set(2, (2, 'e'));
+    assert_eq!((&mut max).now_or_never(), None);
+    assert_next_eq!(sub, Some((2, 'e')));
+    assert_pending!(sub);
+}
+
+#[test]
+fn batched() {
+    let mut ob = ObservableVector::<u32>::from(vector![1, 2]);
+    let mut max = ob.subscribe().batched().max_by_key(|&v| v);
+    let mut sub = max.subscribe();
+
+    // The maximum is only searched for once the whole batch was handled.
+    let mut txn = ob.transaction();
+    txn.pop_back();
+    txn.push_back(0);
+    txn.truncate(1);
+    txn.commit();
+    assert_eq!((&mut max).now_or_never(), None);
+    assert_next_eq!(sub, Some(1));
+    assert_pending!(sub);
+}
+
+#[test]
+fn consistency() {
+    let mut rng = crate::Rng::new(0x2545_f491_4f6c_dd1d);
+
+    let mut ob = ObservableVector::<(u32, usize)>::new();
+    let mut min = ob.subscribe().min_by_key(|&(k, _)| k);
+    let mut max = ob.subscribe().batched().max_by_key(|&(k, _)| k);
+
+    for step in 0..2000 {
+        let mut txn = ob.transaction();
+        for _ in 0..=rng.below(2) {
+            crate::random_update(&mut txn, &mut rng, |rng| (rng.below(10) as u32, step));
+        }
+        txn.commit();
+
+        assert_eq!((&mut min).now_or_never(), None);
+        assert_eq!((&mut max).now_or_never(), None);
+
+        let values: Vector<_> = (*ob).clone();
+        let expected_min = values.iter().min_by_key(|(k, _)| *k).copied();
+        // `Iterator::max_by_key` returns the last of several equal values.
+        let expected_max = values.iter().rev().max_by_key(|(k, _)| *k).copied();
+        assert_eq!(min.get(), expected_min, "min after step {step}");
+        assert_eq!(max.get(), expected_max, "max after step {step}");
+    }
+}
@@ -1,4 +1,4 @@
-use eyeball_im::{ObservableVector, VectorDiff};
+use eyeball_im::{ObservableVector, ResetReason, VectorDiff};
 use eyeball_im_util::vector::VectorObserverExt;
 use imbl::vector;
 use stream_assert::{assert_closed, assert_next_eq, assert_pending};
@@ -303,7 +303,10 @@ fn reset() {
 
     ob.push_front(0);
     ob.append(vector![1000, 2, 3000, 4]);
-    assert_next_eq!(sub, VectorDiff::Reset { values: vector![0, 2, 4] });
+    assert_next_eq!(
+        sub,
+        VectorDiff::Reset { values: vector![0, 2, 4], reason: ResetReason::Lagged }
+    );
     ob.remove(2);
     assert_next_eq!(sub, VectorDiff::Remove { index: 1 });
     ob.remove(1);
@@ -311,7 +314,7 @@ fn reset() {
 
     ob.pop_front();
     ob.insert(2, 5);
-    assert_next_eq!(sub, VectorDiff::Reset { values: vector![4, 5] });
+    assert_next_eq!(sub, VectorDiff::Reset { values: vector![4, 5], reason: ResetReason::Lagged });
     ob.remove(2);
     assert_next_eq!(sub, VectorDiff::Remove { index: 1 });
     ob.remove(1);
@@ -319,3 +322,41 @@ fn reset() {
     ob.remove(0);
     assert_pending!(sub);
 }
+
+#[test]
+fn reset_no_match() {
+    let mut ob: ObservableVector<i32> = ObservableVector::with_capacity(1);
+    let (values, mut sub) = ob.subscribe().filter(|&i| i < 256);
+    assert!(values.is_empty());
+
+    ob.push_back(1);
+    assert_next_eq!(sub, VectorDiff::PushBack { value: 1 });
+
+    // The reset is forwarded even though no value matches, since the previous
+    // values have to be removed.
+    ob.clear();
+    ob.append(vector![1000, 2000]);
+    assert_next_eq!(sub, VectorDiff::Reset { values: vector![], reason: ResetReason::Lagged });
+    assert_pending!(sub);
+}
+
+#[test]
+fn move_item() {
+    let mut ob: ObservableVector<i32> = ObservableVector::from(vector![1, 2, 3, 4, 5, 6]);
+    let (items, mut sub) = ob.subscribe().filter(|&i| i % 2 == 0);
+    assert_eq!(items, vector![2, 4, 6]);
+
+    // [1, 3, 4, 5, 6, 2]
+    ob.move_item(1, 5);
+    assert_next_eq!(sub, VectorDiff::Move { from: 0, to: 2 });
+
+    // [3, 4, 1, 5, 6, 2]: no change to the filtered order.
+    ob.move_item(0, 2);
+    assert_pending!(sub);
+
+    // The filtered indices are still correct.
+    ob.remove(1);
+    assert_next_eq!(sub, VectorDiff::Remove { index: 0 });
+    ob.set(4, 8);
+    assert_next_eq!(sub, VectorDiff::Set { index: 1, value: 8 });
+}
@@ -0,0 +1,91 @@
+use eyeball::Observable;
+use eyeball_im::{ObservableVector, VectorDiff};
+use eyeball_im_util::vector::VectorObserverExt;
+use imbl::vector;
+use stream_assert::{assert_closed, assert_next_eq, assert_pending};
+
+fn is_even(v: &u32) -> bool {
+    v % 2 == 0
+}
+
+#[test]
+fn disabled_passes_through() {
+    let mut ob = ObservableVector::<u32>::from(vector![1, 2, 3]);
+    let enabled = Observable::new(false);
+    let (values, mut sub) =
+        ob.subscribe().filter_if(is_even, false, Observable::subscribe(&enabled));
+
+    assert_eq!(values, vector![1, 2, 3]);
+    assert_pending!(sub);
+
+    ob.push_back(5);
+    assert_next_eq!(sub, VectorDiff::PushBack { value: 5 });
+    ob.remove(0);
+    assert_next_eq!(sub, VectorDiff::Remove { index: 0 });
+    assert_pending!(sub);
+
+    drop(ob);
+    assert_closed!(sub);
+}
+
+#[test]
+fn enabled_filters() {
+    let mut ob = ObservableVector::<u32>::from(vector![1, 2, 3]);
+    let enabled = Observable::new(true);
+    let (values, mut sub) =
+        ob.subscribe().filter_if(is_even, true, Observable::subscribe(&enabled));
+
+    assert_eq!(values, vector![2]);
+    assert_pending!(sub);
+
+    ob.push_back(5);
+    assert_pending!(sub);
+    ob.push_back(6);
+    assert_next_eq!(sub, VectorDiff::PushBack { value: 6 });
+    ob.set(0, 0);
+    assert_next_eq!(sub, VectorDiff::Insert { index: 0, value: 0 });
+    assert_pending!(sub);
+}
+
+#[test]
+fn toggle() {
+    let mut ob = ObservableVector::<u32>::from(vector![1, 2, 3, 4, 5]);
+    let mut enabled = Observable::new(false);
+    let (_, mut sub) = ob.subscribe().filter_if(is_even, false, Observable::subscribe(&enabled));
+
+    Observable::set(&mut enabled, true);
+    assert_next_eq!(sub, VectorDiff::Remove { index: 4 });
+    assert_next_eq!(sub, VectorDiff::Remove { index: 2 });
+    assert_next_eq!(sub, VectorDiff::Remove { index: 0 });
+    assert_pending!(sub);
+
+    // Changes while enabled are filtered, but still tracked.
+    ob.push_front(7);
+    assert_pending!(sub);
+
+    Observable::set(&mut enabled, false);
+    assert_next_eq!(sub, VectorDiff::Insert { index: 0, value: 7 });
+    assert_next_eq!(sub, VectorDiff::Insert { index: 1, value: 1 });
+    assert_next_eq!(sub, VectorDiff::Insert { index: 3, value: 3 });
+    assert_next_eq!(sub, VectorDiff::Insert { index: 5, value: 5 });
+    assert_pending!(sub);
+
+    // Setting the same state again does nothing.
+    Observable::set(&mut enabled, false);
+    assert_pending!(sub);
+}
+
+#[test]
+fn toggle_without_matches() {
+    let ob = ObservableVector::<u32>::from(vector![1, 3]);
+    let mut enabled = Observable::new(false);
+    let (_, mut sub) = ob.subscribe().filter_if(is_even, false, Observable::subscribe(&enabled));
+
+    Observable::set(&mut enabled, true);
+    assert_next_eq!(sub, VectorDiff::Clear);
+    assert_pending!(sub);
+
+    Observable::set(&mut enabled, false);
+    assert_next_eq!(sub, VectorDiff::Append { values: vector![1, 3] });
+    assert_pending!(sub);
+}
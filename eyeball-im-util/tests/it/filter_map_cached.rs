@@ -0,0 +1,112 @@
+use std::cell::Cell;
+
+use eyeball_im::{ObservableVector, VectorDiff};
+use eyeball_im_util::vector::{VectorObserverExt, VectorSubscriberExt};
+use imbl::{vector, Vector};
+use stream_assert::{assert_closed, assert_next_eq, assert_pending};
+
+#[test]
+fn state_follows_items() {
+    let calls = Cell::new(0);
+    let mut ob = ObservableVector::<u32>::from(vector![1, 2, 3]);
+    // Map each value to the number of times it was filter+mapped, leaving
+    // out even values.
+    let (values, mut sub) = ob.subscribe().filter_map_cached(|value, count: &mut u32| {
+        calls.set(calls.get() + 1);
+        *count += 1;
+        (value % 2 == 1).then_some((value, *count))
+    });
+    assert_eq!(values, vector![(1, 1), (3, 1)]);
+    assert_eq!(calls.get(), 3);
+
+    // Shifts don't call the function again.
+    ob.push_front(5);
+    assert_next_eq!(sub, VectorDiff::PushFront { value: (5, 1) });
+    ob.move_item(0, 3);
+    assert_next_eq!(sub, VectorDiff::Move { from: 0, to: 2 });
+    ob.remove(1);
+    assert_pending!(sub);
+    assert_eq!(calls.get(), 4);
+
+    // The state is kept when the value is replaced.
+    // [1, 3, 5]
+    ob.set(1, 7);
+    assert_next_eq!(sub, VectorDiff::Set { index: 1, value: (7, 2) });
+    ob.set(2, 9);
+    assert_next_eq!(sub, VectorDiff::Set { index: 2, value: (9, 2) });
+    ob.set(2, 4);
+    assert_next_eq!(sub, VectorDiff::Remove { index: 2 });
+    ob.set(2, 11);
+    assert_next_eq!(sub, VectorDiff::Insert { index: 2, value: (11, 4) });
+    assert_pending!(sub);
+
+    // New values get a new state.
+    ob.pop_back();
+    ob.push_back(13);
+    ob.insert(0, 15);
+    ob.append(vector![17, 18]);
+    assert_next_eq!(sub, VectorDiff::PopBack);
+    assert_next_eq!(sub, VectorDiff::PushBack { value: (13, 1) });
+    assert_next_eq!(sub, VectorDiff::Insert { index: 0, value: (15, 1) });
+    assert_next_eq!(sub, VectorDiff::Append { values: vector![(17, 1)] });
+    assert_pending!(sub);
+
+    drop(ob);
+    assert_closed!(sub);
+}
+
+#[test]
+fn reset() {
+    let mut ob = ObservableVector::<u32>::from(vector![1, 2]);
+    let (_, mut sub) = ob.subscribe().batched().filter_map_cached(|value, count: &mut u32| {
+        *count += 1;
+        Some((value, *count))
+    });
+
+    ob.set(0, 3);
+    assert_next_eq!(sub, vec![VectorDiff::Set { index: 0, value: (3, 2) }]);
+
+    // All states are dropped.
+    let mut txn = ob.transaction();
+    txn.clear();
+    txn.append(vector![3, 4]);
+    txn.commit();
+    assert_next_eq!(
+        sub,
+        vec![VectorDiff::Clear, VectorDiff::Append { values: vector![(3, 1), (4, 1)] }]
+    );
+    assert_pending!(sub);
+}
+
+#[test]
+fn consistency() {
+    let mut rng = crate::Rng::new(0x2545_f491);
+
+    // Each state remembers the value it was created for, which must be the
+    // value that was added at the position of the current one.
+    let f = |value: u32, origin: &mut Option<u32>| {
+        let origin = *origin.get_or_insert(value);
+        (value % 3 != 0).then_some((value, origin))
+    };
+    let mut ob = ObservableVector::from((0..8).collect::<Vector<u32>>());
+    let mut origins: Vector<u32> = (0..8).collect();
+    let (mut values, mut sub) = ob.subscribe().filter_map_cached(f);
+
+    for step in 0..2000 {
+        // Setting a value keeps its state, everything else changes the states
+        // the same way as the values.
+        let diff = crate::random_update(&mut ob, &mut rng, |rng| rng.below(1000) as u32);
+        if !matches!(diff, VectorDiff::Set { .. }) {
+            diff.apply(&mut origins);
+        }
+        crate::apply_ready(&mut sub, &mut values);
+
+        let expected: Vector<_> = ob
+            .iter()
+            .zip(&origins)
+            .filter(|(value, _)| *value % 3 != 0)
+            .map(|(value, origin)| (*value, *origin))
+            .collect();
+        assert_eq!(values, expected, "after step {step}");
+    }
+}
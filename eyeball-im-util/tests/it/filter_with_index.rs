@@ -0,0 +1,99 @@
+use eyeball_im::{ObservableVector, ResetReason, VectorDiff};
+use eyeball_im_util::vector::{VectorObserverExt, VectorSubscriberExt};
+use imbl::{vector, Vector};
+use stream_assert::{assert_closed, assert_next_eq, assert_pending};
+
+#[test]
+fn decimation() {
+    let mut ob = ObservableVector::<u32>::from((0..7).collect::<Vector<_>>());
+    let (values, mut sub) = ob.subscribe().filter_with_index(|index, _| index % 3 == 0);
+    assert_eq!(values, vector![0, 3, 6]);
+
+    // No shifts
+    ob.push_back(7);
+    ob.push_back(8);
+    assert_pending!(sub);
+    ob.append(vector![9, 10]);
+    assert_next_eq!(sub, VectorDiff::Append { values: vector![9] });
+    ob.set(3, 30);
+    assert_next_eq!(sub, VectorDiff::Set { index: 1, value: 30 });
+    ob.truncate(5);
+    assert_next_eq!(sub, VectorDiff::Truncate { length: 2 });
+    assert_pending!(sub);
+
+    // [0, 1, 2, 30, 4]
+    ob.push_front(100);
+    assert_next_eq!(sub, VectorDiff::Truncate { length: 0 });
+    assert_next_eq!(sub, VectorDiff::Append { values: vector![100, 2] });
+    assert_pending!(sub);
+
+    drop(ob);
+    assert_closed!(sub);
+}
+
+#[test]
+fn kept_items() {
+    let mut ob = ObservableVector::<u32>::from(vector![1, 2, 3, 4]);
+    let (values, mut sub) =
+        ob.subscribe().filter_with_index(|index, value| index < 3 || *value > 2);
+    assert_eq!(values, vector![1, 2, 3, 4]);
+
+    ob.push_front(0);
+    assert_next_eq!(sub, VectorDiff::Insert { index: 0, value: 0 });
+    assert_pending!(sub);
+    // The items that still match with their new index stay in place.
+    ob.insert(1, 1);
+    assert_next_eq!(sub, VectorDiff::Remove { index: 2 });
+    assert_next_eq!(sub, VectorDiff::Insert { index: 1, value: 1 });
+    assert_pending!(sub);
+
+    // [0, 1, 1, 2, 3, 4]
+    ob.remove(1);
+    assert_next_eq!(sub, VectorDiff::Remove { index: 1 });
+    assert_next_eq!(sub, VectorDiff::Insert { index: 2, value: 2 });
+    assert_pending!(sub);
+
+    // [0, 1, 2, 3, 4]
+    ob.move_item(4, 0);
+    assert_next_eq!(sub, VectorDiff::Remove { index: 4 });
+    assert_next_eq!(sub, VectorDiff::Remove { index: 2 });
+    assert_next_eq!(sub, VectorDiff::Insert { index: 0, value: 4 });
+    assert_pending!(sub);
+}
+
+#[test]
+fn batched() {
+    let mut ob = ObservableVector::<u32>::from(vector![5, 6, 7]);
+    let (_, mut sub) = ob.subscribe().batched().filter_with_index(|index, _| index != 1);
+
+    ob.remove(0);
+    assert_next_eq!(
+        sub,
+        vec![VectorDiff::Truncate { length: 0 }, VectorDiff::Append { values: vector![6] }]
+    );
+
+    ob.splice(.., vector![1, 2, 3]);
+    assert_next_eq!(
+        sub,
+        vec![VectorDiff::Reset { values: vector![1, 3], reason: ResetReason::Update }]
+    );
+    assert_pending!(sub);
+}
+
+#[test]
+fn consistency() {
+    let mut rng = crate::Rng::new(0x9e37_79b9);
+
+    let f = |index: usize, value: &u32| (index + *value as usize) % 3 != 0;
+    let mut ob = ObservableVector::from((0..8).collect::<Vector<u32>>());
+    let (mut values, mut sub) = ob.subscribe().filter_with_index(f);
+
+    for step in 0..2000 {
+        crate::random_update(&mut ob, &mut rng, |rng| rng.below(1000) as u32);
+        crate::apply_ready(&mut sub, &mut values);
+
+        let expected: Vector<_> =
+            ob.iter().enumerate().filter(|(idx, v)| f(*idx, v)).map(|(_, v)| *v).collect();
+        assert_eq!(values, expected, "after step {step}");
+    }
+}
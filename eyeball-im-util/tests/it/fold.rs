@@ -0,0 +1,86 @@
+use eyeball_im::{ObservableVector, VectorDiff};
+use eyeball_im_util::vector::{VectorObserverExt, VectorSubscriberExt};
+use futures_util::FutureExt;
+use imbl::vector;
+use stream_assert::{assert_next_eq, assert_pending};
+
+#[test]
+fn diffs() {
+    let mut ob = ObservableVector::<u32>::new();
+    let mut num_diffs = ob.subscribe().fold(0, |acc, _| acc + 1);
+    let mut sub = num_diffs.subscribe();
+    assert_eq!(num_diffs.get(), 0);
+
+    ob.push_back(1);
+    ob.set(0, 2);
+    assert_eq!((&mut num_diffs).now_or_never(), None);
+    assert_eq!(num_diffs.get(), 2);
+
+    assert_next_eq!(sub, 2);
+    assert_pending!(sub);
+
+    drop(ob);
+    assert_eq!((&mut num_diffs).now_or_never(), Some(()));
+}
+
+#[test]
+fn initial_values() {
+    let ob = ObservableVector::<u32>::from(vector![1, 2]);
+    let fold = ob.subscribe().fold(vec![], |mut acc, diff| {
+        acc.push(diff.clone());
+        acc
+    });
+    assert_eq!(fold.get(), vec![VectorDiff::Append { values: vector![1, 2] }]);
+}
+
+#[test]
+fn items() {
+    let mut ob = ObservableVector::<u32>::from(vector![1, 2, 3]);
+    let mut sum = ob.subscribe().fold_items(0, |acc, v| acc + v, |acc, v| acc - v);
+    let mut sub = sum.subscribe();
+    assert_eq!(sum.get(), 6);
+
+    ob.push_front(4);
+    ob.pop_back();
+    ob.insert(1, 5);
+    ob.remove(0);
+    ob.set(1, 10);
+    ob.move_item(0, 2);
+    assert_eq!((&mut sum).now_or_never(), None);
+    // [10, 2, 5]
+    assert_eq!(sum.get(), 17);
+
+    ob.truncate(1);
+    ob.append(vector![6, 7]);
+    ob.pop_front();
+    assert_eq!((&mut sum).now_or_never(), None);
+    assert_eq!(sum.get(), 13);
+
+    ob.splice(.., vector![8]);
+    assert_eq!((&mut sum).now_or_never(), None);
+    assert_eq!(sum.get(), 8);
+
+    ob.clear();
+    assert_eq!((&mut sum).now_or_never(), None);
+    assert_eq!(sum.get(), 0);
+    assert_next_eq!(sub, 0);
+    assert_pending!(sub);
+}
+
+#[test]
+fn items_batched() {
+    let mut ob = ObservableVector::<u32>::from(vector![1, 2, 3]);
+    let mut sum =
+        ob.subscribe().batched().head(2).fold_items(0, |acc, v| acc + v, |acc, v| acc - v);
+    let mut sub = sum.subscribe();
+    assert_eq!(sum.get(), 3);
+
+    let mut txn = ob.transaction();
+    txn.push_front(10);
+    txn.push_front(20);
+    txn.commit();
+    assert_eq!((&mut sum).now_or_never(), None);
+    assert_eq!(sum.get(), 30);
+    assert_next_eq!(sub, 30);
+    assert_pending!(sub);
+}
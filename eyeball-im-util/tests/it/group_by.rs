@@ -0,0 +1,116 @@
+use eyeball_im::{ObservableVector, VectorDiff};
+use eyeball_im_util::vector::{Grouped, VectorObserverExt, VectorSubscriberExt};
+use imbl::{vector, Vector};
+use stream_assert::{assert_closed, assert_next_eq, assert_pending};
+
+use Grouped::{Header, Item};
+
+#[test]
+fn split_and_merge() {
+    let mut ob = ObservableVector::<u32>::from(vector![10, 11, 12]);
+    let (values, mut sub) = ob.subscribe().group_by(|v| v / 10);
+    assert_eq!(values, vector![Header(1), Item(10), Item(11), Item(12)]);
+
+    // Inserting an item with a different key in the middle splits the group
+    ob.insert(1, 20);
+    assert_next_eq!(sub, VectorDiff::Insert { index: 2, value: Header(2) });
+    assert_next_eq!(sub, VectorDiff::Insert { index: 3, value: Item(20) });
+    assert_next_eq!(sub, VectorDiff::Insert { index: 4, value: Header(1) });
+    assert_pending!(sub);
+
+    // Removing it again merges the groups
+    ob.remove(1);
+    assert_next_eq!(sub, VectorDiff::Remove { index: 3 });
+    assert_next_eq!(sub, VectorDiff::Remove { index: 2 });
+    assert_next_eq!(sub, VectorDiff::Remove { index: 2 });
+    assert_pending!(sub);
+
+    // Prepending to an existing group keeps its header in place
+    ob.push_front(13);
+    assert_next_eq!(sub, VectorDiff::Insert { index: 1, value: Item(13) });
+
+    drop(ob);
+    assert_closed!(sub);
+}
+
+#[test]
+fn set() {
+    let mut ob = ObservableVector::<u32>::from(vector![10, 20]);
+    let (_, mut sub) = ob.subscribe().group_by(|v| v / 10);
+
+    // Same key
+    ob.set(1, 21);
+    assert_next_eq!(sub, VectorDiff::Set { index: 3, value: Item(21) });
+
+    // Different key, moving the item to the previous group
+    ob.set(1, 11);
+    assert_next_eq!(sub, VectorDiff::Remove { index: 3 });
+    assert_next_eq!(sub, VectorDiff::Remove { index: 2 });
+    assert_next_eq!(sub, VectorDiff::Insert { index: 2, value: Item(11) });
+    assert_pending!(sub);
+}
+
+#[test]
+fn append_and_truncate() {
+    let mut ob = ObservableVector::<u32>::from(vector![10]);
+    let (_, mut sub) = ob.subscribe().group_by(|v| v / 10);
+
+    ob.append(vector![11, 20, 21]);
+    assert_next_eq!(
+        sub,
+        VectorDiff::Append { values: vector![Item(11), Header(2), Item(20), Item(21)] }
+    );
+
+    ob.truncate(3);
+    assert_next_eq!(sub, VectorDiff::Truncate { length: 5 });
+    ob.truncate(2);
+    assert_next_eq!(sub, VectorDiff::Truncate { length: 3 });
+    ob.truncate(0);
+    assert_next_eq!(sub, VectorDiff::Clear);
+}
+
+#[test]
+fn batched() {
+    let mut ob = ObservableVector::<u32>::new();
+    let (_, mut sub) = ob.subscribe().batched().group_by(|v| v / 10);
+
+    let mut txn = ob.transaction();
+    txn.push_back(10);
+    txn.push_back(20);
+    txn.commit();
+    assert_next_eq!(
+        sub,
+        vec![
+            VectorDiff::Insert { index: 0, value: Header(1) },
+            VectorDiff::Insert { index: 1, value: Item(10) },
+            VectorDiff::Insert { index: 2, value: Header(2) },
+            VectorDiff::Insert { index: 3, value: Item(20) },
+        ]
+    );
+    assert_pending!(sub);
+}
+
+/// Apply random updates to the vector, and check that the emitted diffs always
+/// result in the expected sectioned view.
+#[test]
+fn consistency() {
+    let mut rng = crate::Rng::new(0x2545_f491);
+
+    let key = |v: &u32| v % 3;
+    let mut ob = ObservableVector::from((0..8).collect::<Vector<u32>>());
+    let (mut values, mut sub) = ob.subscribe().group_by(key);
+
+    for step in 0..2000 {
+        crate::random_update(&mut ob, &mut rng, |rng| rng.below(1000) as u32);
+        crate::apply_ready(&mut sub, &mut values);
+
+        let mut expected = Vector::new();
+        for (idx, v) in ob.iter().enumerate() {
+            if idx == 0 || key(&ob[idx - 1]) != key(v) {
+                expected.push_back(Header(key(v)));
+            }
+            expected.push_back(Item(*v));
+        }
+        assert_eq!(values, expected, "after step {step}");
+    }
+}
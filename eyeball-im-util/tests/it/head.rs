@@ -1,6 +1,6 @@
 use eyeball::Observable;
-use eyeball_im::{ObservableVector, VectorDiff};
-use eyeball_im_util::vector::VectorObserverExt;
+use eyeball_im::{ObservableVector, ResetReason, VectorDiff};
+use eyeball_im_util::vector::{Saturation, VectorObserverExt};
 use imbl::vector;
 use stream_assert::{assert_closed, assert_next_eq, assert_pending};
 
@@ -714,7 +714,10 @@ fn reset() {
     ob.insert(0, 14);
 
     // Observe a reset, capped to the limit.
-    assert_next_eq!(sub, VectorDiff::Reset { values: vector![14, 10, 11, 12] });
+    assert_next_eq!(
+        sub,
+        VectorDiff::Reset { values: vector![14, 10, 11, 12], reason: ResetReason::Lagged }
+    );
     assert_pending!(sub);
 
     // Check the content of the vector.
@@ -765,3 +768,55 @@ async fn limit_stream_wake_bug() {
     // It should be finished now.
     task_hdl.now_or_never().unwrap().unwrap();
 }
+
+#[test]
+fn saturation() {
+    let mut ob: ObservableVector<usize> = ObservableVector::from(vector![1]);
+    let mut limit = Observable::new(2);
+    let (values, mut sub) =
+        ob.subscribe().dynamic_head_with_initial_value(2, Observable::subscribe(&limit));
+    assert_eq!(values, vector![1]);
+    assert_eq!(sub.saturation(), Saturation { is_full: false, has_more: false });
+
+    let mut saturation = sub.subscribe_saturation();
+    assert_pending!(saturation);
+
+    ob.push_back(2);
+    assert_next_eq!(sub, VectorDiff::PushBack { value: 2 });
+    assert_next_eq!(saturation, Saturation { is_full: true, has_more: false });
+
+    // Items beyond the limit don't produce diffs, but update the saturation.
+    ob.push_back(3);
+    assert_pending!(sub);
+    assert_next_eq!(saturation, Saturation { is_full: true, has_more: true });
+
+    Observable::set(&mut limit, 4);
+    assert_next_eq!(sub, VectorDiff::Append { values: vector![3] });
+    assert_next_eq!(saturation, Saturation { is_full: false, has_more: false });
+    assert_pending!(saturation);
+}
+
+#[test]
+fn move_item() {
+    let mut ob = ObservableVector::<u32>::from(vector![1, 2, 3, 4]);
+    let (values, mut sub) = ob.subscribe().head(2);
+    assert_eq!(values, vector![1, 2]);
+
+    // [2, 1, 3, 4]
+    ob.move_item(0, 1);
+    assert_next_eq!(sub, VectorDiff::Move { from: 0, to: 1 });
+
+    // [1, 3, 4, 2]: 2 leaves the view, 3 enters it.
+    ob.move_item(0, 3);
+    assert_next_eq!(sub, VectorDiff::Remove { index: 0 });
+    assert_next_eq!(sub, VectorDiff::PushBack { value: 3 });
+
+    // [2, 1, 3, 4]: 2 enters the view, 3 leaves it.
+    ob.move_item(3, 0);
+    assert_next_eq!(sub, VectorDiff::PopBack);
+    assert_next_eq!(sub, VectorDiff::Insert { index: 0, value: 2 });
+
+    // [2, 1, 4, 3]: outside of the view.
+    ob.move_item(2, 3);
+    assert_pending!(sub);
+}
@@ -0,0 +1,126 @@
+use eyeball_im::{ObservableMap, ObservableVector, VectorDiff};
+use eyeball_im_util::vector::{VectorObserverExt, VectorSubscriberExt};
+use imbl::vector;
+use stream_assert::{assert_closed, assert_next_eq, assert_pending};
+
+#[test]
+fn map_changes() {
+    let ob = ObservableVector::<&str>::from(vector!["a", "b", "a"]);
+    let mut map = ObservableMap::<&str, u32>::new();
+    map.insert("a", 1);
+
+    let (values, mut sub) =
+        ob.subscribe().join_map(map.subscribe(), |v| *v, |v, n| (v, n.copied()));
+    assert_eq!(values, vector![("a", Some(1)), ("b", None), ("a", Some(1))]);
+
+    map.insert("b", 2);
+    assert_next_eq!(sub, VectorDiff::Set { index: 1, value: ("b", Some(2)) });
+    assert_pending!(sub);
+
+    map.insert("a", 3);
+    assert_next_eq!(sub, VectorDiff::Set { index: 0, value: ("a", Some(3)) });
+    assert_next_eq!(sub, VectorDiff::Set { index: 2, value: ("a", Some(3)) });
+    assert_pending!(sub);
+
+    // Entries that no item refers to don't cause any diffs.
+    map.insert("c", 4);
+    map.remove("c");
+    assert_pending!(sub);
+
+    map.remove("b");
+    assert_next_eq!(sub, VectorDiff::Set { index: 1, value: ("b", None) });
+    assert_pending!(sub);
+
+    map.clear();
+    assert_next_eq!(sub, VectorDiff::Set { index: 0, value: ("a", None) });
+    assert_next_eq!(sub, VectorDiff::Set { index: 2, value: ("a", None) });
+    assert_pending!(sub);
+
+    drop(ob);
+    assert_closed!(sub);
+}
+
+#[test]
+fn vector_changes() {
+    let mut ob = ObservableVector::<u32>::from(vector![1, 2]);
+    let mut map = ObservableMap::<u32, char>::new();
+    map.insert(2, 'b');
+    map.insert(3, 'c');
+
+    let (values, mut sub) = ob.subscribe().join_map(map.subscribe(), |v| *v, |_, c| c.copied());
+    assert_eq!(values, vector![None, Some('b')]);
+
+    ob.push_back(3);
+    assert_next_eq!(sub, VectorDiff::PushBack { value: Some('c') });
+    ob.insert(0, 2);
+    assert_next_eq!(sub, VectorDiff::Insert { index: 0, value: Some('b') });
+    ob.set(1, 3);
+    assert_next_eq!(sub, VectorDiff::Set { index: 1, value: Some('c') });
+    ob.remove(2);
+    assert_next_eq!(sub, VectorDiff::Remove { index: 2 });
+    assert_pending!(sub);
+
+    // [2, 3, 3]
+    map.insert(3, 'x');
+    assert_next_eq!(sub, VectorDiff::Set { index: 1, value: Some('x') });
+    assert_next_eq!(sub, VectorDiff::Set { index: 2, value: Some('x') });
+    assert_pending!(sub);
+}
+
+#[test]
+fn map_changes_first() {
+    let mut ob = ObservableVector::<u32>::new();
+    let mut map = ObservableMap::<u32, char>::new();
+    let (_, mut sub) = ob.subscribe().join_map(map.subscribe(), |v| *v, |_, c| c.copied());
+
+    // The vector change is made first, but the map's state at the time of
+    // polling is used for it.
+    ob.push_back(1);
+    map.insert(1, 'a');
+    assert_next_eq!(sub, VectorDiff::PushBack { value: Some('a') });
+    assert_pending!(sub);
+}
+
+#[test]
+fn map_closed() {
+    let mut ob = ObservableVector::<u32>::from(vector![1]);
+    let mut map = ObservableMap::<u32, char>::new();
+    map.insert(1, 'a');
+    let (_, mut sub) = ob.subscribe().join_map(map.subscribe(), |v| *v, |_, c| c.copied());
+
+    // The last state of the map is kept.
+    drop(map);
+    ob.push_back(1);
+    assert_next_eq!(sub, VectorDiff::PushBack { value: Some('a') });
+    ob.push_back(2);
+    assert_next_eq!(sub, VectorDiff::PushBack { value: None });
+    assert_pending!(sub);
+}
+
+#[test]
+fn batched() {
+    let mut ob = ObservableVector::<u32>::from(vector![1, 2, 1]);
+    let mut map = ObservableMap::<u32, char>::new();
+    let (_, mut sub) =
+        ob.subscribe().batched().join_map(map.subscribe(), |v| *v, |_, c| c.copied());
+
+    map.insert(1, 'a');
+    assert_next_eq!(
+        sub,
+        vec![
+            VectorDiff::Set { index: 0, value: Some('a') },
+            VectorDiff::Set { index: 2, value: Some('a') },
+        ]
+    );
+    assert_pending!(sub);
+
+    let mut txn = ob.transaction();
+    txn.push_back(2);
+    txn.push_back(1);
+    txn.commit();
+    assert_next_eq!(
+        sub,
+        vec![VectorDiff::PushBack { value: None }, VectorDiff::PushBack { value: Some('a') }]
+    );
+    assert_pending!(sub);
+}
@@ -0,0 +1,88 @@
+use eyeball::Observable;
+use eyeball_im::{ObservableVector, VectorDiff};
+use eyeball_im_util::vector::{latest_of_each, VectorObserverExt, VectorSubscriberExt};
+use imbl::vector;
+use stream_assert::{assert_closed, assert_next_eq, assert_pending};
+
+#[test]
+fn structure_changes() {
+    let a = Observable::new('a');
+    let b = Observable::new('b');
+    let mut c = Observable::new('c');
+
+    let mut sources = ObservableVector::new();
+    let (values, mut sub) = latest_of_each(sources.subscribe());
+    assert!(values.is_empty());
+    assert_pending!(sub);
+
+    sources.append(vector![Observable::subscribe(&a), Observable::subscribe(&b)]);
+    assert_next_eq!(sub, VectorDiff::Append { values: vector!['a', 'b'] });
+    assert_pending!(sub);
+
+    sources.insert(1, Observable::subscribe(&c));
+    assert_next_eq!(sub, VectorDiff::Insert { index: 1, value: 'c' });
+
+    sources.move_item(0, 2);
+    assert_next_eq!(sub, VectorDiff::Move { from: 0, to: 2 });
+
+    Observable::set(&mut c, 'C');
+    assert_next_eq!(sub, VectorDiff::Set { index: 0, value: 'C' });
+
+    let _c_sub = sources.set(0, Observable::subscribe(&a));
+    assert_next_eq!(sub, VectorDiff::Set { index: 0, value: 'a' });
+    Observable::set(&mut c, 'X');
+    assert_pending!(sub);
+
+    sources.clear();
+    assert_next_eq!(sub, VectorDiff::Clear);
+    assert_pending!(sub);
+}
+
+#[test]
+fn closed_sources() {
+    let mut a = Observable::new(1);
+    let b = Observable::new(2);
+
+    let mut sources = ObservableVector::new();
+    sources.push_back(Observable::subscribe(&a));
+    sources.push_back(Observable::subscribe(&b));
+    let (values, mut sub) = latest_of_each(sources.subscribe());
+    assert_eq!(values, vector![1, 2]);
+
+    Observable::set(&mut a, 10);
+    assert_next_eq!(sub, VectorDiff::Set { index: 0, value: 10 });
+    drop(a);
+    drop(b);
+    assert_pending!(sub);
+
+    sources.push_back(Observable::subscribe(&Observable::new(3)));
+    assert_next_eq!(sub, VectorDiff::PushBack { value: 3 });
+    assert_pending!(sub);
+
+    drop(sources);
+    assert_closed!(sub);
+}
+
+#[test]
+fn batched_and_sorted() {
+    let mut a = Observable::new(3);
+    let b = Observable::new(2);
+
+    let mut sources = ObservableVector::new();
+    sources.push_back(Observable::subscribe(&a));
+    let (values, sub) = latest_of_each(sources.subscribe().batched());
+    let (values, mut sub) = (values, sub).sort();
+    assert_eq!(values, vector![3]);
+
+    let mut txn = sources.transaction();
+    txn.push_back(Observable::subscribe(&b));
+    txn.commit();
+    assert_next_eq!(sub, vec![VectorDiff::PushFront { value: 2 }]);
+
+    Observable::set(&mut a, 1);
+    assert_next_eq!(
+        sub,
+        vec![VectorDiff::Set { index: 1, value: 1 }, VectorDiff::Move { from: 1, to: 0 }]
+    );
+    assert_pending!(sub);
+}
@@ -0,0 +1,51 @@
+use eyeball_im::ObservableVector;
+use eyeball_im_util::vector::{VectorObserverExt, VectorSubscriberExt};
+use imbl::vector;
+use stream_assert::{assert_closed, assert_next_eq, assert_pending};
+
+#[test]
+fn changes() {
+    let mut ob = ObservableVector::<u32>::from(vector![1, 2]);
+    let mut len = ob.subscribe().len_observable();
+    assert_eq!(len.get(), 2);
+
+    ob.push_back(3);
+    assert_next_eq!(len, 3);
+
+    // Updates that don't change the length aren't yielded.
+    ob.set(0, 10);
+    ob.move_item(0, 2);
+    assert_pending!(len);
+
+    ob.truncate(1);
+    assert_next_eq!(len, 1);
+    ob.append(vector![4, 5]);
+    assert_next_eq!(len, 3);
+    ob.clear();
+    assert_next_eq!(len, 0);
+    assert_eq!(len.get(), 0);
+
+    drop(ob);
+    assert_closed!(len);
+}
+
+#[test]
+fn batched() {
+    let mut ob = ObservableVector::<u32>::new();
+    let mut len = ob.subscribe().batched().head(2).len_observable();
+
+    // Only the final length of a batch is yielded.
+    let mut txn = ob.transaction();
+    txn.push_back(1);
+    txn.push_back(2);
+    txn.push_back(3);
+    txn.commit();
+    assert_next_eq!(len, 2);
+
+    // No change in length overall.
+    let mut txn = ob.transaction();
+    txn.push_front(0);
+    txn.pop_back();
+    txn.commit();
+    assert_pending!(len);
+}
@@ -1,9 +1,162 @@
 #![allow(missing_docs)]
 
+use std::ops::Deref;
+
+use eyeball_im::{ObservableVector, ObservableVectorTransaction, VectorDiff};
+use futures_util::{FutureExt, Stream, StreamExt};
+use imbl::Vector;
+
+mod adapter_debug;
+mod aggregate;
+mod anchored_window;
+#[cfg(feature = "tokio")]
+mod batch_timeout;
+mod chain;
+mod chunk_timeout;
+mod dedup;
+mod dynamic_filter;
+mod dynamic_sort_by;
+mod empty_transitions;
+mod extremum;
 mod filter;
+mod filter_if;
 mod filter_map;
+mod filter_map_cached;
+mod filter_with_index;
+mod fold;
+mod group_by;
 mod head;
+mod join_map;
+mod latest_of_each;
+mod len_observable;
+mod map_into;
+mod map_with_index;
+#[cfg(feature = "tokio")]
+mod materialize;
+mod merge;
+mod padded_head;
+#[cfg(feature = "proptest")]
+mod proptest;
+mod record;
+mod send_sync;
+#[cfg(feature = "futures-signals")]
+mod signal_vec;
 mod sort;
 mod sort_by;
 mod sort_by_key;
+mod sort_if;
+mod sort_limit;
+mod switch_vectors;
 mod tail;
+mod take_until_closed;
+#[cfg(feature = "tokio")]
+mod throttle;
+mod tombstones;
+mod try_map;
+mod unbatch;
+mod unique_by_key;
+mod validate;
+mod watch_index;
+mod window;
+
+/// A xorshift pseudo-random number generator, which keeps the `consistency`
+/// tests of the adapters reproducible.
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    /// Get a number in `0..max`.
+    fn below(&mut self, max: usize) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 % max as u64) as usize
+    }
+}
+
+/// Update `vector` in a random way that is valid for its current length, with
+/// new values from `value`, and return the corresponding diff.
+///
+/// Every kind of update except `Reset` is used. Clearing is rare, so that the
+/// vector usually grows long enough to be interesting.
+fn random_update<T: Clone>(
+    vector: &mut impl Update<T>,
+    rng: &mut Rng,
+    mut value: impl FnMut(&mut Rng) -> T,
+) -> VectorDiff<T> {
+    let len = vector.len();
+    let diff = if rng.below(100) == 0 {
+        VectorDiff::Clear
+    } else {
+        match rng.below(10) {
+            0 => VectorDiff::PushFront { value: value(rng) },
+            1 => VectorDiff::PushBack { value: value(rng) },
+            2 => VectorDiff::Insert { index: rng.below(len + 1), value: value(rng) },
+            3 => VectorDiff::Truncate { length: rng.below(len + 1) },
+            4 => VectorDiff::Append { values: (0..rng.below(4)).map(|_| value(rng)).collect() },
+            // All the other updates require a value.
+            _ if len == 0 => VectorDiff::PushBack { value: value(rng) },
+            5 => VectorDiff::PopFront,
+            6 => VectorDiff::PopBack,
+            7 => VectorDiff::Remove { index: rng.below(len) },
+            8 => VectorDiff::Set { index: rng.below(len), value: value(rng) },
+            _ => VectorDiff::Move { from: rng.below(len), to: rng.below(len) },
+        }
+    };
+
+    vector.update(diff.clone());
+    diff
+}
+
+/// Apply all the diffs that `sub` has ready to `values`.
+fn apply_ready<T: Clone>(
+    sub: &mut (impl Stream<Item = VectorDiff<T>> + Unpin),
+    values: &mut Vector<T>,
+) {
+    while let Some(Some(diff)) = sub.next().now_or_never() {
+        diff.apply(values);
+    }
+}
+
+/// A vector that [`random_update`] can update, that is an [`ObservableVector`]
+/// or one of its transactions.
+trait Update<T>: Deref<Target = Vector<T>> {
+    fn update(&mut self, diff: VectorDiff<T>);
+}
+
+macro_rules! impl_update {
+    ($ty:ty) => {
+        impl<T: Clone + 'static> Update<T> for $ty {
+            fn update(&mut self, diff: VectorDiff<T>) {
+                match diff {
+                    VectorDiff::Append { values } => self.append(values),
+                    VectorDiff::Clear => self.clear(),
+                    VectorDiff::PushFront { value } => self.push_front(value),
+                    VectorDiff::PushBack { value } => self.push_back(value),
+                    VectorDiff::PopFront => {
+                        self.pop_front();
+                    }
+                    VectorDiff::PopBack => {
+                        self.pop_back();
+                    }
+                    VectorDiff::Insert { index, value } => self.insert(index, value),
+                    VectorDiff::Set { index, value } => {
+                        self.set(index, value);
+                    }
+                    VectorDiff::Remove { index } => {
+                        self.remove(index);
+                    }
+                    VectorDiff::Truncate { length } => self.truncate(length),
+                    VectorDiff::Move { from, to } => self.move_item(from, to),
+                    VectorDiff::Reset { .. } => unreachable!("`random_update` never resets"),
+                }
+            }
+        }
+    };
+}
+
+impl_update!(ObservableVector<T>);
+impl_update!(ObservableVectorTransaction<'_, T>);
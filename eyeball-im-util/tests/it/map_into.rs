@@ -0,0 +1,64 @@
+use eyeball_im::{ObservableVector, VectorDiff};
+use eyeball_im_util::vector::{VectorObserverExt, VectorSubscriberExt};
+use imbl::vector;
+use stream_assert::{assert_closed, assert_next_eq, assert_pending};
+
+#[derive(Clone, Debug, PartialEq)]
+struct Wrapper(u32);
+
+impl From<u32> for Wrapper {
+    fn from(value: u32) -> Self {
+        Self(value)
+    }
+}
+
+#[test]
+fn map_values_into() {
+    let mut ob = ObservableVector::<u32>::from(vector![1, 2]);
+    let (values, mut sub) = ob.subscribe().map_values_into::<Wrapper>();
+    assert_eq!(values, vector![Wrapper(1), Wrapper(2)]);
+
+    ob.set(0, 10);
+    assert_next_eq!(sub, VectorDiff::Set { index: 0, value: Wrapper(10) });
+    ob.append(vector![3, 4]);
+    assert_next_eq!(sub, VectorDiff::Append { values: vector![Wrapper(3), Wrapper(4)] });
+    assert_pending!(sub);
+
+    drop(ob);
+    assert_closed!(sub);
+}
+
+#[test]
+fn map_values_into_batched() {
+    let mut ob = ObservableVector::<u32>::new();
+    let (_, mut sub) = ob.subscribe().batched().map_values_into::<Wrapper>();
+
+    let mut txn = ob.transaction();
+    txn.push_back(1);
+    txn.push_front(0);
+    txn.commit();
+    assert_next_eq!(
+        sub,
+        vec![
+            VectorDiff::PushBack { value: Wrapper(1) },
+            VectorDiff::PushFront { value: Wrapper(0) },
+        ]
+    );
+    assert_pending!(sub);
+}
+
+#[test]
+fn map_values_try_into() {
+    let mut ob = ObservableVector::<i32>::from(vector![1, -1, 2]);
+    let (values, mut sub) = ob.subscribe().map_values_try_into::<u8>();
+    assert_eq!(values, vector![1, 2]);
+
+    // Values that fail to convert are left out
+    ob.push_front(300);
+    assert_pending!(sub);
+    ob.insert(3, 3);
+    assert_next_eq!(sub, VectorDiff::Insert { index: 1, value: 3 });
+    ob.set(2, 4);
+    assert_next_eq!(sub, VectorDiff::Insert { index: 1, value: 4 });
+    assert_pending!(sub);
+}
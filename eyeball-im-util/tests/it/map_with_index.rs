@@ -0,0 +1,87 @@
+use eyeball_im::{ObservableVector, ResetReason, VectorDiff};
+use eyeball_im_util::vector::{VectorObserverExt, VectorSubscriberExt};
+use imbl::{vector, Vector};
+use stream_assert::{assert_closed, assert_next_eq, assert_pending};
+
+#[test]
+fn shifts() {
+    let mut ob = ObservableVector::<char>::from(vector!['a', 'b']);
+    let (values, mut sub) = ob.subscribe().map_with_index(|index, value| (index, value));
+    assert_eq!(values, vector![(0, 'a'), (1, 'b')]);
+
+    ob.push_front('z');
+    assert_next_eq!(sub, VectorDiff::PushFront { value: (0, 'z') });
+    assert_next_eq!(sub, VectorDiff::Set { index: 1, value: (1, 'a') });
+    assert_next_eq!(sub, VectorDiff::Set { index: 2, value: (2, 'b') });
+    assert_pending!(sub);
+
+    ob.insert(2, 'y');
+    assert_next_eq!(sub, VectorDiff::Insert { index: 2, value: (2, 'y') });
+    assert_next_eq!(sub, VectorDiff::Set { index: 3, value: (3, 'b') });
+
+    // No shifts
+    ob.push_back('c');
+    assert_next_eq!(sub, VectorDiff::PushBack { value: (4, 'c') });
+    ob.set(0, 'x');
+    assert_next_eq!(sub, VectorDiff::Set { index: 0, value: (0, 'x') });
+    ob.pop_back();
+    assert_next_eq!(sub, VectorDiff::PopBack);
+    ob.append(vector!['d']);
+    assert_next_eq!(sub, VectorDiff::Append { values: vector![(4, 'd')] });
+    assert_pending!(sub);
+
+    // [x, a, y, b, d]
+    ob.move_item(3, 1);
+    assert_next_eq!(sub, VectorDiff::Set { index: 1, value: (1, 'b') });
+    assert_next_eq!(sub, VectorDiff::Set { index: 2, value: (2, 'a') });
+    assert_next_eq!(sub, VectorDiff::Set { index: 3, value: (3, 'y') });
+    assert_pending!(sub);
+
+    ob.pop_front();
+    assert_next_eq!(sub, VectorDiff::PopFront);
+    assert_next_eq!(sub, VectorDiff::Set { index: 0, value: (0, 'b') });
+    assert_next_eq!(sub, VectorDiff::Set { index: 1, value: (1, 'a') });
+    assert_next_eq!(sub, VectorDiff::Set { index: 2, value: (2, 'y') });
+    assert_next_eq!(sub, VectorDiff::Set { index: 3, value: (3, 'd') });
+    assert_pending!(sub);
+
+    drop(ob);
+    assert_closed!(sub);
+}
+
+#[test]
+fn batched() {
+    let mut ob = ObservableVector::<u32>::from(vector![5, 6]);
+    let (_, mut sub) =
+        ob.subscribe().batched().map_with_index(|index, value| value * 10 + index as u32);
+
+    ob.remove(0);
+    assert_next_eq!(
+        sub,
+        vec![VectorDiff::Remove { index: 0 }, VectorDiff::Set { index: 0, value: 60 }]
+    );
+
+    ob.splice(.., vector![1, 2]);
+    assert_next_eq!(
+        sub,
+        vec![VectorDiff::Reset { values: vector![10, 21], reason: ResetReason::Update }]
+    );
+    assert_pending!(sub);
+}
+
+#[test]
+fn consistency() {
+    let mut rng = crate::Rng::new(0x9e37_79b9);
+
+    let f = |index: usize, value: u32| (index, value);
+    let mut ob = ObservableVector::from((0..8).collect::<Vector<u32>>());
+    let (mut values, mut sub) = ob.subscribe().map_with_index(f);
+
+    for step in 0..2000 {
+        crate::random_update(&mut ob, &mut rng, |rng| rng.below(1000) as u32);
+        crate::apply_ready(&mut sub, &mut values);
+
+        let expected: Vector<_> = ob.iter().enumerate().map(|(idx, v)| f(idx, *v)).collect();
+        assert_eq!(values, expected, "after step {step}");
+    }
+}
@@ -0,0 +1,34 @@
+use eyeball_im::{ObservableVector, VectorDiff};
+use eyeball_im_util::vector::VectorObserverExt;
+use futures_util::StreamExt;
+use imbl::vector;
+
+#[tokio::test]
+async fn spawn_materialized() {
+    let mut ob = ObservableVector::<u32>::from(vector![3, 1, 2]);
+    let materialized = ob
+        .subscribe()
+        .filter(|&v| v != 2)
+        .sort()
+        .spawn_materialized(&tokio::runtime::Handle::current());
+    assert_eq!(materialized.get(), vector![1, 3]);
+
+    let (values, mut sub) = materialized.subscribe().into_values_and_batched_stream();
+    assert_eq!(values, vector![1, 3]);
+
+    ob.push_back(0);
+    ob.push_back(2);
+    assert_eq!(sub.next().await, Some(vec![VectorDiff::PushFront { value: 0 }]));
+    assert_eq!(materialized.get(), vector![0, 1, 3]);
+
+    // Clones share the same background task.
+    let clone = materialized.clone();
+    drop(materialized);
+    ob.pop_front();
+    assert_eq!(sub.next().await, Some(vec![VectorDiff::PopBack]));
+    assert_eq!(clone.get(), vector![0, 1]);
+
+    // Dropping the last handle stops the task and closes subscribers.
+    drop(clone);
+    assert_eq!(sub.next().await, None);
+}
@@ -0,0 +1,86 @@
+use eyeball_im::{ObservableVector, VectorDiff};
+use eyeball_im_util::vector::merge_diff_streams;
+use imbl::vector;
+use stream_assert::{assert_closed, assert_next_eq, assert_pending};
+
+#[test]
+fn translate_indices() {
+    let mut first = ObservableVector::<u32>::from(vector![1, 2]);
+    let mut second = ObservableVector::<u32>::from(vector![10, 20]);
+    let (values, mut sub) = merge_diff_streams([first.subscribe(), second.subscribe()]);
+    assert_eq!(values, vector![1, 2, 10, 20]);
+    assert_pending!(sub);
+
+    second.set(1, 21);
+    assert_next_eq!(sub, VectorDiff::Set { index: 3, value: 21 });
+
+    second.remove(0);
+    assert_next_eq!(sub, VectorDiff::Remove { index: 2 });
+
+    first.push_front(0);
+    assert_next_eq!(sub, VectorDiff::PushFront { value: 0 });
+
+    second.push_back(30);
+    assert_next_eq!(sub, VectorDiff::PushBack { value: 30 });
+
+    first.pop_back();
+    assert_next_eq!(sub, VectorDiff::Remove { index: 2 });
+
+    assert_pending!(sub);
+}
+
+#[test]
+fn non_last_segment() {
+    let mut first = ObservableVector::<u32>::from(vector![1, 2, 3]);
+    let second = ObservableVector::<u32>::from(vector![10]);
+    let (_, mut sub) = merge_diff_streams([first.subscribe(), second.subscribe()]);
+
+    first.append(vector![4, 5]);
+    assert_next_eq!(sub, VectorDiff::Insert { index: 3, value: 4 });
+    assert_next_eq!(sub, VectorDiff::Insert { index: 4, value: 5 });
+
+    first.truncate(3);
+    assert_next_eq!(sub, VectorDiff::Remove { index: 3 });
+    assert_next_eq!(sub, VectorDiff::Remove { index: 3 });
+
+    first.clear();
+    assert_next_eq!(sub, VectorDiff::Remove { index: 0 });
+    assert_next_eq!(sub, VectorDiff::Remove { index: 0 });
+    assert_next_eq!(sub, VectorDiff::Remove { index: 0 });
+
+    assert_pending!(sub);
+}
+
+#[test]
+fn last_segment() {
+    let first = ObservableVector::<u32>::from(vector![1]);
+    let mut second = ObservableVector::<u32>::from(vector![10, 20]);
+    let (_, mut sub) = merge_diff_streams([first.subscribe(), second.subscribe()]);
+
+    second.append(vector![30]);
+    assert_next_eq!(sub, VectorDiff::Append { values: vector![30] });
+
+    second.truncate(1);
+    assert_next_eq!(sub, VectorDiff::Truncate { length: 2 });
+
+    second.clear();
+    assert_next_eq!(sub, VectorDiff::Truncate { length: 1 });
+
+    assert_pending!(sub);
+}
+
+#[test]
+fn closed_once_all_segments_closed() {
+    let first = ObservableVector::<u32>::new();
+    let mut second = ObservableVector::<u32>::new();
+    let (_, mut sub) = merge_diff_streams([first.subscribe(), second.subscribe()]);
+
+    drop(first);
+    assert_pending!(sub);
+
+    second.push_back(1);
+    assert_next_eq!(sub, VectorDiff::PushBack { value: 1 });
+
+    drop(second);
+    assert_closed!(sub);
+}
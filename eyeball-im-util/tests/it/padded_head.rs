@@ -0,0 +1,78 @@
+use eyeball_im::{ObservableVector, ResetReason, VectorDiff};
+use eyeball_im_util::vector::{VectorObserverExt, VectorSubscriberExt};
+use futures_core::Stream;
+use futures_util::{FutureExt, StreamExt};
+use imbl::{vector, Vector};
+use stream_assert::{assert_closed, assert_next_eq, assert_pending};
+
+#[test]
+fn padding() {
+    let mut ob = ObservableVector::<u32>::from(vector![1]);
+    let (values, mut sub) = ob.subscribe().padded_head(3, || 0);
+    assert_eq!(values, vector![1, 0, 0]);
+
+    ob.append(vector![2, 3, 4]);
+    assert_next_eq!(sub, VectorDiff::Set { index: 1, value: 2 });
+    assert_next_eq!(sub, VectorDiff::Set { index: 2, value: 3 });
+
+    ob.truncate(1);
+    assert_next_eq!(sub, VectorDiff::Set { index: 1, value: 0 });
+    assert_next_eq!(sub, VectorDiff::Set { index: 2, value: 0 });
+
+    ob.insert(0, 5);
+    assert_next_eq!(sub, VectorDiff::Insert { index: 0, value: 5 });
+    assert_next_eq!(sub, VectorDiff::PopBack);
+
+    ob.remove(0);
+    assert_next_eq!(sub, VectorDiff::Remove { index: 0 });
+    assert_next_eq!(sub, VectorDiff::PushBack { value: 0 });
+
+    ob.clear();
+    assert_next_eq!(
+        sub,
+        VectorDiff::Reset { values: vector![0, 0, 0], reason: ResetReason::Update }
+    );
+
+    ob.clear();
+    assert_pending!(sub);
+
+    drop(ob);
+    assert_closed!(sub);
+}
+
+#[test]
+fn view_always_has_limit_items() {
+    let mut ob = ObservableVector::<u32>::from(vector![1, 2, 3, 4]);
+    let (mut view, mut sub) = ob.subscribe().batched().padded_head(3, || 0);
+    assert_eq!(view, vector![1, 2, 3]);
+
+    ob.push_front(5);
+    check(&ob, &mut view, &mut sub);
+    ob.pop_front();
+    check(&ob, &mut view, &mut sub);
+    ob.truncate(2);
+    check(&ob, &mut view, &mut sub);
+    ob.push_back(6);
+    check(&ob, &mut view, &mut sub);
+    ob.pop_back();
+    check(&ob, &mut view, &mut sub);
+    ob.set(0, 7);
+    check(&ob, &mut view, &mut sub);
+}
+
+fn check(
+    ob: &ObservableVector<u32>,
+    view: &mut Vector<u32>,
+    sub: &mut (impl Stream<Item = Vec<VectorDiff<u32>>> + Unpin),
+) {
+    while let Some(Some(diffs)) = sub.next().now_or_never() {
+        for diff in diffs {
+            diff.apply(view);
+        }
+        assert_eq!(view.len(), 3);
+    }
+
+    let mut expected: Vector<u32> = ob.iter().take(3).copied().collect();
+    expected.extend((expected.len()..3).map(|_| 0));
+    assert_eq!(*view, expected);
+}
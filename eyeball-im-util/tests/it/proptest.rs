@@ -0,0 +1,71 @@
+use eyeball_im::VectorDiff;
+use eyeball_im_util::{
+    proptest::{vector_diff_batches, vector_diffs},
+    vector::VectorObserverExt,
+};
+use futures_util::{stream, FutureExt, StreamExt};
+use imbl::Vector;
+use proptest::prelude::*;
+
+fn apply_all<T: Clone>(
+    mut values: Vector<T>,
+    diffs: impl IntoIterator<Item = VectorDiff<T>>,
+) -> Vector<T> {
+    for diff in diffs {
+        diff.apply(&mut values);
+    }
+    values
+}
+
+proptest! {
+    #[test]
+    fn filter_matches_model((initial, batches) in vector_diff_batches(any::<u8>(), 8, 32)) {
+        let expected: Vector<_> =
+            apply_all(initial.clone(), batches.iter().flatten().cloned()).into_iter().filter(|v| v % 2 == 0).collect();
+
+        let (mut values, mut sub) = (initial, stream::iter(batches)).filter(|v| v % 2 == 0);
+        while let Some(Some(diffs)) = sub.next().now_or_never() {
+            values = apply_all(values, diffs);
+        }
+        prop_assert_eq!(values, expected);
+    }
+
+    #[test]
+    fn sort_matches_model((initial, diffs) in vector_diffs(any::<u8>(), 8, 32)) {
+        let mut expected = apply_all(initial.clone(), diffs.clone());
+        expected.sort();
+
+        let (mut values, mut sub) = (initial, stream::iter(diffs)).sort();
+        while let Some(Some(diff)) = sub.next().now_or_never() {
+            diff.apply(&mut values);
+        }
+        prop_assert_eq!(values, expected);
+    }
+
+    #[test]
+    fn head_matches_model((initial, batches) in vector_diff_batches(any::<u8>(), 8, 32), limit in 0..6_usize) {
+        let expected: Vector<_> = apply_all(initial.clone(), batches.iter().flatten().cloned())
+            .into_iter()
+            .take(limit)
+            .collect();
+
+        let (mut values, mut sub) = (initial, stream::iter(batches)).head(limit);
+        while let Some(Some(diffs)) = sub.next().now_or_never() {
+            values = apply_all(values, diffs);
+        }
+        prop_assert_eq!(values, expected);
+    }
+
+    #[test]
+    fn sort_limit_matches_model((initial, batches) in vector_diff_batches(any::<u8>(), 8, 32), limit in 0..6_usize) {
+        let mut expected = apply_all(initial.clone(), batches.iter().flatten().cloned());
+        expected.sort();
+        let expected: Vector<_> = expected.into_iter().take(limit).collect();
+
+        let (mut values, mut sub) = (initial, stream::iter(batches)).sort_limit(limit);
+        while let Some(Some(diffs)) = sub.next().now_or_never() {
+            values = apply_all(values, diffs);
+        }
+        prop_assert_eq!(values, expected);
+    }
+}
@@ -0,0 +1,111 @@
+use eyeball_im::{ObservableVector, VectorDiff};
+use eyeball_im_util::vector::{DiffLog, DiffLogEntry, VectorObserverExt, VectorSubscriberExt};
+use futures_util::{FutureExt, StreamExt};
+use imbl::vector;
+use stream_assert::{assert_closed, assert_next_eq, assert_pending};
+
+#[test]
+fn record_and_replay() {
+    let mut ob = ObservableVector::<u32>::from(vector![1, 2]);
+    let (values, mut sub) = ob.subscribe().batched().record();
+    let log = sub.log();
+    assert_eq!(values, vector![1, 2]);
+
+    // Items are forwarded unchanged.
+    let mut txn = ob.transaction();
+    txn.push_back(3);
+    txn.remove(0);
+    txn.commit();
+    assert_next_eq!(sub, vec![VectorDiff::PushBack { value: 3 }, VectorDiff::Remove { index: 0 }]);
+    ob.set(0, 4);
+    assert_next_eq!(sub, vec![VectorDiff::Set { index: 0, value: 4 }]);
+    assert_pending!(sub);
+
+    let recorded = log.get();
+    assert_eq!(
+        recorded,
+        DiffLog {
+            initial_values: vector![1, 2],
+            entries: vec![
+                DiffLogEntry {
+                    elapsed: None,
+                    diffs: vec![VectorDiff::PushBack { value: 3 }, VectorDiff::Remove { index: 0 }],
+                },
+                DiffLogEntry { elapsed: None, diffs: vec![VectorDiff::Set { index: 0, value: 4 }] },
+            ],
+        }
+    );
+    assert_eq!(recorded.final_values(), vector![4, 3]);
+
+    // The log outlives the adapter.
+    drop(sub);
+    ob.push_back(5);
+    assert_eq!(log.get(), recorded);
+
+    // Replaying yields the recorded batches.
+    let (values, mut replay) = recorded.replay();
+    assert_eq!(values, vector![1, 2]);
+    assert_next_eq!(
+        replay,
+        vec![VectorDiff::PushBack { value: 3 }, VectorDiff::Remove { index: 0 }]
+    );
+    assert_next_eq!(replay, vec![VectorDiff::Set { index: 0, value: 4 }]);
+    assert_closed!(replay);
+}
+
+#[test]
+fn replay_into_adapter() {
+    let mut ob = ObservableVector::<u32>::new();
+    let (_, mut recording) = ob.subscribe().record();
+    let log = recording.log();
+
+    ob.append(vector![5, 2, 8]);
+    ob.push_front(1);
+    assert_next_eq!(recording, VectorDiff::Append { values: vector![5, 2, 8] });
+    assert_next_eq!(recording, VectorDiff::PushFront { value: 1 });
+    drop(ob);
+    assert_closed!(recording);
+
+    // The diffs are replayed right away.
+    let (values, diffs) = log.get().replay().unbatched();
+    let (mut values, mut sub) = (values, diffs).sort();
+    while let Some(Some(diff)) = sub.next().now_or_never() {
+        diff.apply(&mut values);
+    }
+    assert_eq!(values, vector![1, 2, 5, 8]);
+    assert_closed!(sub);
+}
+
+#[test]
+fn timestamps() {
+    let mut ob = ObservableVector::<u32>::new();
+    let (_, mut sub) = ob.subscribe().record_with_timestamps();
+    let log = sub.log();
+
+    ob.push_back(1);
+    ob.push_back(2);
+    assert_next_eq!(sub, VectorDiff::PushBack { value: 1 });
+    assert_next_eq!(sub, VectorDiff::PushBack { value: 2 });
+
+    let entries = log.get().entries;
+    assert_eq!(entries.len(), 2);
+    assert!(entries[0].elapsed.unwrap() <= entries[1].elapsed.unwrap());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_roundtrip() {
+    let mut ob = ObservableVector::<u32>::from(vector![1]);
+    let (_, mut sub) = ob.subscribe().record();
+    let log = sub.log();
+
+    ob.insert(0, 2);
+    ob.clear();
+    assert_next_eq!(sub, VectorDiff::Insert { index: 0, value: 2 });
+    assert_next_eq!(sub, VectorDiff::Clear);
+
+    let json = serde_json::to_string(&log.get()).unwrap();
+    assert_eq!(json, r#"[[1],[[null,[{"Insert":{"index":0,"value":2}}]],[null,[{"Clear":{}}]]]]"#);
+    let deserialized: DiffLog<u32> = serde_json::from_str(&json).unwrap();
+    assert_eq!(deserialized, log.get());
+}
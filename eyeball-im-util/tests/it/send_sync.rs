@@ -0,0 +1,73 @@
+//! Compile-time checks that the adapters can be shared across threads
+//! whenever the underlying stream and the functions they hold can.
+
+use std::future;
+
+use eyeball_im::{ObservableMap, ObservableVector};
+use eyeball_im_util::vector::{AnchorSpec, VectorObserverExt, VectorSubscriberExt};
+use futures_util::stream;
+
+fn assert_send_sync<T: Send + Sync>(_: &T) {}
+
+#[test]
+fn adapters() {
+    let ob = ObservableVector::<u32>::new();
+    let flags = || stream::iter([true]);
+    let limits = || stream::iter([1]);
+    let map = ObservableMap::<u32, u8>::new();
+
+    assert_send_sync(&ob.subscribe().chain(ob.subscribe()).1);
+    assert_send_sync(&ob.subscribe().chunk_timeout(2, stream::iter([()])).1);
+    assert_send_sync(&ob.subscribe().dedup().1);
+    assert_send_sync(&ob.subscribe().dedup_by_key(|v| v / 2).1);
+    assert_send_sync(&ob.subscribe().filter(|v| v % 2 == 0).1);
+    assert_send_sync(&ob.subscribe().filter_map(|v| v.checked_sub(1)).1);
+    assert_send_sync(&ob.subscribe().filter_map_cached(|v, c: &mut u32| Some(v + *c)).1);
+    assert_send_sync(&ob.subscribe().filter_with_index(|i, _| i % 2 == 0).1);
+    assert_send_sync(&ob.subscribe().map_with_index(|v, _| v + 1).1);
+    assert_send_sync(&ob.subscribe().map_values_into::<u64>().1);
+    assert_send_sync(&ob.subscribe().try_map(u16::try_from).1);
+    assert_send_sync(
+        &ob.subscribe().join_map(map.subscribe(), |v| v % 2, |v, m| (v, m.copied())).1,
+    );
+    assert_send_sync(&ob.subscribe().filter_if(|v| v % 2 == 0, true, flags()).1);
+    assert_send_sync(&ob.subscribe().dynamic_filter(stream::iter([|v: &u32| v % 2 == 0])));
+    assert_send_sync(&ob.subscribe().group_by(|v| v / 2).1);
+    assert_send_sync(&ob.subscribe().unique_by_key(|v| v / 2).1);
+    assert_send_sync(&ob.subscribe().tombstones(1, stream::iter([()])).1);
+    assert_send_sync(&ob.subscribe().validate().1);
+    assert_send_sync(&ob.subscribe().head(1).1);
+    assert_send_sync(&ob.subscribe().padded_head(1, || 0).1);
+    assert_send_sync(&ob.subscribe().dynamic_head(limits()));
+    assert_send_sync(&ob.subscribe().watch_index_stream(limits()));
+    assert_send_sync(&ob.subscribe().tail(1).1);
+    assert_send_sync(&ob.subscribe().dynamic_tail(limits()));
+    assert_send_sync(
+        &ob.subscribe().anchored_window(|v| *v, AnchorSpec::new(0, 0, 1), stream::pending()).1,
+    );
+    assert_send_sync(&ob.subscribe().take_until_closed(future::pending::<()>()).1);
+    assert_send_sync(&ob.subscribe().sort().1);
+    assert_send_sync(&ob.subscribe().sort_by(|a, b| b.cmp(a)).1);
+    assert_send_sync(&ob.subscribe().dynamic_sort_by(u32::cmp, stream::iter([u32::cmp])).1);
+    assert_send_sync(&ob.subscribe().sort_by_key(|v| v / 2).1);
+    assert_send_sync(&ob.subscribe().sort_limit(1).1);
+    assert_send_sync(&ob.subscribe().dynamic_sort_limit(limits()));
+    assert_send_sync(&ob.subscribe().sort_if(true, flags()).1);
+    assert_send_sync(&ob.subscribe().empty_transitions().1);
+    assert_send_sync(&ob.subscribe().len_observable());
+    assert_send_sync(&ob.subscribe().fold(0, |acc, _| acc + 1));
+    assert_send_sync(&ob.subscribe().fold_items(0, |acc, v| acc + v, |acc, v| acc - v));
+    assert_send_sync(&ob.subscribe().sum(|v| *v));
+    assert_send_sync(&ob.subscribe().mean(|v| f64::from(*v)));
+    assert_send_sync(&ob.subscribe().any(|v| v % 2 == 0));
+    assert_send_sync(&ob.subscribe().min_by_key(|v| *v));
+}
+
+#[test]
+fn batched_adapters() {
+    let ob = ObservableVector::<u32>::new();
+
+    assert_send_sync(&ob.subscribe().batched().filter(|v| v % 2 == 0).1);
+    assert_send_sync(&ob.subscribe().batched().head(1).1);
+    assert_send_sync(&ob.subscribe().batched().sort().1);
+}
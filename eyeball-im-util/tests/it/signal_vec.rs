@@ -0,0 +1,95 @@
+use std::sync::Mutex;
+
+use eyeball_im::{ObservableVector, VectorDiff};
+use eyeball_im_util::vector::{ingest_signal_vec, VectorObserverExt};
+use futures_signals::signal_vec::{SignalVecExt, VecDiff};
+use futures_util::FutureExt;
+use imbl::vector;
+use stream_assert::{assert_closed, assert_next_eq, assert_pending};
+
+#[test]
+fn translate() {
+    let mut ob = ObservableVector::<i32>::from(vector![1, 2]);
+    let mut st = ob.subscribe().into_signal_vec().to_stream();
+    assert_next_eq!(st, VecDiff::Replace { values: vec![1, 2] });
+    assert_pending!(st);
+
+    ob.append(vector![3, 4]);
+    assert_next_eq!(st, VecDiff::Push { value: 3 });
+    assert_next_eq!(st, VecDiff::Push { value: 4 });
+
+    ob.push_front(0);
+    assert_next_eq!(st, VecDiff::InsertAt { index: 0, value: 0 });
+    ob.pop_front();
+    assert_next_eq!(st, VecDiff::RemoveAt { index: 0 });
+    ob.pop_back();
+    assert_next_eq!(st, VecDiff::Pop {});
+    ob.set(1, 20);
+    assert_next_eq!(st, VecDiff::UpdateAt { index: 1, value: 20 });
+    ob.move_item(0, 2);
+    assert_next_eq!(st, VecDiff::Move { old_index: 0, new_index: 2 });
+
+    // [20, 3, 1]
+    ob.truncate(1);
+    assert_next_eq!(st, VecDiff::Pop {});
+    assert_next_eq!(st, VecDiff::Pop {});
+    assert_pending!(st);
+
+    ob.clear();
+    assert_next_eq!(st, VecDiff::Clear {});
+
+    drop(ob);
+    assert_closed!(st);
+}
+
+#[test]
+fn reset() {
+    let mut ob = ObservableVector::<i32>::with_capacity(1);
+    let mut st = ob.subscribe().into_signal_vec().to_stream();
+    assert_next_eq!(st, VecDiff::Replace { values: vec![] });
+
+    ob.push_back(1);
+    ob.push_back(2);
+    assert_next_eq!(st, VecDiff::Replace { values: vec![1, 2] });
+
+    // The length is kept track of across resets.
+    ob.truncate(0);
+    assert_next_eq!(st, VecDiff::Pop {});
+    assert_next_eq!(st, VecDiff::Pop {});
+    assert_pending!(st);
+}
+
+#[test]
+fn after_adapter() {
+    let mut ob = ObservableVector::<i32>::new();
+    let mut st = ob.subscribe().filter(|v| v % 2 == 0).into_signal_vec().to_stream();
+    assert_next_eq!(st, VecDiff::Replace { values: Vec::new() });
+
+    ob.append(vector![1, 2, 3, 4]);
+    assert_next_eq!(st, VecDiff::Push { value: 2 });
+    assert_next_eq!(st, VecDiff::Push { value: 4 });
+    assert_pending!(st);
+}
+
+#[test]
+fn ingest() {
+    let mut source = ObservableVector::<i32>::from(vector![3, 1, 2]);
+    let target = Mutex::new(ObservableVector::new());
+    let (values, mut sub) = target.lock().unwrap().subscribe().sort();
+    assert_eq!(values, vector![]);
+
+    let mut fut = Box::pin(ingest_signal_vec(source.subscribe().into_signal_vec(), &target));
+    assert_eq!((&mut fut).now_or_never(), None);
+    assert_eq!(**target.lock().unwrap(), vector![3, 1, 2]);
+    assert_next_eq!(sub, VectorDiff::Append { values: vector![1, 2, 3] });
+
+    source.push_back(0);
+    source.remove(0);
+    assert_eq!((&mut fut).now_or_never(), None);
+    assert_eq!(**target.lock().unwrap(), vector![1, 2, 0]);
+    assert_next_eq!(sub, VectorDiff::PushFront { value: 0 });
+    assert_next_eq!(sub, VectorDiff::PopBack);
+
+    drop(source);
+    assert_eq!(fut.now_or_never(), Some(()));
+}
@@ -1,4 +1,4 @@
-use eyeball_im::{ObservableVector, VectorDiff};
+use eyeball_im::{ObservableVector, ResetReason, VectorDiff};
 use eyeball_im_util::vector::VectorObserverExt;
 use imbl::vector;
 use stream_assert::{assert_closed, assert_next_eq, assert_pending};
@@ -344,23 +344,23 @@ fn set() {
 
     // Another value, that is moved to the left.
     ob.set(0, 'a');
-    assert_next_eq!(sub, VectorDiff::Remove { index: 1 });
-    assert_next_eq!(sub, VectorDiff::Insert { index: 0, value: 'a' });
+    assert_next_eq!(sub, VectorDiff::Set { index: 1, value: 'a' });
+    assert_next_eq!(sub, VectorDiff::Move { from: 1, to: 0 });
 
     // Another value, that is moved to the right.
     ob.set(0, 'f');
-    assert_next_eq!(sub, VectorDiff::Remove { index: 0 });
-    assert_next_eq!(sub, VectorDiff::Insert { index: 2, value: 'f' });
+    assert_next_eq!(sub, VectorDiff::Set { index: 0, value: 'f' });
+    assert_next_eq!(sub, VectorDiff::Move { from: 0, to: 2 });
 
     // Another value, that is moved to the right-most position.
     ob.set(0, 'h');
-    assert_next_eq!(sub, VectorDiff::Remove { index: 2 });
-    assert_next_eq!(sub, VectorDiff::Insert { index: 3, value: 'h' });
+    assert_next_eq!(sub, VectorDiff::Set { index: 2, value: 'h' });
+    assert_next_eq!(sub, VectorDiff::Move { from: 2, to: 3 });
 
     // Same operation, at another index, just for fun.
     ob.set(2, 'f');
-    assert_next_eq!(sub, VectorDiff::Remove { index: 0 });
-    assert_next_eq!(sub, VectorDiff::Insert { index: 1, value: 'f' });
+    assert_next_eq!(sub, VectorDiff::Set { index: 0, value: 'f' });
+    assert_next_eq!(sub, VectorDiff::Move { from: 0, to: 1 });
 
     // Items in the vector have been updated and are not sorted.
     assert_eq!(*ob, vector!['h', 'e', 'f', 'g']);
@@ -388,7 +388,10 @@ fn truncate() {
 
     // Truncate.
     ob.truncate(2);
-    assert_next_eq!(sub, VectorDiff::Truncate { length: 2 });
+    assert_next_eq!(sub, VectorDiff::Remove { index: 5 });
+    assert_next_eq!(sub, VectorDiff::Remove { index: 4 });
+    assert_next_eq!(sub, VectorDiff::Remove { index: 1 });
+    assert_next_eq!(sub, VectorDiff::Remove { index: 0 });
 
     // Items in the vector have been truncated and are not sorted.
     assert_eq!(*ob, vector!['c', 'd']);
@@ -402,6 +405,23 @@ fn truncate() {
     assert_closed!(sub);
 }
 
+#[test]
+fn truncate_unsorted_tail() {
+    let mut ob = ObservableVector::<char>::from(vector!['a', 'd', 'c', 'b']);
+    let (values, mut sub) = ob.subscribe().sort();
+    assert_eq!(values, vector!['a', 'b', 'c', 'd']);
+
+    // The truncated value isn't at the end of the sorted values.
+    ob.truncate(3);
+    assert_next_eq!(sub, VectorDiff::Remove { index: 1 });
+    assert_pending!(sub);
+
+    // Now they are.
+    ob.truncate(1);
+    assert_next_eq!(sub, VectorDiff::Truncate { length: 1 });
+    assert_pending!(sub);
+}
+
 #[test]
 fn reset() {
     let mut ob = ObservableVector::<char>::with_capacity(1);
@@ -418,7 +438,10 @@ fn reset() {
     // get a reset!
     ob.push_back('b');
     ob.push_back('f');
-    assert_next_eq!(sub, VectorDiff::Reset { values: vector!['a', 'b', 'c', 'd', 'f'] });
+    assert_next_eq!(
+        sub,
+        VectorDiff::Reset { values: vector!['a', 'b', 'c', 'd', 'f'], reason: ResetReason::Lagged }
+    );
 
     // Items in the vector have been inserted and are not sorted.
     assert_eq!(*ob, vector!['c', 'd', 'a', 'b', 'f']);
@@ -426,3 +449,22 @@ fn reset() {
     drop(ob);
     assert_closed!(sub);
 }
+
+#[test]
+fn move_item() {
+    let mut ob = ObservableVector::<u32>::from(vector![3, 1, 2]);
+    let (values, mut sub) = ob.subscribe().sort();
+    assert_eq!(values, vector![1, 2, 3]);
+
+    // Moving items doesn't change the sorted order.
+    ob.move_item(0, 2);
+    assert_pending!(sub);
+    assert_eq!(*ob, vector![1, 2, 3]);
+
+    // The unsorted indices have been updated.
+    ob.set(2, 0);
+    assert_next_eq!(sub, VectorDiff::Set { index: 2, value: 0 });
+    assert_next_eq!(sub, VectorDiff::Move { from: 2, to: 0 });
+    ob.remove(0);
+    assert_next_eq!(sub, VectorDiff::Remove { index: 1 });
+}
@@ -1,4 +1,4 @@
-use eyeball_im::{ObservableVector, VectorDiff};
+use eyeball_im::{ObservableVector, ResetReason, VectorDiff};
 use eyeball_im_util::vector::VectorObserverExt;
 use imbl::vector;
 use std::cmp::Ordering;
@@ -326,13 +326,13 @@ fn set() {
     // This time sorting moves the value.
     // Another value, that is moved to the right.
     ob.set(0, 'f');
-    assert_next_eq!(sub, VectorDiff::Remove { index: 2 });
-    assert_next_eq!(sub, VectorDiff::Insert { index: 1, value: 'f' });
+    assert_next_eq!(sub, VectorDiff::Set { index: 2, value: 'f' });
+    assert_next_eq!(sub, VectorDiff::Move { from: 2, to: 1 });
 
     // Same operation, at another index, just for fun.
     ob.set(2, 'f');
-    assert_next_eq!(sub, VectorDiff::Remove { index: 3 });
-    assert_next_eq!(sub, VectorDiff::Insert { index: 1, value: 'f' });
+    assert_next_eq!(sub, VectorDiff::Set { index: 3, value: 'f' });
+    assert_next_eq!(sub, VectorDiff::Move { from: 3, to: 1 });
 
     // Items in the vector have been updated and are not sorted.
     assert_eq!(*ob, vector!['f', 'e', 'f', 'g']);
@@ -361,7 +361,10 @@ fn truncate() {
 
     // Truncate.
     ob.truncate(2);
-    assert_next_eq!(sub, VectorDiff::Truncate { length: 2 });
+    assert_next_eq!(sub, VectorDiff::Remove { index: 5 });
+    assert_next_eq!(sub, VectorDiff::Remove { index: 4 });
+    assert_next_eq!(sub, VectorDiff::Remove { index: 1 });
+    assert_next_eq!(sub, VectorDiff::Remove { index: 0 });
 
     // Items in the vector have been truncated and are not sorted.
     assert_eq!(*ob, vector!['c', 'd']);
@@ -392,7 +395,10 @@ fn reset() {
     // get a reset!
     ob.push_back('b');
     ob.push_back('f');
-    assert_next_eq!(sub, VectorDiff::Reset { values: vector!['f', 'd', 'c', 'b', 'a'] });
+    assert_next_eq!(
+        sub,
+        VectorDiff::Reset { values: vector!['f', 'd', 'c', 'b', 'a'], reason: ResetReason::Lagged }
+    );
 
     // Items in the vector have been inserted and are not sorted.
     assert_eq!(*ob, vector!['c', 'd', 'a', 'b', 'f']);
@@ -1,5 +1,7 @@
-use eyeball_im::{ObservableVector, VectorDiff};
-use eyeball_im_util::vector::VectorObserverExt;
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use eyeball_im::{ObservableVector, ResetReason, VectorDiff};
+use eyeball_im_util::vector::{VectorObserverExt, VectorSubscriberExt};
 use imbl::vector;
 use stream_assert::{assert_closed, assert_next_eq, assert_pending};
 
@@ -336,23 +338,23 @@ fn set() {
 
     // Another value, that is moved to the left.
     ob.set(0, 'a');
-    assert_next_eq!(sub, VectorDiff::Remove { index: 1 });
-    assert_next_eq!(sub, VectorDiff::Insert { index: 0, value: 'a' });
+    assert_next_eq!(sub, VectorDiff::Set { index: 1, value: 'a' });
+    assert_next_eq!(sub, VectorDiff::Move { from: 1, to: 0 });
 
     // Another value, that is moved to the right.
     ob.set(0, 'f');
-    assert_next_eq!(sub, VectorDiff::Remove { index: 0 });
-    assert_next_eq!(sub, VectorDiff::Insert { index: 2, value: 'f' });
+    assert_next_eq!(sub, VectorDiff::Set { index: 0, value: 'f' });
+    assert_next_eq!(sub, VectorDiff::Move { from: 0, to: 2 });
 
     // Another value, that is moved to the right-most position.
     ob.set(0, 'h');
-    assert_next_eq!(sub, VectorDiff::Remove { index: 2 });
-    assert_next_eq!(sub, VectorDiff::Insert { index: 3, value: 'h' });
+    assert_next_eq!(sub, VectorDiff::Set { index: 2, value: 'h' });
+    assert_next_eq!(sub, VectorDiff::Move { from: 2, to: 3 });
 
     // Same operation, at another index, just for fun.
     ob.set(2, 'f');
-    assert_next_eq!(sub, VectorDiff::Remove { index: 0 });
-    assert_next_eq!(sub, VectorDiff::Insert { index: 1, value: 'f' });
+    assert_next_eq!(sub, VectorDiff::Set { index: 0, value: 'f' });
+    assert_next_eq!(sub, VectorDiff::Move { from: 0, to: 1 });
 
     // Items in the vector have been updated and are not sorted.
     assert_eq!(*ob, vector!['h', 'e', 'f', 'g']);
@@ -380,7 +382,10 @@ fn truncate() {
 
     // Truncate.
     ob.truncate(2);
-    assert_next_eq!(sub, VectorDiff::Truncate { length: 2 });
+    assert_next_eq!(sub, VectorDiff::Remove { index: 5 });
+    assert_next_eq!(sub, VectorDiff::Remove { index: 4 });
+    assert_next_eq!(sub, VectorDiff::Remove { index: 1 });
+    assert_next_eq!(sub, VectorDiff::Remove { index: 0 });
 
     // Items in the vector have been truncated and are not sorted.
     assert_eq!(*ob, vector!['c', 'd']);
@@ -410,7 +415,10 @@ fn reset() {
     // get a reset!
     ob.push_back('b');
     ob.push_back('f');
-    assert_next_eq!(sub, VectorDiff::Reset { values: vector!['a', 'b', 'c', 'd', 'f'] });
+    assert_next_eq!(
+        sub,
+        VectorDiff::Reset { values: vector!['a', 'b', 'c', 'd', 'f'], reason: ResetReason::Lagged }
+    );
 
     // Items in the vector have been inserted and are not sorted.
     assert_eq!(*ob, vector!['c', 'd', 'a', 'b', 'f']);
@@ -418,3 +426,62 @@ fn reset() {
     drop(ob);
     assert_closed!(sub);
 }
+
+#[test]
+fn resort_indices() {
+    let ages = Rc::new(RefCell::new(HashMap::from([('a', 1), ('b', 2), ('c', 3), ('d', 4)])));
+    let key_fn = {
+        let ages = ages.clone();
+        move |x: &char| ages.borrow()[x]
+    };
+
+    let mut ob = ObservableVector::<char>::from(vector!['d', 'c', 'b', 'a']);
+    let (values, mut sub) = ob.subscribe().sort_by_key(key_fn);
+    let handle = sub.resort_handle();
+    assert_eq!(values, vector!['a', 'b', 'c', 'd']);
+
+    // Nothing to do if the position doesn't change.
+    ages.borrow_mut().insert('b', 3);
+    handle.resort_indices([2]);
+    assert_pending!(sub);
+
+    // | `ob`  | d c b a |
+    // | `sub` | a b c d |
+    ages.borrow_mut().extend([('a', 5), ('d', 0)]);
+    handle.resort_indices([3, 0, 7]);
+    assert_next_eq!(sub, VectorDiff::Move { from: 3, to: 1 });
+    assert_next_eq!(sub, VectorDiff::Move { from: 0, to: 3 });
+    assert_pending!(sub);
+
+    // Indices are those of the underlying vector.
+    ages.borrow_mut().insert('e', 6);
+    ob.push_front('e');
+    assert_next_eq!(sub, VectorDiff::PushBack { value: 'e' });
+    ages.borrow_mut().insert('c', 7);
+    handle.resort_indices([2]);
+    assert_next_eq!(sub, VectorDiff::Move { from: 2, to: 4 });
+    assert_pending!(sub);
+
+    drop(ob);
+    assert_closed!(sub);
+}
+
+#[test]
+fn resort_indices_batched() {
+    let ages = Rc::new(RefCell::new(HashMap::from([('a', 1), ('b', 2), ('c', 3)])));
+    let key_fn = {
+        let ages = ages.clone();
+        move |x: &char| ages.borrow()[x]
+    };
+
+    let ob = ObservableVector::<char>::from(vector!['a', 'b', 'c']);
+    let (_, mut sub) = ob.subscribe().batched().sort_by_key(key_fn);
+
+    ages.borrow_mut().extend([('a', 4), ('b', 5)]);
+    sub.resort_handle().resort_indices([0, 1]);
+    assert_next_eq!(
+        sub,
+        vec![VectorDiff::Move { from: 0, to: 2 }, VectorDiff::Move { from: 0, to: 2 }]
+    );
+    assert_pending!(sub);
+}
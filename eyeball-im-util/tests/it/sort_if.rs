@@ -0,0 +1,72 @@
+use eyeball::Observable;
+use eyeball_im::{ObservableVector, ResetReason, VectorDiff};
+use eyeball_im_util::vector::VectorObserverExt;
+use imbl::vector;
+use stream_assert::{assert_closed, assert_next_eq, assert_pending};
+
+#[test]
+fn new() {
+    let ob = ObservableVector::<char>::from(vector!['c', 'a', 'b']);
+    let enabled = Observable::new(true);
+    let (values, mut sub) = ob.subscribe().sort_if(true, Observable::subscribe(&enabled));
+
+    assert_eq!(values, vector!['a', 'b', 'c']);
+    assert_pending!(sub);
+
+    drop(ob);
+    assert_closed!(sub);
+}
+
+#[test]
+fn disabled_passes_through() {
+    let mut ob = ObservableVector::<char>::from(vector!['c', 'a']);
+    let enabled = Observable::new(false);
+    let (values, mut sub) = ob.subscribe().sort_if(false, Observable::subscribe(&enabled));
+
+    assert_eq!(values, vector!['c', 'a']);
+
+    ob.push_back('b');
+    assert_next_eq!(sub, VectorDiff::PushBack { value: 'b' });
+    assert_pending!(sub);
+}
+
+#[test]
+fn toggle() {
+    let mut ob = ObservableVector::<char>::from(vector!['a', 'b', 'e', 'c', 'd']);
+    let mut enabled = Observable::new(false);
+    let (_, mut sub) = ob.subscribe().sort_if(false, Observable::subscribe(&enabled));
+
+    // Only `e` is out of place.
+    Observable::set(&mut enabled, true);
+    assert_next_eq!(sub, VectorDiff::Move { from: 2, to: 4 });
+    assert_pending!(sub);
+
+    // Changes while enabled are sorted.
+    ob.push_front('f');
+    assert_next_eq!(sub, VectorDiff::PushBack { value: 'f' });
+    assert_pending!(sub);
+
+    // Back to the original order: f a b e c d.
+    Observable::set(&mut enabled, false);
+    assert_next_eq!(sub, VectorDiff::Move { from: 5, to: 0 });
+    assert_next_eq!(sub, VectorDiff::Move { from: 5, to: 3 });
+    assert_pending!(sub);
+}
+
+#[test]
+fn sort_by_if() {
+    let ob = ObservableVector::<u32>::from(vector![1, 2, 3]);
+    let mut enabled = Observable::new(false);
+    let (values, mut sub) =
+        ob.subscribe().sort_by_if(|a, b| b.cmp(a), false, Observable::subscribe(&enabled));
+
+    assert_eq!(values, vector![1, 2, 3]);
+
+    Observable::set(&mut enabled, true);
+    // Reversing moves more than half of the items, so it's a reset.
+    assert_next_eq!(
+        sub,
+        VectorDiff::Reset { values: vector![3, 2, 1], reason: ResetReason::Update }
+    );
+    assert_pending!(sub);
+}
@@ -0,0 +1,151 @@
+use eyeball::Observable;
+use eyeball_im::{ObservableVector, VectorDiff};
+use eyeball_im_util::vector::{VectorObserverExt, VectorSubscriberExt};
+use imbl::vector;
+use stream_assert::{assert_closed, assert_next_eq, assert_pending};
+
+#[test]
+fn insert_and_remove() {
+    let mut ob = ObservableVector::<u32>::from(vector![5, 1, 4, 2, 3]);
+    let (values, mut sub) = ob.subscribe().sort_limit(3);
+    assert_eq!(values, vector![1, 2, 3]);
+
+    // Enters the view, pushing 3 out.
+    ob.push_front(0);
+    assert_next_eq!(sub, VectorDiff::PopBack);
+    assert_next_eq!(sub, VectorDiff::PushFront { value: 0 });
+    assert_pending!(sub);
+
+    // Outside of the view.
+    ob.insert(2, 8);
+    ob.remove(1);
+    assert_pending!(sub);
+
+    // [0, 8, 1, 4, 2, 3]
+    ob.remove(2);
+    assert_next_eq!(sub, VectorDiff::Remove { index: 1 });
+    assert_next_eq!(sub, VectorDiff::PushBack { value: 3 });
+    assert_pending!(sub);
+
+    drop(ob);
+    assert_closed!(sub);
+}
+
+#[test]
+fn set() {
+    let mut ob = ObservableVector::<u32>::from(vector![0, 5, 1, 4, 2, 3]);
+    let (values, mut sub) = ob.subscribe().sort_limit(3);
+    assert_eq!(values, vector![0, 1, 2]);
+
+    // Leaves the view, 3 takes its place.
+    ob.set(0, 9);
+    assert_next_eq!(sub, VectorDiff::PopFront);
+    assert_next_eq!(sub, VectorDiff::PushBack { value: 3 });
+    assert_pending!(sub);
+
+    // Stays in the view at the same position.
+    ob.set(2, 2);
+    assert_next_eq!(sub, VectorDiff::Set { index: 0, value: 2 });
+    assert_pending!(sub);
+
+    // Stays in the view at another position.
+    ob.set(5, 1);
+    assert_next_eq!(sub, VectorDiff::PopBack);
+    assert_next_eq!(sub, VectorDiff::PushFront { value: 1 });
+    assert_pending!(sub);
+
+    // Enters the view.
+    ob.set(1, 0);
+    assert_next_eq!(sub, VectorDiff::PopBack);
+    assert_next_eq!(sub, VectorDiff::PushFront { value: 0 });
+    assert_pending!(sub);
+
+    // Stays outside of the view.
+    ob.set(0, 7);
+    assert_pending!(sub);
+}
+
+#[test]
+fn truncate() {
+    let mut ob = ObservableVector::<u32>::from(vector![1, 2, 3, 4]);
+    let (_, mut sub) = ob.subscribe().sort_limit(3);
+
+    // The removed items are at the end of the view.
+    ob.truncate(1);
+    assert_next_eq!(sub, VectorDiff::Truncate { length: 1 });
+    assert_pending!(sub);
+
+    ob.append(vector![0, 5, 9]);
+    assert_next_eq!(sub, VectorDiff::PushFront { value: 0 });
+    assert_next_eq!(sub, VectorDiff::PushBack { value: 5 });
+    assert_pending!(sub);
+
+    // [1, 0, 5, 9]
+    ob.truncate(2);
+    assert_next_eq!(sub, VectorDiff::Truncate { length: 2 });
+    assert_pending!(sub);
+
+    ob.append(vector![3, 2]);
+    assert_next_eq!(sub, VectorDiff::PushBack { value: 3 });
+    assert_next_eq!(sub, VectorDiff::PopBack);
+    assert_next_eq!(sub, VectorDiff::PushBack { value: 2 });
+    assert_pending!(sub);
+
+    // [1, 0, 3, 2], the removed item is not at the end of the view.
+    ob.truncate(1);
+    assert_next_eq!(sub, VectorDiff::PopBack);
+    assert_next_eq!(sub, VectorDiff::PopFront);
+    assert_pending!(sub);
+}
+
+#[test]
+fn move_item() {
+    let mut ob = ObservableVector::<u32>::from(vector![3, 1, 2]);
+    let (_, mut sub) = ob.subscribe().sort_limit(2);
+
+    ob.move_item(0, 2);
+    assert_pending!(sub);
+
+    // [1, 2, 3]
+    ob.remove(2);
+    assert_pending!(sub);
+    ob.remove(0);
+    assert_next_eq!(sub, VectorDiff::PopFront);
+    assert_pending!(sub);
+}
+
+#[test]
+fn dynamic_limit() {
+    let mut ob = ObservableVector::<u32>::from(vector![4, 3, 2, 1]);
+    let mut limit = Observable::new(0);
+    let mut sub = ob.subscribe().dynamic_sort_limit(Observable::subscribe(&limit));
+    assert_pending!(sub);
+
+    Observable::set(&mut limit, 2);
+    assert_next_eq!(sub, VectorDiff::Append { values: vector![1, 2] });
+    Observable::set(&mut limit, 3);
+    assert_next_eq!(sub, VectorDiff::PushBack { value: 3 });
+    Observable::set(&mut limit, 1);
+    assert_next_eq!(sub, VectorDiff::Truncate { length: 1 });
+    assert_pending!(sub);
+
+    ob.push_back(0);
+    assert_next_eq!(sub, VectorDiff::PopBack);
+    assert_next_eq!(sub, VectorDiff::PushFront { value: 0 });
+    assert_pending!(sub);
+}
+
+#[test]
+fn batched() {
+    let mut ob = ObservableVector::<u32>::from(vector![3, 1]);
+    let (values, mut sub) = ob.subscribe().batched().sort_limit(1);
+    assert_eq!(values, vector![1]);
+
+    let mut txn = ob.transaction();
+    txn.push_back(0);
+    txn.push_back(5);
+    txn.remove(1);
+    txn.commit();
+    assert_next_eq!(sub, vec![VectorDiff::PopBack, VectorDiff::PushFront { value: 0 }]);
+    assert_pending!(sub);
+}
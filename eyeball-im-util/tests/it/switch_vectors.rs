@@ -0,0 +1,96 @@
+use std::{
+    sync::mpsc::{self, TryRecvError},
+    task::Poll,
+};
+
+use eyeball::Observable;
+use eyeball_im::{ObservableVector, ResetReason, VectorDiff};
+use eyeball_im_util::vector::{switch_vectors, VectorSubscriberExt};
+use futures_util::{stream, StreamExt};
+use imbl::vector;
+use stream_assert::{assert_closed, assert_next_eq, assert_pending};
+
+#[test]
+fn switching() {
+    let mut first = ObservableVector::<u32>::from(vector![1]);
+    let mut second = ObservableVector::<u32>::from(vector![2, 3]);
+    let mut third = ObservableVector::<u32>::new();
+    let (tx, rx) = channel();
+
+    let (values, mut sub) = switch_vectors(first.subscribe(), rx);
+    assert_eq!(values, vector![1]);
+
+    first.push_back(10);
+    assert_next_eq!(sub, VectorDiff::PushBack { value: 10 });
+
+    // Pending diffs of the previous vector are dropped.
+    first.push_back(11);
+    tx.send(second.subscribe()).unwrap();
+    assert_next_eq!(sub, VectorDiff::Reset { values: vector![2, 3], reason: ResetReason::Update });
+    assert_pending!(sub);
+
+    second.pop_front();
+    assert_next_eq!(sub, VectorDiff::PopFront);
+
+    // Only the latest of several switches is used.
+    tx.send(first.subscribe()).unwrap();
+    tx.send(third.subscribe()).unwrap();
+    assert_next_eq!(sub, VectorDiff::Reset { values: vector![], reason: ResetReason::Update });
+    first.clear();
+    second.clear();
+    assert_pending!(sub);
+
+    third.push_front(5);
+    assert_next_eq!(sub, VectorDiff::PushFront { value: 5 });
+
+    // The stream ends once both the switches and the current vector end.
+    drop(tx);
+    assert_pending!(sub);
+    drop(third);
+    assert_closed!(sub);
+}
+
+#[test]
+fn current_ends_first() {
+    let first = ObservableVector::<u32>::from(vector![1]);
+    let second = ObservableVector::<u32>::new();
+    let mut selected = Observable::new(false);
+    let second_sub = Observable::subscribe(&selected).map(|_| second.subscribe());
+
+    let (_, mut sub) = switch_vectors(first.subscribe(), second_sub);
+    drop(first);
+    assert_pending!(sub);
+
+    Observable::set(&mut selected, true);
+    assert_next_eq!(sub, VectorDiff::Reset { values: vector![], reason: ResetReason::Update });
+}
+
+#[test]
+fn batched() {
+    let mut first = ObservableVector::<u32>::new();
+    let second = ObservableVector::<u32>::from(vector![4]);
+
+    let switches = stream::pending().chain(stream::iter([second.subscribe().batched()]));
+    let (_, mut sub) = switch_vectors(first.subscribe().batched(), switches);
+
+    let mut txn = first.transaction();
+    txn.push_back(1);
+    txn.push_back(2);
+    txn.commit();
+    assert_next_eq!(
+        sub,
+        vec![VectorDiff::PushBack { value: 1 }, VectorDiff::PushBack { value: 2 }]
+    );
+}
+
+/// A channel whose receiver is a stream that is pending while there are no
+/// messages, without registering a waker, which is enough for `stream_assert`.
+fn channel<T>() -> (mpsc::Sender<T>, impl futures_core::Stream<Item = T> + Unpin) {
+    let (tx, rx) = mpsc::channel();
+    let stream = stream::poll_fn(move |_| match rx.try_recv() {
+        Ok(value) => Poll::Ready(Some(value)),
+        Err(TryRecvError::Empty) => Poll::Pending,
+        Err(TryRecvError::Disconnected) => Poll::Ready(None),
+    });
+    (tx, stream)
+}
@@ -1,6 +1,6 @@
 use eyeball::Observable;
-use eyeball_im::{ObservableVector, VectorDiff};
-use eyeball_im_util::vector::VectorObserverExt;
+use eyeball_im::{ObservableVector, ResetReason, VectorDiff};
+use eyeball_im_util::vector::{Saturation, VectorObserverExt};
 use imbl::vector;
 use stream_assert::{assert_closed, assert_next_eq, assert_pending};
 
@@ -1144,7 +1144,10 @@ fn reset() {
 
     // Observe a reset, capped to the limit.
     {
-        assert_next_eq!(sub, VectorDiff::Reset { values: vector![15, 16, 17, 18] });
+        assert_next_eq!(
+            sub,
+            VectorDiff::Reset { values: vector![15, 16, 17, 18], reason: ResetReason::Lagged }
+        );
 
         // State of:
         //
@@ -1205,3 +1208,56 @@ async fn limit_stream_wake() {
     // It should be finished now.
     task_hdl.now_or_never().unwrap().unwrap();
 }
+
+#[test]
+fn saturation() {
+    let mut ob: ObservableVector<usize> = ObservableVector::from(vector![1]);
+    let mut limit = Observable::new(2);
+    let (values, mut sub) =
+        ob.subscribe().dynamic_tail_with_initial_value(2, Observable::subscribe(&limit));
+    assert_eq!(values, vector![1]);
+    assert_eq!(sub.saturation(), Saturation { is_full: false, has_more: false });
+
+    let mut saturation = sub.subscribe_saturation();
+    assert_pending!(saturation);
+
+    ob.push_back(2);
+    assert_next_eq!(sub, VectorDiff::PushBack { value: 2 });
+    assert_next_eq!(saturation, Saturation { is_full: true, has_more: false });
+
+    // The first item is pushed out of the view.
+    ob.push_back(3);
+    assert_next_eq!(sub, VectorDiff::PopFront);
+    assert_next_eq!(sub, VectorDiff::PushBack { value: 3 });
+    assert_next_eq!(saturation, Saturation { is_full: true, has_more: true });
+
+    Observable::set(&mut limit, 4);
+    assert_next_eq!(sub, VectorDiff::PushFront { value: 1 });
+    assert_next_eq!(saturation, Saturation { is_full: false, has_more: false });
+    assert_pending!(saturation);
+}
+
+#[test]
+fn move_item() {
+    let mut ob = ObservableVector::<u32>::from(vector![1, 2, 3, 4]);
+    let (values, mut sub) = ob.subscribe().tail(2);
+    assert_eq!(values, vector![3, 4]);
+
+    // [1, 2, 4, 3]
+    ob.move_item(3, 2);
+    assert_next_eq!(sub, VectorDiff::Move { from: 1, to: 0 });
+
+    // [3, 1, 2, 4]: 3 leaves the view, 2 enters it.
+    ob.move_item(3, 0);
+    assert_next_eq!(sub, VectorDiff::Remove { index: 1 });
+    assert_next_eq!(sub, VectorDiff::PushFront { value: 2 });
+
+    // [1, 2, 4, 3]: 3 enters the view, 2 leaves it.
+    ob.move_item(0, 3);
+    assert_next_eq!(sub, VectorDiff::PopFront);
+    assert_next_eq!(sub, VectorDiff::Insert { index: 1, value: 3 });
+
+    // [2, 1, 4, 3]: outside of the view.
+    ob.move_item(0, 1);
+    assert_pending!(sub);
+}
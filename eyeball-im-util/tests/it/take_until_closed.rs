@@ -0,0 +1,43 @@
+use eyeball_im::{ObservableVector, VectorDiff};
+use eyeball_im_util::vector::{VectorObserverExt, VectorSubscriberExt};
+use futures_util::future;
+use imbl::vector;
+use stream_assert::{assert_closed, assert_next_eq, assert_pending};
+use tokio::sync::oneshot;
+
+#[test]
+fn clear_on_close() {
+    let mut ob = ObservableVector::<u32>::from(vector![1]);
+    let (tx, rx) = oneshot::channel::<()>();
+    let (values, mut sub) = ob.subscribe().take_until_closed(rx);
+    assert_eq!(values, vector![1]);
+    assert_pending!(sub);
+
+    ob.push_back(2);
+    assert_next_eq!(sub, VectorDiff::PushBack { value: 2 });
+
+    drop(tx);
+    ob.push_back(3);
+    assert_next_eq!(sub, VectorDiff::Clear);
+    assert_closed!(sub);
+}
+
+#[test]
+fn batched() {
+    let mut ob = ObservableVector::<u32>::new();
+    let (_, mut sub) = ob.subscribe().batched().take_until_closed(future::ready(()));
+
+    ob.push_back(1);
+    assert_next_eq!(sub, vec![VectorDiff::Clear]);
+    assert_closed!(sub);
+}
+
+#[test]
+fn inner_stream_ends() {
+    let ob = ObservableVector::<u32>::new();
+    let (_, mut sub) = ob.subscribe().take_until_closed(future::pending::<()>());
+
+    drop(ob);
+    assert_closed!(sub);
+    assert_closed!(sub);
+}
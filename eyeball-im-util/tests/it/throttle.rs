@@ -0,0 +1,69 @@
+use std::time::Duration;
+
+use eyeball_im::{ObservableVector, ResetReason, VectorDiff};
+use eyeball_im_util::vector::VectorObserverExt;
+use futures_util::{FutureExt, StreamExt};
+use imbl::vector;
+use tokio::time::advance;
+
+#[tokio::test(start_paused = true)]
+async fn throttle() {
+    let mut ob = ObservableVector::<u32>::from(vector![1]);
+    let (values, mut sub) = ob.subscribe().throttle(Duration::from_millis(100));
+    assert_eq!(values, vector![1]);
+    assert!(sub.next().now_or_never().is_none());
+
+    // The first update is emitted right away
+    ob.push_back(2);
+    assert_eq!(sub.next().now_or_never(), Some(Some(vec![VectorDiff::PushBack { value: 2 }])));
+
+    // Further updates are buffered until the interval has passed
+    ob.push_back(3);
+    ob.push_back(4);
+    ob.set(0, 10);
+    ob.set(0, 11);
+    assert!(sub.next().now_or_never().is_none());
+
+    advance(Duration::from_millis(100)).await;
+    assert_eq!(
+        sub.next().now_or_never(),
+        Some(Some(vec![
+            VectorDiff::Append { values: vector![3, 4] },
+            VectorDiff::Set { index: 0, value: 11 },
+        ]))
+    );
+
+    // Nothing happened during the last interval, so the next update is emitted
+    // right away again
+    advance(Duration::from_millis(100)).await;
+    assert!(sub.next().now_or_never().is_none());
+    ob.remove(1);
+    assert_eq!(sub.next().now_or_never(), Some(Some(vec![VectorDiff::Remove { index: 1 }])));
+}
+
+#[tokio::test(start_paused = true)]
+async fn throttle_reset() {
+    let mut ob = ObservableVector::<u32>::new();
+    let (_, mut sub) = ob.subscribe().throttle(Duration::from_millis(100));
+
+    ob.push_back(1);
+    assert_eq!(sub.next().await, Some(vec![VectorDiff::PushBack { value: 1 }]));
+
+    // Everything before a `Clear` is dropped, and appending afterwards turns
+    // it into a `Reset`
+    ob.push_front(0);
+    ob.insert(1, 5);
+    ob.clear();
+    ob.push_back(7);
+    ob.append(vector![8, 9]);
+    assert_eq!(
+        sub.next().await,
+        Some(vec![VectorDiff::Reset { values: vector![7, 8, 9], reason: ResetReason::Update }])
+    );
+
+    // Pending diffs are emitted once the observable is dropped
+    ob.pop_back();
+    drop(ob);
+    assert_eq!(sub.next().await, Some(vec![VectorDiff::PopBack]));
+    assert_eq!(sub.next().await, None);
+}
@@ -0,0 +1,142 @@
+use eyeball::Observable;
+use eyeball_im::{ObservableVector, ResetReason, VectorDiff};
+use eyeball_im_util::vector::{Tombstoned, VectorObserverExt, VectorSubscriberExt};
+use imbl::{vector, Vector};
+use stream_assert::{assert_closed, assert_next_eq, assert_pending};
+
+use Tombstoned::{Item, Tombstone};
+
+#[test]
+fn expiry() {
+    let mut ob = ObservableVector::<u32>::from(vector![1, 2, 3]);
+    let mut tick = Observable::new(());
+    let (_, mut sub) = ob.subscribe().tombstones(2, Observable::subscribe(&tick));
+
+    ob.remove(1);
+    assert_next_eq!(sub, VectorDiff::Set { index: 1, value: Tombstone(2) });
+    Observable::set(&mut tick, ());
+    assert_pending!(sub);
+
+    // Items around the tombstone
+    ob.insert(1, 4);
+    assert_next_eq!(sub, VectorDiff::Insert { index: 2, value: Item(4) });
+    ob.pop_front();
+    assert_next_eq!(sub, VectorDiff::Set { index: 0, value: Tombstone(1) });
+    ob.set(0, 5);
+    assert_next_eq!(sub, VectorDiff::Set { index: 2, value: Item(5) });
+    // New items go after the tombstones in front of them.
+    ob.push_front(0);
+    assert_next_eq!(sub, VectorDiff::Insert { index: 2, value: Item(0) });
+    ob.push_back(6);
+    assert_next_eq!(sub, VectorDiff::PushBack { value: Item(6) });
+    assert_pending!(sub);
+
+    // [†1, †2, 0, 5, 3, 6]
+    Observable::set(&mut tick, ());
+    assert_next_eq!(sub, VectorDiff::Remove { index: 1 });
+    assert_pending!(sub);
+
+    Observable::set(&mut tick, ());
+    assert_next_eq!(sub, VectorDiff::Remove { index: 0 });
+    assert_pending!(sub);
+
+    drop(ob);
+    assert_closed!(sub);
+}
+
+#[test]
+fn undo() {
+    let mut ob = ObservableVector::<u32>::from(vector![1, 2, 1]);
+    let tick = Observable::new(());
+    let (_, mut sub) = ob.subscribe().tombstones(1, Observable::subscribe(&tick));
+
+    ob.remove(1);
+    assert_next_eq!(sub, VectorDiff::Set { index: 1, value: Tombstone(2) });
+
+    // Only a tombstone at the insertion position is revived.
+    ob.insert(0, 2);
+    assert_next_eq!(sub, VectorDiff::PushFront { value: Item(2) });
+    ob.remove(0);
+    assert_next_eq!(sub, VectorDiff::Set { index: 0, value: Tombstone(2) });
+
+    // [†2, 1, †2, 1]
+    ob.insert(1, 2);
+    assert_next_eq!(sub, VectorDiff::Set { index: 2, value: Item(2) });
+
+    // And only if it's equal.
+    ob.insert(0, 3);
+    assert_next_eq!(sub, VectorDiff::Insert { index: 1, value: Item(3) });
+    assert_pending!(sub);
+}
+
+#[test]
+fn truncate_clear_reset() {
+    let mut ob = ObservableVector::<u32>::from(vector![1, 2, 3]);
+    let tick = Observable::new(());
+    let (_, mut sub) = ob.subscribe().tombstones(1, Observable::subscribe(&tick));
+
+    ob.truncate(1);
+    assert_next_eq!(sub, VectorDiff::Set { index: 2, value: Tombstone(3) });
+    assert_next_eq!(sub, VectorDiff::Set { index: 1, value: Tombstone(2) });
+
+    ob.clear();
+    assert_next_eq!(sub, VectorDiff::Clear);
+
+    ob.append(vector![4, 5]);
+    assert_next_eq!(sub, VectorDiff::Append { values: vector![Item(4), Item(5)] });
+    ob.pop_back();
+    assert_next_eq!(sub, VectorDiff::Set { index: 1, value: Tombstone(5) });
+    ob.splice(.., vector![6, 7, 8]);
+    assert_next_eq!(
+        sub,
+        VectorDiff::Reset {
+            values: vector![Item(6), Item(7), Item(8)],
+            reason: ResetReason::Update
+        }
+    );
+    assert_pending!(sub);
+}
+
+#[test]
+fn no_grace_period() {
+    let mut ob = ObservableVector::<u32>::from(vector![1, 2]);
+    let (_, mut sub) =
+        ob.subscribe().batched().tombstones(0, futures_util::stream::pending::<()>());
+
+    let mut txn = ob.transaction();
+    txn.remove(0);
+    txn.push_back(3);
+    txn.commit();
+    assert_next_eq!(
+        sub,
+        vec![VectorDiff::Remove { index: 0 }, VectorDiff::PushBack { value: Item(3) }]
+    );
+    assert_pending!(sub);
+}
+
+#[test]
+fn consistency() {
+    let mut rng = crate::Rng::new(0x1234_5678);
+
+    let mut ob = ObservableVector::from((0..8).collect::<Vector<u32>>());
+    let mut tick = Observable::new(());
+    let (mut values, mut sub) = ob.subscribe().tombstones(3, Observable::subscribe(&tick));
+
+    for step in 0..2000 {
+        if rng.below(12) == 0 {
+            Observable::set(&mut tick, ());
+        } else {
+            crate::random_update(&mut ob, &mut rng, |rng| rng.below(1000) as u32);
+        }
+        crate::apply_ready(&mut sub, &mut values);
+
+        let items: Vector<u32> = values
+            .iter()
+            .filter_map(|v| match v {
+                Item(v) => Some(*v),
+                Tombstone(_) => None,
+            })
+            .collect();
+        assert_eq!(items, *ob, "after step {step}");
+    }
+}
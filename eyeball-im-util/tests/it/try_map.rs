@@ -0,0 +1,93 @@
+use std::cell::RefCell;
+
+use eyeball_im::{ObservableVector, ResetReason, VectorDiff};
+use eyeball_im_util::vector::{VectorObserverExt, VectorSubscriberExt};
+use imbl::vector;
+use stream_assert::{assert_closed, assert_next_eq, assert_pending};
+
+fn parse(s: &str) -> Result<u32, String> {
+    s.parse().map_err(|_| s.to_owned())
+}
+
+#[test]
+fn discard_errors() {
+    let mut ob = ObservableVector::from(vector!["1", "a", "2"]);
+    let (values, mut sub) = ob.subscribe().try_map(parse);
+    assert_eq!(values, vector![1, 2]);
+
+    ob.push_front("b");
+    ob.push_back("3");
+    assert_next_eq!(sub, VectorDiff::PushBack { value: 3 });
+
+    // Items enter and leave the view when they are replaced.
+    // ["b", "1", "a", "2", "3"]
+    ob.set(2, "4");
+    assert_next_eq!(sub, VectorDiff::Insert { index: 1, value: 4 });
+    ob.set(1, "c");
+    assert_next_eq!(sub, VectorDiff::Remove { index: 0 });
+    ob.set(0, "5");
+    assert_next_eq!(sub, VectorDiff::Insert { index: 0, value: 5 });
+    assert_pending!(sub);
+
+    drop(ob);
+    assert_closed!(sub);
+}
+
+#[test]
+fn error_handler() {
+    let errors = RefCell::new(Vec::new());
+    let mut ob = ObservableVector::from(vector!["a", "1"]);
+    let (values, mut sub) =
+        ob.subscribe().try_map_with_error_handler(parse, |e| errors.borrow_mut().push(e));
+    assert_eq!(values, vector![1]);
+    assert_eq!(*errors.borrow(), ["a"]);
+
+    ob.append(vector!["b", "2", "c"]);
+    assert_next_eq!(sub, VectorDiff::Append { values: vector![2] });
+    assert_eq!(*errors.borrow(), ["a", "b", "c"]);
+
+    // Values that aren't mapped again don't produce errors again.
+    ob.move_item(0, 4);
+    ob.remove(0);
+    assert_next_eq!(sub, VectorDiff::Remove { index: 0 });
+    assert_eq!(errors.borrow().len(), 3);
+
+    ob.clear();
+    ob.insert(0, "d");
+    assert_next_eq!(sub, VectorDiff::Clear);
+    assert_pending!(sub);
+    assert_eq!(*errors.borrow(), ["a", "b", "c", "d"]);
+}
+
+#[test]
+fn reset_without_successes() {
+    let mut ob = ObservableVector::with_capacity(1);
+    ob.append(vector!["1", "2"]);
+    let (values, mut sub) = ob.subscribe().try_map(parse);
+    assert_eq!(values, vector![1, 2]);
+
+    ob.clear();
+    ob.push_back("a");
+    assert_next_eq!(sub, VectorDiff::Reset { values: vector![], reason: ResetReason::Lagged });
+    assert_pending!(sub);
+}
+
+#[test]
+fn batched() {
+    let errors = RefCell::new(0);
+    let mut ob = ObservableVector::from(vector!["1", "a"]);
+    let (values, mut sub) =
+        ob.subscribe().batched().try_map_with_error_handler(parse, |_| *errors.borrow_mut() += 1);
+    assert_eq!(values, vector![1]);
+
+    let mut txn = ob.transaction();
+    txn.push_back("b");
+    txn.push_back("2");
+    txn.set(1, "3");
+    txn.commit();
+    assert_next_eq!(
+        sub,
+        vec![VectorDiff::PushBack { value: 2 }, VectorDiff::Insert { index: 1, value: 3 }]
+    );
+    assert_eq!(*errors.borrow(), 2);
+}
@@ -0,0 +1,50 @@
+use eyeball_im::{ObservableVector, VectorDiff};
+use eyeball_im_util::vector::{VectorObserverExt, VectorSubscriberExt};
+use futures_util::stream;
+use imbl::{vector, Vector};
+use stream_assert::{assert_closed, assert_next_eq, assert_pending};
+
+#[test]
+fn batched() {
+    let mut ob = ObservableVector::<u32>::from(vector![1, 2]);
+    let (values, mut sub) = ob.subscribe().batched().unbatched();
+    assert_eq!(values, vector![1, 2]);
+    assert_pending!(sub);
+
+    let mut txn = ob.transaction();
+    txn.push_back(3);
+    txn.set(0, 0);
+    txn.remove(1);
+    txn.commit();
+    assert_next_eq!(sub, VectorDiff::PushBack { value: 3 });
+    assert_next_eq!(sub, VectorDiff::Set { index: 0, value: 0 });
+    assert_next_eq!(sub, VectorDiff::Remove { index: 1 });
+    assert_pending!(sub);
+
+    ob.clear();
+    assert_next_eq!(sub, VectorDiff::Clear);
+
+    drop(ob);
+    assert_closed!(sub);
+}
+
+#[test]
+fn empty_batches() {
+    let batches = stream::iter([vec![], vec![VectorDiff::PushBack { value: 1 }], vec![]]);
+    let (_, mut sub) = (Vector::<u32>::new(), batches).unbatched();
+
+    assert_next_eq!(sub, VectorDiff::PushBack { value: 1 });
+    assert_closed!(sub);
+}
+
+#[test]
+fn not_batched() {
+    let mut ob = ObservableVector::<u32>::new();
+    let (_, mut sub) = ob.subscribe().unbatched();
+
+    ob.push_back(1);
+    ob.push_front(0);
+    assert_next_eq!(sub, VectorDiff::PushBack { value: 1 });
+    assert_next_eq!(sub, VectorDiff::PushFront { value: 0 });
+    assert_pending!(sub);
+}
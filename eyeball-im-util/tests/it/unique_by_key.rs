@@ -0,0 +1,109 @@
+use eyeball_im::{ObservableVector, VectorDiff};
+use eyeball_im_util::vector::{VectorObserverExt, VectorSubscriberExt};
+use imbl::{vector, Vector};
+use stream_assert::{assert_closed, assert_next_eq, assert_pending};
+
+#[test]
+fn insert_and_remove() {
+    let mut ob = ObservableVector::<u32>::from(vector![10, 20, 11]);
+    let (values, mut sub) = ob.subscribe().unique_by_key(|v| v / 10);
+    assert_eq!(values, vector![10, 20]);
+
+    // A duplicate before the retained occurrence takes its place
+    ob.push_front(12);
+    assert_next_eq!(sub, VectorDiff::Set { index: 0, value: 12 });
+    assert_pending!(sub);
+
+    // It is removed from its position in the view otherwise
+    ob.insert(0, 21);
+    assert_next_eq!(sub, VectorDiff::Insert { index: 0, value: 21 });
+    assert_next_eq!(sub, VectorDiff::Remove { index: 2 });
+    assert_pending!(sub);
+
+    // Removing a later duplicate doesn't change anything
+    ob.pop_back();
+    assert_pending!(sub);
+
+    // Removing the retained occurrence shows the next one
+    ob.remove(0);
+    assert_next_eq!(sub, VectorDiff::Remove { index: 0 });
+    assert_next_eq!(sub, VectorDiff::Insert { index: 1, value: 20 });
+    assert_pending!(sub);
+
+    drop(ob);
+    assert_closed!(sub);
+}
+
+#[test]
+fn set() {
+    let mut ob = ObservableVector::<u32>::from(vector![10, 20, 11]);
+    let (_, mut sub) = ob.subscribe().unique_by_key(|v| v / 10);
+
+    // Same key
+    ob.set(0, 13);
+    assert_next_eq!(sub, VectorDiff::Set { index: 0, value: 13 });
+    ob.set(2, 14);
+    assert_pending!(sub);
+
+    // Different key
+    ob.set(0, 30);
+    assert_next_eq!(sub, VectorDiff::Remove { index: 0 });
+    assert_next_eq!(sub, VectorDiff::Insert { index: 1, value: 14 });
+    assert_next_eq!(sub, VectorDiff::Insert { index: 0, value: 30 });
+    assert_pending!(sub);
+}
+
+#[test]
+fn append_and_truncate() {
+    let mut ob = ObservableVector::<u32>::from(vector![10]);
+    let (_, mut sub) = ob.subscribe().unique_by_key(|v| v / 10);
+
+    ob.append(vector![11, 20, 21, 30]);
+    assert_next_eq!(sub, VectorDiff::Append { values: vector![20, 30] });
+
+    ob.truncate(4);
+    assert_next_eq!(sub, VectorDiff::Truncate { length: 2 });
+    ob.truncate(3);
+    assert_pending!(sub);
+}
+
+#[test]
+fn batched() {
+    let mut ob = ObservableVector::<u32>::from(vector![10]);
+    let (_, mut sub) = ob.subscribe().batched().unique_by_key(|v| v / 10);
+
+    let mut txn = ob.transaction();
+    txn.push_back(11);
+    txn.push_back(20);
+    txn.pop_front();
+    txn.commit();
+    assert_next_eq!(
+        sub,
+        vec![VectorDiff::Insert { index: 1, value: 20 }, VectorDiff::Set { index: 0, value: 11 },]
+    );
+    assert_pending!(sub);
+}
+
+/// Apply random updates to the vector, and check that the emitted diffs always
+/// result in the expected view.
+#[test]
+fn consistency() {
+    let mut rng = crate::Rng::new(0x2545_f491);
+
+    let key = |v: &u32| v % 5;
+    let mut ob = ObservableVector::from((0..8).collect::<Vector<u32>>());
+    let (mut values, mut sub) = ob.subscribe().unique_by_key(key);
+
+    for step in 0..2000 {
+        crate::random_update(&mut ob, &mut rng, |rng| rng.below(1000) as u32);
+        crate::apply_ready(&mut sub, &mut values);
+
+        let mut expected = Vector::new();
+        for (idx, v) in ob.iter().enumerate() {
+            if !ob.iter().take(idx).any(|w| key(w) == key(v)) {
+                expected.push_back(*v);
+            }
+        }
+        assert_eq!(values, expected, "after step {step}");
+    }
+}
@@ -0,0 +1,67 @@
+use eyeball_im::{ObservableVector, ResetReason, VectorDiff};
+use eyeball_im_util::vector::VectorObserverExt;
+use futures_util::stream;
+use imbl::vector;
+use stream_assert::{assert_closed, assert_next_eq, assert_pending};
+
+#[test]
+fn passes_well_formed_diffs() {
+    let mut ob = ObservableVector::<u32>::from(vector![1, 2]);
+    let (values, mut sub) = ob.subscribe().validate();
+    assert_eq!(values, vector![1, 2]);
+
+    ob.insert(2, 3);
+    assert_next_eq!(sub, VectorDiff::Insert { index: 2, value: 3 });
+    ob.move_item(0, 2);
+    assert_next_eq!(sub, VectorDiff::Move { from: 0, to: 2 });
+    ob.truncate(1);
+    assert_next_eq!(sub, VectorDiff::Truncate { length: 1 });
+    ob.pop_back();
+    assert_next_eq!(sub, VectorDiff::PopBack);
+    assert_pending!(sub);
+}
+
+#[test]
+fn malformed_diffs() {
+    let diffs = stream::iter([
+        VectorDiff::PopFront,
+        VectorDiff::PopFront,
+        VectorDiff::PopBack,
+        VectorDiff::Set { index: 1, value: 7 },
+        VectorDiff::Truncate { length: 2 },
+        VectorDiff::PushBack { value: 3 },
+        VectorDiff::Move { from: 0, to: 4 },
+    ]);
+    let (_, mut sub) = (vector![1], diffs).validate();
+
+    let reset = |values| VectorDiff::Reset { values, reason: ResetReason::Lagged };
+    assert_next_eq!(sub, VectorDiff::PopFront);
+    assert_next_eq!(sub, reset(vector![]));
+    assert_next_eq!(sub, reset(vector![]));
+    assert_next_eq!(sub, reset(vector![]));
+    assert_next_eq!(sub, reset(vector![]));
+    assert_next_eq!(sub, VectorDiff::PushBack { value: 3 });
+    assert_next_eq!(sub, reset(vector![3]));
+    assert_closed!(sub);
+}
+
+#[test]
+fn lenient_sort() {
+    // Without `validate`, `Sort` would panic on the out-of-bounds removal.
+    let diffs = stream::iter([
+        vec![VectorDiff::PushBack { value: 1 }, VectorDiff::Remove { index: 3 }],
+        vec![VectorDiff::PushFront { value: 0 }],
+    ]);
+    let (values, mut sub) = (vector![5, 2], diffs).validate().sort();
+    assert_eq!(values, vector![2, 5]);
+
+    assert_next_eq!(
+        sub,
+        vec![
+            VectorDiff::PushFront { value: 1 },
+            VectorDiff::Reset { values: vector![1, 2, 5], reason: ResetReason::Lagged },
+        ]
+    );
+    assert_next_eq!(sub, vec![VectorDiff::PushFront { value: 0 }]);
+    assert_closed!(sub);
+}
@@ -0,0 +1,94 @@
+use eyeball::SharedObservable;
+use eyeball_im::ObservableVector;
+use eyeball_im_util::vector::{VectorObserverExt, VectorSubscriberExt};
+use imbl::vector;
+use stream_assert::{assert_closed, assert_next_eq, assert_pending};
+
+#[test]
+fn element_changes() {
+    let mut ob = ObservableVector::<u32>::from(vector![0, 1, 2, 3]);
+    let index = SharedObservable::new(2);
+    let mut sub = ob.subscribe().watch_index_stream(index.subscribe_reset());
+    assert_next_eq!(sub, Some(2));
+
+    // Changes after the index
+    ob.push_back(4);
+    ob.set(3, 30);
+    ob.truncate(3);
+    assert_pending!(sub);
+
+    // The element itself
+    ob.set(2, 20);
+    assert_next_eq!(sub, Some(20));
+
+    // Shifts from before the index
+    ob.remove(0);
+    assert_next_eq!(sub, None);
+    ob.insert(1, 10);
+    assert_next_eq!(sub, Some(20));
+    ob.move_item(0, 2);
+    assert_next_eq!(sub, Some(1));
+
+    // The index going out of bounds and back
+    ob.truncate(2);
+    assert_next_eq!(sub, None);
+    ob.push_back(5);
+    assert_next_eq!(sub, Some(5));
+    ob.clear();
+    assert_next_eq!(sub, None);
+    ob.clear();
+    assert_pending!(sub);
+    ob.append(vector![6, 7, 8]);
+    assert_next_eq!(sub, Some(8));
+
+    drop(ob);
+    assert_closed!(sub);
+}
+
+#[test]
+fn index_changes() {
+    let mut ob = ObservableVector::<char>::from(vector!['a', 'b', 'c']);
+    let index = SharedObservable::new(0);
+    let mut sub = ob.subscribe().watch_index_stream(index.subscribe());
+
+    // Nothing until the first index is known
+    ob.set(0, 'z');
+    assert_pending!(sub);
+
+    index.set(1);
+    assert_next_eq!(sub, Some('b'));
+    index.set(1);
+    assert_pending!(sub);
+    index.set(0);
+    assert_next_eq!(sub, Some('z'));
+    index.set(3);
+    assert_next_eq!(sub, None);
+    ob.push_back('d');
+    assert_next_eq!(sub, Some('d'));
+
+    // The last index is kept once the index stream ends
+    drop(index);
+    ob.set(3, 'y');
+    assert_next_eq!(sub, Some('y'));
+}
+
+#[test]
+fn batched() {
+    let mut ob = ObservableVector::<u32>::from(vector![0, 1, 2]);
+    let index = SharedObservable::new(1);
+    let mut sub = ob.subscribe().batched().watch_index_stream(index.subscribe_reset());
+    assert_next_eq!(sub, Some(1));
+
+    let mut txn = ob.transaction();
+    txn.push_front(10);
+    txn.pop_front();
+    txn.push_back(3);
+    txn.commit();
+    assert_next_eq!(sub, Some(1));
+
+    let mut txn = ob.transaction();
+    txn.push_back(4);
+    txn.set(2, 20);
+    txn.commit();
+    assert_pending!(sub);
+}
@@ -0,0 +1,141 @@
+use eyeball::SharedObservable;
+use eyeball_im::{ObservableVector, VectorDiff};
+use eyeball_im_util::vector::{
+    VectorObserverExt, VectorSubscriberExt, WindowSpec, WindowSpecStream,
+};
+use imbl::{vector, Vector};
+use stream_assert::{assert_closed, assert_next_eq, assert_pending};
+
+#[test]
+fn updates_within_window() {
+    let mut ob = ObservableVector::<u32>::from(vector![0, 1, 2, 3, 4]);
+    let spec = SharedObservable::new(WindowSpec::new(1, 3));
+    let (values, mut sub) =
+        ob.subscribe().dynamic_window_with_initial_value(spec.get(), spec.subscribe());
+    assert_eq!(values, vector![1, 2, 3]);
+
+    // Before the window: everything shifts by one
+    ob.push_front(10);
+    assert_next_eq!(sub, VectorDiff::PopBack);
+    assert_next_eq!(sub, VectorDiff::PushFront { value: 0 });
+
+    // Inside the window
+    ob.insert(2, 11);
+    assert_next_eq!(sub, VectorDiff::PopBack);
+    assert_next_eq!(sub, VectorDiff::Insert { index: 1, value: 11 });
+    ob.remove(2);
+    assert_next_eq!(sub, VectorDiff::Remove { index: 1 });
+    assert_next_eq!(sub, VectorDiff::PushBack { value: 2 });
+
+    // After the window
+    ob.push_back(12);
+    ob.set(5, 13);
+    assert_pending!(sub);
+
+    ob.truncate(2);
+    assert_next_eq!(sub, VectorDiff::Truncate { length: 1 });
+    ob.append(vector![20, 21, 22]);
+    assert_next_eq!(sub, VectorDiff::Append { values: vector![20, 21] });
+
+    drop(ob);
+    assert_closed!(sub);
+}
+
+#[test]
+fn window_changes() {
+    let mut ob = ObservableVector::from((0..10).collect::<Vector<u32>>());
+    let spec = SharedObservable::new(WindowSpec::new(2, 3));
+    let (values, mut sub) =
+        ob.subscribe().batched().dynamic_window_with_initial_value(spec.get(), spec.subscribe());
+    assert_eq!(values, vector![2, 3, 4]);
+
+    // Changing the offset and the length at once results in a single batch
+    spec.set(WindowSpec::new(1, 5));
+    assert_next_eq!(
+        sub,
+        vec![VectorDiff::PushFront { value: 1 }, VectorDiff::Append { values: vector![5] }]
+    );
+    assert_pending!(sub);
+
+    spec.set(WindowSpec::new(3, 2));
+    assert_next_eq!(
+        sub,
+        vec![VectorDiff::Truncate { length: 4 }, VectorDiff::PopFront, VectorDiff::PopFront]
+    );
+
+    // No overlap
+    spec.set(WindowSpec::new(8, 5));
+    assert_next_eq!(sub, vec![VectorDiff::Clear, VectorDiff::Append { values: vector![8, 9] }]);
+
+    // Nothing changes for the visible items
+    spec.set(WindowSpec::new(8, 10));
+    assert_pending!(sub);
+
+    spec.set(WindowSpec::new(8, 0));
+    assert_next_eq!(sub, vec![VectorDiff::Clear]);
+
+    ob.push_back(10);
+    assert_pending!(sub);
+}
+
+#[test]
+fn dynamic() {
+    let ob = ObservableVector::<u32>::from(vector![1, 2, 3]);
+    let spec = SharedObservable::new(WindowSpec::new(1, 5));
+    let mut sub = ob.subscribe().dynamic_window(spec.subscribe_reset());
+
+    assert_next_eq!(sub, VectorDiff::Append { values: vector![2, 3] });
+    assert_pending!(sub);
+}
+
+#[test]
+fn separate_offset_and_len() {
+    let ob = ObservableVector::<u32>::from(vector![0, 1, 2, 3, 4, 5]);
+    let offset = SharedObservable::new(0);
+    let len = SharedObservable::new(2);
+    let spec_stream =
+        WindowSpecStream::new(WindowSpec::new(0, 2), offset.subscribe(), len.subscribe());
+    let (values, mut sub) =
+        ob.subscribe().dynamic_window_with_initial_value(spec_stream.spec(), spec_stream);
+    assert_eq!(values, vector![0, 1]);
+
+    // Changing both at once results in a single window change
+    offset.set(3);
+    len.set(3);
+    assert_next_eq!(sub, VectorDiff::Clear);
+    assert_next_eq!(sub, VectorDiff::Append { values: vector![3, 4, 5] });
+    assert_pending!(sub);
+    assert_eq!(sub.spec(), WindowSpec::new(3, 3));
+
+    // Setting the same values doesn't change the window
+    offset.set(3);
+    assert_pending!(sub);
+
+    drop(offset);
+    len.set(1);
+    assert_next_eq!(sub, VectorDiff::Truncate { length: 1 });
+}
+
+/// Apply random updates to the vector and the window, and check that the
+/// emitted diffs always result in the expected window.
+#[test]
+fn consistency() {
+    let mut rng = crate::Rng::new(0x2545_f491);
+    let mut ob = ObservableVector::from((0..8).collect::<Vector<u32>>());
+    let spec = SharedObservable::new(WindowSpec::new(2, 4));
+    let (mut values, mut sub) =
+        ob.subscribe().dynamic_window_with_initial_value(spec.get(), spec.subscribe());
+
+    for step in 0..2000 {
+        if rng.below(12) == 0 {
+            spec.set(WindowSpec::new(rng.below(12), rng.below(8)));
+        } else {
+            crate::random_update(&mut ob, &mut rng, |rng| rng.below(1000) as u32);
+        }
+        crate::apply_ready(&mut sub, &mut values);
+
+        let spec = spec.get();
+        let expected: Vector<u32> = ob.iter().skip(spec.offset).take(spec.len).copied().collect();
+        assert_eq!(values, expected, "after step {step}");
+    }
+}
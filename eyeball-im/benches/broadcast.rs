@@ -0,0 +1,105 @@
+#![allow(missing_docs)]
+// Benchmarks are not subject to the MSRV, divan requires a newer toolchain.
+#![allow(clippy::incompatible_msrv)]
+
+use std::{
+    pin::Pin,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    task::{Context, Poll, Wake, Waker},
+};
+
+use divan::{black_box, main, Bencher};
+use eyeball_im::ObservableVector;
+use futures_core::Stream;
+
+struct NoopWaker;
+
+impl Wake for NoopWaker {
+    fn wake(self: Arc<Self>) {}
+}
+
+/// The number of times a `CountedClone` was cloned.
+static CLONES: AtomicUsize = AtomicUsize::new(0);
+
+/// A value that keeps track of how often it is cloned.
+struct CountedClone(Box<[u8; 64]>);
+
+impl Clone for CountedClone {
+    fn clone(&self) -> Self {
+        CLONES.fetch_add(1, Ordering::Relaxed);
+        Self(self.0.clone())
+    }
+}
+
+/// Poll all the given streams until they are pending.
+fn drain<S: Stream + Unpin>(streams: &mut [S], cx: &mut Context<'_>) {
+    for stream in streams {
+        while let Poll::Ready(Some(item)) = Pin::new(&mut *stream).poll_next(cx) {
+            black_box(item);
+        }
+    }
+}
+
+#[divan::bench(args = [0, 1, 4, 16])]
+fn push_back(b: Bencher<'_, '_>, n: usize) {
+    let waker = Waker::from(Arc::new(NoopWaker));
+    b.with_inputs(|| {
+        let ob = ObservableVector::<Box<[u8; 64]>>::with_capacity(1024);
+        let subs: Vec<_> = (0..n).map(|_| ob.subscribe().into_stream()).collect();
+        (ob, subs)
+    })
+    .bench_values(|(mut ob, mut subs)| {
+        let mut cx = Context::from_waker(&waker);
+        for i in 0..=255 {
+            ob.push_back(black_box(Box::new([i; 64])));
+            drain(&mut subs, &mut cx);
+        }
+    });
+}
+
+#[divan::bench(args = [0, 1, 4, 16])]
+fn transaction(b: Bencher<'_, '_>, n: usize) {
+    let waker = Waker::from(Arc::new(NoopWaker));
+    b.with_inputs(|| {
+        let ob = ObservableVector::<Box<[u8; 64]>>::with_capacity(1024);
+        let subs: Vec<_> = (0..n).map(|_| ob.subscribe().into_batched_stream()).collect();
+        (ob, subs)
+    })
+    .bench_values(|(mut ob, mut subs)| {
+        let mut cx = Context::from_waker(&waker);
+        for i in 0..=63 {
+            let mut txn = ob.transaction();
+            for _ in 0..4 {
+                txn.push_back(black_box(Box::new([i; 64])));
+            }
+            txn.commit();
+            drain(&mut subs, &mut cx);
+        }
+    });
+}
+
+#[divan::bench(args = [0, 1])]
+fn push_back_clones(b: Bencher<'_, '_>, n: usize) {
+    let waker = Waker::from(Arc::new(NoopWaker));
+    CLONES.store(0, Ordering::Relaxed);
+    b.with_inputs(|| {
+        let ob = ObservableVector::<CountedClone>::with_capacity(1024);
+        let subs: Vec<_> = (0..n).map(|_| ob.subscribe().into_stream()).collect();
+        (ob, subs)
+    })
+    .bench_values(|(mut ob, mut subs)| {
+        let mut cx = Context::from_waker(&waker);
+        for i in 0..=255 {
+            ob.push_back(black_box(CountedClone(Box::new([i; 64]))));
+            drain(&mut subs, &mut cx);
+        }
+    });
+
+    // Without subscribers, nothing needs to be cloned for broadcasting.
+    if n == 0 {
+        assert_eq!(CLONES.load(Ordering::Relaxed), 0);
+    }
+}
@@ -0,0 +1,139 @@
+//! [`arbitrary::Arbitrary`](https://docs.rs/arbitrary) implementations and
+//! helpers for fuzzing code that consumes diffs.
+//!
+//! The [`Arbitrary`] implementation of [`VectorDiff`] generates diffs with
+//! arbitrary indices, so a sequence of them is usually not valid for any
+//! vector. This is useful for checking that code consuming diffs from
+//! untrusted sources doesn't misbehave. To generate valid sequences of diffs,
+//! apply arbitrary operations to an [`ObservableVector`] with
+//! [`apply_arbitrary_ops`] and use the diffs of its subscribers instead:
+//!
+//! ```
+//! use arbitrary::Unstructured;
+//! use eyeball_im::{arbitrary::apply_arbitrary_ops, ObservableVector};
+//!
+//! # let fuzzer_input = &[1, 2, 3, 4, 5, 6, 7, 8];
+//! let mut u = Unstructured::new(fuzzer_input);
+//! let mut ob: ObservableVector<u8> = u.arbitrary()?;
+//! let (values, stream) = ob.subscribe().into_values_and_batched_stream();
+//! apply_arbitrary_ops(&mut ob, &mut u)?;
+//!
+//! // Feed `values` and the diffs from `stream` into the code under test …
+//! # Ok::<(), arbitrary::Error>(())
+//! ```
+
+use ::arbitrary::{Arbitrary, Result, Unstructured};
+use imbl::Vector;
+
+use crate::{ObservableVector, ResetReason, VectorDiff};
+
+impl<'a> Arbitrary<'a> for ResetReason {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(if u.arbitrary()? { Self::Lagged } else { Self::Update })
+    }
+}
+
+impl<'a, T> Arbitrary<'a> for VectorDiff<T>
+where
+    T: Arbitrary<'a> + Clone,
+{
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let diff = match u.int_in_range(0..=11_u8)? {
+            0 => Self::Append { values: arbitrary_vector(u)? },
+            1 => Self::Clear,
+            2 => Self::PushFront { value: u.arbitrary()? },
+            3 => Self::PushBack { value: u.arbitrary()? },
+            4 => Self::PopFront,
+            5 => Self::PopBack,
+            6 => Self::Insert { index: u.arbitrary()?, value: u.arbitrary()? },
+            7 => Self::Set { index: u.arbitrary()?, value: u.arbitrary()? },
+            8 => Self::Remove { index: u.arbitrary()? },
+            9 => Self::Truncate { length: u.arbitrary()? },
+            10 => Self::Move { from: u.arbitrary()?, to: u.arbitrary()? },
+            _ => Self::Reset { values: arbitrary_vector(u)?, reason: u.arbitrary()? },
+        };
+
+        Ok(diff)
+    }
+}
+
+impl<'a, T> Arbitrary<'a> for ObservableVector<T>
+where
+    T: Arbitrary<'a> + Clone + 'static,
+{
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(arbitrary_vector(u)?.into())
+    }
+}
+
+/// Apply a single arbitrary operation to the given [`ObservableVector`].
+///
+/// Unlike an arbitrary [`VectorDiff`], the operation is always valid for the
+/// current values, so this never panics.
+pub fn apply_arbitrary_op<'a, T>(
+    vector: &mut ObservableVector<T>,
+    u: &mut Unstructured<'a>,
+) -> Result<()>
+where
+    T: Arbitrary<'a> + Clone + 'static,
+{
+    let len = vector.len();
+    match u.int_in_range(0..=10_u8)? {
+        0 => vector.append(arbitrary_vector(u)?),
+        1 => vector.clear(),
+        2 => vector.push_front(u.arbitrary()?),
+        3 => vector.push_back(u.arbitrary()?),
+        4 => {
+            let index = u.int_in_range(0..=len)?;
+            vector.insert(index, u.arbitrary()?);
+        }
+        5 => vector.truncate(u.int_in_range(0..=len)?),
+        // All the other operations require an element.
+        _ if len == 0 => vector.push_back(u.arbitrary()?),
+        6 => {
+            vector.pop_front();
+        }
+        7 => {
+            vector.pop_back();
+        }
+        8 => {
+            let index = u.choose_index(len)?;
+            vector.set(index, u.arbitrary()?);
+        }
+        9 => {
+            vector.remove(u.choose_index(len)?);
+        }
+        _ => {
+            let from = u.choose_index(len)?;
+            let to = u.choose_index(len)?;
+            vector.move_item(from, to);
+        }
+    }
+
+    Ok(())
+}
+
+/// Apply arbitrary operations to the given [`ObservableVector`] until `u` is
+/// exhausted.
+///
+/// See [`apply_arbitrary_op`] for details.
+pub fn apply_arbitrary_ops<'a, T>(
+    vector: &mut ObservableVector<T>,
+    u: &mut Unstructured<'a>,
+) -> Result<()>
+where
+    T: Arbitrary<'a> + Clone + 'static,
+{
+    while !u.is_empty() {
+        apply_arbitrary_op(vector, u)?;
+    }
+
+    Ok(())
+}
+
+fn arbitrary_vector<'a, T>(u: &mut Unstructured<'a>) -> Result<Vector<T>>
+where
+    T: Arbitrary<'a> + Clone,
+{
+    u.arbitrary_iter()?.collect()
+}
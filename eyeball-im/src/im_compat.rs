@@ -0,0 +1,25 @@
+//! Conversions between [`im::Vector`] and [`imbl::Vector`].
+//!
+//! The two crates share their origins, but their vector types have different
+//! internal layouts, so converting between them has to copy the elements
+//! (which is cheap for reference-counted elements).
+
+use imbl::Vector;
+
+use crate::ObservableVector;
+
+/// Convert an [`im::Vector`] into an [`imbl::Vector`].
+pub fn vector_from_im<T: Clone>(values: im::Vector<T>) -> Vector<T> {
+    values.into_iter().collect()
+}
+
+/// Convert an [`imbl::Vector`] into an [`im::Vector`].
+pub fn vector_into_im<T: Clone>(values: Vector<T>) -> im::Vector<T> {
+    values.into_iter().collect()
+}
+
+impl<T: Clone + 'static> From<im::Vector<T>> for ObservableVector<T> {
+    fn from(values: im::Vector<T>) -> Self {
+        vector_from_im(values).into()
+    }
+}
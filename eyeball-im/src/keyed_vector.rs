@@ -0,0 +1,538 @@
+use std::{borrow::Borrow, fmt, hash::Hash, ops};
+
+use imbl::{HashSet, Vector};
+use tokio::sync::broadcast::{self, Sender};
+
+use crate::{ResetReason, VectorDiff};
+
+mod subscriber;
+
+pub use self::subscriber::{
+    KeyedVectorSubscriber, KeyedVectorSubscriberBatchedStream, KeyedVectorSubscriberStream,
+};
+
+/// An ordered list of keyed elements that broadcasts any changes made to it.
+///
+/// Every element has a key that is unique within the list, and every
+/// [`KeyedVectorDiff`] carries the key of the element it affects. This makes
+/// it easy to match updates to the things that were created from the
+/// elements, like UI widgets, without having to track how the indices of the
+/// elements shift around.
+///
+/// Elements can be updated and removed by their key through
+/// [`set_by_key`][Self::set_by_key] and [`remove_by_key`][Self::remove_by_key].
+/// Looking up an element by its key takes linear time.
+pub struct ObservableKeyedVector<K, T> {
+    values: Vector<(K, T)>,
+    keys: HashSet<K>,
+    sender: Sender<KeyedVectorBroadcastMessage<K, T>>,
+}
+
+impl<K, T> ObservableKeyedVector<K, T>
+where
+    K: Hash + Eq + Clone + 'static,
+    T: Clone + 'static,
+{
+    /// Create a new `ObservableKeyedVector`.
+    ///
+    /// As of the time of writing, this is equivalent to
+    /// `ObservableKeyedVector::with_capacity(16)`, but the internal buffer
+    /// capacity is subject to change in non-breaking releases.
+    ///
+    /// See [`with_capacity`][Self::with_capacity] for details about the buffer
+    /// capacity.
+    pub fn new() -> Self {
+        Self::with_capacity(16)
+    }
+
+    /// Create a new `ObservableKeyedVector` with the given capacity for the
+    /// inner buffer.
+    ///
+    /// Up to `capacity` updates that have not been received by all of the
+    /// subscribers yet will be retained in the inner buffer. If an update
+    /// happens while the buffer is at capacity, the oldest update is discarded
+    /// from it and all subscribers that have not yet received it will instead
+    /// see [`KeyedVectorDiff::Reset`] as the next update.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the capacity is `0`, or larger than `usize::MAX / 2`.
+    pub fn with_capacity(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+        Self { values: Vector::new(), keys: HashSet::new(), sender }
+    }
+
+    /// Turn the `ObservableKeyedVector` back into a regular `Vector` of
+    /// key-value pairs.
+    pub fn into_inner(self) -> Vector<(K, T)> {
+        self.values
+    }
+
+    /// Obtain a new subscriber.
+    ///
+    /// If you put the `ObservableKeyedVector` behind a lock, it is highly
+    /// recommended to make access of the elements and subscribing one
+    /// operation. Otherwise, the values could be altered in between the
+    /// reading of the values and subscribing to changes.
+    pub fn subscribe(&self) -> KeyedVectorSubscriber<K, T> {
+        let rx = self.sender.subscribe();
+        KeyedVectorSubscriber::new(self.values.clone(), rx)
+    }
+
+    /// Whether an element with the given key is in the list.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.keys.contains(key)
+    }
+
+    /// Get the index of the element with the given key.
+    pub fn index_of<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        if !self.keys.contains(key) {
+            return None;
+        }
+        self.values.iter().position(|(k, _)| k.borrow() == key)
+    }
+
+    /// Get a reference to the element with the given key.
+    pub fn get_by_key<Q>(&self, key: &Q) -> Option<&T>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = self.index_of(key)?;
+        Some(&self.values[index].1)
+    }
+
+    /// Add an element at the front of the list and notify subscribers.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an element with the same key is already in the list.
+    #[track_caller]
+    pub fn push_front(&mut self, key: K, value: T) {
+        self.insert(0, key, value);
+    }
+
+    /// Add an element at the back of the list and notify subscribers.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an element with the same key is already in the list.
+    #[track_caller]
+    pub fn push_back(&mut self, key: K, value: T) {
+        self.insert(self.values.len(), key, value);
+    }
+
+    /// Insert an element at the given position and notify subscribers.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`, or if an element with the same key is already
+    /// in the list.
+    #[track_caller]
+    pub fn insert(&mut self, index: usize, key: K, value: T) {
+        let len = self.values.len();
+        if index > len {
+            panic!("index out of bounds: the length is {len} but the index is {index}");
+        }
+        if self.keys.contains(&key) {
+            panic!("an element with the same key is already in the list");
+        }
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(target: "eyeball_im::keyed_vector::update", "insert(index = {index})");
+
+        self.keys.insert(key.clone());
+        self.values.insert(index, (key, value));
+        self.broadcast_diff_with(|| {
+            let (key, value) = self.values[index].clone();
+            KeyedVectorDiff::Insert { index, key, value }
+        });
+    }
+
+    /// Replace the element at the given position, notify subscribers and
+    /// return the previous element at that position.
+    ///
+    /// The element keeps its key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index >= len`.
+    #[track_caller]
+    pub fn set(&mut self, index: usize, value: T) -> T {
+        let len = self.values.len();
+        if index >= len {
+            panic!("index out of bounds: the length is {len} but the index is {index}");
+        }
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(target: "eyeball_im::keyed_vector::update", "set(index = {index})");
+
+        let key = self.values[index].0.clone();
+        let (_, old_value) = self.values.set(index, (key, value));
+        self.broadcast_diff_with(|| {
+            let (key, value) = self.values[index].clone();
+            KeyedVectorDiff::Set { index, key, value }
+        });
+        old_value
+    }
+
+    /// Replace the element with the given key, notify subscribers and return
+    /// the previous element.
+    ///
+    /// If there is no element with the given key, subscribers will not be
+    /// notified and this method will return `None`.
+    pub fn set_by_key<Q>(&mut self, key: &Q, value: T) -> Option<T>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = self.index_of(key)?;
+        Some(self.set(index, value))
+    }
+
+    /// Remove the element at the given position, notify subscribers and
+    /// return the key and the element.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index >= len`.
+    #[track_caller]
+    pub fn remove(&mut self, index: usize) -> (K, T) {
+        let len = self.values.len();
+        if index >= len {
+            panic!("index out of bounds: the length is {len} but the index is {index}");
+        }
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(target: "eyeball_im::keyed_vector::update", "remove(index = {index})");
+
+        let (key, value) = self.values.remove(index);
+        self.keys.remove(&key);
+        self.broadcast_diff_with(|| KeyedVectorDiff::Remove { index, key: key.clone() });
+        (key, value)
+    }
+
+    /// Remove the element with the given key, notify subscribers and return
+    /// the element.
+    ///
+    /// If there is no element with the given key, subscribers will not be
+    /// notified and this method will return `None`.
+    pub fn remove_by_key<Q>(&mut self, key: &Q) -> Option<T>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = self.index_of(key)?;
+        Some(self.remove(index).1)
+    }
+
+    /// Move the element at index `from` to index `to` and notify subscribers.
+    ///
+    /// The element is removed first and then inserted at `to`, like with
+    /// [`ObservableVector::move_item`][crate::ObservableVector::move_item]. If
+    /// `from == to`, subscribers will not be notified.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either index is out of bounds.
+    #[track_caller]
+    pub fn move_item(&mut self, from: usize, to: usize) {
+        let len = self.values.len();
+        for index in [from, to] {
+            if index >= len {
+                panic!("index out of bounds: the length is {len} but the index is {index}");
+            }
+        }
+
+        if from != to {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(
+                target: "eyeball_im::keyed_vector::update",
+                "move_item(from = {from}, to = {to})"
+            );
+
+            let entry = self.values.remove(from);
+            self.values.insert(to, entry);
+            self.broadcast_diff_with(|| KeyedVectorDiff::Move {
+                from,
+                to,
+                key: self.values[to].0.clone(),
+            });
+        }
+    }
+
+    /// Clear out all of the elements in this `ObservableKeyedVector` and
+    /// notify subscribers.
+    ///
+    /// If the list is already empty, subscribers will not be notified.
+    pub fn clear(&mut self) {
+        let already_empty = self.values.is_empty();
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            target: "eyeball_im::keyed_vector::update",
+            nop = already_empty.then_some(true),
+            "clear"
+        );
+
+        if !already_empty {
+            self.values.clear();
+            self.keys.clear();
+            self.broadcast_diff_with(|| KeyedVectorDiff::Clear);
+        }
+    }
+
+    fn broadcast_diff_with(&self, make_diff: impl FnOnce() -> KeyedVectorDiff<K, T>) {
+        if self.sender.receiver_count() != 0 {
+            let msg = KeyedVectorBroadcastMessage { diff: make_diff(), state: self.values.clone() };
+            let _num_receivers = self.sender.send(msg).unwrap_or(0);
+            #[cfg(feature = "tracing")]
+            tracing::debug!(
+                target: "eyeball_im::keyed_vector::broadcast",
+                "New observable value broadcast to {_num_receivers} receivers"
+            );
+        }
+    }
+}
+
+impl<K, T> Default for ObservableKeyedVector<K, T>
+where
+    K: Hash + Eq + Clone + 'static,
+    T: Clone + 'static,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, T> fmt::Debug for ObservableKeyedVector<K, T>
+where
+    K: fmt::Debug,
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ObservableKeyedVector")
+            .field("values", &self.values)
+            .finish_non_exhaustive()
+    }
+}
+
+// Note: No DerefMut because all mutating must go through inherent methods that
+// notify subscribers
+impl<K, T> ops::Deref for ObservableKeyedVector<K, T> {
+    type Target = Vector<(K, T)>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.values
+    }
+}
+
+impl<K, T> FromIterator<(K, T)> for ObservableKeyedVector<K, T>
+where
+    K: Hash + Eq + Clone + 'static,
+    T: Clone + 'static,
+{
+    /// Create an `ObservableKeyedVector` from key-value pairs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the same key occurs more than once.
+    fn from_iter<I: IntoIterator<Item = (K, T)>>(iter: I) -> Self {
+        let mut this = Self::new();
+        for (key, value) in iter {
+            this.push_back(key, value);
+        }
+        this
+    }
+}
+
+#[derive(Clone)]
+struct KeyedVectorBroadcastMessage<K, T> {
+    diff: KeyedVectorDiff<K, T>,
+    state: Vector<(K, T)>,
+}
+
+/// A change to an [`ObservableKeyedVector`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum KeyedVectorDiff<K, T> {
+    /// An element was inserted at `index`.
+    Insert {
+        /// The index of the new element.
+        index: usize,
+        /// The key of the new element.
+        key: K,
+        /// The new element.
+        value: T,
+    },
+    /// The element at `index` was replaced by `value`, keeping its key.
+    Set {
+        /// The index of the element that was replaced.
+        index: usize,
+        /// The key of the element that was replaced.
+        key: K,
+        /// The new element.
+        value: T,
+    },
+    /// The element at `index` was removed.
+    Remove {
+        /// The index of the element that was removed.
+        index: usize,
+        /// The key of the element that was removed.
+        key: K,
+    },
+    /// The element at `from` was removed and inserted again at `to`.
+    Move {
+        /// The index of the element before the move.
+        from: usize,
+        /// The index of the element after the move.
+        to: usize,
+        /// The key of the element that was moved.
+        key: K,
+    },
+    /// The list was cleared.
+    Clear,
+    /// The subscriber lagged too far behind, and the next update that should
+    /// have been received has already been discarded from the internal buffer.
+    Reset {
+        /// The full list of keys and elements.
+        values: Vector<(K, T)>,
+    },
+}
+
+impl<K: Clone, T: Clone> KeyedVectorDiff<K, T> {
+    /// The key of the element this diff affects, if it affects a single one.
+    pub fn key(&self) -> Option<&K> {
+        match self {
+            Self::Insert { key, .. }
+            | Self::Set { key, .. }
+            | Self::Remove { key, .. }
+            | Self::Move { key, .. } => Some(key),
+            Self::Clear | Self::Reset { .. } => None,
+        }
+    }
+
+    /// Transform `KeyedVectorDiff<K, T>` into `KeyedVectorDiff<K, U>` by
+    /// applying the given function to any contained elements.
+    pub fn map<U: Clone>(self, mut f: impl FnMut(T) -> U) -> KeyedVectorDiff<K, U> {
+        match self {
+            Self::Insert { index, key, value } => {
+                KeyedVectorDiff::Insert { index, key, value: f(value) }
+            }
+            Self::Set { index, key, value } => KeyedVectorDiff::Set { index, key, value: f(value) },
+            Self::Remove { index, key } => KeyedVectorDiff::Remove { index, key },
+            Self::Move { from, to, key } => KeyedVectorDiff::Move { from, to, key },
+            Self::Clear => KeyedVectorDiff::Clear,
+            Self::Reset { values } => KeyedVectorDiff::Reset {
+                values: values.into_iter().map(|(key, value)| (key, f(value))).collect(),
+            },
+        }
+    }
+
+    /// Applies this [`KeyedVectorDiff`] to a vector of key-value pairs.
+    ///
+    /// This is useful to keep two lists in sync, with potentially one
+    /// containing data [`map`](Self::map)ped from the other.
+    ///
+    /// # Panics
+    ///
+    /// When inserting, setting, removing or moving elements past the end.
+    pub fn apply(self, vec: &mut Vector<(K, T)>) {
+        match self {
+            Self::Insert { index, key, value } => {
+                vec.insert(index, (key, value));
+            }
+            Self::Set { index, key, value } => {
+                vec.set(index, (key, value));
+            }
+            Self::Remove { index, .. } => {
+                vec.remove(index);
+            }
+            Self::Move { from, to, .. } => {
+                let entry = vec.remove(from);
+                vec.insert(to, entry);
+            }
+            Self::Clear => {
+                vec.clear();
+            }
+            Self::Reset { values } => {
+                *vec = values;
+            }
+        }
+    }
+
+    /// Convert this diff into the equivalent [`VectorDiff`] of the key-value
+    /// pairs, to use it with APIs that work with `ObservableVector` diffs.
+    ///
+    /// `Reset` is converted into a `VectorDiff::Reset` with
+    /// [`ResetReason::Lagged`].
+    pub fn into_vector_diff(self) -> VectorDiff<(K, T)> {
+        match self {
+            Self::Insert { index, key, value } => VectorDiff::Insert { index, value: (key, value) },
+            Self::Set { index, key, value } => VectorDiff::Set { index, value: (key, value) },
+            Self::Remove { index, .. } => VectorDiff::Remove { index },
+            Self::Move { from, to, .. } => VectorDiff::Move { from, to },
+            Self::Clear => VectorDiff::Clear,
+            Self::Reset { values } => VectorDiff::Reset { values, reason: ResetReason::Lagged },
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<K, T> serde::Serialize for KeyedVectorDiff<K, T>
+where
+    K: serde::Serialize + Clone,
+    T: serde::Serialize + Clone,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStructVariant;
+
+        const SELF_NAME: &str = "KeyedVectorDiff";
+
+        match self {
+            Self::Insert { index, key, value } => {
+                let mut state = serializer.serialize_struct_variant(SELF_NAME, 0, "Insert", 3)?;
+                state.serialize_field("index", index)?;
+                state.serialize_field("key", key)?;
+                state.serialize_field("value", value)?;
+                state.end()
+            }
+            Self::Set { index, key, value } => {
+                let mut state = serializer.serialize_struct_variant(SELF_NAME, 1, "Set", 3)?;
+                state.serialize_field("index", index)?;
+                state.serialize_field("key", key)?;
+                state.serialize_field("value", value)?;
+                state.end()
+            }
+            Self::Remove { index, key } => {
+                let mut state = serializer.serialize_struct_variant(SELF_NAME, 2, "Remove", 2)?;
+                state.serialize_field("index", index)?;
+                state.serialize_field("key", key)?;
+                state.end()
+            }
+            Self::Move { from, to, key } => {
+                let mut state = serializer.serialize_struct_variant(SELF_NAME, 3, "Move", 3)?;
+                state.serialize_field("from", from)?;
+                state.serialize_field("to", to)?;
+                state.serialize_field("key", key)?;
+                state.end()
+            }
+            Self::Clear => serializer.serialize_struct_variant(SELF_NAME, 4, "Clear", 0)?.end(),
+            Self::Reset { values } => {
+                let mut state = serializer.serialize_struct_variant(SELF_NAME, 5, "Reset", 1)?;
+                state.serialize_field("values", values)?;
+                state.end()
+            }
+        }
+    }
+}
@@ -0,0 +1,197 @@
+use std::{
+    fmt,
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+
+use futures_core::Stream;
+use imbl::Vector;
+use tokio::sync::broadcast::{
+    error::{RecvError, TryRecvError},
+    Receiver,
+};
+#[cfg(feature = "tracing")]
+use tracing::info;
+
+use super::{KeyedVectorBroadcastMessage, KeyedVectorDiff};
+use crate::recv_future::ReusableBoxRecvFuture;
+
+/// A subscriber for updates of an
+/// [`ObservableKeyedVector`][super::ObservableKeyedVector].
+pub struct KeyedVectorSubscriber<K, T> {
+    values: Vector<(K, T)>,
+    rx: Receiver<KeyedVectorBroadcastMessage<K, T>>,
+}
+
+impl<K: Clone + 'static, T: Clone + 'static> KeyedVectorSubscriber<K, T> {
+    pub(super) fn new(
+        values: Vector<(K, T)>,
+        rx: Receiver<KeyedVectorBroadcastMessage<K, T>>,
+    ) -> Self {
+        Self { values, rx }
+    }
+
+    /// Get the values the
+    /// [`ObservableKeyedVector`][super::ObservableKeyedVector] contained when
+    /// this subscriber was created.
+    pub fn values(&self) -> Vector<(K, T)> {
+        self.values.clone()
+    }
+
+    /// Turn this `KeyedVectorSubscriber` into a stream of `KeyedVectorDiff`s.
+    pub fn into_stream(self) -> KeyedVectorSubscriberStream<K, T> {
+        KeyedVectorSubscriberStream::new(ReusableBoxRecvFuture::new(self.rx))
+    }
+
+    /// Turn this `KeyedVectorSubscriber` into a stream of
+    /// `Vec<KeyedVectorDiff>`s.
+    pub fn into_batched_stream(self) -> KeyedVectorSubscriberBatchedStream<K, T> {
+        KeyedVectorSubscriberBatchedStream::new(ReusableBoxRecvFuture::new(self.rx))
+    }
+
+    /// Destructure this `KeyedVectorSubscriber` into the initial values and a
+    /// stream of `KeyedVectorDiff`s.
+    ///
+    /// Semantically equivalent to calling `.values()` and `.into_stream()`
+    /// separately, but guarantees that the values are not unnecessarily cloned.
+    pub fn into_values_and_stream(self) -> (Vector<(K, T)>, KeyedVectorSubscriberStream<K, T>) {
+        let Self { values, rx } = self;
+        (values, KeyedVectorSubscriberStream::new(ReusableBoxRecvFuture::new(rx)))
+    }
+
+    /// Destructure this `KeyedVectorSubscriber` into the initial values and a
+    /// stream of `Vec<KeyedVectorDiff>`s.
+    ///
+    /// Semantically equivalent to calling `.values()` and
+    /// `.into_batched_stream()` separately, but guarantees that the values
+    /// are not unnecessarily cloned.
+    pub fn into_values_and_batched_stream(
+        self,
+    ) -> (Vector<(K, T)>, KeyedVectorSubscriberBatchedStream<K, T>) {
+        let Self { values, rx } = self;
+        (values, KeyedVectorSubscriberBatchedStream::new(ReusableBoxRecvFuture::new(rx)))
+    }
+}
+
+impl<K: fmt::Debug, T: fmt::Debug> fmt::Debug for KeyedVectorSubscriber<K, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyedVectorSubscriber")
+            .field("values", &self.values)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A stream of `KeyedVectorDiff`s created from a [`KeyedVectorSubscriber`].
+///
+/// Use its [`Stream`] implementation to interact with it (futures-util and
+/// other futures-related crates have extension traits with convenience
+/// methods).
+#[derive(Debug)]
+pub struct KeyedVectorSubscriberStream<K, T> {
+    inner: ReusableBoxRecvFuture<KeyedVectorBroadcastMessage<K, T>>,
+}
+
+impl<K, T> KeyedVectorSubscriberStream<K, T> {
+    fn new(inner: ReusableBoxRecvFuture<KeyedVectorBroadcastMessage<K, T>>) -> Self {
+        Self { inner }
+    }
+}
+
+impl<K: Clone + 'static, T: Clone + 'static> Stream for KeyedVectorSubscriberStream<K, T> {
+    type Item = KeyedVectorDiff<K, T>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let (result, mut rx) = ready!(self.inner.poll(cx));
+
+        let poll = match result {
+            Ok(msg) => Poll::Ready(Some(msg.diff)),
+            Err(RecvError::Closed) => Poll::Ready(None),
+            Err(RecvError::Lagged(_)) => {
+                Poll::Ready(handle_lag(&mut rx).map(|values| KeyedVectorDiff::Reset { values }))
+            }
+        };
+
+        self.inner.set(rx);
+        poll
+    }
+}
+
+/// A batched stream of `KeyedVectorDiff`s created from a
+/// [`KeyedVectorSubscriber`].
+///
+/// Use its [`Stream`] implementation to interact with it (futures-util and
+/// other futures-related crates have extension traits with convenience
+/// methods).
+#[derive(Debug)]
+pub struct KeyedVectorSubscriberBatchedStream<K, T> {
+    inner: ReusableBoxRecvFuture<KeyedVectorBroadcastMessage<K, T>>,
+}
+
+impl<K, T> KeyedVectorSubscriberBatchedStream<K, T> {
+    fn new(inner: ReusableBoxRecvFuture<KeyedVectorBroadcastMessage<K, T>>) -> Self {
+        Self { inner }
+    }
+}
+
+impl<K: Clone + 'static, T: Clone + 'static> Stream for KeyedVectorSubscriberBatchedStream<K, T> {
+    type Item = Vec<KeyedVectorDiff<K, T>>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let (result, mut rx) = ready!(self.inner.poll(cx));
+
+        let poll = match result {
+            Ok(msg) => {
+                let mut batch = vec![msg.diff];
+                loop {
+                    match rx.try_recv() {
+                        Ok(msg) => batch.push(msg.diff),
+                        Err(TryRecvError::Empty | TryRecvError::Closed) => {
+                            break Poll::Ready(Some(batch));
+                        }
+                        Err(TryRecvError::Lagged(_)) => {
+                            break Poll::Ready(
+                                handle_lag(&mut rx)
+                                    .map(|values| vec![KeyedVectorDiff::Reset { values }]),
+                            );
+                        }
+                    }
+                }
+            }
+            Err(RecvError::Closed) => Poll::Ready(None),
+            Err(RecvError::Lagged(_)) => Poll::Ready(
+                handle_lag(&mut rx).map(|values| vec![KeyedVectorDiff::Reset { values }]),
+            ),
+        };
+
+        self.inner.set(rx);
+        poll
+    }
+}
+
+fn handle_lag<K: Clone, T: Clone>(
+    rx: &mut Receiver<KeyedVectorBroadcastMessage<K, T>>,
+) -> Option<Vector<(K, T)>> {
+    let mut msg = None;
+    loop {
+        match rx.try_recv() {
+            // There's a newer message in the receiver's buffer, use that for reset.
+            Ok(m) => {
+                msg = Some(m);
+            }
+            // The channel was closed, we have no way of obtaining the last state.
+            Err(TryRecvError::Closed) => {
+                #[cfg(feature = "tracing")]
+                info!("Channel closed after lag, can't return last state");
+                return None;
+            }
+            // Lagged twice in a row, look at the next try_recv result.
+            Err(TryRecvError::Lagged(_)) => {}
+            Err(TryRecvError::Empty) => match msg {
+                // We exhausted the internal buffer using try_recv, msg contains the
+                // last message from it, which we use for the reset.
+                Some(msg) => return Some(msg.state),
+                None => unreachable!("got no new message via try_recv after lag"),
+            },
+        }
+    }
+}
@@ -2,18 +2,69 @@
 //!
 //! Cargo features:
 //!
+//! - `arbitrary`: Implement `arbitrary::Arbitrary` for [`VectorDiff`] and
+//!   [`ObservableVector`], and add helpers for applying arbitrary operations to
+//!   an [`ObservableVector`], in the [`arbitrary`](mod@arbitrary) module
+//! - `im`: Add conversions from and to [`im::Vector`](https://docs.rs/im), in
+//!   the [`im_compat`] module
+//! - `nightly`: Implement the unstable [`AsyncIterator`] trait for
+//!   [`VectorSubscriberStream`] and [`VectorSubscriberBatchedStream`], in
+//!   addition to `Stream`. Requires a nightly compiler.
+//! - `serde`: Implement `serde::Serialize` for [`VectorDiff`], [`MapDiff`],
+//!   [`SetDiff`], [`VecDequeDiff`] and [`KeyedVectorDiff`], and
+//!   `serde::Deserialize` for [`VectorDiff`]
 //! - `tracing`: Emit [tracing] events when updates are sent out
+//! - `update-seq`: Stamp every update of an [`ObservableVector`] with a
+//!   process-wide [`UpdateSeq`], shared with the observables of `eyeball`,
+//!   which can be obtained from its subscriber streams, and allow querying the
+//!   diffs of recent updates with [`ObservableVector::diffs_since`]
+//!
+//! [`AsyncIterator`]: https://doc.rust-lang.org/std/async_iter/trait.AsyncIterator.html
 
 #![cfg_attr(docsrs, feature(doc_auto_cfg))]
+#![cfg_attr(feature = "nightly", feature(async_iterator))]
 
+#[cfg(feature = "arbitrary")]
+pub mod arbitrary;
+#[cfg(feature = "im")]
+pub mod im_compat;
+mod keyed_vector;
+mod map;
+pub mod prelude;
+mod recv_future;
 mod reusable_box;
+#[cfg(doctest)]
+mod send_sync;
+mod set;
+mod vec_deque;
 mod vector;
 
+pub use keyed_vector::{
+    KeyedVectorDiff, KeyedVectorSubscriber, KeyedVectorSubscriberBatchedStream,
+    KeyedVectorSubscriberStream, ObservableKeyedVector,
+};
+pub use map::{
+    MapDiff, MapSubscriber, MapSubscriberBatchedStream, MapSubscriberStream, ObservableMap,
+    ObservableMapTransaction,
+};
+pub use set::{
+    ObservableSet, ObservableSetTransaction, SetDiff, SetSubscriber, SetSubscriberBatchedStream,
+    SetSubscriberStream,
+};
+pub use vec_deque::{
+    ObservableVecDeque, VecDequeDiff, VecDequeSubscriber, VecDequeSubscriberBatchedStream,
+    VecDequeSubscriberStream,
+};
 pub use vector::{
-    ObservableVector, ObservableVectorEntries, ObservableVectorEntry, ObservableVectorTransaction,
-    ObservableVectorTransactionEntries, ObservableVectorTransactionEntry, VectorDiff,
-    VectorSubscriber, VectorSubscriberBatchedStream, VectorSubscriberStream,
+    DiffTable, ElementPosition, ObservableVector, ObservableVectorEntries, ObservableVectorEntry,
+    ObservableVectorFilteredEntries, ObservableVectorTransaction,
+    ObservableVectorTransactionEntries, ObservableVectorTransactionEntry, ParseCompactDiffError,
+    ResetReason, VectorCursor, VectorDiff, VectorLenSubscriber, VectorSubscriber,
+    VectorSubscriberBatchedStream, VectorSubscriberMapped, VectorSubscriberStream,
 };
 
+#[cfg(feature = "update-seq")]
+#[doc(no_inline)]
+pub use eyeball::UpdateSeq;
 #[doc(no_inline)]
-pub use imbl::Vector;
+pub use imbl::{self, Vector};
@@ -0,0 +1,338 @@
+use std::{borrow::Borrow, fmt, hash::Hash, ops};
+
+use imbl::HashMap;
+use tokio::sync::broadcast::{self, Sender};
+
+mod subscriber;
+mod transaction;
+
+pub use self::{
+    subscriber::{MapSubscriber, MapSubscriberBatchedStream, MapSubscriberStream},
+    transaction::ObservableMapTransaction,
+};
+
+/// An unordered map of keys to values that broadcasts any changes made to it.
+///
+/// This is the keyed counterpart of
+/// [`ObservableVector`][crate::ObservableVector]; its API mirrors the vector's
+/// where that makes sense.
+pub struct ObservableMap<K, V> {
+    values: HashMap<K, V>,
+    sender: Sender<MapBroadcastMessage<K, V>>,
+}
+
+impl<K, V> ObservableMap<K, V>
+where
+    K: Hash + Eq + Clone + 'static,
+    V: Clone + 'static,
+{
+    /// Create a new `ObservableMap`.
+    ///
+    /// As of the time of writing, this is equivalent to
+    /// `ObservableMap::with_capacity(16)`, but the internal buffer capacity is
+    /// subject to change in non-breaking releases.
+    ///
+    /// See [`with_capacity`][Self::with_capacity] for details about the buffer
+    /// capacity.
+    pub fn new() -> Self {
+        Self::with_capacity(16)
+    }
+
+    /// Create a new `ObservableMap` with the given capacity for the inner
+    /// buffer.
+    ///
+    /// Up to `capacity` updates that have not been received by all of the
+    /// subscribers yet will be retained in the inner buffer. If an update
+    /// happens while the buffer is at capacity, the oldest update is discarded
+    /// from it and all subscribers that have not yet received it will instead
+    /// see [`MapDiff::Reset`] as the next update.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the capacity is `0`, or larger than `usize::MAX / 2`.
+    pub fn with_capacity(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+        Self { values: HashMap::new(), sender }
+    }
+
+    /// Turn the `ObservableMap` back into a regular `HashMap`.
+    pub fn into_inner(self) -> HashMap<K, V> {
+        self.values
+    }
+
+    /// Obtain a new subscriber.
+    ///
+    /// If you put the `ObservableMap` behind a lock, it is highly recommended
+    /// to make access of the entries and subscribing one operation. Otherwise,
+    /// the values could be altered in between the reading of the values and
+    /// subscribing to changes.
+    pub fn subscribe(&self) -> MapSubscriber<K, V> {
+        let rx = self.sender.subscribe();
+        MapSubscriber::new(self.values.clone(), rx)
+    }
+
+    /// Insert a value under the given key and notify subscribers.
+    ///
+    /// Subscribers see [`MapDiff::Insert`] if the key was not present before,
+    /// and [`MapDiff::Update`] otherwise. Returns the previous value, if any.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let prev = self.values.insert(key.clone(), value.clone());
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            target: "eyeball_im::map::update",
+            update = prev.is_some().then_some(true),
+            "insert"
+        );
+
+        let diff = match prev {
+            Some(_) => MapDiff::Update { key, value },
+            None => MapDiff::Insert { key, value },
+        };
+        self.broadcast_diff(diff);
+        prev
+    }
+
+    /// Remove the value under the given key, notify subscribers and return the
+    /// value.
+    ///
+    /// If the key is not present, subscribers will not be notified and this
+    /// method will return `None`.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let (key, value) = self.values.remove_with_key(key)?;
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(target: "eyeball_im::map::update", "remove");
+
+        self.broadcast_diff(MapDiff::Remove { key });
+        Some(value)
+    }
+
+    /// Clear out all of the entries in this `HashMap` and notify subscribers.
+    pub fn clear(&mut self) {
+        let already_empty = self.values.is_empty();
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            target: "eyeball_im::map::update",
+            nop = already_empty.then_some(true),
+            "clear"
+        );
+
+        if !already_empty {
+            self.values.clear();
+            self.broadcast_diff(MapDiff::Clear);
+        }
+    }
+
+    /// Start a new transaction to make multiple updates as one unit.
+    ///
+    /// See [`ObservableMapTransaction`]s documentation for more details.
+    pub fn transaction(&mut self) -> ObservableMapTransaction<'_, K, V> {
+        ObservableMapTransaction::new(self)
+    }
+
+    fn broadcast_diff(&self, diff: MapDiff<K, V>) {
+        if self.sender.receiver_count() != 0 {
+            let msg = MapBroadcastMessage { diffs: vec![diff], state: self.values.clone() };
+            let _num_receivers = self.sender.send(msg).unwrap_or(0);
+            #[cfg(feature = "tracing")]
+            tracing::debug!(
+                target: "eyeball_im::map::broadcast",
+                "New observable value broadcast to {_num_receivers} receivers"
+            );
+        }
+    }
+}
+
+impl<K, V> Default for ObservableMap<K, V>
+where
+    K: Hash + Eq + Clone + 'static,
+    V: Clone + 'static,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> fmt::Debug for ObservableMap<K, V>
+where
+    K: Hash + Eq + fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ObservableMap").field("values", &self.values).finish_non_exhaustive()
+    }
+}
+
+// Note: No DerefMut because all mutating must go through inherent methods that
+// notify subscribers
+impl<K, V> ops::Deref for ObservableMap<K, V> {
+    type Target = HashMap<K, V>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.values
+    }
+}
+
+impl<K, V> From<HashMap<K, V>> for ObservableMap<K, V>
+where
+    K: Hash + Eq + Clone + 'static,
+    V: Clone + 'static,
+{
+    fn from(values: HashMap<K, V>) -> Self {
+        let (sender, _) = broadcast::channel(16);
+        Self { values, sender }
+    }
+}
+
+#[derive(Clone)]
+struct MapBroadcastMessage<K, V> {
+    diffs: Vec<MapDiff<K, V>>,
+    state: HashMap<K, V>,
+}
+
+/// A change to an [`ObservableMap`].
+#[derive(Clone, Debug)]
+pub enum MapDiff<K, V> {
+    /// A value was inserted under a key that was not present before.
+    Insert {
+        /// The key of the new entry.
+        key: K,
+        /// The new value.
+        value: V,
+    },
+    /// A replacement of the previous value under a key.
+    Update {
+        /// The key of the updated entry.
+        key: K,
+        /// The new value.
+        value: V,
+    },
+    /// Removal of an entry.
+    Remove {
+        /// The key of the removed entry.
+        key: K,
+    },
+    /// The map was cleared.
+    Clear,
+    /// The subscriber lagged too far behind, and the next update that should
+    /// have been received has already been discarded from the internal buffer.
+    Reset {
+        /// The full map.
+        values: HashMap<K, V>,
+    },
+}
+
+impl<K, V> PartialEq for MapDiff<K, V>
+where
+    K: Hash + Eq,
+    V: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Insert { key: k1, value: v1 }, Self::Insert { key: k2, value: v2 })
+            | (Self::Update { key: k1, value: v1 }, Self::Update { key: k2, value: v2 }) => {
+                k1 == k2 && v1 == v2
+            }
+            (Self::Remove { key: k1 }, Self::Remove { key: k2 }) => k1 == k2,
+            (Self::Clear, Self::Clear) => true,
+            (Self::Reset { values: v1 }, Self::Reset { values: v2 }) => v1 == v2,
+            _ => false,
+        }
+    }
+}
+
+impl<K, V> Eq for MapDiff<K, V>
+where
+    K: Hash + Eq,
+    V: Eq,
+{
+}
+
+impl<K, V> MapDiff<K, V>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+{
+    /// Transform `MapDiff<K, V>` into `MapDiff<K, U>` by applying the given
+    /// function to any contained values.
+    pub fn map<U: Clone>(self, mut f: impl FnMut(V) -> U) -> MapDiff<K, U> {
+        match self {
+            MapDiff::Insert { key, value } => MapDiff::Insert { key, value: f(value) },
+            MapDiff::Update { key, value } => MapDiff::Update { key, value: f(value) },
+            MapDiff::Remove { key } => MapDiff::Remove { key },
+            MapDiff::Clear => MapDiff::Clear,
+            MapDiff::Reset { values } => MapDiff::Reset {
+                values: values.into_iter().map(|(key, value)| (key, f(value))).collect(),
+            },
+        }
+    }
+
+    /// Applies this [`MapDiff`] to a map.
+    ///
+    /// This is useful to keep two maps in sync, with potentially one
+    /// containing data [`map`](Self::map)ped from the other.
+    pub fn apply(self, map: &mut HashMap<K, V>) {
+        match self {
+            MapDiff::Insert { key, value } | MapDiff::Update { key, value } => {
+                map.insert(key, value);
+            }
+            MapDiff::Remove { key } => {
+                map.remove(&key);
+            }
+            MapDiff::Clear => {
+                map.clear();
+            }
+            MapDiff::Reset { values } => {
+                *map = values;
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<K, V> serde::Serialize for MapDiff<K, V>
+where
+    K: serde::Serialize + Hash + Eq,
+    V: serde::Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStructVariant;
+
+        const SELF_NAME: &str = "MapDiff";
+
+        match self {
+            MapDiff::Insert { key, value } => {
+                let mut state = serializer.serialize_struct_variant(SELF_NAME, 0, "Insert", 2)?;
+                state.serialize_field("key", key)?;
+                state.serialize_field("value", value)?;
+                state.end()
+            }
+            MapDiff::Update { key, value } => {
+                let mut state = serializer.serialize_struct_variant(SELF_NAME, 1, "Update", 2)?;
+                state.serialize_field("key", key)?;
+                state.serialize_field("value", value)?;
+                state.end()
+            }
+            MapDiff::Remove { key } => {
+                let mut state = serializer.serialize_struct_variant(SELF_NAME, 2, "Remove", 1)?;
+                state.serialize_field("key", key)?;
+                state.end()
+            }
+            MapDiff::Clear => serializer.serialize_struct_variant(SELF_NAME, 3, "Clear", 0)?.end(),
+            MapDiff::Reset { values } => {
+                let mut state = serializer.serialize_struct_variant(SELF_NAME, 4, "Reset", 1)?;
+                state.serialize_field("values", values)?;
+                state.end()
+            }
+        }
+    }
+}
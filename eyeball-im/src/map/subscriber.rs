@@ -0,0 +1,210 @@
+use std::{
+    hash::Hash,
+    pin::Pin,
+    task::{ready, Context, Poll},
+    vec,
+};
+
+use futures_core::Stream;
+use imbl::HashMap;
+use tokio::sync::broadcast::{
+    error::{RecvError, TryRecvError},
+    Receiver,
+};
+#[cfg(feature = "tracing")]
+use tracing::info;
+
+use super::{MapBroadcastMessage, MapDiff};
+use crate::recv_future::ReusableBoxRecvFuture;
+
+/// A subscriber for updates of a [`HashMap`].
+#[derive(Debug)]
+pub struct MapSubscriber<K, V> {
+    values: HashMap<K, V>,
+    rx: Receiver<MapBroadcastMessage<K, V>>,
+}
+
+impl<K, V> MapSubscriber<K, V>
+where
+    K: Hash + Eq + Clone + 'static,
+    V: Clone + 'static,
+{
+    pub(super) fn new(values: HashMap<K, V>, rx: Receiver<MapBroadcastMessage<K, V>>) -> Self {
+        Self { values, rx }
+    }
+
+    /// Get the entries the [`ObservableMap`][super::ObservableMap] contained
+    /// when this subscriber was created.
+    pub fn values(&self) -> HashMap<K, V> {
+        self.values.clone()
+    }
+
+    /// Turn this `MapSubscriber` into a stream of `MapDiff`s.
+    pub fn into_stream(self) -> MapSubscriberStream<K, V> {
+        MapSubscriberStream::new(ReusableBoxRecvFuture::new(self.rx))
+    }
+
+    /// Turn this `MapSubscriber` into a stream of `Vec<MapDiff>`s.
+    pub fn into_batched_stream(self) -> MapSubscriberBatchedStream<K, V> {
+        MapSubscriberBatchedStream::new(ReusableBoxRecvFuture::new(self.rx))
+    }
+
+    /// Destructure this `MapSubscriber` into the initial values and a stream of
+    /// `MapDiff`s.
+    ///
+    /// Semantically equivalent to calling `.values()` and `.into_stream()`
+    /// separately, but guarantees that the values are not unnecessarily cloned.
+    pub fn into_values_and_stream(self) -> (HashMap<K, V>, MapSubscriberStream<K, V>) {
+        let Self { values, rx } = self;
+        (values, MapSubscriberStream::new(ReusableBoxRecvFuture::new(rx)))
+    }
+
+    /// Destructure this `MapSubscriber` into the initial values and a stream of
+    /// `Vec<MapDiff>`s.
+    ///
+    /// Semantically equivalent to calling `.values()` and
+    /// `.into_batched_stream()` separately, but guarantees that the values
+    /// are not unnecessarily cloned.
+    pub fn into_values_and_batched_stream(
+        self,
+    ) -> (HashMap<K, V>, MapSubscriberBatchedStream<K, V>) {
+        let Self { values, rx } = self;
+        (values, MapSubscriberBatchedStream::new(ReusableBoxRecvFuture::new(rx)))
+    }
+}
+
+/// A stream of `MapDiff`s created from a [`MapSubscriber`].
+///
+/// Use its [`Stream`] implementation to interact with it (futures-util and
+/// other futures-related crates have extension traits with convenience
+/// methods).
+#[derive(Debug)]
+pub struct MapSubscriberStream<K, V> {
+    inner: ReusableBoxRecvFuture<MapBroadcastMessage<K, V>>,
+    // Remaining diffs of a previous message with multiple diffs.
+    batch: vec::IntoIter<MapDiff<K, V>>,
+}
+
+impl<K, V> MapSubscriberStream<K, V> {
+    fn new(inner: ReusableBoxRecvFuture<MapBroadcastMessage<K, V>>) -> Self {
+        Self { inner, batch: Vec::new().into_iter() }
+    }
+}
+
+impl<K, V> Unpin for MapSubscriberStream<K, V> {}
+
+impl<K, V> Stream for MapSubscriberStream<K, V>
+where
+    K: Hash + Eq + Clone + 'static,
+    V: Clone + 'static,
+{
+    type Item = MapDiff<K, V>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if let Some(diff) = self.batch.next() {
+            return Poll::Ready(Some(diff));
+        }
+
+        let (result, mut rx) = ready!(self.inner.poll(cx));
+
+        let poll = match result {
+            Ok(msg) => {
+                let mut iter = msg.diffs.into_iter();
+                let fst = iter.next().expect("ObservableMap never sends empty diffs");
+                self.batch = iter;
+                Poll::Ready(Some(fst))
+            }
+            Err(RecvError::Closed) => Poll::Ready(None),
+            Err(RecvError::Lagged(_)) => {
+                Poll::Ready(handle_lag(&mut rx).map(|values| MapDiff::Reset { values }))
+            }
+        };
+
+        self.inner.set(rx);
+        poll
+    }
+}
+
+/// A batched stream of `MapDiff`s created from a [`MapSubscriber`].
+///
+/// Use its [`Stream`] implementation to interact with it (futures-util and
+/// other futures-related crates have extension traits with convenience
+/// methods).
+#[derive(Debug)]
+pub struct MapSubscriberBatchedStream<K, V> {
+    inner: ReusableBoxRecvFuture<MapBroadcastMessage<K, V>>,
+}
+
+impl<K, V> MapSubscriberBatchedStream<K, V> {
+    fn new(inner: ReusableBoxRecvFuture<MapBroadcastMessage<K, V>>) -> Self {
+        Self { inner }
+    }
+}
+
+impl<K, V> Stream for MapSubscriberBatchedStream<K, V>
+where
+    K: Hash + Eq + Clone + 'static,
+    V: Clone + 'static,
+{
+    type Item = Vec<MapDiff<K, V>>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let (result, mut rx) = ready!(self.inner.poll(cx));
+
+        let poll = match result {
+            Ok(msg) => {
+                let mut batch = msg.diffs;
+                loop {
+                    match rx.try_recv() {
+                        Ok(mut msg) => batch.append(&mut msg.diffs),
+                        Err(TryRecvError::Empty | TryRecvError::Closed) => {
+                            break Poll::Ready(Some(batch));
+                        }
+                        Err(TryRecvError::Lagged(_)) => {
+                            break Poll::Ready(
+                                handle_lag(&mut rx).map(|values| vec![MapDiff::Reset { values }]),
+                            );
+                        }
+                    }
+                }
+            }
+            Err(RecvError::Closed) => Poll::Ready(None),
+            Err(RecvError::Lagged(_)) => {
+                Poll::Ready(handle_lag(&mut rx).map(|values| vec![MapDiff::Reset { values }]))
+            }
+        };
+
+        self.inner.set(rx);
+        poll
+    }
+}
+
+fn handle_lag<K, V>(rx: &mut Receiver<MapBroadcastMessage<K, V>>) -> Option<HashMap<K, V>>
+where
+    K: Clone,
+    V: Clone,
+{
+    let mut msg = None;
+    loop {
+        match rx.try_recv() {
+            // There's a newer message in the receiver's buffer, use that for reset.
+            Ok(m) => {
+                msg = Some(m);
+            }
+            // The channel was closed, we have no way of obtaining the last state.
+            Err(TryRecvError::Closed) => {
+                #[cfg(feature = "tracing")]
+                info!("Channel closed after lag, can't return last state");
+                return None;
+            }
+            // Lagged twice in a row, look at the next try_recv result.
+            Err(TryRecvError::Lagged(_)) => {}
+            Err(TryRecvError::Empty) => match msg {
+                // We exhausted the internal buffer using try_recv, msg contains the
+                // last message from it, which we use for the reset.
+                Some(msg) => return Some(msg.state),
+                None => unreachable!("got no new message via try_recv after lag"),
+            },
+        }
+    }
+}
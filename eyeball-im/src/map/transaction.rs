@@ -0,0 +1,141 @@
+use std::{borrow::Borrow, fmt, hash::Hash, mem, ops};
+
+use imbl::HashMap;
+
+use super::{MapBroadcastMessage, MapDiff, ObservableMap};
+
+/// A transaction that allows making multiple updates to an `ObservableMap` as
+/// an atomic unit.
+///
+/// For updates from the transaction to have affect, it has to be finalized with
+/// [`.commit()`](Self::commit). If the transaction is dropped without that
+/// method being called, the updates will be discarded.
+pub struct ObservableMapTransaction<'o, K, V> {
+    // The observable map being modified, only modified on commit.
+    inner: &'o mut ObservableMap<K, V>,
+    // A clone of the observable's values, what the methods operate on until commit.
+    values: HashMap<K, V>,
+    // The batched updates, to be sent to subscribers on commit.
+    batch: Vec<MapDiff<K, V>>,
+}
+
+impl<'o, K, V> ObservableMapTransaction<'o, K, V>
+where
+    K: Hash + Eq + Clone + 'static,
+    V: Clone + 'static,
+{
+    pub(super) fn new(inner: &'o mut ObservableMap<K, V>) -> Self {
+        let values = inner.values.clone();
+        Self { inner, values, batch: Vec::new() }
+    }
+
+    /// Commit this transaction, persisting the changes and notifying
+    /// subscribers.
+    pub fn commit(mut self) {
+        #[cfg(feature = "tracing")]
+        tracing::debug!("commit");
+
+        self.inner.values = mem::take(&mut self.values);
+
+        if self.batch.is_empty() {
+            #[cfg(feature = "tracing")]
+            tracing::trace!(
+                target: "eyeball_im::map::broadcast",
+                "Skipping broadcast of empty list of diffs"
+            );
+        } else {
+            let diffs = mem::take(&mut self.batch);
+            let msg = MapBroadcastMessage { diffs, state: self.inner.values.clone() };
+            let _num_receivers = self.inner.sender.send(msg).unwrap_or(0);
+            #[cfg(feature = "tracing")]
+            tracing::debug!(
+                target: "eyeball_im::map::broadcast",
+                "New observable value broadcast to {_num_receivers} receivers"
+            );
+        }
+    }
+
+    /// Roll back all changes made using this transaction so far.
+    ///
+    /// Same as dropping the transaction and starting a new one, semantically.
+    pub fn rollback(&mut self) {
+        #[cfg(feature = "tracing")]
+        tracing::debug!("rollback (explicit)");
+
+        self.values = self.inner.values.clone();
+        self.batch.clear();
+    }
+
+    /// Insert a value under the given key and notify subscribers.
+    ///
+    /// Returns the previous value, if any.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        #[cfg(feature = "tracing")]
+        tracing::debug!(target: "eyeball_im::map::transaction::update", "insert");
+
+        let prev = self.values.insert(key.clone(), value.clone());
+        let diff = match prev {
+            Some(_) => MapDiff::Update { key, value },
+            None => MapDiff::Insert { key, value },
+        };
+        self.add_to_batch(diff);
+        prev
+    }
+
+    /// Remove the value under the given key, notify subscribers and return the
+    /// value.
+    ///
+    /// If the key is not present, subscribers will not be notified and this
+    /// method will return `None`.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let (key, value) = self.values.remove_with_key(key)?;
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(target: "eyeball_im::map::transaction::update", "remove");
+
+        self.add_to_batch(MapDiff::Remove { key });
+        Some(value)
+    }
+
+    /// Clear out all of the entries in this `HashMap` and notify subscribers.
+    pub fn clear(&mut self) {
+        #[cfg(feature = "tracing")]
+        tracing::debug!(target: "eyeball_im::map::transaction::update", "clear");
+
+        self.values.clear();
+        self.batch.clear(); // All previous batched updates are irrelevant now
+        self.add_to_batch(MapDiff::Clear);
+    }
+
+    fn add_to_batch(&mut self, diff: MapDiff<K, V>) {
+        if self.inner.sender.receiver_count() != 0 {
+            self.batch.push(diff);
+        }
+    }
+}
+
+impl<K, V> fmt::Debug for ObservableMapTransaction<'_, K, V>
+where
+    K: Hash + Eq + fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ObservableMapTransaction")
+            .field("values", &self.values)
+            .finish_non_exhaustive()
+    }
+}
+
+// Note: No DerefMut because all mutating must go through inherent methods that
+// notify subscribers
+impl<K, V> ops::Deref for ObservableMapTransaction<'_, K, V> {
+    type Target = HashMap<K, V>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.values
+    }
+}
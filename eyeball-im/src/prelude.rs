@@ -0,0 +1,13 @@
+//! Re-exports of the most commonly used types, for glob-importing.
+//!
+//! ```
+//! use eyeball_im::prelude::*;
+//! use imbl::vector;
+//!
+//! let ob: ObservableVector<i32> = ObservableVector::from(vector![1, 2]);
+//! let values: Vector<i32> = ob.clone();
+//! let _diff = VectorDiff::Reset { values, reason: ResetReason::Update };
+//! ```
+
+#[doc(no_inline)]
+pub use crate::{ObservableVector, ResetReason, Vector, VectorDiff, VectorSubscriber};
@@ -0,0 +1,59 @@
+use std::{
+    fmt,
+    task::{Context, Poll},
+};
+
+use tokio::sync::broadcast::{self, error::RecvError, Receiver};
+
+use crate::reusable_box::ReusableBoxFuture;
+
+type SubscriberFutureReturn<T> = (Result<T, RecvError>, Receiver<T>);
+
+/// A reusable future receiving the next message from a broadcast channel.
+///
+/// Shared between the subscriber streams of all observable collections.
+pub(crate) struct ReusableBoxRecvFuture<T> {
+    inner: ReusableBoxFuture<'static, SubscriberFutureReturn<T>>,
+}
+
+async fn make_recv_future<T: Clone>(mut rx: Receiver<T>) -> SubscriberFutureReturn<T> {
+    let result = rx.recv().await;
+    (result, rx)
+}
+
+impl<T> ReusableBoxRecvFuture<T>
+where
+    T: Clone + 'static,
+{
+    pub(crate) fn new(rx: Receiver<T>) -> Self {
+        Self { inner: ReusableBoxFuture::new(make_recv_future(rx)) }
+    }
+
+    pub(crate) fn set(&mut self, rx: Receiver<T>) {
+        self.inner.set(make_recv_future(rx));
+    }
+
+    pub(crate) fn poll(&mut self, cx: &mut Context<'_>) -> Poll<SubscriberFutureReturn<T>> {
+        self.inner.poll(cx)
+    }
+}
+
+fn assert_send<T: Send>(_val: T) {}
+#[allow(unused)]
+fn assert_make_future_send() {
+    #[derive(Clone)]
+    struct IsSend(*mut ());
+    unsafe impl Send for IsSend {}
+
+    let (_sender, receiver): (_, Receiver<IsSend>) = broadcast::channel(1);
+
+    assert_send(make_recv_future(receiver));
+}
+// SAFETY: make_future is Send if T is, as proven by assert_make_future_send.
+unsafe impl<T: Send> Send for ReusableBoxRecvFuture<T> {}
+
+impl<T> fmt::Debug for ReusableBoxRecvFuture<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReusableBoxRecvFuture").finish()
+    }
+}
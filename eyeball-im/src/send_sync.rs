@@ -0,0 +1,29 @@
+//! Compile-fail tests checking that the public types can't be sent to or
+//! shared with other threads if the values they hold can't, despite the
+//! manual `Send` and `Sync` implementations of the subscriber internals. The
+//! opposite is checked in `tests/it/send_sync.rs`.
+//!
+//! ```compile_fail,E0277
+//! fn assert_send<T: Send>() {}
+//! assert_send::<eyeball_im::ObservableVector<std::rc::Rc<u8>>>();
+//! ```
+//!
+//! ```compile_fail,E0277
+//! fn assert_send<T: Send>() {}
+//! assert_send::<eyeball_im::VectorSubscriberStream<std::rc::Rc<u8>>>();
+//! ```
+//!
+//! ```compile_fail,E0277
+//! fn assert_send<T: Send>() {}
+//! assert_send::<eyeball_im::VectorSubscriberBatchedStream<std::rc::Rc<u8>>>();
+//! ```
+//!
+//! ```compile_fail,E0277
+//! fn assert_sync<T: Sync>() {}
+//! assert_sync::<eyeball_im::ObservableVector<std::cell::Cell<u8>>>();
+//! ```
+//!
+//! ```compile_fail,E0277
+//! fn assert_sync<T: Sync>() {}
+//! assert_sync::<eyeball_im::VectorSubscriberStream<std::cell::Cell<u8>>>();
+//! ```
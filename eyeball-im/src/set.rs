@@ -0,0 +1,322 @@
+use std::{borrow::Borrow, fmt, hash::Hash, ops};
+
+use imbl::HashSet;
+use tokio::sync::broadcast::{self, Sender};
+
+mod subscriber;
+mod transaction;
+
+pub use self::{
+    subscriber::{SetSubscriber, SetSubscriberBatchedStream, SetSubscriberStream},
+    transaction::ObservableSetTransaction,
+};
+
+/// An unordered set of unique values that broadcasts any changes made to it.
+///
+/// Inserting a value that is already part of the set, or removing one that
+/// isn't, does not notify subscribers.
+pub struct ObservableSet<T> {
+    values: HashSet<T>,
+    sender: Sender<SetBroadcastMessage<T>>,
+}
+
+impl<T> ObservableSet<T>
+where
+    T: Hash + Eq + Clone + 'static,
+{
+    /// Create a new `ObservableSet`.
+    ///
+    /// As of the time of writing, this is equivalent to
+    /// `ObservableSet::with_capacity(16)`, but the internal buffer capacity is
+    /// subject to change in non-breaking releases.
+    ///
+    /// See [`with_capacity`][Self::with_capacity] for details about the buffer
+    /// capacity.
+    pub fn new() -> Self {
+        Self::with_capacity(16)
+    }
+
+    /// Create a new `ObservableSet` with the given capacity for the inner
+    /// buffer.
+    ///
+    /// Up to `capacity` updates that have not been received by all of the
+    /// subscribers yet will be retained in the inner buffer. If an update
+    /// happens while the buffer is at capacity, the oldest update is discarded
+    /// from it and all subscribers that have not yet received it will instead
+    /// see [`SetDiff::Reset`] as the next update.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the capacity is `0`, or larger than `usize::MAX / 2`.
+    pub fn with_capacity(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+        Self { values: HashSet::new(), sender }
+    }
+
+    /// Turn the `ObservableSet` back into a regular `HashSet`.
+    pub fn into_inner(self) -> HashSet<T> {
+        self.values
+    }
+
+    /// Obtain a new subscriber.
+    ///
+    /// If you put the `ObservableSet` behind a lock, it is highly recommended
+    /// to make access of the values and subscribing one operation. Otherwise,
+    /// the values could be altered in between the reading of the values and
+    /// subscribing to changes.
+    pub fn subscribe(&self) -> SetSubscriber<T> {
+        let rx = self.sender.subscribe();
+        SetSubscriber::new(self.values.clone(), rx)
+    }
+
+    /// Insert a value and notify subscribers.
+    ///
+    /// Returns whether the value was newly inserted. If it was already part of
+    /// the set, subscribers will not be notified.
+    pub fn insert(&mut self, value: T) -> bool {
+        let is_new = self.values.insert(value.clone()).is_none();
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            target: "eyeball_im::set::update",
+            nop = (!is_new).then_some(true),
+            "insert"
+        );
+
+        if is_new {
+            self.broadcast_diff(SetDiff::Insert { value });
+        }
+        is_new
+    }
+
+    /// Remove a value, notify subscribers and return the value.
+    ///
+    /// If the value is not part of the set, subscribers will not be notified
+    /// and this method will return `None`.
+    pub fn remove<Q>(&mut self, value: &Q) -> Option<T>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let value = self.values.remove(value)?;
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(target: "eyeball_im::set::update", "remove");
+
+        self.broadcast_diff(SetDiff::Remove { value: value.clone() });
+        Some(value)
+    }
+
+    /// Clear out all of the values in this `HashSet` and notify subscribers.
+    pub fn clear(&mut self) {
+        let already_empty = self.values.is_empty();
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            target: "eyeball_im::set::update",
+            nop = already_empty.then_some(true),
+            "clear"
+        );
+
+        if !already_empty {
+            self.values.clear();
+            self.broadcast_diff(SetDiff::Clear);
+        }
+    }
+
+    /// Start a new transaction to make multiple updates as one unit.
+    ///
+    /// See [`ObservableSetTransaction`]s documentation for more details.
+    pub fn transaction(&mut self) -> ObservableSetTransaction<'_, T> {
+        ObservableSetTransaction::new(self)
+    }
+
+    fn broadcast_diff(&self, diff: SetDiff<T>) {
+        if self.sender.receiver_count() != 0 {
+            let msg = SetBroadcastMessage { diffs: vec![diff], state: self.values.clone() };
+            let _num_receivers = self.sender.send(msg).unwrap_or(0);
+            #[cfg(feature = "tracing")]
+            tracing::debug!(
+                target: "eyeball_im::set::broadcast",
+                "New observable value broadcast to {_num_receivers} receivers"
+            );
+        }
+    }
+}
+
+impl<T> Default for ObservableSet<T>
+where
+    T: Hash + Eq + Clone + 'static,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> fmt::Debug for ObservableSet<T>
+where
+    T: Hash + Eq + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ObservableSet").field("values", &self.values).finish_non_exhaustive()
+    }
+}
+
+// Note: No DerefMut because all mutating must go through inherent methods that
+// notify subscribers
+impl<T> ops::Deref for ObservableSet<T> {
+    type Target = HashSet<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.values
+    }
+}
+
+impl<T> From<HashSet<T>> for ObservableSet<T>
+where
+    T: Hash + Eq + Clone + 'static,
+{
+    fn from(values: HashSet<T>) -> Self {
+        let (sender, _) = broadcast::channel(16);
+        Self { values, sender }
+    }
+}
+
+#[derive(Clone)]
+struct SetBroadcastMessage<T> {
+    diffs: Vec<SetDiff<T>>,
+    state: HashSet<T>,
+}
+
+/// A change to an [`ObservableSet`].
+#[derive(Clone)]
+pub enum SetDiff<T> {
+    /// A value was inserted.
+    Insert {
+        /// The new value.
+        value: T,
+    },
+    /// A value was removed.
+    Remove {
+        /// The removed value.
+        value: T,
+    },
+    /// The set was cleared.
+    Clear,
+    /// The subscriber lagged too far behind, and the next update that should
+    /// have been received has already been discarded from the internal buffer.
+    Reset {
+        /// The full set.
+        values: HashSet<T>,
+    },
+}
+
+impl<T> fmt::Debug for SetDiff<T>
+where
+    T: Hash + Eq + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Insert { value } => f.debug_struct("Insert").field("value", value).finish(),
+            Self::Remove { value } => f.debug_struct("Remove").field("value", value).finish(),
+            Self::Clear => f.write_str("Clear"),
+            Self::Reset { values } => f.debug_struct("Reset").field("values", values).finish(),
+        }
+    }
+}
+
+impl<T> PartialEq for SetDiff<T>
+where
+    T: Hash + Eq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Insert { value: v1 }, Self::Insert { value: v2 })
+            | (Self::Remove { value: v1 }, Self::Remove { value: v2 }) => v1 == v2,
+            (Self::Clear, Self::Clear) => true,
+            (Self::Reset { values: v1 }, Self::Reset { values: v2 }) => v1 == v2,
+            _ => false,
+        }
+    }
+}
+
+impl<T> Eq for SetDiff<T> where T: Hash + Eq {}
+
+impl<T> SetDiff<T>
+where
+    T: Hash + Eq + Clone,
+{
+    /// Transform `SetDiff<T>` into `SetDiff<U>` by applying the given function
+    /// to any contained values.
+    ///
+    /// If `f` maps distinct values to the same one, [`Reset`][Self::Reset]
+    /// will contain fewer values than before.
+    pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> SetDiff<U>
+    where
+        U: Hash + Eq + Clone,
+    {
+        match self {
+            SetDiff::Insert { value } => SetDiff::Insert { value: f(value) },
+            SetDiff::Remove { value } => SetDiff::Remove { value: f(value) },
+            SetDiff::Clear => SetDiff::Clear,
+            SetDiff::Reset { values } => {
+                SetDiff::Reset { values: values.into_iter().map(f).collect() }
+            }
+        }
+    }
+
+    /// Applies this [`SetDiff`] to a set.
+    ///
+    /// This is useful to keep two sets in sync, with potentially one
+    /// containing data [`map`](Self::map)ped from the other.
+    pub fn apply(self, set: &mut HashSet<T>) {
+        match self {
+            SetDiff::Insert { value } => {
+                set.insert(value);
+            }
+            SetDiff::Remove { value } => {
+                set.remove(&value);
+            }
+            SetDiff::Clear => {
+                set.clear();
+            }
+            SetDiff::Reset { values } => {
+                *set = values;
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T> serde::Serialize for SetDiff<T>
+where
+    T: serde::Serialize + Hash + Eq,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStructVariant;
+
+        const SELF_NAME: &str = "SetDiff";
+
+        match self {
+            SetDiff::Insert { value } => {
+                let mut state = serializer.serialize_struct_variant(SELF_NAME, 0, "Insert", 1)?;
+                state.serialize_field("value", value)?;
+                state.end()
+            }
+            SetDiff::Remove { value } => {
+                let mut state = serializer.serialize_struct_variant(SELF_NAME, 1, "Remove", 1)?;
+                state.serialize_field("value", value)?;
+                state.end()
+            }
+            SetDiff::Clear => serializer.serialize_struct_variant(SELF_NAME, 2, "Clear", 0)?.end(),
+            SetDiff::Reset { values } => {
+                let mut state = serializer.serialize_struct_variant(SELF_NAME, 3, "Reset", 1)?;
+                state.serialize_field("values", values)?;
+                state.end()
+            }
+        }
+    }
+}
@@ -0,0 +1,221 @@
+use std::{
+    fmt,
+    hash::Hash,
+    pin::Pin,
+    task::{ready, Context, Poll},
+    vec,
+};
+
+use futures_core::Stream;
+use imbl::HashSet;
+use tokio::sync::broadcast::{
+    error::{RecvError, TryRecvError},
+    Receiver,
+};
+#[cfg(feature = "tracing")]
+use tracing::info;
+
+use super::{SetBroadcastMessage, SetDiff};
+use crate::recv_future::ReusableBoxRecvFuture;
+
+/// A subscriber for updates of a [`HashSet`].
+pub struct SetSubscriber<T> {
+    values: HashSet<T>,
+    rx: Receiver<SetBroadcastMessage<T>>,
+}
+
+impl<T> SetSubscriber<T>
+where
+    T: Hash + Eq + Clone + 'static,
+{
+    pub(super) fn new(values: HashSet<T>, rx: Receiver<SetBroadcastMessage<T>>) -> Self {
+        Self { values, rx }
+    }
+
+    /// Get the values the [`ObservableSet`][super::ObservableSet] contained
+    /// when this subscriber was created.
+    pub fn values(&self) -> HashSet<T> {
+        self.values.clone()
+    }
+
+    /// Turn this `SetSubscriber` into a stream of `SetDiff`s.
+    pub fn into_stream(self) -> SetSubscriberStream<T> {
+        SetSubscriberStream::new(ReusableBoxRecvFuture::new(self.rx))
+    }
+
+    /// Turn this `SetSubscriber` into a stream of `Vec<SetDiff>`s.
+    pub fn into_batched_stream(self) -> SetSubscriberBatchedStream<T> {
+        SetSubscriberBatchedStream::new(ReusableBoxRecvFuture::new(self.rx))
+    }
+
+    /// Destructure this `SetSubscriber` into the initial values and a stream of
+    /// `SetDiff`s.
+    ///
+    /// Semantically equivalent to calling `.values()` and `.into_stream()`
+    /// separately, but guarantees that the values are not unnecessarily cloned.
+    pub fn into_values_and_stream(self) -> (HashSet<T>, SetSubscriberStream<T>) {
+        let Self { values, rx } = self;
+        (values, SetSubscriberStream::new(ReusableBoxRecvFuture::new(rx)))
+    }
+
+    /// Destructure this `SetSubscriber` into the initial values and a stream of
+    /// `Vec<SetDiff>`s.
+    ///
+    /// Semantically equivalent to calling `.values()` and
+    /// `.into_batched_stream()` separately, but guarantees that the values
+    /// are not unnecessarily cloned.
+    pub fn into_values_and_batched_stream(self) -> (HashSet<T>, SetSubscriberBatchedStream<T>) {
+        let Self { values, rx } = self;
+        (values, SetSubscriberBatchedStream::new(ReusableBoxRecvFuture::new(rx)))
+    }
+}
+
+impl<T> fmt::Debug for SetSubscriber<T>
+where
+    T: Hash + Eq + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SetSubscriber").field("values", &self.values).finish_non_exhaustive()
+    }
+}
+
+/// A stream of `SetDiff`s created from a [`SetSubscriber`].
+///
+/// Use its [`Stream`] implementation to interact with it (futures-util and
+/// other futures-related crates have extension traits with convenience
+/// methods).
+pub struct SetSubscriberStream<T> {
+    inner: ReusableBoxRecvFuture<SetBroadcastMessage<T>>,
+    // Remaining diffs of a previous message with multiple diffs.
+    batch: vec::IntoIter<SetDiff<T>>,
+}
+
+impl<T> SetSubscriberStream<T> {
+    fn new(inner: ReusableBoxRecvFuture<SetBroadcastMessage<T>>) -> Self {
+        Self { inner, batch: Vec::new().into_iter() }
+    }
+}
+
+impl<T> Unpin for SetSubscriberStream<T> {}
+
+impl<T> fmt::Debug for SetSubscriberStream<T>
+where
+    T: Hash + Eq + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SetSubscriberStream").field("batch", &self.batch).finish_non_exhaustive()
+    }
+}
+
+impl<T> Stream for SetSubscriberStream<T>
+where
+    T: Hash + Eq + Clone + 'static,
+{
+    type Item = SetDiff<T>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if let Some(diff) = self.batch.next() {
+            return Poll::Ready(Some(diff));
+        }
+
+        let (result, mut rx) = ready!(self.inner.poll(cx));
+
+        let poll = match result {
+            Ok(msg) => {
+                let mut iter = msg.diffs.into_iter();
+                let fst = iter.next().expect("ObservableSet never sends empty diffs");
+                self.batch = iter;
+                Poll::Ready(Some(fst))
+            }
+            Err(RecvError::Closed) => Poll::Ready(None),
+            Err(RecvError::Lagged(_)) => {
+                Poll::Ready(handle_lag(&mut rx).map(|values| SetDiff::Reset { values }))
+            }
+        };
+
+        self.inner.set(rx);
+        poll
+    }
+}
+
+/// A batched stream of `SetDiff`s created from a [`SetSubscriber`].
+///
+/// Use its [`Stream`] implementation to interact with it (futures-util and
+/// other futures-related crates have extension traits with convenience
+/// methods).
+#[derive(Debug)]
+pub struct SetSubscriberBatchedStream<T> {
+    inner: ReusableBoxRecvFuture<SetBroadcastMessage<T>>,
+}
+
+impl<T> SetSubscriberBatchedStream<T> {
+    fn new(inner: ReusableBoxRecvFuture<SetBroadcastMessage<T>>) -> Self {
+        Self { inner }
+    }
+}
+
+impl<T> Stream for SetSubscriberBatchedStream<T>
+where
+    T: Hash + Eq + Clone + 'static,
+{
+    type Item = Vec<SetDiff<T>>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let (result, mut rx) = ready!(self.inner.poll(cx));
+
+        let poll = match result {
+            Ok(msg) => {
+                let mut batch = msg.diffs;
+                loop {
+                    match rx.try_recv() {
+                        Ok(mut msg) => batch.append(&mut msg.diffs),
+                        Err(TryRecvError::Empty | TryRecvError::Closed) => {
+                            break Poll::Ready(Some(batch));
+                        }
+                        Err(TryRecvError::Lagged(_)) => {
+                            break Poll::Ready(
+                                handle_lag(&mut rx).map(|values| vec![SetDiff::Reset { values }]),
+                            );
+                        }
+                    }
+                }
+            }
+            Err(RecvError::Closed) => Poll::Ready(None),
+            Err(RecvError::Lagged(_)) => {
+                Poll::Ready(handle_lag(&mut rx).map(|values| vec![SetDiff::Reset { values }]))
+            }
+        };
+
+        self.inner.set(rx);
+        poll
+    }
+}
+
+fn handle_lag<T>(rx: &mut Receiver<SetBroadcastMessage<T>>) -> Option<HashSet<T>>
+where
+    T: Clone,
+{
+    let mut msg = None;
+    loop {
+        match rx.try_recv() {
+            // There's a newer message in the receiver's buffer, use that for reset.
+            Ok(m) => {
+                msg = Some(m);
+            }
+            // The channel was closed, we have no way of obtaining the last state.
+            Err(TryRecvError::Closed) => {
+                #[cfg(feature = "tracing")]
+                info!("Channel closed after lag, can't return last state");
+                return None;
+            }
+            // Lagged twice in a row, look at the next try_recv result.
+            Err(TryRecvError::Lagged(_)) => {}
+            Err(TryRecvError::Empty) => match msg {
+                // We exhausted the internal buffer using try_recv, msg contains the
+                // last message from it, which we use for the reset.
+                Some(msg) => return Some(msg.state),
+                None => unreachable!("got no new message via try_recv after lag"),
+            },
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use std::{borrow::Borrow, fmt, hash::Hash, mem, ops};
+
+use imbl::HashSet;
+
+use super::{ObservableSet, SetBroadcastMessage, SetDiff};
+
+/// A transaction that allows making multiple updates to an `ObservableSet` as
+/// an atomic unit.
+///
+/// For updates from the transaction to have affect, it has to be finalized with
+/// [`.commit()`](Self::commit). If the transaction is dropped without that
+/// method being called, the updates will be discarded.
+pub struct ObservableSetTransaction<'o, T> {
+    // The observable set being modified, only modified on commit.
+    inner: &'o mut ObservableSet<T>,
+    // A clone of the observable's values, what the methods operate on until commit.
+    values: HashSet<T>,
+    // The batched updates, to be sent to subscribers on commit.
+    batch: Vec<SetDiff<T>>,
+}
+
+impl<'o, T> ObservableSetTransaction<'o, T>
+where
+    T: Hash + Eq + Clone + 'static,
+{
+    pub(super) fn new(inner: &'o mut ObservableSet<T>) -> Self {
+        let values = inner.values.clone();
+        Self { inner, values, batch: Vec::new() }
+    }
+
+    /// Commit this transaction, persisting the changes and notifying
+    /// subscribers.
+    pub fn commit(mut self) {
+        #[cfg(feature = "tracing")]
+        tracing::debug!("commit");
+
+        self.inner.values = mem::take(&mut self.values);
+
+        if self.batch.is_empty() {
+            #[cfg(feature = "tracing")]
+            tracing::trace!(
+                target: "eyeball_im::set::broadcast",
+                "Skipping broadcast of empty list of diffs"
+            );
+        } else {
+            let diffs = mem::take(&mut self.batch);
+            let msg = SetBroadcastMessage { diffs, state: self.inner.values.clone() };
+            let _num_receivers = self.inner.sender.send(msg).unwrap_or(0);
+            #[cfg(feature = "tracing")]
+            tracing::debug!(
+                target: "eyeball_im::set::broadcast",
+                "New observable value broadcast to {_num_receivers} receivers"
+            );
+        }
+    }
+
+    /// Roll back all changes made using this transaction so far.
+    ///
+    /// Same as dropping the transaction and starting a new one, semantically.
+    pub fn rollback(&mut self) {
+        #[cfg(feature = "tracing")]
+        tracing::debug!("rollback (explicit)");
+
+        self.values = self.inner.values.clone();
+        self.batch.clear();
+    }
+
+    /// Insert a value and notify subscribers.
+    ///
+    /// Returns whether the value was newly inserted. If it was already part of
+    /// the set, subscribers will not be notified.
+    pub fn insert(&mut self, value: T) -> bool {
+        #[cfg(feature = "tracing")]
+        tracing::debug!(target: "eyeball_im::set::transaction::update", "insert");
+
+        let is_new = self.values.insert(value.clone()).is_none();
+        if is_new {
+            self.add_to_batch(SetDiff::Insert { value });
+        }
+        is_new
+    }
+
+    /// Remove a value, notify subscribers and return the value.
+    ///
+    /// If the value is not part of the set, subscribers will not be notified
+    /// and this method will return `None`.
+    pub fn remove<Q>(&mut self, value: &Q) -> Option<T>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let value = self.values.remove(value)?;
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(target: "eyeball_im::set::transaction::update", "remove");
+
+        self.add_to_batch(SetDiff::Remove { value: value.clone() });
+        Some(value)
+    }
+
+    /// Clear out all of the values in this `HashSet` and notify subscribers.
+    pub fn clear(&mut self) {
+        #[cfg(feature = "tracing")]
+        tracing::debug!(target: "eyeball_im::set::transaction::update", "clear");
+
+        self.values.clear();
+        self.batch.clear(); // All previous batched updates are irrelevant now
+        self.add_to_batch(SetDiff::Clear);
+    }
+
+    fn add_to_batch(&mut self, diff: SetDiff<T>) {
+        if self.inner.sender.receiver_count() != 0 {
+            self.batch.push(diff);
+        }
+    }
+}
+
+impl<T> fmt::Debug for ObservableSetTransaction<'_, T>
+where
+    T: Hash + Eq + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ObservableSetTransaction")
+            .field("values", &self.values)
+            .finish_non_exhaustive()
+    }
+}
+
+// Note: No DerefMut because all mutating must go through inherent methods that
+// notify subscribers
+impl<T> ops::Deref for ObservableSetTransaction<'_, T> {
+    type Target = HashSet<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.values
+    }
+}
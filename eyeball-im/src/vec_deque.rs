@@ -0,0 +1,533 @@
+use std::{fmt, ops};
+
+use imbl::Vector;
+use tokio::sync::broadcast::{self, Sender};
+
+mod subscriber;
+
+pub use self::subscriber::{
+    VecDequeSubscriber, VecDequeSubscriberBatchedStream, VecDequeSubscriberStream,
+};
+
+/// A double-ended queue that broadcasts any changes made to it.
+///
+/// Compared to [`ObservableVector`][crate::ObservableVector], this has a
+/// smaller API tuned for queue-like usage, like task queues or ring logs:
+/// elements are added and removed at the ends, and reordering the queue with
+/// [`rotate_left`][Self::rotate_left], [`rotate_right`][Self::rotate_right] or
+/// [`swap`][Self::swap] is broadcast as a single [`VecDequeDiff`] rather than
+/// as a series of removals and insertions.
+///
+/// Pushing and popping at either end takes amortized constant time.
+///
+/// A queue created with [`with_max_len`][Self::with_max_len] is bounded: once
+/// it is full, pushing an element at one end evicts the element at the other
+/// end, which is broadcast as a [`VecDequeDiff::PopFront`] or
+/// [`VecDequeDiff::PopBack`] before the push.
+pub struct ObservableVecDeque<T> {
+    values: Vector<T>,
+    max_len: Option<usize>,
+    sender: Sender<VecDequeBroadcastMessage<T>>,
+}
+
+impl<T: Clone + 'static> ObservableVecDeque<T> {
+    /// Create a new `ObservableVecDeque`.
+    ///
+    /// As of the time of writing, this is equivalent to
+    /// `ObservableVecDeque::with_capacity(16)`, but the internal buffer
+    /// capacity is subject to change in non-breaking releases.
+    ///
+    /// See [`with_capacity`][Self::with_capacity] for details about the buffer
+    /// capacity.
+    pub fn new() -> Self {
+        Self::with_capacity(16)
+    }
+
+    /// Create a new `ObservableVecDeque` with the given capacity for the inner
+    /// buffer.
+    ///
+    /// Up to `capacity` updates that have not been received by all of the
+    /// subscribers yet will be retained in the inner buffer. If an update
+    /// happens while the buffer is at capacity, the oldest update is discarded
+    /// from it and all subscribers that have not yet received it will instead
+    /// see [`VecDequeDiff::Reset`] as the next update.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the capacity is `0`, or larger than `usize::MAX / 2`.
+    pub fn with_capacity(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+        Self { values: Vector::new(), max_len: None, sender }
+    }
+
+    /// Create a new `ObservableVecDeque` that holds at most `max_len`
+    /// elements.
+    ///
+    /// Pushing an element at one end of a full queue evicts the element at
+    /// the other end. Subscribers are notified of the eviction before the
+    /// push, so they never see more than `max_len` elements.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_len` is `0`.
+    #[track_caller]
+    pub fn with_max_len(max_len: usize) -> Self {
+        if max_len == 0 {
+            panic!("max_len must be greater than 0");
+        }
+
+        let mut this = Self::new();
+        this.max_len = Some(max_len);
+        this
+    }
+
+    /// The maximum number of elements, if this queue was created with
+    /// [`with_max_len`][Self::with_max_len].
+    pub fn max_len(&self) -> Option<usize> {
+        self.max_len
+    }
+
+    /// Whether the queue is bounded and holds its maximum number of elements.
+    pub fn is_full(&self) -> bool {
+        matches!(self.max_len, Some(max_len) if self.values.len() >= max_len)
+    }
+
+    /// Turn the `ObservableVecDeque` back into a regular `Vector`.
+    pub fn into_inner(self) -> Vector<T> {
+        self.values
+    }
+
+    /// Obtain a new subscriber.
+    ///
+    /// If you put the `ObservableVecDeque` behind a lock, it is highly
+    /// recommended to make access of the elements and subscribing one
+    /// operation. Otherwise, the values could be altered in between the
+    /// reading of the values and subscribing to changes.
+    pub fn subscribe(&self) -> VecDequeSubscriber<T> {
+        let rx = self.sender.subscribe();
+        VecDequeSubscriber::new(self.values.clone(), rx)
+    }
+
+    /// Add an element at the front and notify subscribers.
+    ///
+    /// If the queue [is full][Self::is_full], the last element is evicted
+    /// first, see [`pop_back`][Self::pop_back].
+    pub fn push_front(&mut self, value: T) {
+        if self.is_full() {
+            self.pop_back();
+        }
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(target: "eyeball_im::vec_deque::update", "push_front");
+
+        self.values.push_front(value);
+        self.broadcast_diff_with(|| VecDequeDiff::PushFront { value: self.values[0].clone() });
+    }
+
+    /// Add an element at the back and notify subscribers.
+    ///
+    /// If the queue [is full][Self::is_full], the first element is evicted
+    /// first, see [`pop_front`][Self::pop_front].
+    pub fn push_back(&mut self, value: T) {
+        if self.is_full() {
+            self.pop_front();
+        }
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(target: "eyeball_im::vec_deque::update", "push_back");
+
+        self.values.push_back(value);
+        self.broadcast_diff_with(|| VecDequeDiff::PushBack {
+            value: self.values[self.values.len() - 1].clone(),
+        });
+    }
+
+    /// Remove the first element, notify subscribers and return the element.
+    ///
+    /// If there are no elements, subscribers will not be notified and this
+    /// method will return `None`.
+    pub fn pop_front(&mut self) -> Option<T> {
+        let value = self.values.pop_front();
+        if value.is_some() {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(target: "eyeball_im::vec_deque::update", "pop_front");
+
+            self.broadcast_diff_with(|| VecDequeDiff::PopFront);
+        }
+        value
+    }
+
+    /// Remove the last element, notify subscribers and return the element.
+    ///
+    /// If there are no elements, subscribers will not be notified and this
+    /// method will return `None`.
+    pub fn pop_back(&mut self) -> Option<T> {
+        let value = self.values.pop_back();
+        if value.is_some() {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(target: "eyeball_im::vec_deque::update", "pop_back");
+
+            self.broadcast_diff_with(|| VecDequeDiff::PopBack);
+        }
+        value
+    }
+
+    /// Replace the element at the given position, notify subscribers and
+    /// return the previous element at that position.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index >= len`.
+    #[track_caller]
+    pub fn set(&mut self, index: usize, value: T) -> T {
+        let len = self.values.len();
+        if index >= len {
+            panic!("index out of bounds: the length is {len} but the index is {index}");
+        }
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(target: "eyeball_im::vec_deque::update", "set(index = {index})");
+
+        let old_value = self.values.set(index, value);
+        self.broadcast_diff_with(|| VecDequeDiff::Set { index, value: self.values[index].clone() });
+        old_value
+    }
+
+    /// Swap the elements at indices `a` and `b` and notify subscribers.
+    ///
+    /// If `a == b`, subscribers will not be notified.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either index is out of bounds.
+    #[track_caller]
+    pub fn swap(&mut self, a: usize, b: usize) {
+        let len = self.values.len();
+        for index in [a, b] {
+            if index >= len {
+                panic!("index out of bounds: the length is {len} but the index is {index}");
+            }
+        }
+
+        if a != b {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(target: "eyeball_im::vec_deque::update", "swap(a = {a}, b = {b})");
+
+            self.values.swap(a, b);
+            self.broadcast_diff_with(|| VecDequeDiff::Swap { a, b });
+        }
+    }
+
+    /// Rotate the queue `n` places to the left and notify subscribers.
+    ///
+    /// Equivalent to popping `n` elements from the front and pushing them to
+    /// the back, but is broadcast as a single [`VecDequeDiff::RotateLeft`].
+    /// If `n` is `0` or equal to the length, subscribers will not be notified.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n > len`.
+    #[track_caller]
+    pub fn rotate_left(&mut self, n: usize) {
+        let len = self.values.len();
+        if n > len {
+            panic!("rotation amount {n} is greater than the length {len}");
+        }
+
+        if n != 0 && n != len {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(target: "eyeball_im::vec_deque::update", "rotate_left(n = {n})");
+
+            rotate_left(&mut self.values, n);
+            self.broadcast_diff_with(|| VecDequeDiff::RotateLeft { n });
+        }
+    }
+
+    /// Rotate the queue `n` places to the right and notify subscribers.
+    ///
+    /// Equivalent to popping `n` elements from the back and pushing them to
+    /// the front, but is broadcast as a single [`VecDequeDiff::RotateRight`].
+    /// If `n` is `0` or equal to the length, subscribers will not be notified.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n > len`.
+    #[track_caller]
+    pub fn rotate_right(&mut self, n: usize) {
+        let len = self.values.len();
+        if n > len {
+            panic!("rotation amount {n} is greater than the length {len}");
+        }
+
+        if n != 0 && n != len {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(target: "eyeball_im::vec_deque::update", "rotate_right(n = {n})");
+
+            rotate_left(&mut self.values, len - n);
+            self.broadcast_diff_with(|| VecDequeDiff::RotateRight { n });
+        }
+    }
+
+    /// Clear out all of the elements in this `ObservableVecDeque` and notify
+    /// subscribers.
+    ///
+    /// If the queue is already empty, subscribers will not be notified.
+    pub fn clear(&mut self) {
+        let already_empty = self.values.is_empty();
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            target: "eyeball_im::vec_deque::update",
+            nop = already_empty.then_some(true),
+            "clear"
+        );
+
+        if !already_empty {
+            self.values.clear();
+            self.broadcast_diff_with(|| VecDequeDiff::Clear);
+        }
+    }
+
+    fn broadcast_diff_with(&self, make_diff: impl FnOnce() -> VecDequeDiff<T>) {
+        if self.sender.receiver_count() != 0 {
+            let msg = VecDequeBroadcastMessage { diff: make_diff(), state: self.values.clone() };
+            let _num_receivers = self.sender.send(msg).unwrap_or(0);
+            #[cfg(feature = "tracing")]
+            tracing::debug!(
+                target: "eyeball_im::vec_deque::broadcast",
+                "New observable value broadcast to {_num_receivers} receivers"
+            );
+        }
+    }
+}
+
+impl<T: Clone + 'static> Default for ObservableVecDeque<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> fmt::Debug for ObservableVecDeque<T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ObservableVecDeque")
+            .field("values", &self.values)
+            .field("max_len", &self.max_len)
+            .finish_non_exhaustive()
+    }
+}
+
+// Note: No DerefMut because all mutating must go through inherent methods that
+// notify subscribers
+impl<T> ops::Deref for ObservableVecDeque<T> {
+    type Target = Vector<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.values
+    }
+}
+
+impl<T: Clone + 'static> From<Vector<T>> for ObservableVecDeque<T> {
+    fn from(values: Vector<T>) -> Self {
+        let mut this = Self::new();
+        this.values = values;
+        this
+    }
+}
+
+#[derive(Clone)]
+struct VecDequeBroadcastMessage<T> {
+    diff: VecDequeDiff<T>,
+    state: Vector<T>,
+}
+
+/// A change to an [`ObservableVecDeque`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VecDequeDiff<T> {
+    /// An element was added at the front.
+    PushFront {
+        /// The new element.
+        value: T,
+    },
+    /// An element was added at the back.
+    PushBack {
+        /// The new element.
+        value: T,
+    },
+    /// The element at the front was removed.
+    PopFront,
+    /// The element at the back was removed.
+    PopBack,
+    /// The element at `index` was replaced by `value`.
+    Set {
+        /// The index of the element that was replaced.
+        index: usize,
+        /// The new element.
+        value: T,
+    },
+    /// The elements at `a` and `b` were swapped.
+    Swap {
+        /// The index of the first element.
+        a: usize,
+        /// The index of the second element.
+        b: usize,
+    },
+    /// The queue was rotated `n` places to the left, i.e. the first `n`
+    /// elements were moved to the back.
+    RotateLeft {
+        /// The number of places the queue was rotated by.
+        n: usize,
+    },
+    /// The queue was rotated `n` places to the right, i.e. the last `n`
+    /// elements were moved to the front.
+    RotateRight {
+        /// The number of places the queue was rotated by.
+        n: usize,
+    },
+    /// The queue was cleared.
+    Clear,
+    /// The subscriber lagged too far behind, and the next update that should
+    /// have been received has already been discarded from the internal buffer.
+    Reset {
+        /// The full list of elements.
+        values: Vector<T>,
+    },
+}
+
+impl<T: Clone> VecDequeDiff<T> {
+    /// Transform `VecDequeDiff<T>` into `VecDequeDiff<U>` by applying the given
+    /// function to any contained elements.
+    pub fn map<U: Clone>(self, mut f: impl FnMut(T) -> U) -> VecDequeDiff<U> {
+        match self {
+            VecDequeDiff::PushFront { value } => VecDequeDiff::PushFront { value: f(value) },
+            VecDequeDiff::PushBack { value } => VecDequeDiff::PushBack { value: f(value) },
+            VecDequeDiff::PopFront => VecDequeDiff::PopFront,
+            VecDequeDiff::PopBack => VecDequeDiff::PopBack,
+            VecDequeDiff::Set { index, value } => VecDequeDiff::Set { index, value: f(value) },
+            VecDequeDiff::Swap { a, b } => VecDequeDiff::Swap { a, b },
+            VecDequeDiff::RotateLeft { n } => VecDequeDiff::RotateLeft { n },
+            VecDequeDiff::RotateRight { n } => VecDequeDiff::RotateRight { n },
+            VecDequeDiff::Clear => VecDequeDiff::Clear,
+            VecDequeDiff::Reset { values } => {
+                VecDequeDiff::Reset { values: values.into_iter().map(f).collect() }
+            }
+        }
+    }
+
+    /// Applies this [`VecDequeDiff`] to a vector.
+    ///
+    /// This is useful to keep two queues in sync, with potentially one
+    /// containing data [`map`](Self::map)ped from the other.
+    ///
+    /// # Panics
+    ///
+    /// When setting or swapping elements past the end, or rotating by more
+    /// than the length.
+    pub fn apply(self, vec: &mut Vector<T>) {
+        match self {
+            VecDequeDiff::PushFront { value } => {
+                vec.push_front(value);
+            }
+            VecDequeDiff::PushBack { value } => {
+                vec.push_back(value);
+            }
+            VecDequeDiff::PopFront => {
+                vec.pop_front();
+            }
+            VecDequeDiff::PopBack => {
+                vec.pop_back();
+            }
+            VecDequeDiff::Set { index, value } => {
+                vec.set(index, value);
+            }
+            VecDequeDiff::Swap { a, b } => {
+                vec.swap(a, b);
+            }
+            VecDequeDiff::RotateLeft { n } => {
+                rotate_left(vec, n);
+            }
+            VecDequeDiff::RotateRight { n } => {
+                let len = vec.len();
+                rotate_left(vec, len - n);
+            }
+            VecDequeDiff::Clear => {
+                vec.clear();
+            }
+            VecDequeDiff::Reset { values } => {
+                *vec = values;
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T> serde::Serialize for VecDequeDiff<T>
+where
+    T: serde::Serialize + Clone,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStructVariant;
+
+        const SELF_NAME: &str = "VecDequeDiff";
+
+        match self {
+            Self::PushFront { value } => {
+                let mut state =
+                    serializer.serialize_struct_variant(SELF_NAME, 0, "PushFront", 1)?;
+                state.serialize_field("value", value)?;
+                state.end()
+            }
+            Self::PushBack { value } => {
+                let mut state = serializer.serialize_struct_variant(SELF_NAME, 1, "PushBack", 1)?;
+                state.serialize_field("value", value)?;
+                state.end()
+            }
+            Self::PopFront => {
+                serializer.serialize_struct_variant(SELF_NAME, 2, "PopFront", 0)?.end()
+            }
+            Self::PopBack => serializer.serialize_struct_variant(SELF_NAME, 3, "PopBack", 0)?.end(),
+            Self::Set { index, value } => {
+                let mut state = serializer.serialize_struct_variant(SELF_NAME, 4, "Set", 2)?;
+                state.serialize_field("index", index)?;
+                state.serialize_field("value", value)?;
+                state.end()
+            }
+            Self::Swap { a, b } => {
+                let mut state = serializer.serialize_struct_variant(SELF_NAME, 5, "Swap", 2)?;
+                state.serialize_field("a", a)?;
+                state.serialize_field("b", b)?;
+                state.end()
+            }
+            Self::RotateLeft { n } => {
+                let mut state =
+                    serializer.serialize_struct_variant(SELF_NAME, 6, "RotateLeft", 1)?;
+                state.serialize_field("n", n)?;
+                state.end()
+            }
+            Self::RotateRight { n } => {
+                let mut state =
+                    serializer.serialize_struct_variant(SELF_NAME, 7, "RotateRight", 1)?;
+                state.serialize_field("n", n)?;
+                state.end()
+            }
+            Self::Clear => serializer.serialize_struct_variant(SELF_NAME, 8, "Clear", 0)?.end(),
+            Self::Reset { values } => {
+                let mut state = serializer.serialize_struct_variant(SELF_NAME, 9, "Reset", 1)?;
+                state.serialize_field("values", values)?;
+                state.end()
+            }
+        }
+    }
+}
+
+/// Move the first `n` elements of `values` to the back.
+fn rotate_left<T: Clone>(values: &mut Vector<T>, n: usize) {
+    let back = values.split_off(n);
+    let front = std::mem::replace(values, back);
+    values.append(front);
+}
@@ -0,0 +1,185 @@
+use std::{
+    fmt,
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+
+use futures_core::Stream;
+use imbl::Vector;
+use tokio::sync::broadcast::{
+    error::{RecvError, TryRecvError},
+    Receiver,
+};
+#[cfg(feature = "tracing")]
+use tracing::info;
+
+use super::{VecDequeBroadcastMessage, VecDequeDiff};
+use crate::recv_future::ReusableBoxRecvFuture;
+
+/// A subscriber for updates of an
+/// [`ObservableVecDeque`][super::ObservableVecDeque].
+pub struct VecDequeSubscriber<T> {
+    values: Vector<T>,
+    rx: Receiver<VecDequeBroadcastMessage<T>>,
+}
+
+impl<T: Clone + 'static> VecDequeSubscriber<T> {
+    pub(super) fn new(values: Vector<T>, rx: Receiver<VecDequeBroadcastMessage<T>>) -> Self {
+        Self { values, rx }
+    }
+
+    /// Get the values the [`ObservableVecDeque`][super::ObservableVecDeque]
+    /// contained when this subscriber was created.
+    pub fn values(&self) -> Vector<T> {
+        self.values.clone()
+    }
+
+    /// Turn this `VecDequeSubscriber` into a stream of `VecDequeDiff`s.
+    pub fn into_stream(self) -> VecDequeSubscriberStream<T> {
+        VecDequeSubscriberStream::new(ReusableBoxRecvFuture::new(self.rx))
+    }
+
+    /// Turn this `VecDequeSubscriber` into a stream of `Vec<VecDequeDiff>`s.
+    pub fn into_batched_stream(self) -> VecDequeSubscriberBatchedStream<T> {
+        VecDequeSubscriberBatchedStream::new(ReusableBoxRecvFuture::new(self.rx))
+    }
+
+    /// Destructure this `VecDequeSubscriber` into the initial values and a
+    /// stream of `VecDequeDiff`s.
+    ///
+    /// Semantically equivalent to calling `.values()` and `.into_stream()`
+    /// separately, but guarantees that the values are not unnecessarily cloned.
+    pub fn into_values_and_stream(self) -> (Vector<T>, VecDequeSubscriberStream<T>) {
+        let Self { values, rx } = self;
+        (values, VecDequeSubscriberStream::new(ReusableBoxRecvFuture::new(rx)))
+    }
+
+    /// Destructure this `VecDequeSubscriber` into the initial values and a
+    /// stream of `Vec<VecDequeDiff>`s.
+    ///
+    /// Semantically equivalent to calling `.values()` and
+    /// `.into_batched_stream()` separately, but guarantees that the values
+    /// are not unnecessarily cloned.
+    pub fn into_values_and_batched_stream(self) -> (Vector<T>, VecDequeSubscriberBatchedStream<T>) {
+        let Self { values, rx } = self;
+        (values, VecDequeSubscriberBatchedStream::new(ReusableBoxRecvFuture::new(rx)))
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for VecDequeSubscriber<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VecDequeSubscriber").field("values", &self.values).finish_non_exhaustive()
+    }
+}
+
+/// A stream of `VecDequeDiff`s created from a [`VecDequeSubscriber`].
+///
+/// Use its [`Stream`] implementation to interact with it (futures-util and
+/// other futures-related crates have extension traits with convenience
+/// methods).
+#[derive(Debug)]
+pub struct VecDequeSubscriberStream<T> {
+    inner: ReusableBoxRecvFuture<VecDequeBroadcastMessage<T>>,
+}
+
+impl<T> VecDequeSubscriberStream<T> {
+    fn new(inner: ReusableBoxRecvFuture<VecDequeBroadcastMessage<T>>) -> Self {
+        Self { inner }
+    }
+}
+
+impl<T: Clone + 'static> Stream for VecDequeSubscriberStream<T> {
+    type Item = VecDequeDiff<T>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let (result, mut rx) = ready!(self.inner.poll(cx));
+
+        let poll = match result {
+            Ok(msg) => Poll::Ready(Some(msg.diff)),
+            Err(RecvError::Closed) => Poll::Ready(None),
+            Err(RecvError::Lagged(_)) => {
+                Poll::Ready(handle_lag(&mut rx).map(|values| VecDequeDiff::Reset { values }))
+            }
+        };
+
+        self.inner.set(rx);
+        poll
+    }
+}
+
+/// A batched stream of `VecDequeDiff`s created from a [`VecDequeSubscriber`].
+///
+/// Use its [`Stream`] implementation to interact with it (futures-util and
+/// other futures-related crates have extension traits with convenience
+/// methods).
+#[derive(Debug)]
+pub struct VecDequeSubscriberBatchedStream<T> {
+    inner: ReusableBoxRecvFuture<VecDequeBroadcastMessage<T>>,
+}
+
+impl<T> VecDequeSubscriberBatchedStream<T> {
+    fn new(inner: ReusableBoxRecvFuture<VecDequeBroadcastMessage<T>>) -> Self {
+        Self { inner }
+    }
+}
+
+impl<T: Clone + 'static> Stream for VecDequeSubscriberBatchedStream<T> {
+    type Item = Vec<VecDequeDiff<T>>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let (result, mut rx) = ready!(self.inner.poll(cx));
+
+        let poll = match result {
+            Ok(msg) => {
+                let mut batch = vec![msg.diff];
+                loop {
+                    match rx.try_recv() {
+                        Ok(msg) => batch.push(msg.diff),
+                        Err(TryRecvError::Empty | TryRecvError::Closed) => {
+                            break Poll::Ready(Some(batch));
+                        }
+                        Err(TryRecvError::Lagged(_)) => {
+                            break Poll::Ready(
+                                handle_lag(&mut rx)
+                                    .map(|values| vec![VecDequeDiff::Reset { values }]),
+                            );
+                        }
+                    }
+                }
+            }
+            Err(RecvError::Closed) => Poll::Ready(None),
+            Err(RecvError::Lagged(_)) => {
+                Poll::Ready(handle_lag(&mut rx).map(|values| vec![VecDequeDiff::Reset { values }]))
+            }
+        };
+
+        self.inner.set(rx);
+        poll
+    }
+}
+
+fn handle_lag<T: Clone>(rx: &mut Receiver<VecDequeBroadcastMessage<T>>) -> Option<Vector<T>> {
+    let mut msg = None;
+    loop {
+        match rx.try_recv() {
+            // There's a newer message in the receiver's buffer, use that for reset.
+            Ok(m) => {
+                msg = Some(m);
+            }
+            // The channel was closed, we have no way of obtaining the last state.
+            Err(TryRecvError::Closed) => {
+                #[cfg(feature = "tracing")]
+                info!("Channel closed after lag, can't return last state");
+                return None;
+            }
+            // Lagged twice in a row, look at the next try_recv result.
+            Err(TryRecvError::Lagged(_)) => {}
+            Err(TryRecvError::Empty) => match msg {
+                // We exhausted the internal buffer using try_recv, msg contains the
+                // last message from it, which we use for the reset.
+                Some(msg) => return Some(msg.state),
+                None => unreachable!("got no new message via try_recv after lag"),
+            },
+        }
+    }
+}
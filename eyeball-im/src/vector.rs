@@ -1,25 +1,68 @@
-use std::{fmt, ops};
+use std::{
+    cmp::{min, Ordering},
+    fmt, iter, mem,
+    ops::{self, Bound, Range, RangeBounds},
+    slice,
+    sync::{
+        atomic::{AtomicUsize, Ordering as AtomicOrdering},
+        Arc, Mutex,
+    },
+};
+
+#[cfg(feature = "update-seq")]
+use eyeball::UpdateSeq;
 
 use imbl::Vector;
 use tokio::sync::broadcast::{self, Sender};
 
+mod compact;
+mod cursor;
+#[cfg(feature = "serde")]
+mod de;
 mod entry;
+#[cfg(feature = "update-seq")]
+mod history;
+mod hooks;
+mod len_subscriber;
+mod range;
 mod subscriber;
 mod transaction;
 
+#[cfg(feature = "update-seq")]
+use self::history::History;
 pub use self::{
-    entry::{ObservableVectorEntries, ObservableVectorEntry},
-    subscriber::{VectorSubscriber, VectorSubscriberBatchedStream, VectorSubscriberStream},
+    compact::{DiffTable, ParseCompactDiffError},
+    cursor::VectorCursor,
+    entry::{ObservableVectorEntries, ObservableVectorEntry, ObservableVectorFilteredEntries},
+    len_subscriber::VectorLenSubscriber,
+    subscriber::{
+        VectorSubscriber, VectorSubscriberBatchedStream, VectorSubscriberMapped,
+        VectorSubscriberStream,
+    },
     transaction::{
         ObservableVectorTransaction, ObservableVectorTransactionEntries,
         ObservableVectorTransactionEntry,
     },
 };
+use self::{hooks::Hooks, range::RangeSenders};
 
 /// An ordered list of elements that broadcasts any changes made to it.
 pub struct ObservableVector<T> {
     values: Vector<T>,
     sender: Sender<BroadcastMessage<T>>,
+    hooks: Option<Hooks<T>>,
+    len_sender: Sender<usize>,
+    /// The length that was last sent through `len_sender`.
+    ///
+    /// Only kept up to date while there are length subscribers.
+    sent_len: AtomicUsize,
+    range_senders: Mutex<RangeSenders<T>>,
+    /// The number of senders in `range_senders`, to skip locking it if there
+    /// are none.
+    num_range_senders: AtomicUsize,
+    max_len: Option<usize>,
+    #[cfg(feature = "update-seq")]
+    history: Option<Mutex<History<T>>>,
 }
 
 impl<T: Clone + 'static> ObservableVector<T> {
@@ -49,12 +92,178 @@ impl<T: Clone + 'static> ObservableVector<T> {
     /// Panics if the capacity is `0`, or larger than `usize::MAX / 2`.
     pub fn with_capacity(capacity: usize) -> Self {
         let (sender, _) = broadcast::channel(capacity);
-        Self { values: Vector::new(), sender }
+        let (len_sender, _) = broadcast::channel(1);
+        Self {
+            values: Vector::new(),
+            sender,
+            hooks: None,
+            len_sender,
+            sent_len: AtomicUsize::new(0),
+            range_senders: Mutex::new(RangeSenders::new(capacity)),
+            num_range_senders: AtomicUsize::new(0),
+            max_len: None,
+            #[cfg(feature = "update-seq")]
+            history: None,
+        }
+    }
+
+    /// Limit this `ObservableVector` to `max_len` elements, turning it into a
+    /// ring buffer.
+    ///
+    /// Whenever an update makes the vector longer than `max_len`, the
+    /// elements beyond it are evicted from the opposite end: from the back
+    /// if the new elements were added at the front, through
+    /// [`push_front`][Self::push_front], or [`insert`][Self::insert] or
+    /// [`splice`][Self::splice] at index `0`, and from the front for all other
+    /// updates. If the vector is already longer than `max_len`, the excess
+    /// elements are evicted from the front right away.
+    ///
+    /// The eviction is broadcast in the same update as the change that caused
+    /// it, so a [batched stream][VectorSubscriber::into_batched_stream]
+    /// yields both in one item and never observes the vector being longer
+    /// than `max_len`. A regular [stream][VectorSubscriber::into_stream]
+    /// yields the diffs one by one, so it does see the intermediate state.
+    ///
+    /// This is useful for views of the last N events, like logs, where
+    /// evicting elements manually would double the number of updates.
+    ///
+    /// ```
+    /// use eyeball_im::{ObservableVector, VectorDiff};
+    /// use imbl::vector;
+    /// use stream_assert::assert_next_eq;
+    ///
+    /// let mut ob = ObservableVector::from(vector![1, 2]).with_max_len(2);
+    /// let mut sub = ob.subscribe().into_batched_stream();
+    ///
+    /// ob.push_back(3);
+    /// assert_eq!(*ob, vector![2, 3]);
+    /// assert_next_eq!(sub, vec![VectorDiff::PushBack { value: 3 }, VectorDiff::PopFront]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_len` is `0`.
+    #[track_caller]
+    pub fn with_max_len(mut self, max_len: usize) -> Self {
+        assert!(max_len != 0, "max_len must not be zero");
+        self.max_len = Some(max_len);
+
+        let evicted = self.evict(false);
+        if !evicted.is_empty() {
+            self.broadcast_diffs(evicted);
+        }
+        self
+    }
+
+    /// The maximum number of elements, if it was limited with
+    /// [`with_max_len`][Self::with_max_len].
+    pub fn max_len(&self) -> Option<usize> {
+        self.max_len
+    }
+
+    /// Register callbacks that are run whenever an element enters
+    /// (`on_insert`) or leaves (`on_remove`) this `ObservableVector`.
+    ///
+    /// The callbacks are run synchronously from within the mutating methods,
+    /// before subscribers are notified. This makes them suitable for acquiring
+    /// and releasing resources tied to an element being part of the vector:
+    /// every element that `on_insert` is called for gets exactly one matching
+    /// `on_remove` call, be it from [`remove`][Self::remove],
+    /// [`set`][Self::set] replacing it, [`clear`][Self::clear],
+    /// [`into_inner`][Self::into_inner] or the `ObservableVector` being
+    /// dropped. Since the callbacks are tied to the vector itself,
+    /// subscribers lagging behind and seeing a [`VectorDiff::Reset`] don't
+    /// affect them.
+    ///
+    /// Elements that are modified in place with
+    /// [`update_range`][Self::update_range] are treated like they were
+    /// replaced: `on_remove` is called with a clone of the element from before
+    /// the modification, and `on_insert` with the modified element.
+    ///
+    /// `on_insert` is called right away for the elements that are already part
+    /// of the vector. Any previously registered callbacks are replaced without
+    /// being called.
+    ///
+    /// For [transactions][Self::transaction], the callbacks are run on
+    /// [`commit`][ObservableVectorTransaction::commit] for the changes that
+    /// are actually persisted; changes that are rolled back never invoke them.
+    pub fn with_hooks(
+        mut self,
+        on_insert: impl FnMut(&T) + Send + Sync + 'static,
+        on_remove: impl FnMut(&T) + Send + Sync + 'static,
+    ) -> Self {
+        let mut hooks = Hooks::new(on_insert, on_remove);
+        for value in &self.values {
+            hooks.inserted(value);
+        }
+        self.hooks = Some(hooks);
+        self
+    }
+
+    /// Retain the diffs of the last `capacity` updates, such that they can be
+    /// queried with [`diffs_since`][Self::diffs_since].
+    ///
+    /// This allows pull-based consumers, like long-polling HTTP endpoints, to
+    /// fetch the changes since the state they last saw without keeping a
+    /// subscriber around. Any previously retained updates are discarded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the capacity is `0`.
+    #[cfg(feature = "update-seq")]
+    pub fn with_history(mut self, capacity: usize) -> Self {
+        assert!(capacity != 0, "history capacity must not be zero");
+        self.history = Some(Mutex::new(History::new(capacity)));
+        self
+    }
+
+    /// Get the sequence number of the current state, for use with
+    /// [`diffs_since`][Self::diffs_since].
+    ///
+    /// This is the sequence number of the latest update, or a sequence number
+    /// taken when the history was enabled if there was no update since then.
+    /// Returns `None` if the history is not enabled.
+    #[cfg(feature = "update-seq")]
+    pub fn last_update_seq(&self) -> Option<UpdateSeq> {
+        Some(self.history.as_ref()?.lock().unwrap().last_seq())
+    }
+
+    /// Get the diffs of all updates after the one with the given sequence
+    /// number, as [`UpdateSeq::get`] returned it for the result of
+    /// [`last_update_seq`][Self::last_update_seq].
+    ///
+    /// Returns `None` if the history is not enabled, or if `seq` is unknown,
+    /// for example because it is so old that some of the updates after it are
+    /// no longer retained. In that case, the consumer has to start over with
+    /// the current values. If there were no updates since `seq`, an empty list
+    /// is returned.
+    ///
+    /// ```
+    /// use eyeball_im::{ObservableVector, VectorDiff};
+    /// use imbl::vector;
+    ///
+    /// let mut ob = ObservableVector::from(vector![1]).with_history(8);
+    /// let seq = ob.last_update_seq().unwrap().get();
+    ///
+    /// ob.push_back(2);
+    /// ob.remove(0);
+    /// assert_eq!(
+    ///     ob.diffs_since(seq),
+    ///     Some(vec![VectorDiff::PushBack { value: 2 }, VectorDiff::Remove { index: 0 }])
+    /// );
+    ///
+    /// let seq = ob.last_update_seq().unwrap().get();
+    /// assert_eq!(ob.diffs_since(seq), Some(vec![]));
+    /// ```
+    #[cfg(feature = "update-seq")]
+    pub fn diffs_since(&self, seq: u64) -> Option<Vec<VectorDiff<T>>> {
+        self.history.as_ref()?.lock().unwrap().diffs_since(seq)
     }
 
     /// Turn the `ObservableVector` back into a regular `Vector`.
-    pub fn into_inner(self) -> Vector<T> {
-        self.values
+    pub fn into_inner(mut self) -> Vector<T> {
+        self.run_remove_hooks(0);
+        mem::take(&mut self.values)
     }
 
     /// Obtain a new subscriber.
@@ -68,14 +277,60 @@ impl<T: Clone + 'static> ObservableVector<T> {
         VectorSubscriber::new(self.values.clone(), rx)
     }
 
+    /// Obtain a new subscriber that only observes the elements in the given
+    /// range of indices.
+    ///
+    /// The subscriber presents the elements in `range` as a vector of their
+    /// own: its initial values are the elements currently in the range, and
+    /// its diffs have indices relative to `range.start`. Elements that are
+    /// pushed into or out of the range by insertions or removals before it
+    /// show up as such. Unlike with a regular subscriber that is limited to
+    /// the range afterwards, updates that don't change any element in the
+    /// range don't wake up this subscriber at all, which matters for large
+    /// vectors with many subscribers observing small parts of them.
+    ///
+    /// Like with [`subscribe`][Self::subscribe], the subscriber sees a
+    /// [`VectorDiff::Reset`] of the elements in the range if it lags behind.
+    pub fn subscribe_range(&self, range: Range<usize>) -> VectorSubscriber<T> {
+        let mut range_senders = self.range_senders.lock().unwrap();
+        let subscriber = range_senders.subscribe(range, &self.values);
+        self.num_range_senders.store(range_senders.len(), AtomicOrdering::Relaxed);
+        subscriber
+    }
+
+    /// Obtain a new subscriber that only receives the length of the vector.
+    ///
+    /// Unlike a [`VectorSubscriber`], this doesn't buffer any diffs or clone
+    /// any elements: length updates are sent through a separate channel, and
+    /// only when an update actually changes the length.
+    pub fn subscribe_len(&self) -> VectorLenSubscriber {
+        let rx = self.len_sender.subscribe();
+        let len = self.values.len();
+        self.sent_len.store(len, AtomicOrdering::Relaxed);
+        VectorLenSubscriber::new(len, rx)
+    }
+
+    /// Obtain a new cursor, positioned before the first element.
+    ///
+    /// Unlike an index, the cursor keeps its logical position while the vector
+    /// is updated, so it can be used to process the elements incrementally
+    /// while they continue to change. See [`VectorCursor`] for details.
+    pub fn cursor(&self) -> VectorCursor<T> {
+        let rx = self.sender.subscribe();
+        VectorCursor::new(self.values.clone(), rx)
+    }
+
     /// Append the given elements at the end of the `Vector` and notify
     /// subscribers.
     pub fn append(&mut self, values: Vector<T>) {
         #[cfg(feature = "tracing")]
         tracing::debug!(target: "eyeball_im::vector::update", "append(len = {})", values.len());
 
+        if let Some(hooks) = &mut self.hooks {
+            values.iter().for_each(|value| hooks.inserted(value));
+        }
         self.values.append(values.clone());
-        self.broadcast_diff(VectorDiff::Append { values });
+        self.broadcast_diff_and_evict(false, |_| VectorDiff::Append { values });
     }
 
     /// Clear out all of the elements in this `Vector` and notify subscribers.
@@ -90,6 +345,7 @@ impl<T: Clone + 'static> ObservableVector<T> {
         );
 
         if !already_empty {
+            self.run_remove_hooks(0);
             self.values.clear();
             self.broadcast_diff(VectorDiff::Clear);
         }
@@ -100,8 +356,11 @@ impl<T: Clone + 'static> ObservableVector<T> {
         #[cfg(feature = "tracing")]
         tracing::debug!(target: "eyeball_im::vector::update", "push_front");
 
-        self.values.push_front(value.clone());
-        self.broadcast_diff(VectorDiff::PushFront { value });
+        self.run_insert_hook(&value);
+        self.values.push_front(value);
+        self.broadcast_diff_and_evict(true, |values| VectorDiff::PushFront {
+            value: values[0].clone(),
+        });
     }
 
     /// Add an element at the back of the list and notify subscribers.
@@ -109,8 +368,11 @@ impl<T: Clone + 'static> ObservableVector<T> {
         #[cfg(feature = "tracing")]
         tracing::debug!(target: "eyeball_im::vector::update", "push_back");
 
-        self.values.push_back(value.clone());
-        self.broadcast_diff(VectorDiff::PushBack { value });
+        self.run_insert_hook(&value);
+        self.values.push_back(value);
+        self.broadcast_diff_and_evict(false, |values| VectorDiff::PushBack {
+            value: values.back().expect("vector is not empty").clone(),
+        });
     }
 
     /// Remove the first element, notify subscribers and return the element.
@@ -119,10 +381,11 @@ impl<T: Clone + 'static> ObservableVector<T> {
     /// method will return `None`.
     pub fn pop_front(&mut self) -> Option<T> {
         let value = self.values.pop_front();
-        if value.is_some() {
+        if let Some(value) = &value {
             #[cfg(feature = "tracing")]
             tracing::debug!(target: "eyeball_im::vector::update", "pop_front");
 
+            self.run_remove_hook(value);
             self.broadcast_diff(VectorDiff::PopFront);
         }
         value
@@ -134,10 +397,11 @@ impl<T: Clone + 'static> ObservableVector<T> {
     /// method will return `None`.
     pub fn pop_back(&mut self) -> Option<T> {
         let value = self.values.pop_back();
-        if value.is_some() {
+        if let Some(value) = &value {
             #[cfg(feature = "tracing")]
             tracing::debug!(target: "eyeball_im::vector::update", "pop_back");
 
+            self.run_remove_hook(value);
             self.broadcast_diff(VectorDiff::PopBack);
         }
         value
@@ -155,8 +419,12 @@ impl<T: Clone + 'static> ObservableVector<T> {
             #[cfg(feature = "tracing")]
             tracing::debug!(target: "eyeball_im::vector::update", "insert(index = {index})");
 
-            self.values.insert(index, value.clone());
-            self.broadcast_diff(VectorDiff::Insert { index, value });
+            self.run_insert_hook(&value);
+            self.values.insert(index, value);
+            self.broadcast_diff_and_evict(index == 0, |values| VectorDiff::Insert {
+                index,
+                value: values[index].clone(),
+            });
         } else {
             panic!("index out of bounds: the length is {len} but the index is {index}");
         }
@@ -175,14 +443,107 @@ impl<T: Clone + 'static> ObservableVector<T> {
             #[cfg(feature = "tracing")]
             tracing::debug!(target: "eyeball_im::vector::update", "set(index = {index})");
 
-            let old_value = self.values.set(index, value.clone());
-            self.broadcast_diff(VectorDiff::Set { index, value });
+            let old_value = self.values.set(index, value);
+            if let Some(hooks) = &mut self.hooks {
+                hooks.removed(&old_value);
+                hooks.inserted(&self.values[index]);
+            }
+            self.broadcast_diff_with(|| VectorDiff::Set {
+                index,
+                value: self.values[index].clone(),
+            });
             old_value
         } else {
             panic!("index out of bounds: the length is {len} but the index is {index}");
         }
     }
 
+    /// Replace the elements starting at `start` by `values`, notify
+    /// subscribers and return the replaced elements.
+    ///
+    /// Unlike calling [`set`][Self::set] for each element, subscribers are
+    /// notified with a single update containing one `VectorDiff::Set` per
+    /// element, so they are only woken up once. Does nothing if `values` is
+    /// empty.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start + values.len() > len`.
+    #[track_caller]
+    pub fn set_range(&mut self, start: usize, values: Vector<T>) -> Vec<T> {
+        let len = self.values.len();
+        let end = start.checked_add(values.len()).expect("range end overflowed");
+        if end > len {
+            panic!("range end index {end} out of range for vector of length {len}");
+        }
+        if start == end {
+            return Vec::new();
+        }
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            target: "eyeball_im::vector::update",
+            "set_range(start = {start}, end = {end})"
+        );
+
+        let removed = splice_values(&mut self.values, start, end, values);
+        if let Some(hooks) = &mut self.hooks {
+            removed.iter().for_each(|value| hooks.removed(value));
+            self.values
+                .iter()
+                .skip(start)
+                .take(end - start)
+                .for_each(|value| hooks.inserted(value));
+        }
+
+        self.broadcast_sets(start, end);
+        removed.into_iter().collect()
+    }
+
+    /// Update the elements in the given range in place with `f` and notify
+    /// subscribers.
+    ///
+    /// Subscribers are notified with a single update containing one
+    /// `VectorDiff::Set` per element in the range, like with
+    /// [`set_range`][Self::set_range]. Does nothing if the range is empty.
+    ///
+    /// If [hooks][Self::with_hooks] are registered, every element in the range
+    /// is cloned before being modified, and the hooks are run as if the clone
+    /// was replaced by the modified element.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than its end, or if the end
+    /// is greater than `len`.
+    #[track_caller]
+    pub fn update_range(&mut self, range: impl RangeBounds<usize>, mut f: impl FnMut(&mut T)) {
+        let (start, end) = resolve_range(range, self.values.len());
+        if start == end {
+            return;
+        }
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            target: "eyeball_im::vector::update",
+            "update_range(start = {start}, end = {end})"
+        );
+
+        for index in start..end {
+            let value = &mut self.values[index];
+            match &mut self.hooks {
+                Some(hooks) => {
+                    let old_value = value.clone();
+                    f(value);
+                    hooks.removed(&old_value);
+                    hooks.inserted(value);
+                }
+                None => f(value),
+            }
+        }
+
+        self.broadcast_sets(start, end);
+    }
+
     /// Remove the element at the given position, notify subscribers and return
     /// the element.
     ///
@@ -197,6 +558,7 @@ impl<T: Clone + 'static> ObservableVector<T> {
             tracing::debug!(target: "eyeball_im::vector::update", "remove(index = {index})");
 
             let value = self.values.remove(index);
+            self.run_remove_hook(&value);
             self.broadcast_diff(VectorDiff::Remove { index });
             value
         } else {
@@ -204,6 +566,49 @@ impl<T: Clone + 'static> ObservableVector<T> {
         }
     }
 
+    /// Remove the element at the given position by replacing it with the last
+    /// element, notify subscribers and return the removed element.
+    ///
+    /// This does not preserve the order of the remaining elements, but unlike
+    /// [`remove`][Self::remove] it doesn't shift the elements after `index`,
+    /// which is cheaper for subscribers that maintain their own copy of the
+    /// vector. Subscribers receive a `VectorDiff::Set` for `index` followed by
+    /// a `VectorDiff::PopBack`, or only the latter if `index` is the last
+    /// position.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index >= len`.
+    #[track_caller]
+    pub fn swap_remove(&mut self, index: usize) -> T {
+        let len = self.values.len();
+        if index < len {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(target: "eyeball_im::vector::update", "swap_remove(index = {index})");
+
+            let last = self.values.pop_back().expect("vector is not empty");
+            if index == len - 1 {
+                self.run_remove_hook(&last);
+                self.broadcast_diff(VectorDiff::PopBack);
+                last
+            } else {
+                let value = self.values.set(index, last);
+                self.run_remove_hook(&value);
+                if self.has_diff_receivers() {
+                    self.broadcast(OneOrManyDiffs::Many(vec![
+                        VectorDiff::Set { index, value: self.values[index].clone() },
+                        VectorDiff::PopBack,
+                    ]));
+                } else {
+                    self.broadcast_len();
+                }
+                value
+            }
+        } else {
+            panic!("index out of bounds: the length is {len} but the index is {index}");
+        }
+    }
+
     /// Truncate the vector to `len` elements and notify subscribers.
     ///
     /// Does nothing if `len` is greater or equal to the vector's current
@@ -213,11 +618,165 @@ impl<T: Clone + 'static> ObservableVector<T> {
             #[cfg(feature = "tracing")]
             tracing::debug!(target: "eyeball_im::vector::update", "truncate(len = {len})");
 
+            self.run_remove_hooks(len);
             self.values.truncate(len);
             self.broadcast_diff(VectorDiff::Truncate { length: len });
         }
     }
 
+    /// Remove the elements in the given range, notify subscribers and return
+    /// the removed elements.
+    ///
+    /// Subscribers are notified with a single update: a `VectorDiff::Clear` or
+    /// `VectorDiff::Truncate` if the range extends to the end of the vector,
+    /// otherwise one `VectorDiff::Remove` per element, or a
+    /// `VectorDiff::Reset` if fewer elements remain than were removed. Does
+    /// nothing if the range is empty.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than its end, or if the end
+    /// is greater than `len`.
+    #[track_caller]
+    pub fn drain(&mut self, range: impl RangeBounds<usize>) -> Vec<T> {
+        let (start, end) = resolve_range(range, self.values.len());
+        if start == end {
+            return Vec::new();
+        }
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            target: "eyeball_im::vector::update",
+            "drain(start = {start}, end = {end})"
+        );
+
+        let prev_len = self.values.len();
+        let removed = splice_values(&mut self.values, start, end, Vector::new());
+        if let Some(hooks) = &mut self.hooks {
+            removed.iter().for_each(|value| hooks.removed(value));
+        }
+
+        self.broadcast_diffs(splice_diffs(start, end, prev_len, &Vector::new(), &self.values));
+
+        removed.into_iter().collect()
+    }
+
+    /// Replace the elements in the given range by `replacement`, notify
+    /// subscribers and return the replaced elements.
+    ///
+    /// Like [`drain`][Self::drain], subscribers are notified with a single
+    /// update: elements that are replaced one-to-one are broadcast as
+    /// `VectorDiff::Set`s, followed by the diffs for removing or inserting the
+    /// remaining ones. If the whole vector is replaced, a `VectorDiff::Reset`
+    /// is sent instead. Does nothing if both the range and `replacement` are
+    /// empty.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than its end, or if the end
+    /// is greater than `len`.
+    #[track_caller]
+    pub fn splice(&mut self, range: impl RangeBounds<usize>, replacement: Vector<T>) -> Vec<T> {
+        let (start, end) = resolve_range(range, self.values.len());
+        if start == end && replacement.is_empty() {
+            return Vec::new();
+        }
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            target: "eyeball_im::vector::update",
+            "splice(start = {start}, end = {end}, len = {})",
+            replacement.len()
+        );
+
+        let prev_len = self.values.len();
+        let removed = splice_values(&mut self.values, start, end, replacement.clone());
+        if let Some(hooks) = &mut self.hooks {
+            removed.iter().for_each(|value| hooks.removed(value));
+            replacement.iter().for_each(|value| hooks.inserted(value));
+        }
+
+        let mut diffs = splice_diffs(start, end, prev_len, &replacement, &self.values);
+        diffs.extend(self.evict(start == 0));
+        self.broadcast_diffs(diffs);
+
+        removed.into_iter().collect()
+    }
+
+    /// Sort the vector and notify subscribers.
+    ///
+    /// See [`sort_by`][Self::sort_by] for details.
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.sort_by(Ord::cmp);
+    }
+
+    /// Sort the vector with the given comparison function and notify
+    /// subscribers.
+    ///
+    /// The sort is stable. Subscribers are notified with a single update: the
+    /// `VectorDiff::Move`s that reorder the vector with as few moved elements
+    /// as possible, or a `VectorDiff::Reset` if more than half of the elements
+    /// would have to be moved. Does nothing if the vector is already sorted.
+    pub fn sort_by(&mut self, compare: impl FnMut(&T, &T) -> Ordering) {
+        let (values, new_indices) = sort_values(&self.values, compare);
+        let diffs = reorder_diffs(&new_indices, &values);
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            target: "eyeball_im::vector::update",
+            nop = diffs.is_empty().then_some(true),
+            "sort"
+        );
+
+        if !diffs.is_empty() {
+            self.values = values;
+            self.broadcast_diffs(diffs);
+        }
+    }
+
+    /// Sort the vector with the given key extraction function and notify
+    /// subscribers.
+    ///
+    /// See [`sort_by`][Self::sort_by] for details.
+    pub fn sort_by_key<K: Ord>(&mut self, mut f: impl FnMut(&T) -> K) {
+        self.sort_by(|a, b| f(a).cmp(&f(b)));
+    }
+
+    /// Move the element at position `from` to position `to` and notify
+    /// subscribers.
+    ///
+    /// The elements between `from` and `to` are shifted by one position
+    /// towards `from`. Does nothing if `from == to`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `from >= len` or `to >= len`.
+    #[track_caller]
+    pub fn move_item(&mut self, from: usize, to: usize) {
+        let len = self.values.len();
+        if from >= len {
+            panic!("index out of bounds: the length is {len} but the index is {from}");
+        }
+        if to >= len {
+            panic!("index out of bounds: the length is {len} but the index is {to}");
+        }
+
+        if from != to {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(
+                target: "eyeball_im::vector::update",
+                "move_item(from = {from}, to = {to})"
+            );
+
+            let value = self.values.remove(from);
+            self.values.insert(to, value);
+            self.broadcast_diff(VectorDiff::Move { from, to });
+        }
+    }
+
     /// Gets an entry for the given index, through which only the element at
     /// that index alone can be updated or removed.
     ///
@@ -234,6 +793,15 @@ impl<T: Clone + 'static> ObservableVector<T> {
         }
     }
 
+    /// Gets an entry for the first element matching the given predicate,
+    /// through which only that element can be updated or removed.
+    ///
+    /// Returns `None` if no element matches.
+    pub fn entry_by(&mut self, f: impl FnMut(&T) -> bool) -> Option<ObservableVectorEntry<'_, T>> {
+        let index = self.values.iter().position(f)?;
+        Some(ObservableVectorEntry::new(self, index))
+    }
+
     /// Call the given closure for every element in this `ObservableVector`,
     /// with an entry struct that allows updating or removing that element.
     ///
@@ -269,6 +837,29 @@ impl<T: Clone + 'static> ObservableVector<T> {
         ObservableVectorEntries::new(self)
     }
 
+    /// Get an iterator over the entries of the elements in this
+    /// `ObservableVector` that match the given predicate.
+    ///
+    /// Like with [`entries`][Self::entries], the returned type does not
+    /// implement the `Iterator` trait, so you have to call its `.next()` method
+    /// directly. Elements that don't match are skipped, and the yielded entries
+    /// can be used to update or remove the matching elements.
+    ///
+    /// ```rust
+    /// # use eyeball_im::{ObservableVector, ObservableVectorEntry};
+    /// # let mut ob = ObservableVector::<u8>::new();
+    /// let mut entries = ob.entries_filtered(|&value| value > 100);
+    /// while let Some(entry) = entries.next() {
+    ///     ObservableVectorEntry::remove(entry);
+    /// }
+    /// ```
+    pub fn entries_filtered<F>(&mut self, f: F) -> ObservableVectorFilteredEntries<'_, T, F>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        ObservableVectorFilteredEntries::new(self, f)
+    }
+
     /// Start a new transaction to make multiple updates as one unit.
     ///
     /// See [`ObservableVectorTransaction`]s documentation for more details.
@@ -276,10 +867,117 @@ impl<T: Clone + 'static> ObservableVector<T> {
         ObservableVectorTransaction::new(self)
     }
 
+    fn run_insert_hook(&mut self, value: &T) {
+        if let Some(hooks) = &mut self.hooks {
+            hooks.inserted(value);
+        }
+    }
+
+    fn run_remove_hook(&mut self, value: &T) {
+        if let Some(hooks) = &mut self.hooks {
+            hooks.removed(value);
+        }
+    }
+
+    /// Run the remove hook for all values starting at index `start`.
+    fn run_remove_hooks(&mut self, start: usize) {
+        if let Some(hooks) = &mut self.hooks {
+            self.values.iter().skip(start).for_each(|value| hooks.removed(value));
+        }
+    }
+
+    /// Evict the elements beyond the maximum length, if any, from the back or
+    /// the front, returning the diffs for doing so.
+    fn evict(&mut self, from_back: bool) -> Vec<VectorDiff<T>> {
+        let max_len = match self.max_len {
+            Some(max_len) if self.values.len() > max_len => max_len,
+            _ => return Vec::new(),
+        };
+
+        let num_evicted = self.values.len() - max_len;
+        let evicted = if from_back {
+            self.values.split_off(max_len)
+        } else {
+            let rest = self.values.split_off(num_evicted);
+            mem::replace(&mut self.values, rest)
+        };
+        if let Some(hooks) = &mut self.hooks {
+            evicted.iter().for_each(|value| hooks.removed(value));
+        }
+
+        if num_evicted > max_len {
+            vec![VectorDiff::Reset { values: self.values.clone(), reason: ResetReason::Update }]
+        } else {
+            let diff = if from_back { VectorDiff::PopBack } else { VectorDiff::PopFront };
+            vec![diff; num_evicted]
+        }
+    }
+
+    /// Like `broadcast_diff_with`, but also evicts the elements beyond the
+    /// maximum length and broadcasts that along with the diff.
+    ///
+    /// `make_diff` is called with the values before the eviction.
+    fn broadcast_diff_and_evict(
+        &mut self,
+        from_back: bool,
+        make_diff: impl FnOnce(&Vector<T>) -> VectorDiff<T>,
+    ) {
+        if matches!(self.max_len, Some(max_len) if self.values.len() > max_len) {
+            let mut diffs = vec![make_diff(&self.values)];
+            diffs.extend(self.evict(from_back));
+            self.broadcast_diffs(diffs);
+        } else {
+            self.broadcast_diff_with(|| make_diff(&self.values));
+        }
+    }
+
+    /// Broadcast a `VectorDiff::Set` for each of the elements `start..end`, as
+    /// a single update.
+    fn broadcast_sets(&self, start: usize, end: usize) {
+        if self.has_diff_receivers() {
+            let diffs = (start..end)
+                .map(|index| VectorDiff::Set { index, value: self.values[index].clone() })
+                .collect();
+            self.broadcast_diffs(diffs);
+        }
+    }
+
     fn broadcast_diff(&self, diff: VectorDiff<T>) {
-        if self.sender.receiver_count() != 0 {
-            let msg =
-                BroadcastMessage { diffs: OneOrManyDiffs::One(diff), state: self.values.clone() };
+        self.broadcast(OneOrManyDiffs::One(diff));
+    }
+
+    fn broadcast_diffs(&self, mut diffs: Vec<VectorDiff<T>>) {
+        let diffs = if diffs.len() == 1 {
+            OneOrManyDiffs::One(diffs.pop().unwrap())
+        } else {
+            OneOrManyDiffs::Many(diffs)
+        };
+        self.broadcast(diffs);
+    }
+
+    /// Like `broadcast_diff`, but only creates the diff if there are
+    /// subscribers, to avoid cloning values that nobody receives.
+    fn broadcast_diff_with(&self, make_diff: impl FnOnce() -> VectorDiff<T>) {
+        if self.has_diff_receivers() {
+            self.broadcast(OneOrManyDiffs::One(make_diff()));
+        } else {
+            self.broadcast_len();
+        }
+    }
+
+    fn broadcast(&self, diffs: OneOrManyDiffs<T>) {
+        self.broadcast_len();
+        if self.has_diff_receivers() {
+            let msg = BroadcastMessage::new(diffs, self.values.clone());
+            #[cfg(feature = "update-seq")]
+            if let Some(history) = &self.history {
+                history.lock().unwrap().push(msg.clone());
+            }
+            if self.num_range_senders.load(AtomicOrdering::Relaxed) != 0 {
+                let mut range_senders = self.range_senders.lock().unwrap();
+                range_senders.broadcast(&msg, &self.values);
+                self.num_range_senders.store(range_senders.len(), AtomicOrdering::Relaxed);
+            }
             let _num_receivers = self.sender.send(msg).unwrap_or(0);
             #[cfg(feature = "tracing")]
             tracing::debug!(
@@ -290,6 +988,38 @@ impl<T: Clone + 'static> ObservableVector<T> {
     }
 }
 
+impl<T> ObservableVector<T> {
+    /// Whether there is anything that diffs need to be created for: either
+    /// subscribers or the history.
+    fn has_diff_receivers(&self) -> bool {
+        #[cfg(feature = "update-seq")]
+        if self.history.is_some() {
+            return true;
+        }
+        self.sender.receiver_count() != 0
+            || (self.num_range_senders.load(AtomicOrdering::Relaxed) != 0
+                && self.range_senders.lock().unwrap().has_receivers())
+    }
+
+    /// Notify length subscribers if the length changed.
+    fn broadcast_len(&self) {
+        if self.len_sender.receiver_count() != 0 {
+            let len = self.values.len();
+            if self.sent_len.swap(len, AtomicOrdering::Relaxed) != len {
+                let _ = self.len_sender.send(len);
+            }
+        }
+    }
+}
+
+impl<T> Drop for ObservableVector<T> {
+    fn drop(&mut self) {
+        if let Some(hooks) = &mut self.hooks {
+            self.values.iter().for_each(|value| hooks.removed(value));
+        }
+    }
+}
+
 impl<T: Clone + 'static> Default for ObservableVector<T> {
     fn default() -> Self {
         Self::new()
@@ -323,10 +1053,70 @@ impl<T: Clone + 'static> From<Vector<T>> for ObservableVector<T> {
     }
 }
 
+/// A message sent to subscribers.
+///
+/// The broadcast channel clones messages for every receiver, so the contents
+/// are behind an `Arc`. The last receiver to read a message (which is the only
+/// one in the common case of a single subscriber) takes ownership of the
+/// contents rather than cloning them.
+struct BroadcastMessage<T>(Arc<BroadcastMessageInner<T>>);
+
 #[derive(Clone)]
-struct BroadcastMessage<T> {
+struct BroadcastMessageInner<T> {
     diffs: OneOrManyDiffs<T>,
     state: Vector<T>,
+    #[cfg(feature = "update-seq")]
+    seq: UpdateSeq,
+}
+
+impl<T: Clone> BroadcastMessage<T> {
+    fn new(diffs: OneOrManyDiffs<T>, state: Vector<T>) -> Self {
+        Self(Arc::new(BroadcastMessageInner {
+            diffs,
+            state,
+            #[cfg(feature = "update-seq")]
+            seq: UpdateSeq::next(),
+        }))
+    }
+
+    #[cfg(feature = "update-seq")]
+    fn seq(&self) -> UpdateSeq {
+        self.0.seq
+    }
+
+    fn diffs(&self) -> &OneOrManyDiffs<T> {
+        &self.0.diffs
+    }
+
+    /// Create a message for the same update with other diffs and state.
+    fn with_diffs(&self, diffs: OneOrManyDiffs<T>, state: Vector<T>) -> Self {
+        Self(Arc::new(BroadcastMessageInner {
+            diffs,
+            state,
+            #[cfg(feature = "update-seq")]
+            seq: self.0.seq,
+        }))
+    }
+
+    fn into_diffs(self) -> OneOrManyDiffs<T> {
+        match Arc::try_unwrap(self.0) {
+            Ok(inner) => inner.diffs,
+            Err(inner) => inner.diffs.clone(),
+        }
+    }
+
+    fn into_state(self) -> Vector<T> {
+        match Arc::try_unwrap(self.0) {
+            Ok(inner) => inner.state,
+            Err(inner) => inner.state.clone(),
+        }
+    }
+}
+
+impl<T> Clone for BroadcastMessage<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
 }
 
 #[derive(Clone)]
@@ -336,6 +1126,14 @@ enum OneOrManyDiffs<T> {
 }
 
 impl<T> OneOrManyDiffs<T> {
+    /// The diffs, without allocating for a single one.
+    fn as_slice(&self) -> &[VectorDiff<T>] {
+        match self {
+            OneOrManyDiffs::One(diff) => slice::from_ref(diff),
+            OneOrManyDiffs::Many(diffs) => diffs,
+        }
+    }
+
     fn into_vec(self) -> Vec<VectorDiff<T>> {
         match self {
             OneOrManyDiffs::One(diff) => vec![diff],
@@ -395,11 +1193,31 @@ pub enum VectorDiff<T> {
         /// The number of elements that remain.
         length: usize,
     },
-    /// The subscriber lagged too far behind, and the next update that should
-    /// have been received has already been discarded from the internal buffer.
+    /// An element was moved to a different position.
+    ///
+    /// This is equivalent to a [`Remove`][Self::Remove] at `from` followed by
+    /// an [`Insert`][Self::Insert] of the same element at `to`, but preserves
+    /// the identity of the element.
+    Move {
+        /// The index the element had before it was moved.
+        from: usize,
+        /// The index the element has after it was moved.
+        ///
+        /// The elements between `from` and `to` were shifted by one position
+        /// towards `from`.
+        to: usize,
+    },
+    /// All elements were replaced at once.
+    ///
+    /// This is sent when the subscriber lagged too far behind and the next
+    /// update that should have been received has already been discarded from
+    /// the internal buffer, but also for regular updates that change most of
+    /// the elements. `reason` tells these cases apart.
     Reset {
         /// The full list of elements.
         values: Vector<T>,
+        /// Why all elements were replaced at once.
+        reason: ResetReason,
     },
 }
 
@@ -418,10 +1236,93 @@ impl<T: Clone> VectorDiff<T> {
             VectorDiff::Set { index, value } => VectorDiff::Set { index, value: f(value) },
             VectorDiff::Remove { index } => VectorDiff::Remove { index },
             VectorDiff::Truncate { length } => VectorDiff::Truncate { length },
-            VectorDiff::Reset { values } => VectorDiff::Reset { values: vector_map(values, f) },
+            VectorDiff::Move { from, to } => VectorDiff::Move { from, to },
+            VectorDiff::Reset { values, reason } => {
+                VectorDiff::Reset { values: vector_map(values, f), reason }
+            }
+        }
+    }
+
+    /// Transform `VectorDiff<T>` into `VectorDiff<U>` by applying the given
+    /// function to any contained items, along with their position.
+    ///
+    /// The position passed to `f` is the one the item has in the vector
+    /// *after* this diff was applied:
+    ///
+    /// - [`Insert`][Self::Insert] and [`Set`][Self::Set]: `Index(index)`
+    /// - [`PushFront`][Self::PushFront]: `Index(0)`
+    /// - [`PushBack`][Self::PushBack]: `FromBack(0)`, since the length of the
+    ///   vector is not part of the diff
+    /// - [`Append`][Self::Append]: `FromBack(n - 1 - i)` for the `i`-th of the
+    ///   `n` appended items, for the same reason
+    /// - [`Reset`][Self::Reset]: `Index(i)` for the `i`-th item
+    ///
+    /// Use [`ElementPosition::resolve`] to turn it into an index if the length
+    /// of the vector is known.
+    pub fn map_with_index<U: Clone>(
+        self,
+        mut f: impl FnMut(ElementPosition, T) -> U,
+    ) -> VectorDiff<U> {
+        use ElementPosition::{FromBack, Index};
+
+        match self {
+            VectorDiff::Append { values } => {
+                let len = values.len();
+                let values =
+                    values.into_iter().enumerate().map(|(i, v)| f(FromBack(len - 1 - i), v));
+                VectorDiff::Append { values: values.collect() }
+            }
+            VectorDiff::Clear => VectorDiff::Clear,
+            VectorDiff::PushFront { value } => VectorDiff::PushFront { value: f(Index(0), value) },
+            VectorDiff::PushBack { value } => VectorDiff::PushBack { value: f(FromBack(0), value) },
+            VectorDiff::PopFront => VectorDiff::PopFront,
+            VectorDiff::PopBack => VectorDiff::PopBack,
+            VectorDiff::Insert { index, value } => {
+                VectorDiff::Insert { index, value: f(Index(index), value) }
+            }
+            VectorDiff::Set { index, value } => {
+                VectorDiff::Set { index, value: f(Index(index), value) }
+            }
+            VectorDiff::Remove { index } => VectorDiff::Remove { index },
+            VectorDiff::Truncate { length } => VectorDiff::Truncate { length },
+            VectorDiff::Move { from, to } => VectorDiff::Move { from, to },
+            VectorDiff::Reset { values, reason } => {
+                let values = values.into_iter().enumerate().map(|(i, v)| f(Index(i), v));
+                VectorDiff::Reset { values: values.collect(), reason }
+            }
         }
     }
 
+    /// Transform `VectorDiff<T>` into `VectorDiff<U>` by applying the given
+    /// fallible function to any contained items.
+    ///
+    /// Returns the first error returned by `f`, if any. For diffs with
+    /// multiple items, `f` is not called again after it returned an error.
+    pub fn try_map<U: Clone, E>(
+        self,
+        mut f: impl FnMut(T) -> Result<U, E>,
+    ) -> Result<VectorDiff<U>, E> {
+        Ok(match self {
+            VectorDiff::Append { values } => {
+                VectorDiff::Append { values: values.into_iter().map(f).collect::<Result<_, _>>()? }
+            }
+            VectorDiff::Clear => VectorDiff::Clear,
+            VectorDiff::PushFront { value } => VectorDiff::PushFront { value: f(value)? },
+            VectorDiff::PushBack { value } => VectorDiff::PushBack { value: f(value)? },
+            VectorDiff::PopFront => VectorDiff::PopFront,
+            VectorDiff::PopBack => VectorDiff::PopBack,
+            VectorDiff::Insert { index, value } => VectorDiff::Insert { index, value: f(value)? },
+            VectorDiff::Set { index, value } => VectorDiff::Set { index, value: f(value)? },
+            VectorDiff::Remove { index } => VectorDiff::Remove { index },
+            VectorDiff::Truncate { length } => VectorDiff::Truncate { length },
+            VectorDiff::Move { from, to } => VectorDiff::Move { from, to },
+            VectorDiff::Reset { values, reason } => VectorDiff::Reset {
+                values: values.into_iter().map(f).collect::<Result<_, _>>()?,
+                reason,
+            },
+        })
+    }
+
     /// Applies this [`VectorDiff`] to a vector.
     ///
     /// This is useful to keep two vectors in sync, with potentially one
@@ -462,13 +1363,58 @@ impl<T: Clone> VectorDiff<T> {
             VectorDiff::Truncate { length } => {
                 vec.truncate(length);
             }
-            VectorDiff::Reset { values } => {
+            VectorDiff::Move { from, to } => {
+                let value = vec.remove(from);
+                vec.insert(to, value);
+            }
+            VectorDiff::Reset { values, .. } => {
                 *vec = values;
             }
         }
     }
 }
 
+/// The position of an item in a vector, as passed to
+/// [`VectorDiff::map_with_index`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ElementPosition {
+    /// The index of the item, counted from the front.
+    Index(usize),
+    /// The index of the item, counted from the back.
+    ///
+    /// `FromBack(0)` is the last item.
+    FromBack(usize),
+}
+
+impl ElementPosition {
+    /// Get the index of the item, counted from the front, in a vector of the
+    /// given length.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the position is `FromBack(n)` with `n >= len`.
+    pub fn resolve(self, len: usize) -> usize {
+        match self {
+            Self::Index(index) => index,
+            Self::FromBack(n) => len - 1 - n,
+        }
+    }
+}
+
+/// The reason for a [`VectorDiff::Reset`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ResetReason {
+    /// The subscriber lagged too far behind and missed updates, so it is
+    /// resynchronized with the full list of elements.
+    ///
+    /// Consumers might want to indicate that they are catching up.
+    Lagged,
+    /// The elements were replaced as part of a regular update, e.g. because
+    /// most of them changed position.
+    Update,
+}
+
 #[cfg(feature = "serde")]
 impl<T> serde::Serialize for VectorDiff<T>
 where
@@ -530,15 +1476,292 @@ where
                 state.serialize_field("length", length)?;
                 state.end()
             }
-            VectorDiff::Reset { values } => {
-                let mut state = serializer.serialize_struct_variant(SELF_NAME, 10, "Reset", 1)?;
+            VectorDiff::Reset { values, reason } => {
+                let mut state = serializer.serialize_struct_variant(SELF_NAME, 10, "Reset", 2)?;
                 state.serialize_field("values", values)?;
+                state.serialize_field("reason", reason)?;
+                state.end()
+            }
+            VectorDiff::Move { from, to } => {
+                let mut state = serializer.serialize_struct_variant(SELF_NAME, 11, "Move", 2)?;
+                state.serialize_field("from", from)?;
+                state.serialize_field("to", to)?;
                 state.end()
             }
         }
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for ResetReason {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        const SELF_NAME: &str = "ResetReason";
+
+        match self {
+            Self::Lagged => serializer.serialize_unit_variant(SELF_NAME, 0, "Lagged"),
+            Self::Update => serializer.serialize_unit_variant(SELF_NAME, 1, "Update"),
+        }
+    }
+}
+
+/// Turn `range` into a `start..end` pair of indices into a vector of length
+/// `len`, panicking if it is out of bounds.
+#[track_caller]
+fn resolve_range(range: impl RangeBounds<usize>, len: usize) -> (usize, usize) {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start.checked_add(1).expect("range start overflowed"),
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end.checked_add(1).expect("range end overflowed"),
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+
+    if start > end {
+        panic!("range start index {start} is greater than the end index {end}");
+    }
+    if end > len {
+        panic!("range end index {end} out of range for vector of length {len}");
+    }
+
+    (start, end)
+}
+
+/// Replace the elements `start..end` of `values` by `replacement` and return
+/// the replaced elements.
+fn splice_values<T: Clone>(
+    values: &mut Vector<T>,
+    start: usize,
+    end: usize,
+    replacement: Vector<T>,
+) -> Vector<T> {
+    let mut removed = values.split_off(start);
+    let rest = removed.split_off(end - start);
+    values.append(replacement);
+    values.append(rest);
+    removed
+}
+
+/// The diffs describing the replacement of `start..end` in a vector of length
+/// `prev_len` by `replacement`, given the resulting `values`.
+fn splice_diffs<T: Clone>(
+    start: usize,
+    end: usize,
+    prev_len: usize,
+    replacement: &Vector<T>,
+    values: &Vector<T>,
+) -> Vec<VectorDiff<T>> {
+    if start == 0 && end == prev_len && !replacement.is_empty() {
+        return vec![VectorDiff::Reset { values: values.clone(), reason: ResetReason::Update }];
+    }
+
+    // Elements that are replaced one-to-one become `Set`s, the remaining ones
+    // are removed or inserted after them.
+    let common = min(end - start, replacement.len());
+    let mut diffs: Vec<_> = (start..)
+        .zip(replacement.iter().take(common))
+        .map(|(index, value)| VectorDiff::Set { index, value: value.clone() })
+        .collect();
+    let start = start + common;
+
+    if start < end {
+        if end == prev_len {
+            if start == 0 {
+                diffs.push(VectorDiff::Clear);
+            } else {
+                diffs.push(VectorDiff::Truncate { length: start });
+            }
+        } else if end - start > values.len() {
+            return vec![VectorDiff::Reset { values: values.clone(), reason: ResetReason::Update }];
+        } else {
+            diffs.extend(iter::repeat(VectorDiff::Remove { index: start }).take(end - start));
+        }
+    } else if common < replacement.len() {
+        let inserted = replacement.skip(common);
+        if end == prev_len {
+            diffs.push(VectorDiff::Append { values: inserted });
+        } else {
+            diffs.extend(
+                (start..).zip(inserted).map(|(index, value)| VectorDiff::Insert { index, value }),
+            );
+        }
+    }
+
+    diffs
+}
+
+/// Stably sort `values`, returning the sorted values and the new index of
+/// every element.
+fn sort_values<T: Clone>(
+    values: &Vector<T>,
+    mut compare: impl FnMut(&T, &T) -> Ordering,
+) -> (Vector<T>, Vec<usize>) {
+    let mut order: Vec<_> = values.iter().enumerate().collect();
+    order.sort_by(|(_, a), (_, b)| compare(a, b));
+
+    let mut new_indices = vec![0; order.len()];
+    for (new_index, (old_index, _)) in order.iter().enumerate() {
+        new_indices[*old_index] = new_index;
+    }
+
+    (order.into_iter().map(|(_, value)| value.clone()).collect(), new_indices)
+}
+
+/// The diffs describing a reordering of a vector where the element at index
+/// `i` ends up at index `new_indices[i]`, given the reordered `values`.
+fn reorder_diffs<T: Clone>(new_indices: &[usize], values: &Vector<T>) -> Vec<VectorDiff<T>> {
+    // The elements of a longest increasing subsequence of new indices are
+    // already in the right order relative to each other, only the others
+    // have to be moved.
+    let settled = longest_increasing_subsequence(new_indices);
+    let num_moves = settled.iter().filter(|&&settled| !settled).count();
+    if num_moves == 0 {
+        return Vec::new();
+    }
+    if num_moves * 2 > new_indices.len() {
+        return vec![VectorDiff::Reset { values: values.clone(), reason: ResetReason::Update }];
+    }
+
+    // Move the elements in the order of their new indices, each in front of
+    // the first settled element that comes after it.
+    move_positions(new_indices, &settled)
+        .into_iter()
+        .filter(|(from, to)| from != to)
+        .map(|(from, to)| VectorDiff::Move { from, to })
+        .collect()
+}
+
+/// The `(from, to)` positions of the elements that aren't `settled`, when
+/// moving them in the order of their new indices, each in front of the first
+/// settled element with a greater new index.
+///
+/// Every element has a fixed slot for where it is before being moved and one
+/// for where it ends up, so the positions are the number of occupied slots
+/// before these, which a Fenwick tree over the slots counts in `O(log n)`.
+fn move_positions(new_indices: &[usize], settled: &[bool]) -> Vec<(usize, usize)> {
+    let len = new_indices.len();
+    let mut old_indices = vec![0; len];
+    for (old_index, &new_index) in new_indices.iter().enumerate() {
+        old_indices[new_index] = old_index;
+    }
+
+    // The old index of each element to move, and that of the settled element
+    // it is moved in front of (`len` for the end), by new index. The latter
+    // never decreases, since settled elements are in order already.
+    let mut moves = Vec::new();
+    let mut before = len;
+    for &old_index in old_indices.iter().rev() {
+        if settled[old_index] {
+            before = old_index;
+        } else {
+            moves.push((old_index, before));
+        }
+    }
+    moves.reverse();
+
+    // Element `i` is moved to slot `before + i`, after the ones moved in front
+    // of the same element before it. The slot of an element that isn't moved
+    // yet is after all the ones moved in front of it or an earlier element.
+    let mut moved_before = vec![0; len + 1];
+    for &(_, before) in &moves {
+        moved_before[before] += 1;
+    }
+    let mut slots = Vec::with_capacity(len);
+    let mut num_moved_before = 0;
+    for (old_index, num_moved) in moved_before.into_iter().take(len).enumerate() {
+        num_moved_before += num_moved;
+        slots.push(old_index + num_moved_before);
+    }
+
+    let mut occupied = SlotCounter::new(len + moves.len());
+    for &slot in &slots {
+        occupied.insert(slot);
+    }
+
+    moves
+        .into_iter()
+        .enumerate()
+        .map(|(i, (old_index, before))| {
+            let from = occupied.count_before(slots[old_index]);
+            occupied.remove(slots[old_index]);
+            let to = occupied.count_before(before + i);
+            occupied.insert(before + i);
+            (from, to)
+        })
+        .collect()
+}
+
+/// A Fenwick tree counting the occupied slots before a given one.
+struct SlotCounter {
+    tree: Vec<usize>,
+}
+
+impl SlotCounter {
+    fn new(num_slots: usize) -> Self {
+        Self { tree: vec![0; num_slots + 1] }
+    }
+
+    fn insert(&mut self, slot: usize) {
+        let mut i = slot + 1;
+        while i < self.tree.len() {
+            self.tree[i] += 1;
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    fn remove(&mut self, slot: usize) {
+        let mut i = slot + 1;
+        while i < self.tree.len() {
+            self.tree[i] -= 1;
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    fn count_before(&self, slot: usize) -> usize {
+        let mut count = 0;
+        let mut i = slot;
+        while i > 0 {
+            count += self.tree[i];
+            i &= i - 1;
+        }
+        count
+    }
+}
+
+/// For every element of `seq`, whether it is part of a longest strictly
+/// increasing subsequence.
+fn longest_increasing_subsequence(seq: &[usize]) -> Vec<bool> {
+    // `tails[k]` is the index of the smallest last element of an increasing
+    // subsequence of length `k + 1`, `prev[i]` the index of the element before
+    // `seq[i]` in the subsequence that ends with it.
+    let mut tails: Vec<usize> = Vec::new();
+    let mut prev = vec![None; seq.len()];
+    for (i, &value) in seq.iter().enumerate() {
+        let k = tails.partition_point(|&j| seq[j] < value);
+        if k > 0 {
+            prev[i] = Some(tails[k - 1]);
+        }
+        if k == tails.len() {
+            tails.push(i);
+        } else {
+            tails[k] = i;
+        }
+    }
+
+    let mut result = vec![false; seq.len()];
+    let mut next = tails.last().copied();
+    while let Some(i) = next {
+        result[i] = true;
+        next = prev[i];
+    }
+    result
+}
+
 fn vector_map<T: Clone, U: Clone>(v: Vector<T>, f: impl FnMut(T) -> U) -> Vector<U> {
     v.into_iter().map(f).collect()
 }
@@ -0,0 +1,290 @@
+use std::{
+    error::Error,
+    fmt::{self, Write as _},
+    str::FromStr,
+};
+
+use imbl::Vector;
+
+use super::{ResetReason, VectorDiff};
+
+impl<T> VectorDiff<T> {
+    /// Render this diff in a compact, human-friendly form such as `ins@3=x`,
+    /// `rm@0` or `reset[a,b]`.
+    ///
+    /// | Diff                         | Compact form  |
+    /// |------------------------------|---------------|
+    /// | `Append { values: [a, b] }`  | `append[a,b]` |
+    /// | `Clear`                      | `clear`       |
+    /// | `PushFront { value: a }`     | `pushf=a`     |
+    /// | `PushBack { value: a }`      | `pushb=a`     |
+    /// | `PopFront`                   | `popf`        |
+    /// | `PopBack`                    | `popb`        |
+    /// | `Insert { index: 3, value }` | `ins@3=a`     |
+    /// | `Set { index: 3, value }`    | `set@3=a`     |
+    /// | `Remove { index: 0 }`        | `rm@0`        |
+    /// | `Truncate { length: 2 }`     | `trunc@2`     |
+    /// | `Move { from: 1, to: 3 }`    | `mv@1>3`      |
+    /// | `Reset { values: [a, b] }`   | `reset[a,b]`  |
+    ///
+    /// A `Reset` with [`ResetReason::Lagged`] is written as `lagreset[a,b]`,
+    /// one with [`ResetReason::Update`] as `reset[a,b]`.
+    ///
+    /// Within values, the characters `\`, `,`, `[`, `]` and `"` are escaped
+    /// with a backslash. Empty values are written as `""`, such that a list of
+    /// one empty value can be told apart from an empty list.
+    ///
+    /// This is meant for logging and golden-file tests. It can be parsed back
+    /// with [`from_compact_string`][Self::from_compact_string].
+    pub fn to_compact_string(&self) -> String
+    where
+        T: fmt::Display,
+    {
+        let mut out = String::new();
+        match self {
+            VectorDiff::Append { values } => {
+                out.push_str("append");
+                write_list(&mut out, values);
+            }
+            VectorDiff::Clear => out.push_str("clear"),
+            VectorDiff::PushFront { value } => {
+                out.push_str("pushf=");
+                write_value(&mut out, value);
+            }
+            VectorDiff::PushBack { value } => {
+                out.push_str("pushb=");
+                write_value(&mut out, value);
+            }
+            VectorDiff::PopFront => out.push_str("popf"),
+            VectorDiff::PopBack => out.push_str("popb"),
+            VectorDiff::Insert { index, value } => {
+                write!(out, "ins@{index}=").unwrap();
+                write_value(&mut out, value);
+            }
+            VectorDiff::Set { index, value } => {
+                write!(out, "set@{index}=").unwrap();
+                write_value(&mut out, value);
+            }
+            VectorDiff::Remove { index } => write!(out, "rm@{index}").unwrap(),
+            VectorDiff::Truncate { length } => write!(out, "trunc@{length}").unwrap(),
+            VectorDiff::Move { from, to } => write!(out, "mv@{from}>{to}").unwrap(),
+            VectorDiff::Reset { values, reason } => {
+                if *reason == ResetReason::Lagged {
+                    out.push_str("lag");
+                }
+                out.push_str("reset");
+                write_list(&mut out, values);
+            }
+        }
+        out
+    }
+
+    /// Parse a diff from the form produced by
+    /// [`to_compact_string`][Self::to_compact_string].
+    pub fn from_compact_string(s: &str) -> Result<Self, ParseCompactDiffError>
+    where
+        T: Clone + FromStr,
+    {
+        let err = || ParseCompactDiffError { input: s.to_owned() };
+
+        if let Some(list) = s.strip_prefix("append") {
+            return Ok(VectorDiff::Append { values: parse_list(list).ok_or_else(err)? });
+        }
+        if let Some(list) = s.strip_prefix("reset") {
+            let values = parse_list(list).ok_or_else(err)?;
+            return Ok(VectorDiff::Reset { values, reason: ResetReason::Update });
+        }
+        if let Some(list) = s.strip_prefix("lagreset") {
+            let values = parse_list(list).ok_or_else(err)?;
+            return Ok(VectorDiff::Reset { values, reason: ResetReason::Lagged });
+        }
+
+        if let Some(indices) = s.strip_prefix("mv@") {
+            let (from, to) = indices.split_once('>').ok_or_else(err)?;
+            let from = from.parse().map_err(|_| err())?;
+            let to = to.parse().map_err(|_| err())?;
+            return Ok(VectorDiff::Move { from, to });
+        }
+
+        let (op, value) = match s.split_once('=') {
+            Some((op, value)) => (op, Some(parse_value(value).ok_or_else(err)?)),
+            None => (s, None),
+        };
+        let (op, number) = match op.split_once('@') {
+            Some((op, number)) => (op, Some(number.parse().map_err(|_| err())?)),
+            None => (op, None),
+        };
+
+        let diff = match (op, number, value) {
+            ("clear", None, None) => VectorDiff::Clear,
+            ("pushf", None, Some(value)) => VectorDiff::PushFront { value },
+            ("pushb", None, Some(value)) => VectorDiff::PushBack { value },
+            ("popf", None, None) => VectorDiff::PopFront,
+            ("popb", None, None) => VectorDiff::PopBack,
+            ("ins", Some(index), Some(value)) => VectorDiff::Insert { index, value },
+            ("set", Some(index), Some(value)) => VectorDiff::Set { index, value },
+            ("rm", Some(index), None) => VectorDiff::Remove { index },
+            ("trunc", Some(length), None) => VectorDiff::Truncate { length },
+            _ => return Err(err()),
+        };
+
+        Ok(diff)
+    }
+}
+
+/// The error returned by [`VectorDiff::from_compact_string`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseCompactDiffError {
+    input: String,
+}
+
+impl fmt::Display for ParseCompactDiffError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid compact vector diff: `{}`", self.input)
+    }
+}
+
+impl Error for ParseCompactDiffError {}
+
+/// Renders a sequence of [`VectorDiff`]s as a table of the vector's state
+/// before and after each of them, using the compact format of
+/// [`VectorDiff::to_compact_string`].
+///
+/// ```rust
+/// use eyeball_im::{DiffTable, VectorDiff};
+/// use imbl::vector;
+///
+/// let diffs = [VectorDiff::PushBack { value: 'b' }, VectorDiff::Insert { index: 0, value: 'a' }];
+/// let table = DiffTable::new(vector![], &diffs).to_string();
+/// assert_eq!(
+///     table,
+///     "\
+/// diff    | before | after
+/// --------+--------+------
+/// pushb=b | []     | [b]
+/// ins@0=a | [b]    | [a,b]
+/// "
+/// );
+/// ```
+#[derive(Debug)]
+pub struct DiffTable<'a, T> {
+    initial: Vector<T>,
+    diffs: &'a [VectorDiff<T>],
+}
+
+impl<'a, T: Clone> DiffTable<'a, T> {
+    /// Create a new `DiffTable` for `diffs` applied one after the other,
+    /// starting with `initial`.
+    pub fn new(initial: Vector<T>, diffs: &'a [VectorDiff<T>]) -> Self {
+        Self { initial, diffs }
+    }
+}
+
+impl<T: Clone + fmt::Display> fmt::Display for DiffTable<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut rows = vec![["diff".to_owned(), "before".to_owned(), "after".to_owned()]];
+        let mut state = self.initial.clone();
+        for diff in self.diffs {
+            let mut before = String::new();
+            write_list(&mut before, &state);
+            diff.clone().apply(&mut state);
+            let mut after = String::new();
+            write_list(&mut after, &state);
+
+            rows.push([diff.to_compact_string(), before, after]);
+        }
+
+        let width = |col: usize| rows.iter().map(|row| row[col].chars().count()).max().unwrap();
+        let (w0, w1, w2) = (width(0), width(1), width(2));
+
+        for (i, [diff, before, after]) in rows.iter().enumerate() {
+            writeln!(f, "{diff:w0$} | {before:w1$} | {after}")?;
+            if i == 0 {
+                writeln!(
+                    f,
+                    "{}+{}+{}",
+                    "-".repeat(w0 + 1),
+                    "-".repeat(w1 + 2),
+                    "-".repeat(w2 + 1)
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The compact form of an empty value. Since `"` is escaped within values, it
+/// can't be confused with a value of two quotes.
+const EMPTY_VALUE: &str = r#""""#;
+
+fn write_value<T: fmt::Display>(out: &mut String, value: &T) {
+    let value = value.to_string();
+    if value.is_empty() {
+        out.push_str(EMPTY_VALUE);
+        return;
+    }
+
+    for c in value.chars() {
+        if matches!(c, '\\' | ',' | '[' | ']' | '"') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+}
+
+fn write_list<T: fmt::Display>(out: &mut String, values: &Vector<T>) {
+    out.push('[');
+    for (i, value) in values.iter().enumerate() {
+        if i != 0 {
+            out.push(',');
+        }
+        write_value(out, value);
+    }
+    out.push(']');
+}
+
+fn unescape(s: &str) -> Option<String> {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => out.push(chars.next()?),
+            ',' | '[' | ']' => return None,
+            c => out.push(c),
+        }
+    }
+    Some(out)
+}
+
+fn parse_value<T: FromStr>(s: &str) -> Option<T> {
+    if s == EMPTY_VALUE {
+        return "".parse().ok();
+    }
+    unescape(s)?.parse().ok()
+}
+
+fn parse_list<T: Clone + FromStr>(s: &str) -> Option<Vector<T>> {
+    let inner = s.strip_prefix('[')?.strip_suffix(']')?;
+    if inner.is_empty() {
+        return Some(Vector::new());
+    }
+
+    let mut values = Vector::new();
+    let mut start = 0;
+    let mut escaped = false;
+    for (i, c) in inner.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            ',' => {
+                values.push_back(parse_value(&inner[start..i])?);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    values.push_back(parse_value(&inner[start..])?);
+
+    Some(values)
+}
@@ -0,0 +1,186 @@
+use imbl::Vector;
+use tokio::sync::broadcast::{error::TryRecvError, Receiver};
+
+use super::{subscriber::handle_lag, BroadcastMessage, VectorDiff};
+
+/// A cursor over the elements of an [`ObservableVector`] that keeps its
+/// logical position while the vector continues to change.
+///
+/// Created with [`cursor`][super::ObservableVector::cursor]. The cursor sits
+/// in between two elements, initially before the first one. [`next`] and
+/// [`prev`] move it over the following / preceding element and return that
+/// element, [`current`] returns the element it last moved over.
+///
+/// The cursor doesn't borrow the vector, so it can be moved to a background
+/// task that processes the vector incrementally. Whenever one of its methods
+/// is called, it first catches up with the updates made to the vector since
+/// then, adjusting its position for elements inserted or removed before it.
+/// Elements inserted right at the position of the cursor come after it, so
+/// they are returned by the next call to `next`. If the element last moved
+/// over is removed, `current` returns `None` until the cursor is moved again.
+///
+/// If the cursor falls behind by more updates than the vector's buffer
+/// capacity, it can't know how the elements moved and goes back to the start,
+/// like for a [`VectorDiff::Reset`].
+///
+/// Running out of elements doesn't end the cursor: calling `next` again after
+/// elements were appended returns them.
+///
+/// ```
+/// use eyeball_im::ObservableVector;
+/// use imbl::vector;
+///
+/// let mut ob = ObservableVector::from(vector![1, 2, 3]);
+/// let mut cursor = ob.cursor();
+///
+/// assert_eq!(cursor.next(), Some(1));
+/// assert_eq!(cursor.next(), Some(2));
+///
+/// ob.push_front(0);
+/// ob.remove(3);
+/// assert_eq!(cursor.current(), Some(2));
+/// assert_eq!(cursor.next(), None);
+///
+/// ob.push_back(4);
+/// assert_eq!(cursor.next(), Some(4));
+/// assert_eq!(cursor.prev(), Some(4));
+/// assert_eq!(cursor.prev(), Some(2));
+/// ```
+///
+/// [`ObservableVector`]: super::ObservableVector
+/// [`next`]: Self::next
+/// [`prev`]: Self::prev
+/// [`current`]: Self::current
+#[derive(Debug)]
+pub struct VectorCursor<T> {
+    values: Vector<T>,
+    rx: Receiver<BroadcastMessage<T>>,
+    /// The number of elements before the cursor.
+    position: usize,
+    /// The index of the element the cursor last moved over, if it's still
+    /// part of the vector.
+    current: Option<usize>,
+}
+
+impl<T: Clone + 'static> VectorCursor<T> {
+    pub(super) fn new(values: Vector<T>, rx: Receiver<BroadcastMessage<T>>) -> Self {
+        Self { values, rx, position: 0, current: None }
+    }
+
+    /// Get the number of elements before the cursor.
+    pub fn position(&mut self) -> usize {
+        self.catch_up();
+        self.position
+    }
+
+    /// Get the element the cursor last moved over.
+    ///
+    /// Returns `None` if the cursor wasn't moved yet, or if that element was
+    /// removed from the vector since.
+    pub fn current(&mut self) -> Option<T> {
+        self.catch_up();
+        self.current.map(|index| self.values[index].clone())
+    }
+
+    /// Move the cursor back over the preceding element and return it.
+    ///
+    /// Returns `None` and leaves the cursor where it is if it's at the start of
+    /// the vector.
+    pub fn prev(&mut self) -> Option<T> {
+        self.catch_up();
+        let index = self.position.checked_sub(1)?;
+        self.position = index;
+        self.current = Some(index);
+        Some(self.values[index].clone())
+    }
+
+    /// Apply the updates that were made to the vector since the last call.
+    fn catch_up(&mut self) {
+        loop {
+            match self.rx.try_recv() {
+                Ok(msg) => {
+                    for diff in msg.into_diffs().into_vec() {
+                        self.apply(diff);
+                    }
+                }
+                Err(TryRecvError::Empty | TryRecvError::Closed) => break,
+                Err(TryRecvError::Lagged(_)) => {
+                    if let Some(msg) = handle_lag(&mut self.rx) {
+                        self.values = msg.into_state();
+                        self.position = 0;
+                        self.current = None;
+                    }
+                }
+            }
+        }
+    }
+
+    fn apply(&mut self, diff: VectorDiff<T>) {
+        match &diff {
+            VectorDiff::Append { .. } | VectorDiff::Set { .. } => {}
+            VectorDiff::Clear | VectorDiff::Reset { .. } => {
+                self.position = 0;
+                self.current = None;
+            }
+            VectorDiff::PushFront { .. } => self.inserted(0),
+            VectorDiff::PushBack { .. } => self.inserted(self.values.len()),
+            VectorDiff::PopFront => self.removed(0),
+            VectorDiff::PopBack => self.removed(self.values.len() - 1),
+            VectorDiff::Insert { index, .. } => self.inserted(*index),
+            VectorDiff::Remove { index } => self.removed(*index),
+            VectorDiff::Truncate { length } => {
+                self.position = self.position.min(*length);
+                self.current = self.current.filter(|current| current < length);
+            }
+            VectorDiff::Move { from, to } => {
+                let current = self.current;
+                self.removed(*from);
+                self.inserted(*to);
+                // The element the cursor last moved over is still the same.
+                if current == Some(*from) {
+                    self.current = Some(*to);
+                }
+            }
+        }
+
+        diff.apply(&mut self.values);
+    }
+
+    fn inserted(&mut self, index: usize) {
+        if index < self.position {
+            self.position += 1;
+        }
+        if let Some(current) = &mut self.current {
+            if index <= *current {
+                *current += 1;
+            }
+        }
+    }
+
+    fn removed(&mut self, index: usize) {
+        if index < self.position {
+            self.position -= 1;
+        }
+        self.current = match self.current {
+            Some(current) if current == index => None,
+            Some(current) if current > index => Some(current - 1),
+            current => current,
+        };
+    }
+}
+
+/// Moves the cursor forward over the following element and returns it.
+///
+/// Returns `None` and leaves the cursor where it is if it's at the end of the
+/// vector. Since elements can be appended later, this iterator is not fused.
+impl<T: Clone + 'static> Iterator for VectorCursor<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.catch_up();
+        let value = self.values.get(self.position)?.clone();
+        self.current = Some(self.position);
+        self.position += 1;
+        Some(value)
+    }
+}
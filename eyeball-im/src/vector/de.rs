@@ -0,0 +1,391 @@
+//! The `serde::Deserialize` implementation for [`VectorDiff`], the counterpart
+//! to its `serde::Serialize` implementation.
+
+use std::{fmt, marker::PhantomData};
+
+use imbl::Vector;
+use serde::de::{self, Deserialize, Deserializer, EnumAccess, MapAccess, SeqAccess, VariantAccess};
+
+use super::{ResetReason, VectorDiff};
+
+const SELF_NAME: &str = "VectorDiff";
+
+const VARIANTS: &[&str] = &[
+    "Append",
+    "Clear",
+    "PushFront",
+    "PushBack",
+    "PopFront",
+    "PopBack",
+    "Insert",
+    "Set",
+    "Remove",
+    "Truncate",
+    "Reset",
+    "Move",
+];
+
+impl<'de, T> Deserialize<'de> for VectorDiff<T>
+where
+    T: Deserialize<'de> + Clone,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_enum(SELF_NAME, VARIANTS, DiffVisitor(PhantomData))
+    }
+}
+
+struct DiffVisitor<T>(PhantomData<T>);
+
+impl<'de, T> de::Visitor<'de> for DiffVisitor<T>
+where
+    T: Deserialize<'de> + Clone,
+{
+    type Value = VectorDiff<T>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("enum VectorDiff")
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: EnumAccess<'de>,
+    {
+        let (variant, access) = data.variant::<Variant>()?;
+        let mut fields = access.struct_variant(variant.fields(), FieldsVisitor::new(variant))?;
+
+        let diff = match variant {
+            Variant::Append => VectorDiff::Append { values: fields.values()? },
+            Variant::Clear => VectorDiff::Clear,
+            Variant::PushFront => VectorDiff::PushFront { value: fields.value()? },
+            Variant::PushBack => VectorDiff::PushBack { value: fields.value()? },
+            Variant::PopFront => VectorDiff::PopFront,
+            Variant::PopBack => VectorDiff::PopBack,
+            Variant::Insert => {
+                VectorDiff::Insert { index: fields.index("index")?, value: fields.value()? }
+            }
+            Variant::Set => {
+                VectorDiff::Set { index: fields.index("index")?, value: fields.value()? }
+            }
+            Variant::Remove => VectorDiff::Remove { index: fields.index("index")? },
+            Variant::Truncate => VectorDiff::Truncate { length: fields.index("length")? },
+            Variant::Reset => {
+                // Diffs serialized before the reason was introduced don't have
+                // it. Back then, resets were only sent to subscribers that
+                // lagged behind.
+                let reason = fields.reason.unwrap_or(ResetReason::Lagged);
+                VectorDiff::Reset { values: fields.values()?, reason }
+            }
+            Variant::Move => {
+                VectorDiff::Move { from: fields.index("from")?, to: fields.index("to")? }
+            }
+        };
+
+        Ok(diff)
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Variant {
+    Append,
+    Clear,
+    PushFront,
+    PushBack,
+    PopFront,
+    PopBack,
+    Insert,
+    Set,
+    Remove,
+    Truncate,
+    Reset,
+    Move,
+}
+
+impl Variant {
+    fn from_index(index: u64) -> Option<Self> {
+        let variant = match index {
+            0 => Self::Append,
+            1 => Self::Clear,
+            2 => Self::PushFront,
+            3 => Self::PushBack,
+            4 => Self::PopFront,
+            5 => Self::PopBack,
+            6 => Self::Insert,
+            7 => Self::Set,
+            8 => Self::Remove,
+            9 => Self::Truncate,
+            10 => Self::Reset,
+            11 => Self::Move,
+            _ => return None,
+        };
+        Some(variant)
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        VARIANTS.iter().position(|&v| v == name).and_then(|idx| Self::from_index(idx as u64))
+    }
+
+    /// The fields of this variant, in the order they are serialized in.
+    fn fields(self) -> &'static [&'static str] {
+        match self {
+            Self::Append => &["values"],
+            Self::Reset => &["values", "reason"],
+            Self::Clear | Self::PopFront | Self::PopBack => &[],
+            Self::PushFront | Self::PushBack => &["value"],
+            Self::Insert | Self::Set => &["index", "value"],
+            Self::Remove => &["index"],
+            Self::Truncate => &["length"],
+            Self::Move => &["from", "to"],
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Variant {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct VariantVisitor;
+
+        impl<'de> de::Visitor<'de> for VariantVisitor {
+            type Value = Variant;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("variant identifier")
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<Self::Value, E> {
+                Variant::from_index(value).ok_or_else(|| {
+                    E::invalid_value(de::Unexpected::Unsigned(value), &"variant index 0 <= i < 12")
+                })
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                Variant::from_name(value).ok_or_else(|| E::unknown_variant(value, VARIANTS))
+            }
+
+            fn visit_bytes<E: de::Error>(self, value: &[u8]) -> Result<Self::Value, E> {
+                match std::str::from_utf8(value) {
+                    Ok(value) => self.visit_str(value),
+                    Err(_) => Err(E::invalid_value(de::Unexpected::Bytes(value), &self)),
+                }
+            }
+        }
+
+        deserializer.deserialize_identifier(VariantVisitor)
+    }
+}
+
+/// The fields of any variant, each one set if it was present in the input.
+struct Fields<T> {
+    values: Option<Vector<T>>,
+    value: Option<T>,
+    reason: Option<ResetReason>,
+    /// `index`, `length`, `from` and `to`, in the order of
+    /// [`Variant::fields`].
+    indices: [Option<usize>; 2],
+    variant: Variant,
+}
+
+impl<T> Fields<T> {
+    fn values<E: de::Error>(self) -> Result<Vector<T>, E> {
+        self.values.ok_or_else(|| E::missing_field("values"))
+    }
+
+    fn value<E: de::Error>(&mut self) -> Result<T, E> {
+        self.value.take().ok_or_else(|| E::missing_field("value"))
+    }
+
+    fn index<E: de::Error>(&self, name: &'static str) -> Result<usize, E> {
+        let pos = self.variant.fields().iter().position(|&f| f == name).unwrap();
+        self.indices[pos].ok_or_else(|| E::missing_field(name))
+    }
+}
+
+struct FieldsVisitor<T> {
+    variant: Variant,
+    _phantom: PhantomData<T>,
+}
+
+impl<T> FieldsVisitor<T> {
+    fn new(variant: Variant) -> Self {
+        Self { variant, _phantom: PhantomData }
+    }
+
+    fn empty_fields(&self) -> Fields<T> {
+        Fields {
+            values: None,
+            value: None,
+            reason: None,
+            indices: [None; 2],
+            variant: self.variant,
+        }
+    }
+}
+
+impl<'de, T> de::Visitor<'de> for FieldsVisitor<T>
+where
+    T: Deserialize<'de> + Clone,
+{
+    type Value = Fields<T>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("struct variant of VectorDiff")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut fields = self.empty_fields();
+        for (pos, &name) in self.variant.fields().iter().enumerate() {
+            let missing = || de::Error::invalid_length(pos, &self);
+            match name {
+                "values" => fields.values = Some(seq.next_element()?.ok_or_else(missing)?),
+                "value" => fields.value = Some(seq.next_element()?.ok_or_else(missing)?),
+                "reason" => fields.reason = seq.next_element()?,
+                _ => fields.indices[pos] = Some(seq.next_element()?.ok_or_else(missing)?),
+            }
+        }
+
+        Ok(fields)
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut fields = self.empty_fields();
+        while let Some(key) = map.next_key::<FieldName>()? {
+            let pos = self.variant.fields().iter().position(|&f| f == key.0);
+            match (key.0, pos) {
+                ("values", Some(_)) => {
+                    if fields.values.is_some() {
+                        return Err(de::Error::duplicate_field("values"));
+                    }
+                    fields.values = Some(map.next_value()?);
+                }
+                ("value", Some(_)) => {
+                    if fields.value.is_some() {
+                        return Err(de::Error::duplicate_field("value"));
+                    }
+                    fields.value = Some(map.next_value()?);
+                }
+                ("reason", Some(_)) => {
+                    if fields.reason.is_some() {
+                        return Err(de::Error::duplicate_field("reason"));
+                    }
+                    fields.reason = Some(map.next_value()?);
+                }
+                (name, Some(pos)) => {
+                    if fields.indices[pos].is_some() {
+                        return Err(de::Error::duplicate_field(name));
+                    }
+                    fields.indices[pos] = Some(map.next_value()?);
+                }
+                // Ignore unknown fields, like `#[derive(Deserialize)]` does.
+                (_, None) => {
+                    map.next_value::<de::IgnoredAny>()?;
+                }
+            }
+        }
+
+        Ok(fields)
+    }
+}
+
+/// A field name, mapped to one of the known names or the empty string.
+struct FieldName(&'static str);
+
+impl<'de> Deserialize<'de> for FieldName {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct FieldNameVisitor;
+
+        impl<'de> de::Visitor<'de> for FieldNameVisitor {
+            type Value = FieldName;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("field identifier")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                let name = ["values", "value", "reason", "index", "length", "from", "to"]
+                    .into_iter()
+                    .find(|&name| name == value)
+                    .unwrap_or("");
+                Ok(FieldName(name))
+            }
+
+            fn visit_bytes<E: de::Error>(self, value: &[u8]) -> Result<Self::Value, E> {
+                self.visit_str(std::str::from_utf8(value).unwrap_or(""))
+            }
+        }
+
+        deserializer.deserialize_identifier(FieldNameVisitor)
+    }
+}
+
+impl<'de> Deserialize<'de> for ResetReason {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        const VARIANTS: &[&str] = &["Lagged", "Update"];
+
+        struct ReasonVisitor;
+
+        impl<'de> de::Visitor<'de> for ReasonVisitor {
+            type Value = ResetReason;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("enum ResetReason")
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<Self::Value, E> {
+                match value {
+                    0 => Ok(ResetReason::Lagged),
+                    1 => Ok(ResetReason::Update),
+                    _ => Err(E::invalid_value(de::Unexpected::Unsigned(value), &"0 or 1")),
+                }
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                match value {
+                    "Lagged" => Ok(ResetReason::Lagged),
+                    "Update" => Ok(ResetReason::Update),
+                    _ => Err(E::unknown_variant(value, VARIANTS)),
+                }
+            }
+
+            fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+            where
+                A: EnumAccess<'de>,
+            {
+                let (reason, access) = data.variant_seed(ReasonSeed)?;
+                access.unit_variant()?;
+                Ok(reason)
+            }
+        }
+
+        /// Deserializes the variant identifier of a `ResetReason`.
+        struct ReasonSeed;
+
+        impl<'de> de::DeserializeSeed<'de> for ReasonSeed {
+            type Value = ResetReason;
+
+            fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                deserializer.deserialize_identifier(ReasonVisitor)
+            }
+        }
+
+        deserializer.deserialize_enum("ResetReason", VARIANTS, ReasonVisitor)
+    }
+}
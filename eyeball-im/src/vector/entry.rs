@@ -31,6 +31,15 @@ where
         this.inner.set(this.index.value(), value)
     }
 
+    /// Replace the given element, notify subscribers and return the previous
+    /// element, consuming the entry.
+    ///
+    /// This is the same as [`set`][Self::set], but can be used in expressions
+    /// the same way as [`remove`][Self::remove].
+    pub fn replace(mut this: Self, value: T) -> T {
+        Self::set(&mut this, value)
+    }
+
     /// Remove the given element, notify subscribers and return the element.
     pub fn remove(mut this: Self) -> T {
         this.inner.remove(this.index.make_owned())
@@ -102,6 +111,27 @@ impl EntryIndex<'_> {
             EntryIndex::Owned(idx) => *idx,
         }
     }
+
+    /// Increment the index, along with the externally-stored index, if any.
+    pub(super) fn increment(&mut self) {
+        match self {
+            EntryIndex::Borrowed(idx) => **idx += 1,
+            EntryIndex::Owned(idx) => *idx += 1,
+        }
+    }
+
+    /// Set the externally-stored index, if any, to `next` and remove the
+    /// association with it.
+    ///
+    /// Used for modifications after which iteration should resume at a
+    /// position other than the one after the current index.
+    pub(super) fn finish(&mut self, next: usize) {
+        let idx = self.value();
+        if let EntryIndex::Borrowed(ext_idx) = self {
+            **ext_idx = next;
+        }
+        *self = EntryIndex::Owned(idx);
+    }
 }
 
 /// An "iterator"¹ that yields entries into an [`ObservableVector`].
@@ -132,3 +162,51 @@ where
         }
     }
 }
+
+/// An "iterator"¹ that yields entries into an [`ObservableVector`] for the
+/// elements matching a predicate.
+///
+/// Returned by [`ObservableVector::entries_filtered`].
+///
+/// ¹ conceptually, though it does not implement `std::iterator::Iterator`
+pub struct ObservableVectorFilteredEntries<'a, T, F> {
+    inner: &'a mut ObservableVector<T>,
+    filter: F,
+    index: usize,
+}
+
+impl<'a, T, F> ObservableVectorFilteredEntries<'a, T, F>
+where
+    T: Clone + 'static,
+    F: FnMut(&T) -> bool,
+{
+    pub(super) fn new(inner: &'a mut ObservableVector<T>, filter: F) -> Self {
+        Self { inner, filter, index: 0 }
+    }
+
+    /// Advance this iterator, yielding an `ObservableVectorEntry` for the next
+    /// item in the vector that matches the predicate, or `None` if all items
+    /// have been visited.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Option<ObservableVectorEntry<'_, T>> {
+        while self.index < self.inner.len() {
+            if (self.filter)(&self.inner[self.index]) {
+                return Some(ObservableVectorEntry::new_borrowed(self.inner, &mut self.index));
+            }
+            self.index += 1;
+        }
+        None
+    }
+}
+
+impl<T, F> fmt::Debug for ObservableVectorFilteredEntries<'_, T, F>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ObservableVectorFilteredEntries")
+            .field("inner", &self.inner)
+            .field("index", &self.index)
+            .finish_non_exhaustive()
+    }
+}
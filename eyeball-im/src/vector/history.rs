@@ -0,0 +1,44 @@
+use std::collections::VecDeque;
+
+use eyeball::UpdateSeq;
+
+use super::{BroadcastMessage, VectorDiff};
+
+/// The most recent updates of an [`ObservableVector`][super::ObservableVector],
+/// retained for [`diffs_since`][super::ObservableVector::diffs_since].
+pub(super) struct History<T> {
+    capacity: usize,
+    // The sequence number of the state before the oldest retained update.
+    base_seq: UpdateSeq,
+    messages: VecDeque<BroadcastMessage<T>>,
+}
+
+impl<T: Clone> History<T> {
+    pub(super) fn new(capacity: usize) -> Self {
+        Self { capacity, base_seq: UpdateSeq::next(), messages: VecDeque::with_capacity(capacity) }
+    }
+
+    pub(super) fn push(&mut self, msg: BroadcastMessage<T>) {
+        if self.messages.len() == self.capacity {
+            let oldest = self.messages.pop_front().unwrap();
+            self.base_seq = oldest.seq();
+        }
+        self.messages.push_back(msg);
+    }
+
+    pub(super) fn last_seq(&self) -> UpdateSeq {
+        self.messages.back().map_or(self.base_seq, |msg| msg.seq())
+    }
+
+    pub(super) fn diffs_since(&self, seq: u64) -> Option<Vec<VectorDiff<T>>> {
+        let start = if seq == self.base_seq.get() {
+            0
+        } else {
+            self.messages.iter().position(|msg| msg.seq().get() == seq)? + 1
+        };
+
+        let diffs =
+            self.messages.iter().skip(start).flat_map(|msg| msg.diffs().as_slice().iter().cloned());
+        Some(diffs.collect())
+    }
+}
@@ -0,0 +1,36 @@
+/// Callbacks that are run when elements enter or leave an
+/// [`ObservableVector`](super::ObservableVector).
+pub(super) struct Hooks<T> {
+    on_insert: Box<dyn FnMut(&T) + Send + Sync>,
+    on_remove: Box<dyn FnMut(&T) + Send + Sync>,
+}
+
+impl<T> Hooks<T> {
+    pub(super) fn new(
+        on_insert: impl FnMut(&T) + Send + Sync + 'static,
+        on_remove: impl FnMut(&T) + Send + Sync + 'static,
+    ) -> Self {
+        Self { on_insert: Box::new(on_insert), on_remove: Box::new(on_remove) }
+    }
+
+    pub(super) fn inserted(&mut self, value: &T) {
+        (self.on_insert)(value);
+    }
+
+    pub(super) fn removed(&mut self, value: &T) {
+        (self.on_remove)(value);
+    }
+
+    pub(super) fn run(&mut self, event: &HookEvent<T>) {
+        match event {
+            HookEvent::Inserted(value) => self.inserted(value),
+            HookEvent::Removed(value) => self.removed(value),
+        }
+    }
+}
+
+/// A hook invocation that is deferred until a transaction is committed.
+pub(super) enum HookEvent<T> {
+    Inserted(T),
+    Removed(T),
+}
@@ -0,0 +1,63 @@
+use std::{
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+
+use futures_core::Stream;
+use tokio::sync::broadcast::{error::RecvError, Receiver};
+
+use crate::recv_future::ReusableBoxRecvFuture;
+
+/// A subscriber for the length of an
+/// [`ObservableVector`][super::ObservableVector].
+///
+/// Created with [`subscribe_len`][super::ObservableVector::subscribe_len].
+/// Yields the new length whenever it changes. If the length changed multiple
+/// times since the subscriber was last polled, only the latest length is
+/// yielded.
+///
+/// Use its [`Stream`] implementation to interact with it (futures-util and
+/// other futures-related crates have extension traits with convenience
+/// methods).
+#[derive(Debug)]
+pub struct VectorLenSubscriber {
+    len: usize,
+    inner: ReusableBoxRecvFuture<usize>,
+}
+
+impl VectorLenSubscriber {
+    pub(super) fn new(len: usize, rx: Receiver<usize>) -> Self {
+        Self { len, inner: ReusableBoxRecvFuture::new(rx) }
+    }
+
+    /// Get the latest length this subscriber has seen.
+    ///
+    /// Initially, this is the length of the vector at the time of subscribing.
+    pub fn get(&self) -> usize {
+        self.len
+    }
+}
+
+impl Stream for VectorLenSubscriber {
+    type Item = usize;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            let (result, rx) = ready!(self.inner.poll(cx));
+            self.inner.set(rx);
+
+            match result {
+                Ok(len) if len != self.len => {
+                    self.len = len;
+                    return Poll::Ready(Some(len));
+                }
+                // The length changed back and forth while lagging.
+                Ok(_) => {}
+                // The channel only has room for a single message, so after
+                // lagging, the next message contains the latest length.
+                Err(RecvError::Lagged(_)) => {}
+                Err(RecvError::Closed) => return Poll::Ready(None),
+            }
+        }
+    }
+}
@@ -0,0 +1,219 @@
+use std::{cmp::min, ops::Range};
+
+use imbl::Vector;
+use tokio::sync::broadcast::{self, Sender};
+
+use super::{BroadcastMessage, OneOrManyDiffs, VectorDiff, VectorSubscriber};
+
+/// The senders of the subscribers created by
+/// [`ObservableVector::subscribe_range`][super::ObservableVector::subscribe_range].
+///
+/// Every range subscriber has a channel of its own, so it is only woken up
+/// by updates that change the elements in its range.
+pub(super) struct RangeSenders<T> {
+    capacity: usize,
+    /// The values of the vector as of the last update, to translate the diffs
+    /// of the next one.
+    values: Vector<T>,
+    senders: Vec<RangeSender<T>>,
+}
+
+struct RangeSender<T> {
+    range: Range<usize>,
+    sender: Sender<BroadcastMessage<T>>,
+}
+
+impl<T> RangeSenders<T> {
+    /// The number of range subscribers, including ones that were dropped
+    /// since the last update.
+    pub(super) fn len(&self) -> usize {
+        self.senders.len()
+    }
+
+    /// Whether any range subscriber is still alive.
+    pub(super) fn has_receivers(&self) -> bool {
+        self.senders.iter().any(|s| s.sender.receiver_count() != 0)
+    }
+}
+
+impl<T: Clone + 'static> RangeSenders<T> {
+    pub(super) fn new(capacity: usize) -> Self {
+        Self { capacity, values: Vector::new(), senders: Vec::new() }
+    }
+
+    pub(super) fn subscribe(
+        &mut self,
+        range: Range<usize>,
+        values: &Vector<T>,
+    ) -> VectorSubscriber<T> {
+        let range = range.start..range.end.max(range.start);
+        let (sender, rx) = broadcast::channel(self.capacity);
+        let window = window(values, &range);
+
+        self.values = values.clone();
+        self.senders.push(RangeSender { range, sender });
+        VectorSubscriber::new(window, rx)
+    }
+
+    /// Send the diffs of `msg` that affect their range to the range
+    /// subscribers, rebased to the start of the range.
+    pub(super) fn broadcast(&mut self, msg: &BroadcastMessage<T>, values: &Vector<T>) {
+        self.senders.retain(|s| s.sender.receiver_count() != 0);
+        if self.senders.is_empty() {
+            self.values = Vector::new();
+            return;
+        }
+
+        let mut range_diffs = vec![Vec::new(); self.senders.len()];
+        for diff in msg.diffs().as_slice() {
+            let mut after = self.values.clone();
+            diff.clone().apply(&mut after);
+
+            for (s, diffs) in self.senders.iter().zip(&mut range_diffs) {
+                diffs.extend(rebase(diff, &self.values, &after, &s.range));
+            }
+            self.values = after;
+        }
+
+        for (s, mut diffs) in self.senders.iter().zip(range_diffs) {
+            if diffs.is_empty() {
+                continue;
+            }
+
+            let diffs = if diffs.len() == 1 {
+                OneOrManyDiffs::One(diffs.pop().unwrap())
+            } else {
+                OneOrManyDiffs::Many(diffs)
+            };
+            let _ = s.sender.send(msg.with_diffs(diffs, window(values, &s.range)));
+        }
+    }
+}
+
+/// Translate `diff`, which turned `before` into `after`, into the diffs of
+/// the elements in `range`, with indices relative to `range.start`.
+fn rebase<T: Clone>(
+    diff: &VectorDiff<T>,
+    before: &Vector<T>,
+    after: &Vector<T>,
+    range: &Range<usize>,
+) -> Vec<VectorDiff<T>> {
+    let Range { start, end } = *range;
+    let window_len = |len| min(len, end).saturating_sub(start);
+
+    match diff {
+        VectorDiff::Append { .. } => {
+            let first_new = before.len().max(start);
+            let values: Vector<_> = after.iter().take(end).skip(first_new).cloned().collect();
+            if values.is_empty() {
+                Vec::new()
+            } else {
+                vec![VectorDiff::Append { values }]
+            }
+        }
+        VectorDiff::Clear | VectorDiff::Truncate { .. } => {
+            match (window_len(before.len()), window_len(after.len())) {
+                (old, new) if new >= old => Vec::new(),
+                (_, 0) => vec![VectorDiff::Clear],
+                (_, length) => vec![VectorDiff::Truncate { length }],
+            }
+        }
+        VectorDiff::PushFront { .. } => inserted(0, before, after, range),
+        VectorDiff::PushBack { .. } => inserted(before.len(), before, after, range),
+        VectorDiff::Insert { index, .. } => inserted(*index, before, after, range),
+        VectorDiff::PopFront => removed(0, before, after, range),
+        VectorDiff::PopBack => removed(before.len() - 1, before, after, range),
+        VectorDiff::Remove { index } => removed(*index, before, after, range),
+        VectorDiff::Set { index, value } => {
+            if range.contains(index) {
+                vec![VectorDiff::Set { index: index - start, value: value.clone() }]
+            } else {
+                Vec::new()
+            }
+        }
+        VectorDiff::Move { from, to } => {
+            if from == to {
+                Vec::new()
+            } else if range.contains(from) && range.contains(to) {
+                vec![VectorDiff::Move { from: from - start, to: to - start }]
+            } else {
+                let mut removed_from = before.clone();
+                removed_from.remove(*from);
+
+                let mut diffs = removed(*from, before, &removed_from, range);
+                diffs.extend(inserted(*to, &removed_from, after, range));
+                diffs
+            }
+        }
+        VectorDiff::Reset { reason, .. } => {
+            vec![VectorDiff::Reset { values: window(after, range), reason: *reason }]
+        }
+    }
+}
+
+/// The diffs of the elements in `range` for an element being inserted at
+/// `index`.
+fn inserted<T: Clone>(
+    index: usize,
+    before: &Vector<T>,
+    after: &Vector<T>,
+    range: &Range<usize>,
+) -> Vec<VectorDiff<T>> {
+    let Range { start, end } = *range;
+    if index >= end || after.len() <= start || range.is_empty() {
+        return Vec::new();
+    }
+
+    let mut diffs = Vec::new();
+    let mut len = min(before.len(), end).saturating_sub(start);
+
+    // The last element of a full range is pushed out of it.
+    if len == end - start {
+        diffs.push(VectorDiff::PopBack);
+        len -= 1;
+    }
+
+    // Elements inserted before the range push the one before it into it.
+    let index = index.max(start);
+    let value = after[index].clone();
+    diffs.push(match index - start {
+        0 => VectorDiff::PushFront { value },
+        i if i == len => VectorDiff::PushBack { value },
+        i => VectorDiff::Insert { index: i, value },
+    });
+
+    diffs
+}
+
+/// The diffs of the elements in `range` for the element at `index` being
+/// removed.
+fn removed<T: Clone>(
+    index: usize,
+    before: &Vector<T>,
+    after: &Vector<T>,
+    range: &Range<usize>,
+) -> Vec<VectorDiff<T>> {
+    let Range { start, end } = *range;
+    let len = min(before.len(), end).saturating_sub(start);
+    if index >= end || len == 0 {
+        return Vec::new();
+    }
+
+    // Elements removed before the range pull its first element out of it.
+    let mut diffs = vec![match index.saturating_sub(start) {
+        0 => VectorDiff::PopFront,
+        i if i == len - 1 => VectorDiff::PopBack,
+        index => VectorDiff::Remove { index },
+    }];
+
+    // The element after the range moves into it.
+    if after.len() >= end {
+        diffs.push(VectorDiff::PushBack { value: after[end - 1].clone() });
+    }
+
+    diffs
+}
+
+fn window<T: Clone>(values: &Vector<T>, range: &Range<usize>) -> Vector<T> {
+    values.iter().take(range.end).skip(range.start).cloned().collect()
+}
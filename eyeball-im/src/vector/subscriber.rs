@@ -1,4 +1,8 @@
+#[cfg(feature = "nightly")]
+use std::async_iter::AsyncIterator;
 use std::{
+    cmp::min,
+    collections::VecDeque,
     fmt,
     hint::unreachable_unchecked,
     mem,
@@ -7,18 +11,19 @@ use std::{
     vec,
 };
 
-use crate::reusable_box::ReusableBoxFuture;
+use crate::recv_future::ReusableBoxRecvFuture;
+#[cfg(feature = "update-seq")]
+use eyeball::UpdateSeq;
 use futures_core::Stream;
 use imbl::Vector;
 use tokio::sync::broadcast::{
-    self,
     error::{RecvError, TryRecvError},
     Receiver,
 };
 #[cfg(feature = "tracing")]
 use tracing::info;
 
-use super::{BroadcastMessage, OneOrManyDiffs, VectorDiff};
+use super::{BroadcastMessage, OneOrManyDiffs, ResetReason, VectorDiff};
 
 /// A subscriber for updates of a [`Vector`].
 #[derive(Debug)]
@@ -68,6 +73,55 @@ impl<T: Clone + 'static> VectorSubscriber<T> {
         let Self { values, rx } = self;
         (values, VectorSubscriberBatchedStream::new(ReusableBoxRecvFuture::new(rx)))
     }
+
+    /// Destructure this `VectorSubscriber` into the initial values and a stream
+    /// of `VectorDiff`s, with `f` applied to all of them.
+    ///
+    /// This is a shorthand for calling
+    /// [`into_values_and_stream`][Self::into_values_and_stream] and mapping the
+    /// initial values as well as every diff with [`VectorDiff::map`].
+    ///
+    /// ```
+    /// use eyeball_im::{ObservableVector, VectorDiff};
+    /// use imbl::vector;
+    /// use stream_assert::assert_next_eq;
+    ///
+    /// let mut ob = ObservableVector::from(vector![1, 2]);
+    /// let (values, mut stream) = ob.subscribe().map(|v| v.to_string());
+    /// assert_eq!(values, vector!["1".to_owned(), "2".to_owned()]);
+    ///
+    /// ob.push_back(3);
+    /// assert_next_eq!(stream, VectorDiff::PushBack { value: "3".to_owned() });
+    /// ```
+    pub fn map<U, F>(
+        self,
+        mut f: F,
+    ) -> (Vector<U>, VectorSubscriberMapped<VectorSubscriberStream<T>, F>)
+    where
+        U: Clone,
+        F: FnMut(T) -> U,
+    {
+        let (values, stream) = self.into_values_and_stream();
+        let values = values.into_iter().map(&mut f).collect();
+        (values, VectorSubscriberMapped { inner: stream, f })
+    }
+
+    /// Destructure this `VectorSubscriber` into the initial values and a stream
+    /// of `Vec<VectorDiff>`s, with `f` applied to all of them.
+    ///
+    /// This is the batched counterpart of [`map`][Self::map].
+    pub fn map_batched<U, F>(
+        self,
+        mut f: F,
+    ) -> (Vector<U>, VectorSubscriberMapped<VectorSubscriberBatchedStream<T>, F>)
+    where
+        U: Clone,
+        F: FnMut(T) -> U,
+    {
+        let (values, stream) = self.into_values_and_batched_stream();
+        let values = values.into_iter().map(&mut f).collect();
+        (values, VectorSubscriberMapped { inner: stream, f })
+    }
 }
 
 /// A stream of `VectorDiff`s created from a [`VectorSubscriber`].
@@ -77,13 +131,29 @@ impl<T: Clone + 'static> VectorSubscriber<T> {
 /// methods).
 #[derive(Debug)]
 pub struct VectorSubscriberStream<T> {
-    inner: ReusableBoxRecvFuture<T>,
+    inner: ReusableBoxRecvFuture<BroadcastMessage<T>>,
     state: VectorSubscriberStreamState<T>,
+    #[cfg(feature = "update-seq")]
+    last_update_seq: Option<UpdateSeq>,
 }
 
 impl<T> VectorSubscriberStream<T> {
-    fn new(inner: ReusableBoxRecvFuture<T>) -> Self {
-        Self { inner, state: VectorSubscriberStreamState::Recv }
+    fn new(inner: ReusableBoxRecvFuture<BroadcastMessage<T>>) -> Self {
+        Self {
+            inner,
+            state: VectorSubscriberStreamState::Recv,
+            #[cfg(feature = "update-seq")]
+            last_update_seq: None,
+        }
+    }
+
+    /// Get the sequence number of the update the last diff returned by this
+    /// stream belongs to.
+    ///
+    /// Returns `None` if no diff was returned yet.
+    #[cfg(feature = "update-seq")]
+    pub fn last_update_seq(&self) -> Option<UpdateSeq> {
+        self.last_update_seq
     }
 }
 
@@ -99,34 +169,43 @@ enum VectorSubscriberStreamState<T> {
 // Not clear why this explicit impl is needed, but it's not unsafe so it is fine
 impl<T> Unpin for VectorSubscriberStreamState<T> {}
 
-impl<T: Clone + 'static> Stream for VectorSubscriberStream<T> {
-    type Item = VectorDiff<T>;
-
-    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+impl<T: Clone + 'static> VectorSubscriberStream<T> {
+    fn poll_next_diff(&mut self, cx: &mut Context<'_>) -> Poll<Option<VectorDiff<T>>> {
         match &mut self.state {
             VectorSubscriberStreamState::Recv => {
                 let (result, mut rx) = ready!(self.inner.poll(cx));
 
                 let poll = match result {
-                    Ok(msg) => match msg.diffs {
-                        OneOrManyDiffs::One(diff) => Poll::Ready(Some(diff)),
-                        OneOrManyDiffs::Many(diffs) if diffs.is_empty() => {
-                            unreachable!("ObservableVectorTransaction never sends empty diffs")
-                        }
-                        OneOrManyDiffs::Many(mut diffs) if diffs.len() == 1 => {
-                            Poll::Ready(Some(diffs.pop().unwrap()))
+                    Ok(msg) => {
+                        #[cfg(feature = "update-seq")]
+                        {
+                            self.last_update_seq = Some(msg.seq());
                         }
-                        OneOrManyDiffs::Many(diffs) => {
-                            let mut iter = diffs.into_iter();
-                            let fst = iter.next().unwrap();
-                            self.state = VectorSubscriberStreamState::YieldBatch { iter, rx };
-                            return Poll::Ready(Some(fst));
+
+                        match msg.into_diffs() {
+                            OneOrManyDiffs::One(diff) => Poll::Ready(Some(diff)),
+                            OneOrManyDiffs::Many(diffs) if diffs.is_empty() => {
+                                unreachable!("ObservableVectorTransaction never sends empty diffs")
+                            }
+                            OneOrManyDiffs::Many(mut diffs) if diffs.len() == 1 => {
+                                Poll::Ready(Some(diffs.pop().unwrap()))
+                            }
+                            OneOrManyDiffs::Many(diffs) => {
+                                let mut iter = diffs.into_iter();
+                                let fst = iter.next().unwrap();
+                                self.state = VectorSubscriberStreamState::YieldBatch { iter, rx };
+                                return Poll::Ready(Some(fst));
+                            }
                         }
-                    },
-                    Err(RecvError::Closed) => Poll::Ready(None),
-                    Err(RecvError::Lagged(_)) => {
-                        Poll::Ready(handle_lag(&mut rx).map(|values| VectorDiff::Reset { values }))
                     }
+                    Err(RecvError::Closed) => Poll::Ready(None),
+                    Err(RecvError::Lagged(_)) => Poll::Ready(handle_lag(&mut rx).map(|msg| {
+                        #[cfg(feature = "update-seq")]
+                        {
+                            self.last_update_seq = Some(msg.seq());
+                        }
+                        VectorDiff::Reset { values: msg.into_state(), reason: ResetReason::Lagged }
+                    })),
                 };
 
                 self.inner.set(rx);
@@ -154,6 +233,23 @@ impl<T: Clone + 'static> Stream for VectorSubscriberStream<T> {
     }
 }
 
+impl<T: Clone + 'static> Stream for VectorSubscriberStream<T> {
+    type Item = VectorDiff<T>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().poll_next_diff(cx)
+    }
+}
+
+#[cfg(feature = "nightly")]
+impl<T: Clone + 'static> AsyncIterator for VectorSubscriberStream<T> {
+    type Item = VectorDiff<T>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().poll_next_diff(cx)
+    }
+}
+
 /// A batched stream of `VectorDiff`s created from a [`VectorSubscriber`].
 ///
 /// Use its [`Stream`] implementation to interact with it (futures-util and
@@ -161,19 +257,76 @@ impl<T: Clone + 'static> Stream for VectorSubscriberStream<T> {
 /// methods).
 #[derive(Debug)]
 pub struct VectorSubscriberBatchedStream<T> {
-    inner: ReusableBoxRecvFuture<T>,
+    inner: ReusableBoxRecvFuture<BroadcastMessage<T>>,
+    // The maximum number of diffs per item.
+    max_batch: usize,
+    // Diffs that were received already, but didn't fit into the last item.
+    pending: VecDeque<VectorDiff<T>>,
+    #[cfg(feature = "update-seq")]
+    last_update_seq: Option<UpdateSeq>,
 }
 
 impl<T> VectorSubscriberBatchedStream<T> {
-    fn new(inner: ReusableBoxRecvFuture<T>) -> Self {
-        Self { inner }
+    fn new(inner: ReusableBoxRecvFuture<BroadcastMessage<T>>) -> Self {
+        Self {
+            inner,
+            max_batch: usize::MAX,
+            pending: VecDeque::new(),
+            #[cfg(feature = "update-seq")]
+            last_update_seq: None,
+        }
+    }
+
+    /// Get the sequence number of the last update whose diffs were returned by
+    /// this stream.
+    ///
+    /// If a batch was split because of
+    /// [`with_max_batch`][Self::with_max_batch], this is already the sequence
+    /// number of the last update included in the first part of it. Returns
+    /// `None` if nothing was returned yet.
+    #[cfg(feature = "update-seq")]
+    pub fn last_update_seq(&self) -> Option<UpdateSeq> {
+        self.last_update_seq
+    }
+
+    /// Limit the number of diffs per item of this stream to `max_batch`.
+    ///
+    /// By default, all diffs that are available when the stream is polled are
+    /// returned at once, which can result in very large batches after bursts
+    /// of updates. With a limit, the remaining diffs are returned by
+    /// subsequent polls.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_batch` is zero.
+    ///
+    /// ```
+    /// use eyeball_im::{ObservableVector, VectorDiff};
+    /// use stream_assert::assert_next_eq;
+    ///
+    /// let mut ob = ObservableVector::new();
+    /// let mut st = ob.subscribe().into_batched_stream().with_max_batch(2);
+    ///
+    /// ob.push_back(1);
+    /// ob.push_back(2);
+    /// ob.push_back(3);
+    ///
+    /// assert_next_eq!(st, vec![VectorDiff::PushBack { value: 1 }, VectorDiff::PushBack { value: 2 }]);
+    /// assert_next_eq!(st, vec![VectorDiff::PushBack { value: 3 }]);
+    /// ```
+    #[track_caller]
+    pub fn with_max_batch(mut self, max_batch: usize) -> Self {
+        assert!(max_batch != 0, "max_batch must not be zero");
+        self.max_batch = max_batch;
+        self
     }
 }
 
-impl<T: Clone + 'static> Stream for VectorSubscriberBatchedStream<T> {
-    type Item = Vec<VectorDiff<T>>;
+// The pending diffs are never pinned, so this is fine
+impl<T> Unpin for VectorSubscriberBatchedStream<T> {}
 
-    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+impl<T: Clone + 'static> VectorSubscriberBatchedStream<T> {
+    fn poll_next_batch(&mut self, cx: &mut Context<'_>) -> Poll<Option<Vec<VectorDiff<T>>>> {
         fn append<T>(target: &mut Vec<VectorDiff<T>>, source: OneOrManyDiffs<T>) {
             match source {
                 OneOrManyDiffs::One(diff) => target.push(diff),
@@ -181,30 +334,61 @@ impl<T: Clone + 'static> Stream for VectorSubscriberBatchedStream<T> {
             }
         }
 
+        let max_batch = self.max_batch;
+        if !self.pending.is_empty() {
+            let len = min(max_batch, self.pending.len());
+            return Poll::Ready(Some(self.pending.drain(..len).collect()));
+        }
+
         let (result, mut rx) = ready!(self.inner.poll(cx));
 
         let poll = match result {
             Ok(msg) => {
-                let mut batch = msg.diffs.into_vec();
+                #[cfg(feature = "update-seq")]
+                {
+                    self.last_update_seq = Some(msg.seq());
+                }
+                let mut batch = msg.into_diffs().into_vec();
                 loop {
+                    // Leave further messages in the channel if the batch is full.
+                    if batch.len() >= max_batch {
+                        self.pending.extend(batch.drain(max_batch..));
+                        break Poll::Ready(Some(batch));
+                    }
+
                     match rx.try_recv() {
-                        Ok(msg) => append(&mut batch, msg.diffs),
+                        Ok(msg) => {
+                            #[cfg(feature = "update-seq")]
+                            {
+                                self.last_update_seq = Some(msg.seq());
+                            }
+                            append(&mut batch, msg.into_diffs());
+                        }
                         Err(TryRecvError::Empty | TryRecvError::Closed) => {
                             break Poll::Ready(Some(batch));
                         }
                         Err(TryRecvError::Lagged(_)) => {
-                            break Poll::Ready(
-                                handle_lag(&mut rx)
-                                    .map(|values| vec![VectorDiff::Reset { values }]),
-                            );
+                            break Poll::Ready(handle_lag(&mut rx).map(|msg| {
+                                #[cfg(feature = "update-seq")]
+                                {
+                                    self.last_update_seq = Some(msg.seq());
+                                }
+                                let values = msg.into_state();
+                                vec![VectorDiff::Reset { values, reason: ResetReason::Lagged }]
+                            }));
                         }
                     }
                 }
             }
             Err(RecvError::Closed) => Poll::Ready(None),
-            Err(RecvError::Lagged(_)) => {
-                Poll::Ready(handle_lag(&mut rx).map(|values| vec![VectorDiff::Reset { values }]))
-            }
+            Err(RecvError::Lagged(_)) => Poll::Ready(handle_lag(&mut rx).map(|msg| {
+                #[cfg(feature = "update-seq")]
+                {
+                    self.last_update_seq = Some(msg.seq());
+                }
+                let values = msg.into_state();
+                vec![VectorDiff::Reset { values, reason: ResetReason::Lagged }]
+            })),
         };
 
         self.inner.set(rx);
@@ -212,7 +396,74 @@ impl<T: Clone + 'static> Stream for VectorSubscriberBatchedStream<T> {
     }
 }
 
-fn handle_lag<T: Clone + 'static>(rx: &mut Receiver<BroadcastMessage<T>>) -> Option<Vector<T>> {
+impl<T: Clone + 'static> Stream for VectorSubscriberBatchedStream<T> {
+    type Item = Vec<VectorDiff<T>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().poll_next_batch(cx)
+    }
+}
+
+#[cfg(feature = "nightly")]
+impl<T: Clone + 'static> AsyncIterator for VectorSubscriberBatchedStream<T> {
+    type Item = Vec<VectorDiff<T>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().poll_next_batch(cx)
+    }
+}
+
+/// A stream of mapped `VectorDiff`s or `Vec<VectorDiff>`s, created by
+/// [`VectorSubscriber::map`] or [`VectorSubscriber::map_batched`].
+pub struct VectorSubscriberMapped<S, F> {
+    inner: S,
+    f: F,
+}
+
+impl<S: fmt::Debug, F> fmt::Debug for VectorSubscriberMapped<S, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VectorSubscriberMapped").field("inner", &self.inner).finish_non_exhaustive()
+    }
+}
+
+// The mapping function is never pinned, so this is fine
+impl<S: Unpin, F> Unpin for VectorSubscriberMapped<S, F> {}
+
+impl<T, U, F> Stream for VectorSubscriberMapped<VectorSubscriberStream<T>, F>
+where
+    T: Clone + 'static,
+    U: Clone,
+    F: FnMut(T) -> U,
+{
+    type Item = VectorDiff<U>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let diff = ready!(this.inner.poll_next_diff(cx));
+        Poll::Ready(diff.map(|diff| diff.map(&mut this.f)))
+    }
+}
+
+impl<T, U, F> Stream for VectorSubscriberMapped<VectorSubscriberBatchedStream<T>, F>
+where
+    T: Clone + 'static,
+    U: Clone,
+    F: FnMut(T) -> U,
+{
+    type Item = Vec<VectorDiff<U>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let diffs = ready!(this.inner.poll_next_batch(cx));
+        Poll::Ready(diffs.map(|diffs| diffs.into_iter().map(|d| d.map(&mut this.f)).collect()))
+    }
+}
+
+/// Find the last message in `rx` after it lagged, whose state is used for a
+/// reset.
+pub(super) fn handle_lag<T: Clone + 'static>(
+    rx: &mut Receiver<BroadcastMessage<T>>,
+) -> Option<BroadcastMessage<T>> {
     let mut msg = None;
     loop {
         match rx.try_recv() {
@@ -233,7 +484,7 @@ fn handle_lag<T: Clone + 'static>(rx: &mut Receiver<BroadcastMessage<T>>) -> Opt
             Err(TryRecvError::Empty) => match msg {
                 // We exhausted the internal buffer using try_recv, msg contains the
                 // last message from it, which we use for the reset.
-                Some(msg) => return Some(msg.state),
+                Some(msg) => return Some(msg),
                 // We exhausted the internal buffer using try_recv but there was no
                 // message in it, even though we got TryRecvError::Lagged(_) before.
                 None => unreachable!("got no new message via try_recv after lag"),
@@ -241,56 +492,3 @@ fn handle_lag<T: Clone + 'static>(rx: &mut Receiver<BroadcastMessage<T>>) -> Opt
         }
     }
 }
-
-type SubscriberFutureReturn<T> = (Result<T, RecvError>, Receiver<T>);
-
-struct ReusableBoxRecvFuture<T> {
-    inner: ReusableBoxFuture<'static, SubscriberFutureReturn<BroadcastMessage<T>>>,
-}
-
-async fn make_recv_future<T: Clone>(mut rx: Receiver<T>) -> SubscriberFutureReturn<T> {
-    let result = rx.recv().await;
-    (result, rx)
-}
-
-impl<T> ReusableBoxRecvFuture<T>
-where
-    T: Clone + 'static,
-{
-    fn set(&mut self, rx: Receiver<BroadcastMessage<T>>) {
-        self.inner.set(make_recv_future(rx));
-    }
-
-    fn poll(&mut self, cx: &mut Context<'_>) -> Poll<SubscriberFutureReturn<BroadcastMessage<T>>> {
-        self.inner.poll(cx)
-    }
-}
-
-impl<T> ReusableBoxRecvFuture<T>
-where
-    T: Clone + 'static,
-{
-    fn new(rx: Receiver<BroadcastMessage<T>>) -> Self {
-        Self { inner: ReusableBoxFuture::new(make_recv_future(rx)) }
-    }
-}
-
-fn assert_send<T: Send>(_val: T) {}
-#[allow(unused)]
-fn assert_make_future_send() {
-    #[derive(Clone)]
-    struct IsSend(*mut ());
-    unsafe impl Send for IsSend {}
-
-    let (_sender, receiver): (_, Receiver<IsSend>) = broadcast::channel(1);
-
-    assert_send(make_recv_future(receiver));
-}
-// SAFETY: make_future is Send if T is, as proven by assert_make_future_send.
-unsafe impl<T: Send> Send for ReusableBoxRecvFuture<T> {}
-
-impl<T> fmt::Debug for ReusableBoxRecvFuture<T> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("ReusableBoxRecvFuture").finish()
-    }
-}
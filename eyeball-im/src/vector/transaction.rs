@@ -1,10 +1,17 @@
-use std::{fmt, mem, ops};
+use std::{
+    cmp::Ordering,
+    fmt, mem,
+    ops::{self, RangeBounds},
+};
 
 use imbl::Vector;
 
 use crate::vector::OneOrManyDiffs;
 
-use super::{entry::EntryIndex, BroadcastMessage, ObservableVector, VectorDiff};
+use super::{
+    entry::EntryIndex, hooks::HookEvent, reorder_diffs, resolve_range, sort_values, splice_diffs,
+    splice_values, ObservableVector, VectorDiff,
+};
 
 /// A transaction that allows making multiple updates to an `ObservableVector`
 /// as an atomic unit.
@@ -19,12 +26,14 @@ pub struct ObservableVectorTransaction<'o, T: Clone> {
     values: Vector<T>,
     // The batched updates, to be sent to subscribers on commit.
     batch: Vec<VectorDiff<T>>,
+    // Calls to the observable's hooks, to be run on commit.
+    hook_events: Vec<HookEvent<T>>,
 }
 
 impl<'o, T: Clone + 'static> ObservableVectorTransaction<'o, T> {
     pub(super) fn new(inner: &'o mut ObservableVector<T>) -> Self {
         let values = inner.values.clone();
-        Self { inner, values, batch: Vec::new() }
+        Self { inner, values, batch: Vec::new(), hook_events: Vec::new() }
     }
 
     /// Commit this transaction, persisting the changes and notifying
@@ -34,22 +43,21 @@ impl<'o, T: Clone + 'static> ObservableVectorTransaction<'o, T> {
         tracing::debug!("commit");
 
         self.inner.values = mem::take(&mut self.values);
+        if let Some(hooks) = &mut self.inner.hooks {
+            self.hook_events.drain(..).for_each(|event| hooks.run(&event));
+        }
+        let evicted = self.inner.evict(false);
+        self.batch.extend(evicted);
 
         if self.batch.is_empty() {
+            self.inner.broadcast_len();
             #[cfg(feature = "tracing")]
             tracing::trace!(
                 target: "eyeball_im::vector::broadcast",
                 "Skipping broadcast of empty list of diffs"
             );
         } else {
-            let diffs = OneOrManyDiffs::Many(mem::take(&mut self.batch));
-            let msg = BroadcastMessage { diffs, state: self.inner.values.clone() };
-            let _num_receivers = self.inner.sender.send(msg).unwrap_or(0);
-            #[cfg(feature = "tracing")]
-            tracing::debug!(
-                target: "eyeball_im::vector::broadcast",
-                "New observable value broadcast to {_num_receivers} receivers"
-            );
+            self.inner.broadcast(OneOrManyDiffs::Many(mem::take(&mut self.batch)));
         }
     }
 
@@ -62,6 +70,7 @@ impl<'o, T: Clone + 'static> ObservableVectorTransaction<'o, T> {
 
         self.values = self.inner.values.clone();
         self.batch.clear();
+        self.hook_events.clear();
     }
 
     /// Append the given elements at the end of the `Vector` and notify
@@ -73,6 +82,9 @@ impl<'o, T: Clone + 'static> ObservableVectorTransaction<'o, T> {
             "append(len = {})", values.len()
         );
 
+        for value in &values {
+            self.record_insert(value);
+        }
         self.values.append(values.clone());
         self.add_to_batch(VectorDiff::Append { values });
     }
@@ -82,6 +94,7 @@ impl<'o, T: Clone + 'static> ObservableVectorTransaction<'o, T> {
         #[cfg(feature = "tracing")]
         tracing::debug!(target: "eyeball_im::vector::transaction::update", "clear");
 
+        self.record_removes(0);
         self.values.clear();
         self.batch.clear(); // All previous batched updates are irrelevant now
         self.add_to_batch(VectorDiff::Clear);
@@ -92,8 +105,9 @@ impl<'o, T: Clone + 'static> ObservableVectorTransaction<'o, T> {
         #[cfg(feature = "tracing")]
         tracing::debug!(target: "eyeball_im::vector::transaction::update", "push_front");
 
-        self.values.push_front(value.clone());
-        self.add_to_batch(VectorDiff::PushFront { value });
+        self.record_insert(&value);
+        self.values.push_front(value);
+        self.add_to_batch_with(|values| VectorDiff::PushFront { value: values[0].clone() });
     }
 
     /// Add an element at the back of the list and notify subscribers.
@@ -101,8 +115,11 @@ impl<'o, T: Clone + 'static> ObservableVectorTransaction<'o, T> {
         #[cfg(feature = "tracing")]
         tracing::debug!(target: "eyeball_im::vector::transaction::update", "push_back");
 
-        self.values.push_back(value.clone());
-        self.add_to_batch(VectorDiff::PushBack { value });
+        self.record_insert(&value);
+        self.values.push_back(value);
+        self.add_to_batch_with(|values| VectorDiff::PushBack {
+            value: values.back().expect("vector is not empty").clone(),
+        });
     }
 
     /// Remove the first element, notify subscribers and return the element.
@@ -111,10 +128,11 @@ impl<'o, T: Clone + 'static> ObservableVectorTransaction<'o, T> {
     /// method will return `None`.
     pub fn pop_front(&mut self) -> Option<T> {
         let value = self.values.pop_front();
-        if value.is_some() {
+        if let Some(value) = &value {
             #[cfg(feature = "tracing")]
             tracing::debug!(target: "eyeball_im::vector::transaction::update", "pop_front");
 
+            self.record_remove(value);
             self.add_to_batch(VectorDiff::PopFront);
         }
         value
@@ -126,10 +144,11 @@ impl<'o, T: Clone + 'static> ObservableVectorTransaction<'o, T> {
     /// method will return `None`.
     pub fn pop_back(&mut self) -> Option<T> {
         let value = self.values.pop_back();
-        if value.is_some() {
+        if let Some(value) = &value {
             #[cfg(feature = "tracing")]
             tracing::debug!(target: "eyeball_im::vector::transaction::update", "pop_back");
 
+            self.record_remove(value);
             self.add_to_batch(VectorDiff::PopBack);
         }
         value
@@ -150,8 +169,12 @@ impl<'o, T: Clone + 'static> ObservableVectorTransaction<'o, T> {
                 "insert(index = {index})"
             );
 
-            self.values.insert(index, value.clone());
-            self.add_to_batch(VectorDiff::Insert { index, value });
+            self.record_insert(&value);
+            self.values.insert(index, value);
+            self.add_to_batch_with(|values| VectorDiff::Insert {
+                index,
+                value: values[index].clone(),
+            });
         } else {
             panic!("index out of bounds: the length is {len} but the index is {index}");
         }
@@ -174,6 +197,8 @@ impl<'o, T: Clone + 'static> ObservableVectorTransaction<'o, T> {
             );
 
             let old_value = self.values.set(index, value.clone());
+            self.record_remove(&old_value);
+            self.record_insert(&value);
             self.add_to_batch(VectorDiff::Set { index, value });
             old_value
         } else {
@@ -198,6 +223,7 @@ impl<'o, T: Clone + 'static> ObservableVectorTransaction<'o, T> {
             );
 
             let value = self.values.remove(index);
+            self.record_remove(&value);
             self.add_to_batch(VectorDiff::Remove { index });
             value
         } else {
@@ -205,6 +231,39 @@ impl<'o, T: Clone + 'static> ObservableVectorTransaction<'o, T> {
         }
     }
 
+    /// Remove the element at the given position by replacing it with the last
+    /// element, notify subscribers and return the removed element.
+    ///
+    /// See [`ObservableVector::swap_remove`] for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index >= len`.
+    #[track_caller]
+    pub fn swap_remove(&mut self, index: usize) -> T {
+        let len = self.values.len();
+        if index < len {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(
+                target: "eyeball_im::vector::transaction::update",
+                "swap_remove(index = {index})"
+            );
+
+            let last = self.values.pop_back().expect("vector is not empty");
+            let value = if index == len - 1 {
+                last
+            } else {
+                self.add_to_batch(VectorDiff::Set { index, value: last.clone() });
+                self.values.set(index, last)
+            };
+            self.record_remove(&value);
+            self.add_to_batch(VectorDiff::PopBack);
+            value
+        } else {
+            panic!("index out of bounds: the length is {len} but the index is {index}");
+        }
+    }
+
     /// Truncate the vector to `len` elements and notify subscribers.
     ///
     /// Does nothing if `len` is greater or equal to the vector's current
@@ -214,11 +273,153 @@ impl<'o, T: Clone + 'static> ObservableVectorTransaction<'o, T> {
             #[cfg(feature = "tracing")]
             tracing::debug!(target: "eyeball_im::vector::update", "truncate(len = {len})");
 
+            self.record_removes(len);
             self.values.truncate(len);
             self.add_to_batch(VectorDiff::Truncate { length: len });
         }
     }
 
+    /// Remove the elements in the given range, notify subscribers and return
+    /// the removed elements.
+    ///
+    /// See [`ObservableVector::drain`] for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than its end, or if the end
+    /// is greater than `len`.
+    #[track_caller]
+    pub fn drain(&mut self, range: impl RangeBounds<usize>) -> Vec<T> {
+        let (start, end) = resolve_range(range, self.values.len());
+        if start == end {
+            return Vec::new();
+        }
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            target: "eyeball_im::vector::transaction::update",
+            "drain(start = {start}, end = {end})"
+        );
+
+        let prev_len = self.values.len();
+        let removed = splice_values(&mut self.values, start, end, Vector::new());
+        for value in &removed {
+            self.record_remove(value);
+        }
+
+        self.add_diffs_to_batch(splice_diffs(start, end, prev_len, &Vector::new(), &self.values));
+
+        removed.into_iter().collect()
+    }
+
+    /// Replace the elements in the given range by `replacement`, notify
+    /// subscribers and return the replaced elements.
+    ///
+    /// See [`ObservableVector::splice`] for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than its end, or if the end
+    /// is greater than `len`.
+    #[track_caller]
+    pub fn splice(&mut self, range: impl RangeBounds<usize>, replacement: Vector<T>) -> Vec<T> {
+        let (start, end) = resolve_range(range, self.values.len());
+        if start == end && replacement.is_empty() {
+            return Vec::new();
+        }
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            target: "eyeball_im::vector::transaction::update",
+            "splice(start = {start}, end = {end}, len = {})",
+            replacement.len()
+        );
+
+        let prev_len = self.values.len();
+        let removed = splice_values(&mut self.values, start, end, replacement.clone());
+        for value in &removed {
+            self.record_remove(value);
+        }
+        for value in &replacement {
+            self.record_insert(value);
+        }
+
+        self.add_diffs_to_batch(splice_diffs(start, end, prev_len, &replacement, &self.values));
+
+        removed.into_iter().collect()
+    }
+
+    /// Sort the vector and notify subscribers.
+    ///
+    /// See [`ObservableVector::sort_by`] for details.
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.sort_by(Ord::cmp);
+    }
+
+    /// Sort the vector with the given comparison function and notify
+    /// subscribers.
+    ///
+    /// See [`ObservableVector::sort_by`] for details.
+    pub fn sort_by(&mut self, compare: impl FnMut(&T, &T) -> Ordering) {
+        let (values, new_indices) = sort_values(&self.values, compare);
+        let diffs = reorder_diffs(&new_indices, &values);
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            target: "eyeball_im::vector::transaction::update",
+            nop = diffs.is_empty().then_some(true),
+            "sort"
+        );
+
+        if !diffs.is_empty() {
+            self.values = values;
+            self.add_diffs_to_batch(diffs);
+        }
+    }
+
+    /// Sort the vector with the given key extraction function and notify
+    /// subscribers.
+    ///
+    /// See [`ObservableVector::sort_by`] for details.
+    pub fn sort_by_key<K: Ord>(&mut self, mut f: impl FnMut(&T) -> K) {
+        self.sort_by(|a, b| f(a).cmp(&f(b)));
+    }
+
+    /// Move the element at position `from` to position `to` and notify
+    /// subscribers.
+    ///
+    /// The elements between `from` and `to` are shifted by one position
+    /// towards `from`. Does nothing if `from == to`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `from >= len` or `to >= len`.
+    #[track_caller]
+    pub fn move_item(&mut self, from: usize, to: usize) {
+        let len = self.values.len();
+        if from >= len {
+            panic!("index out of bounds: the length is {len} but the index is {from}");
+        }
+        if to >= len {
+            panic!("index out of bounds: the length is {len} but the index is {to}");
+        }
+
+        if from != to {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(
+                target: "eyeball_im::vector::transaction::update",
+                "move_item(from = {from}, to = {to})"
+            );
+
+            let value = self.values.remove(from);
+            self.values.insert(to, value);
+            self.add_to_batch(VectorDiff::Move { from, to });
+        }
+    }
+
     /// Gets an entry for the given index through which only the element at that
     /// index alone can be updated or removed.
     ///
@@ -270,11 +471,49 @@ impl<'o, T: Clone + 'static> ObservableVectorTransaction<'o, T> {
         ObservableVectorTransactionEntries::new(self)
     }
 
+    fn record_insert(&mut self, value: &T) {
+        if self.inner.hooks.is_some() {
+            self.hook_events.push(HookEvent::Inserted(value.clone()));
+        }
+    }
+
+    fn record_remove(&mut self, value: &T) {
+        if self.inner.hooks.is_some() {
+            self.hook_events.push(HookEvent::Removed(value.clone()));
+        }
+    }
+
+    /// Record removal of all values starting at index `start`.
+    fn record_removes(&mut self, start: usize) {
+        if self.inner.hooks.is_some() {
+            let removed = self.values.iter().skip(start).cloned().map(HookEvent::Removed);
+            self.hook_events.extend(removed);
+        }
+    }
+
     fn add_to_batch(&mut self, diff: VectorDiff<T>) {
-        if self.inner.sender.receiver_count() != 0 {
+        if self.inner.has_diff_receivers() {
             self.batch.push(diff);
         }
     }
+
+    fn add_diffs_to_batch(&mut self, diffs: Vec<VectorDiff<T>>) {
+        if let Some(VectorDiff::Clear | VectorDiff::Reset { .. }) = diffs.first() {
+            // All previous batched updates are irrelevant now
+            self.batch.clear();
+        }
+        for diff in diffs {
+            self.add_to_batch(diff);
+        }
+    }
+
+    /// Like `add_to_batch`, but only creates the diff if there are
+    /// subscribers, to avoid cloning values that nobody receives.
+    fn add_to_batch_with(&mut self, make_diff: impl FnOnce(&Vector<T>) -> VectorDiff<T>) {
+        if self.inner.has_diff_receivers() {
+            self.batch.push(make_diff(&self.values));
+        }
+    }
 }
 
 impl<T> fmt::Debug for ObservableVectorTransaction<'_, T>
@@ -340,10 +579,64 @@ where
         this.inner.set(this.index.value(), value)
     }
 
+    /// Replace the given element, notify subscribers and return the previous
+    /// element, consuming the entry.
+    ///
+    /// This is the same as [`set`][Self::set], but can be used in expressions
+    /// the same way as [`remove`][Self::remove].
+    pub fn replace(mut this: Self, value: T) -> T {
+        Self::set(&mut this, value)
+    }
+
     /// Remove the given element, notify subscribers and return the element.
     pub fn remove(mut this: Self) -> T {
         this.inner.remove(this.index.make_owned())
     }
+
+    /// Insert an element in front of the given one and notify subscribers.
+    ///
+    /// The entry keeps referring to the same element, whose index is
+    /// incremented by one. When iterating, the new element is not visited.
+    pub fn insert_before(this: &mut Self, value: T) {
+        let index = this.index.value();
+        this.inner.insert(index, value);
+        this.index.increment();
+    }
+
+    /// Insert an element after the given one and notify subscribers.
+    ///
+    /// When iterating, the new element is not visited; iteration resumes with
+    /// the element after it.
+    pub fn insert_after(mut this: Self, value: T) {
+        let index = this.index.value();
+        this.inner.insert(index + 1, value);
+        this.index.finish(index + 2);
+    }
+
+    /// Replace the given element with any number of elements, notify
+    /// subscribers and return the previous element.
+    ///
+    /// If `values` is empty, this is equivalent to [`remove`][Self::remove].
+    /// When iterating, the new elements are not visited; iteration resumes with
+    /// the element after them.
+    pub fn splice(mut this: Self, values: impl IntoIterator<Item = T>) -> T {
+        let index = this.index.value();
+        let mut values = values.into_iter();
+        let first = match values.next() {
+            Some(value) => value,
+            None => return this.inner.remove(this.index.make_owned()),
+        };
+
+        let old_value = this.inner.set(index, first);
+        let mut next_index = index + 1;
+        for value in values {
+            this.inner.insert(next_index, value);
+            next_index += 1;
+        }
+
+        this.index.finish(next_index);
+        old_value
+    }
 }
 
 impl<T> fmt::Debug for ObservableVectorTransactionEntry<'_, '_, T>
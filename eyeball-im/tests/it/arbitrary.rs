@@ -0,0 +1,63 @@
+use std::{collections::HashSet, mem};
+
+use arbitrary::{Arbitrary, Unstructured};
+use eyeball_im::{
+    arbitrary::{apply_arbitrary_op, apply_arbitrary_ops},
+    ObservableVector, VectorDiff,
+};
+
+use super::poll_next_now;
+
+fn random_bytes(seed: &mut u32, len: usize) -> Vec<u8> {
+    (0..len)
+        .map(|_| {
+            *seed ^= *seed << 13;
+            *seed ^= *seed >> 17;
+            *seed ^= *seed << 5;
+            *seed as u8
+        })
+        .collect()
+}
+
+#[test]
+fn arbitrary_diffs() {
+    let data = random_bytes(&mut 0x9e37_79b9, 4096);
+    let mut u = Unstructured::new(&data);
+
+    let mut kinds = HashSet::new();
+    while !u.is_empty() {
+        let diff = VectorDiff::<u8>::arbitrary(&mut u).unwrap();
+        kinds.insert(mem::discriminant(&diff));
+    }
+    assert_eq!(kinds.len(), 12);
+}
+
+#[test]
+fn apply_arbitrary_ops_diffs_apply() {
+    let mut seed = 0x9e37_79b9;
+    for _ in 0..200 {
+        let data = random_bytes(&mut seed, 256);
+        let mut u = Unstructured::new(&data);
+
+        let mut ob = ObservableVector::<u8>::arbitrary(&mut u).unwrap();
+        let (mut values, mut sub) = ob.subscribe().into_values_and_batched_stream();
+        apply_arbitrary_ops(&mut ob, &mut u).unwrap();
+        assert!(u.is_empty());
+
+        while let std::task::Poll::Ready(Some(diffs)) = poll_next_now(&mut sub) {
+            diffs.into_iter().for_each(|diff| diff.apply(&mut values));
+        }
+        assert_eq!(values, *ob);
+    }
+}
+
+#[test]
+fn apply_arbitrary_op_empty() {
+    // Operations that require an element must not panic on an empty vector.
+    for op in 0..=u8::MAX {
+        let mut ob = ObservableVector::<u8>::new();
+        let data = [op, 42, 0, 0];
+        let mut u = Unstructured::new(&data);
+        apply_arbitrary_op(&mut ob, &mut u).unwrap();
+    }
+}
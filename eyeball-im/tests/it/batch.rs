@@ -1,5 +1,5 @@
 use imbl::vector;
-use stream_assert::{assert_next_eq, assert_pending};
+use stream_assert::{assert_closed, assert_next_eq, assert_pending};
 
 use eyeball_im::{ObservableVector, VectorDiff};
 
@@ -48,3 +48,27 @@ fn transaction() {
 
     assert_next_eq!(st, vec![VectorDiff::Clear, VectorDiff::PushBack { value: 1 }]);
 }
+
+#[test]
+fn max_batch() {
+    let mut ob = ObservableVector::new();
+    let mut st = ob.subscribe().into_batched_stream().with_max_batch(2);
+
+    ob.push_back(0);
+    let mut txn = ob.transaction();
+    txn.push_back(1);
+    txn.push_back(2);
+    txn.push_back(3);
+    txn.commit();
+    ob.push_back(4);
+
+    assert_next_eq!(st, vec![VectorDiff::PushBack { value: 0 }, VectorDiff::PushBack { value: 1 }]);
+    assert_next_eq!(st, vec![VectorDiff::PushBack { value: 2 }, VectorDiff::PushBack { value: 3 }]);
+    assert_next_eq!(st, vec![VectorDiff::PushBack { value: 4 }]);
+    assert_pending!(st);
+
+    ob.push_back(5);
+    drop(ob);
+    assert_next_eq!(st, vec![VectorDiff::PushBack { value: 5 }]);
+    assert_closed!(st);
+}
@@ -0,0 +1,90 @@
+use imbl::vector;
+
+use eyeball_im::{DiffTable, ResetReason, VectorDiff};
+
+#[test]
+fn golden_strings() {
+    let cases: [(VectorDiff<String>, &str); 13] = [
+        (VectorDiff::Append { values: vector!["a".to_owned(), "b".to_owned()] }, "append[a,b]"),
+        (VectorDiff::Clear, "clear"),
+        (VectorDiff::PushFront { value: "a".to_owned() }, "pushf=a"),
+        (VectorDiff::PushBack { value: "a".to_owned() }, "pushb=a"),
+        (VectorDiff::PopFront, "popf"),
+        (VectorDiff::PopBack, "popb"),
+        (VectorDiff::Insert { index: 3, value: "x".to_owned() }, "ins@3=x"),
+        (VectorDiff::Set { index: 1, value: "y".to_owned() }, "set@1=y"),
+        (VectorDiff::Remove { index: 0 }, "rm@0"),
+        (VectorDiff::Truncate { length: 2 }, "trunc@2"),
+        (VectorDiff::Move { from: 1, to: 3 }, "mv@1>3"),
+        (VectorDiff::Reset { values: vector![], reason: ResetReason::Update }, "reset[]"),
+        (
+            VectorDiff::Reset { values: vector!["a".to_owned()], reason: ResetReason::Lagged },
+            "lagreset[a]",
+        ),
+    ];
+
+    for (diff, compact) in cases {
+        assert_eq!(diff.to_compact_string(), compact);
+        assert_eq!(VectorDiff::from_compact_string(compact), Ok(diff));
+    }
+}
+
+#[test]
+fn escaping() {
+    let diff =
+        VectorDiff::Append { values: vector!["a,b".to_owned(), "[c]".to_owned(), r"\".to_owned()] };
+    let compact = diff.to_compact_string();
+    assert_eq!(compact, r"append[a\,b,\[c\],\\]");
+    assert_eq!(VectorDiff::from_compact_string(&compact), Ok(diff));
+
+    let diff = VectorDiff::Set { index: 0, value: "a=b".to_owned() };
+    let compact = diff.to_compact_string();
+    assert_eq!(compact, "set@0=a=b");
+    assert_eq!(VectorDiff::from_compact_string(&compact), Ok(diff));
+}
+
+#[test]
+fn empty_values() {
+    let cases: [(VectorDiff<String>, &str); 5] = [
+        (VectorDiff::Append { values: vector![String::new()] }, r#"append[""]"#),
+        (VectorDiff::Append { values: vector![String::new(), String::new()] }, r#"append["",""]"#),
+        (VectorDiff::Append { values: vector![] }, "append[]"),
+        (VectorDiff::PushBack { value: String::new() }, r#"pushb="""#),
+        (VectorDiff::PushBack { value: r#""""#.to_owned() }, r#"pushb=\"\""#),
+    ];
+
+    for (diff, compact) in cases {
+        assert_eq!(diff.to_compact_string(), compact);
+        assert_eq!(VectorDiff::from_compact_string(compact), Ok(diff));
+    }
+}
+
+#[test]
+fn parse_errors() {
+    for input in
+        ["", "pop", "ins@x=1", "mv@1", "mv@1>x", "rm", "rm@1=2", "popf=1", "append[1", "pushb=1,2"]
+    {
+        let err = VectorDiff::<String>::from_compact_string(input).unwrap_err();
+        assert_eq!(err.to_string(), format!("invalid compact vector diff: `{input}`"));
+    }
+    assert!(VectorDiff::<i32>::from_compact_string("pushb=abc").is_err());
+}
+
+#[test]
+fn table() {
+    let diffs = [
+        VectorDiff::PushBack { value: 3 },
+        VectorDiff::Remove { index: 0 },
+        VectorDiff::Truncate { length: 0 },
+    ];
+    assert_eq!(
+        DiffTable::new(vector![1, 2], &diffs).to_string(),
+        "\
+diff    | before  | after
+--------+---------+--------
+pushb=3 | [1,2]   | [1,2,3]
+rm@0    | [1,2,3] | [2,3]
+trunc@0 | [2,3]   | []
+"
+    );
+}
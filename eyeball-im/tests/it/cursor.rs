@@ -0,0 +1,95 @@
+use eyeball_im::ObservableVector;
+use imbl::vector;
+
+#[test]
+fn follows_updates() {
+    let mut ob = ObservableVector::from(vector!['a', 'b', 'c', 'd']);
+    let mut cursor = ob.cursor();
+    assert_eq!(cursor.current(), None);
+    assert_eq!(cursor.prev(), None);
+
+    assert_eq!(cursor.next(), Some('a'));
+    assert_eq!(cursor.next(), Some('b'));
+    assert_eq!(cursor.position(), 2);
+
+    // Inserting at the cursor's position puts the element after it
+    ob.insert(2, 'x');
+    ob.push_front('0');
+    assert_eq!(cursor.position(), 3);
+    assert_eq!(cursor.current(), Some('b'));
+    assert_eq!(cursor.next(), Some('x'));
+
+    // Removing elements before the cursor
+    ob.pop_front();
+    ob.remove(0);
+    assert_eq!(cursor.position(), 2);
+    assert_eq!(cursor.current(), Some('x'));
+
+    // Setting the current element
+    ob.set(1, 'y');
+    assert_eq!(cursor.current(), Some('y'));
+
+    // Moving it doesn't move the cursor
+    ob.move_item(1, 3);
+    assert_eq!(*ob, vector!['b', 'c', 'd', 'y']);
+    assert_eq!(cursor.current(), Some('y'));
+    assert_eq!(cursor.next(), Some('c'));
+
+    // Removing it
+    ob.remove(1);
+    assert_eq!(cursor.current(), None);
+    assert_eq!(cursor.position(), 1);
+    assert_eq!(cursor.prev(), Some('b'));
+    assert_eq!(cursor.prev(), None);
+    assert_eq!(cursor.current(), Some('b'));
+}
+
+#[test]
+fn truncate_and_clear() {
+    let mut ob = ObservableVector::from(vector![1, 2, 3, 4]);
+    let mut cursor = ob.cursor();
+    cursor.by_ref().take(3).for_each(drop);
+
+    ob.truncate(2);
+    assert_eq!(cursor.position(), 2);
+    assert_eq!(cursor.current(), None);
+    assert_eq!(cursor.next(), None);
+
+    // Appended elements are returned by later calls to next
+    let mut txn = ob.transaction();
+    txn.push_back(5);
+    txn.push_back(6);
+    txn.commit();
+    assert_eq!(cursor.next(), Some(5));
+
+    ob.clear();
+    ob.append(vector![7, 8]);
+    assert_eq!(cursor.position(), 0);
+    assert_eq!(cursor.next(), Some(7));
+}
+
+#[test]
+fn lag() {
+    let mut ob = ObservableVector::with_capacity(1);
+    ob.append(vector![1, 2]);
+    let mut cursor = ob.cursor();
+    assert_eq!(cursor.next(), Some(1));
+
+    ob.push_front(0);
+    ob.push_back(3);
+
+    // The cursor can't know where it is anymore and starts over
+    assert_eq!(cursor.position(), 0);
+    assert_eq!(cursor.collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+}
+
+#[test]
+fn background_task() {
+    let mut ob = ObservableVector::from(vector![1, 2, 3]);
+    let cursor = ob.cursor();
+    let handle = std::thread::spawn(move || cursor.sum::<i32>());
+
+    ob.push_back(4);
+    let sum = handle.join().unwrap();
+    assert!(sum == 6 || sum == 10, "unexpected sum {sum}");
+}
@@ -0,0 +1,63 @@
+use imbl::{vector, Vector};
+
+use eyeball_im::{ElementPosition, ResetReason, VectorDiff};
+
+#[test]
+fn map_with_index() {
+    let mut vec = vector!['a', 'b'];
+    let mut mapped: Vector<(usize, char)> = vector![(0, 'a'), (1, 'b')];
+
+    let diffs = vec![
+        VectorDiff::PushBack { value: 'c' },
+        VectorDiff::PushFront { value: 'd' },
+        VectorDiff::Append { values: vector!['e', 'f'] },
+        VectorDiff::Insert { index: 2, value: 'g' },
+        VectorDiff::Set { index: 0, value: 'h' },
+    ];
+
+    for diff in diffs {
+        diff.clone().apply(&mut vec);
+        let len = vec.len();
+        diff.map_with_index(|pos, c| (pos.resolve(len), c)).apply(&mut mapped);
+    }
+
+    assert_eq!(vec, vector!['h', 'a', 'g', 'b', 'c', 'e', 'f']);
+    assert_eq!(
+        mapped,
+        vector![(0, 'h'), (0, 'a'), (2, 'g'), (1, 'b'), (2, 'c'), (4, 'e'), (5, 'f')]
+    );
+}
+
+#[test]
+fn map_with_index_positions() {
+    let mut positions = Vec::new();
+    VectorDiff::Append { values: vector![1, 2, 3] }.map_with_index(|pos, _| positions.push(pos));
+    VectorDiff::Reset { values: vector![1, 2], reason: ResetReason::Update }
+        .map_with_index(|pos, _| positions.push(pos));
+
+    use ElementPosition::{FromBack, Index};
+    assert_eq!(positions, [FromBack(2), FromBack(1), FromBack(0), Index(0), Index(1)]);
+}
+
+#[test]
+fn try_map() {
+    let parse = |s: &str| s.parse::<u8>();
+
+    let diff = VectorDiff::Append { values: vector!["1", "2"] }.try_map(parse);
+    assert_eq!(diff, Ok(VectorDiff::Append { values: vector![1, 2] }));
+
+    let diff = VectorDiff::Set { index: 3, value: "x" }.try_map(parse);
+    assert!(diff.is_err());
+
+    let mut calls = 0;
+    let diff = VectorDiff::Reset { values: vector!["1", "x", "3"], reason: ResetReason::Update }
+        .try_map(|s| {
+            calls += 1;
+            parse(s)
+        });
+    assert!(diff.is_err());
+    assert_eq!(calls, 2);
+
+    let diff = VectorDiff::<&str>::Remove { index: 1 }.try_map(parse);
+    assert_eq!(diff, Ok(VectorDiff::Remove { index: 1 }));
+}
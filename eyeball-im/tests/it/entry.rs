@@ -1,14 +1,18 @@
 use imbl::vector;
+use stream_assert::{assert_next_eq, assert_pending};
 
-use eyeball_im::{ObservableVector, ObservableVectorEntry};
+use eyeball_im::{
+    ObservableVector, ObservableVectorEntry, ObservableVectorTransactionEntry, VectorDiff,
+};
 
 #[test]
 fn entry() {
     let mut ob: ObservableVector<u8> = ObservableVector::from(vector![1, 2]);
-    ObservableVectorEntry::set(&mut ob.entry(1), 3);
-    ObservableVectorEntry::remove(ob.entry(0));
+    assert_eq!(ObservableVectorEntry::set(&mut ob.entry(1), 3), 2);
+    assert_eq!(ObservableVectorEntry::remove(ob.entry(0)), 1);
+    assert_eq!(ObservableVectorEntry::replace(ob.entry(0), 4), 3);
 
-    assert_eq!(ob.into_inner(), vector![3]);
+    assert_eq!(ob.into_inner(), vector![4]);
 }
 
 #[test]
@@ -18,6 +22,22 @@ fn entry_out_of_range() {
     ob.entry(0);
 }
 
+#[test]
+fn entry_by() {
+    let mut ob: ObservableVector<u8> = ObservableVector::from(vector![1, 2, 4, 6]);
+    let mut sub = ob.subscribe().into_stream();
+
+    {
+        let mut entry = ob.entry_by(|&v| v % 2 == 0).unwrap();
+        assert_eq!(ObservableVectorEntry::index(&entry), 1);
+        ObservableVectorEntry::set(&mut entry, 3);
+    }
+    assert_next_eq!(sub, VectorDiff::Set { index: 1, value: 3 });
+
+    assert!(ob.entry_by(|&v| v > 10).is_none());
+    assert_pending!(sub);
+}
+
 #[test]
 fn entries() {
     let mut ob = ObservableVector::from(vector![1, 2, 3]);
@@ -45,3 +65,113 @@ fn remove_entries() {
         ObservableVectorEntry::remove(entry);
     }
 }
+
+#[test]
+fn entries_filtered() {
+    let mut ob = ObservableVector::from(vector![1, 2, 3, 4, 5, 6]);
+    let mut sub = ob.subscribe().into_stream();
+
+    let mut entries = ob.entries_filtered(|&v| v % 2 == 0);
+    while let Some(mut entry) = entries.next() {
+        if *entry == 4 {
+            ObservableVectorEntry::remove(entry);
+        } else {
+            let value = *entry * 10;
+            ObservableVectorEntry::set(&mut entry, value);
+        }
+    }
+
+    assert_next_eq!(sub, VectorDiff::Set { index: 1, value: 20 });
+    assert_next_eq!(sub, VectorDiff::Remove { index: 3 });
+    assert_next_eq!(sub, VectorDiff::Set { index: 4, value: 60 });
+    assert_pending!(sub);
+    assert_eq!(ob.into_inner(), vector![1, 20, 3, 5, 60]);
+}
+
+#[test]
+fn transaction_entries_insert() {
+    let mut ob = ObservableVector::from(vector![1, 2, 3]);
+    let mut sub = ob.subscribe().into_batched_stream();
+
+    let mut txn = ob.transaction();
+    let mut entries = txn.entries();
+    while let Some(mut entry) = entries.next() {
+        match *entry {
+            1 => ObservableVectorTransactionEntry::insert_before(&mut entry, 0),
+            2 => ObservableVectorTransactionEntry::insert_after(entry, 2),
+            _ => {}
+        }
+    }
+    txn.commit();
+
+    assert_eq!(*ob, vector![0, 1, 2, 2, 3]);
+    assert_next_eq!(
+        sub,
+        vec![VectorDiff::Insert { index: 0, value: 0 }, VectorDiff::Insert { index: 3, value: 2 },]
+    );
+    assert_pending!(sub);
+}
+
+#[test]
+fn transaction_entries_splice() {
+    let mut ob = ObservableVector::from(vector![1, 20, 3, 40]);
+    let mut sub = ob.subscribe().into_batched_stream();
+
+    // Split every element >= 10 into its digits
+    let mut txn = ob.transaction();
+    let mut entries = txn.entries();
+    while let Some(entry) = entries.next() {
+        if *entry >= 10 {
+            let digits = entry.to_string().bytes().map(|b| u32::from(b - b'0')).collect::<Vec<_>>();
+            ObservableVectorTransactionEntry::splice(entry, digits);
+        }
+    }
+    txn.commit();
+
+    assert_eq!(*ob, vector![1, 2, 0, 3, 4, 0]);
+    assert_next_eq!(
+        sub,
+        vec![
+            VectorDiff::Set { index: 1, value: 2 },
+            VectorDiff::Insert { index: 2, value: 0 },
+            VectorDiff::Set { index: 4, value: 4 },
+            VectorDiff::Insert { index: 5, value: 0 },
+        ]
+    );
+    assert_pending!(sub);
+}
+
+#[test]
+fn transaction_entries_splice_empty() {
+    let mut ob = ObservableVector::from(vector![1, 2, 3]);
+    let mut txn = ob.transaction();
+    let mut entries = txn.entries();
+    while let Some(entry) = entries.next() {
+        if *entry == 2 {
+            assert_eq!(ObservableVectorTransactionEntry::splice(entry, []), 2);
+        }
+    }
+    txn.commit();
+
+    assert_eq!(ob.into_inner(), vector![1, 3]);
+}
+
+#[test]
+fn transaction_entries_replace_and_remove() {
+    let mut ob = ObservableVector::from(vector![1, 2, 3]);
+    let mut removed = Vec::new();
+
+    let mut txn = ob.transaction();
+    let mut entries = txn.entries();
+    while let Some(entry) = entries.next() {
+        match *entry {
+            1 => removed.push(ObservableVectorTransactionEntry::remove(entry)),
+            3 => removed.push(ObservableVectorTransactionEntry::replace(entry, 30)),
+            _ => {}
+        }
+    }
+    txn.commit();
+
+    assert_eq!(removed, [1, 3]);
+    assert_eq!(ob.into_inner(), vector![2, 30]);
+}
@@ -0,0 +1,117 @@
+use std::sync::{Arc, Mutex};
+
+use imbl::vector;
+use stream_assert::{assert_next_eq, assert_pending};
+
+use eyeball_im::{ObservableVector, VectorDiff};
+
+#[derive(Clone, Debug, PartialEq)]
+enum Event {
+    Insert(i32),
+    Remove(i32),
+}
+
+fn observable_with_log(
+    values: imbl::Vector<i32>,
+) -> (ObservableVector<i32>, Arc<Mutex<Vec<Event>>>) {
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let insert_log = log.clone();
+    let remove_log = log.clone();
+    let ob = ObservableVector::from(values).with_hooks(
+        move |v| insert_log.lock().unwrap().push(Event::Insert(*v)),
+        move |v| remove_log.lock().unwrap().push(Event::Remove(*v)),
+    );
+    (ob, log)
+}
+
+fn take(log: &Mutex<Vec<Event>>) -> Vec<Event> {
+    std::mem::take(&mut *log.lock().unwrap())
+}
+
+#[test]
+fn mutations() {
+    let (mut ob, log) = observable_with_log(vector![1, 2]);
+    assert_eq!(take(&log), [Event::Insert(1), Event::Insert(2)]);
+
+    ob.push_back(3);
+    ob.set(0, 10);
+    ob.remove(1);
+    ob.pop_front();
+    assert_eq!(
+        take(&log),
+        [
+            Event::Insert(3),
+            Event::Remove(1),
+            Event::Insert(10),
+            Event::Remove(2),
+            Event::Remove(10),
+        ]
+    );
+
+    ob.append(vector![4, 5]);
+    ob.truncate(2);
+    assert_eq!(take(&log), [Event::Insert(4), Event::Insert(5), Event::Remove(5)]);
+
+    ob.clear();
+    assert_eq!(take(&log), [Event::Remove(3), Event::Remove(4)]);
+}
+
+#[test]
+fn range_updates() {
+    let (mut ob, log) = observable_with_log(vector![1, 2, 3]);
+    take(&log);
+
+    ob.set_range(1, vector![4, 5]);
+    assert_eq!(
+        take(&log),
+        [Event::Remove(2), Event::Remove(3), Event::Insert(4), Event::Insert(5)]
+    );
+
+    ob.update_range(..1, |v| *v += 1);
+    assert_eq!(take(&log), [Event::Remove(1), Event::Insert(2)]);
+}
+
+#[test]
+fn drop_and_into_inner() {
+    let (ob, log) = observable_with_log(vector![1]);
+    drop(ob);
+    assert_eq!(take(&log), [Event::Insert(1), Event::Remove(1)]);
+
+    let (ob, log) = observable_with_log(vector![1]);
+    assert_eq!(ob.into_inner(), vector![1]);
+    assert_eq!(take(&log), [Event::Insert(1), Event::Remove(1)]);
+}
+
+#[test]
+fn runs_before_broadcast() {
+    let (mut ob, log) = observable_with_log(vector![]);
+    let mut sub = ob.subscribe().into_stream();
+
+    ob.push_back(1);
+    assert_eq!(take(&log), [Event::Insert(1)]);
+    assert_next_eq!(sub, VectorDiff::PushBack { value: 1 });
+}
+
+#[test]
+fn transaction() {
+    let (mut ob, log) = observable_with_log(vector![1]);
+    take(&log);
+    let mut sub = ob.subscribe().into_stream();
+
+    let mut txn = ob.transaction();
+    txn.push_back(2);
+    txn.clear();
+    assert_eq!(take(&log), []);
+    txn.rollback();
+    txn.push_back(3);
+    txn.commit();
+
+    assert_eq!(take(&log), [Event::Insert(3)]);
+    assert_next_eq!(sub, VectorDiff::PushBack { value: 3 });
+
+    let mut txn = ob.transaction();
+    txn.clear();
+    drop(txn);
+    assert_eq!(take(&log), []);
+    assert_pending!(sub);
+}
@@ -0,0 +1,15 @@
+use eyeball_im::{
+    im_compat::{vector_from_im, vector_into_im},
+    ObservableVector,
+};
+use imbl::vector;
+
+#[test]
+fn conversions() {
+    let values = im::vector![1, 2, 3];
+    assert_eq!(vector_from_im(values.clone()), vector![1, 2, 3]);
+    assert_eq!(vector_into_im(vector![1, 2, 3]), values);
+
+    let ob = ObservableVector::from(values);
+    assert_eq!(*ob, vector![1, 2, 3]);
+}
@@ -0,0 +1,108 @@
+use imbl::vector;
+use stream_assert::{assert_closed, assert_next_eq, assert_pending};
+
+use eyeball_im::{KeyedVectorDiff, ObservableKeyedVector, ResetReason, VectorDiff};
+
+#[test]
+fn by_key() {
+    let mut ob = ObservableKeyedVector::new();
+    let mut st = ob.subscribe().into_stream();
+
+    ob.push_back("a", 1);
+    assert_next_eq!(st, KeyedVectorDiff::Insert { index: 0, key: "a", value: 1 });
+    ob.push_front("b", 2);
+    assert_next_eq!(st, KeyedVectorDiff::Insert { index: 0, key: "b", value: 2 });
+    ob.insert(1, "c", 3);
+    assert_next_eq!(st, KeyedVectorDiff::Insert { index: 1, key: "c", value: 3 });
+    assert_eq!(*ob, vector![("b", 2), ("c", 3), ("a", 1)]);
+
+    assert_eq!(ob.index_of("a"), Some(2));
+    assert_eq!(ob.get_by_key("c"), Some(&3));
+
+    assert_eq!(ob.set_by_key("a", 10), Some(1));
+    assert_next_eq!(st, KeyedVectorDiff::Set { index: 2, key: "a", value: 10 });
+    assert_eq!(ob.remove_by_key("b"), Some(2));
+    assert_next_eq!(st, KeyedVectorDiff::Remove { index: 0, key: "b" });
+    assert_eq!(*ob, vector![("c", 3), ("a", 10)]);
+
+    // Unknown keys don't notify subscribers.
+    assert_eq!(ob.set_by_key("b", 20), None);
+    assert_eq!(ob.remove_by_key("b"), None);
+    assert!(!ob.contains_key("b"));
+    assert_pending!(st);
+
+    // Removed keys can be used again.
+    ob.push_back("b", 20);
+    assert_next_eq!(st, KeyedVectorDiff::Insert { index: 2, key: "b", value: 20 });
+
+    drop(ob);
+    assert_closed!(st);
+}
+
+#[test]
+fn move_clear() {
+    let mut ob = ObservableKeyedVector::from_iter([("a", 1), ("b", 2), ("c", 3)]);
+    let (mut values, mut st) = ob.subscribe().into_values_and_stream();
+
+    ob.move_item(0, 2);
+    assert_eq!(*ob, vector![("b", 2), ("c", 3), ("a", 1)]);
+    ob.move_item(1, 1);
+    assert_eq!(ob.set(0, 4), 2);
+    assert_eq!(ob.remove(1), ("c", 3));
+
+    let diffs = [
+        KeyedVectorDiff::Move { from: 0, to: 2, key: "a" },
+        KeyedVectorDiff::Set { index: 0, key: "b", value: 4 },
+        KeyedVectorDiff::Remove { index: 1, key: "c" },
+    ];
+    for diff in diffs {
+        assert_next_eq!(st, diff.clone());
+        diff.apply(&mut values);
+    }
+    assert_pending!(st);
+    assert_eq!(values, *ob);
+
+    ob.clear();
+    assert_next_eq!(st, KeyedVectorDiff::Clear);
+    ob.clear();
+    assert_pending!(st);
+
+    ob.push_back("a", 5);
+    assert_next_eq!(st, KeyedVectorDiff::Insert { index: 0, key: "a", value: 5 });
+}
+
+#[test]
+#[should_panic]
+fn duplicate_key() {
+    let mut ob = ObservableKeyedVector::new();
+    ob.push_back("a", 1);
+    ob.push_front("a", 2);
+}
+
+#[test]
+fn lag() {
+    let mut ob = ObservableKeyedVector::with_capacity(1);
+    let mut st = ob.subscribe().into_batched_stream();
+
+    ob.push_back("a", 1);
+    ob.push_back("b", 2);
+    assert_next_eq!(st, vec![KeyedVectorDiff::Reset { values: vector![("a", 1), ("b", 2)] }]);
+
+    ob.remove_by_key("a");
+    assert_next_eq!(st, vec![KeyedVectorDiff::Remove { index: 0, key: "a" }]);
+    assert_pending!(st);
+}
+
+#[test]
+fn map_and_convert() {
+    let diff = KeyedVectorDiff::Set { index: 1, key: "a", value: 1 }.map(|v| v * 10);
+    assert_eq!(diff.key(), Some(&"a"));
+    assert_eq!(diff.into_vector_diff(), VectorDiff::Set { index: 1, value: ("a", 10) });
+
+    let diff = KeyedVectorDiff::<&str, i32>::Reset { values: vector![("a", 1)] };
+    assert_eq!(diff.key(), None);
+    assert_eq!(
+        diff.into_vector_diff(),
+        VectorDiff::Reset { values: vector![("a", 1)], reason: ResetReason::Lagged }
+    );
+}
@@ -1,15 +1,42 @@
 #![allow(missing_docs)]
+#![cfg_attr(feature = "nightly", feature(async_iterator))]
 
+use std::{
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll, Wake, Waker},
+};
+
+use futures_core::Stream;
 use imbl::{vector, Vector};
 use stream_assert::{assert_closed, assert_next_eq, assert_pending};
 
-use eyeball_im::{ObservableVector, ObservableVectorEntry, VectorDiff};
+use eyeball_im::{ObservableVector, ObservableVectorEntry, ResetReason, VectorDiff};
 
 mod apply;
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
 mod batch;
+mod compact;
+mod cursor;
+mod diff_map;
 mod entry;
+mod hooks;
+#[cfg(feature = "im")]
+mod im_compat;
+mod keyed_vector;
+mod map;
+#[cfg(feature = "nightly")]
+mod nightly;
+mod range;
+mod send_sync;
 #[cfg(feature = "serde")]
 mod serde;
+mod set;
+mod subscriber_map;
+#[cfg(feature = "update-seq")]
+mod update_seq;
+mod vec_deque;
 
 #[test]
 fn lag() {
@@ -24,7 +51,10 @@ fn lag() {
     assert_next_eq!(rx1, VectorDiff::PushBack { value: "world".to_owned() });
     assert_next_eq!(
         rx2,
-        VectorDiff::Reset { values: Vector::from_iter(["hello".to_owned(), "world".to_owned()]) }
+        VectorDiff::Reset {
+            values: Vector::from_iter(["hello".to_owned(), "world".to_owned()]),
+            reason: ResetReason::Lagged
+        }
     );
 }
 
@@ -39,7 +69,10 @@ fn lag2() {
 
     // Reset takes us immediately to the latest state, no updates afterwards
     // without modifying the vector again.
-    assert_next_eq!(sub, VectorDiff::Reset { values: vector![0, 1, 2, 3] });
+    assert_next_eq!(
+        sub,
+        VectorDiff::Reset { values: vector![0, 1, 2, 3], reason: ResetReason::Lagged }
+    );
     assert_pending!(sub);
 }
 
@@ -81,6 +114,245 @@ fn clear() {
     assert_closed!(sub);
 }
 
+#[test]
+fn move_item() {
+    let mut ob: ObservableVector<i32> = ObservableVector::from(vector![1, 2, 3, 4]);
+    let mut sub = ob.subscribe().into_stream();
+
+    ob.move_item(0, 2);
+    assert_next_eq!(sub, VectorDiff::Move { from: 0, to: 2 });
+    assert_eq!(*ob, vector![2, 3, 1, 4]);
+
+    ob.move_item(3, 0);
+    assert_next_eq!(sub, VectorDiff::Move { from: 3, to: 0 });
+    assert_eq!(*ob, vector![4, 2, 3, 1]);
+
+    ob.move_item(1, 1);
+    assert_pending!(sub);
+
+    let mut values = vector![1, 2, 3, 4];
+    VectorDiff::Move { from: 0, to: 2 }.apply(&mut values);
+    assert_eq!(values, vector![2, 3, 1, 4]);
+}
+
+#[test]
+fn swap_remove() {
+    let mut ob: ObservableVector<i32> = ObservableVector::from(vector![1, 2, 3, 4]);
+    let mut sub = ob.subscribe().into_stream();
+    let mut batched = ob.subscribe().into_batched_stream();
+
+    assert_eq!(ob.swap_remove(1), 2);
+    assert_eq!(*ob, vector![1, 4, 3]);
+    assert_next_eq!(sub, VectorDiff::Set { index: 1, value: 4 });
+    assert_next_eq!(sub, VectorDiff::PopBack);
+    assert_next_eq!(batched, vec![VectorDiff::Set { index: 1, value: 4 }, VectorDiff::PopBack]);
+
+    assert_eq!(ob.swap_remove(2), 3);
+    assert_eq!(*ob, vector![1, 4]);
+    assert_next_eq!(sub, VectorDiff::PopBack);
+    assert_next_eq!(batched, vec![VectorDiff::PopBack]);
+
+    let mut txn = ob.transaction();
+    assert_eq!(txn.swap_remove(0), 1);
+    txn.commit();
+    assert_eq!(*ob, vector![4]);
+    assert_next_eq!(sub, VectorDiff::Set { index: 0, value: 4 });
+    assert_next_eq!(sub, VectorDiff::PopBack);
+    assert_pending!(sub);
+}
+
+#[test]
+fn drain() {
+    let mut ob: ObservableVector<i32> = ObservableVector::from((0..10).collect::<Vector<_>>());
+    let mut sub = ob.subscribe().into_stream();
+    let mut batched = ob.subscribe().into_batched_stream();
+
+    assert!(ob.drain(3..3).is_empty());
+    assert_pending!(sub);
+
+    assert_eq!(ob.drain(1..3), vec![1, 2]);
+    assert_eq!(*ob, vector![0, 3, 4, 5, 6, 7, 8, 9]);
+    assert_next_eq!(sub, VectorDiff::Remove { index: 1 });
+    assert_next_eq!(sub, VectorDiff::Remove { index: 1 });
+    assert_next_eq!(
+        batched,
+        vec![VectorDiff::Remove { index: 1 }, VectorDiff::Remove { index: 1 }]
+    );
+
+    assert_eq!(ob.drain(6..), vec![8, 9]);
+    assert_next_eq!(sub, VectorDiff::Truncate { length: 6 });
+    assert_next_eq!(batched, vec![VectorDiff::Truncate { length: 6 }]);
+
+    assert_eq!(ob.drain(..=3), vec![0, 3, 4, 5]);
+    assert_eq!(*ob, vector![6, 7]);
+    assert_next_eq!(sub, VectorDiff::Reset { values: vector![6, 7], reason: ResetReason::Update });
+    assert_next_eq!(
+        batched,
+        vec![VectorDiff::Reset { values: vector![6, 7], reason: ResetReason::Update }]
+    );
+
+    let mut txn = ob.transaction();
+    txn.push_back(8);
+    assert_eq!(txn.drain(..), vec![6, 7, 8]);
+    txn.commit();
+    assert!(ob.is_empty());
+    assert_next_eq!(sub, VectorDiff::Clear);
+    assert_pending!(sub);
+}
+
+#[test]
+#[should_panic]
+fn drain_out_of_bounds() {
+    let mut ob: ObservableVector<i32> = ObservableVector::from(vector![1, 2, 3]);
+    ob.drain(1..4);
+}
+
+#[test]
+fn splice() {
+    let mut ob: ObservableVector<i32> = ObservableVector::from(vector![0, 1, 2, 3, 4, 5]);
+    let mut sub = ob.subscribe().into_batched_stream();
+
+    // Same length: only sets
+    assert_eq!(ob.splice(1..3, vector![10, 20]), vec![1, 2]);
+    assert_next_eq!(
+        sub,
+        vec![VectorDiff::Set { index: 1, value: 10 }, VectorDiff::Set { index: 2, value: 20 }]
+    );
+
+    // Longer replacement in the middle: sets and inserts
+    assert_eq!(ob.splice(3..4, vector![30, 31]), vec![3]);
+    assert_eq!(*ob, vector![0, 10, 20, 30, 31, 4, 5]);
+    assert_next_eq!(
+        sub,
+        vec![VectorDiff::Set { index: 3, value: 30 }, VectorDiff::Insert { index: 4, value: 31 }]
+    );
+
+    // Longer replacement at the end: sets and an append
+    assert!(ob.splice(7.., vector![6, 7]).is_empty());
+    assert_next_eq!(sub, vec![VectorDiff::Append { values: vector![6, 7] }]);
+
+    // Shorter replacement: sets and removes
+    assert_eq!(ob.splice(1..4, vector![1]), vec![10, 20, 30]);
+    assert_eq!(*ob, vector![0, 1, 31, 4, 5, 6, 7]);
+    assert_next_eq!(
+        sub,
+        vec![
+            VectorDiff::Set { index: 1, value: 1 },
+            VectorDiff::Remove { index: 2 },
+            VectorDiff::Remove { index: 2 },
+        ]
+    );
+
+    // Whole vector
+    assert_eq!(ob.splice(.., vector![1, 2]), vec![0, 1, 31, 4, 5, 6, 7]);
+    assert_next_eq!(
+        sub,
+        vec![VectorDiff::Reset { values: vector![1, 2], reason: ResetReason::Update }]
+    );
+
+    ob.splice(1..1, Vector::new());
+    assert_pending!(sub);
+}
+
+#[test]
+fn splice_diffs_apply() {
+    let initial: Vector<i32> = (0..8).collect();
+    for start in 0..=8 {
+        for end in start..=8 {
+            for replacement_len in 0..4 {
+                let mut ob = ObservableVector::from(initial.clone());
+                let mut sub = ob.subscribe().into_batched_stream();
+                let replacement = (100..100 + replacement_len).collect();
+                ob.splice(start..end, replacement);
+
+                let mut values = initial.clone();
+                if let Poll::Ready(Some(diffs)) = poll_next_now(&mut sub) {
+                    diffs.into_iter().for_each(|diff| diff.apply(&mut values));
+                }
+                assert_eq!(values, *ob, "splice({start}..{end}, len = {replacement_len})");
+            }
+        }
+    }
+}
+
+#[test]
+fn sort() {
+    let mut ob: ObservableVector<i32> = ObservableVector::from(vector![1, 2, 6, 3, 4, 5, 0]);
+    let mut sub = ob.subscribe().into_batched_stream();
+
+    ob.sort();
+    assert_eq!(*ob, vector![0, 1, 2, 3, 4, 5, 6]);
+    assert_next_eq!(
+        sub,
+        vec![VectorDiff::Move { from: 6, to: 0 }, VectorDiff::Move { from: 3, to: 6 }]
+    );
+
+    ob.sort();
+    assert_pending!(sub);
+
+    ob.sort_by(|a, b| b.cmp(a));
+    assert_next_eq!(
+        sub,
+        vec![VectorDiff::Reset {
+            values: vector![6, 5, 4, 3, 2, 1, 0],
+            reason: ResetReason::Update
+        }]
+    );
+
+    // Stable
+    ob.sort_by_key(|v| v % 2);
+    assert_eq!(*ob, vector![6, 4, 2, 0, 5, 3, 1]);
+
+    let mut txn = ob.transaction();
+    txn.push_back(-1);
+    txn.sort();
+    txn.commit();
+    assert_eq!(*ob, vector![-1, 0, 1, 2, 3, 4, 5, 6]);
+}
+
+#[test]
+fn sort_diffs_apply() {
+    // All permutations of 0..6
+    let mut permutations = vec![Vector::new()];
+    for value in 0..6 {
+        permutations = permutations
+            .into_iter()
+            .flat_map(|perm: Vector<i32>| {
+                (0..=perm.len()).map(move |index| {
+                    let mut perm = perm.clone();
+                    perm.insert(index, value);
+                    perm
+                })
+            })
+            .collect();
+    }
+
+    for perm in permutations {
+        let mut ob = ObservableVector::from(perm.clone());
+        let mut sub = ob.subscribe().into_batched_stream();
+        ob.sort();
+
+        let mut values = perm.clone();
+        if let Poll::Ready(Some(diffs)) = poll_next_now(&mut sub) {
+            let moves = diffs.iter().filter(|d| matches!(d, VectorDiff::Move { .. })).count();
+            assert!(moves <= 3, "too many moves for {perm:?}: {diffs:?}");
+            diffs.into_iter().for_each(|diff| diff.apply(&mut values));
+        }
+        assert_eq!(values, *ob, "sort({perm:?})");
+    }
+}
+
+fn poll_next_now<S: Stream + Unpin>(stream: &mut S) -> Poll<Option<S::Item>> {
+    struct NoopWaker;
+
+    impl Wake for NoopWaker {
+        fn wake(self: Arc<Self>) {}
+    }
+
+    let waker = Waker::from(Arc::new(NoopWaker));
+    Pin::new(stream).poll_next(&mut Context::from_waker(&waker))
+}
+
 #[test]
 fn for_each() {
     let mut ob: ObservableVector<i32> = ObservableVector::from(vector![0, 10, 1, 2, 4, 33, 5]);
@@ -161,3 +433,151 @@ fn transaction_no_subscribers() {
 
     assert_eq!(*ob, vector![45, 123]);
 }
+
+#[test]
+fn set_range() {
+    let mut ob: ObservableVector<i32> = ObservableVector::from(vector![0, 1, 2, 3, 4]);
+    let mut sub = ob.subscribe().into_batched_stream();
+
+    assert_eq!(ob.set_range(1, vector![10, 20]), vec![1, 2]);
+    assert_eq!(*ob, vector![0, 10, 20, 3, 4]);
+    assert_next_eq!(
+        sub,
+        vec![VectorDiff::Set { index: 1, value: 10 }, VectorDiff::Set { index: 2, value: 20 }]
+    );
+
+    // Replacing the whole vector is still broadcast as sets.
+    ob.set_range(0, vector![5, 6, 7, 8, 9]);
+    assert_next_eq!(
+        sub,
+        (0..5).map(|i| VectorDiff::Set { index: i, value: i as i32 + 5 }).collect::<Vec<_>>()
+    );
+
+    assert!(ob.set_range(5, Vector::new()).is_empty());
+    assert_pending!(sub);
+}
+
+#[test]
+#[should_panic]
+fn set_range_out_of_bounds() {
+    let mut ob: ObservableVector<i32> = ObservableVector::from(vector![1, 2, 3]);
+    ob.set_range(2, vector![4, 5]);
+}
+
+#[test]
+fn update_range() {
+    let mut ob: ObservableVector<i32> = ObservableVector::from(vector![0, 1, 2, 3, 4]);
+    let mut sub = ob.subscribe().into_stream();
+    let mut batched = ob.subscribe().into_batched_stream();
+
+    ob.update_range(3.., |value| *value *= 10);
+    assert_eq!(*ob, vector![0, 1, 2, 30, 40]);
+    assert_next_eq!(sub, VectorDiff::Set { index: 3, value: 30 });
+    assert_next_eq!(sub, VectorDiff::Set { index: 4, value: 40 });
+    assert_next_eq!(
+        batched,
+        vec![VectorDiff::Set { index: 3, value: 30 }, VectorDiff::Set { index: 4, value: 40 }]
+    );
+
+    ob.update_range(1..1, |value| *value += 1);
+    assert_pending!(sub);
+    assert_pending!(batched);
+}
+
+#[test]
+fn max_len() {
+    let mut ob = ObservableVector::from(vector![1, 2, 3]).with_max_len(2);
+    assert_eq!(*ob, vector![2, 3]);
+    assert_eq!(ob.max_len(), Some(2));
+
+    let (mut values, mut sub) = ob.subscribe().into_values_and_batched_stream();
+    let mut check = |diffs: Vec<VectorDiff<i32>>, ob: &ObservableVector<i32>| {
+        assert_next_eq!(sub, diffs.clone());
+        diffs.into_iter().for_each(|diff| diff.apply(&mut values));
+        assert_eq!(values, **ob);
+    };
+
+    ob.push_back(4);
+    check(vec![VectorDiff::PushBack { value: 4 }, VectorDiff::PopFront], &ob);
+    ob.push_front(1);
+    check(vec![VectorDiff::PushFront { value: 1 }, VectorDiff::PopBack], &ob);
+    ob.insert(1, 5);
+    check(vec![VectorDiff::Insert { index: 1, value: 5 }, VectorDiff::PopFront], &ob);
+    assert_eq!(*ob, vector![5, 3]);
+
+    ob.append(vector![6, 7, 8]);
+    check(
+        vec![
+            VectorDiff::Append { values: vector![6, 7, 8] },
+            VectorDiff::Reset { values: vector![7, 8], reason: ResetReason::Update },
+        ],
+        &ob,
+    );
+
+    // Updates that don't exceed the maximum aren't affected.
+    ob.pop_back();
+    check(vec![VectorDiff::PopBack], &ob);
+    ob.push_back(9);
+    check(vec![VectorDiff::PushBack { value: 9 }], &ob);
+
+    ob.splice(1..1, vector![10]);
+    check(vec![VectorDiff::Insert { index: 1, value: 10 }, VectorDiff::PopFront], &ob);
+
+    // Elements added at the front evict from the back.
+    ob.insert(0, 11);
+    check(vec![VectorDiff::Insert { index: 0, value: 11 }, VectorDiff::PopBack], &ob);
+    ob.splice(0..0, vector![12]);
+    check(vec![VectorDiff::Insert { index: 0, value: 12 }, VectorDiff::PopBack], &ob);
+    assert_eq!(*ob, vector![12, 11]);
+
+    let mut txn = ob.transaction();
+    txn.push_back(11);
+    txn.push_back(12);
+    txn.commit();
+    assert_eq!(*ob, vector![11, 12]);
+    check(
+        vec![
+            VectorDiff::PushBack { value: 11 },
+            VectorDiff::PushBack { value: 12 },
+            VectorDiff::PopFront,
+            VectorDiff::PopFront,
+        ],
+        &ob,
+    );
+}
+
+#[test]
+fn subscribe_len() {
+    let mut ob = ObservableVector::from(vector![1, 2]);
+    let mut len = ob.subscribe_len();
+    assert_eq!(len.get(), 2);
+
+    ob.push_back(3);
+    assert_next_eq!(len, 3);
+
+    // Updates that don't change the length aren't sent.
+    ob.set(0, 10);
+    ob.sort();
+    assert_pending!(len);
+
+    // Only the latest length is yielded.
+    ob.pop_front();
+    ob.clear();
+    assert_next_eq!(len, 0);
+    assert_pending!(len);
+
+    // Changes that end up with the same length aren't yielded either.
+    ob.push_back(1);
+    ob.pop_back();
+    assert_pending!(len);
+
+    let mut txn = ob.transaction();
+    txn.push_back(4);
+    txn.push_back(5);
+    txn.commit();
+    assert_next_eq!(len, 2);
+    assert_eq!(len.get(), 2);
+
+    drop(ob);
+    assert_closed!(len);
+}
@@ -0,0 +1,90 @@
+use imbl::hashmap;
+use stream_assert::{assert_closed, assert_next_eq, assert_pending};
+
+use eyeball_im::{MapDiff, ObservableMap};
+
+#[test]
+fn insert_update_remove() {
+    let mut ob = ObservableMap::new();
+    let mut st = ob.subscribe().into_stream();
+
+    assert_eq!(ob.insert("a", 1), None);
+    assert_next_eq!(st, MapDiff::Insert { key: "a", value: 1 });
+
+    assert_eq!(ob.insert("a", 2), Some(1));
+    assert_next_eq!(st, MapDiff::Update { key: "a", value: 2 });
+
+    assert_eq!(ob.remove("b"), None);
+    assert_pending!(st);
+
+    assert_eq!(ob.remove("a"), Some(2));
+    assert_next_eq!(st, MapDiff::Remove { key: "a" });
+    assert!(ob.is_empty());
+
+    ob.clear();
+    assert_pending!(st);
+
+    ob.insert("c", 3);
+    ob.clear();
+    assert_next_eq!(st, MapDiff::Insert { key: "c", value: 3 });
+    assert_next_eq!(st, MapDiff::Clear);
+
+    drop(ob);
+    assert_closed!(st);
+}
+
+#[test]
+fn lag() {
+    let mut ob = ObservableMap::with_capacity(1);
+    let mut st = ob.subscribe().into_stream();
+
+    ob.insert(1, 'a');
+    ob.insert(2, 'b');
+    assert_next_eq!(st, MapDiff::Reset { values: hashmap! { 1 => 'a', 2 => 'b' } });
+    assert_pending!(st);
+}
+
+#[test]
+fn transaction() {
+    let mut ob = ObservableMap::from(hashmap! { 1 => 'a' });
+    let (values, mut st) = ob.subscribe().into_values_and_batched_stream();
+    assert_eq!(values, hashmap! { 1 => 'a' });
+
+    let mut txn = ob.transaction();
+    txn.insert(2, 'b');
+    txn.insert(1, 'c');
+    assert_eq!(txn.get(&1), Some(&'c'));
+    assert_eq!(txn.len(), 2);
+    txn.commit();
+
+    assert_next_eq!(
+        st,
+        vec![MapDiff::Insert { key: 2, value: 'b' }, MapDiff::Update { key: 1, value: 'c' }]
+    );
+
+    let mut txn = ob.transaction();
+    txn.remove(&2);
+    txn.clear();
+    txn.insert(3, 'd');
+    txn.commit();
+    assert_next_eq!(st, vec![MapDiff::Clear, MapDiff::Insert { key: 3, value: 'd' }]);
+
+    let mut txn = ob.transaction();
+    txn.remove(&3);
+    txn.rollback();
+    txn.commit();
+    assert_pending!(st);
+    assert_eq!(*ob, hashmap! { 3 => 'd' });
+}
+
+#[test]
+fn map_and_apply() {
+    let mut map = hashmap! { "a" => 10, "b" => 20 };
+    MapDiff::Update { key: "a", value: 3 }.map(|v| v * 10).apply(&mut map);
+    MapDiff::Remove { key: "b" }.apply(&mut map);
+    MapDiff::Insert { key: "c", value: 5 }.map(|v| v * 10).apply(&mut map);
+    assert_eq!(map, hashmap! { "a" => 30, "c" => 50 });
+
+    MapDiff::Reset { values: hashmap! { "d" => 1 } }.apply(&mut map);
+    assert_eq!(map, hashmap! { "d" => 1 });
+}
@@ -0,0 +1,34 @@
+use std::{
+    async_iter::AsyncIterator,
+    pin::Pin,
+    task::{Context, Poll, Waker},
+};
+
+use imbl::vector;
+
+use eyeball_im::{ObservableVector, VectorDiff};
+
+#[test]
+fn async_iterator() {
+    let mut ob = ObservableVector::new();
+    let mut sub = ob.subscribe().into_stream();
+    let mut batched = ob.subscribe().into_batched_stream();
+    let mut cx = Context::from_waker(Waker::noop());
+
+    assert_eq!(Pin::new(&mut sub).poll_next(&mut cx), Poll::Pending);
+
+    ob.append(vector![1, 2]);
+    ob.pop_front();
+    assert_eq!(
+        Pin::new(&mut sub).poll_next(&mut cx),
+        Poll::Ready(Some(VectorDiff::Append { values: vector![1, 2] }))
+    );
+    assert_eq!(Pin::new(&mut sub).poll_next(&mut cx), Poll::Ready(Some(VectorDiff::PopFront)));
+    assert_eq!(
+        Pin::new(&mut batched).poll_next(&mut cx),
+        Poll::Ready(Some(vec![VectorDiff::Append { values: vector![1, 2] }, VectorDiff::PopFront]))
+    );
+
+    drop(ob);
+    assert_eq!(Pin::new(&mut sub).poll_next(&mut cx), Poll::Ready(None));
+}
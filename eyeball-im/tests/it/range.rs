@@ -0,0 +1,119 @@
+use std::task::Poll;
+
+use imbl::{vector, Vector};
+use stream_assert::{assert_closed, assert_next_eq, assert_pending};
+
+use eyeball_im::{ObservableVector, VectorDiff};
+
+use super::poll_next_now;
+
+#[test]
+fn rebased_diffs() {
+    let mut ob: ObservableVector<u32> = ObservableVector::from(vector![0, 1, 2, 3, 4, 5]);
+    let sub = ob.subscribe_range(2..4);
+    assert_eq!(sub.values(), vector![2, 3]);
+    let mut sub = sub.into_stream();
+
+    ob.set(3, 30);
+    assert_next_eq!(sub, VectorDiff::Set { index: 1, value: 30 });
+
+    // Insertion inside of the range pushes the last element out of it.
+    ob.insert(3, 20);
+    assert_next_eq!(sub, VectorDiff::PopBack);
+    assert_next_eq!(sub, VectorDiff::PushBack { value: 20 });
+
+    // Removal before the range pulls the next element into it.
+    ob.pop_front();
+    assert_next_eq!(sub, VectorDiff::PopFront);
+    assert_next_eq!(sub, VectorDiff::PushBack { value: 30 });
+    assert_pending!(sub);
+
+    drop(ob);
+    assert_closed!(sub);
+}
+
+#[test]
+fn no_wakeups_outside_of_range() {
+    let mut ob: ObservableVector<u32> = ObservableVector::from(vector![0, 1, 2, 3]);
+    let mut sub = ob.subscribe_range(0..2).into_stream();
+
+    ob.set(2, 20);
+    ob.push_back(4);
+    ob.remove(3);
+    ob.truncate(2);
+    assert_pending!(sub);
+
+    ob.truncate(1);
+    assert_next_eq!(sub, VectorDiff::Truncate { length: 1 });
+    ob.append(vector![5, 6]);
+    assert_next_eq!(sub, VectorDiff::Append { values: vector![5] });
+    ob.clear();
+    assert_next_eq!(sub, VectorDiff::Clear);
+    assert_pending!(sub);
+}
+
+#[test]
+fn transaction() {
+    let mut ob: ObservableVector<u32> = ObservableVector::from(vector![0, 1, 2, 3]);
+    let mut sub = ob.subscribe_range(1..3).into_batched_stream();
+
+    let mut txn = ob.transaction();
+    txn.push_back(4);
+    txn.set(1, 10);
+    txn.move_item(3, 0);
+    txn.commit();
+    assert_next_eq!(
+        sub,
+        vec![
+            VectorDiff::Set { index: 0, value: 10 },
+            VectorDiff::PopBack,
+            VectorDiff::PushFront { value: 0 },
+        ]
+    );
+    assert_pending!(sub);
+}
+
+#[test]
+fn matches_range_of_values() {
+    let mut ob: ObservableVector<u32> = ObservableVector::from((0..8).collect::<Vector<_>>());
+    let ranges = [0..3, 2..5, 5..9, 4..4];
+    let mut subs: Vec<_> = ranges
+        .iter()
+        .map(|range| {
+            let (values, stream) = ob.subscribe_range(range.clone()).into_values_and_stream();
+            (range.clone(), values, stream)
+        })
+        .collect();
+
+    let mut check = |ob: &ObservableVector<u32>| {
+        for (range, values, stream) in &mut subs {
+            while let Poll::Ready(Some(diff)) = poll_next_now(stream) {
+                diff.apply(values);
+            }
+            let expected: Vector<_> =
+                ob.iter().take(range.end).skip(range.start).copied().collect();
+            assert_eq!(*values, expected, "range {range:?}");
+        }
+    };
+
+    ob.push_front(10);
+    check(&ob);
+    ob.insert(5, 11);
+    check(&ob);
+    ob.remove(2);
+    check(&ob);
+    ob.move_item(0, 7);
+    check(&ob);
+    ob.move_item(6, 3);
+    check(&ob);
+    ob.pop_back();
+    check(&ob);
+    ob.truncate(4);
+    check(&ob);
+    ob.append(vector![12, 13, 14, 15]);
+    check(&ob);
+    ob.pop_front();
+    check(&ob);
+    ob.set(4, 16);
+    check(&ob);
+}
@@ -0,0 +1,29 @@
+//! Compile-time checks that the public types can be shared across threads
+//! whenever the values they hold can.
+
+use eyeball_im::{
+    KeyedVectorSubscriberStream, ObservableKeyedVector, ObservableMap, ObservableSet,
+    ObservableVecDeque, ObservableVector, VectorDiff, VectorLenSubscriber, VectorSubscriber,
+    VectorSubscriberBatchedStream, VectorSubscriberStream,
+};
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn vector() {
+    assert_send_sync::<ObservableVector<String>>();
+    assert_send_sync::<VectorSubscriber<String>>();
+    assert_send_sync::<VectorSubscriberStream<String>>();
+    assert_send_sync::<VectorSubscriberBatchedStream<String>>();
+    assert_send_sync::<VectorLenSubscriber>();
+    assert_send_sync::<VectorDiff<String>>();
+}
+
+#[test]
+fn other_collections() {
+    assert_send_sync::<ObservableMap<u32, String>>();
+    assert_send_sync::<ObservableSet<String>>();
+    assert_send_sync::<ObservableVecDeque<String>>();
+    assert_send_sync::<ObservableKeyedVector<u32, String>>();
+    assert_send_sync::<KeyedVectorSubscriberStream<u32, String>>();
+}
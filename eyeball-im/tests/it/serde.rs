@@ -1,4 +1,8 @@
-use eyeball_im::VectorDiff;
+use std::task::Poll;
+
+use eyeball_im::{
+    KeyedVectorDiff, MapDiff, ObservableVector, ResetReason, VecDequeDiff, VectorDiff,
+};
 use imbl::vector;
 
 macro_rules! test {
@@ -10,6 +14,9 @@ macro_rules! test {
 
             assert_eq!(json, $json);
 
+            let deserialized: VectorDiff<char> = serde_json::from_str(&json)?;
+            assert_eq!(deserialized, vector_diff);
+
             Ok(())
         }
     };
@@ -25,4 +32,93 @@ test!(insert: VectorDiff::Insert { index: 42, value: 'a' } => r#"{"Insert":{"ind
 test!(set: VectorDiff::Set { index: 42, value: 'a' } => r#"{"Set":{"index":42,"value":"a"}}"#);
 test!(remove: VectorDiff::Remove { index: 42 } => r#"{"Remove":{"index":42}}"#);
 test!(truncate: VectorDiff::Truncate { length: 3 } => r#"{"Truncate":{"length":3}}"#);
-test!(reset: VectorDiff::Reset { values: vector!['a', 'b'] } => r#"{"Reset":{"values":["a","b"]}}"#);
+test!(reset: VectorDiff::Reset { values: vector!['a', 'b'], reason: ResetReason::Update } => r#"{"Reset":{"values":["a","b"],"reason":"Update"}}"#);
+test!(lag_reset: VectorDiff::Reset { values: vector!['a'], reason: ResetReason::Lagged } => r#"{"Reset":{"values":["a"],"reason":"Lagged"}}"#);
+test!(move_: VectorDiff::Move { from: 1, to: 3 } => r#"{"Move":{"from":1,"to":3}}"#);
+
+#[test]
+fn map_diff() -> Result<(), Box<dyn std::error::Error>> {
+    let diff: MapDiff<&str, char> = MapDiff::Update { key: "k", value: 'a' };
+    assert_eq!(serde_json::to_string(&diff)?, r#"{"Update":{"key":"k","value":"a"}}"#);
+
+    let diff: MapDiff<&str, char> = MapDiff::Remove { key: "k" };
+    assert_eq!(serde_json::to_string(&diff)?, r#"{"Remove":{"key":"k"}}"#);
+
+    Ok(())
+}
+
+#[test]
+fn keyed_vector_diff() -> Result<(), Box<dyn std::error::Error>> {
+    let diff: KeyedVectorDiff<&str, char> = KeyedVectorDiff::Set { index: 1, key: "k", value: 'a' };
+    assert_eq!(serde_json::to_string(&diff)?, r#"{"Set":{"index":1,"key":"k","value":"a"}}"#);
+
+    let diff: KeyedVectorDiff<&str, char> = KeyedVectorDiff::Move { from: 0, to: 2, key: "k" };
+    assert_eq!(serde_json::to_string(&diff)?, r#"{"Move":{"from":0,"to":2,"key":"k"}}"#);
+
+    Ok(())
+}
+
+#[test]
+fn deserialize_errors() {
+    let res = serde_json::from_str::<VectorDiff<char>>(r#"{"Frobnicate":{}}"#);
+    assert!(res.unwrap_err().to_string().starts_with("unknown variant `Frobnicate`"));
+
+    let res = serde_json::from_str::<VectorDiff<char>>(r#"{"Insert":{"index":1}}"#);
+    assert!(res.unwrap_err().to_string().starts_with("missing field `value`"));
+
+    let res = serde_json::from_str::<VectorDiff<char>>(r#"{"Remove":{"index":1,"index":2}}"#);
+    assert!(res.unwrap_err().to_string().starts_with("duplicate field `index`"));
+
+    // Unknown fields are ignored.
+    let diff: VectorDiff<char> =
+        serde_json::from_str(r#"{"Move":{"from":1,"to":3,"extra":[]}}"#).unwrap();
+    assert_eq!(diff, VectorDiff::Move { from: 1, to: 3 });
+}
+
+#[test]
+fn deserialize_reset_without_reason() -> Result<(), Box<dyn std::error::Error>> {
+    // Resets serialized before the reason was added were always sent because
+    // of lag.
+    let expected = VectorDiff::Reset { values: vector!['a'], reason: ResetReason::Lagged };
+
+    let diff: VectorDiff<char> = serde_json::from_str(r#"{"Reset":{"values":["a"]}}"#)?;
+    assert_eq!(diff, expected);
+
+    let diff: VectorDiff<char> = serde_json::from_str(r#"{"Reset":[["a"]]}"#)?;
+    assert_eq!(diff, expected);
+
+    Ok(())
+}
+
+#[test]
+fn replicate() -> Result<(), Box<dyn std::error::Error>> {
+    let mut ob = ObservableVector::<char>::new();
+    let mut sub = ob.subscribe().into_stream();
+    let mut replica = (*ob).clone();
+
+    ob.append(vector!['a', 'b', 'c']);
+    ob.insert(1, 'x');
+    ob.swap_remove(0);
+    ob.sort();
+
+    while let Poll::Ready(Some(diff)) = crate::poll_next_now(&mut sub) {
+        let json = serde_json::to_string(&diff)?;
+        let diff: VectorDiff<char> = serde_json::from_str(&json)?;
+        diff.apply(&mut replica);
+    }
+
+    assert_eq!(replica, *ob);
+
+    Ok(())
+}
+
+#[test]
+fn vec_deque_diff() -> Result<(), Box<dyn std::error::Error>> {
+    let diff: VecDequeDiff<char> = VecDequeDiff::RotateLeft { n: 2 };
+    assert_eq!(serde_json::to_string(&diff)?, r#"{"RotateLeft":{"n":2}}"#);
+
+    let diff: VecDequeDiff<char> = VecDequeDiff::Swap { a: 0, b: 1 };
+    assert_eq!(serde_json::to_string(&diff)?, r#"{"Swap":{"a":0,"b":1}}"#);
+
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use imbl::hashset;
+use stream_assert::{assert_closed, assert_next_eq, assert_pending};
+
+use eyeball_im::{ObservableSet, SetDiff};
+
+#[test]
+fn insert_remove() {
+    let mut ob = ObservableSet::new();
+    let mut st = ob.subscribe().into_stream();
+
+    assert!(ob.insert("alice"));
+    assert_next_eq!(st, SetDiff::Insert { value: "alice" });
+
+    assert!(!ob.insert("alice"));
+    assert_pending!(st);
+
+    assert_eq!(ob.remove("bob"), None);
+    assert_pending!(st);
+
+    assert_eq!(ob.remove("alice"), Some("alice"));
+    assert_next_eq!(st, SetDiff::Remove { value: "alice" });
+
+    ob.clear();
+    assert_pending!(st);
+
+    ob.insert("carol");
+    ob.clear();
+    assert_next_eq!(st, SetDiff::Insert { value: "carol" });
+    assert_next_eq!(st, SetDiff::Clear);
+
+    drop(ob);
+    assert_closed!(st);
+}
+
+#[test]
+fn lag() {
+    let mut ob = ObservableSet::with_capacity(1);
+    let mut st = ob.subscribe().into_stream();
+
+    ob.insert(1);
+    ob.insert(2);
+    assert_next_eq!(st, SetDiff::Reset { values: hashset![1, 2] });
+    assert_pending!(st);
+}
+
+#[test]
+fn transaction() {
+    let mut ob = ObservableSet::from(hashset![1]);
+    let (mut values, mut st) = ob.subscribe().into_values_and_batched_stream();
+
+    let mut txn = ob.transaction();
+    assert!(!txn.insert(1));
+    assert!(txn.insert(2));
+    assert_eq!(txn.remove(&1), Some(1));
+    assert_pending!(st);
+    txn.commit();
+
+    let diffs = vec![SetDiff::Insert { value: 2 }, SetDiff::Remove { value: 1 }];
+    assert_next_eq!(st, diffs.clone());
+    diffs.into_iter().for_each(|diff| diff.apply(&mut values));
+    assert_eq!(values, *ob);
+
+    let mut txn = ob.transaction();
+    txn.insert(3);
+    txn.rollback();
+    txn.commit();
+    assert_pending!(st);
+    assert_eq!(*ob, hashset![2]);
+}
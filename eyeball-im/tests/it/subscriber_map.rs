@@ -0,0 +1,64 @@
+use imbl::vector;
+use stream_assert::{assert_closed, assert_next_eq, assert_pending};
+
+use eyeball_im::{ObservableVector, ResetReason, VectorDiff};
+
+#[test]
+fn map() {
+    let mut ob = ObservableVector::from(vector![1, 2]);
+    let (values, mut sub) = ob.subscribe().map(|v| v * 10);
+    assert_eq!(values, vector![10, 20]);
+    assert_pending!(sub);
+
+    ob.push_back(3);
+    ob.set(0, 4);
+    ob.remove(1);
+    assert_next_eq!(sub, VectorDiff::PushBack { value: 30 });
+    assert_next_eq!(sub, VectorDiff::Set { index: 0, value: 40 });
+    assert_next_eq!(sub, VectorDiff::Remove { index: 1 });
+    assert_pending!(sub);
+
+    drop(ob);
+    assert_closed!(sub);
+}
+
+#[test]
+fn map_batched() {
+    let mut ob = ObservableVector::from(vector!['a']);
+    let (values, mut sub) = ob.subscribe().map_batched(|c| c.to_ascii_uppercase());
+    assert_eq!(values, vector!['A']);
+    assert_pending!(sub);
+
+    let mut txn = ob.transaction();
+    txn.push_front('b');
+    txn.append(vector!['c', 'd']);
+    txn.commit();
+    assert_next_eq!(
+        sub,
+        vec![
+            VectorDiff::PushFront { value: 'B' },
+            VectorDiff::Append { values: vector!['C', 'D'] }
+        ]
+    );
+
+    ob.splice(.., vector!['e']);
+    assert_next_eq!(
+        sub,
+        vec![VectorDiff::Reset { values: vector!['E'], reason: ResetReason::Update }]
+    );
+    assert_pending!(sub);
+}
+
+#[test]
+fn map_stateful() {
+    let mut ob = ObservableVector::from(vector!["a", "b"]);
+    let mut calls = 0;
+    let (values, mut sub) = ob.subscribe().map(|s| {
+        calls += 1;
+        (calls, s)
+    });
+    assert_eq!(values, vector![(1, "a"), (2, "b")]);
+
+    ob.push_back("c");
+    assert_next_eq!(sub, VectorDiff::PushBack { value: (3, "c") });
+}
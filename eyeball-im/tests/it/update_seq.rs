@@ -0,0 +1,100 @@
+use eyeball_im::{ObservableVector, UpdateSeq, VectorDiff};
+use stream_assert::assert_next_eq;
+
+#[test]
+fn stream() {
+    let mut ob = ObservableVector::<i32>::new();
+    let mut sub = ob.subscribe().into_stream();
+    assert_eq!(sub.last_update_seq(), None);
+
+    let before = UpdateSeq::next();
+    ob.push_back(1);
+    let scalar = eyeball::SharedObservable::new(0);
+    ob.push_back(2);
+
+    assert_next_eq!(sub, VectorDiff::PushBack { value: 1 });
+    let first = sub.last_update_seq().unwrap();
+    assert!(first > before);
+
+    assert_next_eq!(sub, VectorDiff::PushBack { value: 2 });
+    let second = sub.last_update_seq().unwrap();
+    assert!(second > scalar.subscribe().update_seq());
+    assert!(second > first);
+}
+
+#[test]
+fn batched_stream() {
+    let mut ob = ObservableVector::<i32>::new();
+    let mut sub = ob.subscribe().into_batched_stream();
+    let mut sub2 = ob.subscribe().into_stream();
+
+    ob.push_back(1);
+    ob.push_back(2);
+
+    assert_next_eq!(
+        sub,
+        vec![VectorDiff::PushBack { value: 1 }, VectorDiff::PushBack { value: 2 }]
+    );
+    assert_next_eq!(sub2, VectorDiff::PushBack { value: 1 });
+    assert!(sub.last_update_seq() > sub2.last_update_seq());
+    assert_next_eq!(sub2, VectorDiff::PushBack { value: 2 });
+    assert_eq!(sub.last_update_seq(), sub2.last_update_seq());
+}
+
+#[test]
+fn diffs_since() {
+    let ob = ObservableVector::<i32>::new();
+    assert_eq!(ob.last_update_seq(), None);
+    assert_eq!(ob.diffs_since(0), None);
+
+    let mut ob = ob.with_history(3);
+    let start = ob.last_update_seq().unwrap().get();
+    assert_eq!(ob.diffs_since(start), Some(vec![]));
+
+    ob.push_back(1);
+    let after_push = ob.last_update_seq().unwrap().get();
+    assert!(after_push > start);
+
+    // No-op updates aren't recorded.
+    ob.truncate(5);
+    assert_eq!(ob.last_update_seq().unwrap().get(), after_push);
+
+    let mut txn = ob.transaction();
+    txn.push_front(0);
+    txn.set(1, 10);
+    txn.commit();
+    assert_eq!(
+        ob.diffs_since(start),
+        Some(vec![
+            VectorDiff::PushBack { value: 1 },
+            VectorDiff::PushFront { value: 0 },
+            VectorDiff::Set { index: 1, value: 10 },
+        ])
+    );
+    assert_eq!(
+        ob.diffs_since(after_push),
+        Some(vec![VectorDiff::PushFront { value: 0 }, VectorDiff::Set { index: 1, value: 10 }])
+    );
+
+    // Unknown sequence numbers.
+    assert_eq!(ob.diffs_since(UpdateSeq::next().get()), None);
+    assert_eq!(ob.diffs_since(start + 1_000_000), None);
+
+    // Once the updates after `start` are no longer all retained, it can't be
+    // served anymore.
+    ob.pop_back();
+    ob.pop_back();
+    assert_eq!(ob.diffs_since(start), None);
+    assert_eq!(ob.diffs_since(after_push).unwrap().len(), 4);
+    assert!(ob.is_empty());
+}
+
+#[test]
+fn history_and_subscribers_agree() {
+    let mut ob = ObservableVector::<i32>::new().with_history(4);
+    let mut sub = ob.subscribe().into_stream();
+
+    ob.push_back(1);
+    assert_next_eq!(sub, VectorDiff::PushBack { value: 1 });
+    assert_eq!(sub.last_update_seq(), ob.last_update_seq());
+}
@@ -0,0 +1,125 @@
+use imbl::{vector, Vector};
+use stream_assert::{assert_closed, assert_next_eq, assert_pending};
+
+use eyeball_im::{ObservableVecDeque, VecDequeDiff};
+
+#[test]
+fn push_pop() {
+    let mut ob = ObservableVecDeque::new();
+    let mut st = ob.subscribe().into_stream();
+
+    ob.push_back(1);
+    assert_next_eq!(st, VecDequeDiff::PushBack { value: 1 });
+    ob.push_front(0);
+    assert_next_eq!(st, VecDequeDiff::PushFront { value: 0 });
+    assert_eq!(*ob, vector![0, 1]);
+
+    assert_eq!(ob.pop_front(), Some(0));
+    assert_next_eq!(st, VecDequeDiff::PopFront);
+    assert_eq!(ob.pop_back(), Some(1));
+    assert_next_eq!(st, VecDequeDiff::PopBack);
+
+    assert_eq!(ob.pop_front(), None);
+    assert_eq!(ob.pop_back(), None);
+    ob.clear();
+    assert_pending!(st);
+
+    drop(ob);
+    assert_closed!(st);
+}
+
+#[test]
+fn rotate_swap() {
+    let mut ob = ObservableVecDeque::from(vector![1, 2, 3, 4, 5]);
+    let (mut values, mut st) = ob.subscribe().into_values_and_stream();
+
+    ob.rotate_left(2);
+    assert_eq!(*ob, vector![3, 4, 5, 1, 2]);
+    ob.rotate_right(1);
+    assert_eq!(*ob, vector![2, 3, 4, 5, 1]);
+    ob.swap(0, 4);
+    assert_eq!(*ob, vector![1, 3, 4, 5, 2]);
+    assert_eq!(ob.set(1, 6), 3);
+
+    // No-op reorderings don't notify subscribers.
+    ob.rotate_left(0);
+    ob.rotate_right(5);
+    ob.swap(2, 2);
+
+    let diffs = [
+        VecDequeDiff::RotateLeft { n: 2 },
+        VecDequeDiff::RotateRight { n: 1 },
+        VecDequeDiff::Swap { a: 0, b: 4 },
+        VecDequeDiff::Set { index: 1, value: 6 },
+    ];
+    for diff in diffs {
+        assert_next_eq!(st, diff.clone());
+        diff.apply(&mut values);
+    }
+    assert_pending!(st);
+    assert_eq!(values, *ob);
+}
+
+#[test]
+#[should_panic]
+fn rotate_out_of_bounds() {
+    let mut ob = ObservableVecDeque::from(vector![1, 2]);
+    ob.rotate_left(3);
+}
+
+#[test]
+fn max_len() {
+    let mut ob = ObservableVecDeque::with_max_len(2);
+    let (mut values, mut st) = ob.subscribe().into_values_and_batched_stream();
+
+    ob.push_back(1);
+    ob.push_back(2);
+    assert!(ob.is_full());
+    ob.push_back(3);
+    assert_eq!(*ob, vector![2, 3]);
+    ob.push_front(0);
+    assert_eq!(*ob, vector![0, 2]);
+
+    let diffs = vec![
+        VecDequeDiff::PushBack { value: 1 },
+        VecDequeDiff::PushBack { value: 2 },
+        VecDequeDiff::PopFront,
+        VecDequeDiff::PushBack { value: 3 },
+        VecDequeDiff::PopBack,
+        VecDequeDiff::PushFront { value: 0 },
+    ];
+    assert_next_eq!(st, diffs.clone());
+    diffs.into_iter().for_each(|diff| diff.apply(&mut values));
+    assert_eq!(values, *ob);
+
+    ob.pop_back();
+    assert!(!ob.is_full());
+    assert_eq!(ob.max_len(), Some(2));
+    assert!(!ObservableVecDeque::<i32>::new().is_full());
+}
+
+#[test]
+fn lag() {
+    let mut ob = ObservableVecDeque::with_capacity(1);
+    let mut st = ob.subscribe().into_batched_stream();
+
+    ob.push_back(1);
+    ob.push_back(2);
+    assert_next_eq!(st, vec![VecDequeDiff::Reset { values: vector![1, 2] }]);
+
+    ob.push_front(0);
+    ob.rotate_left(1);
+    assert_next_eq!(st, vec![VecDequeDiff::Reset { values: vector![1, 2, 0] }]);
+
+    ob.pop_back();
+    assert_next_eq!(st, vec![VecDequeDiff::PopBack]);
+    assert_pending!(st);
+}
+
+#[test]
+fn map() {
+    let diff = VecDequeDiff::Reset { values: vector![1, 2] }.map(|v| v * 10);
+    let mut values = Vector::new();
+    diff.apply(&mut values);
+    assert_eq!(values, vector![10, 20]);
+}
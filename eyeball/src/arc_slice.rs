@@ -0,0 +1,42 @@
+//! Helpers for updating `Arc<str>` and `Arc<[u8]>` values without allocating
+//! where possible.
+
+use std::sync::Arc;
+
+/// Replace `current` with `value` unless they are equal, returning whether it
+/// was replaced.
+///
+/// If `current` is the only reference to its allocation and `value` has the
+/// same length, the allocation is reused.
+pub(crate) fn replace_str(current: &mut Arc<str>, value: &str) -> bool {
+    if **current == *value {
+        return false;
+    }
+
+    match Arc::get_mut(current) {
+        Some(s) if s.len() == value.len() => {
+            // SAFETY: The bytes are replaced by those of another `str` with
+            // the same length, so they remain valid UTF-8.
+            unsafe { s.as_bytes_mut() }.copy_from_slice(value.as_bytes());
+        }
+        _ => *current = value.into(),
+    }
+    true
+}
+
+/// Replace `current` with `value` unless they are equal, returning whether it
+/// was replaced.
+///
+/// If `current` is the only reference to its allocation and `value` has the
+/// same length, the allocation is reused.
+pub(crate) fn replace_bytes(current: &mut Arc<[u8]>, value: &[u8]) -> bool {
+    if **current == *value {
+        return false;
+    }
+
+    match Arc::get_mut(current) {
+        Some(bytes) if bytes.len() == value.len() => bytes.copy_from_slice(value),
+        _ => *current = value.into(),
+    }
+    true
+}
@@ -0,0 +1,239 @@
+//! Combinators for observing the latest values of multiple observables at
+//! once.
+//!
+//! ```
+//! use eyeball::{combine, Observable};
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() {
+//! let mut name = Observable::new("Alice".to_owned());
+//! let mut age = Observable::new(30);
+//!
+//! let mut combined = combine::latest2(Observable::subscribe(&name), Observable::subscribe(&age));
+//! assert_eq!(combined.get(), ("Alice".to_owned(), 30));
+//!
+//! Observable::set(&mut age, 31);
+//! assert_eq!(combined.next().await, Some(("Alice".to_owned(), 31)));
+//!
+//! // Updates of multiple observables that happen before the combined
+//! // subscriber is polled again result in a single item.
+//! Observable::set(&mut name, "Bob".to_owned());
+//! Observable::set(&mut age, 25);
+//! assert_eq!(combined.next().await, Some(("Bob".to_owned(), 25)));
+//! # }
+//! ```
+
+use std::{
+    fmt,
+    future::poll_fn,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures_core::Stream;
+
+use crate::Subscriber;
+
+macro_rules! latest_n {
+    (
+        $(#[$fn_attr:meta])*
+        fn $fn_name:ident -> $name:ident<$($ty:ident),+> { $($sub:ident: $idx:tt),+ }
+    ) => {
+        $(#[$fn_attr])*
+        pub fn $fn_name<$($ty: Clone),+>($($sub: Subscriber<$ty>),+) -> $name<$($ty),+> {
+            $name { subscribers: ($($sub,)+), closed: Default::default() }
+        }
+
+        #[doc = concat!("Combined subscriber created by [`", stringify!($fn_name), "`].")]
+        ///
+        /// See the [module documentation](self) for details.
+        #[must_use]
+        pub struct $name<$($ty),+> {
+            subscribers: ($(Subscriber<$ty>,)+),
+            closed: [bool; latest_n!(@count $($ty)+)],
+        }
+
+        impl<$($ty: Clone),+> $name<$($ty),+> {
+            /// Wait for an update of any of the observables and get clones of
+            /// all of their latest values.
+            ///
+            /// Awaiting returns `Some(_)` after an update happened, or `None`
+            /// after all of the observables were dropped.
+            pub async fn next(&mut self) -> Option<($($ty,)+)> {
+                poll_fn(|cx| self.poll_next_values(cx)).await
+            }
+
+            /// Get clones of the latest values without waiting for an update.
+            ///
+            /// This does not mark any value as observed.
+            #[must_use]
+            pub fn get(&self) -> ($($ty,)+) {
+                ($(self.subscribers.$idx.get(),)+)
+            }
+
+            /// Get back the individual subscribers.
+            pub fn into_inner(self) -> ($(Subscriber<$ty>,)+) {
+                self.subscribers
+            }
+
+            fn poll_next_values(&mut self, cx: &mut Context<'_>) -> Poll<Option<($($ty,)+)>> {
+                let subscribers = &mut self.subscribers;
+                let sources: [&mut dyn PollChanged; latest_n!(@count $($ty)+)] =
+                    [$(&mut subscribers.$idx),+];
+                poll_sources(sources, &mut self.closed, cx)
+                    .map(|ready| ready.map(|()| ($(subscribers.$idx.next_now(),)+)))
+            }
+        }
+
+        impl<$($ty),+> fmt::Debug for $name<$($ty),+>
+        where
+            $($ty: fmt::Debug,)+
+        {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_struct(stringify!($name))
+                    .field("subscribers", &self.subscribers)
+                    .field("closed", &self.closed)
+                    .finish()
+            }
+        }
+
+        impl<$($ty: Clone),+> Stream for $name<$($ty),+> {
+            type Item = ($($ty,)+);
+
+            fn poll_next(
+                mut self: Pin<&mut Self>,
+                cx: &mut Context<'_>,
+            ) -> Poll<Option<Self::Item>> {
+                self.poll_next_values(cx)
+            }
+        }
+    };
+    (@count $($ty:ident)+) => { 0 $(+ latest_n!(@one $ty))+ };
+    (@one $ty:ident) => { 1 };
+}
+
+latest_n! {
+    /// Combine two subscribers into one that yields the latest values of both
+    /// whenever either of them is updated.
+    fn latest2 -> Latest2<A, B> { a: 0, b: 1 }
+}
+
+latest_n! {
+    /// Combine three subscribers into one that yields the latest values of all
+    /// of them whenever any of them is updated.
+    fn latest3 -> Latest3<A, B, C> { a: 0, b: 1, c: 2 }
+}
+
+latest_n! {
+    /// Combine four subscribers into one that yields the latest values of all
+    /// of them whenever any of them is updated.
+    fn latest4 -> Latest4<A, B, C, D> { a: 0, b: 1, c: 2, d: 3 }
+}
+
+/// Combine any number of subscribers of the same type into one that yields
+/// the latest values of all of them whenever any of them is updated.
+pub fn latest_array<T: Clone, const N: usize>(
+    subscribers: [Subscriber<T>; N],
+) -> LatestArray<T, N> {
+    LatestArray { subscribers, closed: [false; N] }
+}
+
+/// Combined subscriber created by [`latest_array`].
+///
+/// See the [module documentation](self) for details.
+#[must_use]
+pub struct LatestArray<T, const N: usize> {
+    subscribers: [Subscriber<T>; N],
+    closed: [bool; N],
+}
+
+impl<T: Clone, const N: usize> LatestArray<T, N> {
+    /// Wait for an update of any of the observables and get clones of all of
+    /// their latest values.
+    ///
+    /// Awaiting returns `Some(_)` after an update happened, or `None` after
+    /// all of the observables were dropped.
+    pub async fn next(&mut self) -> Option<[T; N]> {
+        poll_fn(|cx| self.poll_next_values(cx)).await
+    }
+
+    /// Get clones of the latest values without waiting for an update.
+    ///
+    /// This does not mark any value as observed.
+    #[must_use]
+    pub fn get(&self) -> [T; N] {
+        std::array::from_fn(|i| self.subscribers[i].get())
+    }
+
+    /// Get back the individual subscribers.
+    pub fn into_inner(self) -> [Subscriber<T>; N] {
+        self.subscribers
+    }
+
+    fn poll_next_values(&mut self, cx: &mut Context<'_>) -> Poll<Option<[T; N]>> {
+        let sources = self.subscribers.iter_mut().map(|sub| sub as &mut dyn PollChanged);
+        poll_sources(sources, &mut self.closed, cx)
+            .map(|ready| ready.map(|()| std::array::from_fn(|i| self.subscribers[i].next_now())))
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for LatestArray<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LatestArray")
+            .field("subscribers", &self.subscribers)
+            .field("closed", &self.closed)
+            .finish()
+    }
+}
+
+impl<T: Clone, const N: usize> Stream for LatestArray<T, N> {
+    type Item = [T; N];
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.poll_next_values(cx)
+    }
+}
+
+/// Object-safe access to a subscriber's update polling, independent of its
+/// value type.
+trait PollChanged {
+    fn poll_changed(&mut self, cx: &Context<'_>) -> Poll<Option<()>>;
+}
+
+impl<T> PollChanged for Subscriber<T> {
+    fn poll_changed(&mut self, cx: &Context<'_>) -> Poll<Option<()>> {
+        self.poll_next_ref(cx).map(|opt| opt.map(|_| ()))
+    }
+}
+
+/// Poll all sources that are not closed yet, such that every one of them
+/// either has its update consumed or registers the waker.
+///
+/// Returns `Ready(Some(()))` if any of them was updated, and `Ready(None)` once
+/// all of them are closed.
+fn poll_sources<'a>(
+    sources: impl IntoIterator<Item = &'a mut dyn PollChanged>,
+    closed: &mut [bool],
+    cx: &Context<'_>,
+) -> Poll<Option<()>> {
+    let mut updated = false;
+    for (source, closed) in sources.into_iter().zip(closed.iter_mut()) {
+        if *closed {
+            continue;
+        }
+
+        match source.poll_changed(cx) {
+            Poll::Ready(Some(())) => updated = true,
+            Poll::Ready(None) => *closed = true,
+            Poll::Pending => {}
+        }
+    }
+
+    if updated {
+        Poll::Ready(Some(()))
+    } else if closed.iter().all(|&closed| closed) {
+        Poll::Ready(None)
+    } else {
+        Poll::Pending
+    }
+}
@@ -0,0 +1,100 @@
+//! Measurement of the time spent waiting for the lock of an observable's
+//! inner value.
+
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, Instant},
+};
+
+/// The threshold in nanoseconds, `u64::MAX` if disabled.
+static THRESHOLD: AtomicU64 = AtomicU64::new(u64::MAX);
+
+static MEASURED: AtomicU64 = AtomicU64::new(0);
+static SLOW: AtomicU64 = AtomicU64::new(0);
+static TOTAL_WAIT: AtomicU64 = AtomicU64::new(0);
+static MAX_WAIT: AtomicU64 = AtomicU64::new(0);
+
+/// Set the time spent waiting for the lock of an observable's inner value
+/// above which a wait counts as slow, or disable the measurement with `None`
+/// (the default).
+///
+/// Updating an observable has to wait for all read guards of the inner value
+/// to be released, e.g. the ones returned by [`Subscriber::read`]. Holding one
+/// for too long blocks updates, which is easy to miss. While a threshold is
+/// set, every update of an observable that doesn't use `AsyncLock` measures
+/// how long it waited for the lock. Slow waits are logged as a warning if the
+/// `tracing` feature is enabled, and all measurements are included in the
+/// statistics returned by [`lock_wait_stats`].
+///
+/// This applies to all observables.
+///
+/// [`Subscriber::read`]: crate::Subscriber::read
+pub fn set_lock_wait_threshold(threshold: Option<Duration>) {
+    let nanos = threshold.map_or(u64::MAX, |t| u64::try_from(t.as_nanos()).unwrap_or(u64::MAX));
+    THRESHOLD.store(nanos, Ordering::Relaxed);
+}
+
+/// Get statistics about the time spent waiting for the locks of observables'
+/// inner values, since the measurement was first enabled.
+#[must_use]
+pub fn lock_wait_stats() -> LockWaitStats {
+    LockWaitStats {
+        measured: MEASURED.load(Ordering::Relaxed),
+        slow: SLOW.load(Ordering::Relaxed),
+        total_wait: Duration::from_nanos(TOTAL_WAIT.load(Ordering::Relaxed)),
+        max_wait: Duration::from_nanos(MAX_WAIT.load(Ordering::Relaxed)),
+    }
+}
+
+/// Statistics about the time spent waiting for the locks of observables' inner
+/// values.
+///
+/// Only waits that happened while a threshold was set with
+/// [`set_lock_wait_threshold`] are taken into account.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct LockWaitStats {
+    /// The number of lock acquisitions that were measured.
+    pub measured: u64,
+
+    /// The number of lock acquisitions that took longer than the threshold.
+    pub slow: u64,
+
+    /// The total time spent waiting for locks.
+    pub total_wait: Duration,
+
+    /// The longest time spent waiting for a lock.
+    pub max_wait: Duration,
+}
+
+/// Acquire a lock through `acquire`, measuring how long it takes if enabled.
+pub(crate) fn measure<G>(acquire: impl FnOnce() -> G) -> G {
+    let threshold = THRESHOLD.load(Ordering::Relaxed);
+    if threshold == u64::MAX {
+        return acquire();
+    }
+
+    let start = Instant::now();
+    let guard = acquire();
+    record(start.elapsed(), threshold);
+    guard
+}
+
+fn record(wait: Duration, threshold: u64) {
+    let nanos = u64::try_from(wait.as_nanos()).unwrap_or(u64::MAX);
+    MEASURED.fetch_add(1, Ordering::Relaxed);
+    TOTAL_WAIT.fetch_add(nanos, Ordering::Relaxed);
+    MAX_WAIT.fetch_max(nanos, Ordering::Relaxed);
+
+    if nanos > threshold {
+        SLOW.fetch_add(1, Ordering::Relaxed);
+
+        #[cfg(feature = "tracing")]
+        tracing::warn!(
+            ?wait,
+            threshold = ?Duration::from_nanos(threshold),
+            "Waited a long time to update an observable, \
+             is a read guard of its value being held for too long?"
+        );
+    }
+}
@@ -0,0 +1,92 @@
+//! This module defines [`DerivedObservable`], a read-only observable whose
+//! value is computed from another observable.
+
+use std::fmt;
+
+use crate::{state::ObservableState, ObservableReadGuard, SharedObservable, Subscriber};
+
+/// A read-only observable whose value is computed from the value of another
+/// observable.
+///
+/// Created by [`Observable::derive`][crate::Observable::derive],
+/// [`SharedObservable::derive`] or [`DerivedObservable::derive`].
+///
+/// The value is recomputed synchronously whenever the source observable is
+/// updated, without a background task. This also means that the computation
+/// happens while the source is locked for writing, so it should be cheap and
+/// must not access the source observable itself.
+///
+/// Subscribers of a `DerivedObservable` are closed once it is dropped. When the
+/// source observable is dropped, the derived value stops updating but is kept.
+pub struct DerivedObservable<U> {
+    inner: SharedObservable<U>,
+}
+
+impl<U> DerivedObservable<U>
+where
+    U: Send + Sync + 'static,
+{
+    /// Create a `DerivedObservable` for the given source state, and register
+    /// the callback updating it on the source.
+    pub(crate) fn new<T>(
+        source: &ObservableState<T>,
+        f: impl Fn(&T) -> U + Send + Sync + 'static,
+    ) -> Self {
+        let inner = SharedObservable::new(f(source.get()));
+        let weak = inner.downgrade();
+        source.add_derived(Box::new(move |value| match weak.upgrade() {
+            Some(inner) => {
+                inner.set(f(value));
+                true
+            }
+            None => false,
+        }));
+
+        Self { inner }
+    }
+
+    /// Get a clone of the current value.
+    pub fn get(&self) -> U
+    where
+        U: Clone,
+    {
+        self.inner.get()
+    }
+
+    /// Lock the current value for reading.
+    ///
+    /// Updates of the source observable are blocked while the returned guard
+    /// is alive.
+    pub fn read(&self) -> ObservableReadGuard<'_, U> {
+        self.inner.read()
+    }
+
+    /// Obtain a new subscriber.
+    ///
+    /// See [`SharedObservable::subscribe`] for details.
+    pub fn subscribe(&self) -> Subscriber<U> {
+        self.inner.subscribe()
+    }
+
+    /// Obtain a new subscriber that immediately yields.
+    ///
+    /// See [`SharedObservable::subscribe_reset`] for details.
+    pub fn subscribe_reset(&self) -> Subscriber<U> {
+        self.inner.subscribe_reset()
+    }
+
+    /// Create a new `DerivedObservable` whose value is computed from this
+    /// one's.
+    pub fn derive<V>(&self, f: impl Fn(&U) -> V + Send + Sync + 'static) -> DerivedObservable<V>
+    where
+        V: Send + Sync + 'static,
+    {
+        self.inner.derive(f)
+    }
+}
+
+impl<U: fmt::Debug> fmt::Debug for DerivedObservable<U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DerivedObservable").field("inner", &self.inner).finish()
+    }
+}
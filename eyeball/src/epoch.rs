@@ -0,0 +1,27 @@
+//! Opaque tokens for detecting changes of an observable by polling.
+
+/// An opaque token identifying the state of an observable at some point.
+///
+/// Obtained from [`Observable::epoch`][crate::Observable::epoch],
+/// [`SharedObservable::epoch`][crate::SharedObservable::epoch] or
+/// [`Subscriber::epoch`][crate::Subscriber::epoch], and passed to
+/// [`Subscriber::changed_since`][crate::Subscriber::changed_since] later to
+/// find out whether the observable was updated in between, without cloning
+/// its value or polling a subscriber. This is useful for intermittent pollers
+/// like frame-based game loops that check many observables once per frame.
+///
+/// Epochs can only be compared for equality, and are not meaningful across
+/// different observables. They are not ordered, as an observable that was
+/// dropped is in a distinct final state rather than a later one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Epoch(u64);
+
+impl Epoch {
+    pub(crate) fn new(version: u64) -> Self {
+        Self(version)
+    }
+
+    pub(crate) fn version(self) -> u64 {
+        self.0
+    }
+}
@@ -0,0 +1,194 @@
+//! This module defines [`FileObservable`], an observable whose value is
+//! loaded from a file and reloaded whenever the file changes.
+
+use std::{
+    error::Error,
+    ffi::OsString,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+
+use crate::{ObservableReadGuard, SharedObservable, Subscriber};
+
+/// An observable value that is loaded from a file, and reloaded whenever that
+/// file changes.
+///
+/// This is useful for hot-reloading configuration files. The parsing function
+/// is user-provided, so any format works; for `serde`-based formats, pass the
+/// format crate's `from_str` function (e.g. `toml::from_str` or
+/// `serde_json::from_str`).
+///
+/// If reloading the file fails, the previous value is kept and the error is
+/// made available through [`error`][Self::error] and
+/// [`subscribe_error`][Self::subscribe_error]. It is reset to `None` on the
+/// next successful reload.
+///
+/// The file is watched until the `FileObservable` is dropped. Subscribers are
+/// closed once the background watcher has shut down, which may happen slightly
+/// after that.
+pub struct FileObservable<T, E> {
+    value: SharedObservable<T>,
+    error: SharedObservable<Option<Arc<ReloadError<E>>>>,
+    _watcher: RecommendedWatcher,
+}
+
+impl<T, E> FileObservable<T, E>
+where
+    T: Send + Sync + 'static,
+    E: Send + Sync + 'static,
+{
+    /// Load the file at `path` using `parse` and start watching it for
+    /// changes.
+    ///
+    /// Returns an error if the initial load fails or the file can't be
+    /// watched.
+    pub fn new(
+        path: impl Into<PathBuf>,
+        parse: impl Fn(&str) -> Result<T, E> + Send + 'static,
+    ) -> Result<Self, ReloadError<E>> {
+        let path = path.into();
+        let file_name = path.file_name().map(ToOwned::to_owned).ok_or_else(|| {
+            ReloadError::Io(io::Error::new(io::ErrorKind::InvalidInput, "path has no file name"))
+        })?;
+
+        let value = SharedObservable::new(load(&path, &parse)?);
+        let error = SharedObservable::new(None);
+
+        // Watch the parent directory rather than the file itself, since many
+        // editors save files by replacing them, which would end the watch.
+        let dir = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent.to_owned(),
+            _ => PathBuf::from("."),
+        };
+
+        let mut watcher = {
+            let value = value.clone();
+            let error = error.clone();
+            notify::recommended_watcher(move |res: notify::Result<notify::Event>| match res {
+                Ok(event) => {
+                    if is_relevant(&event, &file_name) {
+                        reload(&path, &parse, &value, &error);
+                    }
+                }
+                Err(e) => {
+                    error.set(Some(Arc::new(ReloadError::Watch(e))));
+                }
+            })
+            .map_err(ReloadError::Watch)?
+        };
+        watcher.watch(&dir, RecursiveMode::NonRecursive).map_err(ReloadError::Watch)?;
+
+        Ok(Self { value, error, _watcher: watcher })
+    }
+
+    /// Get a clone of the most recently loaded value.
+    pub fn get(&self) -> T
+    where
+        T: Clone,
+    {
+        self.value.get()
+    }
+
+    /// Lock the most recently loaded value for reading.
+    ///
+    /// Reloading is blocked while the returned guard is alive.
+    pub fn read(&self) -> ObservableReadGuard<'_, T> {
+        self.value.read()
+    }
+
+    /// Obtain a new subscriber that is notified whenever the file was
+    /// successfully reloaded.
+    pub fn subscribe(&self) -> Subscriber<T> {
+        self.value.subscribe()
+    }
+
+    /// Get the error of the last reload, if it failed.
+    pub fn error(&self) -> Option<Arc<ReloadError<E>>> {
+        self.error.get()
+    }
+
+    /// Obtain a new subscriber for reload errors.
+    ///
+    /// It is notified with `Some(_)` whenever reloading fails, and with `None`
+    /// when reloading succeeds again after a failure.
+    pub fn subscribe_error(&self) -> Subscriber<Option<Arc<ReloadError<E>>>> {
+        self.error.subscribe()
+    }
+}
+
+impl<T, E> fmt::Debug for FileObservable<T, E>
+where
+    T: fmt::Debug,
+    E: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FileObservable")
+            .field("value", &self.value)
+            .field("error", &self.error)
+            .finish_non_exhaustive()
+    }
+}
+
+/// An error that occurred while loading or watching the file backing a
+/// [`FileObservable`].
+#[derive(Debug)]
+pub enum ReloadError<E> {
+    /// Reading the file failed.
+    Io(io::Error),
+    /// Parsing the file contents failed.
+    Parse(E),
+    /// Watching the file for changes failed.
+    Watch(notify::Error),
+}
+
+impl<E: fmt::Display> fmt::Display for ReloadError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "failed to read file: {e}"),
+            Self::Parse(e) => write!(f, "failed to parse file: {e}"),
+            Self::Watch(e) => write!(f, "failed to watch file: {e}"),
+        }
+    }
+}
+
+impl<E: Error + 'static> Error for ReloadError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::Parse(e) => Some(e),
+            Self::Watch(e) => Some(e),
+        }
+    }
+}
+
+fn load<T, E>(path: &Path, parse: impl Fn(&str) -> Result<T, E>) -> Result<T, ReloadError<E>> {
+    let contents = fs::read_to_string(path).map_err(ReloadError::Io)?;
+    parse(&contents).map_err(ReloadError::Parse)
+}
+
+fn is_relevant(event: &notify::Event, file_name: &OsString) -> bool {
+    matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_))
+        && event.paths.iter().any(|p| p.file_name() == Some(file_name))
+}
+
+fn reload<T, E>(
+    path: &Path,
+    parse: impl Fn(&str) -> Result<T, E>,
+    value: &SharedObservable<T>,
+    error: &SharedObservable<Option<Arc<ReloadError<E>>>>,
+) {
+    match load(path, parse) {
+        Ok(new_value) => {
+            value.set(new_value);
+            error.update_if(|e| e.take().is_some());
+        }
+        Err(e) => {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(path = %path.display(), "failed to reload file");
+            error.set(Some(Arc::new(e)));
+        }
+    }
+}
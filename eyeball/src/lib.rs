@@ -65,27 +65,70 @@
 //!
 //! Cargo features:
 //!
+//! - `nightly`: Implement the unstable [`AsyncIterator`] trait for
+//!   [`Subscriber`], in addition to `Stream`. Requires a nightly compiler.
+//! - `notify`: Add [`FileObservable`], which reloads its value from a file
+//!   whenever that file changes
+//! - `time`: Add [`Subscriber::debounce`], which only yields values once they
+//!   have been stable for a given duration
 //! - `tracing`: Emit [tracing] events when updates are sent out
+//! - `update-seq`: Stamp every update with a process-wide [`UpdateSeq`], which
+//!   can be obtained from subscribers and read guards
+//! - `watch`: Add [`Observable::from_watch`] and [`Observable::into_watch`],
+//!   which keep an observable in sync with a tokio `watch` channel
 //!
 //! [Observer pattern]: https://en.wikipedia.org/wiki/Observer_pattern
+//! [`AsyncIterator`]: https://doc.rust-lang.org/std/async_iter/trait.AsyncIterator.html
 
 #![cfg_attr(docsrs, feature(doc_auto_cfg))]
+#![cfg_attr(feature = "nightly", feature(async_iterator))]
 
+mod arc_slice;
+pub mod combine;
+mod contention;
+mod derived;
+mod epoch;
+#[cfg(feature = "notify")]
+mod file;
 mod lock;
+mod override_guard;
+pub mod prelude;
 mod read_guard;
+mod scope;
+#[cfg(doctest)]
+mod send_sync;
 mod shared;
 mod state;
 pub mod subscriber;
 mod unique;
+#[cfg(feature = "update-seq")]
+mod update_seq;
+#[cfg(feature = "watch")]
+mod watch;
 
+#[cfg(feature = "notify")]
+#[doc(inline)]
+pub use self::file::{FileObservable, ReloadError};
 #[cfg(feature = "async-lock")]
 #[doc(inline)]
 pub use self::lock::AsyncLock;
+#[cfg(feature = "sink")]
+#[doc(inline)]
+pub use self::shared::ObservableClosed;
+#[cfg(feature = "update-seq")]
+#[doc(inline)]
+pub use self::update_seq::UpdateSeq;
 #[doc(inline)]
 pub use self::{
+    contention::{lock_wait_stats, set_lock_wait_threshold, LockWaitStats},
+    derived::DerivedObservable,
+    epoch::Epoch,
     lock::SyncLock,
+    override_guard::{OverrideGuard, SharedOverrideGuard},
     read_guard::ObservableReadGuard,
+    scope::NotifyScope,
     shared::{ObservableWriteGuard, SharedObservable, WeakObservable},
+    state::WakerGcStats,
     subscriber::Subscriber,
     unique::Observable,
 };
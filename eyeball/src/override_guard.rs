@@ -0,0 +1,96 @@
+use std::{fmt, ops};
+
+use crate::{Observable, SharedObservable};
+
+/// A temporary override of an [`Observable`]'s value.
+///
+/// Obtained from [`Observable::override_scope`]. When this guard is dropped,
+/// the value from before the override is restored and subscribers are
+/// notified.
+#[must_use = "the override ends immediately if the guard is not used"]
+pub struct OverrideGuard<'a, T> {
+    observable: &'a mut Observable<T>,
+    /// Always `Some` until the guard is dropped.
+    previous: Option<T>,
+}
+
+impl<'a, T> OverrideGuard<'a, T> {
+    pub(crate) fn new(observable: &'a mut Observable<T>, value: T) -> Self {
+        let previous = Observable::set(observable, value);
+        Self { observable, previous: Some(previous) }
+    }
+
+    /// Get the value that is restored when this guard is dropped.
+    pub fn previous(&self) -> &T {
+        self.previous.as_ref().unwrap()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for OverrideGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OverrideGuard")
+            .field("value", &**self)
+            .field("previous", self.previous())
+            .finish()
+    }
+}
+
+impl<T> ops::Deref for OverrideGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        Observable::get(self.observable)
+    }
+}
+
+impl<T> Drop for OverrideGuard<'_, T> {
+    fn drop(&mut self) {
+        if let Some(previous) = self.previous.take() {
+            Observable::set(self.observable, previous);
+        }
+    }
+}
+
+/// A temporary override of a [`SharedObservable`]'s value.
+///
+/// Obtained from [`SharedObservable::override_scope`]. When this guard is
+/// dropped, the value from before the override is restored and subscribers
+/// are notified.
+///
+/// The guard holds a clone of the observable, so it keeps subscribers from
+/// being notified of the observable being dropped until it is dropped itself.
+#[must_use = "the override ends immediately if the guard is not used"]
+pub struct SharedOverrideGuard<T> {
+    observable: SharedObservable<T>,
+    /// Always `Some` until the guard is dropped.
+    previous: Option<T>,
+}
+
+impl<T> SharedOverrideGuard<T> {
+    pub(crate) fn new(observable: SharedObservable<T>, value: T) -> Self {
+        let previous = observable.set(value);
+        Self { observable, previous: Some(previous) }
+    }
+
+    /// Get the value that is restored when this guard is dropped.
+    pub fn previous(&self) -> &T {
+        self.previous.as_ref().unwrap()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for SharedOverrideGuard<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SharedOverrideGuard")
+            .field("observable", &self.observable)
+            .field("previous", self.previous())
+            .finish()
+    }
+}
+
+impl<T> Drop for SharedOverrideGuard<T> {
+    fn drop(&mut self) {
+        if let Some(previous) = self.previous.take() {
+            self.observable.set(previous);
+        }
+    }
+}
@@ -0,0 +1,12 @@
+//! Re-exports of the most commonly used types, for glob-importing.
+//!
+//! ```
+//! use eyeball::prelude::*;
+//!
+//! let ob = Observable::new(1);
+//! let shared = SharedObservable::new(2);
+//! let _subscribers: [Subscriber<i32>; 2] = [Observable::subscribe(&ob), shared.subscribe()];
+//! ```
+
+#[doc(no_inline)]
+pub use crate::{Observable, SharedObservable, Subscriber};
@@ -1,5 +1,7 @@
 use std::{fmt, ops};
 
+#[cfg(feature = "update-seq")]
+use crate::UpdateSeq;
 use crate::{lock::Lock, state::ObservableState, SyncLock};
 
 /// A read guard for the inner value of an observable.
@@ -16,6 +18,13 @@ impl<'a, T: 'a, L: Lock> ObservableReadGuard<'a, T, L> {
     pub(crate) fn new(inner: L::SharedReadGuard<'a, ObservableState<T>>) -> Self {
         Self { inner }
     }
+
+    /// Get the sequence number of the update that set the value this guard
+    /// dereferences to.
+    #[cfg(feature = "update-seq")]
+    pub fn update_seq(this: &Self) -> UpdateSeq {
+        this.inner.update_seq()
+    }
 }
 
 impl<T: fmt::Debug, L: Lock> fmt::Debug for ObservableReadGuard<'_, T, L> {
@@ -0,0 +1,71 @@
+use std::{fmt, ops};
+
+/// Mutable access to the inner value of an observable that notifies
+/// subscribers at most once, when the scope ends.
+///
+/// Obtained from
+/// [`SharedObservable::update_in_place_with_notify_scope`][crate::SharedObservable::update_in_place_with_notify_scope]
+/// and the equivalent methods on other types. Reading the value through
+/// [`Deref`][ops::Deref] does not count as a change; calling
+/// [`get_mut`][Self::get_mut] or [`mark_dirty`][Self::mark_dirty] does. If the
+/// scope is dirty when it ends, subscribers are notified once, no matter how
+/// many changes were made.
+///
+/// This is useful to update several parts of a large value in place, instead
+/// of cloning, modifying and [`set`][crate::SharedObservable::set]ting the
+/// whole value, or calling [`update`][crate::SharedObservable::update]
+/// multiple times, which would notify subscribers for each call.
+pub struct NotifyScope<'a, T> {
+    value: &'a mut T,
+    dirty: bool,
+}
+
+impl<'a, T> NotifyScope<'a, T> {
+    pub(crate) fn new(value: &'a mut T) -> Self {
+        Self { value, dirty: false }
+    }
+
+    /// Get mutable access to the inner value, marking the scope as dirty.
+    pub fn get_mut(&mut self) -> &mut T {
+        self.dirty = true;
+        self.value
+    }
+
+    /// Get mutable access to the inner value without marking the scope as
+    /// dirty.
+    ///
+    /// Use this for changes that subscribers don't need to know about, or
+    /// together with [`mark_dirty`][Self::mark_dirty] for changes that only
+    /// sometimes matter.
+    pub fn get_mut_untracked(&mut self) -> &mut T {
+        self.value
+    }
+
+    /// Mark the scope as dirty, such that subscribers get notified when it
+    /// ends.
+    pub fn mark_dirty(&mut self) {
+        self.dirty = true;
+    }
+
+    /// Whether subscribers will be notified when the scope ends.
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for NotifyScope<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NotifyScope")
+            .field("value", &self.value)
+            .field("dirty", &self.dirty)
+            .finish()
+    }
+}
+
+impl<T> ops::Deref for NotifyScope<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.value
+    }
+}
@@ -0,0 +1,28 @@
+//! Compile-fail tests checking that the public types can't be sent to or
+//! shared with other threads if the values they hold can't. The opposite is
+//! checked in `tests/it/send_sync.rs`.
+//!
+//! ```compile_fail,E0277
+//! fn assert_send<T: Send>() {}
+//! assert_send::<eyeball::Observable<std::rc::Rc<u8>>>();
+//! ```
+//!
+//! ```compile_fail,E0277
+//! fn assert_send<T: Send>() {}
+//! assert_send::<eyeball::SharedObservable<std::rc::Rc<u8>>>();
+//! ```
+//!
+//! ```compile_fail,E0277
+//! fn assert_send<T: Send>() {}
+//! assert_send::<eyeball::Subscriber<std::rc::Rc<u8>>>();
+//! ```
+//!
+//! ```compile_fail,E0277
+//! fn assert_sync<T: Sync>() {}
+//! assert_sync::<eyeball::SharedObservable<std::cell::Cell<u8>>>();
+//! ```
+//!
+//! ```compile_fail,E0277
+//! fn assert_sync<T: Sync>() {}
+//! assert_sync::<eyeball::Subscriber<std::cell::Cell<u8>>>();
+//! ```
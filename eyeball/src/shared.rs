@@ -9,9 +9,11 @@ use std::{
     fmt,
     hash::Hash,
     ops,
-    sync::{Arc, PoisonError, TryLockError, TryLockResult, Weak},
+    sync::{Arc, PoisonError, RwLockWriteGuard, TryLockError, TryLockResult, Weak},
 };
 
+#[cfg(feature = "async-lock")]
+use futures_core::future::BoxFuture;
 use readlock::{SharedReadGuard, SharedReadLock};
 #[cfg(feature = "async-lock")]
 use readlock_tokio::{
@@ -20,7 +22,19 @@ use readlock_tokio::{
 
 #[cfg(feature = "async-lock")]
 use crate::AsyncLock;
-use crate::{lock::Lock, state::ObservableState, ObservableReadGuard, Subscriber, SyncLock};
+use crate::{
+    arc_slice, contention,
+    lock::Lock,
+    state::{ObservableState, WakerGcStats},
+    DerivedObservable, Epoch, NotifyScope, ObservableReadGuard, SharedOverrideGuard, Subscriber,
+    SyncLock,
+};
+
+#[cfg(feature = "sink")]
+mod sink;
+
+#[cfg(feature = "sink")]
+pub use self::sink::ObservableClosed;
 
 /// A value whose changes will be broadcast to subscribers.
 ///
@@ -75,6 +89,17 @@ impl<T> SharedObservable<T> {
         Subscriber::new(SharedReadLock::from_inner(Arc::clone(&self.state)), 0)
     }
 
+    /// Create a read-only observable whose value is computed from this one's
+    /// using `f`, and recomputed whenever it is updated.
+    ///
+    /// See [`DerivedObservable`] for details.
+    pub fn derive<U>(&self, f: impl Fn(&T) -> U + Send + Sync + 'static) -> DerivedObservable<U>
+    where
+        U: Send + Sync + 'static,
+    {
+        DerivedObservable::new(&self.state.read().unwrap(), f)
+    }
+
     /// Get a clone of the inner value.
     pub fn get(&self) -> T
     where
@@ -117,7 +142,7 @@ impl<T> SharedObservable<T> {
     /// returned write guard dereferences (immutably) to the inner type, and has
     /// associated functions to update it.
     pub fn write(&self) -> ObservableWriteGuard<'_, T> {
-        ObservableWriteGuard::new(self.state.write().unwrap())
+        ObservableWriteGuard::new(self.write_state())
     }
 
     /// Attempts to acquire exclusive write access to the inner value.
@@ -137,7 +162,7 @@ impl<T> SharedObservable<T> {
     /// Set the inner value to the given `value`, notify subscribers and return
     /// the previous value.
     pub fn set(&self, value: T) -> T {
-        self.state.write().unwrap().set(value)
+        self.write_state().set(value)
     }
 
     /// Set the inner value to the given `value` if it doesn't compare equal to
@@ -149,7 +174,7 @@ impl<T> SharedObservable<T> {
     where
         T: PartialEq,
     {
-        self.state.write().unwrap().set_if_not_eq(value)
+        self.write_state().set_if_not_eq(value)
     }
 
     /// Set the inner value to the given `value` if it has a different hash than
@@ -161,7 +186,7 @@ impl<T> SharedObservable<T> {
     where
         T: Hash,
     {
-        self.state.write().unwrap().set_if_hash_not_eq(value)
+        self.write_state().set_if_hash_not_eq(value)
     }
 
     /// Set the inner value to a `Default` instance of its type, notify
@@ -182,7 +207,7 @@ impl<T> SharedObservable<T> {
     /// [`update_if`][Self::update_if] if you want to conditionally mutate the
     /// inner value.
     pub fn update(&self, f: impl FnOnce(&mut T)) {
-        self.state.write().unwrap().update(f);
+        self.write_state().update(f);
     }
 
     /// Maybe update the inner value and notify subscribers if it changed.
@@ -190,7 +215,100 @@ impl<T> SharedObservable<T> {
     /// The closure given to this function must return `true` if subscribers
     /// should be notified of a change to the inner value.
     pub fn update_if(&self, f: impl FnOnce(&mut T) -> bool) {
-        self.state.write().unwrap().update_if(f);
+        self.write_state().update_if(f);
+    }
+
+    /// Update the inner value in place through a [`NotifyScope`], notifying
+    /// subscribers once at the end if the scope was marked as dirty.
+    ///
+    /// The scope is marked as dirty by mutably accessing the value through
+    /// [`NotifyScope::get_mut`] or by calling [`NotifyScope::mark_dirty`].
+    /// This allows making several changes to a large value with a single
+    /// notification, and without cloning it.
+    pub fn update_in_place_with_notify_scope<R>(
+        &self,
+        f: impl FnOnce(&mut NotifyScope<'_, T>) -> R,
+    ) -> R {
+        self.write_state().update_with_notify_scope(f)
+    }
+
+    /// Temporarily set the inner value to the given `value` and notify
+    /// subscribers.
+    ///
+    /// When the returned guard is dropped, the previous value is restored and
+    /// subscribers are notified again. Updates made through other clones of
+    /// this observable while the override is active are overwritten by that.
+    pub fn override_scope(&self, value: T) -> SharedOverrideGuard<T> {
+        SharedOverrideGuard::new(self.clone(), value)
+    }
+
+    /// Get an [`Epoch`] identifying the current state of the inner value.
+    ///
+    /// Pass it to [`Subscriber::changed_since`] later to find out whether the
+    /// inner value was updated in between.
+    #[must_use]
+    pub fn epoch(&self) -> Epoch {
+        Epoch::new(self.state.read().unwrap().version())
+    }
+
+    /// Set the number of wakers registered by subscribers at which redundant
+    /// ones are collected automatically, or disable automatic collection with
+    /// `None` (the default).
+    ///
+    /// See [`Observable::set_waker_gc_threshold`](crate::Observable::set_waker_gc_threshold).
+    pub fn set_waker_gc_threshold(&self, threshold: Option<usize>) {
+        self.state.read().unwrap().set_waker_gc_threshold(threshold);
+    }
+
+    /// Collect redundant wakers registered by subscribers right away.
+    ///
+    /// Returns the number of wakers that were removed.
+    pub fn gc_wakers_now(&self) -> usize {
+        self.state.read().unwrap().gc_wakers_now()
+    }
+
+    /// Get statistics about the wakers registered by subscribers.
+    #[must_use]
+    pub fn waker_gc_stats(&self) -> WakerGcStats {
+        self.state.read().unwrap().waker_gc_stats()
+    }
+
+    fn write_state(&self) -> RwLockWriteGuard<'_, ObservableState<T>> {
+        contention::measure(|| self.state.write().unwrap())
+    }
+}
+
+impl SharedObservable<Arc<str>> {
+    /// Set the inner value to `value` if it doesn't compare equal to the
+    /// existing value, and return whether it was set.
+    ///
+    /// Unlike [`set_if_not_eq`][Self::set_if_not_eq], this doesn't allocate a
+    /// new `Arc<str>` if the value is unchanged. If the value changed but has
+    /// the same length, and nothing else holds a clone of the inner `Arc`, the
+    /// existing allocation is reused.
+    pub fn set_str(&self, value: &str) -> bool {
+        let mut changed = false;
+        self.update_if(|current| {
+            changed = arc_slice::replace_str(current, value);
+            changed
+        });
+        changed
+    }
+}
+
+impl SharedObservable<Arc<[u8]>> {
+    /// Set the inner value to `value` if it doesn't compare equal to the
+    /// existing value, and return whether it was set.
+    ///
+    /// Like [`set_str`][SharedObservable::set_str], this avoids allocating
+    /// where possible.
+    pub fn set_bytes(&self, value: &[u8]) -> bool {
+        let mut changed = false;
+        self.update_if(|current| {
+            changed = arc_slice::replace_bytes(current, value);
+            changed
+        });
+        changed
     }
 }
 
@@ -332,6 +450,63 @@ impl<T: Send + Sync + 'static> SharedObservable<T, AsyncLock> {
     pub async fn update_if(&self, f: impl FnOnce(&mut T) -> bool) {
         self.state.write().await.update_if(f);
     }
+
+    /// Update the inner value in place through a [`NotifyScope`], notifying
+    /// subscribers once at the end if the scope was marked as dirty.
+    ///
+    /// The scope is marked as dirty by mutably accessing the value through
+    /// [`NotifyScope::get_mut`] or by calling [`NotifyScope::mark_dirty`].
+    /// This allows making several changes to a large value with a single
+    /// notification, and without cloning it.
+    pub async fn update_in_place_with_notify_scope<R>(
+        &self,
+        f: impl FnOnce(&mut NotifyScope<'_, T>) -> R,
+    ) -> R {
+        self.state.write().await.update_with_notify_scope(f)
+    }
+
+    /// Update the inner value with a fallible asynchronous closure.
+    ///
+    /// The closure is run on a clone of the inner value. If the future it
+    /// returns resolves to `Ok(())`, the modified clone replaces the inner
+    /// value and subscribers are notified. If it resolves to `Err(_)`, the
+    /// modified clone is discarded, leaving the inner value untouched, and the
+    /// error is returned.
+    ///
+    /// The write lock is held until the future has completed, so no other
+    /// update can happen in between (and readers have to wait).
+    ///
+    /// ```
+    /// # futures_executor::block_on(async {
+    /// use eyeball::SharedObservable;
+    ///
+    /// let ob = SharedObservable::new_async(vec![1, 2]);
+    /// let res = ob
+    ///     .update_async(|v| {
+    ///         Box::pin(async move {
+    ///             v.push(3);
+    ///             Err("something went wrong")
+    ///         })
+    ///     })
+    ///     .await;
+    ///
+    /// assert_eq!(res, Err("something went wrong"));
+    /// assert_eq!(ob.get().await, [1, 2]);
+    /// # });
+    /// ```
+    pub async fn update_async<E>(
+        &self,
+        f: impl for<'a> FnOnce(&'a mut T) -> BoxFuture<'a, Result<(), E>>,
+    ) -> Result<(), E>
+    where
+        T: Clone,
+    {
+        let mut state = self.state.write().await;
+        let mut value = state.get().clone();
+        f(&mut value).await?;
+        state.set(value);
+        Ok(())
+    }
 }
 
 impl<T, L: Lock> SharedObservable<T, L> {
@@ -544,6 +719,18 @@ impl<'a, T: 'a, L: Lock> ObservableWriteGuard<'a, T, L> {
     pub fn update_if(this: &mut Self, f: impl FnOnce(&mut T) -> bool) {
         this.inner.update_if(f);
     }
+
+    /// Update the inner value in place through a [`NotifyScope`], notifying
+    /// subscribers once at the end if the scope was marked as dirty.
+    ///
+    /// See [`SharedObservable::update_in_place_with_notify_scope`] for more
+    /// details.
+    pub fn update_in_place_with_notify_scope<R>(
+        this: &mut Self,
+        f: impl FnOnce(&mut NotifyScope<'_, T>) -> R,
+    ) -> R {
+        this.inner.update_with_notify_scope(f)
+    }
 }
 
 impl<T: fmt::Debug> fmt::Debug for ObservableWriteGuard<'_, T> {
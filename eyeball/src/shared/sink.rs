@@ -0,0 +1,73 @@
+use std::{
+    error::Error,
+    fmt,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use futures_sink::Sink;
+
+use super::SharedObservable;
+
+/// Sets the inner value to every item sent into the sink.
+///
+/// This allows driving a stream of values into an observable, e.g. with
+/// `stream.forward(observable)`.
+///
+/// Closing the sink closes the observable if there are no other clones of it,
+/// which ends all of its subscribers' streams the same way dropping the last
+/// clone does. Once the observable is closed, sending into it fails with
+/// [`ObservableClosed`], and updating it through any of its handles no longer
+/// notifies anyone: existing subscribers have ended, and new subscribers end
+/// right away.
+impl<T> Sink<T> for SharedObservable<T> {
+    type Error = ObservableClosed;
+
+    fn poll_ready(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(if self.is_closed() { Err(ObservableClosed) } else { Ok(()) })
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: T) -> Result<(), Self::Error> {
+        let mut state = self.write_state();
+        if state.version() == 0 {
+            return Err(ObservableClosed);
+        }
+
+        state.set(item);
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        // Other clones can still update the observable, so only close it if
+        // this is the last one.
+        if Arc::strong_count(&self._num_clones) == 1 {
+            self.state.read().unwrap().close();
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<T> SharedObservable<T> {
+    fn is_closed(&self) -> bool {
+        self.state.read().unwrap().version() == 0
+    }
+}
+
+/// The error returned when sending a value into a [`SharedObservable`] that
+/// was closed through its [`Sink`] implementation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ObservableClosed;
+
+impl fmt::Display for ObservableClosed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("the observable was closed")
+    }
+}
+
+impl Error for ObservableClosed {}
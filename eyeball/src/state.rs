@@ -1,21 +1,37 @@
 use std::{
+    fmt,
     hash::{Hash, Hasher},
     mem,
-    sync::RwLock,
+    sync::{Arc, RwLock},
     task::{Context, Poll, Waker},
 };
 
+use crate::NotifyScope;
+#[cfg(feature = "update-seq")]
+use crate::UpdateSeq;
+
 #[derive(Debug)]
 pub struct ObservableState<T> {
     /// The wrapped value.
     value: T,
 
     /// The attached observable metadata.
-    metadata: RwLock<ObservableStateMetadata>,
+    metadata: RwLock<ObservableStateMetadata<T>>,
+
+    /// The sequence number of the update that set the current value.
+    #[cfg(feature = "update-seq")]
+    update_seq: UpdateSeq,
 }
 
-#[derive(Debug)]
-struct ObservableStateMetadata {
+/// A predicate that decides whether a subscriber is woken up for a new value.
+pub(crate) type SubscriberFilter<T> = Arc<dyn Fn(&T) -> bool + Send + Sync>;
+
+/// A callback that updates a derived observable from a new value.
+///
+/// Returns `false` once the derived observable has been dropped.
+pub(crate) type DerivedUpdater<T> = Box<dyn FnMut(&T) -> bool + Send + Sync>;
+
+struct ObservableStateMetadata<T> {
     /// The version of the value.
     ///
     /// Starts at 1 and is incremented by 1 each time the value is updated.
@@ -31,17 +47,97 @@ struct ObservableStateMetadata {
     /// reading the value and adding a waker because the value hasn't changed
     /// yet, no updates to the value could have happened.
     wakers: Vec<Waker>,
+
+    /// List of wakers that are only woken up if the new value matches their
+    /// filter.
+    filtered_wakers: Vec<(Waker, SubscriberFilter<T>)>,
+
+    /// Callbacks updating derived observables, run synchronously on update.
+    derived: Vec<DerivedUpdater<T>>,
+
+    /// Settings and statistics of the collection of redundant wakers.
+    waker_gc: WakerGc,
 }
 
-impl Default for ObservableStateMetadata {
+impl<T> Default for ObservableStateMetadata<T> {
     fn default() -> Self {
-        Self { version: 1, wakers: Vec::new() }
+        Self {
+            version: 1,
+            wakers: Vec::new(),
+            filtered_wakers: Vec::new(),
+            derived: Vec::new(),
+            waker_gc: WakerGc::default(),
+        }
+    }
+}
+
+impl<T> ObservableStateMetadata<T> {
+    /// Collect redundant wakers if the number of registered wakers has reached
+    /// the threshold.
+    fn maybe_gc_wakers(&mut self) {
+        if self.wakers.len() + self.filtered_wakers.len() >= self.waker_gc.next_run_at {
+            self.gc_wakers();
+        }
+    }
+
+    /// Remove wakers that would wake the same task as another registered
+    /// waker, and return how many were removed.
+    fn gc_wakers(&mut self) -> usize {
+        let num_before = self.wakers.len() + self.filtered_wakers.len();
+
+        let mut wakers: Vec<Waker> = Vec::with_capacity(self.wakers.len());
+        for waker in self.wakers.drain(..) {
+            if !wakers.iter().any(|w| w.will_wake(&waker)) {
+                wakers.push(waker);
+            }
+        }
+        self.wakers = wakers;
+
+        let mut filtered_wakers: Vec<(Waker, SubscriberFilter<T>)> =
+            Vec::with_capacity(self.filtered_wakers.len());
+        for (waker, filter) in self.filtered_wakers.drain(..) {
+            if !filtered_wakers.iter().any(|(w, f)| w.will_wake(&waker) && Arc::ptr_eq(f, &filter))
+            {
+                filtered_wakers.push((waker, filter));
+            }
+        }
+        self.filtered_wakers = filtered_wakers;
+
+        let num_after = self.wakers.len() + self.filtered_wakers.len();
+        let collected = num_before - num_after;
+        self.waker_gc.runs += 1;
+        self.waker_gc.collected += collected as u64;
+        // Avoid running again on every registration if most wakers are not
+        // redundant.
+        self.waker_gc.next_run_at = self.waker_gc.threshold.max(num_after.saturating_mul(2));
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!("Collected {collected} redundant wakers, {num_after} remaining");
+
+        collected
+    }
+}
+
+impl<T> fmt::Debug for ObservableStateMetadata<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ObservableStateMetadata")
+            .field("version", &self.version)
+            .field("wakers", &self.wakers)
+            .field("filtered_wakers", &self.filtered_wakers.len())
+            .field("derived", &self.derived.len())
+            .field("waker_gc", &self.waker_gc)
+            .finish()
     }
 }
 
 impl<T> ObservableState<T> {
     pub(crate) fn new(value: T) -> Self {
-        Self { value, metadata: Default::default() }
+        Self {
+            value,
+            metadata: Default::default(),
+            #[cfg(feature = "update-seq")]
+            update_seq: UpdateSeq::next(),
+        }
     }
 
     /// Get a reference to the inner value.
@@ -49,6 +145,12 @@ impl<T> ObservableState<T> {
         &self.value
     }
 
+    /// Get the sequence number of the update that set the inner value.
+    #[cfg(feature = "update-seq")]
+    pub(crate) fn update_seq(&self) -> UpdateSeq {
+        self.update_seq
+    }
+
     /// Get the current version of the inner value.
     pub(crate) fn version(&self) -> u64 {
         self.metadata.read().unwrap().version
@@ -68,10 +170,78 @@ impl<T> ObservableState<T> {
             Poll::Ready(Some(()))
         } else {
             metadata.wakers.push(cx.waker().clone());
+            metadata.maybe_gc_wakers();
             Poll::Pending
         }
     }
 
+    /// Like `poll_update`, but only returns `Ready(Some(_))` for values that
+    /// match `filter`. The filter is evaluated by the observable on update
+    /// rather than after waking up the subscriber.
+    pub(crate) fn poll_update_filtered(
+        &self,
+        observed_version: &mut u64,
+        filter: &SubscriberFilter<T>,
+        cx: &Context<'_>,
+    ) -> Poll<Option<()>> {
+        let mut metadata = self.metadata.write().unwrap();
+
+        if metadata.version == 0 {
+            return Poll::Ready(None);
+        }
+
+        if *observed_version < metadata.version {
+            *observed_version = metadata.version;
+            if filter(&self.value) {
+                return Poll::Ready(Some(()));
+            }
+        }
+
+        // Replace the entry of an earlier poll from the same task, rather than
+        // piling up entries while the values don't match.
+        let waker = cx.waker();
+        metadata.filtered_wakers.retain(|(w, f)| {
+            let replaced = Arc::ptr_eq(f, filter) && w.will_wake(waker);
+            !replaced && !is_orphaned(f)
+        });
+        metadata.filtered_wakers.push((waker.clone(), filter.clone()));
+        metadata.maybe_gc_wakers();
+        Poll::Pending
+    }
+
+    /// Register a callback that is run with the new value on every update,
+    /// until it returns `false`.
+    pub(crate) fn add_derived(&self, updater: DerivedUpdater<T>) {
+        let mut metadata = self.metadata.write().unwrap();
+        // No further updates will happen if the observable was dropped.
+        if metadata.version != 0 {
+            metadata.derived.push(updater);
+        }
+    }
+
+    /// Set the number of registered wakers at which redundant ones are
+    /// collected automatically, or disable automatic collection with `None`.
+    pub(crate) fn set_waker_gc_threshold(&self, threshold: Option<usize>) {
+        let mut metadata = self.metadata.write().unwrap();
+        let threshold = threshold.unwrap_or(usize::MAX);
+        metadata.waker_gc.threshold = threshold;
+        metadata.waker_gc.next_run_at = threshold;
+    }
+
+    /// Collect redundant wakers right away, and return how many were removed.
+    pub(crate) fn gc_wakers_now(&self) -> usize {
+        self.metadata.write().unwrap().gc_wakers()
+    }
+
+    pub(crate) fn waker_gc_stats(&self) -> WakerGcStats {
+        let metadata = self.metadata.read().unwrap();
+        WakerGcStats {
+            registered_wakers: metadata.wakers.len() + metadata.filtered_wakers.len(),
+            runs: metadata.waker_gc.runs,
+            collected: metadata.waker_gc.collected,
+        }
+    }
+
     pub(crate) fn set(&mut self, value: T) -> T {
         let result = mem::replace(&mut self.value, value);
         self.incr_version_and_wake();
@@ -111,21 +281,121 @@ impl<T> ObservableState<T> {
         }
     }
 
+    pub(crate) fn update_with_notify_scope<R>(
+        &mut self,
+        f: impl FnOnce(&mut NotifyScope<'_, T>) -> R,
+    ) -> R {
+        let mut scope = NotifyScope::new(&mut self.value);
+        let result = f(&mut scope);
+        if scope.is_dirty() {
+            self.incr_version_and_wake();
+        }
+        result
+    }
+
     /// "Close" the state – indicate that no further updates will happen.
     pub(crate) fn close(&self) {
         let mut metadata = self.metadata.write().unwrap();
         metadata.version = 0;
         // Clear the backing buffer for the wakers, no new ones will be added.
         wake(mem::take(&mut metadata.wakers));
+        let filtered_wakers = mem::take(&mut metadata.filtered_wakers);
+        wake(filtered_wakers.into_iter().map(|(waker, _)| waker));
+        metadata.derived.clear();
     }
 
     fn incr_version_and_wake(&mut self) {
         let metadata = self.metadata.get_mut().unwrap();
+        // A closed state stays closed, there is nobody left to notify.
+        if metadata.version == 0 {
+            return;
+        }
+
+        #[cfg(feature = "update-seq")]
+        {
+            self.update_seq = UpdateSeq::next();
+        }
+
         metadata.version += 1;
         wake(metadata.wakers.drain(..));
+        metadata.waker_gc.next_run_at = metadata.waker_gc.threshold;
+
+        if !metadata.filtered_wakers.is_empty() {
+            let value = &self.value;
+            metadata.filtered_wakers.retain(|(waker, filter)| {
+                if is_orphaned(filter) {
+                    return false;
+                }
+
+                let matches = filter(value);
+                if matches {
+                    waker.wake_by_ref();
+                }
+                !matches
+            });
+        }
+
+        if !metadata.derived.is_empty() {
+            let value = &self.value;
+            metadata.derived.retain_mut(|update| update(value));
+        }
     }
 }
 
+/// Whether the filtered subscriber that registered `filter` was dropped, such
+/// that the registry holds the only reference to it.
+fn is_orphaned<T>(filter: &SubscriberFilter<T>) -> bool {
+    Arc::strong_count(filter) == 1
+}
+
+#[derive(Debug)]
+struct WakerGc {
+    /// The number of registered wakers at which redundant ones are collected
+    /// automatically. `usize::MAX` if automatic collection is disabled.
+    threshold: usize,
+
+    /// The number of registered wakers at which the next automatic collection
+    /// happens. Never less than `threshold`.
+    next_run_at: usize,
+
+    /// The number of collections so far.
+    runs: u64,
+
+    /// The number of wakers removed by all collections so far.
+    collected: u64,
+}
+
+impl Default for WakerGc {
+    fn default() -> Self {
+        Self { threshold: usize::MAX, next_run_at: usize::MAX, runs: 0, collected: 0 }
+    }
+}
+
+/// Statistics about the wakers registered by the subscribers of an observable.
+///
+/// Every time a subscriber is polled while no update is available, it registers
+/// a waker with the observable, which is only released on the next update. If
+/// subscribers are polled a lot without updates happening, e.g. as part of a
+/// `select!` loop, wakers for the same task can accumulate. Such redundant
+/// wakers can be collected with [`Observable::gc_wakers_now`], or
+/// automatically once there are too many of them with
+/// [`Observable::set_waker_gc_threshold`].
+///
+/// [`Observable::gc_wakers_now`]: crate::Observable::gc_wakers_now
+/// [`Observable::set_waker_gc_threshold`]: crate::Observable::set_waker_gc_threshold
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct WakerGcStats {
+    /// The number of currently registered wakers.
+    pub registered_wakers: usize,
+
+    /// The number of times redundant wakers were collected.
+    pub runs: u64,
+
+    /// The total number of redundant wakers that were collected.
+    pub collected: u64,
+}
+
 fn hash<T: Hash>(value: &T) -> u64 {
     use std::collections::hash_map::DefaultHasher;
 
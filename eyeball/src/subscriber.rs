@@ -3,19 +3,31 @@
 //! Usually, you don't need to interact with this module at all, since its most
 //! important type `Subscriber` is re-exported at the crate root.
 
+#[cfg(feature = "nightly")]
+use std::async_iter::AsyncIterator;
 use std::{
     fmt,
     future::{poll_fn, Future},
     pin::Pin,
+    sync::Arc,
     task::{Context, Poll},
 };
 
 use futures_core::Stream;
 
-use crate::{lock::Lock, state::ObservableState, ObservableReadGuard, SyncLock};
+use crate::{
+    lock::Lock,
+    state::{ObservableState, SubscriberFilter},
+    Epoch, ObservableReadGuard, SyncLock,
+};
 
 #[cfg(feature = "async-lock")]
 pub(crate) mod async_lock;
+#[cfg(feature = "time")]
+mod debounce;
+
+#[cfg(feature = "time")]
+pub use self::debounce::Debounced;
 
 /// A subscriber for updates of an `Observable`.
 #[must_use]
@@ -120,12 +132,99 @@ impl<T> Subscriber<T> {
         ObservableReadGuard::new(self.state.lock())
     }
 
-    fn poll_next_ref(&mut self, cx: &Context<'_>) -> Poll<Option<ObservableReadGuard<'_, T>>> {
+    /// Get the sequence number of the update that set the current inner value.
+    ///
+    /// To get the sequence number that belongs to a particular value, use
+    /// [`ObservableReadGuard::update_seq`] on a read guard of it instead, since
+    /// the value may be updated in between two calls otherwise.
+    #[cfg(feature = "update-seq")]
+    pub fn update_seq(&self) -> crate::UpdateSeq {
+        self.state.lock().update_seq()
+    }
+
+    /// Get an [`Epoch`] identifying the current state of the inner value.
+    ///
+    /// Unlike [`get`][Self::get], this doesn't mark the current value as
+    /// observed.
+    #[must_use]
+    pub fn epoch(&self) -> Epoch {
+        Epoch::new(self.state.lock().version())
+    }
+
+    /// Check whether the inner value was updated since `epoch` was obtained.
+    ///
+    /// This is cheaper than polling the subscriber, since it neither clones
+    /// the value nor registers for wakeups, and it doesn't mark anything as
+    /// observed. After the `Observable` (and all clones for
+    /// `SharedObservable`) is dropped, this returns `true` for any epoch
+    /// obtained before that.
+    ///
+    /// ```
+    /// use eyeball::Observable;
+    ///
+    /// let mut ob = Observable::new(1);
+    /// let sub = Observable::subscribe(&ob);
+    ///
+    /// let epoch = Observable::epoch(&ob);
+    /// assert!(!sub.changed_since(epoch));
+    ///
+    /// Observable::set(&mut ob, 2);
+    /// assert!(sub.changed_since(epoch));
+    /// assert!(!sub.changed_since(sub.epoch()));
+    /// ```
+    #[must_use]
+    pub fn changed_since(&self, epoch: Epoch) -> bool {
+        self.state.lock().version() != epoch.version()
+    }
+
+    /// Turn this subscriber into one that only yields values matching the
+    /// given predicate.
+    ///
+    /// The predicate is evaluated by the observable itself when its value is
+    /// updated, so the task waiting on the returned subscriber is not even
+    /// woken up for values that don't match. This makes it cheap to wait for
+    /// rare states, e.g. `subscriber.with_filter(|state| state.is_ready())`.
+    ///
+    /// Values that don't match are still marked as observed, i.e. the returned
+    /// subscriber never yields a value it skipped.
+    ///
+    /// Since the predicate runs while the observable is locked for writing, it
+    /// should be cheap and must not access the observable itself, e.g. by
+    /// calling [`SharedObservable::get`][crate::SharedObservable::get] on a
+    /// clone of it; doing so deadlocks.
+    pub fn with_filter(self, filter: impl Fn(&T) -> bool + Send + Sync + 'static) -> Filtered<T> {
+        Filtered { inner: self, filter: Arc::new(filter) }
+    }
+
+    /// Turn this subscriber into one that yields the result of `f` applied to
+    /// the inner value, instead of a clone of the inner value.
+    ///
+    /// `f` is called with a reference to the inner value while it is locked for
+    /// reading, so this allows observing a small part of a large value without
+    /// cloning all of it, e.g. `subscriber.map(|settings| settings.theme)`.
+    pub fn map<U, F>(self, f: F) -> Mapped<T, F>
+    where
+        F: Fn(&T) -> U,
+    {
+        Mapped { inner: self, f }
+    }
+
+    pub(crate) fn poll_next_ref(
+        &mut self,
+        cx: &Context<'_>,
+    ) -> Poll<Option<ObservableReadGuard<'_, T>>> {
         let state = self.state.lock();
         state
             .poll_update(&mut self.observed_version, cx)
             .map(|ready| ready.map(|_| ObservableReadGuard::new(state)))
     }
+
+    pub(crate) fn poll_next_owned(&mut self, cx: &Context<'_>) -> Poll<Option<T>>
+    where
+        T: Clone,
+    {
+        self.poll_next_ref(cx).map(opt_guard_to_owned)
+    }
 }
 
 impl<T, L: Lock> Subscriber<T, L> {
@@ -190,11 +289,154 @@ where
 impl<T: Clone> Stream for Subscriber<T> {
     type Item = T;
 
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.poll_next_owned(cx)
+    }
+}
+
+#[cfg(feature = "nightly")]
+impl<T: Clone> AsyncIterator for Subscriber<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.poll_next_owned(cx)
+    }
+}
+
+/// A subscriber that only yields values matching a predicate.
+///
+/// Created with [`Subscriber::with_filter`].
+#[must_use]
+pub struct Filtered<T> {
+    inner: Subscriber<T>,
+    filter: SubscriberFilter<T>,
+}
+
+impl<T> Filtered<T> {
+    /// Wait for an update that matches the filter and get a clone of the
+    /// updated value.
+    ///
+    /// Awaiting returns `Some(_)` after a matching update happened, or `None`
+    /// after the `Observable` (and all clones for `shared::Observable`) is
+    /// dropped.
+    pub async fn next(&mut self) -> Option<T>
+    where
+        T: Clone,
+    {
+        poll_fn(|cx| self.poll_next_ref(cx).map(opt_guard_to_owned)).await
+    }
+
+    /// Get a clone of the inner value without waiting for an update, whether
+    /// it matches the filter or not.
+    ///
+    /// See [`Subscriber::get`].
+    #[must_use]
+    pub fn get(&self) -> T
+    where
+        T: Clone,
+    {
+        self.inner.get()
+    }
+
+    /// Get back the unfiltered subscriber.
+    pub fn into_inner(self) -> Subscriber<T> {
+        self.inner
+    }
+
+    fn poll_next_ref(&mut self, cx: &Context<'_>) -> Poll<Option<ObservableReadGuard<'_, T>>> {
+        let state = self.inner.state.lock();
+        state
+            .poll_update_filtered(&mut self.inner.observed_version, &self.filter, cx)
+            .map(|ready| ready.map(|_| ObservableReadGuard::new(state)))
+    }
+}
+
+impl<T> fmt::Debug for Filtered<T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Filtered").field("inner", &self.inner).finish_non_exhaustive()
+    }
+}
+
+impl<T: Clone> Stream for Filtered<T> {
+    type Item = T;
+
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         self.poll_next_ref(cx).map(opt_guard_to_owned)
     }
 }
 
+/// A subscriber that yields a projection of the inner value.
+///
+/// Created with [`Subscriber::map`].
+#[must_use]
+pub struct Mapped<T, F> {
+    inner: Subscriber<T>,
+    f: F,
+}
+
+impl<T, U, F> Mapped<T, F>
+where
+    F: Fn(&T) -> U,
+{
+    /// Wait for an update and get the projection of the updated value.
+    ///
+    /// Awaiting returns `Some(_)` after an update happened, or `None` after the
+    /// `Observable` (and all clones for `shared::Observable`) is dropped.
+    pub async fn next(&mut self) -> Option<U> {
+        poll_fn(|cx| self.poll_next_mapped(cx)).await
+    }
+
+    /// Get the projection of the inner value without waiting for an update.
+    ///
+    /// See [`Subscriber::get`].
+    #[must_use]
+    pub fn get(&self) -> U {
+        (self.f)(&self.inner.read())
+    }
+
+    /// Get the projection of the inner value without waiting for an update,
+    /// and mark it as observed.
+    ///
+    /// See [`Subscriber::next_now`].
+    #[must_use]
+    pub fn next_now(&mut self) -> U {
+        (self.f)(&self.inner.next_ref_now())
+    }
+
+    /// Get back the original subscriber.
+    pub fn into_inner(self) -> Subscriber<T> {
+        self.inner
+    }
+
+    fn poll_next_mapped(&mut self, cx: &Context<'_>) -> Poll<Option<U>> {
+        let f = &self.f;
+        self.inner.poll_next_ref(cx).map(|opt| opt.map(|guard| f(&guard)))
+    }
+}
+
+impl<T, F> fmt::Debug for Mapped<T, F>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Mapped").field("inner", &self.inner).finish_non_exhaustive()
+    }
+}
+
+impl<T, U, F> Stream for Mapped<T, F>
+where
+    F: Fn(&T) -> U + Unpin,
+{
+    type Item = U;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.poll_next_mapped(cx)
+    }
+}
+
 /// Future returned by [`Subscriber::next`].
 #[must_use]
 #[allow(missing_debug_implementations)]
@@ -212,7 +454,7 @@ impl<T: Clone> Future for Next<'_, T> {
     type Output = Option<T>;
 
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-        self.subscriber.poll_next_ref(cx).map(opt_guard_to_owned)
+        self.subscriber.poll_next_owned(cx)
     }
 }
 
@@ -1,3 +1,5 @@
+#[cfg(feature = "nightly")]
+use std::async_iter::AsyncIterator;
 use std::{
     fmt,
     future::{poll_fn, Future},
@@ -155,6 +157,15 @@ impl<T: Clone + Send + Sync + 'static> Stream for Subscriber<T, AsyncLock> {
     }
 }
 
+#[cfg(feature = "nightly")]
+impl<T: Clone + Send + Sync + 'static> AsyncIterator for Subscriber<T, AsyncLock> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.poll_next_nopin(cx)
+    }
+}
+
 impl<T: Clone + Send + Sync + 'static> Future for Next<'_, T, AsyncLock> {
     type Output = Option<T>;
 
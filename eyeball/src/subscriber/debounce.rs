@@ -0,0 +1,113 @@
+use std::{
+    fmt,
+    future::{poll_fn, Future},
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures_core::Stream;
+use tokio::time::{sleep, Instant, Sleep};
+
+use super::Subscriber;
+
+impl<T> Subscriber<T> {
+    /// Turn this subscriber into one that only yields a value once it has not
+    /// been updated for the given `duration`.
+    ///
+    /// Rapid successive updates are coalesced: every update restarts the
+    /// timer, and only the latest value is yielded once it expires. If the
+    /// `Observable` is dropped while an update is waiting for the timer, the
+    /// latest value is yielded right away.
+    ///
+    /// This uses tokio's timer, so the returned subscriber must be polled from
+    /// within a tokio runtime that has the time driver enabled.
+    pub fn debounce(self, duration: Duration) -> Debounced<T> {
+        Debounced { inner: self, duration, sleep: None, closed: false }
+    }
+}
+
+/// A subscriber that only yields values that have been stable for a while.
+///
+/// Created with [`Subscriber::debounce`].
+#[must_use]
+pub struct Debounced<T> {
+    inner: Subscriber<T>,
+    duration: Duration,
+    /// The timer for the latest update that hasn't been yielded yet, if any.
+    sleep: Option<Pin<Box<Sleep>>>,
+    /// Whether the `Observable` was dropped.
+    closed: bool,
+}
+
+impl<T: Clone> Debounced<T> {
+    /// Wait for an update that is followed by no further updates for the
+    /// configured duration, and get a clone of the updated value.
+    ///
+    /// Awaiting returns `Some(_)` after such an update, or `None` after the
+    /// `Observable` (and all clones for `shared::Observable`) is dropped.
+    pub async fn next(&mut self) -> Option<T> {
+        poll_fn(|cx| self.poll_next_debounced(cx)).await
+    }
+
+    /// Get a clone of the inner value without waiting for an update.
+    ///
+    /// See [`Subscriber::get`].
+    #[must_use]
+    pub fn get(&self) -> T {
+        self.inner.get()
+    }
+
+    /// Get back the original subscriber.
+    ///
+    /// An update that is still waiting for the timer counts as observed.
+    pub fn into_inner(self) -> Subscriber<T> {
+        self.inner
+    }
+
+    fn poll_next_debounced(&mut self, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        while !self.closed {
+            match self.inner.poll_next_ref(cx) {
+                Poll::Ready(Some(_)) => {
+                    let deadline = Instant::now() + self.duration;
+                    match &mut self.sleep {
+                        Some(sleep) => sleep.as_mut().reset(deadline),
+                        None => self.sleep = Some(Box::pin(sleep(self.duration))),
+                    }
+                }
+                Poll::Ready(None) => self.closed = true,
+                Poll::Pending => break,
+            }
+        }
+
+        let Some(sleep) = &mut self.sleep else {
+            return if self.closed { Poll::Ready(None) } else { Poll::Pending };
+        };
+
+        if self.closed || sleep.as_mut().poll(cx).is_ready() {
+            self.sleep = None;
+            Poll::Ready(Some(self.inner.get()))
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Debounced<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Debounced")
+            .field("inner", &self.inner)
+            .field("duration", &self.duration)
+            .field("pending", &self.sleep.is_some())
+            .field("closed", &self.closed)
+            .finish()
+    }
+}
+
+impl<T: Clone> Stream for Debounced<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.poll_next_debounced(cx)
+    }
+}
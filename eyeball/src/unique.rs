@@ -4,15 +4,21 @@
 //! Use this in situations where only a single location in the code should be
 //! able to update the inner value.
 
-use std::{fmt, hash::Hash, mem, ops, ptr};
+use std::{fmt, hash::Hash, mem, ops, ptr, sync::Arc};
 
-use readlock::Shared;
+use readlock::{Shared, SharedWriteGuard};
 #[cfg(feature = "async-lock")]
 use readlock_tokio::Shared as SharedAsync;
 
 #[cfg(feature = "async-lock")]
 use crate::AsyncLock;
-use crate::{lock::Lock, shared::SharedObservable, state::ObservableState, Subscriber, SyncLock};
+use crate::{
+    arc_slice, contention,
+    lock::Lock,
+    shared::SharedObservable,
+    state::{ObservableState, WakerGcStats},
+    DerivedObservable, Epoch, OverrideGuard, Subscriber, SyncLock,
+};
 
 /// A value whose changes will be broadcast to subscribers.
 ///
@@ -65,6 +71,21 @@ impl<T> Observable<T> {
         Subscriber::new(Shared::get_read_lock(&this.state), 0)
     }
 
+    /// Create a read-only observable whose value is computed from this one's
+    /// using `f`, and recomputed whenever it is updated.
+    ///
+    /// This is cheaper than spawning a task that updates another observable
+    /// from a subscriber. See [`DerivedObservable`] for details.
+    pub fn derive<U>(
+        this: &Self,
+        f: impl Fn(&T) -> U + Send + Sync + 'static,
+    ) -> DerivedObservable<U>
+    where
+        U: Send + Sync + 'static,
+    {
+        DerivedObservable::new(&this.state, f)
+    }
+
     /// Get a reference to the inner value.
     ///
     /// Usually, you don't need to call this function since `Observable<T>`
@@ -78,7 +99,7 @@ impl<T> Observable<T> {
     /// Set the inner value to the given `value`, notify subscribers and return
     /// the previous value.
     pub fn set(this: &mut Self, value: T) -> T {
-        Shared::lock(&mut this.state).set(value)
+        Self::lock(this).set(value)
     }
 
     /// Set the inner value to the given `value` if it doesn't compare equal to
@@ -90,7 +111,7 @@ impl<T> Observable<T> {
     where
         T: PartialEq,
     {
-        Shared::lock(&mut this.state).set_if_not_eq(value)
+        Self::lock(this).set_if_not_eq(value)
     }
 
     /// Set the inner value to the given `value` if it has a different hash than
@@ -102,7 +123,7 @@ impl<T> Observable<T> {
     where
         T: Hash,
     {
-        Shared::lock(&mut this.state).set_if_hash_not_eq(value)
+        Self::lock(this).set_if_hash_not_eq(value)
     }
 
     /// Set the inner value to a `Default` instance of its type, notify
@@ -123,7 +144,7 @@ impl<T> Observable<T> {
     /// [`update_if`][Self::update_if] if you want to conditionally mutate the
     /// inner value.
     pub fn update(this: &mut Self, f: impl FnOnce(&mut T)) {
-        Shared::lock(&mut this.state).update(f);
+        Self::lock(this).update(f);
     }
 
     /// Maybe update the inner value and notify subscribers if it changed.
@@ -131,7 +152,54 @@ impl<T> Observable<T> {
     /// The closure given to this function must return `true` if subscribers
     /// should be notified of a change to the inner value.
     pub fn update_if(this: &mut Self, f: impl FnOnce(&mut T) -> bool) {
-        Shared::lock(&mut this.state).update_if(f);
+        Self::lock(this).update_if(f);
+    }
+
+    /// Temporarily set the inner value to the given `value` and notify
+    /// subscribers.
+    ///
+    /// When the returned guard is dropped, the previous value is restored and
+    /// subscribers are notified again.
+    pub fn override_scope(this: &mut Self, value: T) -> OverrideGuard<'_, T> {
+        OverrideGuard::new(this, value)
+    }
+
+    fn lock(this: &mut Self) -> SharedWriteGuard<'_, ObservableState<T>> {
+        contention::measure(|| Shared::lock(&mut this.state))
+    }
+}
+
+impl Observable<Arc<str>> {
+    /// Set the inner value to `value` if it doesn't compare equal to the
+    /// existing value, and return whether it was set.
+    ///
+    /// Unlike [`set_if_not_eq`][Self::set_if_not_eq], this doesn't allocate a
+    /// new `Arc<str>` if the value is unchanged. If the value changed but has
+    /// the same length, and nothing else holds a clone of the inner `Arc`, the
+    /// existing allocation is reused.
+    pub fn set_str(this: &mut Self, value: &str) -> bool {
+        let mut changed = false;
+        Self::lock(this).update_if(|current| {
+            changed = arc_slice::replace_str(current, value);
+            changed
+        });
+        changed
+    }
+}
+
+impl Observable<Arc<[u8]>> {
+    /// Set the inner value to `value` if it doesn't compare equal to the
+    /// existing value, and return whether it was set.
+    ///
+    /// Like [`set_str`][Observable::set_str], this avoids allocating where
+    /// possible.
+    pub fn set_bytes(this: &mut Self, value: &[u8]) -> bool {
+        let mut changed = false;
+        Self::lock(this).update_if(|current| {
+            changed = arc_slice::replace_bytes(current, value);
+            changed
+        });
+        changed
     }
 }
 
@@ -254,6 +322,39 @@ impl<T, L: Lock> Observable<T, L> {
         L::shared_read_count(&this.state)
     }
 
+    /// Get an [`Epoch`] identifying the current state of the inner value.
+    ///
+    /// Pass it to [`Subscriber::changed_since`] later to find out whether the
+    /// inner value was updated in between.
+    #[must_use]
+    pub fn epoch(this: &Self) -> Epoch {
+        Epoch::new(this.state.version())
+    }
+
+    /// Set the number of wakers registered by subscribers at which redundant
+    /// ones are collected automatically, or disable automatic collection with
+    /// `None` (the default).
+    ///
+    /// A low threshold keeps memory usage down for observables whose
+    /// subscribers are polled a lot without updates happening, at the cost of
+    /// CPU time for the collection. See [`WakerGcStats`] for details.
+    pub fn set_waker_gc_threshold(this: &Self, threshold: Option<usize>) {
+        this.state.set_waker_gc_threshold(threshold);
+    }
+
+    /// Collect redundant wakers registered by subscribers right away.
+    ///
+    /// Returns the number of wakers that were removed.
+    pub fn gc_wakers_now(this: &Self) -> usize {
+        this.state.gc_wakers_now()
+    }
+
+    /// Get statistics about the wakers registered by subscribers.
+    #[must_use]
+    pub fn waker_gc_stats(this: &Self) -> WakerGcStats {
+        this.state.waker_gc_stats()
+    }
+
     /// Convert this unique `Observable` into a [`SharedObservable`].
     ///
     /// Any subscribers created for `self` remain valid.
@@ -0,0 +1,43 @@
+//! Process-wide sequence numbers of updates.
+
+use std::{
+    fmt,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+static NEXT: AtomicU64 = AtomicU64::new(1);
+
+/// A process-wide sequence number of an update.
+///
+/// Every update of an observable is stamped with the next sequence number,
+/// which is shared by all observables in the process, including the ones of
+/// `eyeball-im` if its `update-seq` feature is enabled. Comparing the sequence
+/// numbers of updates seen by different subscribers thus tells in which order
+/// the updates happened, which helps with debugging interleavings of updates
+/// across multiple observables, e.g. by including them in logs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UpdateSeq(u64);
+
+impl UpdateSeq {
+    /// Take the next sequence number.
+    ///
+    /// This is what observables use to stamp their updates. It can also be
+    /// used to stamp other events, such that they can be ordered relative to
+    /// the updates of observables.
+    #[must_use]
+    pub fn next() -> Self {
+        Self(NEXT.fetch_add(1, Ordering::Relaxed))
+    }
+
+    /// Get the sequence number as an integer.
+    #[must_use]
+    pub fn get(self) -> u64 {
+        self.0
+    }
+}
+
+impl fmt::Display for UpdateSeq {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
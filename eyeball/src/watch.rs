@@ -0,0 +1,58 @@
+//! Conversions between [`Observable`] and [`tokio::sync::watch`] channels.
+
+use tokio::sync::watch;
+
+use crate::{Observable, Subscriber};
+
+impl<T> Observable<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    /// Create a subscriber that follows the value of a tokio `watch` channel.
+    ///
+    /// The value currently in the channel is used as the initial value of the
+    /// subscriber. A background task on the current tokio runtime forwards
+    /// every value sent on the channel afterwards. It shuts down once all
+    /// senders of the channel have been dropped, or when a value is sent after
+    /// the returned subscriber and all of its clones are gone. Subscribers are
+    /// closed when that happens.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a tokio runtime.
+    pub fn from_watch(mut rx: watch::Receiver<T>) -> Subscriber<T> {
+        let observable = Self::new(rx.borrow_and_update().clone());
+        let subscriber = Self::subscribe(&observable);
+        tokio::spawn(forward(rx, observable));
+        subscriber
+    }
+
+    /// Convert this `Observable` into the sending half of a tokio `watch`
+    /// channel.
+    ///
+    /// Values sent through the returned sender are forwarded to all existing
+    /// subscribers of the observable by a background task on the current tokio
+    /// runtime, so it becomes the only way of updating the value.
+    /// [`Sender::subscribe`][watch::Sender::subscribe] can be used to create
+    /// `watch` receivers for consumers that expect them.
+    ///
+    /// The background task shuts down once the sender has been dropped, or when
+    /// a value is sent after all subscribers of the observable are gone.
+    /// Subscribers are closed when that happens.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a tokio runtime.
+    pub fn into_watch(this: Self) -> watch::Sender<T> {
+        let (tx, rx) = watch::channel(Self::get(&this).clone());
+        tokio::spawn(forward(rx, this));
+        tx
+    }
+}
+
+async fn forward<T: Clone>(mut rx: watch::Receiver<T>, mut observable: Observable<T>) {
+    while Observable::subscriber_count(&observable) > 0 && rx.changed().await.is_ok() {
+        let value = rx.borrow_and_update().clone();
+        Observable::set(&mut observable, value);
+    }
+}
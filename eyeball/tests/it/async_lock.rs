@@ -1,4 +1,4 @@
-use eyeball::Observable;
+use eyeball::{Observable, SharedObservable};
 use futures_util::FutureExt;
 use stream_assert::{assert_next_eq, assert_pending};
 
@@ -30,3 +30,34 @@ async fn smoke_test() {
     assert_next_eq!(rx1, "B");
     assert_next_eq!(rx2, "B");
 }
+
+#[tokio::test]
+async fn update_async() {
+    let ob = SharedObservable::new_async(vec![1]);
+    let mut rx = ob.subscribe().await;
+
+    let res: Result<(), &str> = ob
+        .update_async(|v| {
+            Box::pin(async move {
+                v.push(2);
+                tokio::task::yield_now().await;
+                Ok(())
+            })
+        })
+        .await;
+    assert_eq!(res, Ok(()));
+    assert_next_eq!(rx, vec![1, 2]);
+
+    let res = ob
+        .update_async(|v| {
+            Box::pin(async move {
+                v.clear();
+                tokio::task::yield_now().await;
+                Err("failed")
+            })
+        })
+        .await;
+    assert_eq!(res, Err("failed"));
+    assert_pending!(rx);
+    assert_eq!(ob.get().await, vec![1, 2]);
+}
@@ -0,0 +1,58 @@
+use eyeball::{combine, Observable, SharedObservable};
+use futures_util::FutureExt;
+use stream_assert::{assert_closed, assert_next_eq, assert_pending};
+
+#[test]
+fn latest2() {
+    let mut a = Observable::new(1);
+    let mut b = Observable::new("x");
+    let mut sub = combine::latest2(Observable::subscribe(&a), Observable::subscribe(&b));
+    assert_eq!(sub.get(), (1, "x"));
+    assert_pending!(sub);
+
+    Observable::set(&mut a, 2);
+    assert_next_eq!(sub, (2, "x"));
+    assert_pending!(sub);
+
+    Observable::set(&mut b, "y");
+    Observable::set(&mut a, 3);
+    assert_next_eq!(sub, (3, "y"));
+    assert_pending!(sub);
+
+    // The last value of a dropped observable is still used
+    drop(a);
+    Observable::set(&mut b, "z");
+    assert_next_eq!(sub, (3, "z"));
+
+    drop(b);
+    assert_closed!(sub);
+}
+
+#[test]
+fn latest4() {
+    let a = SharedObservable::new(1);
+    let b = SharedObservable::new(2_u8);
+    let c = SharedObservable::new('c');
+    let d = SharedObservable::new(true);
+    let mut sub = combine::latest4(a.subscribe(), b.subscribe(), c.subscribe(), d.subscribe());
+
+    d.set(false);
+    assert_eq!(sub.next().now_or_never(), Some(Some((1, 2, 'c', false))));
+    assert_pending!(sub);
+}
+
+#[test]
+fn latest_array() {
+    let observables = [SharedObservable::new(0), SharedObservable::new(0)];
+    let mut sub = combine::latest_array([observables[0].subscribe(), observables[1].subscribe()]);
+    assert_eq!(sub.get(), [0, 0]);
+
+    observables[1].set(5);
+    assert_next_eq!(sub, [0, 5]);
+    observables[0].set(3);
+    assert_next_eq!(sub, [3, 5]);
+    assert_pending!(sub);
+
+    drop(observables);
+    assert_closed!(sub);
+}
@@ -0,0 +1,32 @@
+use std::{sync::mpsc, thread, time::Duration};
+
+use eyeball::{lock_wait_stats, set_lock_wait_threshold, SharedObservable};
+
+#[test]
+fn slow_lock_wait() {
+    let ob = SharedObservable::new(0);
+    let subscriber = ob.subscribe();
+
+    set_lock_wait_threshold(Some(Duration::from_millis(10)));
+    let before = lock_wait_stats();
+
+    let (tx, rx) = mpsc::channel();
+    let reader = thread::spawn(move || {
+        let guard = subscriber.read();
+        tx.send(()).unwrap();
+        thread::sleep(Duration::from_millis(50));
+        drop(guard);
+    });
+
+    // Blocked until the reader releases its guard
+    rx.recv().unwrap();
+    ob.set(1);
+    reader.join().unwrap();
+
+    let after = lock_wait_stats();
+    set_lock_wait_threshold(None);
+
+    assert!(after.measured > before.measured);
+    assert!(after.slow > before.slow);
+    assert!(after.max_wait >= Duration::from_millis(40));
+}
@@ -0,0 +1,43 @@
+use std::time::Duration;
+
+use eyeball::SharedObservable;
+use futures_util::FutureExt;
+use tokio::time::advance;
+
+#[tokio::test(start_paused = true)]
+async fn debounce() {
+    let ob = SharedObservable::new(0);
+    let mut subscriber = ob.subscribe().debounce(Duration::from_millis(100));
+    assert!(subscriber.next().now_or_never().is_none());
+
+    ob.set(1);
+    assert!(subscriber.next().now_or_never().is_none());
+    advance(Duration::from_millis(60)).await;
+
+    // Restarts the timer
+    ob.set(2);
+    assert!(subscriber.next().now_or_never().is_none());
+    advance(Duration::from_millis(60)).await;
+    assert!(subscriber.next().now_or_never().is_none());
+
+    advance(Duration::from_millis(40)).await;
+    assert_eq!(subscriber.next().now_or_never(), Some(Some(2)));
+    assert!(subscriber.next().now_or_never().is_none());
+
+    ob.set(3);
+    assert_eq!(subscriber.next().await, Some(3));
+}
+
+#[tokio::test(start_paused = true)]
+async fn debounce_closed() {
+    let ob = SharedObservable::new(0);
+    let mut subscriber = ob.subscribe().debounce(Duration::from_secs(1));
+
+    ob.set(1);
+    assert!(subscriber.next().now_or_never().is_none());
+
+    // The pending update is yielded right away once the observable is gone
+    drop(ob);
+    assert_eq!(subscriber.next().now_or_never(), Some(Some(1)));
+    assert_eq!(subscriber.next().now_or_never(), Some(None));
+}
@@ -0,0 +1,43 @@
+use eyeball::{Observable, SharedObservable};
+use futures_util::FutureExt;
+use macro_rules_attribute::apply;
+
+#[apply(test!)]
+async fn derive_unique() {
+    let mut ob = Observable::new(2);
+    let doubled = Observable::derive(&ob, |v| v * 2);
+    let mut sub = doubled.subscribe();
+    assert_eq!(doubled.get(), 4);
+
+    Observable::set(&mut ob, 5);
+    assert_eq!(doubled.get(), 10);
+    assert_eq!(sub.next().await, Some(10));
+    assert_eq!(sub.next().now_or_never(), None);
+
+    // The derived value is kept once the source is gone.
+    drop(ob);
+    assert_eq!(doubled.get(), 10);
+
+    drop(doubled);
+    assert_eq!(sub.next().await, None);
+}
+
+#[apply(test!)]
+async fn derive_chain() {
+    let ob = SharedObservable::new("hello".to_owned());
+    let len = ob.derive(|s| s.len());
+    let is_long = len.derive(|&len| len > 5);
+    let mut sub = is_long.subscribe();
+    assert!(!is_long.get());
+
+    ob.set("hello, world".to_owned());
+    assert_eq!(len.get(), 12);
+    assert_eq!(sub.next().await, Some(true));
+
+    // Dropping a derived observable unregisters it from its source on the
+    // next update.
+    drop(is_long);
+    assert_eq!(sub.next().await, None);
+    ob.set("hi".to_owned());
+    assert_eq!(len.get(), 2);
+}
@@ -0,0 +1,50 @@
+use std::{fs, thread, time::Duration};
+
+use eyeball::{FileObservable, ReloadError};
+
+fn wait_until(mut cond: impl FnMut() -> bool) {
+    for _ in 0..200 {
+        if cond() {
+            return;
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+    panic!("condition not met in time");
+}
+
+#[test]
+fn reload() {
+    let dir = std::env::temp_dir().join(format!("eyeball-file-test-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("config.txt");
+    fs::write(&path, "1").unwrap();
+
+    let ob = FileObservable::new(&path, |s| s.trim().parse::<u32>()).unwrap();
+    let sub = ob.subscribe();
+    let error_sub = ob.subscribe_error();
+    assert_eq!(ob.get(), 1);
+    assert!(ob.error().is_none());
+
+    fs::write(&path, "2").unwrap();
+    wait_until(|| ob.get() == 2);
+    assert_eq!(sub.get(), 2);
+
+    fs::write(&path, "not a number").unwrap();
+    wait_until(|| ob.error().is_some());
+    assert!(matches!(*error_sub.get().unwrap(), ReloadError::Parse(_)));
+    assert_eq!(ob.get(), 2);
+
+    fs::write(&path, "3").unwrap();
+    wait_until(|| ob.get() == 3);
+    wait_until(|| error_sub.get().is_none());
+
+    drop(ob);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn initial_load_error() {
+    let path = std::env::temp_dir().join("eyeball-file-test-does-not-exist");
+    let err = FileObservable::new(path, |s| s.parse::<u32>()).unwrap_err();
+    assert!(matches!(err, ReloadError::Io(_)));
+}
@@ -1,4 +1,5 @@
 #![allow(missing_docs)]
+#![cfg_attr(feature = "nightly", feature(async_iterator))]
 
 macro_rules! test {
     (
@@ -17,5 +18,22 @@ macro_rules! test {
 
 #[cfg(feature = "async-lock")]
 mod async_lock;
+mod combine;
+mod contention;
+#[cfg(feature = "time")]
+mod debounce;
+mod derived;
+// Miri has no filesystem access by default and no inotify support.
+#[cfg(all(feature = "notify", not(miri)))]
+mod file;
+#[cfg(feature = "nightly")]
+mod nightly;
+mod send_sync;
 mod shared;
+#[cfg(feature = "sink")]
+mod sink;
 mod unique;
+#[cfg(feature = "update-seq")]
+mod update_seq;
+#[cfg(feature = "watch")]
+mod watch;
@@ -0,0 +1,23 @@
+use std::{
+    async_iter::AsyncIterator,
+    pin::Pin,
+    task::{Context, Poll, Waker},
+};
+
+use eyeball::Observable;
+
+#[test]
+fn async_iterator() {
+    let mut ob = Observable::new(0);
+    let mut sub = Observable::subscribe(&ob);
+    let mut cx = Context::from_waker(Waker::noop());
+
+    assert_eq!(Pin::new(&mut sub).poll_next(&mut cx), Poll::Pending);
+
+    Observable::set(&mut ob, 1);
+    Observable::set(&mut ob, 2);
+    assert_eq!(Pin::new(&mut sub).poll_next(&mut cx), Poll::Ready(Some(2)));
+
+    drop(ob);
+    assert_eq!(Pin::new(&mut sub).poll_next(&mut cx), Poll::Ready(None));
+}
@@ -0,0 +1,28 @@
+//! Compile-time checks that the public types can be shared across threads
+//! whenever the values they hold can.
+
+use eyeball::{Observable, SharedObservable, Subscriber, WeakObservable};
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn observables() {
+    assert_send_sync::<Observable<String>>();
+    assert_send_sync::<SharedObservable<String>>();
+    assert_send_sync::<WeakObservable<String>>();
+}
+
+#[test]
+fn subscribers() {
+    assert_send_sync::<Subscriber<String>>();
+    assert_send_sync::<Subscriber<Vec<u8>>>();
+}
+
+#[cfg(feature = "async-lock")]
+#[test]
+fn async_lock() {
+    use eyeball::AsyncLock;
+
+    assert_send_sync::<SharedObservable<String, AsyncLock>>();
+    assert_send_sync::<Subscriber<String, AsyncLock>>();
+}
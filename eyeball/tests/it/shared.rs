@@ -1,5 +1,7 @@
+use std::sync::Arc;
+
 use eyeball::SharedObservable;
-use futures_util::future::join;
+use futures_util::{future::join, FutureExt};
 use macro_rules_attribute::apply;
 
 #[apply(test!)]
@@ -56,3 +58,165 @@ async fn lag_no_clone() {
     assert_eq!(rx1.next_ref().await.as_ref().map(|f| f.0.as_str()), Some("B"));
     assert_eq!(rx2.next_ref().await.as_ref().map(|f| f.0.as_str()), Some("B"));
 }
+
+#[apply(test!)]
+async fn update_in_place_with_notify_scope() {
+    let ob = SharedObservable::new(vec![1, 2, 3]);
+    let mut subscriber = ob.subscribe();
+
+    // Reading doesn't notify.
+    let sum = ob.update_in_place_with_notify_scope(|scope| scope.iter().sum::<i32>());
+    assert_eq!(sum, 6);
+    assert!(subscriber.next().now_or_never().is_none());
+
+    // Multiple changes notify once.
+    ob.update_in_place_with_notify_scope(|scope| {
+        scope.get_mut().push(4);
+        scope.get_mut()[0] = 0;
+        assert!(scope.is_dirty());
+    });
+    assert_eq!(subscriber.next().await, Some(vec![0, 2, 3, 4]));
+    assert!(subscriber.next().now_or_never().is_none());
+
+    // Untracked changes don't notify unless marked dirty.
+    ob.update_in_place_with_notify_scope(|scope| scope.get_mut_untracked().clear());
+    assert!(subscriber.next().now_or_never().is_none());
+    ob.update_in_place_with_notify_scope(|scope| scope.mark_dirty());
+    assert_eq!(subscriber.next().await, Some(vec![]));
+}
+
+#[apply(test!)]
+async fn with_filter() {
+    let ob = SharedObservable::new(0);
+    let mut subscriber = ob.subscribe().with_filter(|v| v % 2 == 0);
+
+    ob.set(1);
+    assert!(subscriber.next().now_or_never().is_none());
+
+    ob.set(2);
+    ob.set(3);
+    // The last value doesn't match, and the matching value before was
+    // replaced before the subscriber could observe it.
+    assert!(subscriber.next().now_or_never().is_none());
+
+    ob.set(4);
+    assert_eq!(subscriber.next().await, Some(4));
+    assert!(subscriber.next().now_or_never().is_none());
+
+    drop(ob);
+    assert_eq!(subscriber.next().await, None);
+}
+
+#[test]
+fn with_filter_wakers() {
+    let ob = SharedObservable::new(0);
+    let mut subscriber = ob.subscribe().with_filter(|v| v % 2 == 0);
+
+    // Polling again from the same task doesn't register more wakers.
+    for i in 0..10 {
+        ob.set(i * 2 + 1);
+        assert!(subscriber.next().now_or_never().is_none());
+        assert_eq!(ob.waker_gc_stats().registered_wakers, 1);
+    }
+
+    // The waker of a dropped subscriber is removed on the next update.
+    drop(subscriber);
+    ob.set(1);
+    assert_eq!(ob.waker_gc_stats().registered_wakers, 0);
+}
+
+#[apply(test!)]
+async fn map() {
+    let ob = SharedObservable::new(("a".to_owned(), 1));
+    let mut subscriber = ob.subscribe().map(|(_, n)| *n);
+    assert_eq!(subscriber.get(), 1);
+    assert!(subscriber.next().now_or_never().is_none());
+
+    ob.update(|(_, n)| *n = 2);
+    assert_eq!(subscriber.next().await, Some(2));
+    assert!(subscriber.next().now_or_never().is_none());
+
+    ob.set(("b".to_owned(), 3));
+    assert_eq!(subscriber.next_now(), 3);
+    assert!(subscriber.next().now_or_never().is_none());
+
+    drop(ob);
+    assert_eq!(subscriber.next().await, None);
+}
+
+#[test]
+fn waker_gc_threshold() {
+    let ob = SharedObservable::new(0);
+    ob.set_waker_gc_threshold(Some(4));
+    let mut sub = ob.subscribe();
+
+    for _ in 0..3 {
+        assert_eq!(sub.next().now_or_never(), None);
+    }
+    assert_eq!(ob.waker_gc_stats().runs, 0);
+
+    // Registering the fourth waker triggers a collection
+    assert_eq!(sub.next().now_or_never(), None);
+    let stats = ob.waker_gc_stats();
+    assert_eq!(stats.registered_wakers, 1);
+    assert_eq!(stats.runs, 1);
+    assert_eq!(stats.collected, 3);
+
+    ob.set(1);
+    assert_eq!(sub.next().now_or_never(), Some(Some(1)));
+}
+
+#[test]
+fn override_scope() {
+    let ob = SharedObservable::new(1);
+    let mut sub = ob.subscribe();
+
+    let outer = ob.override_scope(2);
+    assert_eq!(sub.next().now_or_never(), Some(Some(2)));
+    let inner = ob.override_scope(3);
+    assert_eq!(*inner.previous(), 2);
+    assert_eq!(sub.next().now_or_never(), Some(Some(3)));
+
+    drop(inner);
+    assert_eq!(sub.next().now_or_never(), Some(Some(2)));
+    drop(outer);
+    assert_eq!(sub.next().now_or_never(), Some(Some(1)));
+
+    // The guard keeps the observable alive
+    let guard = ob.override_scope(4);
+    drop(ob);
+    assert_eq!(sub.next().now_or_never(), Some(Some(4)));
+    drop(guard);
+    assert_eq!(sub.get(), 1);
+    assert_eq!(sub.next().now_or_never(), Some(None));
+}
+
+#[test]
+fn set_bytes() {
+    let ob = SharedObservable::new(Arc::<[u8]>::from(&b"abc"[..]));
+    let mut sub = ob.subscribe();
+
+    assert!(!ob.set_bytes(b"abc"));
+    assert_eq!(sub.next().now_or_never(), None);
+
+    assert!(ob.set_bytes(b"xyz"));
+    assert_eq!(sub.next().now_or_never().flatten().as_deref(), Some(&b"xyz"[..]));
+
+    assert!(ob.set_bytes(b""));
+    assert_eq!(&*ob.get(), b"");
+}
+
+#[test]
+fn epoch() {
+    let ob = SharedObservable::new("a");
+    let sub = ob.subscribe();
+    let epoch = ob.epoch();
+
+    // Updates that don't notify subscribers don't change the epoch.
+    ob.update_if(|_| false);
+    assert!(!sub.changed_since(epoch));
+
+    ob.set("b");
+    assert!(sub.changed_since(epoch));
+    assert_eq!(sub.epoch(), ob.epoch());
+}
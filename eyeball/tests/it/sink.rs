@@ -0,0 +1,54 @@
+use eyeball::{ObservableClosed, SharedObservable};
+use futures_util::{stream, FutureExt, SinkExt, StreamExt};
+use macro_rules_attribute::apply;
+
+#[apply(test!)]
+async fn forward() {
+    let ob = SharedObservable::new(0);
+    let mut sub = ob.subscribe();
+
+    stream::iter([1, 2, 3]).map(Ok).forward(ob).await.unwrap();
+
+    assert_eq!(sub.get(), 3);
+    assert_eq!(sub.next().await, None);
+}
+
+#[apply(test!)]
+async fn close_with_other_clones() {
+    let mut ob = SharedObservable::new(0);
+    let ob2 = ob.clone();
+    let mut sub = ob.subscribe();
+
+    ob.send(1).await.unwrap();
+    ob.close().await.unwrap();
+    assert_eq!(sub.next().await, Some(1));
+    assert_eq!(sub.next().now_or_never(), None);
+
+    // The other clone keeps the observable open.
+    ob.send(2).await.unwrap();
+    assert_eq!(sub.next().await, Some(2));
+
+    drop(ob2);
+    ob.close().await.unwrap();
+    assert_eq!(ob.send(3).await, Err(ObservableClosed));
+    assert_eq!(sub.next().await, None);
+}
+
+#[apply(test!)]
+async fn set_after_close() {
+    let mut ob = SharedObservable::new(0);
+    let weak = SharedObservable::downgrade(&ob);
+    let mut sub = ob.subscribe();
+
+    ob.close().await.unwrap();
+    assert_eq!(sub.next().await, None);
+
+    // Updates through the handle don't reopen the observable.
+    ob.set(5);
+    assert_eq!(ob.subscribe().next().await, None);
+    assert_eq!(ob.send(7).await, Err(ObservableClosed));
+
+    let ob2 = weak.upgrade().unwrap();
+    ob2.set(8);
+    assert_eq!(ob2.subscribe().next().await, None);
+}
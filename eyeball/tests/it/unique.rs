@@ -1,5 +1,7 @@
+use std::sync::Arc;
+
 use eyeball::Observable;
-use futures_util::future::join;
+use futures_util::{future::join, FutureExt};
 use macro_rules_attribute::apply;
 
 #[apply(test!)]
@@ -56,3 +58,87 @@ async fn lag_no_clone() {
     assert_eq!(rx1.next_ref().await.as_ref().map(|f| f.0.as_str()), Some("B"));
     assert_eq!(rx2.next_ref().await.as_ref().map(|f| f.0.as_str()), Some("B"));
 }
+
+#[test]
+fn gc_wakers_now() {
+    let mut ob = Observable::new(0);
+    let mut sub = Observable::subscribe(&ob);
+
+    // Every poll without an update registers the (same no-op) waker again.
+    for _ in 0..5 {
+        assert_eq!(sub.next().now_or_never(), None);
+    }
+    assert_eq!(Observable::waker_gc_stats(&ob).registered_wakers, 5);
+
+    assert_eq!(Observable::gc_wakers_now(&ob), 4);
+    let stats = Observable::waker_gc_stats(&ob);
+    assert_eq!(stats.registered_wakers, 1);
+    assert_eq!(stats.runs, 1);
+    assert_eq!(stats.collected, 4);
+
+    Observable::set(&mut ob, 1);
+    assert_eq!(Observable::waker_gc_stats(&ob).registered_wakers, 0);
+    assert_eq!(sub.next().now_or_never(), Some(Some(1)));
+}
+
+#[test]
+fn override_scope() {
+    let mut ob = Observable::new("idle");
+    let mut sub = Observable::subscribe(&ob);
+
+    {
+        let guard = Observable::override_scope(&mut ob, "muted");
+        assert_eq!(*guard, "muted");
+        assert_eq!(*guard.previous(), "idle");
+        assert_eq!(sub.next().now_or_never(), Some(Some("muted")));
+    }
+
+    assert_eq!(*ob, "idle");
+    assert_eq!(sub.next().now_or_never(), Some(Some("idle")));
+}
+
+#[test]
+fn set_str() {
+    let mut ob = Observable::new(Arc::<str>::from("abc"));
+    let mut sub = Observable::subscribe(&ob);
+    let ptr = Arc::as_ptr(&ob);
+
+    assert!(!Observable::set_str(&mut ob, "abc"));
+    assert_eq!(sub.next().now_or_never(), None);
+
+    // Same length and no other references: the allocation is reused.
+    assert!(Observable::set_str(&mut ob, "xyz"));
+    assert_eq!(sub.next().now_or_never(), Some(Some("xyz".into())));
+    assert_eq!(Arc::as_ptr(&ob), ptr);
+
+    // The subscriber holds a clone now, so a new allocation is needed.
+    let held = sub.get();
+    assert!(Observable::set_str(&mut ob, "uvw"));
+    assert_eq!(&*held, "xyz");
+    assert_eq!(&**ob, "uvw");
+
+    assert!(Observable::set_str(&mut ob, "longer"));
+    assert_eq!(sub.next().now_or_never(), Some(Some("longer".into())));
+}
+
+#[test]
+fn epoch() {
+    let mut ob = Observable::new(0);
+    let sub = Observable::subscribe(&ob);
+    let epoch = Observable::epoch(&ob);
+    assert_eq!(sub.epoch(), epoch);
+    assert!(!sub.changed_since(epoch));
+
+    Observable::set(&mut ob, 1);
+    Observable::set(&mut ob, 2);
+    assert!(sub.changed_since(epoch));
+    assert_ne!(Observable::epoch(&ob), epoch);
+
+    // Checking doesn't mark anything as observed.
+    let epoch = sub.epoch();
+    assert!(!sub.changed_since(epoch));
+    assert_eq!(sub.clone().next().now_or_never(), Some(Some(2)));
+
+    drop(ob);
+    assert!(sub.changed_since(epoch));
+}
@@ -0,0 +1,35 @@
+use eyeball::{Observable, ObservableReadGuard, SharedObservable, UpdateSeq};
+
+#[test]
+fn ordered_across_observables() {
+    let mut a = Observable::new(0);
+    let b = SharedObservable::new(0);
+    let sub_a = Observable::subscribe(&a);
+    let sub_b = b.subscribe();
+
+    Observable::set(&mut a, 1);
+    b.set(1);
+    assert!(sub_a.update_seq() < sub_b.update_seq());
+
+    Observable::set(&mut a, 2);
+    assert!(sub_a.update_seq() > sub_b.update_seq());
+
+    let other = UpdateSeq::next();
+    assert!(other > sub_a.update_seq());
+}
+
+#[test]
+fn read_guard() {
+    let ob = SharedObservable::new("a");
+    let sub = ob.subscribe();
+    let seq = sub.update_seq();
+
+    // Not an update, so the sequence number is unchanged.
+    assert_eq!(ob.set_if_not_eq("a"), None);
+    assert_eq!(ObservableReadGuard::update_seq(&sub.read()), seq);
+
+    ob.set("b");
+    let guard = sub.read();
+    assert_eq!(*guard, "b");
+    assert!(ObservableReadGuard::update_seq(&guard) > seq);
+}
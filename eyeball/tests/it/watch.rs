@@ -0,0 +1,38 @@
+use eyeball::Observable;
+use tokio::sync::watch;
+
+#[tokio::test]
+async fn from_watch() {
+    let (tx, rx) = watch::channel(1);
+    let mut subscriber = Observable::from_watch(rx);
+    assert_eq!(subscriber.get(), 1);
+
+    tx.send(2).unwrap();
+    assert_eq!(subscriber.next().await, Some(2));
+
+    tx.send(3).unwrap();
+    tx.send(4).unwrap();
+    assert_eq!(subscriber.next().await, Some(4));
+
+    drop(tx);
+    assert_eq!(subscriber.next().await, None);
+    assert_eq!(subscriber.get(), 4);
+}
+
+#[tokio::test]
+async fn into_watch() {
+    let ob = Observable::new("a");
+    let mut subscriber = Observable::subscribe(&ob);
+
+    let tx = Observable::into_watch(ob);
+    let mut rx = tx.subscribe();
+    assert_eq!(*rx.borrow(), "a");
+
+    tx.send_replace("b");
+    assert_eq!(subscriber.next().await, Some("b"));
+    rx.changed().await.unwrap();
+    assert_eq!(*rx.borrow_and_update(), "b");
+
+    drop(tx);
+    assert_eq!(subscriber.next().await, None);
+}